@@ -14,7 +14,7 @@ use bitcoin::secp256k1::{PublicKey, SecretKey};
 use bitcoin::{
     consensus::Decodable, network::constants::Network, Amount, PrivateKey, Transaction, Txid,
 };
-use bitcoin::{Address, OutPoint, ScriptBuf, TxOut};
+use bitcoin::{Address, BlockHash, OutPoint, ScriptBuf, TxOut};
 use bitcoincore_rpc::jsonrpc::serde_json;
 use bitcoincore_rpc::jsonrpc::serde_json::Value;
 use bitcoincore_rpc::{json, Auth, Client, RpcApi};
@@ -30,6 +30,9 @@ use rust_bitcoin_coin_selection::select_coins;
 /// The minimum feerate we are allowed to send, as specify by LDK.
 const MIN_FEERATE: u32 = 253;
 
+/// The weight of a P2WPKH change output: value(8) + script_length(1) + script(22).
+const CHANGE_OUTPUT_WEIGHT: usize = 124;
+
 pub struct BitcoinCoreProvider {
     client: Arc<Mutex<Client>>,
     // Used to implement the FeeEstimator interface, heavily inspired by
@@ -150,12 +153,35 @@ fn query_fee_estimate(
     Ok(res)
 }
 
+/// A [`Utxo`] together with its value minus the fee required to spend it,
+/// which is used to select the coins funding a given amount.
 #[derive(Clone)]
-struct UtxoWrap(Utxo);
+struct UtxoWrap {
+    utxo: Utxo,
+    effective_value: u64,
+}
+
+impl UtxoWrap {
+    /// Returns `None` if spending the given UTXO at the given fee rate costs
+    /// more than its value.
+    fn new(utxo: Utxo, fee_rate: u64) -> Result<Option<Self>, ManagerError> {
+        let input_weight = dlc::util::get_input_weight(&utxo.redeem_script, utxo.max_witness_len)?;
+        let input_fee = dlc::util::weight_to_fee(input_weight, fee_rate)?;
+        Ok(utxo
+            .tx_out
+            .value
+            .checked_sub(input_fee)
+            .filter(|x| *x > 0)
+            .map(|effective_value| UtxoWrap {
+                utxo,
+                effective_value,
+            }))
+    }
+}
 
 impl rust_bitcoin_coin_selection::Utxo for UtxoWrap {
     fn get_value(&self) -> u64 {
-        self.0.tx_out.value
+        self.effective_value
     }
 }
 
@@ -283,18 +309,30 @@ impl Wallet for BitcoinCoreProvider {
     fn get_utxos_for_amount(
         &self,
         amount: u64,
-        _fee_rate: u64,
+        fee_rate: u64,
         lock_utxos: bool,
     ) -> Result<Vec<Utxo>, ManagerError> {
         let client = self.client.lock().unwrap();
         let utxo_res = client
             .list_unspent(None, None, None, Some(false), None)
             .map_err(rpc_err_to_manager_err)?;
-        let mut utxo_pool: Vec<UtxoWrap> = utxo_res
+        let utxos = utxo_res
             .iter()
             .filter(|x| x.spendable)
-            .map(|x| {
-                Ok(UtxoWrap(Utxo {
+            .filter_map(|x| {
+                let redeem_script = x
+                    .redeem_script
+                    .as_ref()
+                    .cloned()
+                    .unwrap_or(ScriptBuf::new());
+                // Outputs for which the witness size cannot be determined are not
+                // segwit outputs that can be used to fund a DLC.
+                let max_witness_len = dlc::util::get_max_witness_len(
+                    &x.script_pub_key,
+                    &redeem_script,
+                    x.witness_script.as_deref(),
+                )?;
+                Some(Ok(Utxo {
                     tx_out: TxOut {
                         value: x.amount.to_sat(),
                         script_pubkey: x.script_pub_key.clone(),
@@ -303,31 +341,38 @@ impl Wallet for BitcoinCoreProvider {
                         txid: x.txid,
                         vout: x.vout,
                     },
-                    address: x
-                        .address
-                        .as_ref()
-                        .map(|x| x.clone().assume_checked())
-                        .ok_or(Error::InvalidState)?,
-                    redeem_script: x
-                        .redeem_script
-                        .as_ref()
-                        .cloned()
-                        .unwrap_or(ScriptBuf::new()),
+                    address: match x.address.as_ref() {
+                        Some(address) => address.clone().assume_checked(),
+                        None => return Some(Err(Error::InvalidState)),
+                    },
+                    redeem_script,
+                    max_witness_len,
                     reserved: false,
                 }))
             })
-            .collect::<Result<Vec<UtxoWrap>, Error>>()?;
-        // TODO(tibo): properly compute the cost of change
-        let selection = select_coins(amount, 20, &mut utxo_pool).ok_or(Error::NotEnoughCoins)?;
+            .collect::<Result<Vec<Utxo>, Error>>()?;
+        let mut utxo_pool = Vec::new();
+        for utxo in utxos {
+            if let Some(utxo) = UtxoWrap::new(utxo, fee_rate)? {
+                utxo_pool.push(utxo);
+            }
+        }
+        // The cost of adding a change output and of spending it later.
+        let change_spend_weight =
+            dlc::util::get_input_weight(&ScriptBuf::new(), dlc::P2WPKH_WITNESS_SIZE)?;
+        let cost_of_change =
+            dlc::util::weight_to_fee(CHANGE_OUTPUT_WEIGHT + change_spend_weight, fee_rate)?;
+        let selection =
+            select_coins(amount, cost_of_change, &mut utxo_pool).ok_or(Error::NotEnoughCoins)?;
 
         if lock_utxos {
-            let outputs: Vec<_> = selection.iter().map(|x| x.0.outpoint).collect();
+            let outputs: Vec<_> = selection.iter().map(|x| x.utxo.outpoint).collect();
             client
                 .lock_unspent(&outputs)
                 .map_err(rpc_err_to_manager_err)?;
         }
 
-        Ok(selection.into_iter().map(|x| x.0).collect())
+        Ok(selection.into_iter().map(|x| x.utxo).collect())
     }
 
     fn import_address(&self, address: &Address) -> Result<(), ManagerError> {
@@ -456,6 +501,14 @@ impl Blockchain for BitcoinCoreProvider {
         client.get_block(&hash).map_err(rpc_err_to_manager_err)
     }
 
+    fn get_block_hash_at_height(&self, height: u64) -> Result<BlockHash, ManagerError> {
+        self.client
+            .lock()
+            .unwrap()
+            .get_block_hash(height)
+            .map_err(rpc_err_to_manager_err)
+    }
+
     fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, ManagerError> {
         let tx_info = self
             .client
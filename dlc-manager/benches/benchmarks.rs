@@ -157,7 +157,7 @@ fn create_oracle_announcements() -> Vec<OracleAnnouncement> {
             }),
                 oracle_nonces: (0..NB_DIGITS).map(|_| get_schnorr_pubkey()).collect(),
                 event_maturity_epoch: 1234567,
-                event_id: EVENT_ID.to_string(),
+                event_ids: vec![EVENT_ID.to_string()],
         }}}).collect()
 }
 
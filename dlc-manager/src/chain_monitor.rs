@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use bitcoin::{Block, OutPoint, Transaction, Txid};
+use bitcoin::{Block, BlockHash, OutPoint, Transaction, Txid};
 use dlc_messages::ser_impls::{
     read_ecdsa_adaptor_signature, read_hash_map, write_ecdsa_adaptor_signature, write_hash_map,
 };
@@ -10,7 +10,14 @@ use lightning::ln::msgs::DecodeError;
 use lightning::util::ser::{Readable, Writeable, Writer};
 use secp256k1_zkp::EcdsaAdaptorSignature;
 
-use crate::ChannelId;
+use crate::channel::signed_channel::SignedChannel;
+use crate::contract::signed_contract::SignedContract;
+use crate::{ChannelId, ContractId};
+
+/// The number of latest blocks for which the [`ChainMonitor`] keeps the
+/// information required to handle a chain reorganization. Deeper
+/// reorganizations cannot be handled.
+pub const REORG_SAFETY_DEPTH: usize = 100;
 
 /// A `ChainMonitor` keeps a list of transaction ids to watch for in the blockchain,
 /// and some associated information used to apply an action when the id is seen.
@@ -19,9 +26,147 @@ pub struct ChainMonitor {
     pub(crate) watched_tx: HashMap<Txid, WatchState>,
     pub(crate) watched_txo: HashMap<OutPoint, WatchState>,
     pub(crate) last_height: u64,
+    /// The hashes of the latest processed blocks, the last one being the hash of
+    /// the block at `last_height`.
+    pub(crate) block_hashes: Vec<BlockHash>,
+    /// The states of the channels updated following the confirmation of a
+    /// watched transaction, to be restored if the block including it gets
+    /// disconnected.
+    pub(crate) channel_snapshots: Vec<ChannelSnapshot>,
+    /// The funding outputs of the contracts whose refund transaction was
+    /// broadcast, watched to detect which transaction ends up spending them.
+    pub(crate) watched_fund_outputs: HashMap<OutPoint, FundOutputState>,
+    /// The states of the contracts closed following the confirmation of a
+    /// CET, to be restored if the CET gets disconnected from the chain.
+    pub(crate) contract_snapshots: Vec<ContractSnapshot>,
+    /// The broadcast CETs with anchor outputs that are not confirmed yet,
+    /// together with the information required to bump their fee.
+    pub(crate) cet_fee_bumps: HashMap<Txid, CetFeeBump>,
+}
+
+impl_dlc_writeable!(ChainMonitor, {
+    (watched_tx, { cb_writeable, write_hash_map, read_hash_map}),
+    (watched_txo, { cb_writeable, write_hash_map, read_hash_map}),
+    (last_height, writeable),
+    (block_hashes, vec),
+    (channel_snapshots, vec),
+    (watched_fund_outputs, { cb_writeable, write_hash_map, read_hash_map}),
+    (contract_snapshots, vec),
+    (cet_fee_bumps, { cb_writeable, write_hash_map, read_hash_map})
+});
+
+/// The state of a watched contract funding output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum FundOutputState {
+    /// No transaction spending the output was seen in the chain.
+    Unspent { contract_id: ContractId },
+    /// The output was spent by the given transaction.
+    Spent {
+        contract_id: ContractId,
+        transaction: Transaction,
+        /// The height of the block including the transaction.
+        height: u64,
+    },
+}
+
+impl_dlc_writeable_enum!(
+    FundOutputState,;
+    (0, Unspent, {(contract_id, writeable)}),
+    (1, Spent, {(contract_id, writeable), (transaction, writeable), (height, writeable)});;
+);
+
+/// The state of a channel and of its associated contract before they were
+/// updated following the confirmation of a watched transaction, together with
+/// the transactions that were watched for the channel at that time.
+#[derive(Clone)]
+pub(crate) struct ChannelSnapshot {
+    /// The height of the block including the watched transaction.
+    pub height: u64,
+    pub signed_channel: SignedChannel,
+    pub signed_contract: Option<SignedContract>,
+    pub watched_tx: HashMap<Txid, WatchState>,
+    pub watched_txo: HashMap<OutPoint, WatchState>,
+}
+
+impl_dlc_writeable!(ChannelSnapshot, {
+    (height, writeable),
+    (signed_channel, writeable),
+    (signed_contract, option),
+    (watched_tx, { cb_writeable, write_hash_map, read_hash_map}),
+    (watched_txo, { cb_writeable, write_hash_map, read_hash_map})
+});
+
+impl std::fmt::Debug for ChannelSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelSnapshot")
+            .field("height", &self.height)
+            .field("channel_id", &self.signed_channel.channel_id)
+            .finish()
+    }
+}
+
+impl PartialEq for ChannelSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.encode() == other.encode()
+    }
+}
+
+impl Eq for ChannelSnapshot {}
+
+/// The state of a contract before it was closed following the confirmation of
+/// the given CET.
+#[derive(Clone)]
+pub(crate) struct ContractSnapshot {
+    /// The last processed height when the contract was closed.
+    pub height: u64,
+    pub signed_contract: SignedContract,
+    pub closing_txid: Txid,
+}
+
+impl_dlc_writeable!(ContractSnapshot, {
+    (height, writeable),
+    (signed_contract, writeable),
+    (closing_txid, writeable)
+});
+
+impl std::fmt::Debug for ContractSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContractSnapshot")
+            .field("height", &self.height)
+            .field(
+                "contract_id",
+                &self.signed_contract.accepted_contract.get_contract_id(),
+            )
+            .field("closing_txid", &self.closing_txid)
+            .finish()
+    }
+}
+
+impl PartialEq for ContractSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.encode() == other.encode()
+    }
+}
+
+impl Eq for ContractSnapshot {}
+
+/// The fee bumping state of an unconfirmed CET with anchor outputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CetFeeBump {
+    /// The height at which the CET was first seen unconfirmed, or at which its
+    /// fee was last bumped.
+    pub height: u64,
+    /// The fee rate of the last CPFP transaction broadcast for the CET.
+    pub fee_rate_per_vb: u64,
+    /// The last CPFP transaction broadcast for the CET, if any.
+    pub cpfp_tx: Option<Transaction>,
 }
 
-impl_dlc_writeable!(ChainMonitor, { (watched_tx, { cb_writeable, write_hash_map, read_hash_map}), (watched_txo, { cb_writeable, write_hash_map, read_hash_map}), (last_height, writeable) });
+impl_dlc_writeable!(CetFeeBump, {
+    (height, writeable),
+    (fee_rate_per_vb, writeable),
+    (cpfp_tx, option)
+});
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct ChannelInfo {
@@ -43,6 +188,7 @@ pub(crate) enum TxType {
     CollaborativeClose,
     SettleTx,
     Cet,
+    SpliceTx,
 }
 
 impl_dlc_writeable_enum!(TxType,;
@@ -52,12 +198,15 @@ impl_dlc_writeable_enum!(TxType,;
         (is_offer, writeable),
         (revoked_tx_type, writeable)
     });;
-    (1, BufferTx), (2, CollaborativeClose), (3, SettleTx), (4, Cet)
+    (1, BufferTx), (2, CollaborativeClose), (3, SettleTx), (4, Cet), (5, SpliceTx)
 );
 
+/// The type of a revoked transaction of a DLC channel.
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
-pub(crate) enum RevokedTxType {
+pub enum RevokedTxType {
+    /// A buffer transaction.
     Buffer,
+    /// A settle transaction.
     Settle,
 }
 
@@ -70,6 +219,11 @@ impl ChainMonitor {
             watched_tx: HashMap::new(),
             watched_txo: HashMap::new(),
             last_height: init_height,
+            block_hashes: Vec::new(),
+            channel_snapshots: Vec::new(),
+            watched_fund_outputs: HashMap::new(),
+            contract_snapshots: Vec::new(),
+            cet_fee_bumps: HashMap::new(),
         }
     }
 
@@ -123,6 +277,33 @@ impl ChainMonitor {
         self.watched_tx.remove(txid);
     }
 
+    /// Watches the funding output of the given contract for a spending
+    /// transaction. Does nothing if the output is already watched.
+    pub(crate) fn watch_fund_output(&mut self, outpoint: OutPoint, contract_id: ContractId) {
+        log::debug!("Watching funding output {outpoint} of contract {contract_id:?}");
+        self.watched_fund_outputs
+            .entry(outpoint)
+            .or_insert(FundOutputState::Unspent { contract_id });
+    }
+
+    pub(crate) fn remove_fund_output(&mut self, outpoint: &OutPoint) {
+        log::debug!("Stopped watching funding output {outpoint}");
+        self.watched_fund_outputs.remove(outpoint);
+    }
+
+    /// Returns the transaction seen spending the given funding output together
+    /// with the height of the block including it, if any.
+    pub(crate) fn get_fund_output_spend(&self, outpoint: &OutPoint) -> Option<(&Transaction, u64)> {
+        match self.watched_fund_outputs.get(outpoint) {
+            Some(FundOutputState::Spent {
+                transaction,
+                height,
+                ..
+            }) => Some((transaction, *height)),
+            _ => None,
+        }
+    }
+
     /// Check if any watched transactions are part of the block, confirming them if so.
     ///
     /// # Panics
@@ -133,21 +314,186 @@ impl ChainMonitor {
 
         for tx in block.txdata.iter() {
             if let Some(state) = self.watched_tx.get_mut(&tx.txid()) {
-                state.confirm(tx.clone());
+                state.confirm(tx.clone(), height);
             }
 
             for txin in tx.input.iter() {
                 if let Some(state) = self.watched_txo.get_mut(&txin.previous_output) {
-                    state.confirm(tx.clone())
+                    state.confirm(tx.clone(), height)
+                }
+
+                if let Some(FundOutputState::Unspent { contract_id }) = self
+                    .watched_fund_outputs
+                    .get(&txin.previous_output)
+                    .cloned()
+                {
+                    log::info!(
+                        "Funding output of contract {contract_id:?} spent by {}",
+                        tx.txid()
+                    );
+                    self.watched_fund_outputs.insert(
+                        txin.previous_output,
+                        FundOutputState::Spent {
+                            contract_id,
+                            transaction: tx.clone(),
+                            height,
+                        },
+                    );
                 }
             }
         }
 
         self.last_height += 1;
+
+        self.block_hashes.push(block.block_hash());
+        if self.block_hashes.len() > REORG_SAFETY_DEPTH {
+            self.block_hashes.remove(0);
+        }
+        self.channel_snapshots
+            .retain(|x| x.height + REORG_SAFETY_DEPTH as u64 > height);
+        self.contract_snapshots
+            .retain(|x| x.height + REORG_SAFETY_DEPTH as u64 > height);
+    }
+
+    /// Returns the hash of the processed block at the given height if it is
+    /// still tracked.
+    pub(crate) fn get_block_hash(&self, height: u64) -> Option<BlockHash> {
+        if height > self.last_height {
+            return None;
+        }
+        let depth = (self.last_height - height) as usize;
+        if depth >= self.block_hashes.len() {
+            return None;
+        }
+        Some(self.block_hashes[self.block_hashes.len() - 1 - depth])
+    }
+
+    /// Returns the lowest height whose block hash is tracked. Blocks below it
+    /// were processed before any tracked one.
+    pub(crate) fn lowest_tracked_height(&self) -> u64 {
+        self.last_height + 1 - self.block_hashes.len() as u64
     }
 
-    /// All the currently watched transactions which have been confirmed.
-    pub(crate) fn confirmed_txs(&self) -> Vec<(Transaction, ChannelInfo)> {
+    /// Whether blocks were processed before the lowest tracked one, meaning
+    /// that a reorganization disconnecting all the tracked blocks cannot be
+    /// handled.
+    pub(crate) fn is_tracking_full(&self) -> bool {
+        self.block_hashes.len() >= REORG_SAFETY_DEPTH
+    }
+
+    /// Keeps the given state of a channel and of its associated contract to
+    /// be restored if the block at the given height gets disconnected,
+    /// together with the transactions currently watched for the channel.
+    pub(crate) fn add_channel_snapshot(
+        &mut self,
+        height: u64,
+        signed_channel: SignedChannel,
+        signed_contract: Option<SignedContract>,
+    ) {
+        let channel_id = signed_channel.channel_id;
+        self.channel_snapshots.push(ChannelSnapshot {
+            height,
+            signed_channel,
+            signed_contract,
+            watched_tx: self
+                .watched_tx
+                .iter()
+                .filter(|(_, state)| state.channel_id() == channel_id)
+                .map(|(txid, state)| (*txid, state.clone()))
+                .collect(),
+            watched_txo: self
+                .watched_txo
+                .iter()
+                .filter(|(_, state)| state.channel_id() == channel_id)
+                .map(|(outpoint, state)| (*outpoint, state.clone()))
+                .collect(),
+        });
+    }
+
+    /// Keeps the given state of a contract closed following the confirmation
+    /// of the given CET, to be restored if the CET gets disconnected from the
+    /// chain.
+    pub(crate) fn add_contract_snapshot(
+        &mut self,
+        signed_contract: SignedContract,
+        closing_txid: Txid,
+    ) {
+        self.contract_snapshots.push(ContractSnapshot {
+            height: self.last_height,
+            signed_contract,
+            closing_txid,
+        });
+    }
+
+    pub(crate) fn remove_contract_snapshot(&mut self, contract_id: &ContractId) {
+        self.contract_snapshots
+            .retain(|x| &x.signed_contract.accepted_contract.get_contract_id() != contract_id);
+    }
+
+    /// Reverts the effects of the blocks above the given height, which were
+    /// disconnected from the chain. Confirmations of watched transactions
+    /// included in these blocks are forgotten, and the snapshots of the
+    /// channels updated following such confirmations are returned, ordered
+    /// from the most recent to the oldest, after restoring the transactions
+    /// watched for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given height is not lower than the last processed height.
+    pub(crate) fn disconnect_blocks(&mut self, fork_height: u64) -> Vec<ChannelSnapshot> {
+        assert!(fork_height < self.last_height);
+
+        let nb_disconnected = (self.last_height - fork_height) as usize;
+        let nb_kept = self.block_hashes.len().saturating_sub(nb_disconnected);
+        self.block_hashes.truncate(nb_kept);
+        self.last_height = fork_height;
+
+        let (mut disconnected, kept): (Vec<_>, Vec<_>) = self
+            .channel_snapshots
+            .drain(..)
+            .partition(|x| x.height > fork_height);
+        self.channel_snapshots = kept;
+        disconnected.sort_by(|a, b| b.height.cmp(&a.height));
+
+        for snapshot in &disconnected {
+            let channel_id = snapshot.signed_channel.channel_id;
+            self.watched_tx
+                .retain(|_, state| state.channel_id() != channel_id);
+            self.watched_txo
+                .retain(|_, state| state.channel_id() != channel_id);
+            self.watched_tx.extend(snapshot.watched_tx.clone());
+            self.watched_txo.extend(snapshot.watched_txo.clone());
+        }
+
+        for state in self
+            .watched_tx
+            .values_mut()
+            .chain(self.watched_txo.values_mut())
+        {
+            state.unconfirm_above(fork_height);
+        }
+
+        for state in self.watched_fund_outputs.values_mut() {
+            if let FundOutputState::Spent {
+                contract_id,
+                height,
+                ..
+            } = state
+            {
+                if *height > fork_height {
+                    *state = FundOutputState::Unspent {
+                        contract_id: *contract_id,
+                    };
+                }
+            }
+        }
+
+        disconnected
+    }
+
+    /// All the currently watched transactions which have been confirmed,
+    /// together with the height of the block including them.
+    pub(crate) fn confirmed_txs(&self) -> Vec<(Transaction, ChannelInfo, u64)> {
         (self.watched_tx.values())
             .chain(self.watched_txo.values())
             .filter_map(|state| match state {
@@ -155,7 +501,8 @@ impl ChainMonitor {
                 WatchState::Confirmed {
                     channel_info,
                     transaction,
-                } => Some((transaction.clone(), *channel_info)),
+                    height,
+                } => Some((transaction.clone(), *channel_info, *height)),
             })
             .collect()
     }
@@ -171,13 +518,15 @@ pub(crate) enum WatchState {
     Confirmed {
         channel_info: ChannelInfo,
         transaction: Transaction,
+        /// The height of the block including the transaction.
+        height: u64,
     },
 }
 
 impl_dlc_writeable_enum!(
     WatchState,;
     (0, Registered, {(channel_info, writeable)}),
-    (1, Confirmed, {(channel_info, writeable), (transaction, writeable)});;
+    (1, Confirmed, {(channel_info, writeable), (transaction, writeable), (height, writeable)});;
 );
 
 impl WatchState {
@@ -185,7 +534,7 @@ impl WatchState {
         Self::Registered { channel_info }
     }
 
-    fn confirm(&mut self, transaction: Transaction) {
+    fn confirm(&mut self, transaction: Transaction, height: u64) {
         match self {
             WatchState::Registered { ref channel_info } => {
                 log::info!(
@@ -196,11 +545,13 @@ impl WatchState {
                 *self = WatchState::Confirmed {
                     channel_info: *channel_info,
                     transaction,
+                    height,
                 }
             }
             WatchState::Confirmed {
                 channel_info,
                 transaction,
+                ..
             } => {
                 log::error!(
                     "Transaction {} already confirmed: {channel_info:?}",
@@ -210,6 +561,25 @@ impl WatchState {
         }
     }
 
+    fn unconfirm_above(&mut self, fork_height: u64) {
+        if let WatchState::Confirmed {
+            channel_info,
+            transaction,
+            height,
+        } = self
+        {
+            if *height > fork_height {
+                log::info!(
+                    "Transaction {} was reorganized out of the chain: {channel_info:?}",
+                    transaction.txid()
+                );
+                *self = WatchState::Registered {
+                    channel_info: *channel_info,
+                };
+            }
+        }
+    }
+
     fn channel_info(&self) -> ChannelInfo {
         match self {
             WatchState::Registered { channel_info }
@@ -221,3 +591,155 @@ impl WatchState {
         self.channel_info().channel_id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::absolute::LockTime;
+    use bitcoin::block::{Header, Version};
+    use bitcoin::hash_types::TxMerkleNode;
+    use bitcoin::hashes::Hash;
+    use bitcoin::{CompactTarget, ScriptBuf, Sequence, TxIn, TxOut, Witness};
+
+    fn tx(value: u64) -> Transaction {
+        Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: Vec::new(),
+            output: vec![TxOut {
+                value,
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    fn block(nonce: u32, txdata: Vec<Transaction>) -> Block {
+        Block {
+            header: Header {
+                version: Version::ONE,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: 0,
+                bits: CompactTarget::from_consensus(0x207fffff),
+                nonce,
+            },
+            txdata,
+        }
+    }
+
+    #[test]
+    fn disconnect_blocks_unconfirms_reorged_transactions() {
+        let mut monitor = ChainMonitor::new(10);
+        let channel_info = ChannelInfo {
+            channel_id: [1u8; 32],
+            tx_type: TxType::SettleTx,
+        };
+        let first = tx(1);
+        let second = tx(2);
+        monitor.add_tx(first.txid(), channel_info);
+        monitor.add_tx(second.txid(), channel_info);
+
+        let blocks = vec![
+            block(1, vec![first.clone()]),
+            block(2, Vec::new()),
+            block(3, vec![second.clone()]),
+        ];
+        for (i, b) in blocks.iter().enumerate() {
+            monitor.process_block(b, 11 + i as u64);
+        }
+
+        assert_eq!(2, monitor.confirmed_txs().len());
+        assert_eq!(Some(blocks[1].block_hash()), monitor.get_block_hash(12));
+        assert_eq!(11, monitor.lowest_tracked_height());
+
+        monitor.disconnect_blocks(12);
+
+        assert_eq!(12, monitor.last_height);
+        assert_eq!(None, monitor.get_block_hash(13));
+        let confirmed = monitor.confirmed_txs();
+        assert_eq!(1, confirmed.len());
+        assert_eq!(first.txid(), confirmed[0].0.txid());
+        assert_eq!(11, confirmed[0].2);
+        assert!(matches!(
+            monitor.watched_tx.get(&second.txid()),
+            Some(WatchState::Registered { .. })
+        ));
+    }
+
+    #[test]
+    fn detects_and_reverts_fund_output_spend() {
+        let mut monitor = ChainMonitor::new(10);
+        let fund_outpoint = OutPoint {
+            txid: tx(1).txid(),
+            vout: 0,
+        };
+        monitor.watch_fund_output(fund_outpoint, [2u8; 32]);
+
+        let mut spend = tx(2);
+        spend.input.push(TxIn {
+            previous_output: fund_outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::MAX,
+            witness: Witness::new(),
+        });
+
+        monitor.process_block(&block(1, Vec::new()), 11);
+        assert!(monitor.get_fund_output_spend(&fund_outpoint).is_none());
+
+        monitor.process_block(&block(2, vec![spend.clone()]), 12);
+        let (transaction, height) = monitor
+            .get_fund_output_spend(&fund_outpoint)
+            .expect("to have detected the spend");
+        assert_eq!(spend.txid(), transaction.txid());
+        assert_eq!(12, height);
+
+        monitor.disconnect_blocks(11);
+        assert!(monitor.get_fund_output_spend(&fund_outpoint).is_none());
+
+        monitor.remove_fund_output(&fund_outpoint);
+        assert!(monitor.watched_fund_outputs.is_empty());
+    }
+
+    #[test]
+    fn cet_fee_bumps_are_persisted() {
+        let mut monitor = ChainMonitor::new(10);
+        monitor.cet_fee_bumps.insert(
+            tx(1).txid(),
+            CetFeeBump {
+                height: 11,
+                fee_rate_per_vb: 2,
+                cpfp_tx: None,
+            },
+        );
+        monitor.cet_fee_bumps.insert(
+            tx(2).txid(),
+            CetFeeBump {
+                height: 12,
+                fee_rate_per_vb: 10,
+                cpfp_tx: Some(tx(3)),
+            },
+        );
+
+        let deserialized: ChainMonitor =
+            Readable::read(&mut std::io::Cursor::new(monitor.encode()))
+                .expect("to be able to read the serialized monitor");
+
+        assert_eq!(monitor, deserialized);
+    }
+
+    #[test]
+    fn only_keeps_latest_block_hashes() {
+        let mut monitor = ChainMonitor::new(0);
+        for i in 1..=(REORG_SAFETY_DEPTH as u64 + 5) {
+            monitor.process_block(&block(i as u32, Vec::new()), i);
+        }
+
+        assert!(monitor.is_tracking_full());
+        assert_eq!(6, monitor.lowest_tracked_height());
+        assert_eq!(None, monitor.get_block_hash(5));
+        assert_eq!(
+            Some(block(6, Vec::new()).block_hash()),
+            monitor.get_block_hash(6)
+        );
+    }
+}
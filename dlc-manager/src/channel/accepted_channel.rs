@@ -4,7 +4,7 @@ use bitcoin::{ScriptBuf, Transaction};
 use dlc_messages::channel::AcceptChannel;
 use secp256k1_zkp::{EcdsaAdaptorSignature, PublicKey};
 
-use crate::{contract::accepted_contract::AcceptedContract, ChannelId, ContractId};
+use crate::{contract::accepted_contract::AcceptedContract, error::Error, ChannelId, ContractId};
 
 use super::party_points::PartyBasePoints;
 
@@ -38,6 +38,8 @@ pub struct AcceptedChannel {
     pub accept_per_update_seed: PublicKey,
     /// The accept party adaptor signature for the buffer transaction.
     pub accept_buffer_adaptor_signature: EcdsaAdaptorSignature,
+    /// The nSequence value to use for the CETs.
+    pub cet_nsequence: u32,
 }
 
 impl AcceptedChannel {
@@ -46,8 +48,8 @@ impl AcceptedChannel {
         contract: &AcceptedContract,
         buffer_adaptor_signature: &EcdsaAdaptorSignature,
         cet_adaptor_signatures: &[EcdsaAdaptorSignature],
-    ) -> AcceptChannel {
-        AcceptChannel {
+    ) -> Result<AcceptChannel, Error> {
+        Ok(AcceptChannel {
             temporary_channel_id: self.temporary_channel_id,
             accept_collateral: contract.accept_params.collateral,
             funding_pubkey: contract.accept_params.fund_pubkey,
@@ -57,13 +59,13 @@ impl AcceptedChannel {
             change_spk: contract.accept_params.change_script_pubkey.clone(),
             change_serial_id: contract.accept_params.change_serial_id,
             cet_adaptor_signatures: cet_adaptor_signatures.into(),
-            refund_signature: contract.accept_refund_signature,
+            refund_signature: contract.get_accept_refund_signature()?,
             negotiation_fields: None,
             revocation_basepoint: self.accept_base_points.revocation_basepoint,
             publish_basepoint: self.accept_base_points.publish_basepoint,
             own_basepoint: self.accept_base_points.own_basepoint,
             first_per_update_point: self.accept_per_update_point,
             buffer_adaptor_signature: *buffer_adaptor_signature,
-        }
+        })
     }
 }
@@ -1,9 +1,10 @@
 //! # A channel is offered when an offer was made or received. This module contains
 //! the model for it and method for working with it.
 
-use dlc::PartyParams;
+use dlc::{FundingOutputType, PartyParams};
 use dlc_messages::channel::OfferChannel;
 // use dlc_messages::channel::OfferChannel;
+use dlc_messages::ANCHOR_OUTPUTS_CONTRACT_FLAG;
 use secp256k1_zkp::PublicKey;
 
 use crate::{
@@ -48,7 +49,7 @@ impl OfferedChannel {
         let party_points = &self.party_points;
         OfferChannel {
             protocol_version: crate::conversion_utils::PROTOCOL_VERSION,
-            contract_flags: 0,
+            contract_flags: offered_contract.get_contract_flags(),
             chain_hash: crate::conversion_utils::BITCOIN_CHAINHASH,
             temporary_contract_id: offered_contract.id,
             temporary_channel_id: self.temporary_channel_id,
@@ -68,7 +69,7 @@ impl OfferedChannel {
             refund_locktime: offered_contract.refund_locktime,
             fee_rate_per_vb: offered_contract.fee_rate_per_vb,
             fund_output_serial_id: offered_contract.fund_output_serial_id,
-            cet_nsequence: crate::manager::CET_NSEQUENCE,
+            cet_nsequence: self.cet_nsequence,
         }
     }
 
@@ -117,6 +118,16 @@ impl OfferedChannel {
             },
             cet_locktime: offer_channel.cet_locktime,
             refund_locktime: offer_channel.refund_locktime,
+            anchor_outputs: offer_channel.contract_flags & ANCHOR_OUTPUTS_CONTRACT_FLAG != 0,
+            batch_id: None,
+            single_funded: false,
+            fee_payer: None,
+            replaced_contract_id: None,
+            rolled_over_contract_id: None,
+            offer_expiry: None,
+            funding_output_type: FundingOutputType::P2wsh,
+            taproot_funding_nonces: None,
+            taproot_secret_nonces: None,
             fee_rate_per_vb: offer_channel.fee_rate_per_vb,
             fund_output_serial_id: offer_channel.fund_output_serial_id,
             funding_inputs: offer_channel.funding_inputs.clone(),
@@ -25,7 +25,8 @@ impl_dlc_writeable!(AcceptedChannel, {
     (channel_id, writeable),
     (accept_per_update_seed, writeable),
     (accept_buffer_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}),
-    (counter_party, writeable)
+    (counter_party, writeable),
+    (cet_nsequence, writeable)
 });
 impl_dlc_writeable!(SignedChannel, {
     (channel_id, writeable),
@@ -45,23 +46,29 @@ impl_dlc_writeable!(SignedChannel, {
     (roll_back_state, option),
     (own_per_update_seed, writeable),
     (counter_party_commitment_secrets, writeable),
-    (fee_rate_per_vb, writeable)
+    (fee_rate_per_vb, writeable),
+    (cet_nsequence, writeable),
+    (anchor_outputs, writeable)
 });
 
 impl_dlc_writeable_enum!(
     SignedChannelState,;
     (0, Established, {(signed_contract_id, writeable), (own_buffer_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (counter_buffer_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (buffer_transaction, writeable), (is_offer, writeable), (total_collateral, writeable), (keys_id, writeable)}),
-    (1, SettledOffered, {(counter_payout, writeable), (next_per_update_point, writeable), (timeout, writeable), (keys_id, writeable)}),
-    (2, SettledReceived, {(own_payout, writeable), (counter_payout, writeable), (counter_next_per_update_point, writeable), (keys_id, writeable)}),
+    (1, SettledOffered, {(counter_payout, writeable), (next_per_update_point, writeable), (offer_fee_share, writeable), (timeout, writeable), (keys_id, writeable)}),
+    (2, SettledReceived, {(own_payout, writeable), (counter_payout, writeable), (counter_next_per_update_point, writeable), (offer_fee_share, writeable), (keys_id, writeable)}),
     (3, SettledAccepted, {(counter_next_per_update_point, writeable), (own_next_per_update_point, writeable), (settle_tx, writeable), (own_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (timeout, writeable), (own_payout, writeable), (counter_payout, writeable), (keys_id, writeable)}),
     (4, SettledConfirmed, {(settle_tx, writeable), (counter_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (counter_next_per_update_point, writeable), (own_next_per_update_point, writeable), (timeout, writeable), (own_payout, writeable), (counter_payout, writeable), (keys_id, writeable) }),
     (5, Settled, {(settle_tx, writeable), (counter_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_payout, writeable), (counter_payout, writeable), (keys_id, writeable)}),
-    (6, RenewOffered, {(offered_contract_id, writeable), (counter_payout, writeable), (is_offer, writeable), (offer_next_per_update_point, writeable), (timeout, writeable), (keys_id, writeable)}),
+    (6, RenewOffered, {(offered_contract_id, writeable), (counter_payout, writeable), (is_offer, writeable), (offer_next_per_update_point, writeable), (offer_fee_share, writeable), (timeout, writeable), (keys_id, writeable)}),
     (7, RenewAccepted, {(contract_id, writeable), (offer_per_update_point, writeable), (accept_per_update_point, writeable), (buffer_transaction, writeable), (buffer_script_pubkey, writeable), (timeout, writeable), (own_payout, writeable), (keys_id, writeable)}),
     (8, RenewConfirmed, {(contract_id, writeable), (offer_per_update_point, writeable), (accept_per_update_point, writeable), (buffer_transaction, writeable), (buffer_script_pubkey, writeable), (offer_buffer_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (timeout, writeable), (own_payout, writeable), (total_collateral, writeable), (keys_id, writeable)}),
     (10, RenewFinalized, {(contract_id, writeable), (prev_offer_per_update_point, writeable), (buffer_transaction, writeable), (buffer_script_pubkey, writeable), (offer_buffer_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (accept_buffer_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (timeout, writeable), (own_payout, writeable), (total_collateral, writeable), (keys_id, writeable)}),
     (9, Closing, {(buffer_transaction, writeable), (contract_id, writeable), (keys_id, writeable), (is_initiator, writeable)}),
-    (11, CollaborativeCloseOffered, { (counter_payout, writeable), (offer_signature, writeable), (close_tx, writeable), (timeout, writeable), (keys_id, writeable) })
+    (11, CollaborativeCloseOffered, { (counter_payout, writeable), (offer_signature, writeable), (close_tx, writeable), (offer_fee_share, writeable), (timeout, writeable), (keys_id, writeable) }),
+    (12, SpliceOffered, {(splice_tx, writeable), (settle_tx, writeable), (own_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_payout, writeable), (counter_payout, writeable), (funding_inputs, vec), (timeout, writeable), (keys_id, writeable)}),
+    (13, SpliceReceived, {(splice_tx, writeable), (settle_tx, writeable), (counter_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_payout, writeable), (counter_payout, writeable), (keys_id, writeable)}),
+    (14, SpliceAccepted, {(splice_tx, writeable), (settle_tx, writeable), (counter_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_payout, writeable), (counter_payout, writeable), (timeout, writeable), (keys_id, writeable)}),
+    (15, SpliceConfirmed, {(splice_tx, writeable), (settle_tx, writeable), (counter_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}), (own_payout, writeable), (counter_payout, writeable), (keys_id, writeable)})
     ;;
 );
 
@@ -4,6 +4,7 @@
 
 use bitcoin::{ScriptBuf, Transaction};
 use dlc::PartyParams;
+use dlc_messages::FundingInput;
 use lightning::ln::chan_utils::CounterpartyCommitmentSecrets;
 use secp256k1_zkp::{ecdsa::Signature, EcdsaAdaptorSignature, PublicKey};
 
@@ -120,6 +121,8 @@ typed_enum!(
             /// The per update point that the local party would use for the next
             /// channel state.
             next_per_update_point: PublicKey,
+            /// The percentage of the settle transaction fee paid by the local party.
+            offer_fee_share: u8,
             /// The UNIX epoch at which the counter party will be considered
             /// unresponsive and the channel will be forced closed.
             timeout: u64,
@@ -136,6 +139,8 @@ typed_enum!(
             /// The per update point to be used by the counter party for the setup
             /// of the next channel state.
             counter_next_per_update_point: PublicKey,
+            /// The percentage of the settle transaction fee paid by the counter party.
+            offer_fee_share: u8,
             /// Keys Id for generating the signers
             keys_id: KeysId,
         },
@@ -219,6 +224,9 @@ typed_enum!(
             /// The per update point to be used by the offer party for the setup
             /// of the next channel state.
             offer_next_per_update_point: PublicKey,
+            /// The percentage of the fee of the renewed buffer transaction and
+            /// CETs paid by the party that offered the renewal.
+            offer_fee_share: u8,
             /// Indicates whether the local party offered the renewal or not.
             is_offer: bool,
             /// The UNIX epoch at which the counter party will be considered
@@ -328,12 +336,114 @@ typed_enum!(
             offer_signature: Signature,
             /// The closing transaction.
             close_tx: Transaction,
+            /// The percentage of the closing transaction fee paid by the party
+            /// that offered to close the channel.
+            offer_fee_share: u8,
             /// The UNIX epoch at which the counter party will be considered
             /// unresponsive and the channel will be forced closed.
             timeout: u64,
             /// Keys Id for generating the signers
             keys_id: KeysId,
         },
+        /// A [`SignedChannel`] is in `SpliceOffered` state when the local party
+        /// has sent a [`dlc_messages::channel::SpliceOffer`] message.
+        SpliceOffered {
+            /// The unsigned splice transaction replacing the current fund
+            /// transaction.
+            splice_tx: Transaction,
+            /// The settle transaction spending the output of the splice
+            /// transaction.
+            settle_tx: Transaction,
+            /// The adaptor signature for the settle transaction generated by the
+            /// local party.
+            own_settle_adaptor_signature: EcdsaAdaptorSignature,
+            /// The amount the local party will hold in the channel after the
+            /// splice.
+            own_payout: u64,
+            /// The amount the counter party will hold in the channel after the
+            /// splice.
+            counter_payout: u64,
+            /// The inputs provided by the local party to the splice transaction.
+            funding_inputs: Vec<FundingInput>,
+            /// The UNIX epoch at which the counter party will be considered
+            /// unresponsive and the channel will be forced closed.
+            timeout: u64,
+            /// Keys Id for generating the signers
+            keys_id: KeysId,
+        },
+        /// A [`SignedChannel`] is in `SpliceReceived` state when the local party
+        /// has received a [`dlc_messages::channel::SpliceOffer`] message.
+        SpliceReceived {
+            /// The unsigned splice transaction replacing the current fund
+            /// transaction.
+            splice_tx: Transaction,
+            /// The settle transaction spending the output of the splice
+            /// transaction.
+            settle_tx: Transaction,
+            /// The adaptor signature for the settle transaction generated by the
+            /// counter party.
+            counter_settle_adaptor_signature: EcdsaAdaptorSignature,
+            /// The amount the local party will hold in the channel after the
+            /// splice.
+            own_payout: u64,
+            /// The amount the counter party will hold in the channel after the
+            /// splice.
+            counter_payout: u64,
+            /// Keys Id for generating the signers
+            keys_id: KeysId,
+        },
+        /// A [`SignedChannel`] is in `SpliceAccepted` state when the local party
+        /// has sent a [`dlc_messages::channel::SpliceAccept`] message.
+        SpliceAccepted {
+            /// The unsigned splice transaction replacing the current fund
+            /// transaction.
+            splice_tx: Transaction,
+            /// The settle transaction spending the output of the splice
+            /// transaction.
+            settle_tx: Transaction,
+            /// The adaptor signature for the settle transaction generated by the
+            /// counter party.
+            counter_settle_adaptor_signature: EcdsaAdaptorSignature,
+            /// The adaptor signature for the settle transaction generated by the
+            /// local party.
+            own_settle_adaptor_signature: EcdsaAdaptorSignature,
+            /// The amount the local party will hold in the channel after the
+            /// splice.
+            own_payout: u64,
+            /// The amount the counter party will hold in the channel after the
+            /// splice.
+            counter_payout: u64,
+            /// The UNIX epoch at which the counter party will be considered
+            /// unresponsive and the channel will be forced closed.
+            timeout: u64,
+            /// Keys Id for generating the signers
+            keys_id: KeysId,
+        },
+        /// A [`SignedChannel`] is in `SpliceConfirmed` state when the fully
+        /// signed splice transaction has been broadcast and the local party is
+        /// waiting for it to be confirmed.
+        SpliceConfirmed {
+            /// The signed splice transaction replacing the current fund
+            /// transaction.
+            splice_tx: Transaction,
+            /// The settle transaction spending the output of the splice
+            /// transaction.
+            settle_tx: Transaction,
+            /// The adaptor signature for the settle transaction generated by the
+            /// counter party.
+            counter_settle_adaptor_signature: EcdsaAdaptorSignature,
+            /// The adaptor signature for the settle transaction generated by the
+            /// local party.
+            own_settle_adaptor_signature: EcdsaAdaptorSignature,
+            /// The amount the local party will hold in the channel after the
+            /// splice.
+            own_payout: u64,
+            /// The amount the counter party will hold in the channel after the
+            /// splice.
+            counter_payout: u64,
+            /// Keys Id for generating the signers
+            keys_id: KeysId,
+        },
     },
     /// Enum automatically generated associating a number to each signed channel
     /// state.
@@ -376,6 +486,10 @@ impl SignedChannel {
             SignedChannelState::Closing { keys_id, .. } => Some(*keys_id),
             SignedChannelState::CollaborativeCloseOffered { keys_id, .. } => Some(*keys_id),
             SignedChannelState::RenewFinalized { keys_id, .. } => Some(*keys_id),
+            SignedChannelState::SpliceOffered { keys_id, .. } => Some(*keys_id),
+            SignedChannelState::SpliceReceived { keys_id, .. } => Some(*keys_id),
+            SignedChannelState::SpliceAccepted { keys_id, .. } => Some(*keys_id),
+            SignedChannelState::SpliceConfirmed { keys_id, .. } => Some(*keys_id),
         }
     }
 }
@@ -421,4 +535,10 @@ pub struct SignedChannel {
     pub counter_party_commitment_secrets: CounterpartyCommitmentSecrets,
     /// The current fee rate to be used to create transactions.
     pub fee_rate_per_vb: u64,
+    /// The nSequence value to use for the CETs and settle transactions,
+    /// negotiated when establishing the channel.
+    pub cet_nsequence: u32,
+    /// Whether the buffer, settle and CET transactions of the channel include
+    /// an anchor output for each party.
+    pub anchor_outputs: bool,
 }
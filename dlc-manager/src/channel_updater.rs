@@ -14,7 +14,7 @@ use crate::{
     contract::{
         accepted_contract::AcceptedContract, contract_info::ContractInfo,
         contract_input::ContractInput, offered_contract::OfferedContract,
-        signed_contract::SignedContract, AdaptorInfo,
+        signed_contract::SignedContract, AdaptorInfo, Contract,
     },
     contract_updater::{
         accept_contract_internal, verify_accepted_and_sign_contract_internal,
@@ -22,27 +22,33 @@ use crate::{
     },
     error::Error,
     utils::get_new_temporary_id,
-    Blockchain, ChannelId, ContractId, ContractSigner, ContractSignerProvider, KeysId, Time,
-    Wallet,
+    AsyncBlockchain, AsyncWallet, ChannelId, ContractId, ContractSigner, ContractSignerProvider,
+    KeysId, Time,
+};
+use bitcoin::psbt::PartiallySignedTransaction;
+use bitcoin::{
+    consensus::Decodable, OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn, Witness,
 };
-use bitcoin::{OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn, Witness};
 use dlc::{
     channel::{get_tx_adaptor_signature, verify_tx_adaptor_signature, DlcChannelTransactions},
-    PartyParams,
+    DlcTransactionsOptions, FundingOutputType, PartyParams,
 };
 use dlc_messages::{
     channel::{
-        AcceptChannel, CollaborativeCloseOffer, Reject, RenewAccept, RenewConfirm, RenewFinalize,
-        RenewOffer, RenewRevoke, SettleAccept, SettleConfirm, SettleFinalize, SettleOffer,
-        SignChannel,
+        AcceptChannel, ChannelReestablish, ChannelUpdateState, CollaborativeCloseOffer, Reject,
+        RenewAccept, RenewConfirm, RenewFinalize, RenewOffer, RenewRevoke, SettleAccept,
+        SettleConfirm, SettleFinalize, SettleOffer, SignChannel, SpliceAccept, SpliceConfirm,
+        SpliceOffer,
     },
     oracle_msgs::{OracleAnnouncement, OracleAttestation},
-    FundingSignatures,
+    FundingInput, FundingSignature, FundingSignatures, Message, WitnessElement,
 };
 use lightning::ln::chan_utils::{
     build_commitment_secret, derive_private_key, CounterpartyCommitmentSecrets,
 };
-use secp256k1_zkp::{All, EcdsaAdaptorSignature, PublicKey, Secp256k1, SecretKey, Signing};
+use secp256k1_zkp::{
+    All, EcdsaAdaptorSignature, PublicKey, Secp256k1, SecretKey, Signing, Verification,
+};
 
 const INITIAL_UPDATE_NUMBER: u64 = (1 << 48) - 1;
 
@@ -70,7 +76,7 @@ pub(crate) use get_signed_channel_state;
 
 /// Creates an [`OfferedChannel`] and an associated [`OfferedContract`] using
 /// the given parameter.
-pub fn offer_channel<C: Signing, W: Deref, SP: Deref, B: Deref, T: Deref, X: ContractSigner>(
+pub async fn offer_channel<C: Signing, W: Deref, SP: Deref, B: Deref, T: Deref, X: ContractSigner>(
     secp: &Secp256k1<C>,
     contract: &ContractInput,
     counter_party: &PublicKey,
@@ -84,11 +90,12 @@ pub fn offer_channel<C: Signing, W: Deref, SP: Deref, B: Deref, T: Deref, X: Con
     temporary_channel_id: ChannelId,
 ) -> Result<(OfferedChannel, OfferedContract), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
-    B::Target: Blockchain,
+    B::Target: AsyncBlockchain,
     T::Target: Time,
 {
+    check_channel_contract_input(contract)?;
     let id = get_new_temporary_id();
     let keys_id = signer_provider.derive_signer_key_id(true, id);
     let signer = signer_provider.derive_contract_signer(keys_id)?;
@@ -96,10 +103,12 @@ where
         secp,
         contract.offer_collateral,
         contract.fee_rate,
+        &contract.get_dlc_transactions_options(),
         wallet,
         &signer,
         blockchain,
-    )?;
+    )
+    .await?;
     let party_points = crate::utils::get_party_base_points(secp, signer_provider)?;
 
     let offered_contract = OfferedContract::new(
@@ -142,7 +151,7 @@ where
 /// Move the given [`OfferedChannel`] and [`OfferedContract`] to an [`AcceptedChannel`]
 /// and [`AcceptedContract`], returning them as well as the [`AcceptChannel`]
 /// message to be sent to the counter party.
-pub fn accept_channel_offer<W: Deref, SP: Deref, B: Deref, X: ContractSigner>(
+pub async fn accept_channel_offer<W: Deref, SP: Deref, B: Deref, X: ContractSigner>(
     secp: &Secp256k1<All>,
     offered_channel: &OfferedChannel,
     offered_contract: &OfferedContract,
@@ -151,9 +160,9 @@ pub fn accept_channel_offer<W: Deref, SP: Deref, B: Deref, X: ContractSigner>(
     blockchain: &B,
 ) -> Result<(AcceptedChannel, AcceptedContract, AcceptChannel), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
-    B::Target: Blockchain,
+    B::Target: AsyncBlockchain,
 {
     assert_eq!(offered_channel.offered_contract_id, offered_contract.id);
 
@@ -164,10 +173,12 @@ where
         secp,
         total_collateral - offered_contract.offer_params.collateral,
         offered_contract.fee_rate_per_vb,
+        &offered_contract.get_dlc_transactions_options(),
         wallet,
         &signer,
         blockchain,
-    )?;
+    )
+    .await?;
 
     let per_update_seed = signer_provider.get_new_secret_key()?;
 
@@ -212,6 +223,7 @@ where
         offered_contract.cet_locktime,
         offered_contract.fund_output_serial_id,
         Sequence(offered_channel.cet_nsequence),
+        offered_contract.anchor_outputs,
     )?;
 
     let own_base_secret_key =
@@ -258,13 +270,14 @@ where
         accept_per_update_seed: PublicKey::from_secret_key(secp, &per_update_seed),
         accept_buffer_adaptor_signature: buffer_adaptor_signature,
         counter_party: offered_contract.counter_party,
+        cet_nsequence: offered_channel.cet_nsequence,
     };
 
     let accept_channel = accepted_channel.get_accept_channel_msg(
         &accepted_contract,
         &buffer_adaptor_signature,
         &adaptor_sigs,
-    );
+    )?;
 
     Ok((accepted_channel, accepted_contract, accept_channel))
 }
@@ -273,7 +286,7 @@ where
 /// to the given [`OfferedChannel`] and [`OfferedContract`], transforming them
 /// to a [`SignedChannel`] and [`SignedContract`], returning them as well as the
 /// [`SignChannel`] to be sent to the counter party.
-pub fn verify_and_sign_accepted_channel<W: Deref, SP: Deref, X: ContractSigner>(
+pub async fn verify_and_sign_accepted_channel<W: Deref, SP: Deref, X: ContractSigner>(
     secp: &Secp256k1<All>,
     offered_channel: &OfferedChannel,
     offered_contract: &OfferedContract,
@@ -284,7 +297,7 @@ pub fn verify_and_sign_accepted_channel<W: Deref, SP: Deref, X: ContractSigner>(
     chain_monitor: &Mutex<ChainMonitor>,
 ) -> Result<(SignedChannel, SignedContract, SignChannel), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
 {
     let (tx_input_infos, input_amount) =
@@ -346,6 +359,7 @@ where
         offered_contract.cet_locktime,
         offered_contract.fund_output_serial_id,
         Sequence(cet_nsequence),
+        offered_contract.anchor_outputs,
     )?;
 
     let channel_id = crate::utils::compute_id(
@@ -370,7 +384,8 @@ where
         Some(accept_revoke_params.own_pk.inner),
         &dlc_transactions,
         Some(channel_id),
-    )?;
+    )
+    .await?;
 
     verify_tx_adaptor_signature(
         secp,
@@ -434,13 +449,15 @@ where
             .accepted_contract
             .offered_contract
             .fee_rate_per_vb,
+        cet_nsequence,
+        anchor_outputs: offered_contract.anchor_outputs,
     };
 
     let sign_channel = SignChannel {
         channel_id,
         cet_adaptor_signatures: (&cet_adaptor_signatures as &[_]).into(),
         buffer_adaptor_signature: own_buffer_adaptor_signature,
-        refund_signature: signed_contract.offer_refund_signature,
+        refund_signature: signed_contract.get_offer_refund_signature()?,
         funding_signatures: signed_contract.funding_signatures.clone(),
     };
 
@@ -450,7 +467,7 @@ where
 /// Verify that the given [`SignChannel`] message is valid with respect to the
 /// given [`AcceptedChannel`] and [`AcceptedContract`], transforming them
 /// to a [`SignedChannel`] and [`SignedContract`], and returning them.
-pub fn verify_signed_channel<W: Deref>(
+pub async fn verify_signed_channel<W: Deref>(
     secp: &Secp256k1<All>,
     accepted_channel: &AcceptedChannel,
     accepted_contract: &AcceptedContract,
@@ -459,7 +476,7 @@ pub fn verify_signed_channel<W: Deref>(
     chain_monitor: &Mutex<ChainMonitor>,
 ) -> Result<(SignedChannel, SignedContract, Transaction), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
 {
     let own_publish_pk = accepted_channel
         .accept_base_points
@@ -486,12 +503,14 @@ where
         &sign_channel.refund_signature,
         &cet_adaptor_signatures,
         &sign_channel.funding_signatures,
+        None,
         accepted_channel.buffer_transaction.output[0].value,
         Some(&accepted_channel.buffer_script_pubkey),
         Some(counter_own_pk),
         wallet,
         Some(accepted_channel.channel_id),
-    )?;
+    )
+    .await?;
 
     chain_monitor.lock().unwrap().add_tx(
         accepted_channel.buffer_transaction.txid(),
@@ -541,6 +560,8 @@ where
             .accepted_contract
             .offered_contract
             .fee_rate_per_vb,
+        cet_nsequence: accepted_channel.cet_nsequence,
+        anchor_outputs: accepted_contract.offered_contract.anchor_outputs,
     };
 
     Ok((signed_channel, signed_contract, signed_fund_tx))
@@ -553,6 +574,7 @@ pub fn settle_channel_offer<C: Signing, SP: Deref, T: Deref>(
     secp: &Secp256k1<C>,
     channel: &mut SignedChannel,
     counter_payout: u64,
+    offer_fee_share: u8,
     peer_timeout: u64,
     signer_provider: &SP,
     time: &T,
@@ -569,6 +591,22 @@ where
         ));
     };
 
+    let total_collateral = channel.counter_params.collateral + channel.own_params.collateral;
+    if counter_payout > total_collateral {
+        return Err(Error::InvalidParameters(
+            "Counter payout is greater than total collateral".to_string(),
+        ));
+    }
+
+    check_fee_split(
+        channel.fee_rate_per_vb,
+        channel.fund_tx.output[channel.fund_output_index].value,
+        total_collateral - counter_payout,
+        counter_payout,
+        offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, true),
+    )?;
+
     let per_update_seed_pk = channel.own_per_update_seed;
     let per_update_seed = signer_provider.get_secret_key_for_pubkey(&per_update_seed_pk)?;
 
@@ -583,6 +621,7 @@ where
     let mut state = SignedChannelState::SettledOffered {
         counter_payout,
         next_per_update_point,
+        offer_fee_share,
         timeout: time.unix_time_now() + peer_timeout,
         keys_id,
     };
@@ -595,16 +634,19 @@ where
         channel_id: channel.channel_id,
         counter_payout,
         next_per_update_point,
+        offer_fee_share,
     };
 
     Ok(settle_channel_offer)
 }
 
 /// Updates the state of the given [`SignedChannel`] using the given [`SettleOffer`]
-/// message.
+/// message. The offer is rejected if the share of the settle transaction fee
+/// paid by the counter party is lower than `min_counter_offer_fee_share`.
 pub fn on_settle_offer(
     signed_channel: &mut SignedChannel,
     settle_offer: &SettleOffer,
+    min_counter_offer_fee_share: u8,
 ) -> Result<(), Error> {
     let keys_id = if let SignedChannelState::Established { keys_id, .. } = signed_channel.state {
         keys_id
@@ -623,10 +665,21 @@ pub fn on_settle_offer(
         ));
     }
 
+    check_counter_fee_share(settle_offer.offer_fee_share, min_counter_offer_fee_share)?;
+    check_fee_split(
+        signed_channel.fee_rate_per_vb,
+        signed_channel.fund_tx.output[signed_channel.fund_output_index].value,
+        total_collateral - settle_offer.counter_payout,
+        settle_offer.counter_payout,
+        settle_offer.offer_fee_share,
+        get_settle_anchor_script_pubkeys(signed_channel, false),
+    )?;
+
     let mut new_state = SignedChannelState::SettledReceived {
         own_payout: settle_offer.counter_payout,
         counter_payout: total_collateral - settle_offer.counter_payout,
         counter_next_per_update_point: settle_offer.next_per_update_point,
+        offer_fee_share: settle_offer.offer_fee_share,
         keys_id,
     };
 
@@ -653,11 +706,12 @@ where
     SP::Target: ContractSignerProvider,
     T::Target: Time,
 {
-    let (own_payout, counter_next_per_update_point, counter_payout, keys_id) =
+    let (own_payout, counter_next_per_update_point, counter_payout, offer_fee_share, keys_id) =
         if let SignedChannelState::SettledReceived {
             own_payout,
             counter_next_per_update_point,
             counter_payout,
+            offer_fee_share,
             keys_id,
         } = channel.state
         {
@@ -665,6 +719,7 @@ where
                 own_payout,
                 counter_next_per_update_point,
                 counter_payout,
+                offer_fee_share,
                 keys_id,
             )
         } else {
@@ -683,12 +738,6 @@ where
 
     let own_next_per_update_point = PublicKey::from_secret_key(secp, &per_update_secret);
 
-    let total_collateral = channel.counter_params.collateral + channel.own_params.collateral;
-    //Todo(tibo): compute fee for settle transaction.
-    let fee_remainder = 0; //channel.fund_tx.output[channel.fund_output_index].value - total_collateral;
-    let final_offer_payout = total_collateral - own_payout + fee_remainder / 2;
-    let final_accept_payout = own_payout + fee_remainder / 2;
-
     let fund_tx = &channel.fund_tx;
     let fund_vout = channel.fund_output_index;
     let funding_script_pubkey = &channel.fund_script_pubkey;
@@ -705,12 +754,15 @@ where
         &channel.counter_points,
         &channel.own_points,
         &counter_next_per_update_point,
-        final_offer_payout,
-        final_accept_payout,
+        counter_payout,
+        own_payout,
         csv_timelock,
         lock_time,
         None,
+        false,
         channel.fee_rate_per_vb,
+        offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, false),
     )?;
 
     chain_monitor.lock().unwrap().add_tx(
@@ -760,13 +812,19 @@ where
     SP::Target: ContractSignerProvider,
     T::Target: Time,
 {
-    let (counter_payout, next_per_update_point, keys_id) = match channel.state {
+    let (counter_payout, next_per_update_point, offer_fee_share, keys_id) = match channel.state {
         SignedChannelState::SettledOffered {
             counter_payout,
             next_per_update_point,
+            offer_fee_share,
             keys_id,
             ..
-        } => (counter_payout, next_per_update_point, keys_id),
+        } => (
+            counter_payout,
+            next_per_update_point,
+            offer_fee_share,
+            keys_id,
+        ),
         _ => {
             return Err(Error::InvalidState(
                 "Signed channel was not in SettledOffered state as expected.".to_string(),
@@ -775,10 +833,6 @@ where
     };
 
     let total_collateral = channel.counter_params.collateral + channel.own_params.collateral;
-    //Todo(tibo): compute fee for settle transaction.
-    let fee_remainder = 0; //channel.fund_tx.output[channel.fund_output_index].value - total_collateral;
-    let final_offer_payout = total_collateral - counter_payout + fee_remainder / 2;
-    let final_accept_payout = counter_payout + fee_remainder / 2;
 
     let fund_tx = &channel.fund_tx;
     let fund_vout = channel.fund_output_index;
@@ -796,15 +850,18 @@ where
         &channel.own_points,
         &channel.counter_points,
         &settle_channel_accept.next_per_update_point,
-        final_offer_payout,
-        final_accept_payout,
+        total_collateral - counter_payout,
+        counter_payout,
         csv_timelock,
         lock_time,
         Some((
             &settle_channel_accept.settle_adaptor_signature,
             channel.counter_params.fund_pubkey,
         )),
+        true,
         channel.fee_rate_per_vb,
+        offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, true),
     )?;
 
     chain_monitor.lock().unwrap().add_tx(
@@ -1065,6 +1122,7 @@ pub fn renew_offer<C: Signing, SP: Deref, T: Deref, X: ContractSigner>(
     refund_delay: u32,
     peer_timeout: u64,
     cet_nsequence: u32,
+    offer_fee_share: u8,
     signer_provider: &SP,
     time: &T,
 ) -> Result<(RenewOffer, OfferedContract), Error>
@@ -1073,6 +1131,12 @@ where
     T::Target: Time,
 {
     // Validity checks.
+    check_channel_contract_input(contract_input)?;
+    if contract_input.anchor_outputs != signed_channel.anchor_outputs {
+        return Err(Error::InvalidParameters(
+            "Anchor outputs of the contract must match those of the channel.".to_string(),
+        ));
+    }
     match &signed_channel.state {
         SignedChannelState::Established {
             total_collateral, ..
@@ -1131,6 +1195,8 @@ where
 
     offered_contract.fee_rate_per_vb = signed_channel.fee_rate_per_vb;
 
+    check_renewal_fee_split(signed_channel, &offered_contract, offer_fee_share)?;
+
     let per_update_seed =
         signer_provider.get_secret_key_for_pubkey(&signed_channel.own_per_update_seed)?;
 
@@ -1145,6 +1211,7 @@ where
     let mut state = SignedChannelState::RenewOffered {
         offered_contract_id: offered_contract.id,
         offer_next_per_update_point: next_per_update_point,
+        offer_fee_share,
         is_offer: true,
         counter_payout,
         timeout: time.unix_time_now() + peer_timeout,
@@ -1163,6 +1230,7 @@ where
         cet_locktime: offered_contract.cet_locktime,
         refund_locktime: offered_contract.refund_locktime,
         cet_nsequence,
+        offer_fee_share,
     };
 
     Ok((msg, offered_contract))
@@ -1175,6 +1243,7 @@ pub fn on_renew_offer<T: Deref>(
     signed_channel: &mut SignedChannel,
     renew_offer: &RenewOffer,
     peer_timeout: u64,
+    min_counter_offer_fee_share: u8,
     time: &T,
 ) -> Result<OfferedContract, Error>
 where
@@ -1190,6 +1259,14 @@ where
         ));
     };
 
+    if renew_offer.counter_payout
+        > signed_channel.own_params.collateral + signed_channel.counter_params.collateral
+    {
+        return Err(Error::InvalidParameters(
+            "Proposed renew offer payout greater than total collateral".to_string(),
+        ));
+    }
+
     let offered_contract = OfferedContract {
         id: renew_offer.temporary_contract_id,
         is_offer_party: false,
@@ -1205,13 +1282,31 @@ where
         fee_rate_per_vb: signed_channel.fee_rate_per_vb,
         cet_locktime: renew_offer.cet_locktime,
         refund_locktime: renew_offer.refund_locktime,
+        anchor_outputs: signed_channel.anchor_outputs,
+        batch_id: None,
+        single_funded: false,
+        fee_payer: None,
+        replaced_contract_id: None,
+        rolled_over_contract_id: None,
+        offer_expiry: None,
+        funding_output_type: FundingOutputType::P2wsh,
+        taproot_funding_nonces: None,
+        taproot_secret_nonces: None,
         keys_id,
     };
 
+    check_counter_fee_share(renew_offer.offer_fee_share, min_counter_offer_fee_share)?;
+    check_renewal_fee_split(
+        signed_channel,
+        &offered_contract,
+        renew_offer.offer_fee_share,
+    )?;
+
     let mut state = SignedChannelState::RenewOffered {
         offered_contract_id: offered_contract.id,
         counter_payout: renew_offer.counter_payout,
         offer_next_per_update_point: renew_offer.next_per_update_point,
+        offer_fee_share: renew_offer.offer_fee_share,
         is_offer: false,
         timeout: time.unix_time_now() + peer_timeout,
         keys_id,
@@ -1241,19 +1336,26 @@ where
     SP::Target: ContractSignerProvider,
     T::Target: Time,
 {
-    let (offer_next_per_update_point, own_payout, keys_id) = match signed_channel.state {
-        SignedChannelState::RenewOffered {
-            offer_next_per_update_point,
-            counter_payout,
-            keys_id,
-            ..
-        } => (offer_next_per_update_point, counter_payout, keys_id),
-        _ => {
-            return Err(Error::InvalidState(
-                "Signed channel was not in SettledOffered state as expected.".to_string(),
-            ))
-        }
-    };
+    let (offer_next_per_update_point, own_payout, offer_fee_share, keys_id) =
+        match signed_channel.state {
+            SignedChannelState::RenewOffered {
+                offer_next_per_update_point,
+                counter_payout,
+                offer_fee_share,
+                keys_id,
+                ..
+            } => (
+                offer_next_per_update_point,
+                counter_payout,
+                offer_fee_share,
+                keys_id,
+            ),
+            _ => {
+                return Err(Error::InvalidState(
+                    "Signed channel was not in SettledOffered state as expected.".to_string(),
+                ))
+            }
+        };
 
     let own_base_secret_key =
         signer_provider.get_secret_key_for_pubkey(&signed_channel.own_points.own_basepoint)?;
@@ -1297,6 +1399,8 @@ where
         offered_contract.fee_rate_per_vb,
         0,
         Sequence(cet_nsequence),
+        signed_channel.anchor_outputs,
+        offer_fee_share,
     )?;
 
     let own_secret_key = derive_private_key(secp, &accept_per_update_point, &own_base_secret_key);
@@ -1329,7 +1433,7 @@ where
         channel_id: signed_channel.channel_id,
         next_per_update_point: accept_per_update_point,
         cet_adaptor_signatures: (&adaptor_sigs as &[_]).into(),
-        refund_signature: accepted_contract.accept_refund_signature,
+        refund_signature: accepted_contract.get_accept_refund_signature()?,
     };
 
     Ok((accepted_contract, renew_accept))
@@ -1339,7 +1443,7 @@ where
 /// [`RenewAccept`] message, verifying the message and updating the state of the
 /// channel and associated contract the same time. Expects the channel to be in
 /// [`SignedChannelState::RenewOffered`] state.
-pub fn verify_renew_accept_and_confirm<W: Deref, SP: Deref, X: ContractSigner, T: Deref>(
+pub async fn verify_renew_accept_and_confirm<W: Deref, SP: Deref, X: ContractSigner, T: Deref>(
     secp: &Secp256k1<All>,
     renew_accept: &RenewAccept,
     signed_channel: &mut SignedChannel,
@@ -1351,17 +1455,18 @@ pub fn verify_renew_accept_and_confirm<W: Deref, SP: Deref, X: ContractSigner, T
     time: &T,
 ) -> Result<(SignedContract, RenewConfirm), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
     T::Target: Time,
 {
     let own_base_secret_key =
         signer_provider.get_secret_key_for_pubkey(&signed_channel.own_points.own_basepoint)?;
 
-    let (&offer_per_update_point, &keys_id) = get_signed_channel_state!(
+    let (&offer_per_update_point, &offer_fee_share, &keys_id) = get_signed_channel_state!(
         signed_channel,
         RenewOffered,
         offer_next_per_update_point,
+        offer_fee_share,
         keys_id
     )?;
 
@@ -1399,6 +1504,8 @@ where
         offered_contract.fee_rate_per_vb,
         0,
         Sequence(cet_nsequence),
+        signed_channel.anchor_outputs,
+        offer_fee_share,
     )?;
 
     let offer_own_sk = derive_private_key(secp, &offer_per_update_point, &own_base_secret_key);
@@ -1418,7 +1525,8 @@ where
         Some(accept_revoke_params.own_pk.inner),
         &dlc_transactions,
         Some(signed_channel.channel_id),
-    )?;
+    )
+    .await?;
 
     let own_buffer_adaptor_signature = get_tx_adaptor_signature(
         secp,
@@ -1448,7 +1556,7 @@ where
         channel_id: signed_channel.channel_id,
         buffer_adaptor_signature: own_buffer_adaptor_signature,
         cet_adaptor_signatures: (&cet_adaptor_signatures as &[_]).into(),
-        refund_signature: signed_contract.offer_refund_signature,
+        refund_signature: signed_contract.get_offer_refund_signature()?,
     };
 
     Ok((signed_contract, renew_confirm))
@@ -1459,7 +1567,7 @@ where
 /// channel and associated contract the same time. Expects the channel to be in
 /// [`SignedChannelState::RenewAccepted`] state.
 ///
-pub(crate) fn verify_renew_confirm_and_finalize<S: Deref, T: Deref, W: Deref>(
+pub(crate) async fn verify_renew_confirm_and_finalize<S: Deref, T: Deref, W: Deref>(
     secp: &Secp256k1<All>,
     signed_channel: &mut SignedChannel,
     accepted_contract: &AcceptedContract,
@@ -1473,7 +1581,7 @@ pub(crate) fn verify_renew_confirm_and_finalize<S: Deref, T: Deref, W: Deref>(
 where
     T::Target: Time,
     S::Target: ContractSignerProvider,
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
 {
     let (
         &offer_per_update_point,
@@ -1520,12 +1628,14 @@ where
         &FundingSignatures {
             funding_signatures: Vec::new(),
         },
+        None,
         buffer_transaction.output[0].value,
         Some(buffer_script_pubkey),
         Some(counter_own_pk),
         wallet,
         Some(signed_channel.channel_id),
-    )?;
+    )
+    .await?;
 
     let prev_offer_per_update_point = signed_channel.counter_per_update_point;
     signed_channel.counter_per_update_point = offer_per_update_point;
@@ -1769,6 +1879,8 @@ pub fn offer_collaborative_close<C: Signing, SP: Deref, T: Deref>(
     secp: &Secp256k1<C>,
     signed_channel: &mut SignedChannel,
     counter_payout: u64,
+    offer_fee_share: u8,
+    peer_timeout: u64,
     signer_provider: &SP,
     time: &T,
 ) -> Result<(CollaborativeCloseOffer, Transaction), Error>
@@ -1799,7 +1911,9 @@ where
             vout: signed_channel.fund_output_index as u32,
         },
         fund_output_value,
-    );
+        signed_channel.fee_rate_per_vb,
+        offer_fee_share,
+    )?;
 
     let keys_id = signed_channel
         .keys_id()
@@ -1819,7 +1933,8 @@ where
         counter_payout,
         offer_signature: close_signature,
         close_tx: close_tx.clone(),
-        timeout: time.unix_time_now() + super::manager::PEER_TIMEOUT,
+        offer_fee_share,
+        timeout: time.unix_time_now() + peer_timeout,
         keys_id: signed_channel
             .keys_id()
             .ok_or(Error::InvalidState("No keys_id available".to_string()))?,
@@ -1832,16 +1947,20 @@ where
             channel_id: signed_channel.channel_id,
             counter_payout,
             close_signature,
+            offer_fee_share,
         },
         close_tx,
     ))
 }
 
 /// Validates the given [`CollaborativeCloseOffer`] and updates the state of the
-/// channel.
-pub fn on_collaborative_close_offer<T: Deref>(
+/// channel. The offer is rejected if the share of the closing transaction fee
+/// paid by the counter party is lower than `min_counter_offer_fee_share`.
+pub fn on_collaborative_close_offer<C: Verification, T: Deref>(
+    secp: &Secp256k1<C>,
     signed_channel: &mut SignedChannel,
     close_offer: &CollaborativeCloseOffer,
+    min_counter_offer_fee_share: u8,
     peer_timeout: u64,
     time: &T,
 ) -> Result<(), Error>
@@ -1861,6 +1980,8 @@ where
         ));
     }
 
+    check_counter_fee_share(close_offer.offer_fee_share, min_counter_offer_fee_share)?;
+
     let offer_payout = total_collateral - close_offer.counter_payout;
     let fund_output_value = signed_channel.fund_tx.output[signed_channel.fund_output_index].value;
 
@@ -1874,12 +1995,25 @@ where
             vout: signed_channel.fund_output_index as u32,
         },
         fund_output_value,
-    );
+        signed_channel.fee_rate_per_vb,
+        close_offer.offer_fee_share,
+    )?;
+
+    dlc::verify_tx_input_sig(
+        secp,
+        &close_offer.close_signature,
+        &close_tx,
+        0,
+        &signed_channel.fund_script_pubkey,
+        fund_output_value,
+        &signed_channel.counter_params.fund_pubkey,
+    )?;
 
     let mut state = SignedChannelState::CollaborativeCloseOffered {
         counter_payout: close_offer.counter_payout,
         offer_signature: close_offer.close_signature,
         close_tx,
+        offer_fee_share: close_offer.offer_fee_share,
         timeout: time.unix_time_now() + peer_timeout,
         keys_id: signed_channel
             .keys_id()
@@ -1935,123 +2069,1443 @@ where
     Ok((close_tx, channel))
 }
 
-fn get_settle_tx_and_adaptor_sig(
+/// Creates a [`SpliceOffer`] message to change the balance of the local party
+/// in the given channel to `offer_balance`, updating the state of the channel
+/// at the same time. If the new balance is greater than the current one, the
+/// difference is added to the channel using inputs from the wallet, otherwise
+/// it is paid out of the channel to a change address of the wallet. The
+/// balance of the counter party is left unchanged. Expects the channel to be
+/// in [`SignedChannelState::Settled`] state.
+#[allow(clippy::too_many_arguments)]
+pub async fn splice_channel_offer<W: Deref, SP: Deref, B: Deref, T: Deref>(
     secp: &Secp256k1<All>,
-    own_next_per_update_point: &PublicKey,
-    fund_tx: &Transaction,
-    fund_vout: usize,
-    funding_script_pubkey: &Script,
-    own_fund_sk: &SecretKey,
-    offer_points: &PartyBasePoints,
-    accept_points: &PartyBasePoints,
-    counter_per_update_point: &PublicKey,
-    offer_payout: u64,
-    accept_payout: u64,
+    channel: &mut SignedChannel,
+    offer_balance: u64,
+    fee_rate_per_vb: u64,
+    offer_fee_share: u8,
     csv_timelock: u32,
     lock_time: u32,
-    counter_adaptor_signature: Option<(&EcdsaAdaptorSignature, PublicKey)>,
-    fee_rate_per_vb: u64,
-) -> Result<(Transaction, EcdsaAdaptorSignature), Error> {
-    let is_offer = counter_adaptor_signature.is_some();
-    let (offer_per_update_point, accept_per_update_point) = if is_offer {
-        (own_next_per_update_point, counter_per_update_point)
+    peer_timeout: u64,
+    wallet: &W,
+    signer_provider: &SP,
+    blockchain: &B,
+    time: &T,
+) -> Result<SpliceOffer, Error>
+where
+    W::Target: AsyncWallet,
+    SP::Target: ContractSignerProvider,
+    B::Target: AsyncBlockchain,
+    T::Target: Time,
+{
+    let (&own_payout, &counter_payout, &keys_id) =
+        get_signed_channel_state!(channel, Settled, own_payout, counter_payout, keys_id)?;
+
+    if offer_balance == own_payout {
+        return Err(Error::InvalidParameters(
+            "Splice offer does not change the balance of the channel.".to_string(),
+        ));
+    }
+
+    // The fee reserve of the channel is kept unchanged.
+    let new_fund_output_value =
+        channel.fund_tx.output[channel.fund_output_index].value + offer_balance - own_payout;
+
+    check_fee_split(
+        channel.fee_rate_per_vb,
+        new_fund_output_value,
+        offer_balance,
+        counter_payout,
+        offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, true),
+    )?;
+
+    let contract_signer = signer_provider.derive_contract_signer(keys_id)?;
+
+    let (funding_inputs, change_spk) = if offer_balance > own_payout {
+        let (party_params, funding_inputs) = crate::utils::get_party_params(
+            secp,
+            offer_balance - own_payout + dlc::channel::get_splice_tx_base_fee(fee_rate_per_vb)?,
+            fee_rate_per_vb,
+            &DlcTransactionsOptions::default(),
+            wallet,
+            &contract_signer,
+            blockchain,
+        )
+        .await?;
+        (funding_inputs, party_params.change_script_pubkey)
     } else {
-        (counter_per_update_point, own_next_per_update_point)
+        (
+            Vec::new(),
+            wallet.get_new_change_address().await?.script_pubkey(),
+        )
     };
 
-    let fund_tx_in = TxIn {
-        previous_output: bitcoin::OutPoint {
-            txid: fund_tx.txid(),
-            vout: fund_vout as u32,
-        },
-        script_sig: ScriptBuf::new(),
-        sequence: Sequence::MAX,
-        witness: Witness::default(),
-    };
+    let splice_tx = get_splice_tx(
+        channel,
+        new_fund_output_value,
+        &funding_inputs,
+        &change_spk,
+        fee_rate_per_vb,
+    )?;
 
-    let offer_revoke_params = offer_points.get_revokable_params(
-        secp,
-        &accept_points.revocation_basepoint,
-        offer_per_update_point,
-    );
+    if offer_balance < own_payout && splice_tx.output.len() < 2 {
+        return Err(Error::InvalidParameters(
+            "Spliced out amount cannot cover the splice transaction fee.".to_string(),
+        ));
+    }
 
-    let accept_revoke_params = accept_points.get_revokable_params(
+    let (settle_tx, own_settle_adaptor_signature) = get_settle_tx_and_adaptor_sig(
         secp,
-        &offer_points.revocation_basepoint,
-        accept_per_update_point,
-    );
-
-    let settle_tx = dlc::channel::create_settle_transaction(
-        &fund_tx_in,
-        &offer_revoke_params,
-        &accept_revoke_params,
-        offer_payout,
-        accept_payout,
+        &channel.own_per_update_point,
+        &splice_tx,
+        0,
+        &channel.fund_script_pubkey,
+        &contract_signer.get_secret_key()?,
+        &channel.own_points,
+        &channel.counter_points,
+        &channel.counter_per_update_point,
+        offer_balance,
+        counter_payout,
         csv_timelock,
         lock_time,
-        fund_tx.output[fund_vout].value,
-        fee_rate_per_vb,
+        None,
+        true,
+        channel.fee_rate_per_vb,
+        offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, true),
     )?;
 
-    if let Some((adaptor_sig, fund_pk)) = counter_adaptor_signature {
-        verify_tx_adaptor_signature(
-            secp,
-            &settle_tx,
-            fund_tx.output[fund_vout].value,
-            funding_script_pubkey,
-            &fund_pk,
-            &offer_revoke_params.publish_pk.inner,
-            adaptor_sig,
-        )?;
-    }
-
-    let counter_pk = if is_offer {
-        accept_revoke_params.publish_pk.inner
-    } else {
-        offer_revoke_params.publish_pk.inner
+    let mut state = SignedChannelState::SpliceOffered {
+        splice_tx,
+        settle_tx,
+        own_settle_adaptor_signature,
+        own_payout: offer_balance,
+        counter_payout,
+        funding_inputs: funding_inputs.clone(),
+        timeout: time.unix_time_now() + peer_timeout,
+        keys_id,
     };
 
-    let settle_adaptor_signature = dlc::channel::get_tx_adaptor_signature(
-        secp,
-        &settle_tx,
-        fund_tx.output[fund_vout].value,
-        funding_script_pubkey,
-        own_fund_sk,
-        &counter_pk,
-    )?;
+    std::mem::swap(&mut channel.state, &mut state);
+    channel.roll_back_state = Some(state);
 
-    Ok((settle_tx, settle_adaptor_signature))
+    Ok(SpliceOffer {
+        channel_id: channel.channel_id,
+        offer_balance,
+        funding_inputs,
+        change_spk,
+        fee_rate_per_vb,
+        offer_fee_share,
+        settle_adaptor_signature: own_settle_adaptor_signature,
+    })
 }
 
-/// Update the state of the channel if currently in a state that can be rejected.
-pub fn on_reject(signed_channel: &mut SignedChannel) -> Result<(), Error> {
-    if let SignedChannelState::Established { .. } | SignedChannelState::Settled { .. } =
-        signed_channel.state
-    {
-        return Ok(());
+/// Validates the given [`SpliceOffer`] and updates the state of the channel.
+/// The offer is rejected if the share of the settle transaction fee paid by the
+/// counter party is lower than `min_counter_offer_fee_share`.
+#[allow(clippy::too_many_arguments)]
+pub fn on_splice_offer<SP: Deref>(
+    secp: &Secp256k1<All>,
+    channel: &mut SignedChannel,
+    splice_offer: &SpliceOffer,
+    min_counter_offer_fee_share: u8,
+    csv_timelock: u32,
+    lock_time: u32,
+    signer_provider: &SP,
+) -> Result<(), Error>
+where
+    SP::Target: ContractSignerProvider,
+{
+    let (&own_payout, &counter_payout, &keys_id) =
+        get_signed_channel_state!(channel, Settled, own_payout, counter_payout, keys_id)?;
+
+    if splice_offer.offer_balance == counter_payout {
+        return Err(Error::InvalidParameters(
+            "Received splice offer that does not change the balance of the channel.".to_string(),
+        ));
     }
 
-    let mut rollback = false;
+    check_counter_fee_share(splice_offer.offer_fee_share, min_counter_offer_fee_share)?;
 
-    if let SignedChannelState::RenewOffered { is_offer, .. } = signed_channel.state {
-        rollback = is_offer;
-    }
+    let new_fund_output_value = channel.fund_tx.output[channel.fund_output_index].value
+        + splice_offer.offer_balance
+        - counter_payout;
 
-    if let SignedChannelState::SettledOffered { .. } = signed_channel.state {
-        rollback = true;
-    }
+    check_fee_split(
+        channel.fee_rate_per_vb,
+        new_fund_output_value,
+        splice_offer.offer_balance,
+        own_payout,
+        splice_offer.offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, false),
+    )?;
 
-    if rollback {
-        signed_channel.state = signed_channel
-            .roll_back_state
-            .take()
-            .expect("to have a rollback state.");
-        Ok(())
-    } else {
-        Err(Error::InvalidState(
-            "Not in a state adequate to receive a reject message".to_string(),
-        ))
-    }
+    let splice_tx = get_splice_tx(
+        channel,
+        new_fund_output_value,
+        &splice_offer.funding_inputs,
+        &splice_offer.change_spk,
+        splice_offer.fee_rate_per_vb,
+    )?;
+
+    let contract_signer = signer_provider.derive_contract_signer(keys_id)?;
+
+    let (settle_tx, _) = get_settle_tx_and_adaptor_sig(
+        secp,
+        &channel.own_per_update_point,
+        &splice_tx,
+        0,
+        &channel.fund_script_pubkey,
+        &contract_signer.get_secret_key()?,
+        &channel.counter_points,
+        &channel.own_points,
+        &channel.counter_per_update_point,
+        splice_offer.offer_balance,
+        own_payout,
+        csv_timelock,
+        lock_time,
+        Some((
+            &splice_offer.settle_adaptor_signature,
+            channel.counter_params.fund_pubkey,
+        )),
+        false,
+        channel.fee_rate_per_vb,
+        splice_offer.offer_fee_share,
+        get_settle_anchor_script_pubkeys(channel, false),
+    )?;
+
+    let mut state = SignedChannelState::SpliceReceived {
+        splice_tx,
+        settle_tx,
+        counter_settle_adaptor_signature: splice_offer.settle_adaptor_signature,
+        own_payout,
+        counter_payout: splice_offer.offer_balance,
+        keys_id,
+    };
+
+    std::mem::swap(&mut channel.state, &mut state);
+    channel.roll_back_state = Some(state);
+
+    Ok(())
+}
+
+/// Creates a [`SpliceAccept`] message from the given [`SignedChannel`],
+/// signing the settle transaction and the fund output input of the splice
+/// transaction, and updates the state of the channel. Expects the channel to be
+/// in [`SignedChannelState::SpliceReceived`] state.
+pub fn splice_channel_accept<SP: Deref, T: Deref>(
+    secp: &Secp256k1<All>,
+    channel: &mut SignedChannel,
+    peer_timeout: u64,
+    signer_provider: &SP,
+    time: &T,
+    chain_monitor: &Mutex<ChainMonitor>,
+) -> Result<SpliceAccept, Error>
+where
+    SP::Target: ContractSignerProvider,
+    T::Target: Time,
+{
+    let (
+        &counter_settle_adaptor_signature,
+        &own_payout,
+        &counter_payout,
+        &keys_id,
+        splice_tx,
+        settle_tx,
+    ) = get_signed_channel_state!(
+        channel,
+        SpliceReceived,
+        counter_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id | splice_tx,
+        settle_tx
+    )?;
+
+    let own_fund_sk = signer_provider
+        .derive_contract_signer(keys_id)?
+        .get_secret_key()?;
+
+    let counter_revoke_params = channel.counter_points.get_revokable_params(
+        secp,
+        &channel.own_points.revocation_basepoint,
+        &channel.counter_per_update_point,
+    );
+
+    let own_settle_adaptor_signature = get_tx_adaptor_signature(
+        secp,
+        settle_tx,
+        splice_tx.output[0].value,
+        &channel.fund_script_pubkey,
+        &own_fund_sk,
+        &counter_revoke_params.publish_pk.inner,
+    )?;
+
+    let splice_signature = dlc::util::get_raw_sig_for_tx_input(
+        secp,
+        splice_tx,
+        0,
+        &channel.fund_script_pubkey,
+        channel.fund_tx.output[channel.fund_output_index].value,
+        &own_fund_sk,
+    )?;
+
+    chain_monitor.lock().unwrap().add_tx(
+        splice_tx.txid(),
+        ChannelInfo {
+            channel_id: channel.channel_id,
+            tx_type: TxType::SpliceTx,
+        },
+    );
+
+    channel.state = SignedChannelState::SpliceAccepted {
+        splice_tx: splice_tx.clone(),
+        settle_tx: settle_tx.clone(),
+        counter_settle_adaptor_signature,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        timeout: time.unix_time_now() + peer_timeout,
+        keys_id,
+    };
+
+    Ok(SpliceAccept {
+        channel_id: channel.channel_id,
+        settle_adaptor_signature: own_settle_adaptor_signature,
+        splice_signature,
+    })
+}
+
+/// Creates a [`SpliceConfirm`] message from the given [`SignedChannel`] and
+/// [`SpliceAccept`] message, verifying the content of the message and updating
+/// the state of the channel at the same time. Returns the fully signed splice
+/// transaction to be broadcast together with the message. Expects the channel
+/// to be in [`SignedChannelState::SpliceOffered`] state.
+pub async fn splice_channel_confirm<W: Deref, SP: Deref>(
+    secp: &Secp256k1<All>,
+    channel: &mut SignedChannel,
+    splice_accept: &SpliceAccept,
+    wallet: &W,
+    signer_provider: &SP,
+    chain_monitor: &Mutex<ChainMonitor>,
+) -> Result<(SpliceConfirm, Transaction), Error>
+where
+    W::Target: AsyncWallet,
+    SP::Target: ContractSignerProvider,
+{
+    let (
+        &own_settle_adaptor_signature,
+        &own_payout,
+        &counter_payout,
+        &keys_id,
+        splice_tx,
+        settle_tx,
+        funding_inputs,
+    ) = get_signed_channel_state!(
+        channel,
+        SpliceOffered,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id | splice_tx,
+        settle_tx,
+        funding_inputs
+    )?;
+
+    let fund_output_value = channel.fund_tx.output[channel.fund_output_index].value;
+
+    let own_revoke_params = channel.own_points.get_revokable_params(
+        secp,
+        &channel.counter_points.revocation_basepoint,
+        &channel.own_per_update_point,
+    );
+
+    verify_tx_adaptor_signature(
+        secp,
+        settle_tx,
+        splice_tx.output[0].value,
+        &channel.fund_script_pubkey,
+        &channel.counter_params.fund_pubkey,
+        &own_revoke_params.publish_pk.inner,
+        &splice_accept.settle_adaptor_signature,
+    )?;
+
+    dlc::verify_tx_input_sig(
+        secp,
+        &splice_accept.splice_signature,
+        splice_tx,
+        0,
+        &channel.fund_script_pubkey,
+        fund_output_value,
+        &channel.counter_params.fund_pubkey,
+    )?;
+
+    let own_fund_sk = signer_provider
+        .derive_contract_signer(keys_id)?
+        .get_secret_key()?;
+
+    let splice_signature = dlc::util::get_raw_sig_for_tx_input(
+        secp,
+        splice_tx,
+        0,
+        &channel.fund_script_pubkey,
+        fund_output_value,
+        &own_fund_sk,
+    )?;
+
+    let mut signed_splice_tx = splice_tx.clone();
+
+    dlc::util::sign_multi_sig_input(
+        secp,
+        &mut signed_splice_tx,
+        &splice_accept.splice_signature,
+        &channel.counter_params.fund_pubkey,
+        &own_fund_sk,
+        &channel.fund_script_pubkey,
+        fund_output_value,
+        0,
+    )?;
+
+    let mut psbt = PartiallySignedTransaction::from_unsigned_tx(splice_tx.clone())
+        .map_err(|_| Error::InvalidState("Tried to create PSBT from signed tx".to_string()))?;
+
+    // The inputs of the local party follow the fund output input, ordered by
+    // serial id.
+    let mut ordered_inputs = funding_inputs.iter().collect::<Vec<_>>();
+    ordered_inputs.sort_by_key(|x| x.input_serial_id);
+
+    let mut funding_signatures = Vec::new();
+    for (i, funding_input) in ordered_inputs.into_iter().enumerate() {
+        let input_index = i + 1;
+        let prev_tx = Transaction::consensus_decode(&mut funding_input.prev_tx.as_slice())
+            .map_err(|_| {
+                Error::InvalidParameters(
+                    "Could not decode funding input previous tx parameter".to_string(),
+                )
+            })?;
+        let vout = funding_input.prev_tx_vout;
+        let tx_out = prev_tx.output.get(vout as usize).ok_or_else(|| {
+            Error::InvalidParameters(format!("Previous tx output not found at index {}", vout))
+        })?;
+        psbt.inputs[input_index].witness_utxo = Some(tx_out.clone());
+        psbt.inputs[input_index].redeem_script = Some(funding_input.redeem_script.clone());
+
+        wallet.sign_psbt_input(&mut psbt, input_index).await?;
+
+        let witness = psbt.inputs[input_index]
+            .final_script_witness
+            .clone()
+            .ok_or(Error::InvalidParameters(
+                "No witness from signing psbt input".to_string(),
+            ))?;
+
+        funding_signatures.push(FundingSignature {
+            witness_elements: witness
+                .iter()
+                .map(|z| WitnessElement {
+                    witness: z.to_vec(),
+                })
+                .collect(),
+        });
+        signed_splice_tx.input[input_index].witness = witness;
+    }
+
+    chain_monitor.lock().unwrap().add_tx(
+        signed_splice_tx.txid(),
+        ChannelInfo {
+            channel_id: channel.channel_id,
+            tx_type: TxType::SpliceTx,
+        },
+    );
+
+    channel.state = SignedChannelState::SpliceConfirmed {
+        splice_tx: signed_splice_tx.clone(),
+        settle_tx: settle_tx.clone(),
+        counter_settle_adaptor_signature: splice_accept.settle_adaptor_signature,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id,
+    };
+
+    let msg = SpliceConfirm {
+        channel_id: channel.channel_id,
+        splice_signature,
+        funding_signatures: FundingSignatures { funding_signatures },
+    };
+
+    Ok((msg, signed_splice_tx))
+}
+
+/// Verifies the given [`SpliceConfirm`] message and returns the fully signed
+/// splice transaction to be broadcast, updating the state of the channel at the
+/// same time. Expects the channel to be in
+/// [`SignedChannelState::SpliceAccepted`] state.
+pub fn splice_channel_on_confirm<SP: Deref>(
+    secp: &Secp256k1<All>,
+    channel: &mut SignedChannel,
+    splice_confirm: &SpliceConfirm,
+    signer_provider: &SP,
+) -> Result<Transaction, Error>
+where
+    SP::Target: ContractSignerProvider,
+{
+    let (
+        &counter_settle_adaptor_signature,
+        &own_settle_adaptor_signature,
+        &own_payout,
+        &counter_payout,
+        &keys_id,
+        splice_tx,
+        settle_tx,
+    ) = get_signed_channel_state!(
+        channel,
+        SpliceAccepted,
+        counter_settle_adaptor_signature,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id | splice_tx,
+        settle_tx
+    )?;
+
+    let fund_output_value = channel.fund_tx.output[channel.fund_output_index].value;
+
+    dlc::verify_tx_input_sig(
+        secp,
+        &splice_confirm.splice_signature,
+        splice_tx,
+        0,
+        &channel.fund_script_pubkey,
+        fund_output_value,
+        &channel.counter_params.fund_pubkey,
+    )?;
+
+    let funding_signatures = &splice_confirm.funding_signatures.funding_signatures;
+    if funding_signatures.len() + 1 != splice_tx.input.len() {
+        return Err(Error::InvalidParameters(
+            "Invalid number of funding signatures for the splice transaction.".to_string(),
+        ));
+    }
+
+    let own_fund_sk = signer_provider
+        .derive_contract_signer(keys_id)?
+        .get_secret_key()?;
+
+    let mut signed_splice_tx = splice_tx.clone();
+
+    dlc::util::sign_multi_sig_input(
+        secp,
+        &mut signed_splice_tx,
+        &splice_confirm.splice_signature,
+        &channel.counter_params.fund_pubkey,
+        &own_fund_sk,
+        &channel.fund_script_pubkey,
+        fund_output_value,
+        0,
+    )?;
+
+    for (input, funding_signature) in signed_splice_tx
+        .input
+        .iter_mut()
+        .skip(1)
+        .zip(funding_signatures.iter())
+    {
+        input.witness = Witness::from_slice(
+            &funding_signature
+                .witness_elements
+                .iter()
+                .map(|x| x.witness.clone())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    channel.state = SignedChannelState::SpliceConfirmed {
+        splice_tx: signed_splice_tx.clone(),
+        settle_tx: settle_tx.clone(),
+        counter_settle_adaptor_signature,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id,
+    };
+
+    Ok(signed_splice_tx)
+}
+
+/// Creates a [`Reject`] message and rolls back the state of the channel. Expects
+/// the channel to be in [`SignedChannelState::SpliceReceived`] state.
+pub fn reject_splice_offer(signed_channel: &mut SignedChannel) -> Result<Reject, Error> {
+    get_signed_channel_state!(signed_channel, SpliceReceived,)?;
+
+    signed_channel.state = signed_channel
+        .roll_back_state
+        .take()
+        .expect("to have a rollback state");
+
+    Ok(Reject {
+        channel_id: signed_channel.channel_id,
+    })
+}
+
+/// Updates the given [`SignedChannel`] once its splice transaction is confirmed,
+/// replacing the fund transaction of the channel and setting it back in
+/// [`SignedChannelState::Settled`] state with the new balances. Returns the
+/// balance of the local party in the channel.
+pub(crate) fn on_splice_tx_confirmed(
+    channel: &mut SignedChannel,
+    splice_tx: &Transaction,
+    chain_monitor: &Mutex<ChainMonitor>,
+) -> Result<u64, Error> {
+    let (
+        settle_tx,
+        counter_settle_adaptor_signature,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id,
+    ) = match &channel.state {
+        SignedChannelState::SpliceAccepted {
+            settle_tx,
+            counter_settle_adaptor_signature,
+            own_settle_adaptor_signature,
+            own_payout,
+            counter_payout,
+            keys_id,
+            ..
+        }
+        | SignedChannelState::SpliceConfirmed {
+            settle_tx,
+            counter_settle_adaptor_signature,
+            own_settle_adaptor_signature,
+            own_payout,
+            counter_payout,
+            keys_id,
+            ..
+        } => (
+            settle_tx.clone(),
+            *counter_settle_adaptor_signature,
+            *own_settle_adaptor_signature,
+            *own_payout,
+            *counter_payout,
+            *keys_id,
+        ),
+        _ => {
+            return Err(Error::InvalidState(format!(
+                "Expected channel in splice state, got {}.",
+                channel.state
+            )))
+        }
+    };
+
+    let mut chain_monitor = chain_monitor.lock().unwrap();
+
+    if let Some(SignedChannelState::Settled {
+        settle_tx: prev_settle_tx,
+        ..
+    }) = &channel.roll_back_state
+    {
+        chain_monitor.remove_tx(&prev_settle_tx.txid());
+    }
+    chain_monitor.remove_tx(&splice_tx.txid());
+    chain_monitor.add_tx(
+        settle_tx.txid(),
+        ChannelInfo {
+            channel_id: channel.channel_id,
+            tx_type: TxType::SettleTx,
+        },
+    );
+
+    channel.fund_tx = splice_tx.clone();
+    channel.fund_output_index = 0;
+    channel.own_params.collateral = own_payout;
+    channel.counter_params.collateral = counter_payout;
+    channel.state = SignedChannelState::Settled {
+        settle_tx,
+        counter_settle_adaptor_signature,
+        own_settle_adaptor_signature,
+        own_payout,
+        counter_payout,
+        keys_id,
+    };
+    channel.roll_back_state = None;
+
+    Ok(own_payout)
+}
+
+/// Returns the splice transaction for the given channel, funding a new fund
+/// output of `new_fund_output_value` with the fund output of the channel and
+/// the given funding inputs.
+fn get_splice_tx(
+    channel: &SignedChannel,
+    new_fund_output_value: u64,
+    funding_inputs: &[FundingInput],
+    change_spk: &Script,
+    fee_rate_per_vb: u64,
+) -> Result<Transaction, Error> {
+    let (inputs, input_amount) = crate::conversion_utils::get_tx_input_infos(funding_inputs)?;
+
+    dlc::channel::create_splice_transaction(
+        OutPoint {
+            txid: channel.fund_tx.txid(),
+            vout: channel.fund_output_index as u32,
+        },
+        channel.fund_tx.output[channel.fund_output_index].value,
+        &channel.fund_script_pubkey,
+        new_fund_output_value,
+        &inputs,
+        input_amount,
+        change_spk,
+        fee_rate_per_vb,
+    )
+    .map_err(Error::from)
+}
+
+fn check_counter_fee_share(
+    offer_fee_share: u8,
+    min_counter_offer_fee_share: u8,
+) -> Result<(), Error> {
+    if offer_fee_share > 100 || offer_fee_share < min_counter_offer_fee_share {
+        return Err(Error::InvalidParameters(format!(
+            "Counter party fee share of {}% is not within the accepted bounds.",
+            offer_fee_share
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks that the fee reserve of a channel with the given fund output value can
+/// cover the fee of a settle transaction paying the given balances with the
+/// given fee split, and the anchor outputs paying to `anchor_script_pubkeys` if
+/// provided.
+fn check_fee_split(
+    fee_rate_per_vb: u64,
+    fund_output_value: u64,
+    offer_balance: u64,
+    accept_balance: u64,
+    offer_fee_share: u8,
+    anchor_script_pubkeys: Option<(&Script, &Script)>,
+) -> Result<(), Error> {
+    let fee = dlc::channel::get_settle_tx_fee(fee_rate_per_vb, anchor_script_pubkeys)?;
+    dlc::channel::get_fee_split_payouts(
+        fund_output_value,
+        offer_balance,
+        accept_balance,
+        fee,
+        offer_fee_share,
+    )
+    .map_err(|_| {
+        Error::InvalidParameters(
+            "Channel fee reserve cannot cover the settle transaction fee.".to_string(),
+        )
+    })?;
+
+    Ok(())
+}
+
+/// Checks that the given contract renewing a channel has a single contract info
+/// and that the fee reserve of the channel can cover the fee of the renewed
+/// buffer transaction and CETs for any payout with the given fee split.
+fn check_renewal_fee_split(
+    signed_channel: &SignedChannel,
+    offered_contract: &OfferedContract,
+    offer_fee_share: u8,
+) -> Result<(), Error> {
+    if offered_contract.contract_info.len() != 1 {
+        return Err(Error::InvalidParameters(
+            "Contracts within channels must have a single contract info.".to_string(),
+        ));
+    }
+
+    let fee = dlc::channel::get_renewal_tx_fee(
+        &signed_channel.own_params,
+        &signed_channel.counter_params,
+        signed_channel.fee_rate_per_vb,
+        signed_channel.anchor_outputs,
+    )?;
+    let fund_output_value = signed_channel.fund_tx.output[signed_channel.fund_output_index].value;
+    let total_collateral = offered_contract.total_collateral;
+
+    for (offer_balance, accept_balance) in [(0, total_collateral), (total_collateral, 0)] {
+        dlc::channel::get_fee_split_payouts(
+            fund_output_value,
+            offer_balance,
+            accept_balance,
+            fee,
+            offer_fee_share,
+        )
+        .map_err(|_| {
+            Error::InvalidParameters(
+                "Channel fee reserve cannot cover the renewed transactions fee.".to_string(),
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+fn check_channel_contract_input(contract_input: &ContractInput) -> Result<(), Error> {
+    if contract_input.single_funded {
+        return Err(Error::InvalidParameters(
+            "Single funded contracts are not supported within channels.".to_string(),
+        ));
+    }
+
+    if contract_input.offer_expiry.is_some() {
+        return Err(Error::InvalidParameters(
+            "Offer expiry is not supported for contracts within channels.".to_string(),
+        ));
+    }
+
+    if contract_input.funding_output_type != FundingOutputType::P2wsh {
+        return Err(Error::InvalidParameters(
+            "Taproot funding is not supported within channels.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the script pubkeys of the anchor outputs of the offer and accept
+/// parties of a settle transaction of the given channel, that is their payout
+/// script pubkeys, if the channel uses anchor outputs.
+fn get_settle_anchor_script_pubkeys(
+    channel: &SignedChannel,
+    is_offer: bool,
+) -> Option<(&Script, &Script)> {
+    if !channel.anchor_outputs {
+        return None;
+    }
+
+    let own_script_pubkey = &channel.own_params.payout_script_pubkey;
+    let counter_script_pubkey = &channel.counter_params.payout_script_pubkey;
+    if is_offer {
+        Some((own_script_pubkey, counter_script_pubkey))
+    } else {
+        Some((counter_script_pubkey, own_script_pubkey))
+    }
+}
+
+fn get_settle_tx_and_adaptor_sig(
+    secp: &Secp256k1<All>,
+    own_next_per_update_point: &PublicKey,
+    fund_tx: &Transaction,
+    fund_vout: usize,
+    funding_script_pubkey: &Script,
+    own_fund_sk: &SecretKey,
+    offer_points: &PartyBasePoints,
+    accept_points: &PartyBasePoints,
+    counter_per_update_point: &PublicKey,
+    offer_payout: u64,
+    accept_payout: u64,
+    csv_timelock: u32,
+    lock_time: u32,
+    counter_adaptor_signature: Option<(&EcdsaAdaptorSignature, PublicKey)>,
+    is_offer: bool,
+    fee_rate_per_vb: u64,
+    offer_fee_share: u8,
+    anchor_script_pubkeys: Option<(&Script, &Script)>,
+) -> Result<(Transaction, EcdsaAdaptorSignature), Error> {
+    let (offer_per_update_point, accept_per_update_point) = if is_offer {
+        (own_next_per_update_point, counter_per_update_point)
+    } else {
+        (counter_per_update_point, own_next_per_update_point)
+    };
+
+    let fund_tx_in = TxIn {
+        previous_output: bitcoin::OutPoint {
+            txid: fund_tx.txid(),
+            vout: fund_vout as u32,
+        },
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::default(),
+    };
+
+    let offer_revoke_params = offer_points.get_revokable_params(
+        secp,
+        &accept_points.revocation_basepoint,
+        offer_per_update_point,
+    );
+
+    let accept_revoke_params = accept_points.get_revokable_params(
+        secp,
+        &offer_points.revocation_basepoint,
+        accept_per_update_point,
+    );
+
+    let settle_tx = dlc::channel::create_settle_transaction(
+        &fund_tx_in,
+        &offer_revoke_params,
+        &accept_revoke_params,
+        offer_payout,
+        accept_payout,
+        csv_timelock,
+        lock_time,
+        fund_tx.output[fund_vout].value,
+        fee_rate_per_vb,
+        offer_fee_share,
+        anchor_script_pubkeys,
+    )?;
+
+    let (own_pk, counter_pk) = if is_offer {
+        (
+            offer_revoke_params.publish_pk.inner,
+            accept_revoke_params.publish_pk.inner,
+        )
+    } else {
+        (
+            accept_revoke_params.publish_pk.inner,
+            offer_revoke_params.publish_pk.inner,
+        )
+    };
+
+    if let Some((adaptor_sig, fund_pk)) = counter_adaptor_signature {
+        verify_tx_adaptor_signature(
+            secp,
+            &settle_tx,
+            fund_tx.output[fund_vout].value,
+            funding_script_pubkey,
+            &fund_pk,
+            &own_pk,
+            adaptor_sig,
+        )?;
+    }
+
+    let settle_adaptor_signature = dlc::channel::get_tx_adaptor_signature(
+        secp,
+        &settle_tx,
+        fund_tx.output[fund_vout].value,
+        funding_script_pubkey,
+        own_fund_sk,
+        &counter_pk,
+    )?;
+
+    Ok((settle_tx, settle_adaptor_signature))
+}
+
+/// Update the state of the channel if currently in a state that can be rejected.
+pub fn on_reject(signed_channel: &mut SignedChannel) -> Result<(), Error> {
+    if let SignedChannelState::Established { .. } | SignedChannelState::Settled { .. } =
+        signed_channel.state
+    {
+        return Ok(());
+    }
+
+    let mut rollback = false;
+
+    if let SignedChannelState::RenewOffered { is_offer, .. } = signed_channel.state {
+        rollback = is_offer;
+    }
+
+    if let SignedChannelState::SettledOffered { .. } | SignedChannelState::SpliceOffered { .. } =
+        signed_channel.state
+    {
+        rollback = true;
+    }
+
+    if rollback {
+        signed_channel.state = signed_channel
+            .roll_back_state
+            .take()
+            .expect("to have a rollback state.");
+        Ok(())
+    } else {
+        Err(Error::InvalidState(
+            "Not in a state adequate to receive a reject message".to_string(),
+        ))
+    }
+}
+
+/// Returns the step of the channel update protocol at which the local party is
+/// for the given channel, or `None` if the channel is being closed.
+pub fn get_channel_update_state<C: Verification>(
+    secp: &Secp256k1<C>,
+    signed_channel: &SignedChannel,
+) -> Option<ChannelUpdateState> {
+    let update_state = match &signed_channel.state {
+        SignedChannelState::Established { .. } => ChannelUpdateState::Established,
+        SignedChannelState::SettledOffered { .. } => ChannelUpdateState::SettledOffered,
+        SignedChannelState::SettledReceived { .. } => ChannelUpdateState::SettledReceived,
+        SignedChannelState::SettledAccepted { .. } => ChannelUpdateState::SettledAccepted,
+        SignedChannelState::SettledConfirmed { .. } => ChannelUpdateState::SettledConfirmed,
+        SignedChannelState::Settled { .. } => ChannelUpdateState::Settled,
+        SignedChannelState::RenewOffered { is_offer: true, .. } => ChannelUpdateState::RenewOffered,
+        SignedChannelState::RenewOffered {
+            is_offer: false, ..
+        } => ChannelUpdateState::RenewReceived,
+        SignedChannelState::RenewAccepted { .. } => ChannelUpdateState::RenewAccepted,
+        SignedChannelState::RenewConfirmed { .. } => ChannelUpdateState::RenewConfirmed,
+        SignedChannelState::RenewFinalized { .. } => ChannelUpdateState::RenewFinalized,
+        SignedChannelState::CollaborativeCloseOffered {
+            offer_signature,
+            close_tx,
+            ..
+        } => {
+            let fund_output_value =
+                signed_channel.fund_tx.output[signed_channel.fund_output_index].value;
+            if dlc::verify_tx_input_sig(
+                secp,
+                offer_signature,
+                close_tx,
+                0,
+                &signed_channel.fund_script_pubkey,
+                fund_output_value,
+                &signed_channel.own_params.fund_pubkey,
+            )
+            .is_ok()
+            {
+                ChannelUpdateState::CollaborativeCloseOffered
+            } else {
+                ChannelUpdateState::CollaborativeCloseReceived
+            }
+        }
+        SignedChannelState::SpliceOffered { .. } => ChannelUpdateState::SpliceOffered,
+        SignedChannelState::SpliceReceived { .. } => ChannelUpdateState::SpliceReceived,
+        SignedChannelState::SpliceAccepted { .. } => ChannelUpdateState::SpliceAccepted,
+        SignedChannelState::SpliceConfirmed { .. } => ChannelUpdateState::SpliceConfirmed,
+        SignedChannelState::Closing { .. } => return None,
+    };
+
+    Some(update_state)
+}
+
+/// Creates a [`ChannelReestablish`] message for the given [`SignedChannel`], to
+/// be sent to the counter party upon reconnection. Returns `None` if the channel
+/// is being closed.
+pub fn get_channel_reestablish<C: Verification>(
+    secp: &Secp256k1<C>,
+    signed_channel: &SignedChannel,
+) -> Option<ChannelReestablish> {
+    get_channel_update_state(secp, signed_channel).map(|update_state| ChannelReestablish {
+        channel_id: signed_channel.channel_id,
+        update_idx: signed_channel.update_idx,
+        update_state,
+    })
+}
+
+/// Resynchronizes the state of the given [`SignedChannel`] with the one reported
+/// by the counter party in the given [`ChannelReestablish`] message. Depending
+/// on the step of the update protocol at which each party is, the channel is
+/// either rolled back to its last stable state, or the last message sent by the
+/// local party is recreated so that it can be sent again. `contract` is the
+/// contract being renewed when the channel is in the middle of a renewal.
+#[allow(clippy::too_many_arguments)]
+pub fn on_channel_reestablish<SP: Deref, T: Deref>(
+    secp: &Secp256k1<All>,
+    signed_channel: &mut SignedChannel,
+    channel_reestablish: &ChannelReestablish,
+    contract: Option<&Contract>,
+    cet_nsequence: u32,
+    peer_timeout: u64,
+    signer_provider: &SP,
+    time: &T,
+) -> Result<Option<Message>, Error>
+where
+    SP::Target: ContractSignerProvider,
+    T::Target: Time,
+{
+    use ChannelUpdateState::*;
+
+    let own_state = get_channel_update_state(secp, signed_channel).ok_or_else(|| {
+        Error::InvalidState("Cannot reestablish a channel that is being closed.".to_string())
+    })?;
+    let counter_state = channel_reestablish.update_state;
+    let update_idx = signed_channel.update_idx;
+
+    let invalid_state = || {
+        Error::InvalidState(format!(
+            "Cannot reestablish channel in state {:?} at index {} with counter party in state {:?} at index {}.",
+            own_state, update_idx, counter_state, channel_reestablish.update_idx
+        ))
+    };
+
+    if channel_reestablish.update_idx == update_idx + 1 {
+        // The counter party did not receive the last message of an update that
+        // was completed locally, which revealed our previous per update secret.
+        let per_update_seed =
+            signer_provider.get_secret_key_for_pubkey(&signed_channel.own_per_update_seed)?;
+        let prev_per_update_secret = SecretKey::from_slice(&build_commitment_secret(
+            per_update_seed.as_ref(),
+            update_idx + 1,
+        ))?;
+        return match (own_state, counter_state) {
+            (Settled, SettledConfirmed) => Ok(Some(Message::SettleFinalize(SettleFinalize {
+                channel_id: signed_channel.channel_id,
+                prev_per_update_secret,
+            }))),
+            (Established, RenewFinalized) => Ok(Some(Message::RenewRevoke(RenewRevoke {
+                channel_id: signed_channel.channel_id,
+                per_update_secret: prev_per_update_secret,
+            }))),
+            _ => Err(invalid_state()),
+        };
+    }
+
+    if channel_reestablish.update_idx + 1 == update_idx {
+        // The counter party completed an update for which we did not receive
+        // the last message, it will send it again.
+        return match (own_state, counter_state) {
+            (SettledConfirmed, Settled) | (RenewFinalized, Established) => Ok(None),
+            _ => Err(invalid_state()),
+        };
+    }
+
+    if channel_reestablish.update_idx != update_idx {
+        return Err(invalid_state());
+    }
+
+    let counter_is_stable = matches!(counter_state, Established | Settled);
+    let counter_is_offering = matches!(
+        counter_state,
+        SettledOffered | RenewOffered | CollaborativeCloseOffered | SpliceOffered
+    );
+
+    let msg = match own_state {
+        Established | Settled => None,
+        SettledOffered | RenewOffered | CollaborativeCloseOffered | SpliceOffered
+            if counter_is_offering =>
+        {
+            // Both parties offered an update at the same time, both roll back.
+            roll_back(signed_channel)?;
+            None
+        }
+        SettledReceived
+        | RenewReceived
+        | CollaborativeCloseReceived
+        | SettledAccepted
+        | RenewAccepted
+        | RenewConfirmed
+        | SpliceReceived
+            if counter_is_stable =>
+        {
+            // The counter party rolled back the update or never received our
+            // last message, and no secret was revealed yet.
+            roll_back(signed_channel)?;
+            None
+        }
+        SpliceOffered if counter_is_stable => {
+            // The funding inputs of the offer are released by the caller.
+            roll_back(signed_channel)?;
+            None
+        }
+        SettledOffered if counter_is_stable => {
+            let (&counter_payout, &next_per_update_point, &offer_fee_share) = get_signed_channel_state!(
+                signed_channel,
+                SettledOffered,
+                counter_payout,
+                next_per_update_point,
+                offer_fee_share
+            )?;
+            Some(Message::SettleOffer(SettleOffer {
+                channel_id: signed_channel.channel_id,
+                counter_payout,
+                next_per_update_point,
+                offer_fee_share,
+            }))
+        }
+        SettledAccepted if counter_state == SettledOffered => {
+            let (&next_per_update_point, &settle_adaptor_signature) = get_signed_channel_state!(
+                signed_channel,
+                SettledAccepted,
+                own_next_per_update_point,
+                own_settle_adaptor_signature
+            )?;
+            Some(Message::SettleAccept(SettleAccept {
+                channel_id: signed_channel.channel_id,
+                next_per_update_point,
+                settle_adaptor_signature,
+            }))
+        }
+        SettledConfirmed if counter_state == SettledAccepted => {
+            let &settle_adaptor_signature = get_signed_channel_state!(
+                signed_channel,
+                SettledConfirmed,
+                own_settle_adaptor_signature
+            )?;
+            let per_update_seed =
+                signer_provider.get_secret_key_for_pubkey(&signed_channel.own_per_update_seed)?;
+            Some(Message::SettleConfirm(SettleConfirm {
+                channel_id: signed_channel.channel_id,
+                prev_per_update_secret: SecretKey::from_slice(&build_commitment_secret(
+                    per_update_seed.as_ref(),
+                    update_idx,
+                ))?,
+                settle_adaptor_signature,
+            }))
+        }
+        RenewOffered if counter_is_stable => {
+            let offered_contract = match contract {
+                Some(Contract::Offered(o)) => o,
+                _ => {
+                    return Err(Error::InvalidState(
+                        "Expected offered contract.".to_string(),
+                    ))
+                }
+            };
+            let (&counter_payout, &next_per_update_point, &offer_fee_share) = get_signed_channel_state!(
+                signed_channel,
+                RenewOffered,
+                counter_payout,
+                offer_next_per_update_point,
+                offer_fee_share
+            )?;
+            Some(Message::RenewOffer(RenewOffer {
+                channel_id: signed_channel.channel_id,
+                temporary_contract_id: offered_contract.id,
+                counter_payout,
+                next_per_update_point,
+                contract_info: offered_contract.into(),
+                cet_locktime: offered_contract.cet_locktime,
+                refund_locktime: offered_contract.refund_locktime,
+                cet_nsequence,
+                offer_fee_share,
+            }))
+        }
+        RenewAccepted if counter_state == RenewOffered => {
+            let accepted_contract = match contract {
+                Some(Contract::Accepted(a)) => a,
+                _ => {
+                    return Err(Error::InvalidState(
+                        "Expected accepted contract.".to_string(),
+                    ))
+                }
+            };
+            let (accept_per_update_point, buffer_transaction, buffer_script_pubkey) = get_signed_channel_state!(
+                signed_channel,
+                RenewAccepted,
+                accept_per_update_point | buffer_transaction,
+                buffer_script_pubkey
+            )?;
+            let cet_adaptor_signatures = get_own_cet_adaptor_signatures(
+                secp,
+                signed_channel,
+                accepted_contract,
+                accept_per_update_point,
+                buffer_transaction,
+                buffer_script_pubkey,
+                signer_provider,
+            )?;
+            Some(Message::RenewAccept(RenewAccept {
+                channel_id: signed_channel.channel_id,
+                next_per_update_point: *accept_per_update_point,
+                cet_adaptor_signatures: (&cet_adaptor_signatures as &[_]).into(),
+                refund_signature: accepted_contract.get_accept_refund_signature()?,
+            }))
+        }
+        RenewConfirmed if counter_state == RenewAccepted => {
+            let signed_contract = match contract {
+                Some(Contract::Signed(s)) => s,
+                _ => return Err(Error::InvalidState("Expected signed contract.".to_string())),
+            };
+            let (
+                offer_per_update_point,
+                &buffer_adaptor_signature,
+                buffer_transaction,
+                buffer_script_pubkey,
+            ) = get_signed_channel_state!(
+                signed_channel,
+                RenewConfirmed,
+                offer_per_update_point,
+                offer_buffer_adaptor_signature | buffer_transaction,
+                buffer_script_pubkey
+            )?;
+            let cet_adaptor_signatures = get_own_cet_adaptor_signatures(
+                secp,
+                signed_channel,
+                &signed_contract.accepted_contract,
+                offer_per_update_point,
+                buffer_transaction,
+                buffer_script_pubkey,
+                signer_provider,
+            )?;
+            Some(Message::RenewConfirm(RenewConfirm {
+                channel_id: signed_channel.channel_id,
+                buffer_adaptor_signature,
+                cet_adaptor_signatures: (&cet_adaptor_signatures as &[_]).into(),
+                refund_signature: signed_contract.get_offer_refund_signature()?,
+            }))
+        }
+        RenewFinalized if counter_state == RenewConfirmed => {
+            let &buffer_adaptor_signature = get_signed_channel_state!(
+                signed_channel,
+                RenewFinalized,
+                accept_buffer_adaptor_signature
+            )?;
+            let per_update_seed =
+                signer_provider.get_secret_key_for_pubkey(&signed_channel.own_per_update_seed)?;
+            Some(Message::RenewFinalize(RenewFinalize {
+                channel_id: signed_channel.channel_id,
+                per_update_secret: SecretKey::from_slice(&build_commitment_secret(
+                    per_update_seed.as_ref(),
+                    update_idx,
+                ))?,
+                buffer_adaptor_signature,
+            }))
+        }
+        CollaborativeCloseOffered if counter_is_stable => {
+            let (&counter_payout, &close_signature, &offer_fee_share) = get_signed_channel_state!(
+                signed_channel,
+                CollaborativeCloseOffered,
+                counter_payout,
+                offer_signature,
+                offer_fee_share
+            )?;
+            Some(Message::CollaborativeCloseOffer(CollaborativeCloseOffer {
+                channel_id: signed_channel.channel_id,
+                counter_payout,
+                close_signature,
+                offer_fee_share,
+            }))
+        }
+        SpliceAccepted if counter_state == SpliceOffered => {
+            let (&settle_adaptor_signature, &keys_id, splice_tx) = get_signed_channel_state!(
+                signed_channel,
+                SpliceAccepted,
+                own_settle_adaptor_signature,
+                keys_id | splice_tx
+            )?;
+            let splice_signature = dlc::util::get_raw_sig_for_tx_input(
+                secp,
+                splice_tx,
+                0,
+                &signed_channel.fund_script_pubkey,
+                signed_channel.fund_tx.output[signed_channel.fund_output_index].value,
+                &signer_provider
+                    .derive_contract_signer(keys_id)?
+                    .get_secret_key()?,
+            )?;
+            Some(Message::SpliceAccept(SpliceAccept {
+                channel_id: signed_channel.channel_id,
+                settle_adaptor_signature,
+                splice_signature,
+            }))
+        }
+        SpliceConfirmed if counter_state == SpliceAccepted => {
+            let (&keys_id, splice_tx) =
+                get_signed_channel_state!(signed_channel, SpliceConfirmed, keys_id | splice_tx)?;
+            let splice_signature = dlc::util::get_raw_sig_for_tx_input(
+                secp,
+                splice_tx,
+                0,
+                &signed_channel.fund_script_pubkey,
+                signed_channel.fund_tx.output[signed_channel.fund_output_index].value,
+                &signer_provider
+                    .derive_contract_signer(keys_id)?
+                    .get_secret_key()?,
+            )?;
+            let funding_signatures = splice_tx
+                .input
+                .iter()
+                .skip(1)
+                .map(|input| FundingSignature {
+                    witness_elements: input
+                        .witness
+                        .iter()
+                        .map(|x| WitnessElement {
+                            witness: x.to_vec(),
+                        })
+                        .collect(),
+                })
+                .collect();
+            Some(Message::SpliceConfirm(SpliceConfirm {
+                channel_id: signed_channel.channel_id,
+                splice_signature,
+                funding_signatures: FundingSignatures { funding_signatures },
+            }))
+        }
+        // The counter party is the one expected to resend its last message or
+        // to act on the pending update.
+        SettledOffered if matches!(counter_state, SettledReceived | SettledAccepted) => None,
+        SettledReceived if counter_state == SettledOffered => None,
+        SettledAccepted if counter_state == SettledConfirmed => None,
+        RenewOffered if matches!(counter_state, RenewReceived | RenewAccepted) => None,
+        RenewReceived if counter_state == RenewOffered => None,
+        RenewAccepted if counter_state == RenewConfirmed => None,
+        RenewConfirmed if counter_state == RenewFinalized => None,
+        CollaborativeCloseOffered if counter_state == CollaborativeCloseReceived => None,
+        CollaborativeCloseReceived if counter_state == CollaborativeCloseOffered => None,
+        SpliceOffered if matches!(counter_state, SpliceReceived | SpliceAccepted) => None,
+        SpliceReceived if counter_state == SpliceOffered => None,
+        // Once the splice transaction is signed it can be broadcast at any
+        // time, so the update cannot be rolled back anymore and completes when
+        // the transaction is confirmed.
+        SpliceAccepted if counter_is_stable || counter_state == SpliceConfirmed => None,
+        SpliceConfirmed if counter_is_stable || counter_state == SpliceConfirmed => None,
+        _ => return Err(invalid_state()),
+    };
+
+    // Give the counter party time to process the messages before considering
+    // the channel as timed out.
+    refresh_timeout(
+        &mut signed_channel.state,
+        time.unix_time_now() + peer_timeout,
+    );
+
+    Ok(msg)
+}
+
+fn roll_back(signed_channel: &mut SignedChannel) -> Result<(), Error> {
+    signed_channel.state = signed_channel
+        .roll_back_state
+        .take()
+        .ok_or_else(|| Error::InvalidState("No rollback state available.".to_string()))?;
+    Ok(())
+}
+
+fn refresh_timeout(state: &mut SignedChannelState, new_timeout: u64) {
+    match state {
+        SignedChannelState::SettledOffered { timeout, .. }
+        | SignedChannelState::SettledAccepted { timeout, .. }
+        | SignedChannelState::SettledConfirmed { timeout, .. }
+        | SignedChannelState::RenewOffered { timeout, .. }
+        | SignedChannelState::RenewAccepted { timeout, .. }
+        | SignedChannelState::RenewConfirmed { timeout, .. }
+        | SignedChannelState::RenewFinalized { timeout, .. }
+        | SignedChannelState::CollaborativeCloseOffered { timeout, .. }
+        | SignedChannelState::SpliceOffered { timeout, .. }
+        | SignedChannelState::SpliceAccepted { timeout, .. } => *timeout = new_timeout,
+        _ => {}
+    }
+}
+
+/// Recomputes the CET adaptor signatures of the local party for a contract
+/// being renewed in the given channel, as they are not stored.
+fn get_own_cet_adaptor_signatures<SP: Deref>(
+    secp: &Secp256k1<All>,
+    signed_channel: &SignedChannel,
+    accepted_contract: &AcceptedContract,
+    own_per_update_point: &PublicKey,
+    buffer_transaction: &Transaction,
+    buffer_script_pubkey: &Script,
+    signer_provider: &SP,
+) -> Result<Vec<EcdsaAdaptorSignature>, Error>
+where
+    SP::Target: ContractSignerProvider,
+{
+    let own_base_secret_key =
+        signer_provider.get_secret_key_for_pubkey(&signed_channel.own_points.own_basepoint)?;
+    let own_secret_key = derive_private_key(secp, own_per_update_point, &own_base_secret_key);
+
+    let mut adaptor_signatures = Vec::new();
+    for (contract_info, adaptor_info) in accepted_contract
+        .offered_contract
+        .contract_info
+        .iter()
+        .zip(accepted_contract.adaptor_infos.iter())
+    {
+        adaptor_signatures.extend(contract_info.get_adaptor_signatures(
+            secp,
+            adaptor_info,
+            &&own_secret_key,
+            buffer_script_pubkey,
+            buffer_transaction.output[0].value,
+            &accepted_contract.dlc_transactions.cets,
+        )?);
+    }
+
+    Ok(adaptor_signatures)
 }
 
 /// Sign the buffer transaction and closing CET and update the state of the channel.
@@ -2,6 +2,7 @@
 
 use super::offered_contract::OfferedContract;
 use super::AdaptorInfo;
+use crate::error::Error;
 use bitcoin::Transaction;
 use dlc::{DlcTransactions, PartyParams};
 use dlc_messages::{AcceptDlc, FundingInput};
@@ -25,8 +26,9 @@ pub struct AcceptedContract {
     /// The adaptor signatures of the accepting party. Note that the accepting
     /// party does not keep them thus an option is used.
     pub adaptor_signatures: Option<Vec<EcdsaAdaptorSignature>>,
-    /// The signature for the refund transaction from the accepting party.
-    pub accept_refund_signature: Signature,
+    /// The signature for the refund transaction from the accepting party, None
+    /// for contracts funded with a taproot output.
+    pub accept_refund_signature: Option<Signature>,
     /// The bitcoin set of bitcoin transactions for the contract.
     pub dlc_transactions: DlcTransactions,
 }
@@ -54,6 +56,14 @@ impl AcceptedContract {
         string_id
     }
 
+    /// Returns the signature for the refund transaction from the accepting
+    /// party, or an error for contracts funded with a taproot output.
+    pub(crate) fn get_accept_refund_signature(&self) -> Result<Signature, Error> {
+        self.accept_refund_signature.ok_or_else(|| {
+            Error::InvalidState("Missing refund signature of the accept party.".to_string())
+        })
+    }
+
     pub(crate) fn get_accept_contract_msg(
         &self,
         ecdsa_adaptor_signatures: &[EcdsaAdaptorSignature],
@@ -71,6 +81,7 @@ impl AcceptedContract {
             cet_adaptor_signatures: ecdsa_adaptor_signatures.into(),
             refund_signature: self.accept_refund_signature,
             negotiation_fields: None,
+            taproot_signatures: None,
         }
     }
 
@@ -103,14 +114,13 @@ impl AcceptedContract {
 mod tests {
     use std::io::Cursor;
 
-    use lightning::util::ser::Readable;
-
     use super::*;
 
     #[test]
     fn pnl_compute_test() {
         let buf = include_bytes!("../../../dlc-sled-storage-provider/test_files/Accepted");
-        let accepted_contract: AcceptedContract = Readable::read(&mut Cursor::new(&buf)).unwrap();
+        let accepted_contract =
+            crate::legacy::read_accepted_contract(&mut Cursor::new(&buf)).unwrap();
         let cets = &accepted_contract.dlc_transactions.cets;
         assert_eq!(accepted_contract.compute_pnl(&cets[0]), 90000000);
         assert_eq!(
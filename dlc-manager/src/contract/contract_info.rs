@@ -39,6 +39,7 @@ impl ContractInfo {
         match &self.contract_descriptor {
             ContractDescriptor::Enum(e) => Ok(e.get_payouts()),
             ContractDescriptor::Numerical(n) => n.get_payouts(total_collateral),
+            ContractDescriptor::MultiEvent(m) => Ok(m.get_payouts()),
         }
     }
 
@@ -52,7 +53,7 @@ impl ContractInfo {
         }
 
         self.contract_descriptor
-            .validate(&self.oracle_announcements)
+            .validate(&self.oracle_announcements, self.threshold)
     }
 
     /// Utility function returning a set of OracleInfo created using the set
@@ -87,6 +88,15 @@ impl ContractInfo {
                     funding_script_pubkey,
                     fund_output_value,
                 ),
+                ContractDescriptor::MultiEvent(m) => m.get_adaptor_signatures(
+                    secp,
+                    &self.oracle_announcements,
+                    self.threshold,
+                    cets,
+                    &fund_privkey,
+                    funding_script_pubkey,
+                    fund_output_value,
+                ),
                 _ => unreachable!(),
             },
             AdaptorInfo::Numerical(trie) => Ok(trie.sign(
@@ -134,6 +144,17 @@ impl ContractInfo {
                 adaptor_sigs,
                 adaptor_sig_start,
             )?),
+            ContractDescriptor::MultiEvent(m) => Ok(m.verify_and_get_adaptor_info(
+                secp,
+                &self.oracle_announcements,
+                self.threshold,
+                fund_pubkey,
+                funding_script_pubkey,
+                fund_output_value,
+                cets,
+                adaptor_sigs,
+                adaptor_sig_start,
+            )?),
             ContractDescriptor::Numerical(n) => Ok(n.verify_and_get_adaptor_info(
                 secp,
                 total_collateral,
@@ -164,6 +185,12 @@ impl ContractInfo {
                     outcomes,
                     adaptor_sig_start,
                 ),
+                ContractDescriptor::MultiEvent(m) => m.get_range_info_for_outcome(
+                    &self.oracle_announcements,
+                    self.threshold,
+                    outcomes,
+                    adaptor_sig_start,
+                ),
                 _ => unreachable!(),
             },
             AdaptorInfo::Numerical(n) => {
@@ -210,6 +237,17 @@ impl ContractInfo {
                 adaptor_sigs,
                 adaptor_sig_start,
             )?),
+            ContractDescriptor::MultiEvent(m) => Ok(m.verify_adaptor_info(
+                secp,
+                &self.oracle_announcements,
+                self.threshold,
+                fund_pubkey,
+                funding_script_pubkey,
+                fund_output_value,
+                cets,
+                adaptor_sigs,
+                adaptor_sig_start,
+            )?),
             ContractDescriptor::Numerical(_) => match adaptor_info {
                 AdaptorInfo::Enum => unreachable!(),
                 AdaptorInfo::Numerical(trie) => Ok(trie.verify(
@@ -258,6 +296,15 @@ impl ContractInfo {
                     cets,
                 )?)
             }
+            ContractDescriptor::MultiEvent(m) => Ok(m.get_adaptor_info(
+                secp,
+                &self.oracle_announcements,
+                self.threshold,
+                fund_priv_key,
+                funding_script_pubkey,
+                fund_output_value,
+                cets,
+            )?),
             ContractDescriptor::Numerical(n) => Ok(n.get_adaptor_info(
                 secp,
                 total_collateral,
@@ -272,6 +319,34 @@ impl ContractInfo {
         }
     }
 
+    /// Generate the adaptor info for the contract and return it together with
+    /// the index of the CET and the adaptor point of each adaptor signature it
+    /// requires, in adaptor signature order. Used for contracts funded with a
+    /// taproot output, whose adaptor signatures are created jointly.
+    pub fn get_adaptor_info_and_points(
+        &self,
+        secp: &Secp256k1<All>,
+        total_collateral: u64,
+        adaptor_index_start: usize,
+    ) -> Result<(AdaptorInfo, Vec<(usize, PublicKey)>), Error> {
+        match &self.contract_descriptor {
+            ContractDescriptor::Enum(e) => Ok((
+                AdaptorInfo::Enum,
+                e.get_adaptor_points(secp, &self.get_oracle_infos(), self.threshold)?,
+            )),
+            ContractDescriptor::MultiEvent(m) => Ok((
+                AdaptorInfo::Enum,
+                m.get_adaptor_points(secp, &self.oracle_announcements, self.threshold)?,
+            )),
+            ContractDescriptor::Numerical(n) => n.get_adaptor_info_and_points(
+                total_collateral,
+                self.threshold,
+                &self.precompute_points(secp)?,
+                adaptor_index_start,
+            ),
+        }
+    }
+
     fn precompute_points<C: Verification>(
         &self,
         secp: &Secp256k1<C>,
@@ -3,6 +3,7 @@
 use crate::error::Error;
 
 use super::ContractDescriptor;
+use dlc::{DlcTransactionsOptions, FundingOutputType};
 use secp256k1_zkp::XOnlyPublicKey;
 #[cfg(feature = "use-serde")]
 use serde::{Deserialize, Serialize};
@@ -17,14 +18,39 @@ use serde::{Deserialize, Serialize};
 pub struct OracleInput {
     /// The set of public keys for each of the used oracles.
     pub public_keys: Vec<XOnlyPublicKey>,
-    /// The id of the event being used for the contract. Note that at the moment
-    /// a single event id is used, while multiple ids would be preferable.
-    pub event_id: String,
+    /// The ids of the events being used for the contract. When several ids are
+    /// given, the contract descriptor must define payouts over the joint outcome
+    /// of the events, and an oracle that did not announce one of the events is
+    /// not used for it. For compatibility with the previous format, a single id
+    /// can also be given as an `eventId` string.
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(alias = "eventId", deserialize_with = "deserialize_event_ids")
+    )]
+    pub event_ids: Vec<String>,
     /// The number of oracles that need to provide attestations satisfying the
-    /// contract conditions to be able to close the contract.
+    /// contract conditions for each event to be able to close the contract.
     pub threshold: u16,
 }
 
+#[cfg(feature = "use-serde")]
+fn deserialize_event_ids<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum EventIds {
+        Single(String),
+        Multiple(Vec<String>),
+    }
+
+    Ok(match EventIds::deserialize(deserializer)? {
+        EventIds::Single(event_id) => vec![event_id],
+        EventIds::Multiple(event_ids) => event_ids,
+    })
+}
+
 impl OracleInput {
     /// Checks whether the data within the struct is consistent.
     pub fn validate(&self) -> Result<(), Error> {
@@ -46,6 +72,12 @@ impl OracleInput {
             ));
         }
 
+        if self.event_ids.is_empty() {
+            return Err(Error::InvalidParameters(
+                "OracleInput must have at least one event id.".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -81,9 +113,41 @@ pub struct ContractInput {
     /// The set of contract that make up the DLC (a single DLC can be based
     /// on multiple contracts).
     pub contract_infos: Vec<ContractInputInfo>,
+    /// Whether the CETs and refund transaction should include an anchor output
+    /// for each party, enabling fee bumping using CPFP.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub anchor_outputs: bool,
+    /// Whether the contract is funded by the offer party only, the accept party
+    /// contributing no inputs and paying no fees. The accept collateral must be
+    /// zero in this case.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub single_funded: bool,
+    /// The type of output locking the collateral of both parties in the fund
+    /// transaction. Taproot funding cannot be combined with a fee payer, nor
+    /// be used for contracts in channels or batches.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub funding_output_type: FundingOutputType,
+    /// The delay between the latest maturity of the contract and the lock time
+    /// of its refund transaction. Uses the refund delay configured for the
+    /// manager if not set.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub refund_delay: Option<u32>,
+    /// The unix timestamp after which the offer cannot be accepted anymore, if
+    /// any. The funding inputs reserved for an expired offer are released.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub offer_expiry: Option<u64>,
 }
 
 impl ContractInput {
+    /// Returns the options to use to create the transactions of the contract.
+    pub fn get_dlc_transactions_options(&self) -> DlcTransactionsOptions {
+        DlcTransactionsOptions {
+            anchor_outputs: self.anchor_outputs,
+            single_funded: self.single_funded,
+            funding_output_type: self.funding_output_type,
+        }
+    }
+
     /// Validate the contract input parameters
     pub fn validate(&self) -> Result<(), Error> {
         if self.contract_infos.is_empty() {
@@ -94,6 +158,21 @@ impl ContractInput {
 
         for contract_info in &self.contract_infos {
             contract_info.oracles.validate()?;
+            let is_multi_event = matches!(
+                contract_info.contract_descriptor,
+                ContractDescriptor::MultiEvent(_)
+            );
+            if is_multi_event != (contract_info.oracles.event_ids.len() > 1) {
+                return Err(Error::InvalidParameters(
+                    "Multiple event ids must be used with a multi event descriptor.".to_string(),
+                ));
+            }
+        }
+
+        if self.single_funded && self.accept_collateral != 0 {
+            return Err(Error::InvalidParameters(
+                "Accept collateral must be zero for single funded contracts.".to_string(),
+            ));
         }
 
         dlc::util::validate_fee_rate(self.fee_rate)
@@ -115,6 +194,11 @@ mod tests {
             offer_collateral: 1000000,
             accept_collateral: 2000000,
             fee_rate: 1234,
+            anchor_outputs: false,
+            single_funded: false,
+            funding_output_type: FundingOutputType::P2wsh,
+            refund_delay: None,
+            offer_expiry: None,
             contract_infos: vec![ContractInputInfo {
                 contract_descriptor: ContractDescriptor::Enum(EnumDescriptor {
                     outcome_payouts: vec![
@@ -142,7 +226,7 @@ mod tests {
                         ))
                         .0,
                     ],
-                    event_id: "1234".to_string(),
+                    event_ids: vec!["1234".to_string()],
                     threshold: 1,
                 },
             }],
@@ -191,6 +275,26 @@ mod tests {
             .expect_err("the contract input to be invalid.");
     }
 
+    #[test]
+    fn single_funded_with_accept_collateral_contract_input_is_not_valid() {
+        let mut input = get_base_input();
+        input.single_funded = true;
+        input
+            .validate()
+            .expect_err("the contract input to be invalid.");
+        input.accept_collateral = 0;
+        input.validate().expect("the contract input to be valid.");
+    }
+
+    #[test]
+    fn no_event_ids_oracle_input_contract_input_is_not_valid() {
+        let mut input = get_base_input();
+        input.contract_infos[0].oracles.event_ids.clear();
+        input
+            .validate()
+            .expect_err("the contract input to be invalid.");
+    }
+
     #[test]
     fn invalid_oracle_info_threshold_zero() {
         let mut input = get_base_input();
@@ -199,4 +303,20 @@ mod tests {
             .validate()
             .expect_err("the contract input to be invalid.");
     }
+
+    #[test]
+    fn oracle_input_with_single_event_id_is_deserialized() {
+        let input: OracleInput =
+            serde_json::from_str(r#"{"publicKeys":[],"eventId":"btcusd","threshold":1}"#).unwrap();
+        assert_eq!(vec!["btcusd".to_string()], input.event_ids);
+
+        let input: OracleInput = serde_json::from_str(
+            r#"{"publicKeys":[],"eventIds":["btcusd","ethusd"],"threshold":1}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["btcusd".to_string(), "ethusd".to_string()],
+            input.event_ids
+        );
+    }
 }
@@ -226,6 +226,26 @@ impl EnumDescriptor {
         Ok(adaptor_sigs)
     }
 
+    /// Returns the index of the CET and the adaptor point of each adaptor
+    /// signature required for the contract, in adaptor signature order.
+    pub fn get_adaptor_points<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        oracle_infos: &[OracleInfo],
+        threshold: usize,
+    ) -> Result<Vec<(usize, PublicKey)>, dlc::Error> {
+        let mut adaptor_points = Vec::new();
+        let mut callback =
+            |adaptor_point: &PublicKey, cet_index: usize| -> Result<(), dlc::Error> {
+                adaptor_points.push((cet_index, *adaptor_point));
+                Ok(())
+            };
+
+        self.iter_outcomes(secp, oracle_infos, threshold, &mut callback)?;
+
+        Ok(adaptor_points)
+    }
+
     fn iter_outcomes<C: Verification, F>(
         &self,
         secp: &Secp256k1<C>,
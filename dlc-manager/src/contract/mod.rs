@@ -20,6 +20,7 @@ pub mod accepted_contract;
 pub mod contract_info;
 pub mod contract_input;
 pub mod enum_descriptor;
+pub mod multi_event_descriptor;
 pub mod numerical_descriptor;
 pub mod offered_contract;
 pub mod ser;
@@ -41,7 +42,10 @@ pub enum Contract {
     PreClosed(PreClosedContract),
     /// A contract for which a CET was confirmed to blockchain
     Closed(ClosedContract),
-    /// A contract whose refund transaction was broadcast.
+    /// A contract whose refund transaction was broadcast, but not necessarily
+    /// confirmed.
+    PreRefunded(signed_contract::SignedContract),
+    /// A contract whose refund transaction was confirmed.
     Refunded(signed_contract::SignedContract),
     /// A contract that failed when verifying information from an accept message.
     FailedAccept(FailedAcceptContract),
@@ -49,6 +53,15 @@ pub enum Contract {
     FailedSign(FailedSignContract),
     /// A contract that was rejected by the party to whom it was offered.
     Rejected(offered_contract::OfferedContract),
+    /// A contract whose offer was cancelled by the offering party or expired
+    /// before being accepted.
+    Cancelled(offered_contract::OfferedContract),
+    /// A contract that was closed by a close transaction agreed on by both
+    /// parties.
+    CollaborativelyClosed(CollaborativelyClosedContract),
+    /// A contract that was rolled over into a new contract spending the same
+    /// fund output.
+    RolledOver(signed_contract::SignedContract),
 }
 
 impl std::fmt::Debug for Contract {
@@ -60,10 +73,14 @@ impl std::fmt::Debug for Contract {
             Contract::Confirmed(_) => "confirmed",
             Contract::PreClosed(_) => "pre-closed",
             Contract::Closed(_) => "closed",
+            Contract::PreRefunded(_) => "pre-refunded",
             Contract::Refunded(_) => "refunded",
             Contract::FailedAccept(_) => "failed accept",
             Contract::FailedSign(_) => "failed sign",
             Contract::Rejected(_) => "rejected",
+            Contract::Cancelled(_) => "cancelled",
+            Contract::CollaborativelyClosed(_) => "collaboratively closed",
+            Contract::RolledOver(_) => "rolled over",
         };
         f.debug_struct("Contract").field("state", &state).finish()
     }
@@ -74,41 +91,51 @@ impl Contract {
     /// and failed accept contracts.
     pub fn get_id(&self) -> ContractId {
         match self {
-            Contract::Offered(o) | Contract::Rejected(o) => o.id,
+            Contract::Offered(o) | Contract::Rejected(o) | Contract::Cancelled(o) => o.id,
             Contract::Accepted(o) => o.get_contract_id(),
-            Contract::Signed(o) | Contract::Confirmed(o) | Contract::Refunded(o) => {
-                o.accepted_contract.get_contract_id()
-            }
+            Contract::Signed(o)
+            | Contract::Confirmed(o)
+            | Contract::PreRefunded(o)
+            | Contract::Refunded(o)
+            | Contract::RolledOver(o) => o.accepted_contract.get_contract_id(),
             Contract::FailedAccept(c) => c.offered_contract.id,
             Contract::FailedSign(c) => c.accepted_contract.get_contract_id(),
             Contract::PreClosed(c) => c.signed_contract.accepted_contract.get_contract_id(),
             Contract::Closed(c) => c.contract_id,
+            Contract::CollaborativelyClosed(c) => c.contract_id,
         }
     }
 
     /// Returns the temporary contract id of a contract.
     pub fn get_temporary_id(&self) -> ContractId {
         match self {
-            Contract::Offered(o) | Contract::Rejected(o) => o.id,
+            Contract::Offered(o) | Contract::Rejected(o) | Contract::Cancelled(o) => o.id,
             Contract::Accepted(o) => o.offered_contract.id,
-            Contract::Signed(o) | Contract::Confirmed(o) | Contract::Refunded(o) => {
-                o.accepted_contract.offered_contract.id
-            }
+            Contract::Signed(o)
+            | Contract::Confirmed(o)
+            | Contract::PreRefunded(o)
+            | Contract::Refunded(o)
+            | Contract::RolledOver(o) => o.accepted_contract.offered_contract.id,
             Contract::FailedAccept(c) => c.offered_contract.id,
             Contract::FailedSign(c) => c.accepted_contract.offered_contract.id,
             Contract::PreClosed(c) => c.signed_contract.accepted_contract.offered_contract.id,
             Contract::Closed(c) => c.temporary_contract_id,
+            Contract::CollaborativelyClosed(c) => c.temporary_contract_id,
         }
     }
 
     /// Returns the public key of the counter party's node.
     pub fn get_counter_party_id(&self) -> PublicKey {
         match self {
-            Contract::Offered(o) | Contract::Rejected(o) => o.counter_party,
-            Contract::Accepted(a) => a.offered_contract.counter_party,
-            Contract::Signed(s) | Contract::Confirmed(s) | Contract::Refunded(s) => {
-                s.accepted_contract.offered_contract.counter_party
+            Contract::Offered(o) | Contract::Rejected(o) | Contract::Cancelled(o) => {
+                o.counter_party
             }
+            Contract::Accepted(a) => a.offered_contract.counter_party,
+            Contract::Signed(s)
+            | Contract::Confirmed(s)
+            | Contract::PreRefunded(s)
+            | Contract::Refunded(s)
+            | Contract::RolledOver(s) => s.accepted_contract.offered_contract.counter_party,
             Contract::PreClosed(c) => {
                 c.signed_contract
                     .accepted_contract
@@ -116,6 +143,7 @@ impl Contract {
                     .counter_party
             }
             Contract::Closed(c) => c.counter_party_id,
+            Contract::CollaborativelyClosed(c) => c.counter_party_id,
             Contract::FailedAccept(f) => f.offered_contract.counter_party,
             Contract::FailedSign(f) => f.accepted_contract.offered_contract.counter_party,
         }
@@ -172,6 +200,22 @@ pub struct ClosedContract {
     pub pnl: i64,
 }
 
+/// Information about a contract that was closed by a close transaction agreed on
+/// by both parties.
+#[derive(Clone)]
+pub struct CollaborativelyClosedContract {
+    /// The id of the contract
+    pub contract_id: ContractId,
+    /// The temporary id of the contract.
+    pub temporary_contract_id: ContractId,
+    /// The public key of the counter-party's node.
+    pub counter_party_id: PublicKey,
+    /// The signed version of the close transaction that was broadcast.
+    pub close_tx: Transaction,
+    /// The profit and loss for the given contract
+    pub pnl: i64,
+}
+
 /// Information about the adaptor signatures and the CET for which they are
 /// valid.
 #[derive(Clone)]
@@ -197,13 +241,16 @@ pub enum ContractDescriptor {
     Enum(enum_descriptor::EnumDescriptor),
     /// Case for numerical outcome DLC.
     Numerical(numerical_descriptor::NumericalDescriptor),
+    /// Case for DLC whose outcome is the joint outcome of several enumerated
+    /// or numerical events.
+    MultiEvent(multi_event_descriptor::MultiEventDescriptor),
 }
 
 impl ContractDescriptor {
     /// Get the parameters on allowed divergence between oracle if any.
     pub fn get_oracle_params(&self) -> Option<numerical_descriptor::DifferenceParams> {
         match self {
-            ContractDescriptor::Enum(_) => None,
+            ContractDescriptor::Enum(_) | ContractDescriptor::MultiEvent(_) => None,
             ContractDescriptor::Numerical(n) => n.difference_params.clone(),
         }
     }
@@ -213,7 +260,11 @@ impl ContractDescriptor {
     pub fn validate(
         &self,
         announcements: &Vec<OracleAnnouncement>,
+        threshold: usize,
     ) -> Result<(), crate::error::Error> {
+        if let ContractDescriptor::MultiEvent(m) = self {
+            return m.validate(announcements, threshold);
+        }
         let first = announcements
             .first()
             .expect("to have at least one element.");
@@ -0,0 +1,967 @@
+//! #MultiEventDescriptor
+
+use super::contract_info::OracleIndexAndPrefixLength;
+use super::utils::{get_majority_combination, unordered_equal};
+use super::AdaptorInfo;
+use crate::error::Error;
+use bitcoin::{Script, Transaction};
+use dlc::{OracleInfo, Payout};
+use dlc_messages::contract_msgs::EventOutcome;
+use dlc_messages::oracle_msgs::{EventDescriptor, OracleAnnouncement};
+use dlc_trie::{combination_iterator::CombinationIterator, digit_decomposition, RangeInfo};
+use secp256k1_zkp::{
+    All, EcdsaAdaptorSignature, Message, PublicKey, Secp256k1, SecretKey, Verification,
+};
+#[cfg(feature = "use-serde")]
+use serde::{Deserialize, Serialize};
+
+/// The payout associated with a joint outcome of the events of a multi event
+/// contract.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MultiEventOutcomePayout {
+    /// The outcome of each event, in the order in which the events appear in
+    /// the oracle announcements of the contract. Enumerated events take an
+    /// [`EventOutcome::Enum`] and numerical events an [`EventOutcome::Range`].
+    pub outcomes: Vec<EventOutcome>,
+    /// The corresponding payout.
+    pub payout: Payout,
+}
+
+/// A descriptor for a contract whose payouts depend on the joint outcome of
+/// several enumerated or numerical events, possibly attested at different
+/// times or by different oracles. The oracle announcements of the contract are
+/// grouped by event id, and the threshold of the contract applies to each
+/// event. The outcomes of a numerical event are split into ranges that must
+/// cover all its possible values, and oracles attesting to a numerical event
+/// must agree on its exact value.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MultiEventDescriptor {
+    /// The set of joint outcomes and their associated payouts.
+    pub outcome_payouts: Vec<MultiEventOutcomePayout>,
+}
+
+/// The possible outcomes of an event of a multi event contract.
+enum EventOutcomes<'a> {
+    Enum(&'a [String]),
+    Numerical { base: usize, nb_digits: usize },
+}
+
+impl EventOutcomes<'_> {
+    /// Returns whether the given outcome is a possible outcome of the event.
+    fn is_valid(&self, outcome: &EventOutcome) -> bool {
+        match (self, outcome) {
+            (EventOutcomes::Enum(outcomes), EventOutcome::Enum(o)) => outcomes.contains(o),
+            (EventOutcomes::Numerical { base, nb_digits }, EventOutcome::Range { start, end }) => {
+                match get_max_value(*base, *nb_digits) {
+                    Some(max) => start <= end && *end <= max,
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the sets of messages, one per oracle nonce, whose attestation
+    /// reveals the given outcome. A numerical range is covered by one or more
+    /// digit prefixes.
+    fn get_prefixes(&self, outcome: &EventOutcome) -> Vec<Vec<String>> {
+        match (self, outcome) {
+            (EventOutcomes::Enum(_), EventOutcome::Enum(o)) => vec![vec![o.clone()]],
+            (EventOutcomes::Numerical { base, nb_digits }, EventOutcome::Range { start, end }) => {
+                digit_decomposition::group_by_ignoring_digits(
+                    *start as usize,
+                    *end as usize,
+                    *base,
+                    *nb_digits,
+                )
+                .iter()
+                .map(|prefix| prefix.iter().map(|d| d.to_string()).collect())
+                .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Returns the largest value that can be represented with `nb_digits` digits
+/// in the given base, if it fits in a `u64`.
+fn get_max_value(base: usize, nb_digits: usize) -> Option<u64> {
+    (base as u64).checked_pow(nb_digits as u32).map(|x| x - 1)
+}
+
+/// Returns the possible outcomes of the event of the given group of
+/// announcements, checking that all announcements of the group agree on them.
+fn get_event_outcomes<'a>(
+    announcements: &'a [OracleAnnouncement],
+    group: &[usize],
+) -> Result<EventOutcomes<'a>, Error> {
+    match &announcements[group[0]].oracle_event.event_descriptor {
+        EventDescriptor::EnumEvent(e) => {
+            for i in group {
+                match &announcements[*i].oracle_event.event_descriptor {
+                    EventDescriptor::EnumEvent(x) if unordered_equal(&e.outcomes, &x.outcomes) => {}
+                    _ => {
+                        return Err(Error::InvalidParameters(
+                            "Oracles don't have same enum outcomes.".to_string(),
+                        ))
+                    }
+                }
+            }
+            Ok(EventOutcomes::Enum(&e.outcomes))
+        }
+        EventDescriptor::DigitDecompositionEvent(d) => {
+            if d.is_signed {
+                return Err(Error::InvalidParameters(
+                    "Signed numerical events are not supported.".to_string(),
+                ));
+            }
+            for i in group {
+                let oracle_event = &announcements[*i].oracle_event;
+                match &oracle_event.event_descriptor {
+                    EventDescriptor::DigitDecompositionEvent(x)
+                        if x.base == d.base
+                            && x.nb_digits == d.nb_digits
+                            && !x.is_signed
+                            && oracle_event.oracle_nonces.len() == d.nb_digits as usize => {}
+                    _ => {
+                        return Err(Error::InvalidParameters(
+                            "Oracles don't have same numerical event parameters.".to_string(),
+                        ))
+                    }
+                }
+            }
+            Ok(EventOutcomes::Numerical {
+                base: d.base as usize,
+                nb_digits: d.nb_digits as usize,
+            })
+        }
+    }
+}
+
+/// Returns all the combinations made of one index per list, given the length
+/// of each list, with the index of the last list varying the fastest.
+fn get_index_combinations(lens: &[usize]) -> Vec<Vec<usize>> {
+    lens.iter().fold(vec![Vec::new()], |combinations, len| {
+        combinations
+            .iter()
+            .flat_map(|combination| {
+                (0..*len).map(move |i| {
+                    let mut combination = combination.clone();
+                    combination.push(i);
+                    combination
+                })
+            })
+            .collect()
+    })
+}
+
+/// Checks that the ranges of the numerical event at the given index are
+/// contiguous, cover all values from zero to `max` and are at least two, and
+/// returns their number.
+fn validate_ranges(
+    outcome_payouts: &[MultiEventOutcomePayout],
+    event_index: usize,
+    max: u64,
+) -> Result<usize, Error> {
+    let mut ranges: Vec<&EventOutcome> = outcome_payouts
+        .iter()
+        .map(|x| &x.outcomes[event_index])
+        .collect();
+    ranges.sort();
+    ranges.dedup();
+
+    let mut next_start = 0;
+    for range in &ranges {
+        match range {
+            EventOutcome::Range { start, end } if *start == next_start => next_start = end + 1,
+            _ => {
+                return Err(Error::InvalidParameters(
+                    "Numerical event ranges must be contiguous and not overlap.".to_string(),
+                ))
+            }
+        }
+    }
+
+    if next_start != max + 1 || ranges.len() < 2 {
+        return Err(Error::InvalidParameters(
+            "Numerical event ranges must cover all outcomes with at least two ranges.".to_string(),
+        ));
+    }
+
+    Ok(ranges.len())
+}
+
+/// Returns, for each event, the message prefixes revealing the outcome of the
+/// event in the given joint outcome.
+fn get_outcome_prefixes(
+    event_outcomes: &[EventOutcomes],
+    outcome_payout: &MultiEventOutcomePayout,
+) -> Vec<Vec<Vec<String>>> {
+    event_outcomes
+        .iter()
+        .zip(outcome_payout.outcomes.iter())
+        .map(|(e, o)| e.get_prefixes(o))
+        .collect()
+}
+
+/// Returns the indexes of the given announcements grouped by event id, in the
+/// order in which the events first appear.
+pub(crate) fn get_event_groups(announcements: &[OracleAnnouncement]) -> Vec<Vec<usize>> {
+    let mut event_ids: Vec<&String> = Vec::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (i, announcement) in announcements.iter().enumerate() {
+        let event_id = &announcement.oracle_event.event_id;
+        match event_ids.iter().position(|x| *x == event_id) {
+            Some(pos) => groups[pos].push(i),
+            None => {
+                event_ids.push(event_id);
+                groups.push(vec![i]);
+            }
+        }
+    }
+
+    groups
+}
+
+impl MultiEventDescriptor {
+    /// Returns the set of payouts.
+    pub fn get_payouts(&self) -> Vec<Payout> {
+        self.outcome_payouts
+            .iter()
+            .map(|x| x.payout.clone())
+            .collect()
+    }
+
+    /// Validate that the descriptor associates a single payout to each possible
+    /// joint outcome of the events of the given announcements, and that each
+    /// event has enough announcements to satisfy the threshold.
+    pub fn validate(
+        &self,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+    ) -> Result<(), Error> {
+        let groups = get_event_groups(announcements);
+        if self
+            .outcome_payouts
+            .iter()
+            .any(|x| x.outcomes.len() != groups.len())
+        {
+            return Err(Error::InvalidParameters(
+                "Joint outcomes must have one outcome per event.".to_string(),
+            ));
+        }
+
+        let mut nb_joint_outcomes: usize = 1;
+        for (i, group) in groups.iter().enumerate() {
+            if group.len() < threshold {
+                return Err(Error::InvalidParameters(
+                    "Not enough oracle announcements for event to satisfy threshold.".to_string(),
+                ));
+            }
+            let event_outcomes = get_event_outcomes(announcements, group)?;
+            if !self
+                .outcome_payouts
+                .iter()
+                .all(|x| event_outcomes.is_valid(&x.outcomes[i]))
+            {
+                return Err(Error::InvalidParameters(
+                    "Invalid outcome for event.".to_string(),
+                ));
+            }
+            let nb_outcomes = match event_outcomes {
+                EventOutcomes::Enum(outcomes) => outcomes.len(),
+                EventOutcomes::Numerical { base, nb_digits } => {
+                    let max = get_max_value(base, nb_digits).ok_or_else(|| {
+                        Error::InvalidParameters("Too many digits for event.".to_string())
+                    })?;
+                    validate_ranges(&self.outcome_payouts, i, max)?
+                }
+            };
+            nb_joint_outcomes = nb_joint_outcomes
+                .checked_mul(nb_outcomes)
+                .ok_or_else(|| Error::InvalidParameters("Too many joint outcomes.".to_string()))?;
+        }
+
+        let mut joint_outcomes: Vec<_> = self.outcome_payouts.iter().map(|x| &x.outcomes).collect();
+        joint_outcomes.sort();
+        joint_outcomes.dedup();
+
+        if joint_outcomes.len() == self.outcome_payouts.len()
+            && joint_outcomes.len() == nb_joint_outcomes
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidParameters(
+                "Joint outcomes do not each have a single associated payout.".to_string(),
+            ))
+        }
+    }
+
+    /// Returns the `RangeInfo` that matches the given set of outcomes if any.
+    pub fn get_range_info_for_outcome(
+        &self,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+        outcomes: &[(usize, &Vec<String>)],
+        adaptor_sig_start: usize,
+    ) -> Option<(OracleIndexAndPrefixLength, RangeInfo)> {
+        let groups = get_event_groups(announcements);
+        let event_outcomes = groups
+            .iter()
+            .map(|group| get_event_outcomes(announcements, group))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        let mut attested = Vec::with_capacity(groups.len());
+        let mut selection_pos = 0;
+        let mut selection_count = 1;
+
+        for group in &groups {
+            let group_outcomes: Vec<(usize, &Vec<String>)> = outcomes
+                .iter()
+                .filter(|x| group.contains(&x.0))
+                .cloned()
+                .collect();
+            let (outcome, mut actual_combination) = get_majority_combination(&group_outcomes)?;
+
+            if actual_combination.len() < threshold {
+                return None;
+            }
+
+            actual_combination.truncate(threshold);
+            let mut local_combination: Vec<usize> = actual_combination
+                .iter()
+                .map(|x| group.iter().position(|y| y == x))
+                .collect::<Option<_>>()?;
+            local_combination.sort_unstable();
+
+            let combinations: Vec<Vec<usize>> =
+                CombinationIterator::new(group.len(), threshold).collect();
+            let comb_pos = combinations.iter().position(|x| *x == local_combination)?;
+
+            selection_pos = selection_pos * combinations.len() + comb_pos;
+            selection_count *= combinations.len();
+            attested.push((outcome, actual_combination));
+        }
+
+        let mut adaptor_index = adaptor_sig_start;
+        for (pos, outcome_payout) in self.outcome_payouts.iter().enumerate() {
+            let prefixes = get_outcome_prefixes(&event_outcomes, outcome_payout);
+            let matching: Option<Vec<usize>> = prefixes
+                .iter()
+                .zip(attested.iter())
+                .map(|(event_prefixes, (outcome, _))| {
+                    event_prefixes.iter().position(|p| outcome.starts_with(p))
+                })
+                .collect();
+
+            if let Some(matching) = matching {
+                let prefix_pos = matching
+                    .iter()
+                    .zip(prefixes.iter())
+                    .fold(0, |acc, (i, p)| acc * p.len() + i);
+                let selector = attested
+                    .iter()
+                    .zip(matching.iter().zip(prefixes.iter()))
+                    .flat_map(|((_, combination), (i, p))| {
+                        combination.iter().map(move |x| (*x, p[*i].len()))
+                    })
+                    .collect();
+                let range_info = RangeInfo {
+                    cet_index: pos,
+                    adaptor_index: adaptor_index + prefix_pos * selection_count + selection_pos,
+                };
+                return Some((selector, range_info));
+            }
+
+            adaptor_index += prefixes.iter().map(|x| x.len()).product::<usize>() * selection_count;
+        }
+
+        None
+    }
+
+    /// Verify the given set adaptor signatures.
+    pub fn verify_adaptor_info(
+        &self,
+        secp: &Secp256k1<All>,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+        fund_pubkey: &PublicKey,
+        funding_script_pubkey: &Script,
+        fund_output_value: u64,
+        cets: &[Transaction],
+        adaptor_sigs: &[EcdsaAdaptorSignature],
+        adaptor_sig_start: usize,
+    ) -> Result<usize, dlc::Error> {
+        let mut adaptor_sig_index = adaptor_sig_start;
+        let mut callback =
+            |adaptor_point: &PublicKey, cet_index: usize| -> Result<(), dlc::Error> {
+                let sig = adaptor_sigs[adaptor_sig_index];
+                adaptor_sig_index += 1;
+                dlc::verify_cet_adaptor_sig_from_point(
+                    secp,
+                    &sig,
+                    &cets[cet_index],
+                    adaptor_point,
+                    fund_pubkey,
+                    funding_script_pubkey,
+                    fund_output_value,
+                )?;
+                Ok(())
+            };
+
+        self.iter_outcomes(secp, announcements, threshold, &mut callback)?;
+
+        Ok(adaptor_sig_index)
+    }
+
+    /// Verify the given set of adaptor signature and generates the adaptor info.
+    pub fn verify_and_get_adaptor_info(
+        &self,
+        secp: &Secp256k1<All>,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+        fund_pubkey: &PublicKey,
+        funding_script_pubkey: &Script,
+        fund_output_value: u64,
+        cets: &[Transaction],
+        adaptor_sigs: &[EcdsaAdaptorSignature],
+        adaptor_sig_start: usize,
+    ) -> Result<(AdaptorInfo, usize), dlc::Error> {
+        let adaptor_sig_index = self.verify_adaptor_info(
+            secp,
+            announcements,
+            threshold,
+            fund_pubkey,
+            funding_script_pubkey,
+            fund_output_value,
+            cets,
+            adaptor_sigs,
+            adaptor_sig_start,
+        )?;
+
+        Ok((AdaptorInfo::Enum, adaptor_sig_index))
+    }
+
+    /// Generate the set of adaptor signatures and return the adaptor info.
+    pub fn get_adaptor_info(
+        &self,
+        secp: &Secp256k1<All>,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+        fund_privkey: &SecretKey,
+        funding_script_pubkey: &Script,
+        fund_output_value: u64,
+        cets: &[Transaction],
+    ) -> Result<(AdaptorInfo, Vec<EcdsaAdaptorSignature>), Error> {
+        let adaptor_sigs = self.get_adaptor_signatures(
+            secp,
+            announcements,
+            threshold,
+            cets,
+            fund_privkey,
+            funding_script_pubkey,
+            fund_output_value,
+        )?;
+
+        Ok((AdaptorInfo::Enum, adaptor_sigs))
+    }
+
+    /// Generate the set of adaptor signatures.
+    pub fn get_adaptor_signatures(
+        &self,
+        secp: &Secp256k1<All>,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+        cets: &[Transaction],
+        fund_privkey: &SecretKey,
+        funding_script_pubkey: &Script,
+        fund_output_value: u64,
+    ) -> Result<Vec<EcdsaAdaptorSignature>, Error> {
+        let mut adaptor_sigs = Vec::new();
+        let mut callback =
+            |adaptor_point: &PublicKey, cet_index: usize| -> Result<(), dlc::Error> {
+                let sig = dlc::create_cet_adaptor_sig_from_point(
+                    secp,
+                    &cets[cet_index],
+                    adaptor_point,
+                    fund_privkey,
+                    funding_script_pubkey,
+                    fund_output_value,
+                )?;
+                adaptor_sigs.push(sig);
+                Ok(())
+            };
+
+        self.iter_outcomes(secp, announcements, threshold, &mut callback)?;
+
+        Ok(adaptor_sigs)
+    }
+
+    /// Returns the sets of oracles that can be used to close the contract,
+    /// each set containing `threshold` oracles for each event. The sets are
+    /// ordered with the combinations of the last event varying the fastest.
+    fn get_selections(announcements: &[OracleAnnouncement], threshold: usize) -> Vec<Vec<usize>> {
+        let mut selections: Vec<Vec<usize>> = vec![Vec::new()];
+        for group in get_event_groups(announcements) {
+            let combinations: Vec<Vec<usize>> =
+                CombinationIterator::new(group.len(), threshold).collect();
+            selections = selections
+                .iter()
+                .flat_map(|selection| {
+                    combinations.iter().map(move |combination| {
+                        let mut selection = selection.clone();
+                        selection.extend(combination.iter().map(|x| group[*x]));
+                        selection
+                    })
+                })
+                .collect();
+        }
+
+        selections
+    }
+
+    /// Returns the index of the CET and the adaptor point of each adaptor
+    /// signature required for the contract, in adaptor signature order.
+    pub fn get_adaptor_points<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+    ) -> Result<Vec<(usize, PublicKey)>, dlc::Error> {
+        let mut adaptor_points = Vec::new();
+        let mut callback =
+            |adaptor_point: &PublicKey, cet_index: usize| -> Result<(), dlc::Error> {
+                adaptor_points.push((cet_index, *adaptor_point));
+                Ok(())
+            };
+
+        self.iter_outcomes(secp, announcements, threshold, &mut callback)?;
+
+        Ok(adaptor_points)
+    }
+
+    fn iter_outcomes<C: Verification, F>(
+        &self,
+        secp: &Secp256k1<C>,
+        announcements: &[OracleAnnouncement],
+        threshold: usize,
+        callback: &mut F,
+    ) -> Result<(), dlc::Error>
+    where
+        F: FnMut(&PublicKey, usize) -> Result<(), dlc::Error>,
+    {
+        let groups = get_event_groups(announcements);
+        let event_outcomes = groups
+            .iter()
+            .map(|group| get_event_outcomes(announcements, group))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| dlc::Error::InvalidArgument)?;
+        let mut event_indexes = vec![0; announcements.len()];
+        for (i, group) in groups.iter().enumerate() {
+            for x in group {
+                event_indexes[*x] = i;
+            }
+        }
+        let oracle_infos: Vec<OracleInfo> = announcements.iter().map(|x| x.into()).collect();
+        let selections = Self::get_selections(announcements, threshold);
+
+        for (i, outcome_payout) in self.outcome_payouts.iter().enumerate() {
+            if outcome_payout.outcomes.len() != groups.len() {
+                return Err(dlc::Error::InvalidArgument);
+            }
+            let event_messages: Vec<Vec<Vec<Message>>> =
+                get_outcome_prefixes(&event_outcomes, outcome_payout)
+                    .iter()
+                    .map(|prefixes| {
+                        prefixes
+                            .iter()
+                            .map(|prefix| {
+                                prefix
+                                    .iter()
+                                    .map(|x| {
+                                        Message::from_hashed_data::<
+                                            secp256k1_zkp::hashes::sha256::Hash,
+                                        >(x.as_bytes())
+                                    })
+                                    .collect()
+                            })
+                            .collect()
+                    })
+                    .collect();
+            let prefix_lens: Vec<usize> = event_messages.iter().map(|x| x.len()).collect();
+            for prefix_combination in get_index_combinations(&prefix_lens) {
+                for selection in &selections {
+                    let cur_oracle_infos: Vec<_> =
+                        selection.iter().map(|x| oracle_infos[*x].clone()).collect();
+                    let messages: Vec<Vec<Message>> = selection
+                        .iter()
+                        .map(|x| {
+                            let event_index = event_indexes[*x];
+                            event_messages[event_index][prefix_combination[event_index]].clone()
+                        })
+                        .collect();
+                    let adaptor_point = dlc::get_adaptor_point_from_oracle_info(
+                        secp,
+                        &cur_oracle_infos,
+                        &messages,
+                    )?;
+                    callback(&adaptor_point, i)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dlc_messages::oracle_msgs::{
+        DigitDecompositionEventDescriptor, EnumEventDescriptor, OracleEvent,
+    };
+    use secp256k1_zkp::{schnorr::Signature, XOnlyPublicKey, SECP256K1};
+
+    fn get_announcement(event_id: &str, outcomes: &[&str]) -> OracleAnnouncement {
+        let xonly_pk = XOnlyPublicKey::from_slice(&[
+            0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+            0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b,
+            0x16, 0xf8, 0x17, 0x98,
+        ])
+        .unwrap();
+        OracleAnnouncement {
+            announcement_signature: Signature::from_slice(&[0; 64]).unwrap(),
+            oracle_public_key: xonly_pk,
+            oracle_event: OracleEvent {
+                oracle_nonces: vec![xonly_pk],
+                event_maturity_epoch: 1,
+                event_descriptor: EventDescriptor::EnumEvent(EnumEventDescriptor {
+                    outcomes: outcomes.iter().map(|x| x.to_string()).collect(),
+                }),
+                event_id: event_id.to_string(),
+            },
+        }
+    }
+
+    fn get_numerical_announcement(event_id: &str, nb_digits: u16) -> OracleAnnouncement {
+        let mut announcement = get_announcement(event_id, &[]);
+        let nonce = announcement.oracle_event.oracle_nonces[0];
+        announcement.oracle_event.oracle_nonces = vec![nonce; nb_digits as usize];
+        announcement.oracle_event.event_descriptor =
+            EventDescriptor::DigitDecompositionEvent(DigitDecompositionEventDescriptor {
+                base: 2,
+                is_signed: false,
+                unit: "btc/usd".to_string(),
+                precision: 0,
+                nb_digits,
+            });
+        announcement
+    }
+
+    fn get_numerical_descriptor(ranges: &[(u64, u64)]) -> MultiEventDescriptor {
+        let mut outcome_payouts = Vec::new();
+        for outcome in ["a", "b"] {
+            for (start, end) in ranges {
+                outcome_payouts.push(MultiEventOutcomePayout {
+                    outcomes: vec![
+                        EventOutcome::Enum(outcome.to_string()),
+                        EventOutcome::Range {
+                            start: *start,
+                            end: *end,
+                        },
+                    ],
+                    payout: Payout {
+                        offer: outcome_payouts.len() as u64,
+                        accept: 0,
+                    },
+                });
+            }
+        }
+        MultiEventDescriptor { outcome_payouts }
+    }
+
+    fn get_numerical_announcements() -> Vec<OracleAnnouncement> {
+        vec![
+            get_announcement("e1", &["a", "b"]),
+            get_numerical_announcement("e2", 3),
+        ]
+    }
+
+    fn get_descriptor() -> MultiEventDescriptor {
+        let joint = [["a", "c"], ["a", "d"], ["b", "c"], ["b", "d"]];
+        MultiEventDescriptor {
+            outcome_payouts: joint
+                .iter()
+                .enumerate()
+                .map(|(i, x)| MultiEventOutcomePayout {
+                    outcomes: x
+                        .iter()
+                        .map(|y| EventOutcome::Enum(y.to_string()))
+                        .collect(),
+                    payout: Payout {
+                        offer: i as u64,
+                        accept: 3 - i as u64,
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn get_announcements() -> Vec<OracleAnnouncement> {
+        vec![
+            get_announcement("e1", &["a", "b"]),
+            get_announcement("e1", &["b", "a"]),
+            get_announcement("e2", &["c", "d"]),
+            get_announcement("e2", &["c", "d"]),
+        ]
+    }
+
+    #[test]
+    fn get_event_groups_test() {
+        let mut announcements = get_announcements();
+        announcements.swap(1, 2);
+        assert_eq!(
+            vec![vec![0, 2], vec![1, 3]],
+            get_event_groups(&announcements)
+        );
+    }
+
+    #[test]
+    fn valid_descriptor_is_valid() {
+        get_descriptor()
+            .validate(&get_announcements(), 2)
+            .expect("the descriptor to be valid");
+    }
+
+    #[test]
+    fn descriptor_with_missing_joint_outcome_is_not_valid() {
+        let mut descriptor = get_descriptor();
+        descriptor.outcome_payouts.pop();
+        descriptor
+            .validate(&get_announcements(), 1)
+            .expect_err("the descriptor to be invalid");
+    }
+
+    #[test]
+    fn descriptor_with_duplicate_joint_outcome_is_not_valid() {
+        let mut descriptor = get_descriptor();
+        descriptor.outcome_payouts[3].outcomes = descriptor.outcome_payouts[0].outcomes.clone();
+        descriptor
+            .validate(&get_announcements(), 1)
+            .expect_err("the descriptor to be invalid");
+    }
+
+    #[test]
+    fn descriptor_with_threshold_larger_than_event_oracles_is_not_valid() {
+        get_descriptor()
+            .validate(&get_announcements(), 3)
+            .expect_err("the descriptor to be invalid");
+    }
+
+    #[test]
+    fn get_range_info_for_outcome_test() {
+        let announcements = get_announcements();
+        let outcomes_b = vec!["b".to_string()];
+        let outcomes_d = vec!["d".to_string()];
+        let attestations = vec![(1, &outcomes_b), (2, &outcomes_d), (3, &outcomes_d)];
+
+        let (selector, range_info) = get_descriptor()
+            .get_range_info_for_outcome(&announcements, 1, &attestations, 0)
+            .expect("to find a range info");
+
+        // Two combinations per event, so four selections per outcome, with
+        // the second oracle of the first event and first oracle of the second
+        // event giving the third selection.
+        assert_eq!(vec![(1, 1), (2, 1)], selector);
+        assert_eq!(3, range_info.cet_index);
+        assert_eq!(4 * 3 + 2, range_info.adaptor_index);
+        assert_eq!(
+            vec![vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3]],
+            MultiEventDescriptor::get_selections(&announcements, 1)
+        );
+    }
+
+    #[test]
+    fn get_range_info_for_outcome_missing_event_test() {
+        let outcomes_b = vec!["b".to_string()];
+        assert!(get_descriptor()
+            .get_range_info_for_outcome(&get_announcements(), 1, &[(0, &outcomes_b)], 0)
+            .is_none());
+    }
+
+    #[test]
+    fn adaptor_signatures_verify_test() {
+        use bitcoin::{absolute::LockTime, ScriptBuf};
+        let announcements = get_announcements();
+        let descriptor = get_descriptor();
+        let fund_privkey = SecretKey::from_slice(&[1; 32]).unwrap();
+        let fund_pubkey = PublicKey::from_secret_key(SECP256K1, &fund_privkey);
+        let funding_script_pubkey = ScriptBuf::new();
+        let cets: Vec<Transaction> = (0..4)
+            .map(|i| Transaction {
+                version: 2,
+                lock_time: LockTime::from_consensus(i),
+                input: vec![Default::default()],
+                output: vec![],
+            })
+            .collect();
+        let secp = Secp256k1::new();
+        let (_, adaptor_sigs) = descriptor
+            .get_adaptor_info(
+                &secp,
+                &announcements,
+                1,
+                &fund_privkey,
+                &funding_script_pubkey,
+                1000,
+                &cets,
+            )
+            .unwrap();
+
+        assert_eq!(16, adaptor_sigs.len());
+        let end = descriptor
+            .verify_adaptor_info(
+                &secp,
+                &announcements,
+                1,
+                &fund_pubkey,
+                &funding_script_pubkey,
+                1000,
+                &cets,
+                &adaptor_sigs,
+                0,
+            )
+            .expect("the adaptor signatures to be valid");
+        assert_eq!(16, end);
+    }
+
+    #[test]
+    fn numerical_descriptor_is_valid() {
+        get_numerical_descriptor(&[(0, 2), (3, 7)])
+            .validate(&get_numerical_announcements(), 1)
+            .expect("the descriptor to be valid");
+    }
+
+    #[test]
+    fn numerical_descriptor_with_invalid_ranges_is_not_valid() {
+        let announcements = get_numerical_announcements();
+        for ranges in [
+            vec![(0, 2), (4, 7)],
+            vec![(0, 3), (3, 7)],
+            vec![(0, 2), (3, 8)],
+            vec![(0, 7)],
+        ] {
+            get_numerical_descriptor(&ranges)
+                .validate(&announcements, 1)
+                .expect_err("the descriptor to be invalid");
+        }
+    }
+
+    #[test]
+    fn numerical_descriptor_with_enum_outcome_for_numerical_event_is_not_valid() {
+        let mut descriptor = get_numerical_descriptor(&[(0, 2), (3, 7)]);
+        descriptor.outcome_payouts[0].outcomes[1] = EventOutcome::Enum("0".to_string());
+        descriptor
+            .validate(&get_numerical_announcements(), 1)
+            .expect_err("the descriptor to be invalid");
+    }
+
+    #[test]
+    fn numerical_adaptor_signatures_match_range_info_test() {
+        use bitcoin::{absolute::LockTime, ScriptBuf};
+        let announcements = get_numerical_announcements();
+        let descriptor = get_numerical_descriptor(&[(0, 2), (3, 7)]);
+        let fund_privkey = SecretKey::from_slice(&[1; 32]).unwrap();
+        let fund_pubkey = PublicKey::from_secret_key(SECP256K1, &fund_privkey);
+        let funding_script_pubkey = ScriptBuf::new();
+        let cets: Vec<Transaction> = (0..4)
+            .map(|i| Transaction {
+                version: 2,
+                lock_time: LockTime::from_consensus(i),
+                input: vec![Default::default()],
+                output: vec![],
+            })
+            .collect();
+        let secp = Secp256k1::new();
+        let (_, adaptor_sigs) = descriptor
+            .get_adaptor_info(
+                &secp,
+                &announcements,
+                1,
+                &fund_privkey,
+                &funding_script_pubkey,
+                1000,
+                &cets,
+            )
+            .unwrap();
+
+        // Each range is covered by two digit prefixes: [0, 0] and [0, 1, 0]
+        // for the first one, [0, 1, 1] and [1] for the second one.
+        assert_eq!(8, adaptor_sigs.len());
+
+        let to_strings = |x: &[&str]| x.iter().map(|y| y.to_string()).collect::<Vec<_>>();
+        let outcome_b = to_strings(&["b"]);
+        let outcome_a = to_strings(&["a"]);
+        let value_5 = to_strings(&["1", "0", "1"]);
+        let value_2 = to_strings(&["0", "1", "0"]);
+        let oracle_infos: Vec<OracleInfo> = announcements.iter().map(|x| x.into()).collect();
+
+        for (attestations, cet_index, adaptor_index, selector) in [
+            (
+                vec![(0, &outcome_b), (1, &value_5)],
+                3,
+                7,
+                vec![(0, 1), (1, 1)],
+            ),
+            (
+                vec![(0, &outcome_a), (1, &value_2)],
+                0,
+                1,
+                vec![(0, 1), (1, 3)],
+            ),
+        ] {
+            let (actual_selector, range_info) = descriptor
+                .get_range_info_for_outcome(&announcements, 1, &attestations, 0)
+                .expect("to find a range info");
+            assert_eq!(selector, actual_selector);
+            assert_eq!(cet_index, range_info.cet_index);
+            assert_eq!(adaptor_index, range_info.adaptor_index);
+
+            let messages: Vec<Vec<Message>> = actual_selector
+                .iter()
+                .map(|(oracle, prefix_len)| {
+                    attestations[*oracle].1[..*prefix_len]
+                        .iter()
+                        .map(|x| {
+                            Message::from_hashed_data::<secp256k1_zkp::hashes::sha256::Hash>(
+                                x.as_bytes(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect();
+            let adaptor_point =
+                dlc::get_adaptor_point_from_oracle_info(&secp, &oracle_infos, &messages).unwrap();
+            dlc::verify_cet_adaptor_sig_from_point(
+                &secp,
+                &adaptor_sigs[range_info.adaptor_index],
+                &cets[range_info.cet_index],
+                &adaptor_point,
+                &fund_pubkey,
+                &funding_script_pubkey,
+                1000,
+            )
+            .expect("the adaptor signature to match the attested outcome");
+        }
+    }
+}
@@ -183,4 +183,44 @@ impl NumericalDescriptor {
             }
         }
     }
+
+    /// Generate the adaptor info and return it together with the index of the
+    /// CET and the adaptor point of each adaptor signature it requires, in
+    /// adaptor signature order.
+    pub fn get_adaptor_info_and_points(
+        &self,
+        total_collateral: u64,
+        threshold: usize,
+        precomputed_points: &[Vec<Vec<PublicKey>>],
+        adaptor_index_start: usize,
+    ) -> Result<(AdaptorInfo, Vec<(usize, PublicKey)>), Error> {
+        match &self.difference_params {
+            Some(params) => {
+                let mut multi_trie = MultiOracleTrieWithDiff::new(
+                    &self.oracle_numeric_infos,
+                    threshold,
+                    params.min_support_exp,
+                    params.max_error_exp,
+                )?;
+                let adaptor_points = multi_trie.generate_adaptor_points(
+                    &self.get_range_payouts(total_collateral)?,
+                    precomputed_points,
+                    adaptor_index_start,
+                )?;
+                Ok((
+                    AdaptorInfo::NumericalWithDifference(multi_trie),
+                    adaptor_points,
+                ))
+            }
+            None => {
+                let mut trie = MultiOracleTrie::new(&self.oracle_numeric_infos, threshold)?;
+                let adaptor_points = trie.generate_adaptor_points(
+                    &self.get_range_payouts(total_collateral)?,
+                    precomputed_points,
+                    adaptor_index_start,
+                )?;
+                Ok((AdaptorInfo::Numerical(trie), adaptor_points))
+            }
+        }
+    }
 }
@@ -9,9 +9,12 @@ use super::contract_info::ContractInfo;
 use super::contract_input::ContractInput;
 use super::ContractDescriptor;
 use crate::{ContractId, KeysId};
-use dlc::PartyParams;
+use dlc::{DlcTransactionsOptions, FeePayerParams, FundingOutputType, PartyParams};
 use dlc_messages::oracle_msgs::OracleAnnouncement;
-use dlc_messages::{FundingInput, OfferDlc};
+use dlc_messages::{
+    FeePayerInfo, FundingInput, OfferDlc, TaprootFundingNonces, ANCHOR_OUTPUTS_CONTRACT_FLAG,
+    SINGLE_FUNDED_CONTRACT_FLAG, TAPROOT_FUNDING_CONTRACT_FLAG,
+};
 use secp256k1_zkp::PublicKey;
 
 /// Contains information about a contract that was offered.
@@ -45,10 +48,67 @@ pub struct OfferedContract {
     pub cet_locktime: u32,
     /// The time at which the contract becomes refundable.
     pub refund_locktime: u32,
+    /// Whether the CETs and refund transaction include an anchor output for
+    /// each party.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub anchor_outputs: bool,
+    /// The temporary id of the batch the contract is part of, if it is funded
+    /// together with other contracts by a single funding transaction.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub batch_id: Option<[u8; 32]>,
+    /// Whether the contract is funded by the offer party only.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub single_funded: bool,
+    /// Information about a third party paying the fees of the contract
+    /// transactions, if any.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub fee_payer: Option<FeePayerInfo>,
+    /// The id of the contract whose funding transaction is replaced by the one
+    /// of this contract to bump its fee, if any.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub replaced_contract_id: Option<ContractId>,
+    /// The id of the contract that was rolled over into this one, if any.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub rolled_over_contract_id: Option<ContractId>,
+    /// The unix timestamp after which the offer cannot be accepted anymore, if
+    /// any.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub offer_expiry: Option<u64>,
+    /// The type of output locking the collateral of both parties in the fund
+    /// transaction.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub funding_output_type: FundingOutputType,
+    /// The MuSig2 public nonces of the offer party for the CETs and refund
+    /// transaction, set for contracts funded with a taproot output.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub taproot_funding_nonces: Option<TaprootFundingNonces>,
+    /// The MuSig2 secret nonces matching the public nonces of the offer party,
+    /// set on the offer party side for contracts funded with a taproot output
+    /// until they are used to sign the contract.
+    #[cfg_attr(feature = "use-serde", serde(skip))]
+    pub taproot_secret_nonces: Option<TaprootSecretNonces>,
     /// Keys Id for generating the signers
     pub(crate) keys_id: KeysId,
 }
 
+/// The serialized MuSig2 secret nonces of the offer party for the CETs and
+/// refund transaction of a contract funded with a taproot output, see
+/// [`dlc::taproot::SecretNonce::serialize`].
+#[derive(Clone)]
+pub struct TaprootSecretNonces {
+    /// The nonces for the CET adaptor signatures, in adaptor signature order.
+    pub cet_nonces: Vec<[u8; 64]>,
+    /// The nonce for the refund transaction signature.
+    pub refund_nonce: [u8; 64],
+}
+
+impl std::fmt::Debug for TaprootSecretNonces {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaprootSecretNonces")
+            .finish_non_exhaustive()
+    }
+}
+
 impl OfferedContract {
     /// Validate that the contract info covers all the possible outcomes that
     /// can be attested by the oracle(s).
@@ -57,11 +117,18 @@ impl OfferedContract {
             crate::error::Error::InvalidParameters("Fee rate is too high".to_string())
         })?;
 
+        if self.single_funded && self.total_collateral != self.offer_params.collateral {
+            return Err(crate::error::Error::InvalidParameters(
+                "Accept collateral must be zero for single funded contracts".to_string(),
+            ));
+        }
+
         for info in &self.contract_info {
             info.validate()?;
             let payouts = match &info.contract_descriptor {
                 ContractDescriptor::Enum(e) => e.get_payouts(),
                 ContractDescriptor::Numerical(e) => e.get_payouts(self.total_collateral)?,
+                ContractDescriptor::MultiEvent(e) => e.get_payouts(),
             };
             let valid = payouts
                 .iter()
@@ -76,7 +143,56 @@ impl OfferedContract {
         Ok(())
     }
 
-    /// Creates a new [`OfferedContract`] from the given parameters.
+    /// Returns the options to use to create the transactions of the contract.
+    pub fn get_dlc_transactions_options(&self) -> DlcTransactionsOptions {
+        DlcTransactionsOptions {
+            anchor_outputs: self.anchor_outputs,
+            single_funded: self.single_funded,
+            funding_output_type: self.funding_output_type,
+        }
+    }
+
+    /// Returns whether the offer expired at the given unix timestamp.
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.offer_expiry.map_or(false, |expiry| expiry <= now)
+    }
+
+    /// Returns the parameters of the third party paying the fees of the
+    /// contract transactions, if any.
+    pub fn get_fee_payer_params(
+        &self,
+    ) -> Result<Option<FeePayerParams>, crate::conversion_utils::Error> {
+        self.fee_payer
+            .as_ref()
+            .map(|fee_payer| {
+                let (inputs, input_amount) = get_tx_input_infos(&fee_payer.funding_inputs)?;
+                Ok(FeePayerParams {
+                    inputs,
+                    input_amount,
+                    change_script_pubkey: fee_payer.change_spk.clone(),
+                    change_serial_id: fee_payer.change_serial_id,
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns the flags of the offer message of the contract.
+    pub(crate) fn get_contract_flags(&self) -> u8 {
+        let mut contract_flags = 0;
+        if self.anchor_outputs {
+            contract_flags |= ANCHOR_OUTPUTS_CONTRACT_FLAG;
+        }
+        if self.single_funded {
+            contract_flags |= SINGLE_FUNDED_CONTRACT_FLAG;
+        }
+        if self.funding_output_type == FundingOutputType::Taproot {
+            contract_flags |= TAPROOT_FUNDING_CONTRACT_FLAG;
+        }
+        contract_flags
+    }
+
+    /// Creates a new [`OfferedContract`] from the given parameters. The refund
+    /// delay of the contract input takes precedence over `refund_delay` if set.
     pub fn new(
         id: ContractId,
         contract: &ContractInput,
@@ -116,7 +232,17 @@ impl OfferedContract {
             fund_output_serial_id,
             fee_rate_per_vb: contract.fee_rate,
             cet_locktime,
-            refund_locktime: latest_maturity + refund_delay,
+            refund_locktime: latest_maturity + contract.refund_delay.unwrap_or(refund_delay),
+            anchor_outputs: contract.anchor_outputs,
+            batch_id: None,
+            single_funded: contract.single_funded,
+            fee_payer: None,
+            replaced_contract_id: None,
+            rolled_over_contract_id: None,
+            offer_expiry: contract.offer_expiry,
+            funding_output_type: contract.funding_output_type,
+            taproot_funding_nonces: None,
+            taproot_secret_nonces: None,
             counter_party: *counter_party,
             keys_id,
         }
@@ -148,6 +274,20 @@ impl OfferedContract {
             },
             cet_locktime: offer_dlc.cet_locktime,
             refund_locktime: offer_dlc.refund_locktime,
+            anchor_outputs: offer_dlc.contract_flags & ANCHOR_OUTPUTS_CONTRACT_FLAG != 0,
+            batch_id: None,
+            single_funded: offer_dlc.contract_flags & SINGLE_FUNDED_CONTRACT_FLAG != 0,
+            fee_payer: offer_dlc.fee_payer.clone(),
+            replaced_contract_id: None,
+            rolled_over_contract_id: None,
+            offer_expiry: offer_dlc.offer_expiry,
+            funding_output_type: if offer_dlc.contract_flags & TAPROOT_FUNDING_CONTRACT_FLAG != 0 {
+                FundingOutputType::Taproot
+            } else {
+                FundingOutputType::P2wsh
+            },
+            taproot_funding_nonces: offer_dlc.taproot_funding_nonces.clone(),
+            taproot_secret_nonces: None,
             fee_rate_per_vb: offer_dlc.fee_rate_per_vb,
             fund_output_serial_id: offer_dlc.fund_output_serial_id,
             funding_inputs: offer_dlc.funding_inputs.clone(),
@@ -163,7 +303,7 @@ impl From<&OfferedContract> for OfferDlc {
         OfferDlc {
             protocol_version: PROTOCOL_VERSION,
             temporary_contract_id: offered_contract.id,
-            contract_flags: 0,
+            contract_flags: offered_contract.get_contract_flags(),
             chain_hash: BITCOIN_CHAINHASH,
             contract_info: offered_contract.into(),
             funding_pubkey: offered_contract.offer_params.fund_pubkey,
@@ -177,6 +317,9 @@ impl From<&OfferedContract> for OfferDlc {
             refund_locktime: offered_contract.refund_locktime,
             fee_rate_per_vb: offered_contract.fee_rate_per_vb,
             fund_output_serial_id: offered_contract.fund_output_serial_id,
+            fee_payer: offered_contract.fee_payer.clone(),
+            offer_expiry: offered_contract.offer_expiry,
+            taproot_funding_nonces: offered_contract.taproot_funding_nonces.clone(),
         }
     }
 }
@@ -4,21 +4,24 @@
 use crate::contract::accepted_contract::AcceptedContract;
 use crate::contract::contract_info::ContractInfo;
 use crate::contract::enum_descriptor::EnumDescriptor;
+use crate::contract::multi_event_descriptor::{MultiEventDescriptor, MultiEventOutcomePayout};
 use crate::contract::numerical_descriptor::{DifferenceParams, NumericalDescriptor};
-use crate::contract::offered_contract::OfferedContract;
-use crate::contract::signed_contract::SignedContract;
+use crate::contract::offered_contract::{OfferedContract, TaprootSecretNonces};
+use crate::contract::signed_contract::{PendingCloseOffer, SignedContract, TaprootSignatures};
 use crate::contract::AdaptorInfo;
 use crate::contract::{
-    ClosedContract, ContractDescriptor, FailedAcceptContract, FailedSignContract, PreClosedContract,
+    ClosedContract, CollaborativelyClosedContract, ContractDescriptor, FailedAcceptContract,
+    FailedSignContract, PreClosedContract,
 };
 use crate::payout_curve::{
     HyperbolaPayoutCurvePiece, PayoutFunction, PayoutFunctionPiece, PayoutPoint,
     PolynomialPayoutCurvePiece, RoundingInterval, RoundingIntervals,
 };
-use dlc::DlcTransactions;
+use dlc::{DlcTransactions, FundingOutputType};
 use dlc_messages::ser_impls::{
-    read_ecdsa_adaptor_signatures, read_option_cb, read_usize, read_vec, read_vec_cb,
-    write_ecdsa_adaptor_signatures, write_option_cb, write_usize, write_vec, write_vec_cb,
+    read_ecdsa_adaptor_signatures, read_option_cb, read_schnorr_adaptor_signature, read_schnorrsig,
+    read_usize, read_vec, read_vec_cb, write_ecdsa_adaptor_signatures, write_option_cb,
+    write_schnorr_adaptor_signature, write_schnorrsig, write_usize, write_vec, write_vec_cb,
 };
 use dlc_trie::digit_trie::{DigitNodeData, DigitTrieDump};
 use dlc_trie::multi_oracle_trie::{MultiOracleTrie, MultiOracleTrieDump};
@@ -78,7 +81,7 @@ impl_dlc_writeable!(HyperbolaPayoutCurvePiece, {
     (c, float),
     (d, float)
 });
-impl_dlc_writeable_enum!(ContractDescriptor, (0, Enum), (1, Numerical);;;);
+impl_dlc_writeable_enum!(ContractDescriptor, (0, Enum), (1, Numerical), (2, MultiEvent);;;);
 impl_dlc_writeable!(ContractInfo, { (contract_descriptor, writeable), (oracle_announcements, vec), (threshold, usize)});
 impl_dlc_writeable!(EnumDescriptor, {
     (
@@ -86,9 +89,24 @@ impl_dlc_writeable!(EnumDescriptor, {
         {vec_cb, dlc_messages::ser_impls::enum_payout::write, dlc_messages::ser_impls::enum_payout::read}
     )
 });
+impl_dlc_writeable!(MultiEventOutcomePayout, {
+    (outcomes, vec),
+    (payout, {cb_writeable, dlc_messages::ser_impls::payout::write, dlc_messages::ser_impls::payout::read})
+});
+impl_dlc_writeable!(MultiEventDescriptor, { (outcome_payouts, vec) });
 impl_dlc_writeable!(OfferedContract, {
     (id, writeable),
     (is_offer_party, writeable),
+    (anchor_outputs, writeable),
+    (batch_id, option),
+    (single_funded, writeable),
+    (fee_payer, option),
+    (replaced_contract_id, option),
+    (rolled_over_contract_id, option),
+    (offer_expiry, option),
+    (funding_output_type, {cb_writeable, write_funding_output_type, read_funding_output_type}),
+    (taproot_funding_nonces, option),
+    (taproot_secret_nonces, option),
     (contract_info, vec),
     (offer_params, { cb_writeable, dlc_messages::ser_impls::party_params::write, dlc_messages::ser_impls::party_params::read }),
     (total_collateral, writeable),
@@ -115,15 +133,29 @@ impl_dlc_writeable!(AcceptedContract, {
     (funding_inputs, vec),
     (adaptor_infos, vec),
     (adaptor_signatures, {option_cb, write_ecdsa_adaptor_signatures, read_ecdsa_adaptor_signatures }),
-    (accept_refund_signature, writeable),
+    (accept_refund_signature, option),
     (dlc_transactions, {cb_writeable, dlc_transactions::write, dlc_transactions::read })
 });
 impl_dlc_writeable!(SignedContract, {
     (accepted_contract, writeable),
     (adaptor_signatures, {option_cb, write_ecdsa_adaptor_signatures, read_ecdsa_adaptor_signatures }),
-    (offer_refund_signature, writeable),
+    (offer_refund_signature, option),
     (funding_signatures, writeable),
-    (channel_id, option)
+    (channel_id, option),
+    (close_offer, option),
+    (taproot_signatures, option)
+});
+impl_dlc_writeable!(TaprootSecretNonces, { (cet_nonces, vec), (refund_nonce, writeable) });
+impl_dlc_writeable!(TaprootSignatures, {
+    (cet_adaptor_signatures, {vec_cb, write_schnorr_adaptor_signature, read_schnorr_adaptor_signature}),
+    (refund_signature, {cb_writeable, write_schnorrsig, read_schnorrsig})
+});
+impl_dlc_writeable!(PendingCloseOffer, {
+    (is_offer_party, writeable),
+    (counter_payout, writeable),
+    (offer_signature, writeable),
+    (close_tx, writeable),
+    (timeout, writeable)
 });
 impl_dlc_writeable!(PreClosedContract, {
     (signed_contract, writeable),
@@ -138,6 +170,13 @@ impl_dlc_writeable!(ClosedContract, {
     (counter_party_id, writeable),
     (pnl, i64)
 });
+impl_dlc_writeable!(CollaborativelyClosedContract, {
+    (contract_id, writeable),
+    (temporary_contract_id, writeable),
+    (counter_party_id, writeable),
+    (close_tx, writeable),
+    (pnl, i64)
+});
 impl_dlc_writeable!(FailedAcceptContract, {(offered_contract, writeable), (accept_message, writeable), (error_message, string)});
 impl_dlc_writeable!(FailedSignContract, {(accepted_contract, writeable), (sign_message, writeable), (error_message, string)});
 
@@ -156,6 +195,26 @@ impl_dlc_writeable_external!(MultiTrieDump<RangeInfo>, multi_trie_dump, { (node_
 impl_dlc_writeable_external!(MultiOracleTrieWithDiffDump, multi_oracle_trie_with_diff_dump, { (multi_trie_dump, {cb_writeable, multi_trie_dump::write, multi_trie_dump::read}), (oracle_numeric_infos, {cb_writeable, oracle_params::write, oracle_params::read}) });
 impl_dlc_writeable_external!(TrieNodeInfo, trie_node_info, { (trie_index, usize), (store_index, usize) });
 
+fn write_funding_output_type<W: Writer>(
+    funding_output_type: &FundingOutputType,
+    writer: &mut W,
+) -> Result<(), lightning::io::Error> {
+    let id: u8 = match funding_output_type {
+        FundingOutputType::P2wsh => 0,
+        FundingOutputType::Taproot => 1,
+    };
+    id.write(writer)
+}
+
+fn read_funding_output_type<R: Read>(reader: &mut R) -> Result<FundingOutputType, DecodeError> {
+    let id: u8 = Readable::read(reader)?;
+    match id {
+        0 => Ok(FundingOutputType::P2wsh),
+        1 => Ok(FundingOutputType::Taproot),
+        _ => Err(DecodeError::UnknownRequiredFeature),
+    }
+}
+
 fn write_digit_node_data_trie<W: Writer>(
     input: &DigitNodeData<Vec<TrieNodeInfo>>,
     writer: &mut W,
@@ -1,14 +1,17 @@
 //! #SignedContract
 
 use crate::conversion_utils::PROTOCOL_VERSION;
+use crate::error::Error;
 use crate::ChannelId;
 
 use super::accepted_contract::AcceptedContract;
-use dlc_messages::CetAdaptorSignature;
-use dlc_messages::CetAdaptorSignatures;
+use bitcoin::Transaction;
+use dlc::schnorr_adaptor::SchnorrAdaptorSignature;
 use dlc_messages::FundingSignatures;
 use dlc_messages::SignDlc;
+use dlc_messages::{SchnorrCetAdaptorSignature, TaprootSignSignatures};
 use secp256k1_zkp::ecdsa::Signature;
+use secp256k1_zkp::schnorr::Signature as SchnorrSignature;
 use secp256k1_zkp::EcdsaAdaptorSignature;
 
 /// Contain information about a contract that was fully signed.
@@ -18,32 +21,82 @@ pub struct SignedContract {
     pub accepted_contract: AcceptedContract,
     /// The adaptor signatures of the offering party (None if offering party).
     pub adaptor_signatures: Option<Vec<EcdsaAdaptorSignature>>,
-    /// The refund signature of the offering party.
-    pub offer_refund_signature: Signature,
+    /// The refund signature of the offering party, None for contracts funded
+    /// with a taproot output.
+    pub offer_refund_signature: Option<Signature>,
     /// The signatures for the funding inputs of the offering party.
     pub funding_signatures: FundingSignatures,
     /// The [`ChannelId`] to which the contract was associated if any.
     pub channel_id: Option<ChannelId>,
+    /// The pending offer to collaboratively close the contract if any.
+    pub close_offer: Option<PendingCloseOffer>,
+    /// The signatures for the CETs and refund transaction of a contract funded
+    /// with a taproot output, in which case the ECDSA adaptor and refund
+    /// signatures are not set.
+    pub taproot_signatures: Option<TaprootSignatures>,
+}
+
+/// The signatures for the CETs and refund transaction of a contract funded with
+/// a taproot output, aggregating the MuSig2 partial signatures of both parties.
+#[derive(Clone)]
+pub struct TaprootSignatures {
+    /// The Schnorr adaptor signatures for the CETs, in adaptor signature order.
+    pub cet_adaptor_signatures: Vec<SchnorrAdaptorSignature>,
+    /// The key path signature for the refund transaction.
+    pub refund_signature: SchnorrSignature,
+}
+
+/// Information about an offer to collaboratively close a contract that was
+/// sent or received but not yet accepted.
+#[derive(Clone)]
+pub struct PendingCloseOffer {
+    /// Whether the local party is the one that offered to close the contract.
+    pub is_offer_party: bool,
+    /// The payout of the party receiving the offer.
+    pub counter_payout: u64,
+    /// The signature of the offering party for the close transaction.
+    pub offer_signature: Signature,
+    /// The close transaction, without the witness.
+    pub close_tx: Transaction,
+    /// The time after which the offer can no longer be accepted.
+    pub timeout: u64,
 }
 
 impl SignedContract {
+    /// Returns the refund signature of the offering party, or an error for
+    /// contracts funded with a taproot output.
+    pub(crate) fn get_offer_refund_signature(&self) -> Result<Signature, Error> {
+        self.offer_refund_signature.ok_or_else(|| {
+            Error::InvalidState("Missing refund signature of the offer party.".to_string())
+        })
+    }
+
     pub(crate) fn get_sign_dlc(
         &self,
         cet_adaptor_signatures: Vec<EcdsaAdaptorSignature>,
     ) -> SignDlc {
         let contract_id = self.accepted_contract.get_contract_id();
 
+        let taproot_signatures =
+            self.taproot_signatures
+                .as_ref()
+                .map(|taproot_signatures| TaprootSignSignatures {
+                    cet_adaptor_signatures: taproot_signatures
+                        .cet_adaptor_signatures
+                        .iter()
+                        .map(|x| SchnorrCetAdaptorSignature { signature: *x })
+                        .collect(),
+                    refund_signature: taproot_signatures.refund_signature,
+                });
+
         SignDlc {
             protocol_version: PROTOCOL_VERSION,
             contract_id,
-            cet_adaptor_signatures: CetAdaptorSignatures {
-                ecdsa_adaptor_signatures: cet_adaptor_signatures
-                    .into_iter()
-                    .map(|x| CetAdaptorSignature { signature: x })
-                    .collect(),
-            },
+            cet_adaptor_signatures: (&cet_adaptor_signatures as &[_]).into(),
             refund_signature: self.offer_refund_signature,
             funding_signatures: self.funding_signatures.clone(),
+            fee_payer_funding_signatures: None,
+            taproot_signatures,
         }
     }
 }
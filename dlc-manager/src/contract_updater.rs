@@ -3,49 +3,75 @@
 use std::ops::Deref;
 
 use bitcoin::psbt::PartiallySignedTransaction;
-use bitcoin::{consensus::Decodable, Script, Transaction, Witness};
-use dlc::{DlcTransactions, PartyParams};
-use dlc_messages::FundingInput;
+use bitcoin::{
+    absolute::LockTime, consensus::Decodable, OutPoint, Script, ScriptBuf, Transaction, TxIn,
+    TxOut, Witness,
+};
+use dlc::taproot::{self, PublicNonce, SecretNonce};
+use dlc::{
+    schnorr_adaptor, BatchContractParams, DlcTransactions, FundingOutputType, PartyParams, Payout,
+};
+use dlc_messages::batch::{AcceptBatchDlc, OfferBatchDlc, SignBatchDlc};
+use dlc_messages::close::{CloseAccept, CloseOffer};
+use dlc_messages::fee_bump::{FeeBumpAccept, FeeBumpOffer};
+use dlc_messages::rollover::{RolloverAccept, RolloverConfirm, RolloverOffer};
 use dlc_messages::{
     oracle_msgs::{OracleAnnouncement, OracleAttestation},
-    AcceptDlc, FundingSignature, FundingSignatures, OfferDlc, SignDlc, WitnessElement,
+    AcceptDlc, FundingNonce, FundingPartialSignature, FundingSignature, FundingSignatures,
+    OfferDlc, SignDlc, TaprootAcceptSignatures, TaprootFundingNonces, WitnessElement,
 };
+use dlc_messages::{FeePayerInfo, FundingInput};
+use dlc_trie::{combination_iterator::CombinationIterator, DlcTrie};
 use secp256k1_zkp::{
-    ecdsa::Signature, All, EcdsaAdaptorSignature, PublicKey, Secp256k1, SecretKey, Signing,
+    ecdsa::Signature, hashes::sha256, schnorr::Signature as SchnorrSignature, All,
+    EcdsaAdaptorSignature, Message, PublicKey, Secp256k1, SecretKey, Signing, Verification,
 };
 
 use crate::{
     contract::{
-        accepted_contract::AcceptedContract, contract_info::ContractInfo,
-        contract_input::ContractInput, offered_contract::OfferedContract,
-        signed_contract::SignedContract, AdaptorInfo,
+        accepted_contract::AcceptedContract,
+        contract_info::ContractInfo,
+        contract_input::ContractInput,
+        offered_contract::{OfferedContract, TaprootSecretNonces},
+        signed_contract::{PendingCloseOffer, SignedContract, TaprootSignatures},
+        AdaptorInfo, CollaborativelyClosedContract, ContractDescriptor,
     },
-    conversion_utils::get_tx_input_infos,
+    conversion_utils::{get_tx_input_infos, PROTOCOL_VERSION},
     error::Error,
-    Blockchain, ChannelId, ContractSigner, ContractSignerProvider, Time, Wallet,
+    AsyncBlockchain, AsyncWallet, ChannelId, ContractId, ContractSigner, ContractSignerProvider,
+    Time, Utxo,
 };
 
 /// Creates an [`OfferedContract`] and [`OfferDlc`] message from the provided
 /// contract and oracle information.
-pub fn offer_contract<W: Deref, B: Deref, T: Deref, X: ContractSigner, SP: Deref, C: Signing>(
-    secp: &Secp256k1<C>,
+pub async fn offer_contract<W: Deref, B: Deref, T: Deref, X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
     contract_input: &ContractInput,
     oracle_announcements: Vec<Vec<OracleAnnouncement>>,
     refund_delay: u32,
     counter_party: &PublicKey,
+    fee_payer: Option<FeePayerInfo>,
     wallet: &W,
     blockchain: &B,
     time: &T,
     signer_provider: &SP,
 ) -> Result<(OfferedContract, OfferDlc), Error>
 where
-    W::Target: Wallet,
-    B::Target: Blockchain,
+    W::Target: AsyncWallet,
+    B::Target: AsyncBlockchain,
     T::Target: Time,
     SP::Target: ContractSignerProvider<Signer = X>,
 {
     contract_input.validate()?;
 
+    if let Some(offer_expiry) = contract_input.offer_expiry {
+        if offer_expiry <= time.unix_time_now() {
+            return Err(Error::InvalidParameters(
+                "Offer expiry must be in the future.".to_string(),
+            ));
+        }
+    }
+
     let id = crate::utils::get_new_temporary_id();
     let keys_id = signer_provider.derive_signer_key_id(true, id);
     let signer = signer_provider.derive_contract_signer(keys_id)?;
@@ -53,12 +79,14 @@ where
         secp,
         contract_input.offer_collateral,
         contract_input.fee_rate,
+        &contract_input.get_dlc_transactions_options(),
         wallet,
         &signer,
         blockchain,
-    )?;
+    )
+    .await?;
 
-    let offered_contract = OfferedContract::new(
+    let mut offered_contract = OfferedContract::new(
         id,
         contract_input,
         oracle_announcements,
@@ -69,6 +97,39 @@ where
         time.unix_time_now() as u32,
         keys_id,
     );
+    offered_contract.fee_payer = fee_payer;
+
+    if offered_contract.funding_output_type == FundingOutputType::Taproot {
+        if offered_contract.fee_payer.is_some() {
+            return Err(Error::InvalidParameters(
+                "Taproot funding is not supported for contracts with a fee payer.".to_string(),
+            ));
+        }
+        let fund_secret_key = signer.get_secret_key()?;
+        let (_, adaptor_points) = get_taproot_adaptor_points(secp, &offered_contract)?;
+        let mut nonces = (0..=adaptor_points.len())
+            .map(|_| {
+                let (sec_nonce, pub_nonce) = taproot::generate_nonce(
+                    secp,
+                    crate::utils::get_new_session_rand(),
+                    &fund_secret_key,
+                    None,
+                    None,
+                )?;
+                Ok((sec_nonce.serialize(), FundingNonce::from(&pub_nonce)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let (refund_sec_nonce, refund_nonce) = nonces.pop().expect("to have a refund nonce");
+        let (cet_sec_nonces, cet_nonces) = nonces.into_iter().unzip();
+        offered_contract.taproot_funding_nonces = Some(TaprootFundingNonces {
+            cet_nonces,
+            refund_nonce,
+        });
+        offered_contract.taproot_secret_nonces = Some(TaprootSecretNonces {
+            cet_nonces: cet_sec_nonces,
+            refund_nonce: refund_sec_nonce,
+        });
+    }
 
     let offer_msg: OfferDlc = (&offered_contract).into();
 
@@ -77,7 +138,7 @@ where
 
 /// Creates an [`AcceptedContract`] and produces
 /// the accepting party's cet adaptor signatures.
-pub fn accept_contract<W: Deref, X: ContractSigner, SP: Deref, B: Deref>(
+pub async fn accept_contract<W: Deref, X: ContractSigner, SP: Deref, B: Deref>(
     secp: &Secp256k1<All>,
     offered_contract: &OfferedContract,
     wallet: &W,
@@ -85,32 +146,48 @@ pub fn accept_contract<W: Deref, X: ContractSigner, SP: Deref, B: Deref>(
     blockchain: &B,
 ) -> Result<(AcceptedContract, AcceptDlc), Error>
 where
-    W::Target: Wallet,
-    B::Target: Blockchain,
+    W::Target: AsyncWallet,
+    B::Target: AsyncBlockchain,
     SP::Target: ContractSignerProvider<Signer = X>,
 {
     let total_collateral = offered_contract.total_collateral;
 
     let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
-    let (accept_params, funding_inputs) = crate::utils::get_party_params(
-        secp,
-        total_collateral - offered_contract.offer_params.collateral,
-        offered_contract.fee_rate_per_vb,
-        wallet,
-        &signer,
-        blockchain,
-    )?;
+    let (accept_params, funding_inputs) = if offered_contract.single_funded {
+        (
+            crate::utils::get_unfunded_party_params(secp, wallet, &signer).await?,
+            Vec::new(),
+        )
+    } else {
+        crate::utils::get_party_params(
+            secp,
+            total_collateral - offered_contract.offer_params.collateral,
+            offered_contract.fee_rate_per_vb,
+            &offered_contract.get_dlc_transactions_options(),
+            wallet,
+            &signer,
+            blockchain,
+        )
+        .await?
+    };
 
-    let dlc_transactions = dlc::create_dlc_transactions(
-        &offered_contract.offer_params,
-        &accept_params,
-        &offered_contract.contract_info[0].get_payouts(total_collateral)?,
-        offered_contract.refund_locktime,
-        offered_contract.fee_rate_per_vb,
-        0,
-        offered_contract.cet_locktime,
-        offered_contract.fund_output_serial_id,
-    )?;
+    let dlc_transactions = create_dlc_transactions(secp, offered_contract, &accept_params)?;
+
+    if offered_contract.funding_output_type == FundingOutputType::Taproot {
+        let (accepted_contract, taproot_signatures) = accept_taproot_contract_internal(
+            secp,
+            offered_contract,
+            &accept_params,
+            &funding_inputs,
+            &signer.get_secret_key()?,
+            &dlc_transactions,
+        )?;
+
+        let mut accept_msg: AcceptDlc = accepted_contract.get_accept_contract_msg(&[]);
+        accept_msg.taproot_signatures = Some(taproot_signatures);
+
+        return Ok((accepted_contract, accept_msg));
+    }
 
     let fund_output_value = dlc_transactions.get_fund_output().value;
 
@@ -171,15 +248,8 @@ pub(crate) fn accept_contract_internal(
     for contract_info in offered_contract.contract_info.iter().skip(1) {
         let payouts = contract_info.get_payouts(total_collateral)?;
 
-        let tmp_cets = dlc::create_cets(
-            &cet_input,
-            &offered_contract.offer_params.payout_script_pubkey,
-            offered_contract.offer_params.payout_serial_id,
-            &accept_params.payout_script_pubkey,
-            accept_params.payout_serial_id,
-            &payouts,
-            0,
-        );
+        let tmp_cets =
+            create_additional_cets(offered_contract, accept_params, &cet_input, &payouts);
 
         let (adaptor_info, adaptor_sig) = contract_info.get_adaptor_info(
             secp,
@@ -221,23 +291,136 @@ pub(crate) fn accept_contract_internal(
         accept_params: accept_params.clone(),
         funding_inputs: funding_inputs.to_vec(),
         dlc_transactions,
-        accept_refund_signature: refund_signature,
+        accept_refund_signature: Some(refund_signature),
     };
 
     Ok((accepted_contract, adaptor_sigs))
 }
 
+/// Creates the [`AcceptedContract`] of a contract funded with a taproot output,
+/// and the MuSig2 nonces and partial signatures of the accepting party for its
+/// CETs and refund transaction. The partial signatures for the CETs are partial
+/// adaptor signatures, aggregated by the offering party.
+fn accept_taproot_contract_internal(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    accept_params: &PartyParams,
+    funding_inputs: &[FundingInput],
+    fund_secret_key: &SecretKey,
+    dlc_transactions: &DlcTransactions,
+) -> Result<(AcceptedContract, TaprootAcceptSignatures), Error> {
+    if offered_contract.fee_payer.is_some() {
+        return Err(Error::InvalidParameters(
+            "Taproot funding is not supported for contracts with a fee payer.".to_string(),
+        ));
+    }
+
+    let offer_nonces = offered_contract
+        .taproot_funding_nonces
+        .as_ref()
+        .ok_or_else(|| Error::InvalidParameters("Missing taproot funding nonces.".to_string()))?;
+    let (adaptor_infos, adaptor_points) = get_taproot_adaptor_points(secp, offered_contract)?;
+    if offer_nonces.cet_nonces.len() != adaptor_points.len() {
+        return Err(Error::InvalidParameters(
+            "Invalid number of taproot funding nonces.".to_string(),
+        ));
+    }
+
+    let cets = get_all_cets(offered_contract, accept_params, dlc_transactions)?;
+    let key_agg_context = taproot::get_funding_key_agg_context(
+        secp,
+        &offered_contract.offer_params.fund_pubkey,
+        &accept_params.fund_pubkey,
+    )?;
+    let fund_output = dlc_transactions.get_fund_output();
+
+    let mut cet_nonces = Vec::with_capacity(adaptor_points.len());
+    let mut cet_partial_signatures = Vec::with_capacity(adaptor_points.len());
+    for ((cet_index, adaptor_point), offer_nonce) in
+        adaptor_points.iter().zip(offer_nonces.cet_nonces.iter())
+    {
+        let (sec_nonce, pub_nonce) = taproot::generate_nonce(
+            secp,
+            crate::utils::get_new_session_rand(),
+            fund_secret_key,
+            Some(&key_agg_context),
+            None,
+        )?;
+        let agg_nonce = taproot::aggregate_nonces(&[offer_nonce.into(), pub_nonce])?;
+        let signature = taproot::create_cet_partial_adaptor_sig(
+            secp,
+            &cets[*cet_index],
+            fund_output,
+            sec_nonce,
+            fund_secret_key,
+            &key_agg_context,
+            &agg_nonce,
+            adaptor_point,
+        )?;
+        cet_nonces.push((&pub_nonce).into());
+        cet_partial_signatures.push(FundingPartialSignature { signature });
+    }
+
+    let (sec_nonce, pub_nonce) = taproot::generate_nonce(
+        secp,
+        crate::utils::get_new_session_rand(),
+        fund_secret_key,
+        Some(&key_agg_context),
+        None,
+    )?;
+    let agg_nonce = taproot::aggregate_nonces(&[(&offer_nonces.refund_nonce).into(), pub_nonce])?;
+    let refund_partial_signature = taproot::create_funding_input_partial_sig(
+        secp,
+        &dlc_transactions.refund,
+        fund_output,
+        sec_nonce,
+        fund_secret_key,
+        &key_agg_context,
+        &agg_nonce,
+    )?;
+
+    let accepted_contract = AcceptedContract {
+        offered_contract: offered_contract.clone(),
+        adaptor_infos,
+        adaptor_signatures: None,
+        accept_params: accept_params.clone(),
+        funding_inputs: funding_inputs.to_vec(),
+        dlc_transactions: DlcTransactions {
+            cets,
+            ..dlc_transactions.clone()
+        },
+        accept_refund_signature: None,
+    };
+
+    let taproot_signatures = TaprootAcceptSignatures {
+        nonces: TaprootFundingNonces {
+            cet_nonces,
+            refund_nonce: (&pub_nonce).into(),
+        },
+        cet_partial_signatures,
+        refund_partial_signature: FundingPartialSignature {
+            signature: refund_partial_signature,
+        },
+    };
+
+    Ok((accepted_contract, taproot_signatures))
+}
+
 /// Verifies the information of the accepting party [`Accept` message](dlc_messages::AcceptDlc),
 /// creates a [`SignedContract`], and generates the offering party CET adaptor signatures.
-pub fn verify_accepted_and_sign_contract<W: Deref, X: ContractSigner, SP: Deref>(
+/// For contracts funded with a taproot output, the secret nonces of the
+/// offering party must be given and are consumed, as they must be removed from
+/// the stored offered contract before being used.
+pub async fn verify_accepted_and_sign_contract<W: Deref, X: ContractSigner, SP: Deref>(
     secp: &Secp256k1<All>,
     offered_contract: &OfferedContract,
+    taproot_secret_nonces: Option<TaprootSecretNonces>,
     accept_msg: &AcceptDlc,
     wallet: &W,
     signer_provider: &SP,
 ) -> Result<(SignedContract, SignDlc), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
 {
     let (tx_input_infos, input_amount) = get_tx_input_infos(&accept_msg.funding_inputs)?;
@@ -253,34 +436,38 @@ where
         collateral: accept_msg.accept_collateral,
     };
 
-    let cet_adaptor_signatures = accept_msg
-        .cet_adaptor_signatures
-        .ecdsa_adaptor_signatures
-        .iter()
-        .map(|x| x.signature)
-        .collect::<Vec<_>>();
+    let dlc_transactions = create_dlc_transactions(secp, offered_contract, &accept_params)?;
+    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
 
-    let total_collateral = offered_contract.total_collateral;
+    if offered_contract.funding_output_type == FundingOutputType::Taproot {
+        let taproot_secret_nonces = taproot_secret_nonces
+            .ok_or_else(|| Error::InvalidState("Missing taproot secret nonces.".to_string()))?;
+        let signed_contract = verify_taproot_accepted_and_sign_contract(
+            secp,
+            offered_contract,
+            taproot_secret_nonces,
+            &accept_params,
+            accept_msg,
+            wallet,
+            &signer,
+            &dlc_transactions,
+        )
+        .await?;
 
-    let dlc_transactions = dlc::create_dlc_transactions(
-        &offered_contract.offer_params,
-        &accept_params,
-        &offered_contract.contract_info[0].get_payouts(total_collateral)?,
-        offered_contract.refund_locktime,
-        offered_contract.fee_rate_per_vb,
-        0,
-        offered_contract.cet_locktime,
-        offered_contract.fund_output_serial_id,
-    )?;
+        let signed_msg: SignDlc = signed_contract.get_sign_dlc(Vec::new());
+
+        return Ok((signed_contract, signed_msg));
+    }
+
+    let cet_adaptor_signatures: Vec<_> = (&accept_msg.cet_adaptor_signatures).into();
     let fund_output_value = dlc_transactions.get_fund_output().value;
 
-    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
     let (signed_contract, adaptor_sigs) = verify_accepted_and_sign_contract_internal(
         secp,
         offered_contract,
         &accept_params,
         &accept_msg.funding_inputs,
-        &accept_msg.refund_signature,
+        get_refund_signature(&accept_msg.refund_signature)?,
         &cet_adaptor_signatures,
         fund_output_value,
         wallet,
@@ -289,13 +476,292 @@ where
         None,
         &dlc_transactions,
         None,
-    )?;
+    )
+    .await?;
 
     let signed_msg: SignDlc = signed_contract.get_sign_dlc(adaptor_sigs);
 
     Ok((signed_contract, signed_msg))
 }
 
+/// Creates a PSBT of the funding transaction of an accepted contract whose fees
+/// are paid by a third party, to be handed to the fee payer for them to sign
+/// their inputs.
+pub fn get_fee_payer_psbt(
+    accepted_contract: &AcceptedContract,
+) -> Result<PartiallySignedTransaction, Error> {
+    let offered_contract = &accepted_contract.offered_contract;
+    if offered_contract.fee_payer.is_none() {
+        return Err(Error::InvalidState(
+            "Contract does not have a fee payer.".to_string(),
+        ));
+    }
+
+    let mut fund_psbt = PartiallySignedTransaction::from_unsigned_tx(
+        accepted_contract.dlc_transactions.fund.clone(),
+    )
+    .map_err(|_| Error::InvalidState("Tried to create PSBT from signed tx".to_string()))?;
+    let all_funding_inputs =
+        get_all_funding_inputs(offered_contract, &accepted_contract.funding_inputs);
+    populate_psbt(&mut fund_psbt, &all_funding_inputs)?;
+
+    Ok(fund_psbt)
+}
+
+/// Extracts the fee payer signatures from the given PSBT, signed by the fee
+/// payer, and creates the offering party [`SignedContract`] and [`SignDlc`]
+/// message including them.
+pub async fn sign_contract_with_fee_payer_psbt<W: Deref, X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
+    accepted_contract: &AcceptedContract,
+    fee_payer_psbt: &PartiallySignedTransaction,
+    wallet: &W,
+    signer_provider: &SP,
+) -> Result<(SignedContract, SignDlc), Error>
+where
+    W::Target: AsyncWallet,
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let offered_contract = &accepted_contract.offered_contract;
+    let fee_payer = offered_contract
+        .fee_payer
+        .as_ref()
+        .ok_or_else(|| Error::InvalidState("Contract does not have a fee payer.".to_string()))?;
+
+    if fee_payer_psbt.unsigned_tx.txid() != accepted_contract.dlc_transactions.fund.txid() {
+        return Err(Error::InvalidParameters(
+            "PSBT does not match the contract funding transaction.".to_string(),
+        ));
+    }
+
+    let all_funding_inputs =
+        get_all_funding_inputs(offered_contract, &accepted_contract.funding_inputs);
+    let fee_payer_funding_signatures = fee_payer
+        .funding_inputs
+        .iter()
+        .map(|x| {
+            let input_index = all_funding_inputs
+                .iter()
+                .position(|y| y == &x)
+                .ok_or_else(|| {
+                    Error::InvalidState(format!(
+                        "Could not find input for serial id {}",
+                        x.input_serial_id
+                    ))
+                })?;
+            let witness = fee_payer_psbt.inputs[input_index]
+                .final_script_witness
+                .as_ref()
+                .ok_or_else(|| {
+                    Error::InvalidParameters(format!(
+                        "Missing fee payer signature for input with serial id {}",
+                        x.input_serial_id
+                    ))
+                })?;
+            Ok(FundingSignature {
+                witness_elements: witness
+                    .iter()
+                    .map(|z| WitnessElement {
+                        witness: z.to_vec(),
+                    })
+                    .collect(),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let cet_adaptor_signatures =
+        accepted_contract
+            .adaptor_signatures
+            .as_ref()
+            .ok_or_else(|| {
+                Error::InvalidState("Missing accept party adaptor signatures.".to_string())
+            })?;
+
+    let dlc_transactions =
+        create_dlc_transactions(secp, offered_contract, &accepted_contract.accept_params)?;
+    let fund_output_value = dlc_transactions.get_fund_output().value;
+
+    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
+    let (signed_contract, adaptor_sigs) = verify_accepted_and_sign_contract_internal(
+        secp,
+        offered_contract,
+        &accepted_contract.accept_params,
+        &accepted_contract.funding_inputs,
+        &accepted_contract.get_accept_refund_signature()?,
+        cet_adaptor_signatures,
+        fund_output_value,
+        wallet,
+        &signer,
+        None,
+        None,
+        &dlc_transactions,
+        None,
+    )
+    .await?;
+
+    let mut signed_msg: SignDlc = signed_contract.get_sign_dlc(adaptor_sigs);
+    signed_msg.fee_payer_funding_signatures = Some(FundingSignatures {
+        funding_signatures: fee_payer_funding_signatures,
+    });
+
+    Ok((signed_contract, signed_msg))
+}
+
+/// Creates the transactions of the contract, having them funded partly by a
+/// third party if the offered contract includes a fee payer.
+fn create_dlc_transactions(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    accept_params: &PartyParams,
+) -> Result<DlcTransactions, Error> {
+    let payouts =
+        offered_contract.contract_info[0].get_payouts(offered_contract.total_collateral)?;
+    let options = offered_contract.get_dlc_transactions_options();
+
+    let dlc_transactions = match offered_contract.get_fee_payer_params()? {
+        Some(fee_payer_params) => dlc::create_dlc_transactions_with_fee_payer(
+            secp,
+            &offered_contract.offer_params,
+            accept_params,
+            &fee_payer_params,
+            &payouts,
+            offered_contract.refund_locktime,
+            offered_contract.fee_rate_per_vb,
+            0,
+            offered_contract.cet_locktime,
+            offered_contract.fund_output_serial_id,
+            &options,
+        )?,
+        None => dlc::create_dlc_transactions_with_options(
+            secp,
+            &offered_contract.offer_params,
+            accept_params,
+            &payouts,
+            offered_contract.refund_locktime,
+            offered_contract.fee_rate_per_vb,
+            0,
+            offered_contract.cet_locktime,
+            offered_contract.fund_output_serial_id,
+            &options,
+        )?,
+    };
+
+    Ok(dlc_transactions)
+}
+
+/// Returns the funding inputs of both parties and of the fee payer if any,
+/// ordered by serial id as in the funding transaction.
+fn get_all_funding_inputs<'a>(
+    offered_contract: &'a OfferedContract,
+    accept_funding_inputs: &'a [FundingInput],
+) -> Vec<&'a FundingInput> {
+    let mut all_funding_inputs = offered_contract
+        .funding_inputs
+        .iter()
+        .chain(accept_funding_inputs.iter())
+        .chain(
+            offered_contract
+                .fee_payer
+                .iter()
+                .flat_map(|x| x.funding_inputs.iter()),
+        )
+        .collect::<Vec<_>>();
+    // sort by serial id
+    all_funding_inputs.sort_by_key(|x| x.input_serial_id);
+    all_funding_inputs
+}
+
+fn create_additional_cets(
+    offered_contract: &OfferedContract,
+    accept_params: &PartyParams,
+    cet_input: &TxIn,
+    payouts: &[Payout],
+) -> Vec<Transaction> {
+    let mut cets = dlc::create_cets(
+        cet_input,
+        &offered_contract.offer_params.payout_script_pubkey,
+        offered_contract.offer_params.payout_serial_id,
+        &accept_params.payout_script_pubkey,
+        accept_params.payout_serial_id,
+        payouts,
+        0,
+    );
+
+    if offered_contract.anchor_outputs {
+        for cet in cets.iter_mut() {
+            dlc::add_anchor_outputs(
+                cet,
+                &offered_contract.offer_params.payout_script_pubkey,
+                &accept_params.payout_script_pubkey,
+            );
+        }
+    }
+
+    cets
+}
+
+/// Returns the CETs of all the contract infos of the given contract, the CETs
+/// of each contract info following the ones of the previous one.
+fn get_all_cets(
+    offered_contract: &OfferedContract,
+    accept_params: &PartyParams,
+    dlc_transactions: &DlcTransactions,
+) -> Result<Vec<Transaction>, Error> {
+    let mut cets = dlc_transactions.cets.clone();
+    let cet_input = cets[0].input[0].clone();
+
+    for contract_info in offered_contract.contract_info.iter().skip(1) {
+        let payouts = contract_info.get_payouts(offered_contract.total_collateral)?;
+        cets.extend(create_additional_cets(
+            offered_contract,
+            accept_params,
+            &cet_input,
+            &payouts,
+        ));
+    }
+
+    Ok(cets)
+}
+
+/// Returns the refund signature of an [`AcceptDlc`] or [`SignDlc`] message, or
+/// an error if the message does not include one.
+fn get_refund_signature(refund_signature: &Option<Signature>) -> Result<&Signature, Error> {
+    refund_signature
+        .as_ref()
+        .ok_or_else(|| Error::InvalidParameters("Missing refund signature.".to_string()))
+}
+
+/// Generates the adaptor infos of a contract funded with a taproot output, and
+/// returns them together with the index of the CET and the adaptor point of
+/// each of its adaptor signatures, in adaptor signature order. The CET indexes
+/// refer to the CETs returned by [`get_all_cets`].
+fn get_taproot_adaptor_points(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+) -> Result<(Vec<AdaptorInfo>, Vec<(usize, PublicKey)>), Error> {
+    let total_collateral = offered_contract.total_collateral;
+    let mut adaptor_infos = Vec::with_capacity(offered_contract.contract_info.len());
+    let mut adaptor_points = Vec::new();
+    let mut cet_index_start = 0;
+
+    for contract_info in &offered_contract.contract_info {
+        let (adaptor_info, points) = contract_info.get_adaptor_info_and_points(
+            secp,
+            total_collateral,
+            adaptor_points.len(),
+        )?;
+        adaptor_points.extend(
+            points
+                .into_iter()
+                .map(|(cet_index, point)| (cet_index_start + cet_index, point)),
+        );
+        cet_index_start += contract_info.get_payouts(total_collateral)?.len();
+        adaptor_infos.push(adaptor_info);
+    }
+
+    Ok((adaptor_infos, adaptor_points))
+}
+
 fn populate_psbt(
     psbt: &mut PartiallySignedTransaction,
     all_funding_inputs: &[&FundingInput],
@@ -319,7 +785,7 @@ fn populate_psbt(
     Ok(())
 }
 
-pub(crate) fn verify_accepted_and_sign_contract_internal<W: Deref, X: ContractSigner>(
+pub(crate) async fn verify_accepted_and_sign_contract_internal<W: Deref, X: ContractSigner>(
     secp: &Secp256k1<All>,
     offered_contract: &OfferedContract,
     accept_params: &PartyParams,
@@ -335,28 +801,136 @@ pub(crate) fn verify_accepted_and_sign_contract_internal<W: Deref, X: ContractSi
     channel_id: Option<ChannelId>,
 ) -> Result<(SignedContract, Vec<EcdsaAdaptorSignature>), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
 {
-    let DlcTransactions {
-        fund,
-        cets,
-        refund,
-        funding_script_pubkey,
-    } = dlc_transactions;
+    let (accepted_contract, own_signatures, offer_refund_signature) =
+        verify_accepted_and_sign_cets(
+            secp,
+            offered_contract,
+            accept_params,
+            funding_inputs_info,
+            refund_signature,
+            cet_adaptor_signatures,
+            input_value,
+            signer,
+            input_script_pubkey,
+            counter_adaptor_pk,
+            dlc_transactions,
+        )?;
 
-    let mut fund_psbt = PartiallySignedTransaction::from_unsigned_tx(fund.clone())
-        .map_err(|_| Error::InvalidState("Tried to create PSBT from signed tx".to_string()))?;
-    let mut cets = cets.clone();
+    let funding_signatures = sign_offer_funding_inputs(
+        offered_contract,
+        funding_inputs_info,
+        wallet,
+        dlc_transactions,
+    )
+    .await?;
 
-    let input_script_pubkey = input_script_pubkey.unwrap_or_else(|| funding_script_pubkey);
-    let counter_adaptor_pk = counter_adaptor_pk.unwrap_or(accept_params.fund_pubkey);
+    let signed_contract = SignedContract {
+        accepted_contract,
+        adaptor_signatures: None,
+        offer_refund_signature: Some(offer_refund_signature),
+        funding_signatures,
+        channel_id,
+        close_offer: None,
+        taproot_signatures: None,
+    };
 
-    dlc::verify_tx_input_sig(
-        secp,
-        refund_signature,
-        refund,
-        0,
-        input_script_pubkey,
+    Ok((signed_contract, own_signatures))
+}
+
+/// Signs the funding inputs of the offering party in the fund transaction of
+/// the contract.
+async fn sign_offer_funding_inputs<W: Deref>(
+    offered_contract: &OfferedContract,
+    accept_funding_inputs: &[FundingInput],
+    wallet: &W,
+    dlc_transactions: &DlcTransactions,
+) -> Result<FundingSignatures, Error>
+where
+    W::Target: AsyncWallet,
+{
+    let mut fund_psbt = PartiallySignedTransaction::from_unsigned_tx(dlc_transactions.fund.clone())
+        .map_err(|_| Error::InvalidState("Tried to create PSBT from signed tx".to_string()))?;
+
+    let all_funding_inputs = get_all_funding_inputs(offered_contract, accept_funding_inputs);
+
+    populate_psbt(&mut fund_psbt, &all_funding_inputs)?;
+
+    let mut witnesses: Vec<Witness> = Vec::with_capacity(offered_contract.funding_inputs.len());
+    for x in &offered_contract.funding_inputs {
+        let input_index = all_funding_inputs
+            .iter()
+            .position(|y| y == &x)
+            .ok_or_else(|| {
+                Error::InvalidState(format!(
+                    "Could not find input for serial id {}",
+                    x.input_serial_id
+                ))
+            })?;
+
+        wallet.sign_psbt_input(&mut fund_psbt, input_index).await?;
+
+        let witness = fund_psbt.inputs[input_index]
+            .final_script_witness
+            .clone()
+            .ok_or(Error::InvalidParameters(
+                "No witness from signing psbt input".to_string(),
+            ))?;
+
+        witnesses.push(witness);
+    }
+
+    let funding_signatures: Vec<FundingSignature> = witnesses
+        .into_iter()
+        .map(|witness| {
+            let witness_elements = witness
+                .iter()
+                .map(|z| WitnessElement {
+                    witness: z.to_vec(),
+                })
+                .collect();
+            Ok(FundingSignature { witness_elements })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(FundingSignatures { funding_signatures })
+}
+
+/// Verifies the CET adaptor signatures and refund signature of the accept party
+/// and generates the ones of the offer party, returning the resulting
+/// [`AcceptedContract`] together with the offer party signatures.
+fn verify_accepted_and_sign_cets<X: ContractSigner>(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    accept_params: &PartyParams,
+    funding_inputs_info: &[FundingInput],
+    refund_signature: &Signature,
+    cet_adaptor_signatures: &[EcdsaAdaptorSignature],
+    input_value: u64,
+    signer: &X,
+    input_script_pubkey: Option<&Script>,
+    counter_adaptor_pk: Option<PublicKey>,
+    dlc_transactions: &DlcTransactions,
+) -> Result<(AcceptedContract, Vec<EcdsaAdaptorSignature>, Signature), Error> {
+    let DlcTransactions {
+        fund,
+        cets,
+        refund,
+        funding_script_pubkey,
+    } = dlc_transactions;
+
+    let mut cets = cets.clone();
+
+    let input_script_pubkey = input_script_pubkey.unwrap_or_else(|| funding_script_pubkey);
+    let counter_adaptor_pk = counter_adaptor_pk.unwrap_or(accept_params.fund_pubkey);
+
+    dlc::verify_tx_input_sig(
+        secp,
+        refund_signature,
+        refund,
+        0,
+        input_script_pubkey,
         input_value,
         &counter_adaptor_pk,
     )?;
@@ -382,15 +956,8 @@ where
     for contract_info in offered_contract.contract_info.iter().skip(1) {
         let payouts = contract_info.get_payouts(total_collateral)?;
 
-        let tmp_cets = dlc::create_cets(
-            &cet_input,
-            &offered_contract.offer_params.payout_script_pubkey,
-            offered_contract.offer_params.payout_serial_id,
-            &accept_params.payout_script_pubkey,
-            accept_params.payout_serial_id,
-            &payouts,
-            0,
-        );
+        let tmp_cets =
+            create_additional_cets(offered_contract, accept_params, &cet_input, &payouts);
 
         let (adaptor_info, tmp_adaptor_index) = contract_info.verify_and_get_adaptor_info(
             secp,
@@ -428,58 +995,6 @@ where
         own_signatures.extend(sigs);
     }
 
-    // get all funding inputs
-    let mut all_funding_inputs = offered_contract
-        .funding_inputs
-        .iter()
-        .chain(funding_inputs_info.iter())
-        .collect::<Vec<_>>();
-    // sort by serial id
-    all_funding_inputs.sort_by_key(|x| x.input_serial_id);
-
-    populate_psbt(&mut fund_psbt, &all_funding_inputs)?;
-
-    // Vec<Witness>
-    let witnesses: Vec<Witness> = offered_contract
-        .funding_inputs
-        .iter()
-        .map(|x| {
-            let input_index = all_funding_inputs
-                .iter()
-                .position(|y| y == &x)
-                .ok_or_else(|| {
-                    Error::InvalidState(format!(
-                        "Could not find input for serial id {}",
-                        x.input_serial_id
-                    ))
-                })?;
-
-            wallet.sign_psbt_input(&mut fund_psbt, input_index)?;
-
-            let witness = fund_psbt.inputs[input_index]
-                .final_script_witness
-                .clone()
-                .ok_or(Error::InvalidParameters(
-                    "No witness from signing psbt input".to_string(),
-                ))?;
-
-            Ok(witness)
-        })
-        .collect::<Result<Vec<_>, Error>>()?;
-
-    let funding_signatures: Vec<FundingSignature> = witnesses
-        .into_iter()
-        .map(|witness| {
-            let witness_elements = witness
-                .iter()
-                .map(|z| WitnessElement {
-                    witness: z.to_vec(),
-                })
-                .collect();
-            Ok(FundingSignature { witness_elements })
-        })
-        .collect::<Result<Vec<_>, Error>>()?;
-
     let offer_refund_signature = dlc::util::get_raw_sig_for_tx_input(
         secp,
         refund,
@@ -502,54 +1017,214 @@ where
         funding_inputs: funding_inputs_info.to_vec(),
         adaptor_infos,
         adaptor_signatures: Some(cet_adaptor_signatures.to_vec()),
-        accept_refund_signature: *refund_signature,
+        accept_refund_signature: Some(*refund_signature),
         dlc_transactions,
     };
 
-    let signed_contract = SignedContract {
-        accepted_contract,
+    Ok((accepted_contract, own_signatures, offer_refund_signature))
+}
+
+/// Verifies the MuSig2 nonces and partial signatures of the accepting party for
+/// the CETs and refund transaction of a contract funded with a taproot output,
+/// and aggregates them with the ones of the offering party to create the
+/// [`SignedContract`].
+async fn verify_taproot_accepted_and_sign_contract<W: Deref, X: ContractSigner>(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    secret_nonces: TaprootSecretNonces,
+    accept_params: &PartyParams,
+    accept_msg: &AcceptDlc,
+    wallet: &W,
+    signer: &X,
+    dlc_transactions: &DlcTransactions,
+) -> Result<SignedContract, Error>
+where
+    W::Target: AsyncWallet,
+{
+    let accept_signatures = accept_msg.taproot_signatures.as_ref().ok_or_else(|| {
+        Error::InvalidParameters("Missing taproot signatures in accept message.".to_string())
+    })?;
+    let offer_nonces = offered_contract
+        .taproot_funding_nonces
+        .as_ref()
+        .ok_or_else(|| Error::InvalidState("Missing taproot funding nonces.".to_string()))?;
+
+    let (adaptor_infos, adaptor_points) = get_taproot_adaptor_points(secp, offered_contract)?;
+    if offer_nonces.cet_nonces.len() != adaptor_points.len()
+        || secret_nonces.cet_nonces.len() != adaptor_points.len()
+        || accept_signatures.nonces.cet_nonces.len() != adaptor_points.len()
+        || accept_signatures.cet_partial_signatures.len() != adaptor_points.len()
+    {
+        return Err(Error::InvalidParameters(
+            "Invalid number of taproot nonces or partial signatures.".to_string(),
+        ));
+    }
+
+    let fund_secret_key = signer.get_secret_key()?;
+    let cets = get_all_cets(offered_contract, accept_params, dlc_transactions)?;
+    let key_agg_context = taproot::get_funding_key_agg_context(
+        secp,
+        &offered_contract.offer_params.fund_pubkey,
+        &accept_params.fund_pubkey,
+    )?;
+    let fund_output = dlc_transactions.get_fund_output();
+
+    let mut cet_adaptor_signatures = Vec::with_capacity(adaptor_points.len());
+    for (
+        ((((cet_index, adaptor_point), sec_nonce), offer_nonce), accept_nonce),
+        accept_partial_sig,
+    ) in adaptor_points
+        .iter()
+        .zip(secret_nonces.cet_nonces.iter())
+        .zip(offer_nonces.cet_nonces.iter())
+        .zip(accept_signatures.nonces.cet_nonces.iter())
+        .zip(accept_signatures.cet_partial_signatures.iter())
+    {
+        let cet = &cets[*cet_index];
+        let sec_nonce = SecretNonce::from_slice(sec_nonce)?;
+        let pub_nonce: PublicNonce = offer_nonce.into();
+        let accept_pub_nonce: PublicNonce = accept_nonce.into();
+        let agg_nonce = taproot::aggregate_nonces(&[pub_nonce, accept_pub_nonce])?;
+        taproot::verify_cet_partial_adaptor_sig(
+            secp,
+            &accept_partial_sig.signature,
+            cet,
+            fund_output,
+            &accept_pub_nonce,
+            &accept_params.fund_pubkey,
+            &key_agg_context,
+            &agg_nonce,
+            adaptor_point,
+        )?;
+        let partial_sig = taproot::create_cet_partial_adaptor_sig(
+            secp,
+            cet,
+            fund_output,
+            sec_nonce,
+            &fund_secret_key,
+            &key_agg_context,
+            &agg_nonce,
+            adaptor_point,
+        )?;
+        cet_adaptor_signatures.push(taproot::aggregate_cet_adaptor_sig(
+            secp,
+            cet,
+            fund_output,
+            &key_agg_context,
+            &agg_nonce,
+            adaptor_point,
+            &[partial_sig, accept_partial_sig.signature],
+        )?);
+    }
+
+    let sec_nonce = SecretNonce::from_slice(&secret_nonces.refund_nonce)?;
+    let pub_nonce: PublicNonce = (&offer_nonces.refund_nonce).into();
+    let accept_pub_nonce: PublicNonce = (&accept_signatures.nonces.refund_nonce).into();
+    let agg_nonce = taproot::aggregate_nonces(&[pub_nonce, accept_pub_nonce])?;
+    let accept_partial_sig = accept_signatures.refund_partial_signature.signature;
+    taproot::verify_funding_input_partial_sig(
+        secp,
+        &accept_partial_sig,
+        &dlc_transactions.refund,
+        fund_output,
+        &accept_pub_nonce,
+        &accept_params.fund_pubkey,
+        &key_agg_context,
+        &agg_nonce,
+    )?;
+    let partial_sig = taproot::create_funding_input_partial_sig(
+        secp,
+        &dlc_transactions.refund,
+        fund_output,
+        sec_nonce,
+        &fund_secret_key,
+        &key_agg_context,
+        &agg_nonce,
+    )?;
+    let refund_signature = taproot::aggregate_funding_input_sig(
+        secp,
+        &dlc_transactions.refund,
+        fund_output,
+        &key_agg_context,
+        &agg_nonce,
+        &[partial_sig, accept_partial_sig],
+    )?;
+
+    let funding_signatures = sign_offer_funding_inputs(
+        offered_contract,
+        &accept_msg.funding_inputs,
+        wallet,
+        dlc_transactions,
+    )
+    .await?;
+
+    let accepted_contract = AcceptedContract {
+        offered_contract: offered_contract.clone(),
+        accept_params: accept_params.clone(),
+        funding_inputs: accept_msg.funding_inputs.clone(),
+        adaptor_infos,
         adaptor_signatures: None,
-        offer_refund_signature,
-        funding_signatures: FundingSignatures { funding_signatures },
-        channel_id,
+        accept_refund_signature: None,
+        dlc_transactions: DlcTransactions {
+            cets,
+            ..dlc_transactions.clone()
+        },
     };
 
-    Ok((signed_contract, own_signatures))
+    Ok(SignedContract {
+        accepted_contract,
+        adaptor_signatures: None,
+        offer_refund_signature: None,
+        funding_signatures,
+        channel_id: None,
+        close_offer: None,
+        taproot_signatures: Some(TaprootSignatures {
+            cet_adaptor_signatures,
+            refund_signature,
+        }),
+    })
 }
 
 /// Verifies the information from the offer party [`Sign` message](dlc_messages::SignDlc),
 /// creates the accepting party's [`SignedContract`] and returns it along with the
 /// signed fund transaction.
-pub fn verify_signed_contract<W: Deref>(
+pub async fn verify_signed_contract<W: Deref>(
     secp: &Secp256k1<All>,
     accepted_contract: &AcceptedContract,
     sign_msg: &SignDlc,
     wallet: &W,
 ) -> Result<(SignedContract, Transaction), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
 {
+    if accepted_contract.offered_contract.funding_output_type == FundingOutputType::Taproot {
+        return verify_taproot_signed_contract(secp, accepted_contract, sign_msg, wallet).await;
+    }
+
     let cet_adaptor_signatures: Vec<_> = (&sign_msg.cet_adaptor_signatures).into();
     verify_signed_contract_internal(
         secp,
         accepted_contract,
-        &sign_msg.refund_signature,
+        get_refund_signature(&sign_msg.refund_signature)?,
         &cet_adaptor_signatures,
         &sign_msg.funding_signatures,
+        sign_msg.fee_payer_funding_signatures.as_ref(),
         accepted_contract.dlc_transactions.get_fund_output().value,
         None,
         None,
         wallet,
         None,
     )
+    .await
 }
 
-pub(crate) fn verify_signed_contract_internal<W: Deref>(
+pub(crate) async fn verify_signed_contract_internal<W: Deref>(
     secp: &Secp256k1<All>,
     accepted_contract: &AcceptedContract,
     refund_signature: &Signature,
     cet_adaptor_signatures: &[EcdsaAdaptorSignature],
     funding_signatures: &FundingSignatures,
+    fee_payer_funding_signatures: Option<&FundingSignatures>,
     input_value: u64,
     input_script_pubkey: Option<&Script>,
     counter_adaptor_pk: Option<PublicKey>,
@@ -557,63 +1232,86 @@ pub(crate) fn verify_signed_contract_internal<W: Deref>(
     channel_id: Option<ChannelId>,
 ) -> Result<(SignedContract, Transaction), Error>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
 {
-    let offered_contract = &accepted_contract.offered_contract;
-    let input_script_pubkey = input_script_pubkey
-        .unwrap_or_else(|| &accepted_contract.dlc_transactions.funding_script_pubkey);
-    let counter_adaptor_pk =
-        counter_adaptor_pk.unwrap_or(accepted_contract.offered_contract.offer_params.fund_pubkey);
-
-    dlc::verify_tx_input_sig(
+    verify_signed_cets(
         secp,
+        accepted_contract,
         refund_signature,
-        &accepted_contract.dlc_transactions.refund,
-        0,
-        input_script_pubkey,
+        cet_adaptor_signatures,
         input_value,
-        &counter_adaptor_pk,
+        input_script_pubkey,
+        counter_adaptor_pk,
     )?;
 
-    let mut adaptor_sig_start = 0;
+    let fund_tx = sign_accept_funding_inputs(
+        accepted_contract,
+        funding_signatures,
+        fee_payer_funding_signatures,
+        wallet,
+    )
+    .await?;
 
-    for (adaptor_info, contract_info) in accepted_contract
-        .adaptor_infos
-        .iter()
-        .zip(offered_contract.contract_info.iter())
-    {
-        adaptor_sig_start = contract_info.verify_adaptor_info(
-            secp,
-            &counter_adaptor_pk,
-            input_script_pubkey,
-            input_value,
-            &accepted_contract.dlc_transactions.cets,
-            cet_adaptor_signatures,
-            adaptor_sig_start,
-            adaptor_info,
-        )?;
-    }
+    let signed_contract = SignedContract {
+        accepted_contract: accepted_contract.clone(),
+        adaptor_signatures: Some(cet_adaptor_signatures.to_vec()),
+        offer_refund_signature: Some(*refund_signature),
+        funding_signatures: funding_signatures.clone(),
+        channel_id,
+        close_offer: None,
+        taproot_signatures: None,
+    };
+
+    Ok((signed_contract, fund_tx))
+}
+
+/// Adds the funding signatures of the offering party and of the fee payer to
+/// the fund transaction of the given contract, signs the funding inputs of the
+/// accepting party and returns the fully signed fund transaction.
+async fn sign_accept_funding_inputs<W: Deref>(
+    accepted_contract: &AcceptedContract,
+    funding_signatures: &FundingSignatures,
+    fee_payer_funding_signatures: Option<&FundingSignatures>,
+    wallet: &W,
+) -> Result<Transaction, Error>
+where
+    W::Target: AsyncWallet,
+{
+    let offered_contract = &accepted_contract.offered_contract;
 
     let fund_tx = &accepted_contract.dlc_transactions.fund;
     let mut fund_psbt = PartiallySignedTransaction::from_unsigned_tx(fund_tx.clone())
         .map_err(|_| Error::InvalidState("Tried to create PSBT from signed tx".to_string()))?;
 
-    // get all funding inputs
-    let mut all_funding_inputs = offered_contract
-        .funding_inputs
-        .iter()
-        .chain(accepted_contract.funding_inputs.iter())
-        .collect::<Vec<_>>();
-    // sort by serial id
-    all_funding_inputs.sort_by_key(|x| x.input_serial_id);
+    let all_funding_inputs =
+        get_all_funding_inputs(offered_contract, &accepted_contract.funding_inputs);
 
     populate_psbt(&mut fund_psbt, &all_funding_inputs)?;
 
-    for (funding_input, funding_signatures) in offered_contract
+    let mut counter_funding_inputs = offered_contract
         .funding_inputs
         .iter()
         .zip(funding_signatures.funding_signatures.iter())
-    {
+        .collect::<Vec<_>>();
+
+    if let Some(fee_payer) = &offered_contract.fee_payer {
+        let fee_payer_funding_signatures = fee_payer_funding_signatures.ok_or_else(|| {
+            Error::InvalidParameters("Missing fee payer funding signatures.".to_string())
+        })?;
+        if fee_payer.funding_inputs.len() != fee_payer_funding_signatures.funding_signatures.len() {
+            return Err(Error::InvalidParameters(
+                "Invalid number of fee payer funding signatures.".to_string(),
+            ));
+        }
+        counter_funding_inputs.extend(
+            fee_payer
+                .funding_inputs
+                .iter()
+                .zip(fee_payer_funding_signatures.funding_signatures.iter()),
+        );
+    }
+
+    for (funding_input, funding_signatures) in counter_funding_inputs {
         let input_index = all_funding_inputs
             .iter()
             .position(|x| x == &funding_input)
@@ -644,37 +1342,1534 @@ where
                 ))
             })?;
 
-        wallet.sign_psbt_input(&mut fund_psbt, input_index)?;
+        wallet.sign_psbt_input(&mut fund_psbt, input_index).await?;
     }
 
-    let signed_contract = SignedContract {
-        accepted_contract: accepted_contract.clone(),
-        adaptor_signatures: Some(cet_adaptor_signatures.to_vec()),
-        offer_refund_signature: *refund_signature,
-        funding_signatures: funding_signatures.clone(),
-        channel_id,
-    };
-
-    Ok((signed_contract, fund_psbt.extract_tx()))
+    Ok(fund_psbt.extract_tx())
 }
 
-/// Signs and return the CET that can be used to close the given contract.
-pub fn get_signed_cet<C: Signing, S: Deref>(
-    secp: &Secp256k1<C>,
-    contract: &SignedContract,
-    contract_info: &ContractInfo,
-    adaptor_info: &AdaptorInfo,
-    attestations: &[(usize, OracleAttestation)],
-    signer: S,
-) -> Result<Transaction, Error>
+/// Verifies the Schnorr adaptor signatures and refund signature of the offer
+/// party [`Sign` message](dlc_messages::SignDlc) for a contract funded with a
+/// taproot output, and creates the accepting party's [`SignedContract`] and
+/// signed fund transaction.
+async fn verify_taproot_signed_contract<W: Deref>(
+    secp: &Secp256k1<All>,
+    accepted_contract: &AcceptedContract,
+    sign_msg: &SignDlc,
+    wallet: &W,
+) -> Result<(SignedContract, Transaction), Error>
 where
-    S::Target: ContractSigner,
+    W::Target: AsyncWallet,
 {
-    let (range_info, sigs) =
-        crate::utils::get_range_info_and_oracle_sigs(contract_info, adaptor_info, attestations)?;
-    let mut cet = contract.accepted_contract.dlc_transactions.cets[range_info.cet_index].clone();
+    let offered_contract = &accepted_contract.offered_contract;
+    let dlc_transactions = &accepted_contract.dlc_transactions;
+    let taproot_signatures = sign_msg.taproot_signatures.as_ref().ok_or_else(|| {
+        Error::InvalidParameters("Missing taproot signatures in sign message.".to_string())
+    })?;
+    let cet_adaptor_signatures = taproot_signatures
+        .cet_adaptor_signatures
+        .iter()
+        .map(|x| x.signature)
+        .collect::<Vec<_>>();
+    let refund_signature = taproot_signatures.refund_signature;
+
+    let key_agg_context = taproot::get_funding_key_agg_context(
+        secp,
+        &offered_contract.offer_params.fund_pubkey,
+        &accepted_contract.accept_params.fund_pubkey,
+    )?;
+    let output_key = key_agg_context.output_key();
+    let fund_output = dlc_transactions.get_fund_output();
+
+    taproot::verify_funding_input_sig(
+        secp,
+        &refund_signature,
+        &dlc_transactions.refund,
+        fund_output,
+        &key_agg_context,
+    )?;
+
+    let (_, adaptor_points) = get_taproot_adaptor_points(secp, offered_contract)?;
+    if cet_adaptor_signatures.len() != adaptor_points.len() {
+        return Err(Error::InvalidParameters(
+            "Invalid number of CET adaptor signatures.".to_string(),
+        ));
+    }
+
+    for ((cet_index, adaptor_point), adaptor_sig) in
+        adaptor_points.iter().zip(cet_adaptor_signatures.iter())
+    {
+        let cet = dlc_transactions
+            .cets
+            .get(*cet_index)
+            .ok_or_else(|| Error::InvalidState("Missing CET for adaptor signature.".to_string()))?;
+        schnorr_adaptor::verify_cet_adaptor_sig_from_point(
+            secp,
+            adaptor_sig,
+            cet,
+            adaptor_point,
+            &output_key,
+            fund_output,
+        )?;
+    }
+
+    let fund_tx = sign_accept_funding_inputs(
+        accepted_contract,
+        &sign_msg.funding_signatures,
+        None,
+        wallet,
+    )
+    .await?;
+
+    let signed_contract = SignedContract {
+        accepted_contract: accepted_contract.clone(),
+        adaptor_signatures: None,
+        offer_refund_signature: None,
+        funding_signatures: sign_msg.funding_signatures.clone(),
+        channel_id: None,
+        close_offer: None,
+        taproot_signatures: Some(TaprootSignatures {
+            cet_adaptor_signatures,
+            refund_signature,
+        }),
+    };
+
+    Ok((signed_contract, fund_tx))
+}
+
+/// Verifies the CET adaptor signatures and refund signature of the counter
+/// party for the given [`AcceptedContract`].
+fn verify_signed_cets(
+    secp: &Secp256k1<All>,
+    accepted_contract: &AcceptedContract,
+    refund_signature: &Signature,
+    cet_adaptor_signatures: &[EcdsaAdaptorSignature],
+    input_value: u64,
+    input_script_pubkey: Option<&Script>,
+    counter_adaptor_pk: Option<PublicKey>,
+) -> Result<(), Error> {
+    let offered_contract = &accepted_contract.offered_contract;
+    let input_script_pubkey = input_script_pubkey
+        .unwrap_or_else(|| &accepted_contract.dlc_transactions.funding_script_pubkey);
+    let counter_adaptor_pk =
+        counter_adaptor_pk.unwrap_or(accepted_contract.offered_contract.offer_params.fund_pubkey);
+
+    dlc::verify_tx_input_sig(
+        secp,
+        refund_signature,
+        &accepted_contract.dlc_transactions.refund,
+        0,
+        input_script_pubkey,
+        input_value,
+        &counter_adaptor_pk,
+    )?;
+
+    let mut adaptor_sig_start = 0;
+
+    for (adaptor_info, contract_info) in accepted_contract
+        .adaptor_infos
+        .iter()
+        .zip(offered_contract.contract_info.iter())
+    {
+        adaptor_sig_start = contract_info.verify_adaptor_info(
+            secp,
+            &counter_adaptor_pk,
+            input_script_pubkey,
+            input_value,
+            &accepted_contract.dlc_transactions.cets,
+            cet_adaptor_signatures,
+            adaptor_sig_start,
+            adaptor_info,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the [`OfferedContract`] of a contract replacing the funding
+/// transaction of the given signed contract with one paying the given fee rate,
+/// and the [`FeeBumpOffer`] message to send to the counter party.
+pub fn offer_fee_bump(
+    signed_contract: &SignedContract,
+    fee_rate_per_vb: u64,
+) -> Result<(OfferedContract, FeeBumpOffer), Error> {
+    if !signed_contract
+        .accepted_contract
+        .offered_contract
+        .is_offer_party
+    {
+        return Err(Error::InvalidState(
+            "Only the offer party can offer a fee bump.".to_string(),
+        ));
+    }
+
+    let offered_contract = get_fee_bump_offered_contract(
+        signed_contract,
+        crate::utils::get_new_temporary_id(),
+        fee_rate_per_vb,
+    )?;
+
+    let offer_msg = FeeBumpOffer {
+        protocol_version: PROTOCOL_VERSION,
+        contract_id: signed_contract.accepted_contract.get_contract_id(),
+        temporary_contract_id: offered_contract.id,
+        fee_rate_per_vb,
+    };
+
+    Ok((offered_contract, offer_msg))
+}
+
+/// Creates the [`OfferedContract`] of a contract replacing the funding
+/// transaction of the given signed contract from a received [`FeeBumpOffer`].
+pub fn on_fee_bump_offer(
+    signed_contract: &SignedContract,
+    offer_msg: &FeeBumpOffer,
+) -> Result<OfferedContract, Error> {
+    if signed_contract
+        .accepted_contract
+        .offered_contract
+        .is_offer_party
+    {
+        return Err(Error::InvalidState(
+            "Only the accept party can receive a fee bump offer.".to_string(),
+        ));
+    }
+
+    get_fee_bump_offered_contract(
+        signed_contract,
+        offer_msg.temporary_contract_id,
+        offer_msg.fee_rate_per_vb,
+    )
+}
+
+fn get_fee_bump_offered_contract(
+    signed_contract: &SignedContract,
+    temporary_contract_id: ContractId,
+    fee_rate_per_vb: u64,
+) -> Result<OfferedContract, Error> {
+    let replaced_contract = &signed_contract.accepted_contract.offered_contract;
+    if signed_contract.channel_id.is_some()
+        || replaced_contract.batch_id.is_some()
+        || replaced_contract.fee_payer.is_some()
+    {
+        return Err(Error::InvalidParameters(
+            "Fee bump is only supported for contracts funded only by their parties.".to_string(),
+        ));
+    }
+
+    if replaced_contract.funding_output_type == FundingOutputType::Taproot {
+        return Err(Error::InvalidParameters(
+            "Fee bump is not supported for contracts funded with a taproot output.".to_string(),
+        ));
+    }
+
+    if fee_rate_per_vb <= replaced_contract.fee_rate_per_vb {
+        return Err(Error::InvalidParameters(
+            "Fee rate must be higher than the one of the replaced contract.".to_string(),
+        ));
+    }
+
+    dlc::util::validate_fee_rate(fee_rate_per_vb)
+        .map_err(|_| Error::InvalidParameters("Fee rate is too high".to_string()))?;
+
+    Ok(OfferedContract {
+        id: temporary_contract_id,
+        fee_rate_per_vb,
+        replaced_contract_id: Some(signed_contract.accepted_contract.get_contract_id()),
+        offer_expiry: None,
+        ..replaced_contract.clone()
+    })
+}
+
+/// Creates the [`AcceptedContract`] of a contract replacing the funding
+/// transaction of the given signed contract, re-using the parameters and
+/// funding inputs of the accept party, and the [`FeeBumpAccept`] message.
+pub fn accept_fee_bump<X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    replaced_contract: &SignedContract,
+    signer_provider: &SP,
+) -> Result<(AcceptedContract, FeeBumpAccept), Error>
+where
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let replaced_contract = &replaced_contract.accepted_contract;
+    let dlc_transactions =
+        create_dlc_transactions(secp, offered_contract, &replaced_contract.accept_params)?;
+    let fund_output_value = dlc_transactions.get_fund_output().value;
+
+    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
+    let (accepted_contract, adaptor_sigs) = accept_contract_internal(
+        secp,
+        offered_contract,
+        &replaced_contract.accept_params,
+        &replaced_contract.funding_inputs,
+        &signer.get_secret_key()?,
+        fund_output_value,
+        None,
+        &dlc_transactions,
+    )?;
+
+    let accept_msg = FeeBumpAccept {
+        protocol_version: PROTOCOL_VERSION,
+        temporary_contract_id: offered_contract.id,
+        cet_adaptor_signatures: (&adaptor_sigs as &[_]).into(),
+        refund_signature: accepted_contract.get_accept_refund_signature()?,
+    };
+
+    Ok((accepted_contract, accept_msg))
+}
+
+/// Verifies the signatures of the accept party for the transactions of a
+/// contract replacing the funding transaction of the given signed contract,
+/// and creates the offering party's [`SignedContract`] and [`SignDlc`] message.
+pub async fn verify_fee_bump_accept_and_sign<W: Deref, X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    replaced_contract: &SignedContract,
+    accept_msg: &FeeBumpAccept,
+    wallet: &W,
+    signer_provider: &SP,
+) -> Result<(SignedContract, SignDlc), Error>
+where
+    W::Target: AsyncWallet,
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let replaced_contract = &replaced_contract.accepted_contract;
+    let cet_adaptor_signatures: Vec<_> = (&accept_msg.cet_adaptor_signatures).into();
+
+    let dlc_transactions =
+        create_dlc_transactions(secp, offered_contract, &replaced_contract.accept_params)?;
+    let fund_output_value = dlc_transactions.get_fund_output().value;
+
+    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
+    let (signed_contract, adaptor_sigs) = verify_accepted_and_sign_contract_internal(
+        secp,
+        offered_contract,
+        &replaced_contract.accept_params,
+        &replaced_contract.funding_inputs,
+        &accept_msg.refund_signature,
+        &cet_adaptor_signatures,
+        fund_output_value,
+        wallet,
+        &signer,
+        None,
+        None,
+        &dlc_transactions,
+        None,
+    )
+    .await?;
+
+    let signed_msg: SignDlc = signed_contract.get_sign_dlc(adaptor_sigs);
+
+    Ok((signed_contract, signed_msg))
+}
+
+/// Creates a transaction spending the fund output of the given contract, paying
+/// `counter_payout` to the party receiving the close offer and the rest of the
+/// total collateral to the offering one, the fee being paid from the fee reserve
+/// of the fund output.
+fn get_close_tx(
+    signed_contract: &SignedContract,
+    counter_payout: u64,
+    is_close_offer_party: bool,
+) -> Result<Transaction, Error> {
+    let accepted_contract = &signed_contract.accepted_contract;
+    let offered_contract = &accepted_contract.offered_contract;
+    let total_collateral = offered_contract.total_collateral;
+
+    if counter_payout > total_collateral {
+        return Err(Error::InvalidParameters(
+            "Counter payout is greater than total collateral".to_string(),
+        ));
+    }
+
+    let own_payout = if is_close_offer_party {
+        total_collateral - counter_payout
+    } else {
+        counter_payout
+    };
+    let (offer_payout, accept_payout) = if offered_contract.is_offer_party {
+        (own_payout, total_collateral - own_payout)
+    } else {
+        (total_collateral - own_payout, own_payout)
+    };
+
+    let dlc_transactions = &accepted_contract.dlc_transactions;
+    // The fee of the closing transaction is split equally between the parties.
+    dlc::channel::create_collaborative_close_transaction(
+        &offered_contract.offer_params,
+        offer_payout,
+        &accepted_contract.accept_params,
+        accept_payout,
+        dlc_transactions.get_fund_outpoint(),
+        dlc_transactions.get_fund_output().value,
+        offered_contract.fee_rate_per_vb,
+        50,
+    )
+    .map_err(Error::from)
+}
+
+fn get_counter_fund_pubkey(signed_contract: &SignedContract) -> &PublicKey {
+    let accepted_contract = &signed_contract.accepted_contract;
+    if accepted_contract.offered_contract.is_offer_party {
+        &accepted_contract.accept_params.fund_pubkey
+    } else {
+        &accepted_contract.offered_contract.offer_params.fund_pubkey
+    }
+}
+
+fn check_can_close<T: Deref>(signed_contract: &SignedContract, time: &T) -> Result<(), Error>
+where
+    T::Target: Time,
+{
+    if signed_contract.channel_id.is_some() {
+        return Err(Error::InvalidState(
+            "Contracts associated with a channel are closed through the channel.".to_string(),
+        ));
+    }
+
+    if signed_contract
+        .accepted_contract
+        .offered_contract
+        .funding_output_type
+        == FundingOutputType::Taproot
+    {
+        return Err(Error::InvalidState(
+            "Collaborative close is not supported for contracts funded with a taproot output."
+                .to_string(),
+        ));
+    }
+
+    if let Some(close_offer) = &signed_contract.close_offer {
+        if close_offer.is_offer_party && close_offer.timeout > time.unix_time_now() {
+            return Err(Error::InvalidState(
+                "An offer to close the contract is already pending.".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a [`CloseOffer`] message to collaboratively close the given contract
+/// with the given payout for the counter party, and records the offer in the
+/// contract.
+pub fn offer_contract_close<C: Signing, SP: Deref, T: Deref>(
+    secp: &Secp256k1<C>,
+    signed_contract: &mut SignedContract,
+    counter_payout: u64,
+    peer_timeout: u64,
+    signer_provider: &SP,
+    time: &T,
+) -> Result<CloseOffer, Error>
+where
+    SP::Target: ContractSignerProvider,
+    T::Target: Time,
+{
+    check_can_close(signed_contract, time)?;
+
+    let close_tx = get_close_tx(signed_contract, counter_payout, true)?;
+    let accepted_contract = &signed_contract.accepted_contract;
+    let signer =
+        signer_provider.derive_contract_signer(accepted_contract.offered_contract.keys_id)?;
+
+    let close_signature = dlc::util::get_raw_sig_for_tx_input(
+        secp,
+        &close_tx,
+        0,
+        &accepted_contract.dlc_transactions.funding_script_pubkey,
+        accepted_contract.dlc_transactions.get_fund_output().value,
+        &signer.get_secret_key()?,
+    )?;
+
+    let contract_id = accepted_contract.get_contract_id();
+    signed_contract.close_offer = Some(PendingCloseOffer {
+        is_offer_party: true,
+        counter_payout,
+        offer_signature: close_signature,
+        close_tx,
+        timeout: time.unix_time_now() + peer_timeout,
+    });
+
+    Ok(CloseOffer {
+        protocol_version: PROTOCOL_VERSION,
+        contract_id,
+        counter_payout,
+        close_signature,
+    })
+}
+
+/// Validates the given [`CloseOffer`] and records it in the contract so that it
+/// can later be accepted.
+pub fn on_close_offer<T: Deref>(
+    secp: &Secp256k1<All>,
+    signed_contract: &mut SignedContract,
+    close_offer: &CloseOffer,
+    peer_timeout: u64,
+    time: &T,
+) -> Result<(), Error>
+where
+    T::Target: Time,
+{
+    check_can_close(signed_contract, time)?;
+
+    let close_tx = get_close_tx(signed_contract, close_offer.counter_payout, false)?;
+    let dlc_transactions = &signed_contract.accepted_contract.dlc_transactions;
+
+    dlc::verify_tx_input_sig(
+        secp,
+        &close_offer.close_signature,
+        &close_tx,
+        0,
+        &dlc_transactions.funding_script_pubkey,
+        dlc_transactions.get_fund_output().value,
+        get_counter_fund_pubkey(signed_contract),
+    )?;
+
+    signed_contract.close_offer = Some(PendingCloseOffer {
+        is_offer_party: false,
+        counter_payout: close_offer.counter_payout,
+        offer_signature: close_offer.close_signature,
+        close_tx,
+        timeout: time.unix_time_now() + peer_timeout,
+    });
+
+    Ok(())
+}
+
+/// Accepts the close offer recorded in the given contract, returning the fully
+/// signed close transaction, the closed contract and the [`CloseAccept`]
+/// message to send to the counter party.
+pub fn accept_contract_close<C: Signing, SP: Deref, T: Deref>(
+    secp: &Secp256k1<C>,
+    signed_contract: &SignedContract,
+    signer_provider: &SP,
+    time: &T,
+) -> Result<(Transaction, CollaborativelyClosedContract, CloseAccept), Error>
+where
+    SP::Target: ContractSignerProvider,
+    T::Target: Time,
+{
+    let close_offer = match &signed_contract.close_offer {
+        Some(c) if !c.is_offer_party => c,
+        _ => {
+            return Err(Error::InvalidState(
+                "No offer to close the contract was received.".to_string(),
+            ))
+        }
+    };
+
+    if close_offer.timeout < time.unix_time_now() {
+        return Err(Error::InvalidState(
+            "The offer to close the contract has expired.".to_string(),
+        ));
+    }
+
+    let accepted_contract = &signed_contract.accepted_contract;
+    let dlc_transactions = &accepted_contract.dlc_transactions;
+    let fund_output_value = dlc_transactions.get_fund_output().value;
+    let signer =
+        signer_provider.derive_contract_signer(accepted_contract.offered_contract.keys_id)?;
+    let fund_sk = signer.get_secret_key()?;
+
+    let close_signature = dlc::util::get_raw_sig_for_tx_input(
+        secp,
+        &close_offer.close_tx,
+        0,
+        &dlc_transactions.funding_script_pubkey,
+        fund_output_value,
+        &fund_sk,
+    )?;
+
+    let mut close_tx = close_offer.close_tx.clone();
+    dlc::util::sign_multi_sig_input(
+        secp,
+        &mut close_tx,
+        &close_offer.offer_signature,
+        get_counter_fund_pubkey(signed_contract),
+        &fund_sk,
+        &dlc_transactions.funding_script_pubkey,
+        fund_output_value,
+        0,
+    )?;
+
+    let closed_contract = get_collaboratively_closed_contract(signed_contract, close_tx.clone());
+
+    let msg = CloseAccept {
+        protocol_version: PROTOCOL_VERSION,
+        contract_id: accepted_contract.get_contract_id(),
+        close_signature,
+    };
+
+    Ok((close_tx, closed_contract, msg))
+}
+
+/// Verifies the signature of the counter party in the given [`CloseAccept`]
+/// message for the close transaction offered by the local party, returning the
+/// fully signed close transaction and the closed contract.
+pub fn on_close_accept<SP: Deref>(
+    secp: &Secp256k1<All>,
+    signed_contract: &SignedContract,
+    close_accept: &CloseAccept,
+    signer_provider: &SP,
+) -> Result<(Transaction, CollaborativelyClosedContract), Error>
+where
+    SP::Target: ContractSignerProvider,
+{
+    let close_offer = match &signed_contract.close_offer {
+        Some(c) if c.is_offer_party => c,
+        _ => {
+            return Err(Error::InvalidState(
+                "No offer to close the contract was sent.".to_string(),
+            ))
+        }
+    };
+
+    let accepted_contract = &signed_contract.accepted_contract;
+    let dlc_transactions = &accepted_contract.dlc_transactions;
+    let fund_output_value = dlc_transactions.get_fund_output().value;
+    let counter_fund_pubkey = get_counter_fund_pubkey(signed_contract);
+
+    dlc::verify_tx_input_sig(
+        secp,
+        &close_accept.close_signature,
+        &close_offer.close_tx,
+        0,
+        &dlc_transactions.funding_script_pubkey,
+        fund_output_value,
+        counter_fund_pubkey,
+    )?;
+
+    let signer =
+        signer_provider.derive_contract_signer(accepted_contract.offered_contract.keys_id)?;
+    let mut close_tx = close_offer.close_tx.clone();
+    dlc::util::sign_multi_sig_input(
+        secp,
+        &mut close_tx,
+        &close_accept.close_signature,
+        counter_fund_pubkey,
+        &signer.get_secret_key()?,
+        &dlc_transactions.funding_script_pubkey,
+        fund_output_value,
+        0,
+    )?;
+
+    let closed_contract = get_collaboratively_closed_contract(signed_contract, close_tx.clone());
+
+    Ok((close_tx, closed_contract))
+}
+
+/// Returns the [`CollaborativelyClosedContract`] corresponding to the given
+/// contract closed by the given close transaction.
+pub(crate) fn get_collaboratively_closed_contract(
+    signed_contract: &SignedContract,
+    close_tx: Transaction,
+) -> CollaborativelyClosedContract {
+    let accepted_contract = &signed_contract.accepted_contract;
+    CollaborativelyClosedContract {
+        contract_id: accepted_contract.get_contract_id(),
+        temporary_contract_id: accepted_contract.offered_contract.id,
+        counter_party_id: accepted_contract.offered_contract.counter_party,
+        pnl: accepted_contract.compute_pnl(&close_tx),
+        close_tx,
+    }
+}
+
+fn check_can_roll_over(signed_contract: &SignedContract) -> Result<(), Error> {
+    if signed_contract.channel_id.is_some() {
+        return Err(Error::InvalidState(
+            "Contracts associated with a channel are renewed through the channel.".to_string(),
+        ));
+    }
+
+    if signed_contract
+        .accepted_contract
+        .offered_contract
+        .funding_output_type
+        == FundingOutputType::Taproot
+    {
+        return Err(Error::InvalidState(
+            "Rollover is not supported for contracts funded with a taproot output.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the parameters of the local and counter party of the given contract
+/// to use in the contract it is rolled over into, with the given collaterals.
+fn get_rollover_party_params(
+    signed_contract: &SignedContract,
+    own_collateral: u64,
+    counter_collateral: u64,
+) -> (PartyParams, PartyParams) {
+    let accepted_contract = &signed_contract.accepted_contract;
+    let (mut own_params, mut counter_params) = if accepted_contract.offered_contract.is_offer_party
+    {
+        (
+            accepted_contract.offered_contract.offer_params.clone(),
+            accepted_contract.accept_params.clone(),
+        )
+    } else {
+        (
+            accepted_contract.accept_params.clone(),
+            accepted_contract.offered_contract.offer_params.clone(),
+        )
+    };
+    own_params.collateral = own_collateral;
+    counter_params.collateral = counter_collateral;
+    (own_params, counter_params)
+}
+
+fn create_rollover_dlc_transactions(
+    offered_contract: &OfferedContract,
+    accept_params: &PartyParams,
+    rolled_over_contract: &SignedContract,
+) -> Result<DlcTransactions, Error> {
+    let payouts =
+        offered_contract.contract_info[0].get_payouts(offered_contract.total_collateral)?;
+    Ok(dlc::create_dlc_transactions_for_fund_output(
+        &rolled_over_contract.accepted_contract.dlc_transactions,
+        &offered_contract.offer_params,
+        accept_params,
+        &payouts,
+        offered_contract.refund_locktime,
+        offered_contract.cet_locktime,
+        offered_contract.anchor_outputs,
+    )?)
+}
+
+/// Creates the [`OfferedContract`] of a contract replacing the given one, whose
+/// CETs and refund transaction spend the same fund output, and the
+/// [`RolloverOffer`] message to send to the counter party. The local party
+/// becomes the offer party of the new contract, regardless of its role in the
+/// rolled over one.
+///
+/// Note that the CETs of the rolled over contract are not revoked, and remain
+/// valid until the fund output is spent.
+pub fn offer_contract_rollover<T: Deref>(
+    signed_contract: &SignedContract,
+    contract_input: &ContractInput,
+    oracle_announcements: Vec<Vec<OracleAnnouncement>>,
+    refund_delay: u32,
+    time: &T,
+) -> Result<(OfferedContract, RolloverOffer), Error>
+where
+    T::Target: Time,
+{
+    contract_input.validate()?;
+    check_can_roll_over(signed_contract)?;
+
+    let rolled_over_contract = &signed_contract.accepted_contract.offered_contract;
+
+    if contract_input.offer_collateral + contract_input.accept_collateral
+        != rolled_over_contract.total_collateral
+    {
+        return Err(Error::InvalidParameters(
+            "Sum of collaterals must equal the total collateral of the rolled over contract."
+                .to_string(),
+        ));
+    }
+
+    if contract_input.offer_expiry.is_some() {
+        return Err(Error::InvalidParameters(
+            "Offer expiry is not supported for contract rollovers.".to_string(),
+        ));
+    }
+
+    if contract_input.funding_output_type != FundingOutputType::P2wsh {
+        return Err(Error::InvalidParameters(
+            "Taproot funding is not supported for contract rollovers.".to_string(),
+        ));
+    }
+
+    if contract_input.anchor_outputs != rolled_over_contract.anchor_outputs {
+        return Err(Error::InvalidParameters(
+            "Anchor outputs must be used if and only if the rolled over contract uses them."
+                .to_string(),
+        ));
+    }
+
+    let (offer_params, _) = get_rollover_party_params(
+        signed_contract,
+        contract_input.offer_collateral,
+        contract_input.accept_collateral,
+    );
+
+    let mut offered_contract = OfferedContract::new(
+        crate::utils::get_new_temporary_id(),
+        contract_input,
+        oracle_announcements,
+        &offer_params,
+        &[],
+        &rolled_over_contract.counter_party,
+        refund_delay,
+        time.unix_time_now() as u32,
+        rolled_over_contract.keys_id,
+    );
+    offered_contract.fund_output_serial_id = rolled_over_contract.fund_output_serial_id;
+    offered_contract.fee_rate_per_vb = rolled_over_contract.fee_rate_per_vb;
+    offered_contract.single_funded = false;
+    offered_contract.offer_expiry = None;
+    offered_contract.rolled_over_contract_id =
+        Some(signed_contract.accepted_contract.get_contract_id());
+
+    offered_contract.validate()?;
+
+    let offer_msg = RolloverOffer {
+        protocol_version: PROTOCOL_VERSION,
+        contract_id: signed_contract.accepted_contract.get_contract_id(),
+        temporary_contract_id: offered_contract.id,
+        offer_collateral: contract_input.offer_collateral,
+        contract_info: (&offered_contract).into(),
+        cet_locktime: offered_contract.cet_locktime,
+        refund_locktime: offered_contract.refund_locktime,
+    };
+
+    Ok((offered_contract, offer_msg))
+}
+
+/// Creates the [`OfferedContract`] of a contract replacing the given one from a
+/// received [`RolloverOffer`].
+pub fn on_rollover_offer(
+    signed_contract: &SignedContract,
+    offer_msg: &RolloverOffer,
+) -> Result<OfferedContract, Error> {
+    check_can_roll_over(signed_contract)?;
+
+    let rolled_over_contract = &signed_contract.accepted_contract.offered_contract;
+    let total_collateral = offer_msg.contract_info.get_total_collateral();
+
+    if total_collateral != rolled_over_contract.total_collateral
+        || offer_msg.offer_collateral > total_collateral
+    {
+        return Err(Error::InvalidParameters(
+            "Invalid collateral for contract rollover.".to_string(),
+        ));
+    }
+
+    let (_, offer_params) = get_rollover_party_params(
+        signed_contract,
+        total_collateral - offer_msg.offer_collateral,
+        offer_msg.offer_collateral,
+    );
+
+    let offered_contract = OfferedContract {
+        id: offer_msg.temporary_contract_id,
+        is_offer_party: false,
+        contract_info: crate::conversion_utils::get_contract_info_and_announcements(
+            &offer_msg.contract_info,
+        )?,
+        counter_party: rolled_over_contract.counter_party,
+        offer_params,
+        total_collateral,
+        funding_inputs: Vec::new(),
+        fund_output_serial_id: rolled_over_contract.fund_output_serial_id,
+        fee_rate_per_vb: rolled_over_contract.fee_rate_per_vb,
+        cet_locktime: offer_msg.cet_locktime,
+        refund_locktime: offer_msg.refund_locktime,
+        anchor_outputs: rolled_over_contract.anchor_outputs,
+        batch_id: None,
+        single_funded: false,
+        fee_payer: None,
+        replaced_contract_id: None,
+        rolled_over_contract_id: Some(signed_contract.accepted_contract.get_contract_id()),
+        offer_expiry: None,
+        funding_output_type: FundingOutputType::P2wsh,
+        taproot_funding_nonces: None,
+        taproot_secret_nonces: None,
+        keys_id: rolled_over_contract.keys_id,
+    };
+
+    offered_contract.validate()?;
+
+    Ok(offered_contract)
+}
+
+/// Creates an [`AcceptedContract`] for the given offered rollover contract and
+/// the [`RolloverAccept`] message containing the CET adaptor signatures and
+/// refund signature of the local party.
+pub fn accept_contract_rollover<X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    rolled_over_contract: &SignedContract,
+    signer_provider: &SP,
+) -> Result<(AcceptedContract, RolloverAccept), Error>
+where
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let offer_collateral = offered_contract.offer_params.collateral;
+    let (accept_params, _) = get_rollover_party_params(
+        rolled_over_contract,
+        offered_contract.total_collateral - offer_collateral,
+        offer_collateral,
+    );
+
+    let dlc_transactions =
+        create_rollover_dlc_transactions(offered_contract, &accept_params, rolled_over_contract)?;
+    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
+
+    let (accepted_contract, adaptor_sigs) = accept_contract_internal(
+        secp,
+        offered_contract,
+        &accept_params,
+        &[],
+        &signer.get_secret_key()?,
+        dlc_transactions.get_fund_output().value,
+        None,
+        &dlc_transactions,
+    )?;
+
+    let accept_msg = RolloverAccept {
+        protocol_version: PROTOCOL_VERSION,
+        temporary_contract_id: offered_contract.id,
+        cet_adaptor_signatures: (&adaptor_sigs as &[_]).into(),
+        refund_signature: accepted_contract.get_accept_refund_signature()?,
+    };
+
+    Ok((accepted_contract, accept_msg))
+}
+
+/// Verifies the signatures of the counter party contained in the given
+/// [`RolloverAccept`] message, returning the [`SignedContract`] replacing the
+/// rolled over one and the [`RolloverConfirm`] message containing the
+/// signatures of the local party.
+pub fn verify_rollover_accept_and_confirm<X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
+    offered_contract: &OfferedContract,
+    rolled_over_contract: &SignedContract,
+    accept_msg: &RolloverAccept,
+    signer_provider: &SP,
+) -> Result<(SignedContract, RolloverConfirm), Error>
+where
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let offer_collateral = offered_contract.offer_params.collateral;
+    let (_, accept_params) = get_rollover_party_params(
+        rolled_over_contract,
+        offer_collateral,
+        offered_contract.total_collateral - offer_collateral,
+    );
+
+    let dlc_transactions =
+        create_rollover_dlc_transactions(offered_contract, &accept_params, rolled_over_contract)?;
+    let cet_adaptor_signatures: Vec<_> = (&accept_msg.cet_adaptor_signatures).into();
+    let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
+
+    let (accepted_contract, adaptor_sigs, offer_refund_signature) = verify_accepted_and_sign_cets(
+        secp,
+        offered_contract,
+        &accept_params,
+        &[],
+        &accept_msg.refund_signature,
+        &cet_adaptor_signatures,
+        dlc_transactions.get_fund_output().value,
+        &signer,
+        None,
+        None,
+        &dlc_transactions,
+    )?;
+
+    let signed_contract = SignedContract {
+        accepted_contract,
+        adaptor_signatures: None,
+        offer_refund_signature: Some(offer_refund_signature),
+        funding_signatures: FundingSignatures {
+            funding_signatures: Vec::new(),
+        },
+        channel_id: None,
+        close_offer: None,
+        taproot_signatures: None,
+    };
+
+    let confirm_msg = RolloverConfirm {
+        protocol_version: PROTOCOL_VERSION,
+        contract_id: signed_contract.accepted_contract.get_contract_id(),
+        cet_adaptor_signatures: (&adaptor_sigs as &[_]).into(),
+        refund_signature: offer_refund_signature,
+    };
+
+    Ok((signed_contract, confirm_msg))
+}
+
+/// Verifies the signatures of the counter party contained in the given
+/// [`RolloverConfirm`] message, returning the [`SignedContract`] replacing the
+/// rolled over one.
+pub fn verify_rollover_confirm(
+    secp: &Secp256k1<All>,
+    accepted_contract: &AcceptedContract,
+    confirm_msg: &RolloverConfirm,
+) -> Result<SignedContract, Error> {
+    let cet_adaptor_signatures: Vec<_> = (&confirm_msg.cet_adaptor_signatures).into();
+
+    verify_signed_cets(
+        secp,
+        accepted_contract,
+        &confirm_msg.refund_signature,
+        &cet_adaptor_signatures,
+        accepted_contract.dlc_transactions.get_fund_output().value,
+        None,
+        None,
+    )?;
+
+    Ok(SignedContract {
+        accepted_contract: accepted_contract.clone(),
+        adaptor_signatures: Some(cet_adaptor_signatures),
+        offer_refund_signature: Some(confirm_msg.refund_signature),
+        funding_signatures: FundingSignatures {
+            funding_signatures: Vec::new(),
+        },
+        channel_id: None,
+        close_offer: None,
+        taproot_signatures: None,
+    })
+}
+
+/// Creates the [`OfferedContract`]s of a batch of contracts funded by a single
+/// funding transaction, and the [`OfferBatchDlc`] message to send to the
+/// counter party. The contracts are returned ordered by temporary id, the
+/// first one carrying the funding inputs of the batch.
+pub async fn offer_batch_contract<
+    W: Deref,
+    B: Deref,
+    T: Deref,
+    X: ContractSigner,
+    SP: Deref,
+    C: Signing,
+>(
+    secp: &Secp256k1<C>,
+    contract_inputs: &[ContractInput],
+    oracle_announcements: Vec<Vec<Vec<OracleAnnouncement>>>,
+    refund_delay: u32,
+    counter_party: &PublicKey,
+    wallet: &W,
+    blockchain: &B,
+    time: &T,
+    signer_provider: &SP,
+) -> Result<(Vec<OfferedContract>, OfferBatchDlc), Error>
+where
+    W::Target: AsyncWallet,
+    B::Target: AsyncBlockchain,
+    T::Target: Time,
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let fee_rate = contract_inputs
+        .first()
+        .ok_or_else(|| Error::InvalidParameters("Batch cannot be empty.".to_string()))?
+        .fee_rate;
+    if contract_inputs.len() != oracle_announcements.len() {
+        return Err(Error::InvalidParameters(
+            "Expected oracle announcements for each contract.".to_string(),
+        ));
+    }
+
+    for contract_input in contract_inputs {
+        contract_input.validate()?;
+        if contract_input.fee_rate != fee_rate {
+            return Err(Error::InvalidParameters(
+                "All contracts of a batch must use the same fee rate.".to_string(),
+            ));
+        }
+        if contract_input.single_funded {
+            return Err(Error::InvalidParameters(
+                "Single funded contracts cannot be part of a batch.".to_string(),
+            ));
+        }
+        if contract_input.offer_expiry.is_some() {
+            return Err(Error::InvalidParameters(
+                "Offer expiry is not supported for contracts part of a batch.".to_string(),
+            ));
+        }
+        if contract_input.funding_output_type != FundingOutputType::P2wsh {
+            return Err(Error::InvalidParameters(
+                "Taproot funding is not supported for contracts part of a batch.".to_string(),
+            ));
+        }
+    }
+
+    let mut contracts = contract_inputs
+        .iter()
+        .zip(oracle_announcements)
+        .map(|(contract_input, announcements)| {
+            let id = crate::utils::get_new_temporary_id();
+            let keys_id = signer_provider.derive_signer_key_id(true, id);
+            let signer = signer_provider.derive_contract_signer(keys_id)?;
+            Ok((id, keys_id, signer, contract_input, announcements))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    contracts.sort_by_key(|x| x.0);
+
+    let (party_params, funding_inputs) = crate::utils::get_batch_party_params(
+        secp,
+        &contracts
+            .iter()
+            .map(|(_, _, signer, input, _)| {
+                (
+                    input.offer_collateral,
+                    input.get_dlc_transactions_options(),
+                    signer,
+                )
+            })
+            .collect::<Vec<_>>(),
+        fee_rate,
+        wallet,
+        blockchain,
+    )
+    .await?;
+
+    let batch_id = crate::utils::get_new_temporary_id();
+    let cet_locktime = time.unix_time_now() as u32;
+
+    let offered_contracts = contracts
+        .into_iter()
+        .zip(party_params.iter())
+        .enumerate()
+        .map(
+            |(i, ((id, keys_id, _, contract_input, announcements), party_params))| {
+                let contract_funding_inputs: &[FundingInput] =
+                    if i == 0 { &funding_inputs[..] } else { &[] };
+                let mut offered_contract = OfferedContract::new(
+                    id,
+                    contract_input,
+                    announcements,
+                    party_params,
+                    contract_funding_inputs,
+                    counter_party,
+                    refund_delay,
+                    cet_locktime,
+                    keys_id,
+                );
+                offered_contract.batch_id = Some(batch_id);
+                offered_contract
+            },
+        )
+        .collect::<Vec<_>>();
+
+    let offer_dlcs = offered_contracts
+        .iter()
+        .map(|x| {
+            let mut offer_dlc: OfferDlc = x.into();
+            offer_dlc.funding_inputs.clear();
+            offer_dlc
+        })
+        .collect();
+
+    let offer_msg = OfferBatchDlc {
+        protocol_version: PROTOCOL_VERSION,
+        temporary_batch_id: batch_id,
+        funding_inputs,
+        offer_dlcs,
+    };
+
+    Ok((offered_contracts, offer_msg))
+}
+
+/// Creates the [`OfferedContract`]s of a batch from the received
+/// [`OfferBatchDlc`] message, attaching the funding inputs of the batch to the
+/// contract with the lowest temporary id. The contracts are returned ordered
+/// by temporary id.
+pub fn get_offered_batch_contracts<SP: Deref>(
+    offer_msg: &OfferBatchDlc,
+    counter_party: PublicKey,
+    signer_provider: &SP,
+) -> Result<Vec<OfferedContract>, Error>
+where
+    SP::Target: ContractSignerProvider,
+{
+    let mut offer_dlcs = offer_msg.offer_dlcs.clone();
+    offer_dlcs.sort_by_key(|x| x.temporary_contract_id);
+
+    offer_dlcs
+        .iter_mut()
+        .enumerate()
+        .map(|(i, offer_dlc)| {
+            if i == 0 {
+                offer_dlc.funding_inputs = offer_msg.funding_inputs.clone();
+            }
+            let keys_id =
+                signer_provider.derive_signer_key_id(false, offer_dlc.temporary_contract_id);
+            let mut offered_contract =
+                OfferedContract::try_from_offer_dlc(offer_dlc, counter_party, keys_id)?;
+            offered_contract.batch_id = Some(offer_msg.temporary_batch_id);
+            Ok(offered_contract)
+        })
+        .collect()
+}
+
+/// Creates the [`AcceptedContract`]s for a batch of offered contracts ordered
+/// by temporary id, and the [`AcceptBatchDlc`] message to send to the offer
+/// party.
+pub async fn accept_batch_contract<W: Deref, X: ContractSigner, SP: Deref, B: Deref>(
+    secp: &Secp256k1<All>,
+    offered_contracts: &[OfferedContract],
+    wallet: &W,
+    signer_provider: &SP,
+    blockchain: &B,
+) -> Result<(Vec<AcceptedContract>, AcceptBatchDlc), Error>
+where
+    W::Target: AsyncWallet,
+    B::Target: AsyncBlockchain,
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    let (batch_id, fee_rate) = get_batch_info(offered_contracts)?;
+
+    let signers = offered_contracts
+        .iter()
+        .map(|x| signer_provider.derive_contract_signer(x.keys_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (accept_params, funding_inputs) = crate::utils::get_batch_party_params(
+        secp,
+        &offered_contracts
+            .iter()
+            .zip(signers.iter())
+            .map(|(x, signer)| {
+                (
+                    x.total_collateral - x.offer_params.collateral,
+                    x.get_dlc_transactions_options(),
+                    signer,
+                )
+            })
+            .collect::<Vec<_>>(),
+        fee_rate,
+        wallet,
+        blockchain,
+    )
+    .await?;
+
+    let dlc_transactions = create_batch_dlc_transactions(secp, offered_contracts, &accept_params)?;
+
+    let mut accepted_contracts = Vec::with_capacity(offered_contracts.len());
+    let mut accept_dlcs = Vec::with_capacity(offered_contracts.len());
+
+    for (i, offered_contract) in offered_contracts.iter().enumerate() {
+        let contract_funding_inputs: &[FundingInput] =
+            if i == 0 { &funding_inputs[..] } else { &[] };
+        let (accepted_contract, adaptor_sigs) = accept_contract_internal(
+            secp,
+            offered_contract,
+            &accept_params[i],
+            contract_funding_inputs,
+            &signers[i].get_secret_key()?,
+            dlc_transactions[i].get_fund_output().value,
+            None,
+            &dlc_transactions[i],
+        )?;
+
+        let mut accept_dlc = accepted_contract.get_accept_contract_msg(&adaptor_sigs);
+        accept_dlc.funding_inputs.clear();
+
+        accepted_contracts.push(accepted_contract);
+        accept_dlcs.push(accept_dlc);
+    }
+
+    let accept_msg = AcceptBatchDlc {
+        protocol_version: PROTOCOL_VERSION,
+        temporary_batch_id: batch_id,
+        funding_inputs,
+        fund_txid: dlc_transactions[0].fund.txid(),
+        accept_dlcs,
+        fund_output_indexes: dlc_transactions
+            .iter()
+            .map(|x| x.get_fund_output_index() as u16)
+            .collect(),
+    };
+
+    Ok((accepted_contracts, accept_msg))
+}
+
+/// Verifies the [`AcceptBatchDlc`] message received for a batch of offered
+/// contracts ordered by temporary id, creates the [`SignedContract`]s and
+/// generates the [`SignBatchDlc`] message to send to the accept party.
+pub async fn verify_accepted_and_sign_batch_contract<W: Deref, X: ContractSigner, SP: Deref>(
+    secp: &Secp256k1<All>,
+    offered_contracts: &[OfferedContract],
+    accept_msg: &AcceptBatchDlc,
+    wallet: &W,
+    signer_provider: &SP,
+) -> Result<(Vec<SignedContract>, SignBatchDlc), Error>
+where
+    W::Target: AsyncWallet,
+    SP::Target: ContractSignerProvider<Signer = X>,
+{
+    get_batch_info(offered_contracts)?;
+
+    let is_matching = accept_msg.accept_dlcs.len() == offered_contracts.len()
+        && accept_msg.fund_output_indexes.len() == offered_contracts.len()
+        && accept_msg
+            .accept_dlcs
+            .iter()
+            .zip(offered_contracts.iter())
+            .all(|(a, o)| a.temporary_contract_id == o.id && a.funding_inputs.is_empty());
+    if !is_matching {
+        return Err(Error::InvalidParameters(
+            "Accept message does not match the offered batch.".to_string(),
+        ));
+    }
+
+    let accept_params = accept_msg
+        .accept_dlcs
+        .iter()
+        .enumerate()
+        .map(|(i, accept_dlc)| {
+            let (inputs, input_amount) = if i == 0 {
+                get_tx_input_infos(&accept_msg.funding_inputs)?
+            } else {
+                (Vec::new(), 0)
+            };
+            Ok(PartyParams {
+                fund_pubkey: accept_dlc.funding_pubkey,
+                change_script_pubkey: accept_dlc.change_spk.clone(),
+                change_serial_id: accept_dlc.change_serial_id,
+                payout_script_pubkey: accept_dlc.payout_spk.clone(),
+                payout_serial_id: accept_dlc.payout_serial_id,
+                inputs,
+                input_amount,
+                collateral: accept_dlc.accept_collateral,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let dlc_transactions = create_batch_dlc_transactions(secp, offered_contracts, &accept_params)?;
+
+    let is_valid_fund_tx = dlc_transactions[0].fund.txid() == accept_msg.fund_txid
+        && dlc_transactions
+            .iter()
+            .zip(accept_msg.fund_output_indexes.iter())
+            .all(|(x, i)| x.get_fund_output_index() == *i as usize);
+    if !is_valid_fund_tx {
+        return Err(Error::InvalidParameters(
+            "Fund transaction does not match the one of the accept message.".to_string(),
+        ));
+    }
+
+    let mut signed_contracts = Vec::with_capacity(offered_contracts.len());
+    let mut sign_dlcs = Vec::with_capacity(offered_contracts.len());
+
+    for (i, (offered_contract, accept_dlc)) in offered_contracts
+        .iter()
+        .zip(accept_msg.accept_dlcs.iter())
+        .enumerate()
+    {
+        let contract_funding_inputs: &[FundingInput] = if i == 0 {
+            &accept_msg.funding_inputs[..]
+        } else {
+            &[]
+        };
+        let cet_adaptor_signatures: Vec<_> = (&accept_dlc.cet_adaptor_signatures).into();
+        let signer = signer_provider.derive_contract_signer(offered_contract.keys_id)?;
+        let (signed_contract, adaptor_sigs) = verify_accepted_and_sign_contract_internal(
+            secp,
+            offered_contract,
+            &accept_params[i],
+            contract_funding_inputs,
+            get_refund_signature(&accept_dlc.refund_signature)?,
+            &cet_adaptor_signatures,
+            dlc_transactions[i].get_fund_output().value,
+            wallet,
+            &signer,
+            None,
+            None,
+            &dlc_transactions[i],
+            None,
+        )
+        .await?;
+
+        sign_dlcs.push(signed_contract.get_sign_dlc(adaptor_sigs));
+        signed_contracts.push(signed_contract);
+    }
+
+    let funding_signatures = std::mem::replace(
+        &mut sign_dlcs[0].funding_signatures,
+        FundingSignatures {
+            funding_signatures: Vec::new(),
+        },
+    );
+
+    let sign_msg = SignBatchDlc {
+        protocol_version: PROTOCOL_VERSION,
+        fund_txid: accept_msg.fund_txid,
+        funding_signatures,
+        sign_dlcs,
+    };
+
+    Ok((signed_contracts, sign_msg))
+}
+
+/// Verifies the [`SignBatchDlc`] message received for a batch of accepted
+/// contracts ordered by temporary id, and returns the accepting party's
+/// [`SignedContract`]s along with the signed fund transaction.
+pub async fn verify_signed_batch_contract<W: Deref>(
+    secp: &Secp256k1<All>,
+    accepted_contracts: &[AcceptedContract],
+    sign_msg: &SignBatchDlc,
+    wallet: &W,
+) -> Result<(Vec<SignedContract>, Transaction), Error>
+where
+    W::Target: AsyncWallet,
+{
+    let is_matching = sign_msg.sign_dlcs.len() == accepted_contracts.len()
+        && sign_msg
+            .sign_dlcs
+            .iter()
+            .zip(accepted_contracts.iter())
+            .all(|(s, a)| {
+                s.contract_id == a.get_contract_id()
+                    && s.funding_signatures.funding_signatures.is_empty()
+                    && a.dlc_transactions.fund.txid() == sign_msg.fund_txid
+            })
+        && accepted_contracts
+            .windows(2)
+            .all(|x| x[0].offered_contract.id < x[1].offered_contract.id);
+    if !is_matching {
+        return Err(Error::InvalidParameters(
+            "Sign message does not match the accepted batch.".to_string(),
+        ));
+    }
+
+    let empty_signatures = FundingSignatures {
+        funding_signatures: Vec::new(),
+    };
+    let mut signed_contracts = Vec::with_capacity(accepted_contracts.len());
+    let mut fund_tx = None;
+
+    for (i, (accepted_contract, sign_dlc)) in accepted_contracts
+        .iter()
+        .zip(sign_msg.sign_dlcs.iter())
+        .enumerate()
+    {
+        let funding_signatures = if i == 0 {
+            &sign_msg.funding_signatures
+        } else {
+            &empty_signatures
+        };
+        let cet_adaptor_signatures: Vec<_> = (&sign_dlc.cet_adaptor_signatures).into();
+        let (signed_contract, tx) = verify_signed_contract_internal(
+            secp,
+            accepted_contract,
+            get_refund_signature(&sign_dlc.refund_signature)?,
+            &cet_adaptor_signatures,
+            funding_signatures,
+            None,
+            accepted_contract.dlc_transactions.get_fund_output().value,
+            None,
+            None,
+            wallet,
+            None,
+        )
+        .await?;
+
+        // Only the first contract carries the funding inputs, so only its fund
+        // transaction is fully signed.
+        if i == 0 {
+            fund_tx = Some(tx);
+        }
+        signed_contracts.push(signed_contract);
+    }
+
+    let fund_tx = fund_tx.ok_or_else(|| {
+        Error::InvalidParameters("Batch does not contain any contract.".to_string())
+    })?;
+
+    Ok((signed_contracts, fund_tx))
+}
+
+fn get_batch_info(offered_contracts: &[OfferedContract]) -> Result<([u8; 32], u64), Error> {
+    let first = offered_contracts
+        .first()
+        .ok_or_else(|| Error::InvalidParameters("Batch cannot be empty.".to_string()))?;
+    let batch_id = first
+        .batch_id
+        .ok_or_else(|| Error::InvalidParameters("Contract is not part of a batch.".to_string()))?;
+
+    let is_valid = offered_contracts.iter().all(|x| {
+        x.batch_id == Some(batch_id)
+            && x.fee_rate_per_vb == first.fee_rate_per_vb
+            && !x.single_funded
+            && x.fee_payer.is_none()
+            && x.funding_output_type == FundingOutputType::P2wsh
+    }) && offered_contracts.windows(2).all(|x| x[0].id < x[1].id);
+    if !is_valid {
+        return Err(Error::InvalidParameters(
+            "Contracts do not form a valid batch.".to_string(),
+        ));
+    }
+
+    Ok((batch_id, first.fee_rate_per_vb))
+}
+
+fn create_batch_dlc_transactions(
+    secp: &Secp256k1<All>,
+    offered_contracts: &[OfferedContract],
+    accept_params: &[PartyParams],
+) -> Result<Vec<DlcTransactions>, Error> {
+    let contracts = offered_contracts
+        .iter()
+        .zip(accept_params.iter())
+        .map(|(offered_contract, accept_params)| {
+            Ok(BatchContractParams {
+                offer_params: offered_contract.offer_params.clone(),
+                accept_params: accept_params.clone(),
+                payouts: offered_contract.contract_info[0]
+                    .get_payouts(offered_contract.total_collateral)?,
+                refund_lock_time: offered_contract.refund_locktime,
+                cet_lock_time: offered_contract.cet_locktime,
+                fund_output_serial_id: offered_contract.fund_output_serial_id,
+                options: offered_contract.get_dlc_transactions_options(),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(dlc::create_batch_dlc_transactions(
+        secp,
+        &contracts,
+        offered_contracts[0].fee_rate_per_vb,
+        0,
+    )?)
+}
+
+/// Signs and return the CET that can be used to close the given contract.
+pub fn get_signed_cet<C: Signing + Verification, S: Deref>(
+    secp: &Secp256k1<C>,
+    contract: &SignedContract,
+    contract_info: &ContractInfo,
+    adaptor_info: &AdaptorInfo,
+    attestations: &[(usize, OracleAttestation)],
+    signer: S,
+) -> Result<Transaction, Error>
+where
+    S::Target: ContractSigner,
+{
+    let (range_info, sigs) =
+        crate::utils::get_range_info_and_oracle_sigs(contract_info, adaptor_info, attestations)?;
+    let mut cet = contract.accepted_contract.dlc_transactions.cets[range_info.cet_index].clone();
     let offered_contract = &contract.accepted_contract.offered_contract;
 
+    if let Some(taproot_signatures) = &contract.taproot_signatures {
+        let adaptor_sig = taproot_signatures
+            .cet_adaptor_signatures
+            .get(range_info.adaptor_index)
+            .ok_or_else(|| Error::InvalidState("Missing CET adaptor signature.".to_string()))?;
+        let key_agg_context = taproot::get_funding_key_agg_context(
+            secp,
+            &offered_contract.offer_params.fund_pubkey,
+            &contract.accepted_contract.accept_params.fund_pubkey,
+        )?;
+        schnorr_adaptor::sign_cet(
+            secp,
+            &mut cet,
+            adaptor_sig,
+            &sigs,
+            &key_agg_context.output_key(),
+            contract
+                .accepted_contract
+                .dlc_transactions
+                .get_fund_output(),
+        )?;
+        return Ok(cet);
+    }
+
     let (adaptor_sigs, other_pubkey) = if offered_contract.is_offer_party {
         (
             contract
@@ -715,7 +2910,7 @@ where
 }
 
 /// Signs and return the refund transaction to refund the contract.
-pub fn get_signed_refund<C: Signing, S: Deref>(
+pub fn get_signed_refund<C: Signing + Verification, S: Deref>(
     secp: &Secp256k1<C>,
     contract: &SignedContract,
     signer: S,
@@ -725,17 +2920,35 @@ where
 {
     let accepted_contract = &contract.accepted_contract;
     let offered_contract = &accepted_contract.offered_contract;
+
+    if let Some(taproot_signatures) = &contract.taproot_signatures {
+        let key_agg_context = taproot::get_funding_key_agg_context(
+            secp,
+            &offered_contract.offer_params.fund_pubkey,
+            &accepted_contract.accept_params.fund_pubkey,
+        )?;
+        let mut refund = accepted_contract.dlc_transactions.refund.clone();
+        taproot::sign_funding_input(
+            secp,
+            &mut refund,
+            &taproot_signatures.refund_signature,
+            accepted_contract.dlc_transactions.get_fund_output(),
+            &key_agg_context,
+        )?;
+        return Ok(refund);
+    }
+
     let funding_script_pubkey = &accepted_contract.dlc_transactions.funding_script_pubkey;
     let fund_output_value = accepted_contract.dlc_transactions.get_fund_output().value;
     let (other_fund_pubkey, other_sig) = if offered_contract.is_offer_party {
         (
             &accepted_contract.accept_params.fund_pubkey,
-            &accepted_contract.accept_refund_signature,
+            accepted_contract.get_accept_refund_signature()?,
         )
     } else {
         (
             &offered_contract.offer_params.fund_pubkey,
-            &contract.offer_refund_signature,
+            contract.get_offer_refund_signature()?,
         )
     };
 
@@ -744,7 +2957,7 @@ where
     dlc::util::sign_multi_sig_input(
         secp,
         &mut refund,
-        other_sig,
+        &other_sig,
         other_fund_pubkey,
         &fund_priv_key,
         funding_script_pubkey,
@@ -754,15 +2967,379 @@ where
     Ok(refund)
 }
 
+/// Tries to recover the oracle attestations used by the counter party to close
+/// the given contract with the given CET. The signature of the local party in
+/// the CET witness, or the key path signature of the CET for contracts funded
+/// with a taproot output, is the decryption of an adaptor signature for the
+/// CET, from which the adaptor secret can be extracted. The attestations can only be
+/// recovered for enumerated outcome contracts with a threshold of one, as the
+/// adaptor secret is otherwise the sum of several oracle signatures.
+pub(crate) fn recover_attestations_from_cet(
+    secp: &Secp256k1<All>,
+    contract: &SignedContract,
+    cet: &Transaction,
+) -> Option<Vec<OracleAttestation>> {
+    let accepted_contract = &contract.accepted_contract;
+    let offered_contract = &accepted_contract.offered_contract;
+    let dlc_transactions = &accepted_contract.dlc_transactions;
+    let cet_txid = cet.txid();
+    let cet_index = dlc_transactions
+        .cets
+        .iter()
+        .position(|x| x.txid() == cet_txid)?;
+
+    let fund_input = cet
+        .input
+        .iter()
+        .find(|x| x.previous_output == dlc_transactions.get_fund_outpoint())?;
+
+    // The adaptor signatures of a contract funded with a taproot output are
+    // shared by both parties, and decrypted into the key path signature of the
+    // CET. Otherwise, the local party signature is the decryption of its own
+    // ECDSA adaptor signature.
+    if let Some(taproot_signatures) = &contract.taproot_signatures {
+        let sig = SchnorrSignature::from_slice(fund_input.witness.nth(0)?).ok()?;
+        return recover_attestation_from_adaptor_sigs(
+            secp,
+            &offered_contract.contract_info,
+            &accepted_contract.adaptor_infos,
+            offered_contract.total_collateral,
+            cet_index,
+            &taproot_signatures.cet_adaptor_signatures,
+            |adaptor_sig, adaptor_point| adaptor_sig.recover(secp, &sig, adaptor_point).ok(),
+        );
+    }
+
+    let (own_adaptor_sigs, own_fund_pubkey, counter_fund_pubkey) =
+        if offered_contract.is_offer_party {
+            (
+                contract.adaptor_signatures.as_ref()?,
+                &offered_contract.offer_params.fund_pubkey,
+                &accepted_contract.accept_params.fund_pubkey,
+            )
+        } else {
+            (
+                accepted_contract.adaptor_signatures.as_ref()?,
+                &accepted_contract.accept_params.fund_pubkey,
+                &offered_contract.offer_params.fund_pubkey,
+            )
+        };
+
+    // The signatures in the witness are ordered as the public keys in the
+    // funding script.
+    let own_sig_index = if own_fund_pubkey < counter_fund_pubkey {
+        1
+    } else {
+        2
+    };
+    let sig_data = fund_input.witness.nth(own_sig_index)?;
+    let own_sig = Signature::from_der(sig_data.get(..sig_data.len().checked_sub(1)?)?).ok()?;
+
+    recover_attestation_from_adaptor_sigs(
+        secp,
+        &offered_contract.contract_info,
+        &accepted_contract.adaptor_infos,
+        offered_contract.total_collateral,
+        cet_index,
+        own_adaptor_sigs,
+        |adaptor_sig, adaptor_point| adaptor_sig.recover(secp, &own_sig, adaptor_point).ok(),
+    )
+}
+
+/// Tries to recover the oracle attestation for the outcome of the CET with the
+/// given index from the given adaptor signatures, using `recover_secret` to
+/// extract the adaptor secret from an adaptor signature and its adaptor point.
+/// The CETs and adaptor signatures of each contract info follow the ones of the
+/// previous contract infos, so the index of the CET and the adaptor signatures
+/// are offset by the number of CETs and adaptor signatures of the contract infos
+/// preceding the one the CET belongs to.
+fn recover_attestation_from_adaptor_sigs<S, F>(
+    secp: &Secp256k1<All>,
+    contract_infos: &[ContractInfo],
+    adaptor_infos: &[AdaptorInfo],
+    total_collateral: u64,
+    cet_index: usize,
+    adaptor_sigs: &[S],
+    recover_secret: F,
+) -> Option<Vec<OracleAttestation>>
+where
+    F: Fn(&S, &PublicKey) -> Option<SecretKey>,
+{
+    let mut cet_start = 0;
+    let mut adaptor_sig_start = 0;
+    for (contract_info, adaptor_info) in contract_infos.iter().zip(adaptor_infos) {
+        let nb_cets = contract_info.get_payouts(total_collateral).ok()?.len();
+        if cet_index >= cet_start + nb_cets {
+            cet_start += nb_cets;
+            adaptor_sig_start += get_adaptor_signature_count(secp, contract_info, adaptor_info)?;
+            continue;
+        }
+
+        let enum_descriptor = match &contract_info.contract_descriptor {
+            ContractDescriptor::Enum(e) if contract_info.threshold == 1 => e,
+            _ => return None,
+        };
+        let local_cet_index = cet_index - cet_start;
+        let outcome = &enum_descriptor
+            .outcome_payouts
+            .get(local_cet_index)?
+            .outcome;
+        let nb_oracles = contract_info.oracle_announcements.len();
+        let sig_start = adaptor_sig_start + local_cet_index * nb_oracles;
+        let adaptor_sigs = adaptor_sigs.get(sig_start..sig_start + nb_oracles)?;
+        for announcement in &contract_info.oracle_announcements {
+            let attestation = adaptor_sigs.iter().find_map(|x| {
+                recover_attestation(secp, announcement, outcome, |adaptor_point| {
+                    recover_secret(x, adaptor_point)
+                })
+            });
+            if attestation.is_some() {
+                return attestation.map(|x| vec![x]);
+            }
+        }
+
+        return None;
+    }
+
+    None
+}
+
+/// Returns the number of adaptor signatures required by the given contract
+/// info.
+fn get_adaptor_signature_count(
+    secp: &Secp256k1<All>,
+    contract_info: &ContractInfo,
+    adaptor_info: &AdaptorInfo,
+) -> Option<usize> {
+    match (&contract_info.contract_descriptor, adaptor_info) {
+        (ContractDescriptor::Enum(e), _) => Some(
+            e.outcome_payouts.len()
+                * CombinationIterator::new(
+                    contract_info.oracle_announcements.len(),
+                    contract_info.threshold,
+                )
+                .count(),
+        ),
+        (ContractDescriptor::MultiEvent(m), _) => Some(
+            m.get_adaptor_points(
+                secp,
+                &contract_info.oracle_announcements,
+                contract_info.threshold,
+            )
+            .ok()?
+            .len(),
+        ),
+        (ContractDescriptor::Numerical(_), AdaptorInfo::Numerical(trie)) => {
+            Some(trie.iter().count())
+        }
+        (ContractDescriptor::Numerical(_), AdaptorInfo::NumericalWithDifference(trie)) => {
+            Some(trie.iter().count())
+        }
+        (ContractDescriptor::Numerical(_), AdaptorInfo::Enum) => None,
+    }
+}
+
+/// Returns the attestation of the given oracle for the given outcome if
+/// `recover_secret` returns the adaptor secret for its adaptor point.
+fn recover_attestation<F>(
+    secp: &Secp256k1<All>,
+    announcement: &OracleAnnouncement,
+    outcome: &str,
+    recover_secret: F,
+) -> Option<OracleAttestation>
+where
+    F: Fn(&PublicKey) -> Option<SecretKey>,
+{
+    let nonce = announcement.oracle_event.oracle_nonces.first()?;
+    let msg = Message::from_hashed_data::<sha256::Hash>(outcome.as_bytes());
+    let adaptor_point = dlc::secp_utils::schnorrsig_compute_sig_point(
+        secp,
+        &announcement.oracle_public_key,
+        nonce,
+        &msg,
+    )
+    .ok()?;
+    let adaptor_secret = recover_secret(&adaptor_point)?;
+    let mut sig_bytes = nonce.serialize().to_vec();
+    sig_bytes.extend_from_slice(&adaptor_secret.secret_bytes());
+    let signature = SchnorrSignature::from_slice(&sig_bytes).ok()?;
+
+    Some(OracleAttestation {
+        oracle_public_key: announcement.oracle_public_key,
+        signatures: vec![signature],
+        outcomes: vec![outcome.to_string()],
+    })
+}
+
+/// Creates and signs a transaction spending the anchor output of the local
+/// party in the given CET, which pays to its payout script pubkey
+/// `anchor_script_pubkey`, together with UTXOs from the wallet, so that the CET
+/// and the created transaction together pay the given fee rate. The value of
+/// the output spent by the CET, either the fund output or the output of the
+/// buffer transaction of a channel, is given by `cet_input_value`. The wallet
+/// UTXOs used are reserved and should be unreserved by the caller if the
+/// transaction is not broadcast successfully.
+pub(crate) async fn create_cpfp_transaction<W: Deref>(
+    cet: &Transaction,
+    cet_input_value: u64,
+    anchor_script_pubkey: &Script,
+    fee_rate_per_vb: u64,
+    wallet: &W,
+) -> Result<Transaction, Error>
+where
+    W::Target: AsyncWallet,
+{
+    let anchor_vout = cet
+        .output
+        .iter()
+        .rposition(|x| {
+            x.script_pubkey == *anchor_script_pubkey && x.value == dlc::ANCHOR_OUTPUT_VALUE
+        })
+        .ok_or_else(|| Error::InvalidParameters("CET has no anchor output".to_string()))?;
+    let anchor_max_witness_len =
+        dlc::util::get_max_witness_len(anchor_script_pubkey, &ScriptBuf::new(), None).ok_or_else(
+            || Error::InvalidParameters("Unsupported anchor output script pubkey".to_string()),
+        )?;
+    let anchor_input_weight =
+        dlc::util::get_input_weight(&ScriptBuf::new(), anchor_max_witness_len)?;
+
+    let cet_fee = cet_input_value
+        .checked_sub(cet.output.iter().map(|x| x.value).sum::<u64>())
+        .ok_or_else(|| Error::InvalidParameters("Invalid CET input value".to_string()))?;
+
+    let change_script_pubkey = wallet.get_new_change_address().await?.script_pubkey();
+    // The wallet accounts for the weight of the inputs it selects.
+    let appr_fee = dlc::get_cpfp_fee(
+        cet,
+        cet_fee,
+        anchor_input_weight,
+        &change_script_pubkey,
+        fee_rate_per_vb,
+    )?;
+    let required_amount = (appr_fee + dlc::DUST_LIMIT).saturating_sub(dlc::ANCHOR_OUTPUT_VALUE);
+    let utxos = wallet
+        .get_utxos_for_amount(required_amount, fee_rate_per_vb, true)
+        .await?;
+
+    let res = sign_cpfp_transaction(
+        cet,
+        cet_fee,
+        anchor_vout,
+        anchor_input_weight,
+        &utxos,
+        change_script_pubkey,
+        fee_rate_per_vb,
+        wallet,
+    )
+    .await;
+
+    if res.is_err() {
+        let outpoints = utxos.iter().map(|x| x.outpoint).collect::<Vec<_>>();
+        wallet.unreserve_utxos(&outpoints).await?;
+    }
+
+    res
+}
+
+async fn sign_cpfp_transaction<W: Deref>(
+    cet: &Transaction,
+    cet_fee: u64,
+    anchor_vout: usize,
+    anchor_input_weight: usize,
+    utxos: &[Utxo],
+    change_script_pubkey: ScriptBuf,
+    fee_rate_per_vb: u64,
+    wallet: &W,
+) -> Result<Transaction, Error>
+where
+    W::Target: AsyncWallet,
+{
+    let mut inputs_weight = anchor_input_weight;
+    for utxo in utxos {
+        inputs_weight = inputs_weight
+            .checked_add(dlc::util::get_input_weight(
+                &utxo.redeem_script,
+                utxo.max_witness_len,
+            )?)
+            .ok_or_else(|| Error::InvalidParameters("Invalid UTXO witness size".to_string()))?;
+    }
+    let fee = dlc::get_cpfp_fee(
+        cet,
+        cet_fee,
+        inputs_weight,
+        &change_script_pubkey,
+        fee_rate_per_vb,
+    )?;
+    let total_input = dlc::ANCHOR_OUTPUT_VALUE + utxos.iter().map(|x| x.tx_out.value).sum::<u64>();
+    let change_value = total_input
+        .checked_sub(fee)
+        .filter(|x| *x >= dlc::DUST_LIMIT)
+        .ok_or_else(|| Error::InvalidState("Not enough funds to bump the CET fee.".to_string()))?;
+
+    let anchor_input = TxIn {
+        previous_output: OutPoint {
+            txid: cet.txid(),
+            vout: anchor_vout as u32,
+        },
+        ..Default::default()
+    };
+    let mut cpfp_tx = Transaction {
+        version: dlc::TX_VERSION,
+        lock_time: LockTime::ZERO,
+        input: std::iter::once(anchor_input)
+            .chain(utxos.iter().map(|x| TxIn {
+                previous_output: x.outpoint,
+                ..Default::default()
+            }))
+            .collect(),
+        output: vec![TxOut {
+            value: change_value,
+            script_pubkey: change_script_pubkey,
+        }],
+    };
+
+    let mut psbt = PartiallySignedTransaction::from_unsigned_tx(cpfp_tx.clone())
+        .map_err(|_| Error::InvalidState("Tried to create PSBT from signed tx".to_string()))?;
+
+    psbt.inputs[0].witness_utxo = Some(cet.output[anchor_vout].clone());
+    for (i, utxo) in utxos.iter().enumerate() {
+        let input_index = i + 1;
+        psbt.inputs[input_index].witness_utxo = Some(utxo.tx_out.clone());
+        psbt.inputs[input_index].redeem_script = Some(utxo.redeem_script.clone());
+    }
+
+    for input_index in 0..cpfp_tx.input.len() {
+        wallet.sign_psbt_input(&mut psbt, input_index).await?;
+        cpfp_tx.input[input_index].witness = psbt.inputs[input_index]
+            .final_script_witness
+            .clone()
+            .ok_or(Error::InvalidParameters(
+                "No witness from signing psbt input".to_string(),
+            ))?;
+    }
+
+    Ok(cpfp_tx)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use crate::contract::{
+        contract_info::ContractInfo, enum_descriptor::EnumDescriptor, AdaptorInfo,
+        ContractDescriptor,
+    };
 
+    use dlc::schnorr_adaptor::SchnorrAdaptorSignature;
+    use dlc::{EnumerationPayout, Payout};
+    use dlc_messages::oracle_msgs::{EnumEventDescriptor, EventDescriptor};
     use mocks::dlc_manager::contract::offered_contract::OfferedContract;
-    use secp256k1_zkp::PublicKey;
+    use mocks::dlc_manager::{Oracle, SyncBlockchainAdapter, SyncWalletAdapter};
+    use mocks::mock_oracle_provider::MockOracle;
+    use secp256k1_zkp::{rand::thread_rng, PublicKey, SecretKey};
 
-    #[test]
-    fn accept_contract_test() {
+    #[tokio::test]
+    async fn accept_contract_test() {
         let offer_dlc =
             serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
         let dummy_pubkey: PublicKey =
@@ -771,23 +3348,214 @@ mod tests {
                 .unwrap();
         let offered_contract =
             OfferedContract::try_from_offer_dlc(&offer_dlc, dummy_pubkey, [0; 32]).unwrap();
-        let blockchain = Rc::new(mocks::mock_blockchain::MockBlockchain::new());
+        let blockchain = Arc::new(mocks::mock_blockchain::MockBlockchain::new());
         let fee_rate: u64 = offered_contract.fee_rate_per_vb;
         let utxo_value: u64 = offered_contract.total_collateral
             - offered_contract.offer_params.collateral
             + crate::utils::get_half_common_fee(fee_rate).unwrap();
-        let wallet = Rc::new(mocks::mock_wallet::MockWallet::new(
+        let wallet = Arc::new(mocks::mock_wallet::MockWallet::new(
             &blockchain,
             &[utxo_value, 10000],
         ));
 
+        let blockchain = Box::new(SyncBlockchainAdapter(blockchain));
+
         mocks::dlc_manager::contract_updater::accept_contract(
             secp256k1_zkp::SECP256K1,
             &offered_contract,
-            &wallet,
+            &Box::new(SyncWalletAdapter(wallet.clone())),
             &wallet,
             &blockchain,
         )
+        .await
         .expect("Not to fail");
     }
+
+    #[test]
+    fn recover_attestation_from_decrypted_adaptor_signature() {
+        let secp = secp256k1_zkp::Secp256k1::new();
+        let mut oracle = MockOracle::new();
+        let event_descriptor = EventDescriptor::EnumEvent(EnumEventDescriptor {
+            outcomes: vec!["a".to_string(), "b".to_string()],
+        });
+        oracle.add_event("event", &event_descriptor, 0);
+        oracle.add_attestation("event", &["a".to_string()]);
+        let announcement = oracle.get_announcement("event").unwrap();
+        let attestation = oracle.get_attestation("event").unwrap();
+
+        let cet = bitcoin_test_utils::tx_from_string("01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff020000ffffffff0101000000000000000000000000");
+        let funding_sk = SecretKey::new(&mut thread_rng());
+        let funding_script_pubkey = bitcoin::ScriptBuf::new();
+        let adaptor_point = dlc::secp_utils::schnorrsig_compute_sig_point(
+            &secp,
+            &announcement.oracle_public_key,
+            &announcement.oracle_event.oracle_nonces[0],
+            &secp256k1_zkp::Message::from_hashed_data::<secp256k1_zkp::hashes::sha256::Hash>(b"a"),
+        )
+        .unwrap();
+        let adaptor_sig = dlc::create_cet_adaptor_sig_from_point(
+            &secp,
+            &cet,
+            &adaptor_point,
+            &funding_sk,
+            &funding_script_pubkey,
+            1000,
+        )
+        .unwrap();
+        let (_, adaptor_secret) =
+            dlc::secp_utils::schnorrsig_decompose(&attestation.signatures[0]).unwrap();
+        let sig = adaptor_sig
+            .decrypt(&SecretKey::from_slice(adaptor_secret).unwrap())
+            .unwrap();
+
+        let recover_secret =
+            |adaptor_point: &PublicKey| adaptor_sig.recover(&secp, &sig, adaptor_point).ok();
+        let recovered =
+            super::recover_attestation(&secp, &announcement, "a", recover_secret).unwrap();
+        assert_eq!(attestation.signatures, recovered.signatures);
+        assert_eq!(attestation.outcomes, recovered.outcomes);
+        assert!(super::recover_attestation(&secp, &announcement, "b", recover_secret).is_none());
+    }
+
+    #[test]
+    fn recover_attestation_from_decrypted_schnorr_adaptor_signature() {
+        let secp = secp256k1_zkp::Secp256k1::new();
+        let mut oracle = MockOracle::new();
+        let event_descriptor = EventDescriptor::EnumEvent(EnumEventDescriptor {
+            outcomes: vec!["a".to_string(), "b".to_string()],
+        });
+        oracle.add_event("event", &event_descriptor, 0);
+        oracle.add_attestation("event", &["a".to_string()]);
+        let announcement = oracle.get_announcement("event").unwrap();
+        let attestation = oracle.get_attestation("event").unwrap();
+
+        let keypair = secp256k1_zkp::KeyPair::new(&secp, &mut thread_rng());
+        let msg = secp256k1_zkp::Message::from_slice(&[1u8; 32]).unwrap();
+        let adaptor_point = dlc::secp_utils::schnorrsig_compute_sig_point(
+            &secp,
+            &announcement.oracle_public_key,
+            &announcement.oracle_event.oracle_nonces[0],
+            &secp256k1_zkp::Message::from_hashed_data::<secp256k1_zkp::hashes::sha256::Hash>(b"a"),
+        )
+        .unwrap();
+        let adaptor_sig = dlc::schnorr_adaptor::SchnorrAdaptorSignature::encrypt_no_aux_rand(
+            &secp,
+            &msg,
+            &keypair,
+            &adaptor_point,
+        )
+        .unwrap();
+        let (_, adaptor_secret) =
+            dlc::secp_utils::schnorrsig_decompose(&attestation.signatures[0]).unwrap();
+        let sig = adaptor_sig
+            .decrypt(&secp, &SecretKey::from_slice(adaptor_secret).unwrap())
+            .unwrap();
+
+        let recover_secret =
+            |adaptor_point: &PublicKey| adaptor_sig.recover(&secp, &sig, adaptor_point).ok();
+        let recovered =
+            super::recover_attestation(&secp, &announcement, "a", recover_secret).unwrap();
+        assert_eq!(attestation.signatures, recovered.signatures);
+        assert_eq!(attestation.outcomes, recovered.outcomes);
+        assert!(super::recover_attestation(&secp, &announcement, "b", recover_secret).is_none());
+    }
+
+    #[test]
+    fn recover_attestation_from_cet_of_second_contract_info() {
+        let secp = secp256k1_zkp::Secp256k1::new();
+        let mut oracle = MockOracle::new();
+        let mut contract_infos = Vec::new();
+        for (event_id, outcomes) in [("event1", ["a", "b"]), ("event2", ["c", "d"])] {
+            let outcomes: Vec<String> = outcomes.iter().map(|x| x.to_string()).collect();
+            let event_descriptor = EventDescriptor::EnumEvent(EnumEventDescriptor {
+                outcomes: outcomes.clone(),
+            });
+            oracle.add_event(event_id, &event_descriptor, 0);
+            contract_infos.push(ContractInfo {
+                contract_descriptor: ContractDescriptor::Enum(EnumDescriptor {
+                    outcome_payouts: outcomes
+                        .into_iter()
+                        .map(|outcome| EnumerationPayout {
+                            outcome,
+                            payout: Payout {
+                                offer: 1000,
+                                accept: 0,
+                            },
+                        })
+                        .collect(),
+                }),
+                oracle_announcements: vec![oracle.get_announcement(event_id).unwrap()],
+                threshold: 1,
+            });
+        }
+        oracle.add_attestation("event2", &["d".to_string()]);
+        let attestation = oracle.get_attestation("event2").unwrap();
+
+        let keypair = secp256k1_zkp::KeyPair::new(&secp, &mut thread_rng());
+        let msg = secp256k1_zkp::Message::from_slice(&[1u8; 32]).unwrap();
+        let adaptor_sigs: Vec<_> = contract_infos
+            .iter()
+            .flat_map(|contract_info| {
+                let announcement = &contract_info.oracle_announcements[0];
+                match &contract_info.contract_descriptor {
+                    ContractDescriptor::Enum(e) => e
+                        .outcome_payouts
+                        .iter()
+                        .map(|x| {
+                            let adaptor_point = dlc::secp_utils::schnorrsig_compute_sig_point(
+                                &secp,
+                                &announcement.oracle_public_key,
+                                &announcement.oracle_event.oracle_nonces[0],
+                                &secp256k1_zkp::Message::from_hashed_data::<
+                                    secp256k1_zkp::hashes::sha256::Hash,
+                                >(x.outcome.as_bytes()),
+                            )
+                            .unwrap();
+                            SchnorrAdaptorSignature::encrypt_no_aux_rand(
+                                &secp,
+                                &msg,
+                                &keypair,
+                                &adaptor_point,
+                            )
+                            .unwrap()
+                        })
+                        .collect::<Vec<_>>(),
+                    _ => unreachable!(),
+                }
+            })
+            .collect();
+        let (_, adaptor_secret) =
+            dlc::secp_utils::schnorrsig_decompose(&attestation.signatures[0]).unwrap();
+        let sig = adaptor_sigs[3]
+            .decrypt(&secp, &SecretKey::from_slice(adaptor_secret).unwrap())
+            .unwrap();
+
+        let adaptor_infos = vec![AdaptorInfo::Enum, AdaptorInfo::Enum];
+        let recover_secret = |adaptor_sig: &SchnorrAdaptorSignature, adaptor_point: &PublicKey| {
+            adaptor_sig.recover(&secp, &sig, adaptor_point).ok()
+        };
+        let recovered = super::recover_attestation_from_adaptor_sigs(
+            &secp,
+            &contract_infos,
+            &adaptor_infos,
+            1000,
+            3,
+            &adaptor_sigs,
+            recover_secret,
+        )
+        .unwrap();
+        assert_eq!(1, recovered.len());
+        assert_eq!(attestation.signatures, recovered[0].signatures);
+        assert_eq!(attestation.outcomes, recovered[0].outcomes);
+        assert!(super::recover_attestation_from_adaptor_sigs(
+            &secp,
+            &contract_infos,
+            &adaptor_infos,
+            1000,
+            1,
+            &adaptor_sigs,
+            recover_secret,
+        )
+        .is_none());
+    }
 }
@@ -1,6 +1,7 @@
 use crate::contract::{
     contract_info::ContractInfo,
     enum_descriptor::EnumDescriptor,
+    multi_event_descriptor::{MultiEventDescriptor, MultiEventOutcomePayout},
     numerical_descriptor::{DifferenceParams, NumericalDescriptor},
     offered_contract::OfferedContract,
     ContractDescriptor,
@@ -19,14 +20,15 @@ use dlc_messages::{
     contract_msgs::{
         ContractDescriptor as SerContractDescriptor, ContractInfo as SerContractInfo,
         ContractInfoInner, ContractOutcome, DisjointContractInfo, EnumeratedContractDescriptor,
-        HyperbolaPayoutCurvePiece as SerHyperbolaPayoutCurvePiece,
-        NumericOutcomeContractDescriptor, PayoutCurvePiece as SerPayoutCurvePiece,
-        PayoutFunction as SerPayoutFunction, PayoutFunctionPiece as SerPayoutFunctionPiece,
-        PayoutPoint as SerPayoutPoint, PolynomialPayoutCurvePiece as SerPolynomialPayoutCurvePiece,
+        HyperbolaPayoutCurvePiece as SerHyperbolaPayoutCurvePiece, MultiEventContractDescriptor,
+        MultiEventContractOutcome, NumericOutcomeContractDescriptor,
+        PayoutCurvePiece as SerPayoutCurvePiece, PayoutFunction as SerPayoutFunction,
+        PayoutFunctionPiece as SerPayoutFunctionPiece, PayoutPoint as SerPayoutPoint,
+        PolynomialPayoutCurvePiece as SerPolynomialPayoutCurvePiece,
         RoundingInterval as SerRoundingInterval, RoundingIntervals as SerRoundingIntervals,
         SingleContractInfo,
     },
-    oracle_msgs::EventDescriptor,
+    oracle_msgs::{EventDescriptor, OracleAnnouncement},
 };
 use dlc_trie::OracleNumericInfo;
 use std::fmt;
@@ -88,7 +90,7 @@ pub fn get_tx_input_infos(
                 txid: tx.txid(),
                 vout,
             },
-            max_witness_len: 107,
+            max_witness_len: fund_input.max_witness_len as usize,
             redeem_script: fund_input.redeem_script.clone(),
             serial_id: fund_input.input_serial_id,
         });
@@ -126,25 +128,24 @@ pub(crate) fn get_contract_info_and_announcements(
                     })
                     .collect();
                 let descriptor = ContractDescriptor::Enum(EnumDescriptor { outcome_payouts });
-                let mut threshold = 1;
-                let announcements = match contract_info.oracle_info {
-                    SerOracleInfo::Single(single) => vec![single.oracle_announcement],
-                    SerOracleInfo::Multi(multi) => {
-                        threshold = multi.threshold;
-                        multi.oracle_announcements
-                    }
-                };
-
-                if announcements
+                let (announcements, threshold) = get_enum_announcements(contract_info.oracle_info)?;
+                (descriptor, announcements, threshold)
+            }
+            SerContractDescriptor::MultiEventContractDescriptor(multi_event) => {
+                let outcome_payouts = multi_event
+                    .payouts
                     .iter()
-                    .any(|x| match &x.oracle_event.event_descriptor {
-                        EventDescriptor::EnumEvent(_) => false,
-                        EventDescriptor::DigitDecompositionEvent(_) => true,
+                    .map(|x| MultiEventOutcomePayout {
+                        outcomes: x.outcomes.clone(),
+                        payout: Payout {
+                            offer: x.offer_payout,
+                            accept: total_collateral - x.offer_payout,
+                        },
                     })
-                {
-                    return Err(Error::InvalidParameters);
-                }
-
+                    .collect();
+                let descriptor =
+                    ContractDescriptor::MultiEvent(MultiEventDescriptor { outcome_payouts });
+                let (announcements, threshold) = get_announcements(contract_info.oracle_info);
                 (descriptor, announcements, threshold)
             }
             SerContractDescriptor::NumericOutcomeContractDescriptor(numeric) => {
@@ -212,6 +213,31 @@ pub(crate) fn get_contract_info_and_announcements(
     Ok(contract_infos)
 }
 
+fn get_announcements(oracle_info: SerOracleInfo) -> (Vec<OracleAnnouncement>, u16) {
+    match oracle_info {
+        SerOracleInfo::Single(single) => (vec![single.oracle_announcement], 1),
+        SerOracleInfo::Multi(multi) => (multi.oracle_announcements, multi.threshold),
+    }
+}
+
+fn get_enum_announcements(
+    oracle_info: SerOracleInfo,
+) -> Result<(Vec<OracleAnnouncement>, u16), Error> {
+    let (announcements, threshold) = get_announcements(oracle_info);
+
+    if announcements
+        .iter()
+        .any(|x| match &x.oracle_event.event_descriptor {
+            EventDescriptor::EnumEvent(_) => false,
+            EventDescriptor::DigitDecompositionEvent(_) => true,
+        })
+    {
+        return Err(Error::InvalidParameters);
+    }
+
+    Ok((announcements, threshold))
+}
+
 impl From<&OfferedContract> for SerContractInfo {
     fn from(offered_contract: &OfferedContract) -> SerContractInfo {
         let oracle_infos: Vec<SerOracleInfo> = offered_contract.into();
@@ -288,6 +314,20 @@ impl From<&EnumDescriptor> for EnumeratedContractDescriptor {
     }
 }
 
+impl From<&MultiEventDescriptor> for MultiEventContractDescriptor {
+    fn from(multi_event_descriptor: &MultiEventDescriptor) -> Self {
+        let payouts: Vec<MultiEventContractOutcome> = multi_event_descriptor
+            .outcome_payouts
+            .iter()
+            .map(|x| MultiEventContractOutcome {
+                outcomes: x.outcomes.clone(),
+                offer_payout: x.payout.offer,
+            })
+            .collect();
+        MultiEventContractDescriptor { payouts }
+    }
+}
+
 impl From<&NumericalDescriptor> for NumericOutcomeContractDescriptor {
     fn from(num_descriptor: &NumericalDescriptor) -> NumericOutcomeContractDescriptor {
         NumericOutcomeContractDescriptor {
@@ -312,6 +352,9 @@ impl From<&ContractDescriptor> for SerContractDescriptor {
             ContractDescriptor::Numerical(n) => {
                 SerContractDescriptor::NumericOutcomeContractDescriptor(n.into())
             }
+            ContractDescriptor::MultiEvent(m) => {
+                SerContractDescriptor::MultiEventContractDescriptor(m.into())
+            }
         }
     }
 }
@@ -0,0 +1,224 @@
+//! Events emitted by the [`crate::manager::Manager`] when contracts and channels
+//! go through notable state transitions.
+
+use bitcoin::Txid;
+use dlc_messages::reject::RejectErrorCode;
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+use secp256k1_zkp::PublicKey;
+
+use crate::contract::Contract;
+use crate::watchtower::EncryptedPunishmentPackage;
+use crate::{ChannelId, ContractId};
+
+/// An event generated by the [`crate::manager::Manager`]. Events are persisted
+/// until retrieved through
+/// [`crate::manager::Manager::get_and_clear_pending_events`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A contract offer was received from a peer.
+    OfferReceived {
+        /// The temporary id of the offered contract.
+        temporary_contract_id: ContractId,
+        /// The public key of the peer that sent the offer.
+        counter_party: PublicKey,
+    },
+    /// A channel offer was received from a peer.
+    ChannelOfferReceived {
+        /// The temporary id of the offered channel.
+        temporary_channel_id: ChannelId,
+        /// The public key of the peer that sent the offer.
+        counter_party: PublicKey,
+    },
+    /// The funding transaction of a contract reached the required number of
+    /// confirmations.
+    ContractConfirmed {
+        /// The id of the confirmed contract.
+        contract_id: ContractId,
+    },
+    /// A CET closing a contract was broadcast.
+    CetBroadcast {
+        /// The id of the contract being closed.
+        contract_id: ContractId,
+        /// The id of the broadcast CET.
+        cet_txid: Txid,
+    },
+    /// A contract was closed.
+    ContractClosed {
+        /// The id of the closed contract.
+        contract_id: ContractId,
+        /// The profit and loss of the local party for the contract.
+        pnl: i64,
+    },
+    /// The refund transaction of a contract was broadcast.
+    RefundBroadcast {
+        /// The id of the refunded contract.
+        contract_id: ContractId,
+        /// The id of the broadcast refund transaction.
+        refund_txid: Txid,
+    },
+    /// A channel was settled, closing the contract it contained off-chain.
+    ChannelSettled {
+        /// The id of the settled channel.
+        channel_id: ChannelId,
+        /// The payout of the local party in the settled channel.
+        own_payout: u64,
+    },
+    /// The counter party broadcast a revoked transaction of a channel and a
+    /// punishment transaction was broadcast in response.
+    ChannelPunished {
+        /// The id of the punished channel.
+        channel_id: ChannelId,
+        /// The id of the punishment transaction.
+        punish_txid: Txid,
+    },
+    /// The refund transaction of a contract reached the required number of
+    /// confirmations.
+    ContractRefunded {
+        /// The id of the refunded contract.
+        contract_id: ContractId,
+    },
+    /// A contract offer was cancelled by the offering peer or expired.
+    OfferCancelled {
+        /// The temporary id of the cancelled contract offer.
+        temporary_contract_id: ContractId,
+    },
+    /// A contract offer was rejected, either by the offer policy of the
+    /// [`crate::manager::Manager`] upon reception or by the peer it was sent
+    /// to.
+    OfferRejected {
+        /// The temporary id of the rejected contract offer.
+        temporary_contract_id: ContractId,
+        /// The code indicating why the offer was rejected.
+        error_code: RejectErrorCode,
+        /// The reason for which the offer was rejected.
+        reason: String,
+    },
+    /// The splice transaction of a channel was confirmed, updating the amount
+    /// the local party holds in the channel.
+    ChannelSpliced {
+        /// The id of the spliced channel.
+        channel_id: ChannelId,
+        /// The amount the local party holds in the channel after the splice.
+        own_payout: u64,
+    },
+    /// A state of a channel was revoked, and a package enabling a watchtower to
+    /// punish the publication of its transaction was created.
+    PunishmentPackageExported {
+        /// The id of the channel whose state was revoked.
+        channel_id: ChannelId,
+        /// The encrypted punishment package, to be handed to a watchtower.
+        package: EncryptedPunishmentPackage,
+    },
+}
+
+impl Event {
+    /// Returns an [`Event::ContractClosed`] for the given contract if it is in
+    /// a closed state.
+    pub(crate) fn from_closed_contract(contract: &Contract) -> Option<Event> {
+        match contract {
+            Contract::Closed(c) => Some(Event::ContractClosed {
+                contract_id: c.contract_id,
+                pnl: c.pnl,
+            }),
+            Contract::CollaborativelyClosed(c) => Some(Event::ContractClosed {
+                contract_id: c.contract_id,
+                pnl: c.pnl,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl_dlc_writeable_enum!(Event,;
+    (0, OfferReceived, {(temporary_contract_id, writeable), (counter_party, writeable)}),
+    (1, ChannelOfferReceived, {(temporary_channel_id, writeable), (counter_party, writeable)}),
+    (2, ContractConfirmed, {(contract_id, writeable)}),
+    (3, CetBroadcast, {(contract_id, writeable), (cet_txid, writeable)}),
+    (4, ContractClosed, {(contract_id, writeable), (pnl, i64)}),
+    (5, RefundBroadcast, {(contract_id, writeable), (refund_txid, writeable)}),
+    (6, ChannelSettled, {(channel_id, writeable), (own_payout, writeable)}),
+    (7, ChannelPunished, {(channel_id, writeable), (punish_txid, writeable)}),
+    (8, ContractRefunded, {(contract_id, writeable)}),
+    (9, OfferCancelled, {(temporary_contract_id, writeable)}),
+    (10, OfferRejected, {(temporary_contract_id, writeable), (error_code, writeable), (reason, string)}),
+    (11, ChannelSpliced, {(channel_id, writeable), (own_payout, writeable)}),
+    (12, PunishmentPackageExported, {(channel_id, writeable), (package, writeable)});;
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::ser::Serializable;
+    use bitcoin::hashes::Hash;
+    use secp256k1_zkp::{Secp256k1, SecretKey};
+
+    #[test]
+    fn event_serialization_roundtrip() {
+        let secp = Secp256k1::new();
+        let counter_party =
+            PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[1u8; 32]).unwrap());
+        let events = vec![
+            Event::OfferReceived {
+                temporary_contract_id: [1u8; 32],
+                counter_party,
+            },
+            Event::ChannelOfferReceived {
+                temporary_channel_id: [2u8; 32],
+                counter_party,
+            },
+            Event::ContractConfirmed {
+                contract_id: [3u8; 32],
+            },
+            Event::CetBroadcast {
+                contract_id: [4u8; 32],
+                cet_txid: Txid::all_zeros(),
+            },
+            Event::ContractClosed {
+                contract_id: [5u8; 32],
+                pnl: -1000,
+            },
+            Event::RefundBroadcast {
+                contract_id: [6u8; 32],
+                refund_txid: Txid::all_zeros(),
+            },
+            Event::ChannelSettled {
+                channel_id: [7u8; 32],
+                own_payout: 100000,
+            },
+            Event::ChannelPunished {
+                channel_id: [8u8; 32],
+                punish_txid: Txid::all_zeros(),
+            },
+            Event::ContractRefunded {
+                contract_id: [9u8; 32],
+            },
+            Event::OfferCancelled {
+                temporary_contract_id: [10u8; 32],
+            },
+            Event::OfferRejected {
+                temporary_contract_id: [11u8; 32],
+                error_code: RejectErrorCode::UnsupportedOracle,
+                reason: "Oracle is not allowed.".to_string(),
+            },
+            Event::ChannelSpliced {
+                channel_id: [12u8; 32],
+                own_payout: 200000,
+            },
+            Event::PunishmentPackageExported {
+                channel_id: [13u8; 32],
+                package: EncryptedPunishmentPackage {
+                    hint: [14u8; 32],
+                    nonce: [15u8; 12],
+                    data: vec![1, 2, 3],
+                },
+            },
+        ];
+
+        for event in events {
+            let serialized = event.serialize().unwrap();
+            let deserialized = Event::deserialize(&mut serialized.as_slice()).unwrap();
+            assert_eq!(event, deserialized);
+        }
+    }
+}
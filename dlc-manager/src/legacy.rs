@@ -0,0 +1,328 @@
+//! # Deserialization of data persisted with the serialization format used by
+//! earlier versions of this crate.
+//!
+//! The serialization format of some stored structures was extended by adding
+//! fields to them. The functions in this module read the previous format of
+//! these structures, setting the new fields to values matching the behavior of
+//! the version that wrote them. They are meant to be used by storage providers
+//! to migrate their data, after which the regular [`Readable`] implementations
+//! can be used.
+
+use crate::chain_monitor::{ChainMonitor, WatchState};
+use crate::channel::accepted_channel::AcceptedChannel;
+use crate::channel::signed_channel::{SignedChannel, SignedChannelState};
+use crate::contract::accepted_contract::AcceptedContract;
+use crate::contract::offered_contract::OfferedContract;
+use crate::contract::ser::dlc_transactions;
+use crate::contract::signed_contract::SignedContract;
+use crate::contract::{FailedAcceptContract, FailedSignContract, PreClosedContract};
+use crate::manager::{CET_NSEQUENCE, OFFER_FEE_SHARE};
+use crate::Utxo;
+use bitcoin::{OutPoint, Txid};
+use dlc::FundingOutputType;
+use dlc_messages::ser_impls::{
+    party_params, read_address, read_ecdsa_adaptor_signature, read_ecdsa_adaptor_signatures,
+    read_hash_map, read_option, read_option_cb, read_string, read_usize, read_vec,
+};
+use dlc_messages::{AcceptDlc, SignDlc};
+use lightning::io::Read;
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::Readable;
+use std::collections::HashMap;
+
+/// Reads an [`OfferedContract`] serialized without the anchor outputs, batch,
+/// single funding, fee payer, replacement, roll over, expiry and funding output
+/// type information.
+pub fn read_offered_contract<R: Read>(r: &mut R) -> Result<OfferedContract, DecodeError> {
+    Ok(OfferedContract {
+        id: Readable::read(r)?,
+        is_offer_party: Readable::read(r)?,
+        contract_info: read_vec(r)?,
+        offer_params: party_params::read(r)?,
+        total_collateral: Readable::read(r)?,
+        funding_inputs: read_vec(r)?,
+        fund_output_serial_id: Readable::read(r)?,
+        fee_rate_per_vb: Readable::read(r)?,
+        cet_locktime: Readable::read(r)?,
+        refund_locktime: Readable::read(r)?,
+        counter_party: Readable::read(r)?,
+        keys_id: Readable::read(r)?,
+        anchor_outputs: false,
+        batch_id: None,
+        single_funded: false,
+        fee_payer: None,
+        replaced_contract_id: None,
+        rolled_over_contract_id: None,
+        offer_expiry: None,
+        funding_output_type: FundingOutputType::P2wsh,
+        taproot_funding_nonces: None,
+        taproot_secret_nonces: None,
+    })
+}
+
+/// Reads an [`AcceptedContract`] whose offered contract is in the format read
+/// by [`read_offered_contract`].
+pub fn read_accepted_contract<R: Read>(r: &mut R) -> Result<AcceptedContract, DecodeError> {
+    Ok(AcceptedContract {
+        offered_contract: read_offered_contract(r)?,
+        accept_params: party_params::read(r)?,
+        funding_inputs: read_vec(r)?,
+        adaptor_infos: read_vec(r)?,
+        adaptor_signatures: read_option_cb(r, &read_ecdsa_adaptor_signatures)?,
+        accept_refund_signature: Some(Readable::read(r)?),
+        dlc_transactions: dlc_transactions::read(r)?,
+    })
+}
+
+/// Reads a [`SignedContract`] serialized without a pending close offer nor
+/// taproot signatures, whose accepted contract is in the format read by [`read_accepted_contract`].
+pub fn read_signed_contract<R: Read>(r: &mut R) -> Result<SignedContract, DecodeError> {
+    Ok(SignedContract {
+        accepted_contract: read_accepted_contract(r)?,
+        adaptor_signatures: read_option_cb(r, &read_ecdsa_adaptor_signatures)?,
+        offer_refund_signature: Some(Readable::read(r)?),
+        funding_signatures: Readable::read(r)?,
+        channel_id: read_option(r)?,
+        close_offer: None,
+        taproot_signatures: None,
+    })
+}
+
+/// Reads a [`PreClosedContract`] whose signed contract is in the format read by
+/// [`read_signed_contract`].
+pub fn read_pre_closed_contract<R: Read>(r: &mut R) -> Result<PreClosedContract, DecodeError> {
+    Ok(PreClosedContract {
+        signed_contract: read_signed_contract(r)?,
+        attestations: read_option_cb(r, &read_vec)?,
+        signed_cet: Readable::read(r)?,
+    })
+}
+
+/// Reads a [`FailedAcceptContract`] whose offered contract is in the format
+/// read by [`read_offered_contract`] and whose accept message does not include
+/// taproot signatures.
+pub fn read_failed_accept_contract<R: Read>(
+    r: &mut R,
+) -> Result<FailedAcceptContract, DecodeError> {
+    Ok(FailedAcceptContract {
+        offered_contract: read_offered_contract(r)?,
+        accept_message: AcceptDlc {
+            protocol_version: Readable::read(r)?,
+            temporary_contract_id: Readable::read(r)?,
+            accept_collateral: Readable::read(r)?,
+            funding_pubkey: Readable::read(r)?,
+            payout_spk: Readable::read(r)?,
+            payout_serial_id: Readable::read(r)?,
+            funding_inputs: read_vec(r)?,
+            change_spk: Readable::read(r)?,
+            change_serial_id: Readable::read(r)?,
+            cet_adaptor_signatures: Readable::read(r)?,
+            refund_signature: Some(Readable::read(r)?),
+            negotiation_fields: read_option(r)?,
+            taproot_signatures: None,
+        },
+        error_message: read_string(r)?,
+    })
+}
+
+/// Reads a [`FailedSignContract`] whose accepted contract is in the format read
+/// by [`read_accepted_contract`] and whose sign message does not include fee
+/// payer funding signatures nor taproot signatures.
+pub fn read_failed_sign_contract<R: Read>(r: &mut R) -> Result<FailedSignContract, DecodeError> {
+    Ok(FailedSignContract {
+        accepted_contract: read_accepted_contract(r)?,
+        sign_message: SignDlc {
+            protocol_version: Readable::read(r)?,
+            contract_id: Readable::read(r)?,
+            cet_adaptor_signatures: Readable::read(r)?,
+            refund_signature: Some(Readable::read(r)?),
+            funding_signatures: Readable::read(r)?,
+            fee_payer_funding_signatures: None,
+            taproot_signatures: None,
+        },
+        error_message: read_string(r)?,
+    })
+}
+
+/// Reads an [`AcceptedChannel`] serialized without the nSequence value of the
+/// CETs, which is set to [`CET_NSEQUENCE`].
+pub fn read_accepted_channel<R: Read>(r: &mut R) -> Result<AcceptedChannel, DecodeError> {
+    Ok(AcceptedChannel {
+        accepted_contract_id: Readable::read(r)?,
+        offer_base_points: Readable::read(r)?,
+        accept_base_points: Readable::read(r)?,
+        offer_per_update_point: Readable::read(r)?,
+        accept_per_update_point: Readable::read(r)?,
+        buffer_transaction: Readable::read(r)?,
+        buffer_script_pubkey: Readable::read(r)?,
+        temporary_channel_id: Readable::read(r)?,
+        channel_id: Readable::read(r)?,
+        accept_per_update_seed: Readable::read(r)?,
+        accept_buffer_adaptor_signature: read_ecdsa_adaptor_signature(r)?,
+        counter_party: Readable::read(r)?,
+        cet_nsequence: CET_NSEQUENCE,
+    })
+}
+
+/// Reads a [`SignedChannelState`] whose settle, renew and collaborative close
+/// offer states do not include the share of the fee paid by the offer party.
+/// This share is set to [`OFFER_FEE_SHARE`].
+pub fn read_signed_channel_state<R: Read>(r: &mut R) -> Result<SignedChannelState, DecodeError> {
+    let id: u8 = Readable::read(r)?;
+    let state = match id {
+        1 => SignedChannelState::SettledOffered {
+            counter_payout: Readable::read(r)?,
+            next_per_update_point: Readable::read(r)?,
+            offer_fee_share: OFFER_FEE_SHARE,
+            timeout: Readable::read(r)?,
+            keys_id: Readable::read(r)?,
+        },
+        2 => SignedChannelState::SettledReceived {
+            own_payout: Readable::read(r)?,
+            counter_payout: Readable::read(r)?,
+            counter_next_per_update_point: Readable::read(r)?,
+            offer_fee_share: OFFER_FEE_SHARE,
+            keys_id: Readable::read(r)?,
+        },
+        6 => SignedChannelState::RenewOffered {
+            offered_contract_id: Readable::read(r)?,
+            counter_payout: Readable::read(r)?,
+            is_offer: Readable::read(r)?,
+            offer_next_per_update_point: Readable::read(r)?,
+            offer_fee_share: OFFER_FEE_SHARE,
+            timeout: Readable::read(r)?,
+            keys_id: Readable::read(r)?,
+        },
+        11 => SignedChannelState::CollaborativeCloseOffered {
+            counter_payout: Readable::read(r)?,
+            offer_signature: Readable::read(r)?,
+            close_tx: Readable::read(r)?,
+            offer_fee_share: OFFER_FEE_SHARE,
+            timeout: Readable::read(r)?,
+            keys_id: Readable::read(r)?,
+        },
+        // The other states did not change, and the states with a higher id did
+        // not exist.
+        0 | 3..=5 | 7..=10 => {
+            let prefix = [id];
+            let mut reader = (&prefix[..]).chain(&mut *r);
+            SignedChannelState::read(&mut reader)?
+        }
+        _ => return Err(DecodeError::UnknownRequiredFeature),
+    };
+    Ok(state)
+}
+
+/// Reads a [`SignedChannel`] serialized without the nSequence value of the CETs
+/// and settle transactions, which is set to [`CET_NSEQUENCE`], nor anchor
+/// outputs, and whose states are in the format read by
+/// [`read_signed_channel_state`].
+pub fn read_signed_channel<R: Read>(r: &mut R) -> Result<SignedChannel, DecodeError> {
+    Ok(SignedChannel {
+        channel_id: Readable::read(r)?,
+        counter_party: Readable::read(r)?,
+        temporary_channel_id: Readable::read(r)?,
+        fund_output_index: read_usize(r)?,
+        own_points: Readable::read(r)?,
+        own_params: party_params::read(r)?,
+        own_per_update_point: Readable::read(r)?,
+        counter_points: Readable::read(r)?,
+        counter_per_update_point: Readable::read(r)?,
+        counter_params: party_params::read(r)?,
+        state: read_signed_channel_state(r)?,
+        update_idx: Readable::read(r)?,
+        fund_tx: Readable::read(r)?,
+        fund_script_pubkey: Readable::read(r)?,
+        roll_back_state: read_option_cb(r, &read_signed_channel_state)?,
+        own_per_update_seed: Readable::read(r)?,
+        counter_party_commitment_secrets: Readable::read(r)?,
+        fee_rate_per_vb: Readable::read(r)?,
+        cet_nsequence: CET_NSEQUENCE,
+        anchor_outputs: false,
+    })
+}
+
+/// Reads a [`ChainMonitor`] serialized without the information used to handle
+/// chain reorganizations. As the heights at which the watched transactions
+/// were confirmed are unknown, they are considered confirmed at the last
+/// processed height.
+pub fn read_chain_monitor<R: Read>(r: &mut R) -> Result<ChainMonitor, DecodeError> {
+    let watched_tx: HashMap<Txid, WatchStateV0> = read_hash_map(r)?;
+    let watched_txo: HashMap<OutPoint, WatchStateV0> = read_hash_map(r)?;
+    let last_height: u64 = Readable::read(r)?;
+    let mut chain_monitor = ChainMonitor::new(last_height);
+    chain_monitor.watched_tx = watched_tx
+        .into_iter()
+        .map(|(txid, state)| (txid, state.into_watch_state(last_height)))
+        .collect();
+    chain_monitor.watched_txo = watched_txo
+        .into_iter()
+        .map(|(outpoint, state)| (outpoint, state.into_watch_state(last_height)))
+        .collect();
+    Ok(chain_monitor)
+}
+
+/// Reads a [`Utxo`] serialized without the maximum size of the witness required
+/// to spend it. The size is computed from the script pubkey of the output,
+/// defaulting to the size of a P2WPKH witness.
+pub fn read_utxo<R: Read>(r: &mut R) -> Result<Utxo, DecodeError> {
+    let tx_out: bitcoin::TxOut = Readable::read(r)?;
+    let outpoint = Readable::read(r)?;
+    let address = read_address(r)?;
+    let redeem_script: bitcoin::ScriptBuf = Readable::read(r)?;
+    let max_witness_len =
+        dlc::util::get_max_witness_len(&tx_out.script_pubkey, &redeem_script, None)
+            .unwrap_or(dlc::P2WPKH_WITNESS_SIZE);
+    Ok(Utxo {
+        tx_out,
+        outpoint,
+        address,
+        redeem_script,
+        max_witness_len,
+        reserved: Readable::read(r)?,
+    })
+}
+
+/// The state of a watched transaction, without the height of the block
+/// including it if confirmed.
+enum WatchStateV0 {
+    Registered {
+        channel_info: crate::chain_monitor::ChannelInfo,
+    },
+    Confirmed {
+        channel_info: crate::chain_monitor::ChannelInfo,
+        transaction: bitcoin::Transaction,
+    },
+}
+
+impl WatchStateV0 {
+    fn into_watch_state(self, height: u64) -> WatchState {
+        match self {
+            WatchStateV0::Registered { channel_info } => WatchState::Registered { channel_info },
+            WatchStateV0::Confirmed {
+                channel_info,
+                transaction,
+            } => WatchState::Confirmed {
+                channel_info,
+                transaction,
+                height,
+            },
+        }
+    }
+}
+
+impl Readable for WatchStateV0 {
+    fn read<R: Read>(r: &mut R) -> Result<Self, DecodeError> {
+        let id: u8 = Readable::read(r)?;
+        match id {
+            0 => Ok(WatchStateV0::Registered {
+                channel_info: Readable::read(r)?,
+            }),
+            1 => Ok(WatchStateV0::Confirmed {
+                channel_info: Readable::read(r)?,
+                transaction: Readable::read(r)?,
+            }),
+            _ => Err(DecodeError::UnknownRequiredFeature),
+        }
+    }
+}
@@ -14,6 +14,7 @@
 
 extern crate async_trait;
 extern crate bitcoin;
+extern crate chacha20poly1305;
 extern crate dlc;
 #[macro_use]
 extern crate dlc_messages;
@@ -32,12 +33,16 @@ pub mod contract;
 pub mod contract_updater;
 mod conversion_utils;
 pub mod error;
+pub mod events;
+pub mod legacy;
 pub mod manager;
+pub mod offer_policy;
 pub mod payout_curve;
 mod utils;
+pub mod watchtower;
 
 use bitcoin::psbt::PartiallySignedTransaction;
-use bitcoin::{Address, Block, OutPoint, ScriptBuf, Transaction, TxOut, Txid};
+use bitcoin::{Address, Block, BlockHash, OutPoint, ScriptBuf, Transaction, TxOut, Txid};
 use chain_monitor::ChainMonitor;
 use channel::offered_channel::OfferedChannel;
 use channel::signed_channel::{SignedChannel, SignedChannelStateType};
@@ -47,6 +52,7 @@ use contract::{offered_contract::OfferedContract, signed_contract::SignedContrac
 use dlc_messages::oracle_msgs::{OracleAnnouncement, OracleAttestation};
 use dlc_messages::ser_impls::{read_address, write_address};
 use error::Error;
+use events::Event;
 use lightning::ln::msgs::DecodeError;
 use lightning::util::ser::{Readable, Writeable, Writer};
 use secp256k1_zkp::{PublicKey, SecretKey, Signing};
@@ -152,7 +158,8 @@ pub trait Wallet {
     fn get_new_address(&self) -> Result<Address, Error>;
     /// Returns a new (unused) change address.
     fn get_new_change_address(&self) -> Result<Address, Error>;
-    /// Get a set of UTXOs to fund the given amount.
+    /// Get a set of UTXOs to fund the given amount. The cost of spending each
+    /// UTXO should be taken into account using their witness size.
     fn get_utxos_for_amount(
         &self,
         amount: u64,
@@ -181,6 +188,10 @@ pub trait Blockchain {
     fn get_blockchain_height(&self) -> Result<u64, Error>;
     /// Returns the block at given height
     fn get_block_at_height(&self, height: u64) -> Result<Block, Error>;
+    /// Returns the hash of the block at given height.
+    fn get_block_hash_at_height(&self, height: u64) -> Result<BlockHash, Error> {
+        Ok(self.get_block_at_height(height)?.block_hash())
+    }
     /// Get the transaction with given id.
     fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, Error>;
     /// Get the number of confirmation for the transaction with given id.
@@ -188,6 +199,13 @@ pub trait Blockchain {
 }
 
 /// Storage trait provides functionalities to store and retrieve DLCs.
+///
+/// Unlike the other provider traits, storage has no asynchronous version.
+/// Implementations are expected to be backed by local resources, as the sled
+/// and in memory ones are, and keeping the calls synchronous ensures that the
+/// [`manager::AsyncManager`] performs the writes making up a state transition
+/// without yielding to the executor in between, so that dropping one of its
+/// futures cannot leave a transition partially persisted.
 pub trait Storage {
     /// Returns the contract with given id if found.
     fn get_contract(&self, id: &ContractId) -> Result<Option<Contract>, Error>;
@@ -208,6 +226,21 @@ pub trait Storage {
     /// Returns the set of contracts whos broadcasted cet has not been verified to be confirmed on
     /// blockchain
     fn get_preclosed_contracts(&self) -> Result<Vec<PreClosedContract>, Error>;
+    /// Returns the set of contracts whose broadcast refund transaction has not
+    /// reached the required number of confirmations yet.
+    fn get_prerefunded_contracts(&self) -> Result<Vec<SignedContract>, Error>;
+    /// Returns the set of contracts that were rolled over into a new contract.
+    /// The contract replacing each of them refers to it through its
+    /// `rolled_over_contract_id`, making it possible to retrieve the history of
+    /// a rolled over contract.
+    fn get_rolled_over_contracts(&self) -> Result<Vec<SignedContract>, Error>;
+    /// Moves the given contract to the [`Contract::RolledOver`] state and
+    /// updates the contract it was rolled over into atomically.
+    fn roll_over_contract(
+        &self,
+        rolled_over_contract: &SignedContract,
+        contract: &Contract,
+    ) -> Result<(), Error>;
     /// Update the state of the channel and optionally its associated contract
     /// atomically.
     fn upsert_channel(&self, channel: Channel, contract: Option<Contract>) -> Result<(), Error>;
@@ -227,6 +260,11 @@ pub trait Storage {
     fn persist_chain_monitor(&self, monitor: &ChainMonitor) -> Result<(), Error>;
     /// Returns the latest [`ChainMonitor`] in the store if any.
     fn get_chain_monitor(&self) -> Result<Option<ChainMonitor>, Error>;
+    /// Writes the set of pending [`Event`] to the store, replacing the
+    /// previously stored ones.
+    fn persist_events(&self, events: &[Event]) -> Result<(), Error>;
+    /// Returns the set of pending [`Event`] in the store.
+    fn get_events(&self) -> Result<Vec<Event>, Error>;
 }
 
 /// Oracle trait provides access to oracle information.
@@ -239,6 +277,181 @@ pub trait Oracle {
     fn get_attestation(&self, event_id: &str) -> Result<OracleAttestation, Error>;
 }
 
+/// Asynchronous version of the [`Wallet`] trait, used by the
+/// [`manager::AsyncManager`] so that wallets backed by a remote service do not
+/// block the executor.
+#[async_trait::async_trait]
+pub trait AsyncWallet {
+    /// Returns a new (unused) address.
+    async fn get_new_address(&self) -> Result<Address, Error>;
+    /// Returns a new (unused) change address.
+    async fn get_new_change_address(&self) -> Result<Address, Error>;
+    /// Get a set of UTXOs to fund the given amount. The cost of spending each
+    /// UTXO should be taken into account using their witness size.
+    async fn get_utxos_for_amount(
+        &self,
+        amount: u64,
+        fee_rate: u64,
+        lock_utxos: bool,
+    ) -> Result<Vec<Utxo>, Error>;
+    /// Import the provided address.
+    async fn import_address(&self, address: &Address) -> Result<(), Error>;
+    /// Signs a transaction input
+    async fn sign_psbt_input(
+        &self,
+        psbt: &mut PartiallySignedTransaction,
+        input_index: usize,
+    ) -> Result<(), Error>;
+    /// Unlock reserved utxo
+    async fn unreserve_utxos(&self, outpoints: &[OutPoint]) -> Result<(), Error>;
+}
+
+/// Asynchronous version of the [`Blockchain`] trait, used by the
+/// [`manager::AsyncManager`] so that requests to the blockchain backend do not
+/// block the executor.
+#[async_trait::async_trait]
+pub trait AsyncBlockchain {
+    /// Broadcast the given transaction to the bitcoin network.
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<(), Error>;
+    /// Returns the network currently used (mainnet, testnet or regtest).
+    async fn get_network(&self) -> Result<bitcoin::network::constants::Network, Error>;
+    /// Returns the height of the blockchain
+    async fn get_blockchain_height(&self) -> Result<u64, Error>;
+    /// Returns the block at given height
+    async fn get_block_at_height(&self, height: u64) -> Result<Block, Error>;
+    /// Returns the hash of the block at given height.
+    async fn get_block_hash_at_height(&self, height: u64) -> Result<BlockHash, Error>;
+    /// Get the transaction with given id.
+    async fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, Error>;
+    /// Get the number of confirmation for the transaction with given id.
+    async fn get_transaction_confirmations(&self, tx_id: &Txid) -> Result<u32, Error>;
+}
+
+/// Asynchronous version of the [`Oracle`] trait, used by the
+/// [`manager::AsyncManager`] so that requests to oracles do not block the
+/// executor.
+#[async_trait::async_trait]
+pub trait AsyncOracle {
+    /// Returns the public key of the oracle.
+    fn get_public_key(&self) -> XOnlyPublicKey;
+    /// Returns the announcement for the event with the given id if found.
+    async fn get_announcement(&self, event_id: &str) -> Result<OracleAnnouncement, Error>;
+    /// Returns the attestation for the event with the given id if found.
+    async fn get_attestation(&self, event_id: &str) -> Result<OracleAttestation, Error>;
+}
+
+/// Exposes a [`Wallet`] as an [`AsyncWallet`]. The calls to the wrapped wallet
+/// are made synchronously when the futures are first polled, so the returned
+/// futures never need to wait.
+pub struct SyncWalletAdapter<W: Deref>(pub W)
+where
+    W::Target: Wallet;
+
+#[async_trait::async_trait]
+impl<W: Deref + Send + Sync> AsyncWallet for SyncWalletAdapter<W>
+where
+    W::Target: Wallet,
+{
+    async fn get_new_address(&self) -> Result<Address, Error> {
+        self.0.get_new_address()
+    }
+
+    async fn get_new_change_address(&self) -> Result<Address, Error> {
+        self.0.get_new_change_address()
+    }
+
+    async fn get_utxos_for_amount(
+        &self,
+        amount: u64,
+        fee_rate: u64,
+        lock_utxos: bool,
+    ) -> Result<Vec<Utxo>, Error> {
+        self.0.get_utxos_for_amount(amount, fee_rate, lock_utxos)
+    }
+
+    async fn import_address(&self, address: &Address) -> Result<(), Error> {
+        self.0.import_address(address)
+    }
+
+    async fn sign_psbt_input(
+        &self,
+        psbt: &mut PartiallySignedTransaction,
+        input_index: usize,
+    ) -> Result<(), Error> {
+        self.0.sign_psbt_input(psbt, input_index)
+    }
+
+    async fn unreserve_utxos(&self, outpoints: &[OutPoint]) -> Result<(), Error> {
+        self.0.unreserve_utxos(outpoints)
+    }
+}
+
+/// Exposes a [`Blockchain`] as an [`AsyncBlockchain`]. The calls to the wrapped
+/// blockchain are made synchronously when the futures are first polled, so the
+/// returned futures never need to wait.
+pub struct SyncBlockchainAdapter<B: Deref>(pub B)
+where
+    B::Target: Blockchain;
+
+#[async_trait::async_trait]
+impl<B: Deref + Send + Sync> AsyncBlockchain for SyncBlockchainAdapter<B>
+where
+    B::Target: Blockchain,
+{
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<(), Error> {
+        self.0.send_transaction(transaction)
+    }
+
+    async fn get_network(&self) -> Result<bitcoin::network::constants::Network, Error> {
+        self.0.get_network()
+    }
+
+    async fn get_blockchain_height(&self) -> Result<u64, Error> {
+        self.0.get_blockchain_height()
+    }
+
+    async fn get_block_at_height(&self, height: u64) -> Result<Block, Error> {
+        self.0.get_block_at_height(height)
+    }
+
+    async fn get_block_hash_at_height(&self, height: u64) -> Result<BlockHash, Error> {
+        self.0.get_block_hash_at_height(height)
+    }
+
+    async fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, Error> {
+        self.0.get_transaction(tx_id)
+    }
+
+    async fn get_transaction_confirmations(&self, tx_id: &Txid) -> Result<u32, Error> {
+        self.0.get_transaction_confirmations(tx_id)
+    }
+}
+
+/// Exposes an [`Oracle`] as an [`AsyncOracle`]. The calls to the wrapped oracle
+/// are made synchronously when the futures are first polled, so the returned
+/// futures never need to wait.
+pub struct SyncOracleAdapter<O: Deref>(pub O)
+where
+    O::Target: Oracle;
+
+#[async_trait::async_trait]
+impl<O: Deref + Send + Sync> AsyncOracle for SyncOracleAdapter<O>
+where
+    O::Target: Oracle,
+{
+    fn get_public_key(&self) -> XOnlyPublicKey {
+        self.0.get_public_key()
+    }
+
+    async fn get_announcement(&self, event_id: &str) -> Result<OracleAnnouncement, Error> {
+        self.0.get_announcement(event_id)
+    }
+
+    async fn get_attestation(&self, event_id: &str) -> Result<OracleAttestation, Error> {
+        self.0.get_attestation(event_id)
+    }
+}
+
 /// Represents a UTXO.
 #[derive(Clone, Debug)]
 pub struct Utxo {
@@ -250,6 +463,10 @@ pub struct Utxo {
     pub address: Address,
     /// The redeem script for the referenced output.
     pub redeem_script: ScriptBuf,
+    /// The maximum size of the witness required to spend the referenced output,
+    /// used to estimate the fees of the transactions spending it. See
+    /// [`dlc::util::get_max_witness_len`].
+    pub max_witness_len: usize,
     /// Whether this Utxo has been reserved (and so should not be used to fund
     /// a DLC).
     pub reserved: bool,
@@ -260,6 +477,7 @@ impl_dlc_writeable!(Utxo, {
     (outpoint, writeable),
     (address, {cb_writeable, write_address, read_address}),
     (redeem_script, writeable),
+    (max_witness_len, usize),
     (reserved, writeable)
 });
 
@@ -1,9 +1,11 @@
 //! #Manager a component to create and update DLCs.
 
 use super::{
-    Blockchain, CachedContractSignerProvider, ContractSigner, Oracle, Storage, Time, Wallet,
+    AsyncBlockchain, AsyncOracle, AsyncWallet, Blockchain, CachedContractSignerProvider,
+    ContractSigner, Oracle, Storage, SyncBlockchainAdapter, SyncOracleAdapter, SyncWalletAdapter,
+    Time, Wallet,
 };
-use crate::chain_monitor::{ChainMonitor, ChannelInfo, RevokedTxType, TxType};
+use crate::chain_monitor::{CetFeeBump, ChainMonitor, ChannelInfo, RevokedTxType, TxType};
 use crate::channel::offered_channel::OfferedChannel;
 use crate::channel::signed_channel::{SignedChannel, SignedChannelState, SignedChannelStateType};
 use crate::channel::{Channel, ClosedChannel, ClosedPunishedChannel};
@@ -15,22 +17,38 @@ use crate::contract::{
     signed_contract::SignedContract, AdaptorInfo, ClosedContract, Contract, FailedAcceptContract,
     FailedSignContract, PreClosedContract,
 };
-use crate::contract_updater::{accept_contract, verify_accepted_and_sign_contract};
+use crate::contract_updater::{
+    accept_batch_contract, accept_contract, accept_fee_bump, sign_contract_with_fee_payer_psbt,
+    verify_accepted_and_sign_batch_contract, verify_accepted_and_sign_contract,
+    verify_fee_bump_accept_and_sign,
+};
 use crate::error::Error;
+use crate::events::Event;
+use crate::offer_policy::{OfferDecision, OfferPolicy};
 use crate::utils::get_object_in_state;
+use crate::watchtower::PunishmentPackage;
 use crate::{ChannelId, ContractId, ContractSignerProvider};
 use bitcoin::absolute::Height;
 use bitcoin::consensus::encode::serialize_hex;
 use bitcoin::consensus::Decodable;
-use bitcoin::Address;
-use bitcoin::{OutPoint, Transaction};
+use bitcoin::psbt::PartiallySignedTransaction;
+use bitcoin::{Address, Network};
+use bitcoin::{OutPoint, Transaction, Txid};
+use dlc::channel::RevokeParams;
+use dlc::DlcTransactions;
+use dlc_messages::batch::{AcceptBatchDlc, OfferBatchDlc, SignBatchDlc};
+use dlc_messages::cancel::CancelOffer;
 use dlc_messages::channel::{
-    AcceptChannel, CollaborativeCloseOffer, OfferChannel, Reject, RenewAccept, RenewConfirm,
-    RenewFinalize, RenewOffer, RenewRevoke, SettleAccept, SettleConfirm, SettleFinalize,
-    SettleOffer, SignChannel,
+    AcceptChannel, ChannelReestablish, CollaborativeCloseOffer, OfferChannel, Reject, RenewAccept,
+    RenewConfirm, RenewFinalize, RenewOffer, RenewRevoke, SettleAccept, SettleConfirm,
+    SettleFinalize, SettleOffer, SignChannel, SpliceAccept, SpliceConfirm, SpliceOffer,
 };
+use dlc_messages::close::{CloseAccept, CloseOffer};
+use dlc_messages::fee_bump::{FeeBumpAccept, FeeBumpOffer};
 use dlc_messages::oracle_msgs::{OracleAnnouncement, OracleAttestation};
-use dlc_messages::{AcceptDlc, Message as DlcMessage, OfferDlc, SignDlc};
+use dlc_messages::reject::{RejectErrorCode, RejectOffer};
+use dlc_messages::rollover::{RolloverAccept, RolloverConfirm, RolloverOffer};
+use dlc_messages::{AcceptDlc, FeePayerInfo, Message as DlcMessage, OfferDlc, SignDlc};
 use hex::DisplayHex;
 use lightning::chain::chaininterface::FeeEstimator;
 use lightning::ln::chan_utils::{
@@ -42,19 +60,120 @@ use secp256k1_zkp::{
     ecdsa::Signature, All, EcdsaAdaptorSignature, PublicKey, Secp256k1, SecretKey,
 };
 use std::collections::HashMap;
+use std::future::Future;
 use std::ops::Deref;
 use std::string::ToString;
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
 
-/// The number of confirmations required before moving the the confirmed state.
+/// The default number of confirmations required before moving the the confirmed state.
 pub const NB_CONFIRMATIONS: u32 = 6;
-/// The delay to set the refund value to.
+/// The default delay to set the refund value to.
 pub const REFUND_DELAY: u32 = 86400 * 7;
-/// The nSequence value used for CETs in DLC channels
+/// The default nSequence value used for CETs in DLC channels
 pub const CET_NSEQUENCE: u32 = 288;
-/// Timeout in seconds when waiting for a peer's reply, after which a DLC channel
-/// is forced closed.
+/// Default timeout in seconds when waiting for a peer's reply, after which a DLC
+/// channel is forced closed.
 pub const PEER_TIMEOUT: u64 = 3600;
+/// Default number of blocks after which a broadcast CET with anchor outputs
+/// that is still unconfirmed gets its fee bumped using CPFP.
+pub const CPFP_CONFIRMATION_TARGET: u64 = 6;
+/// The minimum percentage by which the fee rate of an unconfirmed CET is
+/// increased each time its fee is bumped.
+pub const CPFP_FEE_RATE_INCREMENT: u64 = 25;
+/// The default percentage of the fee of a channel settle or closing transaction
+/// paid by the party proposing it.
+pub const OFFER_FEE_SHARE: u8 = 50;
+
+/// Parameters used by the [`Manager`] to create contracts and channels, and
+/// bounds within which the parameters of the offers received from peers must
+/// fall for them to be accepted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagerConfig {
+    /// The number of confirmations required before considering a funding
+    /// transaction or a CET confirmed.
+    pub nb_confirmations: u32,
+    /// The delay between the latest maturity of a contract and the lock time of
+    /// its refund transaction, unless overridden in the
+    /// [`ContractInput`].
+    pub refund_delay: u32,
+    /// The minimum refund delay accepted in offers received.
+    pub min_refund_delay: u32,
+    /// The maximum refund delay accepted in offers received.
+    pub max_refund_delay: u32,
+    /// The nSequence value used for CETs in DLC channels.
+    pub cet_nsequence: u32,
+    /// The minimum CET nSequence value accepted in channel offers received.
+    pub min_cet_nsequence: u32,
+    /// The maximum CET nSequence value accepted in channel offers received.
+    pub max_cet_nsequence: u32,
+    /// Timeout in seconds when waiting for a peer's reply, after which a DLC
+    /// channel is forced closed.
+    pub peer_timeout: u64,
+    /// Number of blocks after which a broadcast CET with anchor outputs that is
+    /// still unconfirmed gets its fee bumped using CPFP, and after which the fee
+    /// is bumped again if it still does not confirm.
+    pub cpfp_confirmation_target: u64,
+    /// The percentage of the fee of a channel settle, renewal or closing
+    /// transaction paid by the local party when it proposes the update.
+    pub offer_fee_share: u8,
+    /// The minimum percentage of the fee of a channel settle, renewal or
+    /// closing transaction that the counter party must pay when it proposes the
+    /// update.
+    pub min_counter_offer_fee_share: u8,
+    /// Whether to export a punishment package, through an
+    /// [`Event::PunishmentPackageExported`], each time a state of a channel
+    /// gets revoked, so that a watchtower can punish its publication while
+    /// the node is offline.
+    pub export_punishment_packages: bool,
+}
+
+impl Default for ManagerConfig {
+    fn default() -> Self {
+        ManagerConfig {
+            nb_confirmations: NB_CONFIRMATIONS,
+            refund_delay: REFUND_DELAY,
+            min_refund_delay: REFUND_DELAY,
+            max_refund_delay: REFUND_DELAY * 2,
+            cet_nsequence: CET_NSEQUENCE,
+            min_cet_nsequence: CET_NSEQUENCE,
+            max_cet_nsequence: CET_NSEQUENCE * 2,
+            peer_timeout: PEER_TIMEOUT,
+            cpfp_confirmation_target: CPFP_CONFIRMATION_TARGET,
+            offer_fee_share: OFFER_FEE_SHARE,
+            min_counter_offer_fee_share: OFFER_FEE_SHARE,
+            export_punishment_packages: false,
+        }
+    }
+}
+
+impl ManagerConfig {
+    /// Checks that the configured values are consistent with the configured
+    /// bounds.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.min_refund_delay > self.refund_delay || self.refund_delay > self.max_refund_delay {
+            return Err(Error::InvalidParameters(
+                "Refund delay must be within the configured bounds.".to_string(),
+            ));
+        }
+
+        if self.min_cet_nsequence > self.cet_nsequence
+            || self.cet_nsequence > self.max_cet_nsequence
+        {
+            return Err(Error::InvalidParameters(
+                "CET nSequence must be within the configured bounds.".to_string(),
+            ));
+        }
+
+        if self.offer_fee_share > 100 || self.min_counter_offer_fee_share > 100 {
+            return Err(Error::InvalidParameters(
+                "Fee shares must be percentages between 0 and 100.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
 
 type ClosableContractInfo<'a> = Option<(
     &'a ContractInfo,
@@ -62,8 +181,9 @@ type ClosableContractInfo<'a> = Option<(
     Vec<(usize, OracleAttestation)>,
 )>;
 
-/// Used to create and update DLCs.
-pub struct Manager<
+/// Used to create and update DLCs using asynchronous wallet, blockchain and
+/// oracle providers.
+pub struct AsyncManager<
     W: Deref,
     SP: Deref,
     B: Deref,
@@ -73,11 +193,11 @@ pub struct Manager<
     F: Deref,
     X: ContractSigner,
 > where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
-    B::Target: Blockchain,
+    B::Target: AsyncBlockchain,
     S::Target: Storage,
-    O::Target: Oracle,
+    O::Target: AsyncOracle,
     T::Target: Time,
     F::Target: FeeEstimator,
 {
@@ -90,6 +210,9 @@ pub struct Manager<
     chain_monitor: Mutex<ChainMonitor>,
     time: T,
     fee_estimator: F,
+    pending_events: Mutex<Vec<Event>>,
+    config: ManagerConfig,
+    offer_policy: Option<Box<dyn OfferPolicy>>,
 }
 
 macro_rules! get_contract_in_state {
@@ -137,7 +260,7 @@ macro_rules! check_for_timed_out_channels {
             if let SignedChannelState::$state { timeout, .. } = channel.state {
                 let is_timed_out = timeout < $manager.time.unix_time_now();
                 if is_timed_out {
-                    match $manager.force_close_channel_internal(channel, true) {
+                    match $manager.force_close_channel_internal(channel, true).await {
                         Err(e) => error!("Error force closing channel {}", e),
                         _ => {}
                     }
@@ -148,18 +271,18 @@ macro_rules! check_for_timed_out_channels {
 }
 
 impl<W: Deref, SP: Deref, B: Deref, S: Deref, O: Deref, T: Deref, F: Deref, X: ContractSigner>
-    Manager<W, Arc<CachedContractSignerProvider<SP, X>>, B, S, O, T, F, X>
+    AsyncManager<W, Arc<CachedContractSignerProvider<SP, X>>, B, S, O, T, F, X>
 where
-    W::Target: Wallet,
+    W::Target: AsyncWallet,
     SP::Target: ContractSignerProvider<Signer = X>,
-    B::Target: Blockchain,
+    B::Target: AsyncBlockchain,
     S::Target: Storage,
-    O::Target: Oracle,
+    O::Target: AsyncOracle,
     T::Target: Time,
     F::Target: FeeEstimator,
 {
-    /// Create a new Manager struct.
-    pub fn new(
+    /// Create a new AsyncManager struct.
+    pub async fn new(
         wallet: W,
         signer_provider: SP,
         blockchain: B,
@@ -167,17 +290,22 @@ where
         oracles: HashMap<XOnlyPublicKey, O>,
         time: T,
         fee_estimator: F,
+        config: ManagerConfig,
     ) -> Result<Self, Error> {
-        let init_height = blockchain.get_blockchain_height()?;
+        config.validate()?;
+
+        let init_height = blockchain.get_blockchain_height().await?;
         let chain_monitor = Mutex::new(
             store
                 .get_chain_monitor()?
                 .unwrap_or(ChainMonitor::new(init_height)),
         );
 
+        let pending_events = Mutex::new(store.get_events()?);
+
         let signer_provider = Arc::new(CachedContractSignerProvider::new(signer_provider));
 
-        Ok(Manager {
+        Ok(AsyncManager {
             secp: secp256k1_zkp::Secp256k1::new(),
             wallet,
             signer_provider,
@@ -187,6 +315,9 @@ where
             time,
             fee_estimator,
             chain_monitor,
+            pending_events,
+            config,
+            offer_policy: None,
         })
     }
 
@@ -195,20 +326,95 @@ where
         &self.store
     }
 
+    /// Get the configuration used by the Manager.
+    pub fn get_config(&self) -> &ManagerConfig {
+        &self.config
+    }
+
+    /// Sets the [`OfferPolicy`] used to automatically accept or reject the
+    /// contract offers received from peers. Offers are kept until manually
+    /// accepted or rejected when no policy is set.
+    pub fn set_offer_policy(&mut self, offer_policy: Option<Box<dyn OfferPolicy>>) {
+        self.offer_policy = offer_policy;
+    }
+
+    /// Returns the [`Event`] generated since the last call and removes them
+    /// from the store.
+    pub fn get_and_clear_pending_events(&self) -> Result<Vec<Event>, Error> {
+        let mut pending_events = self.pending_events.lock().unwrap();
+        self.store.persist_events(&[])?;
+        Ok(std::mem::take(&mut *pending_events))
+    }
+
+    fn push_events<I: IntoIterator<Item = Event>>(&self, events: I) -> Result<(), Error> {
+        let mut pending_events = self.pending_events.lock().unwrap();
+        let prev_len = pending_events.len();
+        pending_events.extend(events);
+        if pending_events.len() > prev_len {
+            self.store.persist_events(&pending_events)?;
+        }
+        Ok(())
+    }
+
     /// Function called to pass a DlcMessage to the Manager.
-    pub fn on_dlc_message(
+    pub async fn on_dlc_message(
         &self,
         msg: &DlcMessage,
         counter_party: PublicKey,
     ) -> Result<Option<DlcMessage>, Error> {
         match msg {
             DlcMessage::Offer(o) => {
-                self.on_offer_message(o, counter_party)?;
-                Ok(None)
+                let offered_contract = self.on_offer_message(o, counter_party)?;
+                self.apply_offer_policy(offered_contract).await
             }
-            DlcMessage::Accept(a) => Ok(Some(self.on_accept_message(a, &counter_party)?)),
+            DlcMessage::Accept(a) => self.on_accept_message(a, &counter_party).await,
             DlcMessage::Sign(s) => {
-                self.on_sign_message(s, &counter_party)?;
+                self.on_sign_message(s, &counter_party).await?;
+                Ok(None)
+            }
+            DlcMessage::OfferBatch(o) => {
+                self.on_offer_batch_message(o, counter_party)?;
+                Ok(None)
+            }
+            DlcMessage::AcceptBatch(a) => Ok(Some(DlcMessage::SignBatch(
+                self.on_accept_batch_message(a, &counter_party).await?,
+            ))),
+            DlcMessage::SignBatch(s) => {
+                self.on_sign_batch_message(s, &counter_party).await?;
+                Ok(None)
+            }
+            DlcMessage::FeeBumpOffer(o) => {
+                self.on_fee_bump_offer(o, counter_party)?;
+                Ok(None)
+            }
+            DlcMessage::FeeBumpAccept(a) => Ok(Some(DlcMessage::Sign(
+                self.on_fee_bump_accept(a, &counter_party).await?,
+            ))),
+            DlcMessage::CloseOffer(o) => {
+                self.on_close_offer(o, &counter_party)?;
+                Ok(None)
+            }
+            DlcMessage::CloseAccept(a) => {
+                self.on_close_accept(a, &counter_party).await?;
+                Ok(None)
+            }
+            DlcMessage::RolloverOffer(o) => {
+                self.on_rollover_offer(o, &counter_party)?;
+                Ok(None)
+            }
+            DlcMessage::RolloverAccept(a) => Ok(Some(DlcMessage::RolloverConfirm(
+                self.on_rollover_accept(a, &counter_party)?,
+            ))),
+            DlcMessage::RolloverConfirm(c) => {
+                self.on_rollover_confirm(c, &counter_party)?;
+                Ok(None)
+            }
+            DlcMessage::CancelOffer(c) => {
+                self.on_cancel_offer(c, &counter_party).await?;
+                Ok(None)
+            }
+            DlcMessage::RejectOffer(r) => {
+                self.on_reject_offer(r, &counter_party).await?;
                 Ok(None)
             }
             DlcMessage::OfferChannel(o) => {
@@ -216,10 +422,10 @@ where
                 Ok(None)
             }
             DlcMessage::AcceptChannel(a) => Ok(Some(DlcMessage::SignChannel(
-                self.on_accept_channel(a, &counter_party)?,
+                self.on_accept_channel(a, &counter_party).await?,
             ))),
             DlcMessage::SignChannel(s) => {
-                self.on_sign_channel(s, &counter_party)?;
+                self.on_sign_channel(s, &counter_party).await?;
                 Ok(None)
             }
             DlcMessage::SettleOffer(s) => match self.on_settle_offer(s, &counter_party)? {
@@ -230,10 +436,10 @@ where
                 self.on_settle_accept(s, &counter_party)?,
             ))),
             DlcMessage::SettleConfirm(s) => Ok(Some(DlcMessage::SettleFinalize(
-                self.on_settle_confirm(s, &counter_party)?,
+                self.on_settle_confirm(s, &counter_party).await?,
             ))),
             DlcMessage::SettleFinalize(s) => {
-                self.on_settle_finalize(s, &counter_party)?;
+                self.on_settle_finalize(s, &counter_party).await?;
                 Ok(None)
             }
             DlcMessage::RenewOffer(r) => match self.on_renew_offer(r, &counter_party)? {
@@ -241,13 +447,13 @@ where
                 None => Ok(None),
             },
             DlcMessage::RenewAccept(r) => Ok(Some(DlcMessage::RenewConfirm(
-                self.on_renew_accept(r, &counter_party)?,
+                self.on_renew_accept(r, &counter_party).await?,
             ))),
             DlcMessage::RenewConfirm(r) => Ok(Some(DlcMessage::RenewFinalize(
-                self.on_renew_confirm(r, &counter_party)?,
+                self.on_renew_confirm(r, &counter_party).await?,
             ))),
             DlcMessage::RenewFinalize(r) => {
-                let revoke = self.on_renew_finalize(r, &counter_party)?;
+                let revoke = self.on_renew_finalize(r, &counter_party).await?;
                 Ok(Some(DlcMessage::RenewRevoke(revoke)))
             }
             DlcMessage::RenewRevoke(r) => {
@@ -258,10 +464,24 @@ where
                 self.on_collaborative_close_offer(c, &counter_party)?;
                 Ok(None)
             }
+            DlcMessage::SpliceOffer(s) => match self.on_splice_offer(s, &counter_party)? {
+                Some(msg) => Ok(Some(DlcMessage::Reject(msg))),
+                None => Ok(None),
+            },
+            DlcMessage::SpliceAccept(s) => Ok(Some(DlcMessage::SpliceConfirm(
+                self.on_splice_accept(s, &counter_party).await?,
+            ))),
+            DlcMessage::SpliceConfirm(s) => {
+                self.on_splice_confirm(s, &counter_party).await?;
+                Ok(None)
+            }
             DlcMessage::Reject(r) => {
-                self.on_reject(r, &counter_party)?;
+                self.on_reject(r, &counter_party).await?;
                 Ok(None)
             }
+            DlcMessage::ChannelReestablish(c) => {
+                self.on_channel_reestablish(c, &counter_party).await
+            }
         }
     }
 
@@ -269,18 +489,17 @@ where
     /// and an OfferDlc message returned.
     ///
     /// This function will fetch the oracle announcements from the oracle.
-    pub fn send_offer(
+    pub async fn send_offer(
         &self,
         contract_input: &ContractInput,
         counter_party: PublicKey,
     ) -> Result<OfferDlc, Error> {
-        let oracle_announcements = contract_input
-            .contract_infos
-            .iter()
-            .map(|x| self.get_oracle_announcements(&x.oracles))
-            .collect::<Result<Vec<_>, Error>>()?;
+        let oracle_announcements = self
+            .get_contract_oracle_announcements(contract_input)
+            .await?;
 
         self.send_offer_with_announcements(contract_input, counter_party, oracle_announcements)
+            .await
     }
 
     /// Function called to create a new DLC. The offered contract will be stored
@@ -288,23 +507,60 @@ where
     ///
     /// This function allows to pass the oracle announcements directly instead of
     /// fetching them from the oracle.
-    pub fn send_offer_with_announcements(
+    pub async fn send_offer_with_announcements(
+        &self,
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+        oracle_announcements: Vec<Vec<OracleAnnouncement>>,
+    ) -> Result<OfferDlc, Error> {
+        self.send_offer_internal(contract_input, counter_party, oracle_announcements, None)
+            .await
+    }
+
+    /// Function called to create a new DLC whose transaction fees are paid by
+    /// a third party contributing the given inputs to the funding transaction.
+    /// Once the offer is accepted, the funding transaction must be signed by the
+    /// fee payer using the PSBT returned by [`Manager::get_fee_payer_psbt`],
+    /// and provided back through [`Manager::sign_with_fee_payer_psbt`].
+    pub async fn send_offer_with_fee_payer(
+        &self,
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+        fee_payer: FeePayerInfo,
+    ) -> Result<OfferDlc, Error> {
+        let oracle_announcements = self
+            .get_contract_oracle_announcements(contract_input)
+            .await?;
+
+        self.send_offer_internal(
+            contract_input,
+            counter_party,
+            oracle_announcements,
+            Some(fee_payer),
+        )
+        .await
+    }
+
+    async fn send_offer_internal(
         &self,
         contract_input: &ContractInput,
         counter_party: PublicKey,
         oracle_announcements: Vec<Vec<OracleAnnouncement>>,
+        fee_payer: Option<FeePayerInfo>,
     ) -> Result<OfferDlc, Error> {
         let (offered_contract, offer_msg) = crate::contract_updater::offer_contract(
             &self.secp,
             contract_input,
             oracle_announcements,
-            REFUND_DELAY,
+            self.config.refund_delay,
             &counter_party,
+            fee_payer,
             &self.wallet,
             &self.blockchain,
             &self.time,
             &self.signer_provider,
-        )?;
+        )
+        .await?;
 
         offered_contract.validate()?;
 
@@ -314,13 +570,37 @@ where
     }
 
     /// Function to call to accept a DLC for which an offer was received.
-    pub fn accept_contract_offer(
+    pub async fn accept_contract_offer(
         &self,
         contract_id: &ContractId,
     ) -> Result<(ContractId, PublicKey, AcceptDlc), Error> {
         let offered_contract =
             get_contract_in_state!(self, contract_id, Offered, None as Option<PublicKey>)?;
 
+        if offered_contract.batch_id.is_some() {
+            return Err(Error::InvalidState(
+                "Batch offers must be accepted using accept_batch_offer.".to_string(),
+            ));
+        }
+
+        if offered_contract.replaced_contract_id.is_some() {
+            return Err(Error::InvalidState(
+                "Fee bump offers must be accepted using accept_fee_bump_offer.".to_string(),
+            ));
+        }
+
+        if offered_contract.rolled_over_contract_id.is_some() {
+            return Err(Error::InvalidState(
+                "Rollover offers must be accepted using accept_contract_rollover.".to_string(),
+            ));
+        }
+
+        if offered_contract.is_expired(self.time.unix_time_now()) {
+            return Err(Error::InvalidState(
+                "Contract offer has expired.".to_string(),
+            ));
+        }
+
         let counter_party = offered_contract.counter_party;
 
         let (accepted_contract, accept_msg) = accept_contract(
@@ -329,12 +609,15 @@ where
             &self.wallet,
             &self.signer_provider,
             &self.blockchain,
-        )?;
+        )
+        .await?;
 
-        self.wallet.import_address(&Address::p2wsh(
-            &accepted_contract.dlc_transactions.funding_script_pubkey,
-            self.blockchain.get_network()?,
-        ))?;
+        self.wallet
+            .import_address(&get_fund_address(
+                &accepted_contract.dlc_transactions,
+                self.blockchain.get_network().await?,
+            )?)
+            .await?;
 
         let contract_id = accepted_contract.get_contract_id();
 
@@ -344,1859 +627,3792 @@ where
         Ok((contract_id, counter_party, accept_msg))
     }
 
-    /// Function to update the state of the [`ChainMonitor`] with new
-    /// blocks.
-    ///
-    /// Consumers **MUST** call this periodically in order to
-    /// determine when pending transactions reach confirmation.
-    pub fn periodic_chain_monitor(&self) -> Result<(), Error> {
-        let cur_height = self.blockchain.get_blockchain_height()?;
-        let last_height = self.chain_monitor.lock().unwrap().last_height;
+    /// Function called to cancel a contract offer that was sent and not
+    /// accepted yet. The funding inputs reserved for the offer are released
+    /// and a [`CancelOffer`] message to be sent to the counter party returned.
+    pub async fn cancel_offer(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<(CancelOffer, PublicKey), Error> {
+        let offered_contract =
+            get_contract_in_state!(self, contract_id, Offered, None as Option<PublicKey>)?;
 
-        // TODO(luckysori): We could end up reprocessing a block at
-        // the same height if there is a reorg.
-        if cur_height < last_height {
+        if !offered_contract.is_offer_party {
             return Err(Error::InvalidState(
-                "Current height is lower than last height.".to_string(),
+                "Only the offer party can cancel a contract offer.".to_string(),
             ));
         }
 
-        for height in last_height + 1..=cur_height {
-            let block = self.blockchain.get_block_at_height(height)?;
-
-            self.chain_monitor
-                .lock()
-                .unwrap()
-                .process_block(&block, height);
+        if offered_contract.batch_id.is_some()
+            || offered_contract.replaced_contract_id.is_some()
+            || offered_contract.rolled_over_contract_id.is_some()
+        {
+            return Err(Error::InvalidState(
+                "Only offers of single contracts can be cancelled.".to_string(),
+            ));
         }
 
-        Ok(())
-    }
+        let counter_party = offered_contract.counter_party;
+        let msg = CancelOffer {
+            protocol_version: crate::conversion_utils::PROTOCOL_VERSION,
+            temporary_contract_id: offered_contract.id,
+        };
 
-    /// Function to call to check the state of the currently executing DLCs and
-    /// update them if possible.
-    pub fn periodic_check(&self, check_channels: bool) -> Result<(), Error> {
-        self.check_signed_contracts()?;
-        self.check_confirmed_contracts()?;
-        self.check_preclosed_contracts()?;
+        self.cancel_offered_contract(offered_contract).await?;
 
-        if check_channels {
-            self.channel_checks()?;
+        Ok((msg, counter_party))
+    }
+
+    /// Releases the funding inputs reserved for the given contract offer if it
+    /// was sent by the local party, and moves it to the cancelled state.
+    async fn cancel_offered_contract(
+        &self,
+        offered_contract: OfferedContract,
+    ) -> Result<(), Error> {
+        if offered_contract.is_offer_party {
+            self.unreserve_offer_inputs(&offered_contract).await?;
         }
 
-        Ok(())
+        self.store
+            .update_contract(&Contract::Cancelled(offered_contract))
     }
 
-    fn on_offer_message(
+    async fn unreserve_offer_inputs(
         &self,
-        offered_message: &OfferDlc,
-        counter_party: PublicKey,
+        offered_contract: &OfferedContract,
     ) -> Result<(), Error> {
-        offered_message.validate(&self.secp, REFUND_DELAY, REFUND_DELAY * 2)?;
-        let keys_id = self
-            .signer_provider
-            .derive_signer_key_id(false, offered_message.temporary_contract_id);
-        let contract: OfferedContract =
-            OfferedContract::try_from_offer_dlc(offered_message, counter_party, keys_id)?;
-        contract.validate()?;
+        let outpoints = offered_contract
+            .offer_params
+            .inputs
+            .iter()
+            .map(|x| x.outpoint)
+            .collect::<Vec<_>>();
+        self.wallet.unreserve_utxos(&outpoints).await
+    }
 
-        if self.store.get_contract(&contract.id)?.is_some() {
-            return Err(Error::InvalidParameters(
-                "Contract with identical id already exists".to_string(),
+    /// Function to call to reject a contract offer received from a peer.
+    /// Returns the [`RejectOffer`] message to be sent, containing the given
+    /// error code and reason, as well as the public key of the offering node.
+    pub fn reject_contract_offer(
+        &self,
+        contract_id: &ContractId,
+        error_code: RejectErrorCode,
+        reason: String,
+    ) -> Result<(RejectOffer, PublicKey), Error> {
+        let offered_contract =
+            get_contract_in_state!(self, contract_id, Offered, None as Option<PublicKey>)?;
+
+        if offered_contract.is_offer_party {
+            return Err(Error::InvalidState(
+                "Cannot reject contract offered by us.".to_string(),
             ));
         }
 
-        self.store.create_contract(&contract)?;
+        if offered_contract.batch_id.is_some()
+            || offered_contract.replaced_contract_id.is_some()
+            || offered_contract.rolled_over_contract_id.is_some()
+        {
+            return Err(Error::InvalidState(
+                "Only offers of single contracts can be rejected.".to_string(),
+            ));
+        }
 
-        Ok(())
+        let counter_party = offered_contract.counter_party;
+        let msg = RejectOffer {
+            protocol_version: crate::conversion_utils::PROTOCOL_VERSION,
+            temporary_contract_id: offered_contract.id,
+            error_code,
+            reason,
+        };
+
+        self.store
+            .update_contract(&Contract::Rejected(offered_contract))?;
+
+        Ok((msg, counter_party))
     }
 
-    fn on_accept_message(
+    /// Function called to offer a batch of DLCs funded by a single funding
+    /// transaction. The offered contracts will be stored and an OfferBatchDlc
+    /// message returned. All the contracts must use the same fee rate.
+    ///
+    /// This function will fetch the oracle announcements from the oracle.
+    pub async fn send_batch_offer(
         &self,
-        accept_msg: &AcceptDlc,
-        counter_party: &PublicKey,
-    ) -> Result<DlcMessage, Error> {
-        let offered_contract = get_contract_in_state!(
-            self,
-            &accept_msg.temporary_contract_id,
-            Offered,
-            Some(*counter_party)
-        )?;
+        contract_inputs: &[ContractInput],
+        counter_party: PublicKey,
+    ) -> Result<OfferBatchDlc, Error> {
+        let mut oracle_announcements = Vec::with_capacity(contract_inputs.len());
+        for contract_input in contract_inputs {
+            oracle_announcements.push(
+                self.get_contract_oracle_announcements(contract_input)
+                    .await?,
+            );
+        }
 
-        let (signed_contract, signed_msg) = match verify_accepted_and_sign_contract(
+        let (offered_contracts, offer_msg) = crate::contract_updater::offer_batch_contract(
             &self.secp,
-            &offered_contract,
-            accept_msg,
+            contract_inputs,
+            oracle_announcements,
+            self.config.refund_delay,
+            &counter_party,
             &self.wallet,
+            &self.blockchain,
+            &self.time,
             &self.signer_provider,
-        ) {
-            Ok(contract) => contract,
-            Err(e) => return self.accept_fail_on_error(offered_contract, accept_msg.clone(), e),
-        };
+        )
+        .await?;
 
-        self.wallet.import_address(&Address::p2wsh(
-            &signed_contract
-                .accepted_contract
-                .dlc_transactions
-                .funding_script_pubkey,
-            self.blockchain.get_network()?,
-        ))?;
+        for offered_contract in &offered_contracts {
+            offered_contract.validate()?;
+        }
 
-        self.store
-            .update_contract(&Contract::Signed(signed_contract))?;
+        for offered_contract in &offered_contracts {
+            self.store.create_contract(offered_contract)?;
+        }
 
-        Ok(DlcMessage::Sign(signed_msg))
+        Ok(offer_msg)
     }
 
-    fn on_sign_message(&self, sign_message: &SignDlc, peer_id: &PublicKey) -> Result<(), Error> {
-        let accepted_contract =
-            get_contract_in_state!(self, &sign_message.contract_id, Accepted, Some(*peer_id))?;
+    /// Function to call to accept a batch of DLCs for which an offer was
+    /// received. Returns the ids of the accepted contracts.
+    pub async fn accept_batch_offer(
+        &self,
+        temporary_batch_id: &[u8; 32],
+    ) -> Result<(Vec<ContractId>, PublicKey, AcceptBatchDlc), Error> {
+        let offered_contracts = self.get_batch_contract_offers(temporary_batch_id, None)?;
 
-        let (signed_contract, fund_tx) = match crate::contract_updater::verify_signed_contract(
+        let counter_party = offered_contracts[0].counter_party;
+
+        let (accepted_contracts, accept_msg) = accept_batch_contract(
             &self.secp,
-            &accepted_contract,
-            sign_message,
+            &offered_contracts,
             &self.wallet,
-        ) {
-            Ok(contract) => contract,
-            Err(e) => return self.sign_fail_on_error(accepted_contract, sign_message.clone(), e),
-        };
+            &self.signer_provider,
+            &self.blockchain,
+        )
+        .await?;
 
-        self.store
-            .update_contract(&Contract::Signed(signed_contract))?;
+        let mut contract_ids = Vec::with_capacity(accepted_contracts.len());
+        for accepted_contract in accepted_contracts {
+            self.wallet
+                .import_address(&Address::p2wsh(
+                    &accepted_contract.dlc_transactions.funding_script_pubkey,
+                    self.blockchain.get_network().await?,
+                ))
+                .await?;
 
-        self.blockchain.send_transaction(&fund_tx)?;
+            contract_ids.push(accepted_contract.get_contract_id());
 
-        Ok(())
+            self.store
+                .update_contract(&Contract::Accepted(accepted_contract))?;
+        }
+
+        Ok((contract_ids, counter_party, accept_msg))
     }
 
-    fn get_oracle_announcements(
-        &self,
-        oracle_inputs: &OracleInput,
-    ) -> Result<Vec<OracleAnnouncement>, Error> {
-        let mut announcements = Vec::new();
-        for pubkey in &oracle_inputs.public_keys {
-            let oracle = self
-                .oracles
-                .get(pubkey)
-                .ok_or_else(|| Error::InvalidParameters("Unknown oracle public key".to_string()))?;
-            announcements.push(oracle.get_announcement(&oracle_inputs.event_id)?.clone());
+    /// Function to update the state of the [`ChainMonitor`] with new
+    /// blocks.
+    ///
+    /// Consumers **MUST** call this periodically in order to
+    /// determine when pending transactions reach confirmation.
+    pub async fn periodic_chain_monitor(&self) -> Result<(), Error> {
+        let cur_height = self.blockchain.get_blockchain_height().await?;
+
+        if let Some(fork_height) = self.find_fork_height(cur_height).await? {
+            self.on_reorg(fork_height).await?;
         }
 
-        Ok(announcements)
-    }
+        let last_height = self.chain_monitor.lock().unwrap().last_height;
 
-    fn sign_fail_on_error<R>(
-        &self,
-        accepted_contract: AcceptedContract,
-        sign_message: SignDlc,
-        e: Error,
-    ) -> Result<R, Error> {
-        error!("Error in on_sign {}", e);
-        self.store
-            .update_contract(&Contract::FailedSign(FailedSignContract {
-                accepted_contract,
-                sign_message,
-                error_message: e.to_string(),
-            }))?;
-        Err(e)
-    }
+        if cur_height < last_height {
+            return Err(Error::InvalidState(
+                "Current height is lower than last height.".to_string(),
+            ));
+        }
 
-    fn accept_fail_on_error<R>(
-        &self,
-        offered_contract: OfferedContract,
-        accept_message: AcceptDlc,
-        e: Error,
-    ) -> Result<R, Error> {
-        error!("Error in on_accept {}", e);
-        self.store
-            .update_contract(&Contract::FailedAccept(FailedAcceptContract {
-                offered_contract,
-                accept_message,
-                error_message: e.to_string(),
-            }))?;
-        Err(e)
-    }
+        for height in last_height + 1..=cur_height {
+            let block = self.blockchain.get_block_at_height(height).await?;
 
-    fn check_signed_contract(&self, contract: &SignedContract) -> Result<(), Error> {
-        let confirmations = self.blockchain.get_transaction_confirmations(
-            &contract.accepted_contract.dlc_transactions.fund.txid(),
-        )?;
-        if confirmations >= NB_CONFIRMATIONS {
+            self.chain_monitor
+                .lock()
+                .unwrap()
+                .process_block(&block, height);
+        }
+
+        if cur_height > last_height {
             self.store
-                .update_contract(&Contract::Confirmed(contract.clone()))?;
+                .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
         }
+
         Ok(())
     }
 
-    fn check_signed_contracts(&self) -> Result<(), Error> {
-        for c in self.store.get_signed_contracts()? {
-            if let Err(e) = self.check_signed_contract(&c) {
-                error!(
-                    "Error checking confirmed contract {}: {}",
-                    c.accepted_contract.get_contract_id_string(),
-                    e
-                )
+    /// Returns the height of the last processed block that is still part of
+    /// the chain if some processed blocks were disconnected, `None` otherwise.
+    async fn find_fork_height(&self, cur_height: u64) -> Result<Option<u64>, Error> {
+        let (last_height, lowest_tracked_height, is_tracking_full) = {
+            let chain_monitor = self.chain_monitor.lock().unwrap();
+            (
+                chain_monitor.last_height,
+                chain_monitor.lowest_tracked_height(),
+                chain_monitor.is_tracking_full(),
+            )
+        };
+
+        let mut height = std::cmp::min(last_height, cur_height);
+        while height >= lowest_tracked_height && height > 0 {
+            let tracked_hash = self.chain_monitor.lock().unwrap().get_block_hash(height);
+            let tracked_hash = match tracked_hash {
+                Some(h) => h,
+                None => break,
+            };
+            if tracked_hash == self.blockchain.get_block_hash_at_height(height).await? {
+                return Ok(if height < last_height {
+                    Some(height)
+                } else {
+                    None
+                });
             }
+            height -= 1;
         }
 
-        Ok(())
+        if height < last_height && lowest_tracked_height <= last_height {
+            if is_tracking_full {
+                return Err(Error::InvalidState(format!(
+                    "Chain reorganization deeper than {} blocks cannot be handled.",
+                    crate::chain_monitor::REORG_SAFETY_DEPTH
+                )));
+            }
+            return Ok(Some(height));
+        }
+
+        Ok(None)
+    }
+
+    /// Reverts the effects of the processed blocks above the given height which
+    /// were disconnected from the chain.
+    async fn on_reorg(&self, fork_height: u64) -> Result<(), Error> {
+        warn!(
+            "Chain reorganization detected, blocks above height {} were disconnected.",
+            fork_height
+        );
+
+        let snapshots = self
+            .chain_monitor
+            .lock()
+            .unwrap()
+            .disconnect_blocks(fork_height);
+
+        for snapshot in snapshots {
+            log::info!(
+                "Restoring state of channel {:?} prior to height {}",
+                snapshot.signed_channel.channel_id,
+                snapshot.height
+            );
+            self.store.upsert_channel(
+                Channel::Signed(snapshot.signed_channel),
+                snapshot.signed_contract.map(Contract::Confirmed),
+            )?;
+        }
+
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        self.revert_reorged_contracts().await
     }
 
-    fn check_confirmed_contracts(&self) -> Result<(), Error> {
+    /// Reverts contracts whose funding transaction, CET or refund transaction
+    /// is not part of the chain anymore to their previous state.
+    async fn revert_reorged_contracts(&self) -> Result<(), Error> {
+        let contract_snapshots = self
+            .chain_monitor
+            .lock()
+            .unwrap()
+            .contract_snapshots
+            .clone();
+        for snapshot in contract_snapshots {
+            let contract_id = snapshot.signed_contract.accepted_contract.get_contract_id();
+            if !matches!(
+                self.store.get_contract(&contract_id)?,
+                Some(Contract::Closed(_))
+            ) {
+                self.chain_monitor
+                    .lock()
+                    .unwrap()
+                    .remove_contract_snapshot(&contract_id);
+                continue;
+            }
+            if self
+                .blockchain
+                .get_transaction_confirmations(&snapshot.closing_txid)
+                .await?
+                == 0
+            {
+                warn!(
+                    "CET of closed contract {:?} was reorganized out of the chain.",
+                    contract_id
+                );
+                self.clear_cet_fee_bump(&snapshot.closing_txid).await?;
+                self.chain_monitor
+                    .lock()
+                    .unwrap()
+                    .remove_contract_snapshot(&contract_id);
+                self.store
+                    .update_contract(&Contract::Confirmed(snapshot.signed_contract))?;
+            }
+        }
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
         for c in self.store.get_confirmed_contracts()? {
-            // Confirmed contracts from channel are processed in channel specific methods.
             if c.channel_id.is_some() {
                 continue;
             }
-            if let Err(e) = self.check_confirmed_contract(&c) {
-                error!(
-                    "Error checking confirmed contract {}: {}",
-                    c.accepted_contract.get_contract_id_string(),
-                    e
-                )
+            let fund_txid = c.accepted_contract.dlc_transactions.fund.txid();
+            if self
+                .blockchain
+                .get_transaction_confirmations(&fund_txid)
+                .await?
+                == 0
+            {
+                warn!(
+                    "Funding transaction of contract {:?} was reorganized out of the chain.",
+                    c.accepted_contract.get_contract_id()
+                );
+                self.store.update_contract(&Contract::Signed(c))?;
             }
         }
 
-        Ok(())
-    }
-
-    fn get_closable_contract_info<'a>(
-        &'a self,
-        contract: &'a SignedContract,
-    ) -> ClosableContractInfo<'a> {
-        let contract_infos = &contract.accepted_contract.offered_contract.contract_info;
-        let adaptor_infos = &contract.accepted_contract.adaptor_infos;
-        for (contract_info, adaptor_info) in contract_infos.iter().zip(adaptor_infos.iter()) {
-            let matured: Vec<_> = contract_info
-                .oracle_announcements
-                .iter()
-                .filter(|x| {
-                    (x.oracle_event.event_maturity_epoch as u64) <= self.time.unix_time_now()
-                })
-                .enumerate()
-                .collect();
-            if matured.len() >= contract_info.threshold {
-                let attestations: Vec<_> = matured
-                    .iter()
-                    .filter_map(|(i, announcement)| {
-                        let oracle = self.oracles.get(&announcement.oracle_public_key)?;
-                        Some((
-                            *i,
-                            oracle
-                                .get_attestation(&announcement.oracle_event.event_id)
-                                .ok()?,
-                        ))
-                    })
-                    .collect();
-                if attestations.len() >= contract_info.threshold {
-                    return Some((contract_info, adaptor_info, attestations));
-                }
+        for c in self.store.get_preclosed_contracts()? {
+            if c.signed_contract.channel_id.is_some() {
+                continue;
+            }
+            if self
+                .blockchain
+                .get_transaction_confirmations(&c.signed_cet.txid())
+                .await?
+                == 0
+            {
+                warn!(
+                    "CET of contract {:?} was reorganized out of the chain.",
+                    c.signed_contract.accepted_contract.get_contract_id()
+                );
+                self.clear_cet_fee_bump(&c.signed_cet.txid()).await?;
+                self.store
+                    .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+                self.store
+                    .update_contract(&Contract::Confirmed(c.signed_contract))?;
             }
         }
-        None
-    }
 
-    fn check_confirmed_contract(&self, contract: &SignedContract) -> Result<(), Error> {
-        let closable_contract_info = self.get_closable_contract_info(contract);
-        if let Some((contract_info, adaptor_info, attestations)) = closable_contract_info {
-            let offer = &contract.accepted_contract.offered_contract;
-            let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
-            let cet = crate::contract_updater::get_signed_cet(
-                &self.secp,
-                contract,
-                contract_info,
-                adaptor_info,
-                &attestations,
-                &signer,
-            )?;
-            match self.close_contract(
-                contract,
-                cet,
-                attestations.iter().map(|x| x.1.clone()).collect(),
-            ) {
-                Ok(closed_contract) => {
-                    self.store.update_contract(&closed_contract)?;
-                    return Ok(());
-                }
-                Err(e) => {
+        for contract in self.store.get_contracts()? {
+            if let Contract::Refunded(c) = &contract {
+                if c.channel_id.is_none()
+                    && self
+                        .blockchain
+                        .get_transaction_confirmations(
+                            &c.accepted_contract.dlc_transactions.refund.txid(),
+                        )
+                        .await?
+                        == 0
+                {
                     warn!(
-                        "Failed to close contract {}: {}",
-                        contract.accepted_contract.get_contract_id_string(),
-                        e
+                        "Refund transaction of contract {:?} was reorganized out of the chain.",
+                        c.accepted_contract.get_contract_id()
                     );
-                    return Err(e);
+                    self.mark_prerefunded(c)?;
                 }
             }
         }
 
-        self.check_refund(contract)?;
+        Ok(())
+    }
+
+    /// Function to call to check the state of the currently executing DLCs and
+    /// update them if possible.
+    pub async fn periodic_check(&self, check_channels: bool) -> Result<(), Error> {
+        self.check_expired_offers().await?;
+        self.check_signed_contracts().await?;
+        self.check_confirmed_contracts().await?;
+        self.check_preclosed_contracts().await?;
+        self.check_prerefunded_contracts().await?;
+
+        if check_channels {
+            self.channel_checks().await?;
+        }
 
         Ok(())
     }
 
-    /// Manually close a contract with the oracle attestations.
-    pub fn close_confirmed_contract(
+    fn on_offer_message(
         &self,
-        contract_id: &ContractId,
-        attestations: Vec<(usize, OracleAttestation)>,
-    ) -> Result<Contract, Error> {
-        let contract = get_contract_in_state!(self, contract_id, Confirmed, None::<PublicKey>)?;
-        let contract_infos = &contract.accepted_contract.offered_contract.contract_info;
-        let adaptor_infos = &contract.accepted_contract.adaptor_infos;
+        offered_message: &OfferDlc,
+        counter_party: PublicKey,
+    ) -> Result<OfferedContract, Error> {
+        offered_message.validate(
+            &self.secp,
+            self.config.min_refund_delay,
+            self.config.max_refund_delay,
+        )?;
+        let keys_id = self
+            .signer_provider
+            .derive_signer_key_id(false, offered_message.temporary_contract_id);
+        let contract: OfferedContract =
+            OfferedContract::try_from_offer_dlc(offered_message, counter_party, keys_id)?;
+        contract.validate()?;
 
-        // find the contract info that matches the attestations
-        if let Some((contract_info, adaptor_info)) =
-            contract_infos.iter().zip(adaptor_infos).find(|(c, _)| {
-                let matches = attestations
-                    .iter()
-                    .filter(|(i, a)| {
-                        c.oracle_announcements[*i].oracle_event.oracle_nonces == a.nonces()
-                    })
-                    .count();
+        if contract.is_expired(self.time.unix_time_now()) {
+            return Err(Error::InvalidParameters(
+                "Received an expired contract offer.".to_string(),
+            ));
+        }
 
-                matches >= c.threshold
-            })
-        {
-            let offer = &contract.accepted_contract.offered_contract;
-            let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
-            let cet = crate::contract_updater::get_signed_cet(
-                &self.secp,
-                &contract,
-                contract_info,
-                adaptor_info,
-                &attestations,
-                &signer,
-            )?;
+        if self.store.get_contract(&contract.id)?.is_some() {
+            return Err(Error::InvalidParameters(
+                "Contract with identical id already exists".to_string(),
+            ));
+        }
 
-            // Check that the lock time has passed
-            let time = bitcoin::absolute::Time::from_consensus(self.time.unix_time_now() as u32)
-                .expect("Time is not in valid range. This should never happen.");
-            let height = Height::from_consensus(self.blockchain.get_blockchain_height()? as u32)
-                .expect("Height is not in valid range. This should never happen.");
-            let locktime = cet.lock_time;
+        self.store.create_contract(&contract)?;
 
-            if !locktime.is_satisfied_by(height, time) {
-                return Err(Error::InvalidState(
-                    "CET lock time has not passed yet".to_string(),
-                ));
-            }
+        self.push_events(Some(Event::OfferReceived {
+            temporary_contract_id: contract.id,
+            counter_party,
+        }))?;
 
-            match self.close_contract(
-                &contract,
-                cet,
-                attestations.into_iter().map(|x| x.1).collect(),
-            ) {
-                Ok(closed_contract) => {
-                    self.store.update_contract(&closed_contract)?;
-                    Ok(closed_contract)
-                }
+        Ok(contract)
+    }
+
+    async fn apply_offer_policy(
+        &self,
+        offered_contract: OfferedContract,
+    ) -> Result<Option<DlcMessage>, Error> {
+        let decision = match &self.offer_policy {
+            Some(policy) => policy.decide(&offered_contract, self.time.unix_time_now()),
+            None => return Ok(None),
+        };
+
+        match decision {
+            OfferDecision::Accept => match self.accept_contract_offer(&offered_contract.id).await {
+                Ok((_, _, accept_msg)) => Ok(Some(DlcMessage::Accept(accept_msg))),
                 Err(e) => {
-                    warn!(
-                        "Failed to close contract {}: {e}",
-                        contract.accepted_contract.get_contract_id_string()
+                    error!(
+                        "Could not automatically accept offer {}: {}",
+                        offered_contract.id.to_lower_hex_string(),
+                        e
                     );
-                    Err(e)
+                    Ok(None)
                 }
+            },
+            OfferDecision::Reject(error_code, reason) => {
+                let (msg, _) =
+                    self.reject_contract_offer(&offered_contract.id, error_code, reason.clone())?;
+                self.push_events(Some(Event::OfferRejected {
+                    temporary_contract_id: offered_contract.id,
+                    error_code,
+                    reason,
+                }))?;
+                Ok(Some(DlcMessage::RejectOffer(msg)))
             }
-        } else {
-            Err(Error::InvalidState(
-                "Attestations did not match contract infos".to_string(),
-            ))
+            OfferDecision::Hold => Ok(None),
         }
     }
 
-    fn check_preclosed_contracts(&self) -> Result<(), Error> {
-        for c in self.store.get_preclosed_contracts()? {
-            if let Err(e) = self.check_preclosed_contract(&c) {
-                error!(
-                    "Error checking pre-closed contract {}: {}",
-                    c.signed_contract.accepted_contract.get_contract_id_string(),
-                    e
-                )
-            }
-        }
+    async fn on_cancel_offer(
+        &self,
+        cancel_offer: &CancelOffer,
+        counter_party: &PublicKey,
+    ) -> Result<(), Error> {
+        let temporary_contract_id = cancel_offer.temporary_contract_id;
+        // An offer that was accepted is stored under its final contract id.
+        let contract = match self.store.get_contract(&temporary_contract_id)? {
+            Some(contract) => Some(contract),
+            None => self.store.get_contracts()?.into_iter().find(|c| {
+                matches!(c, Contract::Accepted(a) if a.offered_contract.id == temporary_contract_id)
+            }),
+        };
 
-        Ok(())
-    }
+        let contract =
+            contract.ok_or_else(|| Error::InvalidParameters("Unknown contract id.".to_string()))?;
 
-    fn check_preclosed_contract(&self, contract: &PreClosedContract) -> Result<(), Error> {
-        let broadcasted_txid = contract.signed_cet.txid();
-        let confirmations = self
-            .blockchain
-            .get_transaction_confirmations(&broadcasted_txid)?;
-        if confirmations >= NB_CONFIRMATIONS {
-            let closed_contract = ClosedContract {
-                attestations: contract.attestations.clone(),
-                signed_cet: Some(contract.signed_cet.clone()),
-                contract_id: contract.signed_contract.accepted_contract.get_contract_id(),
-                temporary_contract_id: contract
-                    .signed_contract
-                    .accepted_contract
-                    .offered_contract
-                    .id,
-                counter_party_id: contract
-                    .signed_contract
-                    .accepted_contract
-                    .offered_contract
-                    .counter_party,
-                pnl: contract
-                    .signed_contract
-                    .accepted_contract
-                    .compute_pnl(&contract.signed_cet),
-            };
-            self.store
-                .update_contract(&Contract::Closed(closed_contract))?;
+        if contract.get_counter_party_id() != *counter_party {
+            return Err(Error::InvalidParameters(format!(
+                "Peer {:02x?} is not involved with contract {:02x?}.",
+                counter_party, temporary_contract_id
+            )));
         }
 
+        let offered_contract = match contract {
+            Contract::Offered(o) if !o.is_offer_party => o,
+            Contract::Accepted(a) if !a.offered_contract.is_offer_party => {
+                let outpoints = a
+                    .accept_params
+                    .inputs
+                    .iter()
+                    .map(|x| x.outpoint)
+                    .collect::<Vec<_>>();
+                self.wallet.unreserve_utxos(&outpoints).await?;
+                self.store.delete_contract(&a.get_contract_id())?;
+                a.offered_contract
+            }
+            c => {
+                return Err(Error::InvalidState(format!(
+                    "Cannot cancel contract offer in state {:?}.",
+                    c
+                )))
+            }
+        };
+
+        self.store
+            .update_contract(&Contract::Cancelled(offered_contract))?;
+        self.push_events(Some(Event::OfferCancelled {
+            temporary_contract_id,
+        }))?;
+
         Ok(())
     }
 
-    fn close_contract(
+    async fn on_reject_offer(
         &self,
-        contract: &SignedContract,
-        signed_cet: Transaction,
-        attestations: Vec<OracleAttestation>,
-    ) -> Result<Contract, Error> {
-        let confirmations = self
-            .blockchain
-            .get_transaction_confirmations(&signed_cet.txid())?;
+        reject_offer: &RejectOffer,
+        counter_party: &PublicKey,
+    ) -> Result<(), Error> {
+        let offered_contract = get_contract_in_state!(
+            self,
+            &reject_offer.temporary_contract_id,
+            Offered,
+            Some(*counter_party)
+        )?;
 
-        if confirmations < 1 {
-            // TODO(tibo): if this fails because another tx is already in
-            // mempool or blockchain, we might have been cheated. There is
-            // not much to be done apart from possibly extracting a fraud
-            // proof but ideally it should be handled.
-            self.blockchain.send_transaction(&signed_cet)?;
+        if !offered_contract.is_offer_party {
+            return Err(Error::InvalidState(
+                "Cannot receive a reject for a contract offer not sent by us.".to_string(),
+            ));
+        }
 
-            let preclosed_contract = PreClosedContract {
-                signed_contract: contract.clone(),
-                attestations: Some(attestations),
-                signed_cet,
-            };
+        self.unreserve_offer_inputs(&offered_contract).await?;
+        self.store
+            .update_contract(&Contract::Rejected(offered_contract))?;
+        self.push_events(Some(Event::OfferRejected {
+            temporary_contract_id: reject_offer.temporary_contract_id,
+            error_code: reject_offer.error_code,
+            reason: reject_offer.reason.clone(),
+        }))?;
 
-            return Ok(Contract::PreClosed(preclosed_contract));
-        } else if confirmations < NB_CONFIRMATIONS {
-            let preclosed_contract = PreClosedContract {
-                signed_contract: contract.clone(),
-                attestations: Some(attestations),
-                signed_cet,
-            };
+        Ok(())
+    }
 
-            return Ok(Contract::PreClosed(preclosed_contract));
+    async fn on_accept_message(
+        &self,
+        accept_msg: &AcceptDlc,
+        counter_party: &PublicKey,
+    ) -> Result<Option<DlcMessage>, Error> {
+        let mut offered_contract = get_contract_in_state!(
+            self,
+            &accept_msg.temporary_contract_id,
+            Offered,
+            Some(*counter_party)
+        )?;
+
+        if offered_contract.batch_id.is_some() {
+            return Err(Error::InvalidState(
+                "Expected a batch accept message for a batch offer.".to_string(),
+            ));
         }
 
-        let closed_contract = ClosedContract {
-            attestations: Some(attestations.to_vec()),
-            pnl: contract.accepted_contract.compute_pnl(&signed_cet),
-            signed_cet: Some(signed_cet),
-            contract_id: contract.accepted_contract.get_contract_id(),
-            temporary_contract_id: contract.accepted_contract.offered_contract.id,
-            counter_party_id: contract.accepted_contract.offered_contract.counter_party,
+        if offered_contract.replaced_contract_id.is_some() {
+            return Err(Error::InvalidState(
+                "Expected a fee bump accept message for a fee bump offer.".to_string(),
+            ));
+        }
+
+        if offered_contract.rolled_over_contract_id.is_some() {
+            return Err(Error::InvalidState(
+                "Expected a rollover accept message for a rollover offer.".to_string(),
+            ));
+        }
+
+        if offered_contract.is_expired(self.time.unix_time_now()) {
+            return Err(Error::InvalidState(
+                "Received an accept message for an expired contract offer.".to_string(),
+            ));
+        }
+
+        // The secret nonces of a contract funded with a taproot output are
+        // deleted from the storage before being used, so that each of them
+        // produces at most one partial signature even if signing fails or the
+        // process stops before the signed contract is stored.
+        let taproot_secret_nonces = offered_contract.taproot_secret_nonces.take();
+        if taproot_secret_nonces.is_some() {
+            self.store
+                .update_contract(&Contract::Offered(offered_contract.clone()))?;
+        }
+
+        let (signed_contract, signed_msg) = match verify_accepted_and_sign_contract(
+            &self.secp,
+            &offered_contract,
+            taproot_secret_nonces,
+            accept_msg,
+            &self.wallet,
+            &self.signer_provider,
+        )
+        .await
+        {
+            Ok(contract) => contract,
+            Err(e) => return self.accept_fail_on_error(offered_contract, accept_msg.clone(), e),
         };
 
-        Ok(Contract::Closed(closed_contract))
-    }
+        self.wallet
+            .import_address(&get_fund_address(
+                &signed_contract.accepted_contract.dlc_transactions,
+                self.blockchain.get_network().await?,
+            )?)
+            .await?;
 
-    fn check_refund(&self, contract: &SignedContract) -> Result<(), Error> {
-        // TODO(tibo): should check for confirmation of refund before updating state
-        if contract
+        // The sign message can only be sent once the fee payer has signed its
+        // inputs, so the contract remains accepted until then.
+        if signed_contract
             .accepted_contract
-            .dlc_transactions
-            .refund
-            .lock_time
-            .to_consensus_u32() as u64
-            <= self.time.unix_time_now()
+            .offered_contract
+            .fee_payer
+            .is_some()
         {
-            let accepted_contract = &contract.accepted_contract;
-            let refund = accepted_contract.dlc_transactions.refund.clone();
-            let confirmations = self
-                .blockchain
-                .get_transaction_confirmations(&refund.txid())?;
-            if confirmations == 0 {
-                let offer = &contract.accepted_contract.offered_contract;
-                let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
-                let refund =
-                    crate::contract_updater::get_signed_refund(&self.secp, contract, &signer)?;
-                self.blockchain.send_transaction(&refund)?;
-            }
-
             self.store
-                .update_contract(&Contract::Refunded(contract.clone()))?;
+                .update_contract(&Contract::Accepted(signed_contract.accepted_contract))?;
+            return Ok(None);
         }
 
-        Ok(())
-    }
+        self.store
+            .update_contract(&Contract::Signed(signed_contract))?;
 
-    /// Function to call when we detect that a contract was closed by our counter party.
-    /// This will update the state of the contract and return the [`Contract`] object.
-    pub fn on_counterparty_close(
-        &mut self,
-        contract: &SignedContract,
-        closing_tx: Transaction,
-        confirmations: u32,
-    ) -> Result<Contract, Error> {
-        // check if the closing tx actually spends the funding output
-        if !closing_tx.input.iter().any(|i| {
-            i.previous_output
-                == contract
-                    .accepted_contract
-                    .dlc_transactions
-                    .get_fund_outpoint()
-        }) {
-            return Err(Error::InvalidParameters(
-                "Closing tx does not spend the funding tx".to_string(),
-            ));
-        }
+        Ok(Some(DlcMessage::Sign(signed_msg)))
+    }
 
-        // check if it is the refund tx (easy case)
-        if contract.accepted_contract.dlc_transactions.refund.txid() == closing_tx.txid() {
-            let refunded = Contract::Refunded(contract.clone());
-            self.store.update_contract(&refunded)?;
-            return Ok(refunded);
-        }
+    /// Function called to offer to replace the funding transaction of the given
+    /// signed contract with one paying the given fee rate. The offered contract
+    /// will be stored and a FeeBumpOffer message returned.
+    pub fn send_fee_bump_offer(
+        &self,
+        contract_id: &ContractId,
+        fee_rate_per_vb: u64,
+    ) -> Result<(FeeBumpOffer, PublicKey), Error> {
+        let signed_contract =
+            get_contract_in_state!(self, contract_id, Signed, None as Option<PublicKey>)?;
 
-        let contract = if confirmations < NB_CONFIRMATIONS {
-            Contract::PreClosed(PreClosedContract {
-                signed_contract: contract.clone(),
-                attestations: None, // todo in some cases we can get the attestations from the closing tx
-                signed_cet: closing_tx,
-            })
-        } else {
-            Contract::Closed(ClosedContract {
-                attestations: None, // todo in some cases we can get the attestations from the closing tx
-                pnl: contract.accepted_contract.compute_pnl(&closing_tx),
-                signed_cet: Some(closing_tx),
-                contract_id: contract.accepted_contract.get_contract_id(),
-                temporary_contract_id: contract.accepted_contract.offered_contract.id,
-                counter_party_id: contract.accepted_contract.offered_contract.counter_party,
-            })
-        };
+        let (offered_contract, offer_msg) =
+            crate::contract_updater::offer_fee_bump(&signed_contract, fee_rate_per_vb)?;
 
-        self.store.update_contract(&contract)?;
+        let counter_party = offered_contract.counter_party;
+        self.store.create_contract(&offered_contract)?;
 
-        Ok(contract)
+        Ok((offer_msg, counter_party))
     }
-}
 
-impl<W: Deref, SP: Deref, B: Deref, S: Deref, O: Deref, T: Deref, F: Deref, X: ContractSigner>
-    Manager<W, Arc<CachedContractSignerProvider<SP, X>>, B, S, O, T, F, X>
-where
-    W::Target: Wallet,
-    SP::Target: ContractSignerProvider<Signer = X>,
-    B::Target: Blockchain,
-    S::Target: Storage,
-    O::Target: Oracle,
-    T::Target: Time,
-    F::Target: FeeEstimator,
-{
-    /// Create a new channel offer and return the [`dlc_messages::channel::OfferChannel`]
-    /// message to be sent to the `counter_party`.
-    pub fn offer_channel(
+    /// Function to call to accept a fee bump offer for which a FeeBumpOffer
+    /// message was received.
+    pub fn accept_fee_bump_offer(
         &self,
-        contract_input: &ContractInput,
-        counter_party: PublicKey,
-    ) -> Result<OfferChannel, Error> {
-        let oracle_announcements = contract_input
-            .contract_infos
-            .iter()
-            .map(|x| self.get_oracle_announcements(&x.oracles))
-            .collect::<Result<Vec<_>, Error>>()?;
+        contract_id: &ContractId,
+    ) -> Result<(ContractId, PublicKey, FeeBumpAccept), Error> {
+        let offered_contract =
+            get_contract_in_state!(self, contract_id, Offered, None as Option<PublicKey>)?;
 
-        let (offered_channel, offered_contract) = crate::channel_updater::offer_channel(
+        let replaced_contract_id = offered_contract.replaced_contract_id.ok_or_else(|| {
+            Error::InvalidState("Contract offer is not a fee bump offer.".to_string())
+        })?;
+
+        let replaced_contract = get_contract_in_state!(
+            self,
+            &replaced_contract_id,
+            Signed,
+            Some(offered_contract.counter_party)
+        )?;
+
+        let (accepted_contract, accept_msg) = accept_fee_bump(
             &self.secp,
-            contract_input,
-            &counter_party,
-            &oracle_announcements,
-            CET_NSEQUENCE,
-            REFUND_DELAY,
-            &self.wallet,
+            &offered_contract,
+            &replaced_contract,
             &self.signer_provider,
-            &self.blockchain,
-            &self.time,
-            crate::utils::get_new_temporary_id(),
         )?;
 
-        let msg = offered_channel.get_offer_channel_msg(&offered_contract);
+        let contract_id = accepted_contract.get_contract_id();
+        let counter_party = offered_contract.counter_party;
 
-        self.store.upsert_channel(
-            Channel::Offered(offered_channel),
-            Some(Contract::Offered(offered_contract)),
-        )?;
+        self.store
+            .update_contract(&Contract::Accepted(accepted_contract))?;
 
-        Ok(msg)
+        Ok((contract_id, counter_party, accept_msg))
     }
 
-    /// Reject a channel that was offered. Returns the [`dlc_messages::channel::Reject`]
-    /// message to be sent as well as the public key of the offering node.
-    pub fn reject_channel(&self, channel_id: &ChannelId) -> Result<(Reject, PublicKey), Error> {
-        let offered_channel =
-            get_channel_in_state!(self, channel_id, Offered, None as Option<PublicKey>)?;
+    fn on_fee_bump_offer(
+        &self,
+        offer_msg: &FeeBumpOffer,
+        counter_party: PublicKey,
+    ) -> Result<(), Error> {
+        let replaced_contract =
+            get_contract_in_state!(self, &offer_msg.contract_id, Signed, Some(counter_party))?;
 
-        if offered_channel.is_offer_party {
-            return Err(Error::InvalidState(
-                "Cannot reject channel initiated by us.".to_string(),
+        let contract = crate::contract_updater::on_fee_bump_offer(&replaced_contract, offer_msg)?;
+
+        if self.store.get_contract(&contract.id)?.is_some() {
+            return Err(Error::InvalidParameters(
+                "Contract with identical id already exists".to_string(),
             ));
         }
 
+        self.store.create_contract(&contract)?;
+
+        Ok(())
+    }
+
+    async fn on_fee_bump_accept(
+        &self,
+        accept_msg: &FeeBumpAccept,
+        counter_party: &PublicKey,
+    ) -> Result<SignDlc, Error> {
         let offered_contract = get_contract_in_state!(
             self,
-            &offered_channel.offered_contract_id,
+            &accept_msg.temporary_contract_id,
             Offered,
-            None as Option<PublicKey>
+            Some(*counter_party)
         )?;
 
-        let counterparty = offered_channel.counter_party;
-        self.store.upsert_channel(
-            Channel::Cancelled(offered_channel),
-            Some(Contract::Rejected(offered_contract)),
-        )?;
+        let replaced_contract_id = offered_contract.replaced_contract_id.ok_or_else(|| {
+            Error::InvalidState("Contract offer is not a fee bump offer.".to_string())
+        })?;
 
-        let msg = Reject {
-            channel_id: *channel_id,
-        };
-        Ok((msg, counterparty))
+        let replaced_contract =
+            get_contract_in_state!(self, &replaced_contract_id, Signed, Some(*counter_party))?;
+
+        let (signed_contract, signed_msg) = verify_fee_bump_accept_and_sign(
+            &self.secp,
+            &offered_contract,
+            &replaced_contract,
+            accept_msg,
+            &self.wallet,
+            &self.signer_provider,
+        )
+        .await?;
+
+        self.store
+            .update_contract(&Contract::Signed(signed_contract))?;
+
+        Ok(signed_msg)
     }
 
-    /// Accept a channel that was offered. Returns the [`dlc_messages::channel::AcceptChannel`]
-    /// message to be sent, the updated [`crate::ChannelId`] and [`crate::ContractId`],
-    /// as well as the public key of the offering node.
-    pub fn accept_channel(
+    /// Function called to offer to collaboratively close the given confirmed
+    /// contract, giving `counter_payout` to the counter party. The offer is
+    /// recorded in the contract and a CloseOffer message returned.
+    pub fn offer_contract_close(
         &self,
-        channel_id: &ChannelId,
-    ) -> Result<(AcceptChannel, ChannelId, ContractId, PublicKey), Error> {
-        let offered_channel =
-            get_channel_in_state!(self, channel_id, Offered, None as Option<PublicKey>)?;
-
-        if offered_channel.is_offer_party {
-            return Err(Error::InvalidState(
-                "Cannot accept channel initiated by us.".to_string(),
-            ));
-        }
+        contract_id: &ContractId,
+        counter_payout: u64,
+    ) -> Result<(CloseOffer, PublicKey), Error> {
+        let mut signed_contract =
+            get_contract_in_state!(self, contract_id, Confirmed, None as Option<PublicKey>)?;
 
-        let offered_contract = get_contract_in_state!(
-            self,
-            &offered_channel.offered_contract_id,
-            Offered,
-            None as Option<PublicKey>
+        let close_offer = crate::contract_updater::offer_contract_close(
+            &self.secp,
+            &mut signed_contract,
+            counter_payout,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
         )?;
 
-        let (accepted_channel, accepted_contract, accept_channel) =
-            crate::channel_updater::accept_channel_offer(
-                &self.secp,
-                &offered_channel,
-                &offered_contract,
-                &self.wallet,
-                &self.signer_provider,
-                &self.blockchain,
-            )?;
+        let counter_party = signed_contract
+            .accepted_contract
+            .offered_contract
+            .counter_party;
 
-        self.wallet.import_address(&Address::p2wsh(
-            &accepted_contract.dlc_transactions.funding_script_pubkey,
-            self.blockchain.get_network()?,
-        ))?;
+        self.store
+            .update_contract(&Contract::Confirmed(signed_contract))?;
 
-        let channel_id = accepted_channel.channel_id;
-        let contract_id = accepted_contract.get_contract_id();
-        let counter_party = accepted_contract.offered_contract.counter_party;
+        Ok((close_offer, counter_party))
+    }
 
-        self.store.upsert_channel(
-            Channel::Accepted(accepted_channel),
-            Some(Contract::Accepted(accepted_contract)),
-        )?;
+    /// Function to call to accept an offer to collaboratively close the given
+    /// contract for which a CloseOffer message was received. The close
+    /// transaction is broadcast and the contract moved to the collaboratively
+    /// closed state.
+    pub async fn accept_contract_close(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<(CloseAccept, PublicKey), Error> {
+        let signed_contract =
+            get_contract_in_state!(self, contract_id, Confirmed, None as Option<PublicKey>)?;
 
-        Ok((accept_channel, channel_id, contract_id, counter_party))
-    }
+        let (close_tx, closed_contract, accept_msg) =
+            crate::contract_updater::accept_contract_close(
+                &self.secp,
+                &signed_contract,
+                &self.signer_provider,
+                &self.time,
+            )?;
 
-    /// Force close the channel with given [`crate::ChannelId`].
-    pub fn force_close_channel(&self, channel_id: &ChannelId) -> Result<(), Error> {
-        let channel = get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        self.blockchain.send_transaction(&close_tx).await?;
+
+        let closed_contract = Contract::CollaborativelyClosed(closed_contract);
+        self.store.update_contract(&closed_contract)?;
+        self.push_events(Event::from_closed_contract(&closed_contract))?;
 
-        self.force_close_channel_internal(channel, true)
+        Ok((
+            accept_msg,
+            signed_contract
+                .accepted_contract
+                .offered_contract
+                .counter_party,
+        ))
     }
 
-    /// Offer to settle the balance of a channel so that the counter party gets
-    /// `counter_payout`. Returns the [`dlc_messages::channel::SettleChannelOffer`]
-    /// message to be sent and the public key of the counter party node.
-    pub fn settle_offer(
+    fn on_close_offer(
         &self,
-        channel_id: &ChannelId,
-        counter_payout: u64,
-    ) -> Result<(SettleOffer, PublicKey), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        close_offer: &CloseOffer,
+        counter_party: &PublicKey,
+    ) -> Result<(), Error> {
+        let mut signed_contract = get_contract_in_state!(
+            self,
+            &close_offer.contract_id,
+            Confirmed,
+            Some(*counter_party)
+        )?;
 
-        let msg = crate::channel_updater::settle_channel_offer(
+        crate::contract_updater::on_close_offer(
             &self.secp,
-            &mut signed_channel,
-            counter_payout,
-            PEER_TIMEOUT,
-            &self.signer_provider,
+            &mut signed_contract,
+            close_offer,
+            self.config.peer_timeout,
             &self.time,
         )?;
 
-        let counter_party = signed_channel.counter_party;
-
         self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+            .update_contract(&Contract::Confirmed(signed_contract))?;
 
-        Ok((msg, counter_party))
+        Ok(())
     }
 
-    /// Accept a settlement offer, returning the [`SettleAccept`] message to be
-    /// sent to the node with the returned [`PublicKey`] id.
-    pub fn accept_settle_offer(
+    async fn on_close_accept(
         &self,
-        channel_id: &ChannelId,
-    ) -> Result<(SettleAccept, PublicKey), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        close_accept: &CloseAccept,
+        counter_party: &PublicKey,
+    ) -> Result<(), Error> {
+        let signed_contract = get_contract_in_state!(
+            self,
+            &close_accept.contract_id,
+            Confirmed,
+            Some(*counter_party)
+        )?;
 
-        let msg = crate::channel_updater::settle_channel_accept(
+        let (close_tx, closed_contract) = crate::contract_updater::on_close_accept(
             &self.secp,
-            &mut signed_channel,
-            CET_NSEQUENCE,
-            0,
-            PEER_TIMEOUT,
+            &signed_contract,
+            close_accept,
             &self.signer_provider,
-            &self.time,
-            &self.chain_monitor,
         )?;
 
-        let counter_party = signed_channel.counter_party;
+        // The counter party is expected to have already broadcast the close
+        // transaction, so failing to broadcast it here is not an error.
+        if let Err(e) = self.blockchain.send_transaction(&close_tx).await {
+            warn!(
+                "Could not broadcast close transaction {}: {}",
+                close_tx.txid(),
+                e
+            );
+        }
 
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+        let closed_contract = Contract::CollaborativelyClosed(closed_contract);
+        self.store.update_contract(&closed_contract)?;
+        self.push_events(Event::from_closed_contract(&closed_contract))?;
 
-        Ok((msg, counter_party))
+        Ok(())
     }
 
-    /// Returns a [`RenewOffer`] message as well as the [`PublicKey`] of the
-    /// counter party's node to offer the establishment of a new contract in the
-    /// channel.
-    pub fn renew_offer(
+    /// Function called to offer to roll the given confirmed contract over into
+    /// a new contract created from the given contract input, whose CETs and
+    /// refund transaction spend the same fund output. The sum of the collaterals
+    /// in the contract input must equal the total collateral of the rolled over
+    /// contract, and its fee rate is ignored as the fees of the new CETs are
+    /// paid by the fund output. The offered contract will be stored and a
+    /// RolloverOffer message returned.
+    pub async fn offer_contract_rollover(
         &self,
-        channel_id: &ChannelId,
-        counter_payout: u64,
+        contract_id: &ContractId,
         contract_input: &ContractInput,
-    ) -> Result<(RenewOffer, PublicKey), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+    ) -> Result<(RolloverOffer, PublicKey), Error> {
+        let signed_contract =
+            get_contract_in_state!(self, contract_id, Confirmed, None as Option<PublicKey>)?;
 
-        let oracle_announcements = contract_input
-            .contract_infos
-            .iter()
-            .map(|x| self.get_oracle_announcements(&x.oracles))
-            .collect::<Result<Vec<_>, Error>>()?;
+        let oracle_announcements = self
+            .get_contract_oracle_announcements(contract_input)
+            .await?;
 
-        let (msg, offered_contract) = crate::channel_updater::renew_offer(
-            &self.secp,
-            &mut signed_channel,
+        let (offered_contract, offer_msg) = crate::contract_updater::offer_contract_rollover(
+            &signed_contract,
             contract_input,
             oracle_announcements,
-            counter_payout,
-            REFUND_DELAY,
-            PEER_TIMEOUT,
-            CET_NSEQUENCE,
-            &self.signer_provider,
+            self.config.refund_delay,
             &self.time,
         )?;
 
         let counter_party = offered_contract.counter_party;
+        self.store.create_contract(&offered_contract)?;
 
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Offered(offered_contract)),
-        )?;
-
-        Ok((msg, counter_party))
+        Ok((offer_msg, counter_party))
     }
 
-    /// Accept an offer to renew the contract in the channel. Returns the
-    /// [`RenewAccept`] message to be sent to the peer with the returned
-    /// [`PublicKey`] as node id.
-    pub fn accept_renew_offer(
+    /// Function to call to accept a rollover offer for which a RolloverOffer
+    /// message was received.
+    pub fn accept_contract_rollover(
         &self,
-        channel_id: &ChannelId,
-    ) -> Result<(RenewAccept, PublicKey), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
-        let offered_contract_id = signed_channel.get_contract_id().ok_or_else(|| {
-            Error::InvalidState("Expected to have a contract id but did not.".to_string())
-        })?;
+        contract_id: &ContractId,
+    ) -> Result<(ContractId, PublicKey, RolloverAccept), Error> {
+        let offered_contract =
+            get_contract_in_state!(self, contract_id, Offered, None as Option<PublicKey>)?;
 
-        let offered_contract = get_contract_in_state!(
+        let rolled_over_contract_id =
+            offered_contract.rolled_over_contract_id.ok_or_else(|| {
+                Error::InvalidState("Contract offer is not a rollover offer.".to_string())
+            })?;
+
+        let rolled_over_contract = get_contract_in_state!(
             self,
-            &offered_contract_id,
-            Offered,
-            None as Option<PublicKey>
+            &rolled_over_contract_id,
+            Confirmed,
+            Some(offered_contract.counter_party)
         )?;
 
-        let (accepted_contract, msg) = crate::channel_updater::accept_channel_renewal(
+        let (accepted_contract, accept_msg) = crate::contract_updater::accept_contract_rollover(
             &self.secp,
-            &mut signed_channel,
             &offered_contract,
-            CET_NSEQUENCE,
-            PEER_TIMEOUT,
+            &rolled_over_contract,
             &self.signer_provider,
-            &self.time,
         )?;
 
-        let counter_party = signed_channel.counter_party;
+        let contract_id = accepted_contract.get_contract_id();
+        let counter_party = offered_contract.counter_party;
 
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Accepted(accepted_contract)),
-        )?;
+        self.store
+            .update_contract(&Contract::Accepted(accepted_contract))?;
 
-        Ok((msg, counter_party))
+        Ok((contract_id, counter_party, accept_msg))
     }
 
-    /// Reject an offer to renew the contract in the channel. Returns the
-    /// [`Reject`] message to be sent to the peer with the returned
-    /// [`PublicKey`] node id.
-    pub fn reject_renew_offer(&self, channel_id: &ChannelId) -> Result<(Reject, PublicKey), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
-        let offered_contract_id = signed_channel.get_contract_id().ok_or_else(|| {
-            Error::InvalidState(
-                "Expected to be in a state with an associated contract id but was not.".to_string(),
-            )
-        })?;
+    fn on_rollover_offer(
+        &self,
+        offer_msg: &RolloverOffer,
+        counter_party: &PublicKey,
+    ) -> Result<(), Error> {
+        offer_msg.validate(
+            &self.secp,
+            self.config.min_refund_delay,
+            self.config.max_refund_delay,
+        )?;
 
-        let offered_contract = get_contract_in_state!(
+        let rolled_over_contract = get_contract_in_state!(
             self,
-            &offered_contract_id,
-            Offered,
-            None as Option<PublicKey>
+            &offer_msg.contract_id,
+            Confirmed,
+            Some(*counter_party)
         )?;
 
-        let reject_msg = crate::channel_updater::reject_renew_offer(&mut signed_channel)?;
+        let contract =
+            crate::contract_updater::on_rollover_offer(&rolled_over_contract, offer_msg)?;
 
-        let counter_party = signed_channel.counter_party;
+        if self.store.get_contract(&contract.id)?.is_some() {
+            return Err(Error::InvalidParameters(
+                "Contract with identical id already exists".to_string(),
+            ));
+        }
 
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Rejected(offered_contract)),
-        )?;
+        self.store.create_contract(&contract)?;
 
-        Ok((reject_msg, counter_party))
+        Ok(())
     }
 
-    /// Returns a [`Reject`] message to be sent to the counter party of the
-    /// channel to inform them that the local party does not wish to accept the
-    /// proposed settle offer.
-    pub fn reject_settle_offer(
+    fn on_rollover_accept(
         &self,
-        channel_id: &ChannelId,
-    ) -> Result<(Reject, PublicKey), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        accept_msg: &RolloverAccept,
+        counter_party: &PublicKey,
+    ) -> Result<RolloverConfirm, Error> {
+        let offered_contract = get_contract_in_state!(
+            self,
+            &accept_msg.temporary_contract_id,
+            Offered,
+            Some(*counter_party)
+        )?;
 
-        let msg = crate::channel_updater::reject_settle_offer(&mut signed_channel)?;
+        let rolled_over_contract_id =
+            offered_contract.rolled_over_contract_id.ok_or_else(|| {
+                Error::InvalidState("Contract offer is not a rollover offer.".to_string())
+            })?;
 
-        let counter_party = signed_channel.counter_party;
+        let rolled_over_contract = get_contract_in_state!(
+            self,
+            &rolled_over_contract_id,
+            Confirmed,
+            Some(*counter_party)
+        )?;
+
+        let (signed_contract, confirm_msg) =
+            crate::contract_updater::verify_rollover_accept_and_confirm(
+                &self.secp,
+                &offered_contract,
+                &rolled_over_contract,
+                accept_msg,
+                &self.signer_provider,
+            )?;
 
         self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+            .roll_over_contract(&rolled_over_contract, &Contract::Confirmed(signed_contract))?;
 
-        Ok((msg, counter_party))
+        Ok(confirm_msg)
     }
 
-    /// Returns a [`CollaborativeCloseOffer`] message to be sent to the counter
-    /// party of the channel and update the state of the channel. Note that the
-    /// channel will be forced closed after a timeout if the counter party does
-    /// not broadcast the close transaction.
-    pub fn offer_collaborative_close(
+    fn on_rollover_confirm(
         &self,
-        channel_id: &ChannelId,
-        counter_payout: u64,
-    ) -> Result<CollaborativeCloseOffer, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        confirm_msg: &RolloverConfirm,
+        counter_party: &PublicKey,
+    ) -> Result<(), Error> {
+        let accepted_contract = get_contract_in_state!(
+            self,
+            &confirm_msg.contract_id,
+            Accepted,
+            Some(*counter_party)
+        )?;
 
-        let (msg, close_tx) = crate::channel_updater::offer_collaborative_close(
-            &self.secp,
-            &mut signed_channel,
-            counter_payout,
-            &self.signer_provider,
-            &self.time,
+        let rolled_over_contract_id = accepted_contract
+            .offered_contract
+            .rolled_over_contract_id
+            .ok_or_else(|| {
+                Error::InvalidState("Contract is not a rollover contract.".to_string())
+            })?;
+
+        let rolled_over_contract = get_contract_in_state!(
+            self,
+            &rolled_over_contract_id,
+            Confirmed,
+            Some(*counter_party)
         )?;
 
-        self.chain_monitor.lock().unwrap().add_tx(
-            close_tx.txid(),
-            ChannelInfo {
-                channel_id: *channel_id,
-                tx_type: TxType::CollaborativeClose,
-            },
-        );
+        let signed_contract = crate::contract_updater::verify_rollover_confirm(
+            &self.secp,
+            &accepted_contract,
+            confirm_msg,
+        )?;
 
         self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+            .roll_over_contract(&rolled_over_contract, &Contract::Confirmed(signed_contract))?;
 
-        Ok(msg)
+        Ok(())
     }
 
-    /// Accept an offer to collaboratively close the channel. The close transaction
-    /// will be broadcast and the state of the channel updated.
-    pub fn accept_collaborative_close(&self, channel_id: &ChannelId) -> Result<(), Error> {
-        let signed_channel =
-            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+    /// Returns a PSBT of the funding transaction of the given accepted contract
+    /// to be signed by the fee payer of the contract.
+    pub fn get_fee_payer_psbt(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<PartiallySignedTransaction, Error> {
+        let accepted_contract =
+            get_contract_in_state!(self, contract_id, Accepted, None as Option<PublicKey>)?;
 
-        let closed_contract = if let Some(SignedChannelState::Established {
-            signed_contract_id,
-            ..
-        }) = &signed_channel.roll_back_state
-        {
-            let counter_payout = get_signed_channel_state!(
-                signed_channel,
-                CollaborativeCloseOffered,
-                counter_payout
-            )?;
-            Some(self.get_collaboratively_closed_contract(
-                signed_contract_id,
-                *counter_payout,
-                true,
-            )?)
-        } else {
-            None
-        };
+        crate::contract_updater::get_fee_payer_psbt(&accepted_contract)
+    }
 
-        let (close_tx, closed_channel) = crate::channel_updater::accept_collaborative_close_offer(
-            &self.secp,
-            &signed_channel,
-            &self.signer_provider,
-        )?;
+    /// Function to call with the PSBT signed by the fee payer of the given
+    /// accepted contract, to generate the sign message to send to the counter
+    /// party.
+    pub async fn sign_with_fee_payer_psbt(
+        &self,
+        contract_id: &ContractId,
+        fee_payer_psbt: &PartiallySignedTransaction,
+    ) -> Result<(SignDlc, PublicKey), Error> {
+        let accepted_contract =
+            get_contract_in_state!(self, contract_id, Accepted, None as Option<PublicKey>)?;
 
-        self.blockchain.send_transaction(&close_tx)?;
+        if !accepted_contract.offered_contract.is_offer_party {
+            return Err(Error::InvalidState(
+                "Only the offer party can provide the fee payer signatures.".to_string(),
+            ));
+        }
 
-        self.store.upsert_channel(closed_channel, None)?;
+        let (signed_contract, signed_msg) = sign_contract_with_fee_payer_psbt(
+            &self.secp,
+            &accepted_contract,
+            fee_payer_psbt,
+            &self.wallet,
+            &self.signer_provider,
+        )
+        .await?;
 
-        if let Some(closed_contract) = closed_contract {
-            self.store
-                .update_contract(&Contract::Closed(closed_contract))?;
-        }
+        let counter_party = signed_contract
+            .accepted_contract
+            .offered_contract
+            .counter_party;
+        self.store
+            .update_contract(&Contract::Signed(signed_contract))?;
 
-        Ok(())
+        Ok((signed_msg, counter_party))
     }
 
-    fn try_finalize_closing_established_channel(
+    async fn on_sign_message(
         &self,
-        signed_channel: SignedChannel,
+        sign_message: &SignDlc,
+        peer_id: &PublicKey,
     ) -> Result<(), Error> {
-        let (buffer_tx, contract_id, &is_initiator) = get_signed_channel_state!(
-            signed_channel,
-            Closing,
-            buffer_transaction,
-            contract_id,
-            is_initiator
-        )?;
-
-        if self
-            .blockchain
-            .get_transaction_confirmations(&buffer_tx.txid())?
-            >= CET_NSEQUENCE
-        {
-            log::info!(
-                "Buffer transaction for contract {} has enough confirmations to spend from it",
-                serialize_hex(&contract_id)
-            );
-
-            let confirmed_contract =
-                get_contract_in_state!(self, &contract_id, Confirmed, None as Option<PublicKey>)?;
+        let accepted_contract =
+            get_contract_in_state!(self, &sign_message.contract_id, Accepted, Some(*peer_id))?;
 
-            let (contract_info, adaptor_info, attestations) = self
-                .get_closable_contract_info(&confirmed_contract)
-                .ok_or_else(|| {
-                    Error::InvalidState("Could not get information to close contract".to_string())
-                })?;
+        if accepted_contract.offered_contract.batch_id.is_some() {
+            return Err(Error::InvalidState(
+                "Expected a batch sign message for a batch contract.".to_string(),
+            ));
+        }
 
-            let (signed_cet, closed_channel) =
-                crate::channel_updater::finalize_unilateral_close_settled_channel(
-                    &self.secp,
-                    &signed_channel,
-                    &confirmed_contract,
-                    contract_info,
-                    &attestations,
-                    adaptor_info,
-                    &self.signer_provider,
-                    is_initiator,
-                )?;
+        if accepted_contract
+            .offered_contract
+            .rolled_over_contract_id
+            .is_some()
+        {
+            return Err(Error::InvalidState(
+                "Expected a rollover confirm message for a rollover contract.".to_string(),
+            ));
+        }
 
-            let closed_contract = self.close_contract(
-                &confirmed_contract,
-                signed_cet,
-                attestations.iter().map(|x| &x.1).cloned().collect(),
-            )?;
+        let (signed_contract, fund_tx) = match crate::contract_updater::verify_signed_contract(
+            &self.secp,
+            &accepted_contract,
+            sign_message,
+            &self.wallet,
+        )
+        .await
+        {
+            Ok(contract) => contract,
+            Err(e) => return self.sign_fail_on_error(accepted_contract, sign_message.clone(), e),
+        };
 
-            self.chain_monitor
-                .lock()
-                .unwrap()
-                .cleanup_channel(signed_channel.channel_id);
+        self.store
+            .update_contract(&Contract::Signed(signed_contract))?;
 
-            self.store
-                .upsert_channel(closed_channel, Some(closed_contract))?;
-        }
+        self.blockchain.send_transaction(&fund_tx).await?;
 
         Ok(())
     }
 
-    fn on_offer_channel(
+    fn on_offer_batch_message(
         &self,
-        offer_channel: &OfferChannel,
+        offer_msg: &OfferBatchDlc,
         counter_party: PublicKey,
     ) -> Result<(), Error> {
-        offer_channel.validate(
+        offer_msg.validate(
             &self.secp,
-            REFUND_DELAY,
-            REFUND_DELAY * 2,
-            CET_NSEQUENCE,
-            CET_NSEQUENCE * 2,
+            self.config.min_refund_delay,
+            self.config.max_refund_delay,
         )?;
 
-        let keys_id = self
-            .signer_provider
-            .derive_signer_key_id(false, offer_channel.temporary_contract_id);
-        let (channel, contract) =
-            OfferedChannel::from_offer_channel(offer_channel, counter_party, keys_id)?;
+        let offered_contracts = crate::contract_updater::get_offered_batch_contracts(
+            offer_msg,
+            counter_party,
+            &self.signer_provider,
+        )?;
 
-        contract.validate()?;
+        for contract in &offered_contracts {
+            contract.validate()?;
 
-        if self
-            .store
-            .get_channel(&channel.temporary_channel_id)?
-            .is_some()
-        {
-            return Err(Error::InvalidParameters(
-                "Channel with identical idea already in store".to_string(),
-            ));
+            if self.store.get_contract(&contract.id)?.is_some() {
+                return Err(Error::InvalidParameters(
+                    "Contract with identical id already exists".to_string(),
+                ));
+            }
         }
 
-        self.store
-            .upsert_channel(Channel::Offered(channel), Some(Contract::Offered(contract)))?;
+        for contract in &offered_contracts {
+            self.store.create_contract(contract)?;
+        }
 
         Ok(())
     }
 
-    fn on_accept_channel(
+    async fn on_accept_batch_message(
         &self,
-        accept_channel: &AcceptChannel,
-        peer_id: &PublicKey,
-    ) -> Result<SignChannel, Error> {
-        let offered_channel = get_channel_in_state!(
-            self,
-            &accept_channel.temporary_channel_id,
-            Offered,
-            Some(*peer_id)
-        )?;
-        let offered_contract = get_contract_in_state!(
-            self,
-            &offered_channel.offered_contract_id,
-            Offered,
-            Some(*peer_id)
-        )?;
-
-        let (signed_channel, signed_contract, sign_channel) = {
-            let res = crate::channel_updater::verify_and_sign_accepted_channel(
-                &self.secp,
-                &offered_channel,
-                &offered_contract,
-                accept_channel,
-                //TODO(tibo): this should be parameterizable.
-                CET_NSEQUENCE,
-                &self.wallet,
-                &self.signer_provider,
-                &self.chain_monitor,
-            );
+        accept_msg: &AcceptBatchDlc,
+        counter_party: &PublicKey,
+    ) -> Result<SignBatchDlc, Error> {
+        let offered_contracts =
+            self.get_batch_contract_offers(&accept_msg.temporary_batch_id, Some(*counter_party))?;
 
-            match res {
-                Ok(res) => res,
-                Err(e) => {
-                    let channel = crate::channel::FailedAccept {
-                        temporary_channel_id: accept_channel.temporary_channel_id,
-                        error_message: format!("Error validating accept channel: {}", e),
-                        accept_message: accept_channel.clone(),
-                        counter_party: *peer_id,
-                    };
-                    self.store
-                        .upsert_channel(Channel::FailedAccept(channel), None)?;
-                    return Err(e);
+        let (signed_contracts, sign_msg) = match verify_accepted_and_sign_batch_contract(
+            &self.secp,
+            &offered_contracts,
+            accept_msg,
+            &self.wallet,
+            &self.signer_provider,
+        )
+        .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                error!("Error in on_accept_batch {}", e);
+                for offered_contract in offered_contracts {
+                    let accept_message = accept_msg
+                        .accept_dlcs
+                        .iter()
+                        .find(|x| x.temporary_contract_id == offered_contract.id);
+                    if let Some(accept_message) = accept_message {
+                        self.store.update_contract(&Contract::FailedAccept(
+                            FailedAcceptContract {
+                                offered_contract,
+                                accept_message: accept_message.clone(),
+                                error_message: e.to_string(),
+                            },
+                        ))?;
+                    }
                 }
+                return Err(e);
             }
         };
 
-        self.wallet.import_address(&Address::p2wsh(
-            &signed_contract
-                .accepted_contract
-                .dlc_transactions
-                .funding_script_pubkey,
-            self.blockchain.get_network()?,
-        ))?;
+        for signed_contract in signed_contracts {
+            self.wallet
+                .import_address(&Address::p2wsh(
+                    &signed_contract
+                        .accepted_contract
+                        .dlc_transactions
+                        .funding_script_pubkey,
+                    self.blockchain.get_network().await?,
+                ))
+                .await?;
 
-        if let SignedChannelState::Established {
-            buffer_transaction, ..
-        } = &signed_channel.state
-        {
-            self.chain_monitor.lock().unwrap().add_tx(
-                buffer_transaction.txid(),
-                ChannelInfo {
-                    channel_id: signed_channel.channel_id,
-                    tx_type: TxType::BufferTx,
-                },
-            );
-        } else {
-            unreachable!();
+            self.store
+                .update_contract(&Contract::Signed(signed_contract))?;
         }
 
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Signed(signed_contract)),
-        )?;
-
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
-
-        Ok(sign_channel)
+        Ok(sign_msg)
     }
 
-    fn on_sign_channel(
+    async fn on_sign_batch_message(
         &self,
-        sign_channel: &SignChannel,
+        sign_msg: &SignBatchDlc,
         peer_id: &PublicKey,
     ) -> Result<(), Error> {
-        let accepted_channel =
-            get_channel_in_state!(self, &sign_channel.channel_id, Accepted, Some(*peer_id))?;
-        let accepted_contract = get_contract_in_state!(
-            self,
-            &accepted_channel.accepted_contract_id,
-            Accepted,
-            Some(*peer_id)
-        )?;
+        let mut accepted_contracts = sign_msg
+            .sign_dlcs
+            .iter()
+            .map(|s| get_contract_in_state!(self, &s.contract_id, Accepted, Some(*peer_id)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        accepted_contracts.sort_by_key(|x| x.offered_contract.id);
 
-        let (signed_channel, signed_contract, signed_fund_tx) = {
-            let res = verify_signed_channel(
+        let (signed_contracts, fund_tx) =
+            match crate::contract_updater::verify_signed_batch_contract(
                 &self.secp,
-                &accepted_channel,
-                &accepted_contract,
-                sign_channel,
+                &accepted_contracts,
+                sign_msg,
                 &self.wallet,
-                &self.chain_monitor,
-            );
-
-            match res {
+            )
+            .await
+            {
                 Ok(res) => res,
                 Err(e) => {
-                    let channel = crate::channel::FailedSign {
-                        channel_id: sign_channel.channel_id,
-                        error_message: format!("Error validating accept channel: {}", e),
-                        sign_message: sign_channel.clone(),
-                        counter_party: *peer_id,
-                    };
-                    self.store
-                        .upsert_channel(Channel::FailedSign(channel), None)?;
+                    error!("Error in on_sign_batch {}", e);
+                    for accepted_contract in accepted_contracts {
+                        let sign_message = sign_msg
+                            .sign_dlcs
+                            .iter()
+                            .find(|x| x.contract_id == accepted_contract.get_contract_id());
+                        if let Some(sign_message) = sign_message {
+                            self.store.update_contract(&Contract::FailedSign(
+                                FailedSignContract {
+                                    accepted_contract,
+                                    sign_message: sign_message.clone(),
+                                    error_message: e.to_string(),
+                                },
+                            ))?;
+                        }
+                    }
                     return Err(e);
                 }
-            }
-        };
+            };
 
-        if let SignedChannelState::Established {
-            buffer_transaction, ..
-        } = &signed_channel.state
-        {
-            self.chain_monitor.lock().unwrap().add_tx(
-                buffer_transaction.txid(),
-                ChannelInfo {
-                    channel_id: signed_channel.channel_id,
-                    tx_type: TxType::BufferTx,
-                },
-            );
-        } else {
-            unreachable!();
+        for signed_contract in signed_contracts {
+            self.store
+                .update_contract(&Contract::Signed(signed_contract))?;
         }
 
-        self.blockchain.send_transaction(&signed_fund_tx)?;
-
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Signed(signed_contract)),
-        )?;
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+        self.blockchain.send_transaction(&fund_tx).await?;
 
         Ok(())
     }
 
-    fn on_settle_offer(
+    /// Returns the offered contracts of the batch with the given id, ordered by
+    /// temporary id.
+    fn get_batch_contract_offers(
         &self,
-        settle_offer: &SettleOffer,
-        peer_id: &PublicKey,
-    ) -> Result<Option<Reject>, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &settle_offer.channel_id, Signed, Some(*peer_id))?;
+        temporary_batch_id: &[u8; 32],
+        counter_party: Option<PublicKey>,
+    ) -> Result<Vec<OfferedContract>, Error> {
+        let mut offered_contracts = self
+            .store
+            .get_contract_offers()?
+            .into_iter()
+            .filter(|x| x.batch_id.as_ref() == Some(temporary_batch_id))
+            .collect::<Vec<_>>();
 
-        if let SignedChannelState::SettledOffered { .. } = signed_channel.state {
-            return Ok(Some(Reject {
-                channel_id: settle_offer.channel_id,
-            }));
+        if offered_contracts.is_empty() {
+            return Err(Error::InvalidParameters("Unknown batch id.".to_string()));
         }
 
-        crate::channel_updater::on_settle_offer(&mut signed_channel, settle_offer)?;
+        if let Some(p) = counter_party {
+            if offered_contracts.iter().any(|x| x.counter_party != p) {
+                return Err(Error::InvalidParameters(format!(
+                    "Peer {:02x?} is not involved with batch {:02x?}.",
+                    p, temporary_batch_id
+                )));
+            }
+        }
 
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+        offered_contracts.sort_by_key(|x| x.id);
 
-        Ok(None)
+        Ok(offered_contracts)
     }
 
-    fn on_settle_accept(
+    async fn get_contract_oracle_announcements(
         &self,
-        settle_accept: &SettleAccept,
-        peer_id: &PublicKey,
-    ) -> Result<SettleConfirm, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &settle_accept.channel_id, Signed, Some(*peer_id))?;
-
-        let msg = crate::channel_updater::settle_channel_confirm(
-            &self.secp,
-            &mut signed_channel,
-            settle_accept,
-            CET_NSEQUENCE,
-            0,
-            PEER_TIMEOUT,
-            &self.signer_provider,
-            &self.time,
-            &self.chain_monitor,
-        )?;
-
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
-
-        Ok(msg)
+        contract_input: &ContractInput,
+    ) -> Result<Vec<Vec<OracleAnnouncement>>, Error> {
+        let mut oracle_announcements = Vec::with_capacity(contract_input.contract_infos.len());
+        for contract_info in &contract_input.contract_infos {
+            oracle_announcements.push(
+                self.get_oracle_announcements(&contract_info.oracles)
+                    .await?,
+            );
+        }
+        Ok(oracle_announcements)
     }
 
-    fn on_settle_confirm(
+    async fn get_oracle_announcements(
         &self,
-        settle_confirm: &SettleConfirm,
-        peer_id: &PublicKey,
-    ) -> Result<SettleFinalize, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &settle_confirm.channel_id, Signed, Some(*peer_id))?;
-        let &own_payout = get_signed_channel_state!(signed_channel, SettledAccepted, own_payout)?;
-        let (prev_buffer_tx, own_buffer_adaptor_signature, is_offer, signed_contract_id) = get_signed_channel_rollback_state!(
-            signed_channel,
-            Established,
-            buffer_transaction,
-            own_buffer_adaptor_signature,
-            is_offer,
-            signed_contract_id
-        )?;
-
-        let prev_buffer_txid = prev_buffer_tx.txid();
-        let own_buffer_adaptor_signature = *own_buffer_adaptor_signature;
-        let is_offer = *is_offer;
-        let signed_contract_id = *signed_contract_id;
-
-        let msg = crate::channel_updater::settle_channel_finalize(
-            &self.secp,
-            &mut signed_channel,
-            settle_confirm,
-            &self.signer_provider,
-        )?;
-
-        self.chain_monitor.lock().unwrap().add_tx(
-            prev_buffer_txid,
-            ChannelInfo {
-                channel_id: signed_channel.channel_id,
-                tx_type: TxType::Revoked {
-                    update_idx: signed_channel.update_idx + 1,
-                    own_adaptor_signature: own_buffer_adaptor_signature,
-                    is_offer,
-                    revoked_tx_type: RevokedTxType::Buffer,
-                },
-            },
-        );
+        oracle_inputs: &OracleInput,
+    ) -> Result<Vec<OracleAnnouncement>, Error> {
+        let mut announcements = Vec::new();
+        let is_multi_event = oracle_inputs.event_ids.len() > 1;
+        for event_id in &oracle_inputs.event_ids {
+            let mut nb_announcements = 0;
+            for pubkey in &oracle_inputs.public_keys {
+                let oracle = self.oracles.get(pubkey).ok_or_else(|| {
+                    Error::InvalidParameters("Unknown oracle public key".to_string())
+                })?;
+                match oracle.get_announcement(event_id).await {
+                    Ok(announcement) => {
+                        announcements.push(announcement);
+                        nb_announcements += 1;
+                    }
+                    // For multi event contracts, each oracle only needs to
+                    // attest some of the events.
+                    Err(e) if !is_multi_event => return Err(e),
+                    Err(_) => {}
+                }
+            }
+            if nb_announcements < oracle_inputs.threshold {
+                return Err(Error::InvalidParameters(format!(
+                    "Not enough oracle announcements for event {}.",
+                    event_id
+                )));
+            }
+        }
 
-        let closed_contract = Contract::Closed(self.get_collaboratively_closed_contract(
-            &signed_contract_id,
-            own_payout,
-            true,
-        )?);
+        Ok(announcements)
+    }
 
+    fn sign_fail_on_error<R>(
+        &self,
+        accepted_contract: AcceptedContract,
+        sign_message: SignDlc,
+        e: Error,
+    ) -> Result<R, Error> {
+        error!("Error in on_sign {}", e);
         self.store
-            .upsert_channel(Channel::Signed(signed_channel), Some(closed_contract))?;
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
-
-        Ok(msg)
+            .update_contract(&Contract::FailedSign(FailedSignContract {
+                accepted_contract,
+                sign_message,
+                error_message: e.to_string(),
+            }))?;
+        Err(e)
     }
 
-    fn on_settle_finalize(
+    fn accept_fail_on_error<R>(
         &self,
-        settle_finalize: &SettleFinalize,
-        peer_id: &PublicKey,
-    ) -> Result<(), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &settle_finalize.channel_id, Signed, Some(*peer_id))?;
-        let &own_payout = get_signed_channel_state!(signed_channel, SettledConfirmed, own_payout)?;
-        let (buffer_tx, own_buffer_adaptor_signature, is_offer, signed_contract_id) = get_signed_channel_rollback_state!(
-            signed_channel,
-            Established,
-            buffer_transaction,
-            own_buffer_adaptor_signature,
-            is_offer,
-            signed_contract_id
-        )?;
-
-        let own_buffer_adaptor_signature = *own_buffer_adaptor_signature;
-        let is_offer = *is_offer;
-        let buffer_txid = buffer_tx.txid();
-        let signed_contract_id = *signed_contract_id;
-
-        crate::channel_updater::settle_channel_on_finalize(
-            &self.secp,
-            &mut signed_channel,
-            settle_finalize,
-        )?;
-
-        self.chain_monitor.lock().unwrap().add_tx(
-            buffer_txid,
-            ChannelInfo {
-                channel_id: signed_channel.channel_id,
-                tx_type: TxType::Revoked {
-                    update_idx: signed_channel.update_idx + 1,
-                    own_adaptor_signature: own_buffer_adaptor_signature,
-                    is_offer,
-                    revoked_tx_type: RevokedTxType::Buffer,
-                },
-            },
-        );
-
-        let closed_contract = Contract::Closed(self.get_collaboratively_closed_contract(
-            &signed_contract_id,
-            own_payout,
-            true,
-        )?);
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), Some(closed_contract))?;
+        offered_contract: OfferedContract,
+        accept_message: AcceptDlc,
+        e: Error,
+    ) -> Result<R, Error> {
+        error!("Error in on_accept {}", e);
         self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+            .update_contract(&Contract::FailedAccept(FailedAcceptContract {
+                offered_contract,
+                accept_message,
+                error_message: e.to_string(),
+            }))?;
+        Err(e)
+    }
 
+    async fn check_signed_contract(&self, contract: &SignedContract) -> Result<(), Error> {
+        let confirmations = self
+            .blockchain
+            .get_transaction_confirmations(&contract.accepted_contract.dlc_transactions.fund.txid())
+            .await?;
+        if confirmations >= self.config.nb_confirmations {
+            self.store
+                .update_contract(&Contract::Confirmed(contract.clone()))?;
+            self.push_events(Some(Event::ContractConfirmed {
+                contract_id: contract.accepted_contract.get_contract_id(),
+            }))?;
+            self.remove_conflicting_contracts(contract)?;
+        }
         Ok(())
     }
 
-    fn on_renew_offer(
-        &self,
-        renew_offer: &RenewOffer,
-        peer_id: &PublicKey,
-    ) -> Result<Option<Reject>, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &renew_offer.channel_id, Signed, Some(*peer_id))?;
+    /// Removes the signed contracts whose funding transaction spends an input
+    /// of the funding transaction of the given confirmed contract, which is the
+    /// case for the other versions of a fee bumped funding transaction.
+    fn remove_conflicting_contracts(&self, contract: &SignedContract) -> Result<(), Error> {
+        let fund_tx = &contract.accepted_contract.dlc_transactions.fund;
+        let fund_txid = fund_tx.txid();
+        for c in self.store.get_signed_contracts()? {
+            let other_fund_tx = &c.accepted_contract.dlc_transactions.fund;
+            if other_fund_tx.txid() == fund_txid {
+                continue;
+            }
+            if other_fund_tx.input.iter().any(|x| {
+                fund_tx
+                    .input
+                    .iter()
+                    .any(|y| x.previous_output == y.previous_output)
+            }) {
+                self.store
+                    .delete_contract(&c.accepted_contract.get_contract_id())?;
+            }
+        }
+        Ok(())
+    }
 
-        // Received a renew offer when we already sent one, we reject it.
-        if let SignedChannelState::RenewOffered { is_offer, .. } = signed_channel.state {
-            if is_offer {
-                return Ok(Some(Reject {
-                    channel_id: renew_offer.channel_id,
-                }));
+    /// Cancels the contract offers whose expiry was reached, releasing the
+    /// funding inputs reserved for the ones sent by the local party.
+    async fn check_expired_offers(&self) -> Result<(), Error> {
+        let now = self.time.unix_time_now();
+        for offered_contract in self.store.get_contract_offers()? {
+            if !offered_contract.is_expired(now) {
+                continue;
+            }
+            let temporary_contract_id = offered_contract.id;
+            if let Err(e) = self.cancel_offered_contract(offered_contract).await {
+                error!(
+                    "Error cancelling expired contract offer {:02x?}: {}",
+                    temporary_contract_id, e
+                );
+                continue;
             }
+            self.push_events(Some(Event::OfferCancelled {
+                temporary_contract_id,
+            }))?;
         }
 
-        let offered_contract = crate::channel_updater::on_renew_offer(
-            &mut signed_channel,
-            renew_offer,
-            PEER_TIMEOUT,
-            &self.time,
-        )?;
+        Ok(())
+    }
 
-        self.store.create_contract(&offered_contract)?;
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+    async fn check_signed_contracts(&self) -> Result<(), Error> {
+        for c in self.store.get_signed_contracts()? {
+            if let Err(e) = self.check_signed_contract(&c).await {
+                error!(
+                    "Error checking confirmed contract {}: {}",
+                    c.accepted_contract.get_contract_id_string(),
+                    e
+                )
+            }
+        }
 
-        Ok(None)
+        Ok(())
     }
 
-    fn on_renew_accept(
-        &self,
-        renew_accept: &RenewAccept,
-        peer_id: &PublicKey,
-    ) -> Result<RenewConfirm, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &renew_accept.channel_id, Signed, Some(*peer_id))?;
-        let offered_contract_id = signed_channel.get_contract_id().ok_or_else(|| {
-            Error::InvalidState(
-                "Expected to be in a state with an associated contract id but was not.".to_string(),
-            )
-        })?;
-
-        let offered_contract =
-            get_contract_in_state!(self, &offered_contract_id, Offered, Some(*peer_id))?;
-
-        let (signed_contract, msg) = crate::channel_updater::verify_renew_accept_and_confirm(
-            &self.secp,
-            renew_accept,
-            &mut signed_channel,
-            &offered_contract,
-            CET_NSEQUENCE,
-            PEER_TIMEOUT,
-            &self.wallet,
-            &self.signer_provider,
-            &self.time,
-        )?;
+    async fn check_confirmed_contracts(&self) -> Result<(), Error> {
+        for c in self.store.get_confirmed_contracts()? {
+            // Confirmed contracts from channel are processed in channel specific methods.
+            if c.channel_id.is_some() {
+                continue;
+            }
+            if let Err(e) = self.check_confirmed_contract(&c).await {
+                error!(
+                    "Error checking confirmed contract {}: {}",
+                    c.accepted_contract.get_contract_id_string(),
+                    e
+                )
+            }
+        }
 
-        // Directly confirmed as we're in a channel the fund tx is already confirmed.
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Confirmed(signed_contract)),
-        )?;
+        Ok(())
+    }
 
-        Ok(msg)
+    async fn get_closable_contract_info<'a>(
+        &'a self,
+        contract: &'a SignedContract,
+    ) -> ClosableContractInfo<'a> {
+        let contract_infos = &contract.accepted_contract.offered_contract.contract_info;
+        let adaptor_infos = &contract.accepted_contract.adaptor_infos;
+        for (contract_info, adaptor_info) in contract_infos.iter().zip(adaptor_infos.iter()) {
+            let matured: Vec<_> = contract_info
+                .oracle_announcements
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| {
+                    (x.oracle_event.event_maturity_epoch as u64) <= self.time.unix_time_now()
+                })
+                .collect();
+            if matured.len() >= contract_info.threshold {
+                let mut attestations = Vec::new();
+                for (i, announcement) in &matured {
+                    let oracle = match self.oracles.get(&announcement.oracle_public_key) {
+                        Some(oracle) => oracle,
+                        None => continue,
+                    };
+                    if let Ok(attestation) = oracle
+                        .get_attestation(&announcement.oracle_event.event_id)
+                        .await
+                    {
+                        attestations.push((*i, attestation));
+                    }
+                }
+                // Multi event contracts can only be closed once enough
+                // oracles have attested each of the events.
+                let outcomes: Vec<_> = attestations
+                    .iter()
+                    .map(|(i, x)| (*i, &x.outcomes))
+                    .collect();
+                if attestations.len() >= contract_info.threshold
+                    && contract_info
+                        .get_range_info_for_outcome(adaptor_info, &outcomes, 0)
+                        .is_some()
+                {
+                    return Some((contract_info, adaptor_info, attestations));
+                }
+            }
+        }
+        None
     }
 
-    fn on_renew_confirm(
-        &self,
-        renew_confirm: &RenewConfirm,
-        peer_id: &PublicKey,
-    ) -> Result<RenewFinalize, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &renew_confirm.channel_id, Signed, Some(*peer_id))?;
-        let own_payout = get_signed_channel_state!(signed_channel, RenewAccepted, own_payout)?;
-        let contract_id = signed_channel.get_contract_id().ok_or_else(|| {
-            Error::InvalidState(
-                "Expected to be in a state with an associated contract id but was not.".to_string(),
-            )
-        })?;
+    async fn check_confirmed_contract(&self, contract: &SignedContract) -> Result<(), Error> {
+        if let Some(close_offer) = &contract.close_offer {
+            let close_txid = close_offer.close_tx.txid();
+            if self
+                .blockchain
+                .get_transaction_confirmations(&close_txid)
+                .await
+                .unwrap_or(0)
+                > 0
+            {
+                let close_tx = self.blockchain.get_transaction(&close_txid).await?;
+                let closed_contract = crate::contract_updater::get_collaboratively_closed_contract(
+                    contract, close_tx,
+                );
+                let closed_contract = Contract::CollaborativelyClosed(closed_contract);
+                self.store.update_contract(&closed_contract)?;
+                self.push_events(Event::from_closed_contract(&closed_contract))?;
+                return Ok(());
+            }
+        }
 
-        let (tx_type, prev_tx_id, closed_contract) = match signed_channel
-            .roll_back_state
-            .as_ref()
-            .expect("to have a rollback state")
-        {
-            SignedChannelState::Established {
-                own_buffer_adaptor_signature,
-                buffer_transaction,
-                signed_contract_id,
-                ..
-            } => {
-                let closed_contract = Contract::Closed(self.get_collaboratively_closed_contract(
-                    signed_contract_id,
-                    *own_payout,
-                    true,
-                )?);
-                (
-                    TxType::Revoked {
-                        update_idx: signed_channel.update_idx,
-                        own_adaptor_signature: *own_buffer_adaptor_signature,
-                        is_offer: false,
-                        revoked_tx_type: RevokedTxType::Buffer,
-                    },
-                    buffer_transaction.txid(),
-                    Some(closed_contract),
+        let closable_contract_info = self.get_closable_contract_info(contract).await;
+        if let Some((contract_info, adaptor_info, attestations)) = closable_contract_info {
+            let offer = &contract.accepted_contract.offered_contract;
+            let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
+            let cet = crate::contract_updater::get_signed_cet(
+                &self.secp,
+                contract,
+                contract_info,
+                adaptor_info,
+                &attestations,
+                &signer,
+            )?;
+            match self
+                .close_contract(
+                    contract,
+                    cet,
+                    attestations.iter().map(|x| x.1.clone()).collect(),
                 )
+                .await
+            {
+                Ok(closed_contract) => {
+                    self.store.update_contract(&closed_contract)?;
+                    self.push_events(Event::from_closed_contract(&closed_contract))?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to close contract {}: {}",
+                        contract.accepted_contract.get_contract_id_string(),
+                        e
+                    );
+                    return Err(e);
+                }
             }
-            SignedChannelState::Settled {
-                settle_tx,
-                own_settle_adaptor_signature,
-                ..
-            } => (
-                TxType::Revoked {
-                    update_idx: signed_channel.update_idx,
-                    own_adaptor_signature: *own_settle_adaptor_signature,
-                    is_offer: false,
-                    revoked_tx_type: RevokedTxType::Settle,
-                },
-                settle_tx.txid(),
-                None,
-            ),
-            s => {
-                return Err(Error::InvalidState(format!(
-                    "Expected rollback state Established or Revoked but found {s:?}"
-                )))
-            }
-        };
-        let accepted_contract =
-            get_contract_in_state!(self, &contract_id, Accepted, Some(*peer_id))?;
+        }
 
-        let (signed_contract, msg) = crate::channel_updater::verify_renew_confirm_and_finalize(
-            &self.secp,
-            &mut signed_channel,
-            &accepted_contract,
-            renew_confirm,
-            PEER_TIMEOUT,
-            &self.time,
-            &self.wallet,
-            &self.signer_provider,
-            &self.chain_monitor,
-        )?;
+        self.check_refund(contract).await?;
 
-        self.chain_monitor.lock().unwrap().add_tx(
-            prev_tx_id,
-            ChannelInfo {
-                channel_id: signed_channel.channel_id,
-                tx_type,
-            },
-        );
+        Ok(())
+    }
 
-        // Directly confirmed as we're in a channel the fund tx is already confirmed.
-        self.store.upsert_channel(
-            Channel::Signed(signed_channel),
-            Some(Contract::Confirmed(signed_contract)),
-        )?;
+    /// Manually close a contract with the oracle attestations.
+    pub async fn close_confirmed_contract(
+        &self,
+        contract_id: &ContractId,
+        attestations: Vec<(usize, OracleAttestation)>,
+    ) -> Result<Contract, Error> {
+        let contract = get_contract_in_state!(self, contract_id, Confirmed, None::<PublicKey>)?;
+        let contract_infos = &contract.accepted_contract.offered_contract.contract_info;
+        let adaptor_infos = &contract.accepted_contract.adaptor_infos;
 
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+        // find the contract info that matches the attestations
+        if let Some((contract_info, adaptor_info)) =
+            contract_infos.iter().zip(adaptor_infos).find(|(c, _)| {
+                let matches = attestations
+                    .iter()
+                    .filter(|(i, a)| {
+                        c.oracle_announcements[*i].oracle_event.oracle_nonces == a.nonces()
+                    })
+                    .count();
 
-        if let Some(closed_contract) = closed_contract {
-            self.store.update_contract(&closed_contract)?;
-        }
+                matches >= c.threshold
+            })
+        {
+            let offer = &contract.accepted_contract.offered_contract;
+            let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
+            let cet = crate::contract_updater::get_signed_cet(
+                &self.secp,
+                &contract,
+                contract_info,
+                adaptor_info,
+                &attestations,
+                &signer,
+            )?;
 
-        Ok(msg)
-    }
+            // Check that the lock time has passed
+            let time = bitcoin::absolute::Time::from_consensus(self.time.unix_time_now() as u32)
+                .expect("Time is not in valid range. This should never happen.");
+            let height =
+                Height::from_consensus(self.blockchain.get_blockchain_height().await? as u32)
+                    .expect("Height is not in valid range. This should never happen.");
+            let locktime = cet.lock_time;
 
-    fn on_renew_finalize(
-        &self,
-        renew_finalize: &RenewFinalize,
-        peer_id: &PublicKey,
-    ) -> Result<RenewRevoke, Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &renew_finalize.channel_id, Signed, Some(*peer_id))?;
-        let own_payout = get_signed_channel_state!(signed_channel, RenewConfirmed, own_payout)?;
+            if !locktime.is_satisfied_by(height, time) {
+                return Err(Error::InvalidState(
+                    "CET lock time has not passed yet".to_string(),
+                ));
+            }
 
-        let (tx_type, prev_tx_id, closed_contract) = match signed_channel
-            .roll_back_state
-            .as_ref()
-            .expect("to have a rollback state")
-        {
-            SignedChannelState::Established {
-                own_buffer_adaptor_signature,
-                buffer_transaction,
-                signed_contract_id,
-                ..
-            } => {
-                let closed_contract = self.get_collaboratively_closed_contract(
-                    signed_contract_id,
-                    *own_payout,
-                    true,
-                )?;
-                (
-                    TxType::Revoked {
-                        update_idx: signed_channel.update_idx,
-                        own_adaptor_signature: *own_buffer_adaptor_signature,
-                        is_offer: false,
-                        revoked_tx_type: RevokedTxType::Buffer,
-                    },
-                    buffer_transaction.txid(),
-                    Some(Contract::Closed(closed_contract)),
+            match self
+                .close_contract(
+                    &contract,
+                    cet,
+                    attestations.into_iter().map(|x| x.1).collect(),
                 )
+                .await
+            {
+                Ok(closed_contract) => {
+                    self.store.update_contract(&closed_contract)?;
+                    self.push_events(Event::from_closed_contract(&closed_contract))?;
+                    Ok(closed_contract)
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to close contract {}: {e}",
+                        contract.accepted_contract.get_contract_id_string()
+                    );
+                    Err(e)
+                }
             }
-            SignedChannelState::Settled {
-                settle_tx,
-                own_settle_adaptor_signature,
-                ..
-            } => (
-                TxType::Revoked {
-                    update_idx: signed_channel.update_idx,
-                    own_adaptor_signature: *own_settle_adaptor_signature,
-                    is_offer: false,
-                    revoked_tx_type: RevokedTxType::Settle,
-                },
-                settle_tx.txid(),
-                None,
-            ),
-            s => {
-                return Err(Error::InvalidState(format!(
-                    "Expected rollback state of Established or Settled but was {s:?}"
-                )))
+        } else {
+            Err(Error::InvalidState(
+                "Attestations did not match contract infos".to_string(),
+            ))
+        }
+    }
+
+    async fn check_preclosed_contracts(&self) -> Result<(), Error> {
+        for c in self.store.get_preclosed_contracts()? {
+            if let Err(e) = self.check_preclosed_contract(&c).await {
+                error!(
+                    "Error checking pre-closed contract {}: {}",
+                    c.signed_contract.accepted_contract.get_contract_id_string(),
+                    e
+                )
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn check_preclosed_contract(&self, contract: &PreClosedContract) -> Result<(), Error> {
+        let broadcasted_txid = contract.signed_cet.txid();
+        let confirmations = self
+            .blockchain
+            .get_transaction_confirmations(&broadcasted_txid)
+            .await?;
+        if confirmations == 0
+            && contract
+                .signed_contract
+                .accepted_contract
+                .offered_contract
+                .anchor_outputs
+        {
+            self.bump_cet_fee_if_needed(contract).await?;
+        } else if confirmations >= self.config.nb_confirmations {
+            self.clear_cet_fee_bump(&broadcasted_txid).await?;
+            self.store
+                .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+            let closed_contract = ClosedContract {
+                attestations: contract.attestations.clone(),
+                signed_cet: Some(contract.signed_cet.clone()),
+                contract_id: contract.signed_contract.accepted_contract.get_contract_id(),
+                temporary_contract_id: contract
+                    .signed_contract
+                    .accepted_contract
+                    .offered_contract
+                    .id,
+                counter_party_id: contract
+                    .signed_contract
+                    .accepted_contract
+                    .offered_contract
+                    .counter_party,
+                pnl: contract
+                    .signed_contract
+                    .accepted_contract
+                    .compute_pnl(&contract.signed_cet),
+            };
+            self.snapshot_closed_contract(&contract.signed_contract, broadcasted_txid)?;
+            let closed_contract = Contract::Closed(closed_contract);
+            self.store.update_contract(&closed_contract)?;
+            self.push_events(Event::from_closed_contract(&closed_contract))?;
+        }
+
+        Ok(())
+    }
+
+    /// Broadcasts a CPFP transaction spending the anchor output of the CET of
+    /// the given contract if it has not confirmed within
+    /// [`ManagerConfig::cpfp_confirmation_target`] blocks of being first checked
+    /// or of its fee being last bumped. Each new CPFP transaction replaces the
+    /// previous one and increases the fee rate by at least
+    /// [`CPFP_FEE_RATE_INCREMENT`] percent.
+    async fn bump_cet_fee_if_needed(&self, contract: &PreClosedContract) -> Result<(), Error> {
+        let cet_txid = contract.signed_cet.txid();
+        let cur_height = self.blockchain.get_blockchain_height().await?;
+        let offer = &contract.signed_contract.accepted_contract.offered_contract;
+        let fee_bump = self
+            .chain_monitor
+            .lock()
+            .unwrap()
+            .cet_fee_bumps
+            .get(&cet_txid)
+            .cloned();
+        let fee_bump = match fee_bump {
+            Some(fee_bump) => fee_bump,
+            None => {
+                let mut chain_monitor = self.chain_monitor.lock().unwrap();
+                chain_monitor.cet_fee_bumps.insert(
+                    cet_txid,
+                    CetFeeBump {
+                        height: cur_height,
+                        fee_rate_per_vb: offer.fee_rate_per_vb,
+                        cpfp_tx: None,
+                    },
+                );
+                return self.store.persist_chain_monitor(&chain_monitor);
             }
         };
 
-        let msg = crate::channel_updater::renew_channel_on_finalize(
-            &self.secp,
-            &mut signed_channel,
-            renew_finalize,
-            &self.signer_provider,
-        )?;
+        if cur_height < fee_bump.height + self.config.cpfp_confirmation_target {
+            return Ok(());
+        }
 
-        self.chain_monitor.lock().unwrap().add_tx(
-            prev_tx_id,
-            ChannelInfo {
-                channel_id: signed_channel.channel_id,
-                tx_type,
-            },
-        );
+        let estimated_fee_rate: u64 = (self.fee_estimator.get_est_sat_per_1000_weight(
+            lightning::chain::chaininterface::ConfirmationTarget::OnChainSweep,
+        ) / 250)
+            .into();
+        let min_fee_rate = fee_bump.fee_rate_per_vb
+            + std::cmp::max(1, fee_bump.fee_rate_per_vb * CPFP_FEE_RATE_INCREMENT / 100);
+        let fee_rate_per_vb = std::cmp::max(estimated_fee_rate, min_fee_rate);
+        let cet_input = contract.signed_cet.input[0].previous_output;
+        let cet_input_value = self
+            .blockchain
+            .get_transaction(&cet_input.txid)
+            .await?
+            .output
+            .get(cet_input.vout as usize)
+            .ok_or_else(|| Error::InvalidState("Could not find the CET input.".to_string()))?
+            .value;
+        let anchor_script_pubkey = if offer.is_offer_party {
+            &offer.offer_params.payout_script_pubkey
+        } else {
+            &contract
+                .signed_contract
+                .accepted_contract
+                .accept_params
+                .payout_script_pubkey
+        };
+        let cpfp_tx = crate::contract_updater::create_cpfp_transaction(
+            &contract.signed_cet,
+            cet_input_value,
+            anchor_script_pubkey,
+            fee_rate_per_vb,
+            &self.wallet,
+        )
+        .await?;
+        let cpfp_wallet_inputs = get_cpfp_wallet_inputs(&cpfp_tx);
 
-        let buffer_tx =
-            get_signed_channel_state!(signed_channel, Established, ref buffer_transaction)?;
+        if let Err(e) = self.blockchain.send_transaction(&cpfp_tx).await {
+            self.wallet.unreserve_utxos(&cpfp_wallet_inputs).await?;
+            return Err(e);
+        }
 
-        self.chain_monitor.lock().unwrap().add_tx(
-            buffer_tx.txid(),
-            ChannelInfo {
-                channel_id: signed_channel.channel_id,
-                tx_type: TxType::BufferTx,
+        if let Some(prev_cpfp_tx) = &fee_bump.cpfp_tx {
+            let replaced_inputs = get_cpfp_wallet_inputs(prev_cpfp_tx)
+                .into_iter()
+                .filter(|x| !cpfp_wallet_inputs.contains(x))
+                .collect::<Vec<_>>();
+            self.wallet.unreserve_utxos(&replaced_inputs).await?;
+        }
+
+        let mut chain_monitor = self.chain_monitor.lock().unwrap();
+        chain_monitor.cet_fee_bumps.insert(
+            cet_txid,
+            CetFeeBump {
+                height: cur_height,
+                fee_rate_per_vb,
+                cpfp_tx: Some(cpfp_tx),
             },
         );
+        self.store.persist_chain_monitor(&chain_monitor)
+    }
 
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
-
-        if let Some(closed_contract) = closed_contract {
-            self.store.update_contract(&closed_contract)?;
+    /// Stops bumping the fee of the given CET, releasing the wallet UTXOs used
+    /// by its last CPFP transaction if it is not confirmed.
+    async fn clear_cet_fee_bump(&self, cet_txid: &Txid) -> Result<(), Error> {
+        let fee_bump = self
+            .chain_monitor
+            .lock()
+            .unwrap()
+            .cet_fee_bumps
+            .remove(cet_txid);
+        if let Some(cpfp_tx) = fee_bump.and_then(|x| x.cpfp_tx) {
+            if self
+                .blockchain
+                .get_transaction_confirmations(&cpfp_tx.txid())
+                .await?
+                == 0
+            {
+                self.wallet
+                    .unreserve_utxos(&get_cpfp_wallet_inputs(&cpfp_tx))
+                    .await?;
+            }
         }
 
-        Ok(msg)
+        Ok(())
     }
 
-    fn on_renew_revoke(
+    async fn close_contract(
         &self,
-        renew_revoke: &RenewRevoke,
-        peer_id: &PublicKey,
-    ) -> Result<(), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &renew_revoke.channel_id, Signed, Some(*peer_id))?;
+        contract: &SignedContract,
+        signed_cet: Transaction,
+        attestations: Vec<OracleAttestation>,
+    ) -> Result<Contract, Error> {
+        let confirmations = self
+            .blockchain
+            .get_transaction_confirmations(&signed_cet.txid())
+            .await?;
 
-        crate::channel_updater::renew_channel_on_revoke(
-            &self.secp,
-            &mut signed_channel,
-            renew_revoke,
-        )?;
+        if confirmations < 1 {
+            // TODO(tibo): if this fails because another tx is already in
+            // mempool or blockchain, we might have been cheated. There is
+            // not much to be done apart from possibly extracting a fraud
+            // proof but ideally it should be handled.
+            self.blockchain.send_transaction(&signed_cet).await?;
+            self.push_events(Some(Event::CetBroadcast {
+                contract_id: contract.accepted_contract.get_contract_id(),
+                cet_txid: signed_cet.txid(),
+            }))?;
+
+            let preclosed_contract = PreClosedContract {
+                signed_contract: contract.clone(),
+                attestations: Some(attestations),
+                signed_cet,
+            };
+
+            return Ok(Contract::PreClosed(preclosed_contract));
+        } else if confirmations < self.config.nb_confirmations {
+            let preclosed_contract = PreClosedContract {
+                signed_contract: contract.clone(),
+                attestations: Some(attestations),
+                signed_cet,
+            };
+
+            return Ok(Contract::PreClosed(preclosed_contract));
+        }
+
+        self.snapshot_closed_contract(contract, signed_cet.txid())?;
+        let closed_contract = ClosedContract {
+            attestations: Some(attestations.to_vec()),
+            pnl: contract.accepted_contract.compute_pnl(&signed_cet),
+            signed_cet: Some(signed_cet),
+            contract_id: contract.accepted_contract.get_contract_id(),
+            temporary_contract_id: contract.accepted_contract.offered_contract.id,
+            counter_party_id: contract.accepted_contract.offered_contract.counter_party,
+        };
+
+        Ok(Contract::Closed(closed_contract))
+    }
+
+    async fn check_refund(&self, contract: &SignedContract) -> Result<(), Error> {
+        if contract
+            .accepted_contract
+            .dlc_transactions
+            .refund
+            .lock_time
+            .to_consensus_u32() as u64
+            <= self.time.unix_time_now()
+        {
+            let accepted_contract = &contract.accepted_contract;
+            let refund = accepted_contract.dlc_transactions.refund.clone();
+            let confirmations = self
+                .blockchain
+                .get_transaction_confirmations(&refund.txid())
+                .await?;
+            if confirmations == 0 {
+                let offer = &contract.accepted_contract.offered_contract;
+                let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
+                let refund =
+                    crate::contract_updater::get_signed_refund(&self.secp, contract, &signer)?;
+                self.blockchain.send_transaction(&refund).await?;
+                self.push_events(Some(Event::RefundBroadcast {
+                    contract_id: accepted_contract.get_contract_id(),
+                    refund_txid: refund.txid(),
+                }))?;
+            }
+
+            self.mark_prerefunded(contract)?;
+        }
+
+        Ok(())
+    }
 
+    /// Updates the given contract to the [`Contract::PreRefunded`] state and
+    /// starts watching its funding output to detect which transaction ends up
+    /// spending it.
+    fn mark_prerefunded(&self, contract: &SignedContract) -> Result<(), Error> {
+        self.chain_monitor.lock().unwrap().watch_fund_output(
+            contract
+                .accepted_contract
+                .dlc_transactions
+                .get_fund_outpoint(),
+            contract.accepted_contract.get_contract_id(),
+        );
         self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+        self.store
+            .update_contract(&Contract::PreRefunded(contract.clone()))
     }
 
-    fn on_collaborative_close_offer(
+    /// Keeps the state of the given standalone contract in the chain monitor
+    /// before it gets closed following the confirmation of the given CET, so
+    /// that it can be restored if the CET gets disconnected from the chain.
+    fn snapshot_closed_contract(
         &self,
-        close_offer: &CollaborativeCloseOffer,
-        peer_id: &PublicKey,
+        contract: &SignedContract,
+        closing_txid: Txid,
     ) -> Result<(), Error> {
-        let mut signed_channel =
-            get_channel_in_state!(self, &close_offer.channel_id, Signed, Some(*peer_id))?;
-
-        crate::channel_updater::on_collaborative_close_offer(
-            &mut signed_channel,
-            close_offer,
-            PEER_TIMEOUT,
-            &self.time,
-        )?;
-
+        if contract.channel_id.is_some() {
+            return Ok(());
+        }
+        self.chain_monitor
+            .lock()
+            .unwrap()
+            .add_contract_snapshot(contract.clone(), closing_txid);
         self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())
+    }
+
+    async fn check_prerefunded_contracts(&self) -> Result<(), Error> {
+        for c in self.store.get_prerefunded_contracts()? {
+            if let Err(e) = self.check_prerefunded_contract(&c).await {
+                error!(
+                    "Error checking pre-refunded contract {}: {}",
+                    c.accepted_contract.get_contract_id_string(),
+                    e
+                )
+            }
+        }
 
         Ok(())
     }
 
-    fn on_reject(&self, reject: &Reject, counter_party: &PublicKey) -> Result<(), Error> {
-        let channel = self.store.get_channel(&reject.channel_id)?;
+    /// Moves the given contract to the [`Contract::Refunded`] state once its
+    /// refund transaction reached the required number of confirmations,
+    /// broadcasting it again if it was evicted. If another transaction, such
+    /// as a CET broadcast by the counter party, was seen spending the funding
+    /// output instead, the contract is closed using it.
+    async fn check_prerefunded_contract(&self, contract: &SignedContract) -> Result<(), Error> {
+        let fund_outpoint = contract
+            .accepted_contract
+            .dlc_transactions
+            .get_fund_outpoint();
+        let refund_txid = contract.accepted_contract.dlc_transactions.refund.txid();
 
-        if let Some(channel) = channel {
-            if channel.get_counter_party_id() != *counter_party {
-                return Err(Error::InvalidParameters(format!(
-                    "Peer {:02x?} is not involved with {} {:02x?}.",
-                    counter_party,
-                    stringify!(Channel),
-                    channel.get_id()
-                )));
+        let spend = self
+            .chain_monitor
+            .lock()
+            .unwrap()
+            .get_fund_output_spend(&fund_outpoint)
+            .map(|(tx, height)| (tx.clone(), height));
+        if let Some((closing_tx, height)) = spend {
+            if closing_tx.txid() != refund_txid {
+                warn!(
+                    "Funding output of pre-refunded contract {} was spent by {} instead of the refund transaction.",
+                    contract.accepted_contract.get_contract_id_string(),
+                    closing_tx.txid()
+                );
+                let last_height = self.chain_monitor.lock().unwrap().last_height;
+                let confirmations = (last_height + 1).saturating_sub(height) as u32;
+                self.chain_monitor
+                    .lock()
+                    .unwrap()
+                    .remove_fund_output(&fund_outpoint);
+                self.store
+                    .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+                self.on_counterparty_close(contract, closing_tx, confirmations)?;
+                return Ok(());
             }
-            match channel {
-                Channel::Offered(offered_channel) => {
-                    let offered_contract = get_contract_in_state!(
-                        self,
-                        &offered_channel.offered_contract_id,
-                        Offered,
-                        None as Option<PublicKey>
-                    )?;
-                    let utxos = offered_contract
-                        .funding_inputs
-                        .iter()
-                        .map(|funding_input| {
-                            let txid = Transaction::consensus_decode(
-                                &mut funding_input.prev_tx.as_slice(),
-                            )
-                            .expect("Transaction Decode Error")
-                            .txid();
-                            let vout = funding_input.prev_tx_vout;
-                            OutPoint { txid, vout }
-                        })
-                        .collect::<Vec<_>>();
-
-                    self.wallet.unreserve_utxos(&utxos)?;
-
-                    // remove rejected channel, since nothing has been confirmed on chain yet.
-                    self.store.upsert_channel(
-                        Channel::Cancelled(offered_channel),
-                        Some(Contract::Rejected(offered_contract)),
-                    )?;
-                }
-                Channel::Signed(mut signed_channel) => {
-                    let contract = match signed_channel.state {
-                        SignedChannelState::RenewOffered {
-                            offered_contract_id,
-                            ..
-                        } => {
-                            let offered_contract = get_contract_in_state!(
-                                self,
-                                &offered_contract_id,
-                                Offered,
-                                None::<PublicKey>
-                            )?;
-                            Some(Contract::Rejected(offered_contract))
-                        }
-                        _ => None,
-                    };
-
-                    crate::channel_updater::on_reject(&mut signed_channel)?;
+        }
 
-                    self.store
-                        .upsert_channel(Channel::Signed(signed_channel), contract)?;
-                }
-                channel => {
-                    return Err(Error::InvalidState(format!(
-                        "Not in a state adequate to receive a reject message. {:?}",
-                        channel
-                    )))
-                }
+        let confirmations = self
+            .blockchain
+            .get_transaction_confirmations(&refund_txid)
+            .await?;
+        if confirmations >= self.config.nb_confirmations {
+            self.chain_monitor
+                .lock()
+                .unwrap()
+                .remove_fund_output(&fund_outpoint);
+            self.store
+                .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+            self.store
+                .update_contract(&Contract::Refunded(contract.clone()))?;
+            self.push_events(Some(Event::ContractRefunded {
+                contract_id: contract.accepted_contract.get_contract_id(),
+            }))?;
+        } else if confirmations == 0 {
+            let offer = &contract.accepted_contract.offered_contract;
+            let signer = self.signer_provider.derive_contract_signer(offer.keys_id)?;
+            let refund = crate::contract_updater::get_signed_refund(&self.secp, contract, &signer)?;
+            if let Err(e) = self.blockchain.send_transaction(&refund).await {
+                warn!(
+                    "Could not rebroadcast refund transaction {} of contract {}: {}",
+                    refund_txid,
+                    contract.accepted_contract.get_contract_id_string(),
+                    e
+                );
             }
-        } else {
-            warn!(
-                "Couldn't find rejected dlc channel with id: {}",
-                reject.channel_id.to_lower_hex_string()
-            );
         }
 
         Ok(())
     }
 
-    fn channel_checks(&self) -> Result<(), Error> {
-        let established_closing_channels = self
-            .store
-            .get_signed_channels(Some(SignedChannelStateType::Closing))?;
+    /// Function to call when we detect that a contract was closed by our counter party.
+    /// This will update the state of the contract and return the [`Contract`] object.
+    /// The oracle attestations used to close the contract are recovered from
+    /// the closing transaction when possible. If the closing transaction is
+    /// the refund transaction and it does not have the required number of
+    /// confirmations yet, the contract is moved to the
+    /// [`Contract::PreRefunded`] state.
+    pub fn on_counterparty_close(
+        &self,
+        contract: &SignedContract,
+        closing_tx: Transaction,
+        confirmations: u32,
+    ) -> Result<Contract, Error> {
+        // check if the closing tx actually spends the funding output
+        if !closing_tx.input.iter().any(|i| {
+            i.previous_output
+                == contract
+                    .accepted_contract
+                    .dlc_transactions
+                    .get_fund_outpoint()
+        }) {
+            return Err(Error::InvalidParameters(
+                "Closing tx does not spend the funding tx".to_string(),
+            ));
+        }
 
-        for channel in established_closing_channels {
-            if let Err(e) = self.try_finalize_closing_established_channel(channel) {
-                error!("Error trying to close established channel: {}", e);
+        // check if it is the refund tx (easy case)
+        if contract.accepted_contract.dlc_transactions.refund.txid() == closing_tx.txid() {
+            if confirmations < self.config.nb_confirmations {
+                self.mark_prerefunded(contract)?;
+                return Ok(Contract::PreRefunded(contract.clone()));
             }
+            let refunded = Contract::Refunded(contract.clone());
+            self.store.update_contract(&refunded)?;
+            self.push_events(Some(Event::ContractRefunded {
+                contract_id: contract.accepted_contract.get_contract_id(),
+            }))?;
+            return Ok(refunded);
         }
 
-        if let Err(e) = self.check_for_timed_out_channels() {
-            error!("Error checking timed out channels {}", e);
-        }
-        self.check_for_watched_tx()
-    }
+        let attestations = crate::contract_updater::recover_attestations_from_cet(
+            &self.secp,
+            contract,
+            &closing_tx,
+        );
 
-    fn check_for_timed_out_channels(&self) -> Result<(), Error> {
-        check_for_timed_out_channels!(self, RenewOffered);
-        check_for_timed_out_channels!(self, RenewAccepted);
-        check_for_timed_out_channels!(self, RenewConfirmed);
-        check_for_timed_out_channels!(self, SettledOffered);
-        check_for_timed_out_channels!(self, SettledAccepted);
-        check_for_timed_out_channels!(self, SettledConfirmed);
+        let contract = if confirmations < self.config.nb_confirmations {
+            Contract::PreClosed(PreClosedContract {
+                signed_contract: contract.clone(),
+                attestations,
+                signed_cet: closing_tx,
+            })
+        } else {
+            self.snapshot_closed_contract(contract, closing_tx.txid())?;
+            Contract::Closed(ClosedContract {
+                attestations,
+                pnl: contract.accepted_contract.compute_pnl(&closing_tx),
+                signed_cet: Some(closing_tx),
+                contract_id: contract.accepted_contract.get_contract_id(),
+                temporary_contract_id: contract.accepted_contract.offered_contract.id,
+                counter_party_id: contract.accepted_contract.offered_contract.counter_party,
+            })
+        };
 
-        Ok(())
+        self.store.update_contract(&contract)?;
+        self.push_events(Event::from_closed_contract(&contract))?;
+
+        Ok(contract)
     }
+}
 
-    pub(crate) fn process_watched_txs(
+impl<W: Deref, SP: Deref, B: Deref, S: Deref, O: Deref, T: Deref, F: Deref, X: ContractSigner>
+    AsyncManager<W, Arc<CachedContractSignerProvider<SP, X>>, B, S, O, T, F, X>
+where
+    W::Target: AsyncWallet,
+    SP::Target: ContractSignerProvider<Signer = X>,
+    B::Target: AsyncBlockchain,
+    S::Target: Storage,
+    O::Target: AsyncOracle,
+    T::Target: Time,
+    F::Target: FeeEstimator,
+{
+    /// Create a new channel offer and return the [`dlc_messages::channel::OfferChannel`]
+    /// message to be sent to the `counter_party`.
+    pub async fn offer_channel(
         &self,
-        watched_txs: Vec<(Transaction, ChannelInfo)>,
-    ) -> Result<(), Error> {
-        for (tx, channel_info) in watched_txs {
-            let mut signed_channel = match get_channel_in_state!(
-                self,
-                &channel_info.channel_id,
-                Signed,
-                None as Option<PublicKey>
-            ) {
-                Ok(c) => c,
-                Err(e) => {
-                    error!(
-                        "Could not retrieve channel {:?}: {}",
-                        channel_info.channel_id, e
-                    );
-                    continue;
-                }
-            };
-
-            let persist = match channel_info.tx_type {
-                TxType::BufferTx => {
-                    // TODO(tibo): should only considered closed after some confirmations.
-                    // Ideally should save previous state, and maybe restore in
-                    // case of reorg, though if the counter party has sent the
-                    // tx to close the channel it is unlikely that the tx will
-                    // not be part of a future block.
-
-                    let contract_id = signed_channel
-                        .get_contract_id()
-                        .expect("to have a contract id");
-                    let mut state = SignedChannelState::Closing {
-                        buffer_transaction: tx.clone(),
-                        is_initiator: false,
-                        contract_id,
-                        keys_id: signed_channel.keys_id().ok_or_else(|| {
-                            Error::InvalidState("Expected to have keys_id.".to_string())
-                        })?,
-                    };
-                    std::mem::swap(&mut signed_channel.state, &mut state);
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+    ) -> Result<OfferChannel, Error> {
+        let oracle_announcements = self
+            .get_contract_oracle_announcements(contract_input)
+            .await?;
 
-                    signed_channel.roll_back_state = Some(state);
+        let (offered_channel, offered_contract) = crate::channel_updater::offer_channel(
+            &self.secp,
+            contract_input,
+            &counter_party,
+            &oracle_announcements,
+            self.config.cet_nsequence,
+            self.config.refund_delay,
+            &self.wallet,
+            &self.signer_provider,
+            &self.blockchain,
+            &self.time,
+            crate::utils::get_new_temporary_id(),
+        )
+        .await?;
 
-                    self.store
-                        .upsert_channel(Channel::Signed(signed_channel), None)?;
+        let msg = offered_channel.get_offer_channel_msg(&offered_contract);
 
-                    false
-                }
-                TxType::Revoked {
-                    update_idx,
-                    own_adaptor_signature,
-                    is_offer,
-                    revoked_tx_type,
-                } => {
-                    let secret = signed_channel
-                        .counter_party_commitment_secrets
-                        .get_secret(update_idx)
-                        .expect("to be able to retrieve the per update secret");
-                    let counter_per_update_secret = SecretKey::from_slice(&secret)
-                        .expect("to be able to parse the counter per update secret.");
+        self.store.upsert_channel(
+            Channel::Offered(offered_channel),
+            Some(Contract::Offered(offered_contract)),
+        )?;
 
-                    let per_update_seed_pk = signed_channel.own_per_update_seed;
+        Ok(msg)
+    }
 
-                    let per_update_seed_sk = self
-                        .signer_provider
-                        .get_secret_key_for_pubkey(&per_update_seed_pk)?;
+    /// Reject a channel that was offered. Returns the [`dlc_messages::channel::Reject`]
+    /// message to be sent as well as the public key of the offering node.
+    pub fn reject_channel(&self, channel_id: &ChannelId) -> Result<(Reject, PublicKey), Error> {
+        let offered_channel =
+            get_channel_in_state!(self, channel_id, Offered, None as Option<PublicKey>)?;
 
-                    let per_update_secret = SecretKey::from_slice(&build_commitment_secret(
-                        per_update_seed_sk.as_ref(),
-                        update_idx,
-                    ))
-                    .expect("a valid secret key.");
+        if offered_channel.is_offer_party {
+            return Err(Error::InvalidState(
+                "Cannot reject channel initiated by us.".to_string(),
+            ));
+        }
 
-                    let per_update_point =
-                        PublicKey::from_secret_key(&self.secp, &per_update_secret);
+        let offered_contract = get_contract_in_state!(
+            self,
+            &offered_channel.offered_contract_id,
+            Offered,
+            None as Option<PublicKey>
+        )?;
 
-                    let own_revocation_params = signed_channel.own_points.get_revokable_params(
-                        &self.secp,
-                        &signed_channel.counter_points.revocation_basepoint,
-                        &per_update_point,
-                    );
+        let counterparty = offered_channel.counter_party;
+        self.store.upsert_channel(
+            Channel::Cancelled(offered_channel),
+            Some(Contract::Rejected(offered_contract)),
+        )?;
 
-                    let counter_per_update_point =
-                        PublicKey::from_secret_key(&self.secp, &counter_per_update_secret);
+        let msg = Reject {
+            channel_id: *channel_id,
+        };
+        Ok((msg, counterparty))
+    }
 
-                    let base_own_sk = self
-                        .signer_provider
-                        .get_secret_key_for_pubkey(&signed_channel.own_points.own_basepoint)?;
+    /// Accept a channel that was offered. Returns the [`dlc_messages::channel::AcceptChannel`]
+    /// message to be sent, the updated [`crate::ChannelId`] and [`crate::ContractId`],
+    /// as well as the public key of the offering node.
+    pub async fn accept_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(AcceptChannel, ChannelId, ContractId, PublicKey), Error> {
+        let offered_channel =
+            get_channel_in_state!(self, channel_id, Offered, None as Option<PublicKey>)?;
 
-                    let own_sk = derive_private_key(&self.secp, &per_update_point, &base_own_sk);
+        if offered_channel.is_offer_party {
+            return Err(Error::InvalidState(
+                "Cannot accept channel initiated by us.".to_string(),
+            ));
+        }
 
-                    let counter_revocation_params =
-                        signed_channel.counter_points.get_revokable_params(
-                            &self.secp,
-                            &signed_channel.own_points.revocation_basepoint,
-                            &counter_per_update_point,
-                        );
+        let offered_contract = get_contract_in_state!(
+            self,
+            &offered_channel.offered_contract_id,
+            Offered,
+            None as Option<PublicKey>
+        )?;
 
-                    let witness = if signed_channel.own_params.fund_pubkey
+        let (accepted_channel, accepted_contract, accept_channel) =
+            crate::channel_updater::accept_channel_offer(
+                &self.secp,
+                &offered_channel,
+                &offered_contract,
+                &self.wallet,
+                &self.signer_provider,
+                &self.blockchain,
+            )
+            .await?;
+
+        self.wallet
+            .import_address(&Address::p2wsh(
+                &accepted_contract.dlc_transactions.funding_script_pubkey,
+                self.blockchain.get_network().await?,
+            ))
+            .await?;
+
+        let channel_id = accepted_channel.channel_id;
+        let contract_id = accepted_contract.get_contract_id();
+        let counter_party = accepted_contract.offered_contract.counter_party;
+
+        self.store.upsert_channel(
+            Channel::Accepted(accepted_channel),
+            Some(Contract::Accepted(accepted_contract)),
+        )?;
+
+        Ok((accept_channel, channel_id, contract_id, counter_party))
+    }
+
+    /// Force close the channel with given [`crate::ChannelId`].
+    pub async fn force_close_channel(&self, channel_id: &ChannelId) -> Result<(), Error> {
+        let channel = get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        self.force_close_channel_internal(channel, true).await
+    }
+
+    /// Offer to settle the balance of a channel so that the counter party gets
+    /// `counter_payout`. Returns the [`dlc_messages::channel::SettleChannelOffer`]
+    /// message to be sent and the public key of the counter party node.
+    pub fn settle_offer(
+        &self,
+        channel_id: &ChannelId,
+        counter_payout: u64,
+    ) -> Result<(SettleOffer, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let msg = crate::channel_updater::settle_channel_offer(
+            &self.secp,
+            &mut signed_channel,
+            counter_payout,
+            self.config.offer_fee_share,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+        )?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Accept a settlement offer, returning the [`SettleAccept`] message to be
+    /// sent to the node with the returned [`PublicKey`] id.
+    pub fn accept_settle_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(SettleAccept, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let msg = crate::channel_updater::settle_channel_accept(
+            &self.secp,
+            &mut signed_channel,
+            cet_nsequence,
+            0,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+            &self.chain_monitor,
+        )?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Returns a [`RenewOffer`] message as well as the [`PublicKey`] of the
+    /// counter party's node to offer the establishment of a new contract in the
+    /// channel.
+    pub async fn renew_offer(
+        &self,
+        channel_id: &ChannelId,
+        counter_payout: u64,
+        contract_input: &ContractInput,
+    ) -> Result<(RenewOffer, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let oracle_announcements = self
+            .get_contract_oracle_announcements(contract_input)
+            .await?;
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let (msg, offered_contract) = crate::channel_updater::renew_offer(
+            &self.secp,
+            &mut signed_channel,
+            contract_input,
+            oracle_announcements,
+            counter_payout,
+            self.config.refund_delay,
+            self.config.peer_timeout,
+            cet_nsequence,
+            self.config.offer_fee_share,
+            &self.signer_provider,
+            &self.time,
+        )?;
+
+        let counter_party = offered_contract.counter_party;
+
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Offered(offered_contract)),
+        )?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Accept an offer to renew the contract in the channel. Returns the
+    /// [`RenewAccept`] message to be sent to the peer with the returned
+    /// [`PublicKey`] as node id.
+    pub fn accept_renew_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(RenewAccept, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        let offered_contract_id = signed_channel.get_contract_id().ok_or_else(|| {
+            Error::InvalidState("Expected to have a contract id but did not.".to_string())
+        })?;
+
+        let offered_contract = get_contract_in_state!(
+            self,
+            &offered_contract_id,
+            Offered,
+            None as Option<PublicKey>
+        )?;
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let (accepted_contract, msg) = crate::channel_updater::accept_channel_renewal(
+            &self.secp,
+            &mut signed_channel,
+            &offered_contract,
+            cet_nsequence,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+        )?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Accepted(accepted_contract)),
+        )?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Reject an offer to renew the contract in the channel. Returns the
+    /// [`Reject`] message to be sent to the peer with the returned
+    /// [`PublicKey`] node id.
+    pub fn reject_renew_offer(&self, channel_id: &ChannelId) -> Result<(Reject, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+        let offered_contract_id = signed_channel.get_contract_id().ok_or_else(|| {
+            Error::InvalidState(
+                "Expected to be in a state with an associated contract id but was not.".to_string(),
+            )
+        })?;
+
+        let offered_contract = get_contract_in_state!(
+            self,
+            &offered_contract_id,
+            Offered,
+            None as Option<PublicKey>
+        )?;
+
+        let reject_msg = crate::channel_updater::reject_renew_offer(&mut signed_channel)?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Rejected(offered_contract)),
+        )?;
+
+        Ok((reject_msg, counter_party))
+    }
+
+    /// Returns a [`Reject`] message to be sent to the counter party of the
+    /// channel to inform them that the local party does not wish to accept the
+    /// proposed settle offer.
+    pub fn reject_settle_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(Reject, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let msg = crate::channel_updater::reject_settle_offer(&mut signed_channel)?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Offer to splice the settled channel with the given [`ChannelId`] so
+    /// that the local party holds `offer_balance` in it. Funds are added to the
+    /// channel from the wallet if `offer_balance` is greater than the current
+    /// balance of the local party, and paid out to the wallet otherwise, the
+    /// splice transaction fee being paid by the local party at the given fee
+    /// rate. Returns the [`SpliceOffer`] message to be sent and the public key
+    /// of the counter party node.
+    pub async fn splice_channel(
+        &self,
+        channel_id: &ChannelId,
+        offer_balance: u64,
+        fee_rate_per_vb: u64,
+    ) -> Result<(SpliceOffer, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let msg = crate::channel_updater::splice_channel_offer(
+            &self.secp,
+            &mut signed_channel,
+            offer_balance,
+            fee_rate_per_vb,
+            self.config.offer_fee_share,
+            cet_nsequence,
+            0,
+            self.config.peer_timeout,
+            &self.wallet,
+            &self.signer_provider,
+            &self.blockchain,
+            &self.time,
+        )
+        .await?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Accept an offer to splice a channel, returning the [`SpliceAccept`]
+    /// message to be sent to the node with the returned [`PublicKey`] id.
+    pub fn accept_splice_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(SpliceAccept, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let msg = crate::channel_updater::splice_channel_accept(
+            &self.secp,
+            &mut signed_channel,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+            &self.chain_monitor,
+        )?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Returns a [`Reject`] message to be sent to the counter party of the
+    /// channel to inform them that the local party does not wish to accept the
+    /// proposed splice offer.
+    pub fn reject_splice_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(Reject, PublicKey), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let msg = crate::channel_updater::reject_splice_offer(&mut signed_channel)?;
+
+        let counter_party = signed_channel.counter_party;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok((msg, counter_party))
+    }
+
+    /// Returns the [`ChannelReestablish`] messages to be sent to the given peer
+    /// upon reconnection, one for each signed channel with the peer that is not
+    /// being closed. Both parties are expected to send them so that they can
+    /// complete or roll back the channel updates that were interrupted by a
+    /// disconnection.
+    pub fn get_channel_reestablish_messages(
+        &self,
+        counter_party: &PublicKey,
+    ) -> Result<Vec<ChannelReestablish>, Error> {
+        Ok(self
+            .store
+            .get_signed_channels(None)?
+            .iter()
+            .filter(|c| c.counter_party == *counter_party)
+            .filter_map(|c| crate::channel_updater::get_channel_reestablish(&self.secp, c))
+            .collect())
+    }
+
+    /// Returns a [`CollaborativeCloseOffer`] message to be sent to the counter
+    /// party of the channel and update the state of the channel. Note that the
+    /// channel will be forced closed after a timeout if the counter party does
+    /// not broadcast the close transaction.
+    pub fn offer_collaborative_close(
+        &self,
+        channel_id: &ChannelId,
+        counter_payout: u64,
+    ) -> Result<CollaborativeCloseOffer, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let (msg, close_tx) = crate::channel_updater::offer_collaborative_close(
+            &self.secp,
+            &mut signed_channel,
+            counter_payout,
+            self.config.offer_fee_share,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+        )?;
+
+        self.chain_monitor.lock().unwrap().add_tx(
+            close_tx.txid(),
+            ChannelInfo {
+                channel_id: *channel_id,
+                tx_type: TxType::CollaborativeClose,
+            },
+        );
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        Ok(msg)
+    }
+
+    /// Accept an offer to collaboratively close the channel. The close transaction
+    /// will be broadcast and the state of the channel updated.
+    pub async fn accept_collaborative_close(&self, channel_id: &ChannelId) -> Result<(), Error> {
+        let signed_channel =
+            get_channel_in_state!(self, channel_id, Signed, None as Option<PublicKey>)?;
+
+        let closed_contract = if let Some(SignedChannelState::Established {
+            signed_contract_id,
+            ..
+        }) = &signed_channel.roll_back_state
+        {
+            let counter_payout = get_signed_channel_state!(
+                signed_channel,
+                CollaborativeCloseOffered,
+                counter_payout
+            )?;
+            Some(self.get_collaboratively_closed_contract(
+                signed_contract_id,
+                *counter_payout,
+                true,
+            )?)
+        } else {
+            None
+        };
+
+        let (close_tx, closed_channel) = crate::channel_updater::accept_collaborative_close_offer(
+            &self.secp,
+            &signed_channel,
+            &self.signer_provider,
+        )?;
+
+        self.blockchain.send_transaction(&close_tx).await?;
+
+        self.store.upsert_channel(closed_channel, None)?;
+
+        if let Some(closed_contract) = closed_contract {
+            let closed_contract = Contract::Closed(closed_contract);
+            self.store.update_contract(&closed_contract)?;
+            self.push_events(Event::from_closed_contract(&closed_contract))?;
+        }
+
+        Ok(())
+    }
+
+    async fn try_finalize_closing_established_channel(
+        &self,
+        signed_channel: SignedChannel,
+    ) -> Result<(), Error> {
+        let (buffer_tx, contract_id, &is_initiator) = get_signed_channel_state!(
+            signed_channel,
+            Closing,
+            buffer_transaction,
+            contract_id,
+            is_initiator
+        )?;
+
+        if self
+            .blockchain
+            .get_transaction_confirmations(&buffer_tx.txid())
+            .await?
+            >= signed_channel.cet_nsequence
+        {
+            log::info!(
+                "Buffer transaction for contract {} has enough confirmations to spend from it",
+                serialize_hex(&contract_id)
+            );
+
+            let confirmed_contract =
+                get_contract_in_state!(self, &contract_id, Confirmed, None as Option<PublicKey>)?;
+
+            let (contract_info, adaptor_info, attestations) = self
+                .get_closable_contract_info(&confirmed_contract)
+                .await
+                .ok_or_else(|| {
+                    Error::InvalidState("Could not get information to close contract".to_string())
+                })?;
+
+            // Keeps the closing state of the channel so that it is restored
+            // if the buffer transaction gets disconnected from the chain.
+            let height = self.blockchain.get_blockchain_height().await?;
+            self.snapshot_channel(&signed_channel, height)?;
+
+            let (signed_cet, closed_channel) =
+                crate::channel_updater::finalize_unilateral_close_settled_channel(
+                    &self.secp,
+                    &signed_channel,
+                    &confirmed_contract,
+                    contract_info,
+                    &attestations,
+                    adaptor_info,
+                    &self.signer_provider,
+                    is_initiator,
+                )?;
+
+            let closed_contract = self
+                .close_contract(
+                    &confirmed_contract,
+                    signed_cet,
+                    attestations.iter().map(|x| &x.1).cloned().collect(),
+                )
+                .await?;
+
+            self.chain_monitor
+                .lock()
+                .unwrap()
+                .cleanup_channel(signed_channel.channel_id);
+
+            self.store
+                .upsert_channel(closed_channel, Some(closed_contract))?;
+            self.store
+                .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+        }
+
+        Ok(())
+    }
+
+    fn on_offer_channel(
+        &self,
+        offer_channel: &OfferChannel,
+        counter_party: PublicKey,
+    ) -> Result<(), Error> {
+        offer_channel.validate(
+            &self.secp,
+            self.config.min_refund_delay,
+            self.config.max_refund_delay,
+            self.config.min_cet_nsequence,
+            self.config.max_cet_nsequence,
+        )?;
+
+        let keys_id = self
+            .signer_provider
+            .derive_signer_key_id(false, offer_channel.temporary_contract_id);
+        let (channel, contract) =
+            OfferedChannel::from_offer_channel(offer_channel, counter_party, keys_id)?;
+
+        contract.validate()?;
+
+        if self
+            .store
+            .get_channel(&channel.temporary_channel_id)?
+            .is_some()
+        {
+            return Err(Error::InvalidParameters(
+                "Channel with identical idea already in store".to_string(),
+            ));
+        }
+
+        let event = Event::ChannelOfferReceived {
+            temporary_channel_id: channel.temporary_channel_id,
+            counter_party,
+        };
+
+        self.store
+            .upsert_channel(Channel::Offered(channel), Some(Contract::Offered(contract)))?;
+
+        self.push_events(Some(event))?;
+
+        Ok(())
+    }
+
+    async fn on_accept_channel(
+        &self,
+        accept_channel: &AcceptChannel,
+        peer_id: &PublicKey,
+    ) -> Result<SignChannel, Error> {
+        let offered_channel = get_channel_in_state!(
+            self,
+            &accept_channel.temporary_channel_id,
+            Offered,
+            Some(*peer_id)
+        )?;
+        let offered_contract = get_contract_in_state!(
+            self,
+            &offered_channel.offered_contract_id,
+            Offered,
+            Some(*peer_id)
+        )?;
+
+        let (signed_channel, signed_contract, sign_channel) = {
+            let res = crate::channel_updater::verify_and_sign_accepted_channel(
+                &self.secp,
+                &offered_channel,
+                &offered_contract,
+                accept_channel,
+                offered_channel.cet_nsequence,
+                &self.wallet,
+                &self.signer_provider,
+                &self.chain_monitor,
+            )
+            .await;
+
+            match res {
+                Ok(res) => res,
+                Err(e) => {
+                    let channel = crate::channel::FailedAccept {
+                        temporary_channel_id: accept_channel.temporary_channel_id,
+                        error_message: format!("Error validating accept channel: {}", e),
+                        accept_message: accept_channel.clone(),
+                        counter_party: *peer_id,
+                    };
+                    self.store
+                        .upsert_channel(Channel::FailedAccept(channel), None)?;
+                    return Err(e);
+                }
+            }
+        };
+
+        self.wallet
+            .import_address(&Address::p2wsh(
+                &signed_contract
+                    .accepted_contract
+                    .dlc_transactions
+                    .funding_script_pubkey,
+                self.blockchain.get_network().await?,
+            ))
+            .await?;
+
+        if let SignedChannelState::Established {
+            buffer_transaction, ..
+        } = &signed_channel.state
+        {
+            self.chain_monitor.lock().unwrap().add_tx(
+                buffer_transaction.txid(),
+                ChannelInfo {
+                    channel_id: signed_channel.channel_id,
+                    tx_type: TxType::BufferTx,
+                },
+            );
+        } else {
+            unreachable!();
+        }
+
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Signed(signed_contract)),
+        )?;
+
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        Ok(sign_channel)
+    }
+
+    async fn on_sign_channel(
+        &self,
+        sign_channel: &SignChannel,
+        peer_id: &PublicKey,
+    ) -> Result<(), Error> {
+        let accepted_channel =
+            get_channel_in_state!(self, &sign_channel.channel_id, Accepted, Some(*peer_id))?;
+        let accepted_contract = get_contract_in_state!(
+            self,
+            &accepted_channel.accepted_contract_id,
+            Accepted,
+            Some(*peer_id)
+        )?;
+
+        let (signed_channel, signed_contract, signed_fund_tx) = {
+            let res = verify_signed_channel(
+                &self.secp,
+                &accepted_channel,
+                &accepted_contract,
+                sign_channel,
+                &self.wallet,
+                &self.chain_monitor,
+            )
+            .await;
+
+            match res {
+                Ok(res) => res,
+                Err(e) => {
+                    let channel = crate::channel::FailedSign {
+                        channel_id: sign_channel.channel_id,
+                        error_message: format!("Error validating accept channel: {}", e),
+                        sign_message: sign_channel.clone(),
+                        counter_party: *peer_id,
+                    };
+                    self.store
+                        .upsert_channel(Channel::FailedSign(channel), None)?;
+                    return Err(e);
+                }
+            }
+        };
+
+        if let SignedChannelState::Established {
+            buffer_transaction, ..
+        } = &signed_channel.state
+        {
+            self.chain_monitor.lock().unwrap().add_tx(
+                buffer_transaction.txid(),
+                ChannelInfo {
+                    channel_id: signed_channel.channel_id,
+                    tx_type: TxType::BufferTx,
+                },
+            );
+        } else {
+            unreachable!();
+        }
+
+        self.blockchain.send_transaction(&signed_fund_tx).await?;
+
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Signed(signed_contract)),
+        )?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        Ok(())
+    }
+
+    fn on_settle_offer(
+        &self,
+        settle_offer: &SettleOffer,
+        peer_id: &PublicKey,
+    ) -> Result<Option<Reject>, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &settle_offer.channel_id, Signed, Some(*peer_id))?;
+
+        if let SignedChannelState::SettledOffered { .. } = signed_channel.state {
+            return Ok(Some(Reject {
+                channel_id: settle_offer.channel_id,
+            }));
+        }
+
+        crate::channel_updater::on_settle_offer(
+            &mut signed_channel,
+            settle_offer,
+            self.config.min_counter_offer_fee_share,
+        )?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok(None)
+    }
+
+    fn on_splice_offer(
+        &self,
+        splice_offer: &SpliceOffer,
+        peer_id: &PublicKey,
+    ) -> Result<Option<Reject>, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &splice_offer.channel_id, Signed, Some(*peer_id))?;
+
+        if let SignedChannelState::SpliceOffered { .. } = signed_channel.state {
+            return Ok(Some(Reject {
+                channel_id: splice_offer.channel_id,
+            }));
+        }
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        crate::channel_updater::on_splice_offer(
+            &self.secp,
+            &mut signed_channel,
+            splice_offer,
+            self.config.min_counter_offer_fee_share,
+            cet_nsequence,
+            0,
+            &self.signer_provider,
+        )?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok(None)
+    }
+
+    async fn on_splice_accept(
+        &self,
+        splice_accept: &SpliceAccept,
+        peer_id: &PublicKey,
+    ) -> Result<SpliceConfirm, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &splice_accept.channel_id, Signed, Some(*peer_id))?;
+
+        let (msg, splice_tx) = crate::channel_updater::splice_channel_confirm(
+            &self.secp,
+            &mut signed_channel,
+            splice_accept,
+            &self.wallet,
+            &self.signer_provider,
+            &self.chain_monitor,
+        )
+        .await?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        self.blockchain.send_transaction(&splice_tx).await?;
+
+        Ok(msg)
+    }
+
+    async fn on_splice_confirm(
+        &self,
+        splice_confirm: &SpliceConfirm,
+        peer_id: &PublicKey,
+    ) -> Result<(), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &splice_confirm.channel_id, Signed, Some(*peer_id))?;
+
+        let splice_tx = crate::channel_updater::splice_channel_on_confirm(
+            &self.secp,
+            &mut signed_channel,
+            splice_confirm,
+            &self.signer_provider,
+        )?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        self.blockchain.send_transaction(&splice_tx).await?;
+
+        Ok(())
+    }
+
+    fn on_settle_accept(
+        &self,
+        settle_accept: &SettleAccept,
+        peer_id: &PublicKey,
+    ) -> Result<SettleConfirm, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &settle_accept.channel_id, Signed, Some(*peer_id))?;
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let msg = crate::channel_updater::settle_channel_confirm(
+            &self.secp,
+            &mut signed_channel,
+            settle_accept,
+            cet_nsequence,
+            0,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+            &self.chain_monitor,
+        )?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok(msg)
+    }
+
+    async fn on_settle_confirm(
+        &self,
+        settle_confirm: &SettleConfirm,
+        peer_id: &PublicKey,
+    ) -> Result<SettleFinalize, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &settle_confirm.channel_id, Signed, Some(*peer_id))?;
+        let &own_payout = get_signed_channel_state!(signed_channel, SettledAccepted, own_payout)?;
+        let (prev_buffer_tx, own_buffer_adaptor_signature, is_offer, signed_contract_id) = get_signed_channel_rollback_state!(
+            signed_channel,
+            Established,
+            buffer_transaction,
+            own_buffer_adaptor_signature,
+            is_offer,
+            signed_contract_id
+        )?;
+
+        let prev_buffer_tx = prev_buffer_tx.clone();
+        let own_buffer_adaptor_signature = *own_buffer_adaptor_signature;
+        let is_offer = *is_offer;
+        let signed_contract_id = *signed_contract_id;
+
+        let msg = crate::channel_updater::settle_channel_finalize(
+            &self.secp,
+            &mut signed_channel,
+            settle_confirm,
+            &self.signer_provider,
+        )?;
+
+        let tx_type = TxType::Revoked {
+            update_idx: signed_channel.update_idx + 1,
+            own_adaptor_signature: own_buffer_adaptor_signature,
+            is_offer,
+            revoked_tx_type: RevokedTxType::Buffer,
+        };
+
+        self.chain_monitor.lock().unwrap().add_tx(
+            prev_buffer_tx.txid(),
+            ChannelInfo {
+                channel_id: signed_channel.channel_id,
+                tx_type,
+            },
+        );
+
+        self.export_punishment_package(&signed_channel, &prev_buffer_tx, tx_type)
+            .await?;
+
+        let closed_contract = Contract::Closed(self.get_collaboratively_closed_contract(
+            &signed_contract_id,
+            own_payout,
+            true,
+        )?);
+        let events = [
+            Event::ChannelSettled {
+                channel_id: signed_channel.channel_id,
+                own_payout,
+            },
+            Event::from_closed_contract(&closed_contract).expect("a closed contract"),
+        ];
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), Some(closed_contract))?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+        self.push_events(events)?;
+
+        Ok(msg)
+    }
+
+    async fn on_settle_finalize(
+        &self,
+        settle_finalize: &SettleFinalize,
+        peer_id: &PublicKey,
+    ) -> Result<(), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &settle_finalize.channel_id, Signed, Some(*peer_id))?;
+        let &own_payout = get_signed_channel_state!(signed_channel, SettledConfirmed, own_payout)?;
+        let (buffer_tx, own_buffer_adaptor_signature, is_offer, signed_contract_id) = get_signed_channel_rollback_state!(
+            signed_channel,
+            Established,
+            buffer_transaction,
+            own_buffer_adaptor_signature,
+            is_offer,
+            signed_contract_id
+        )?;
+
+        let own_buffer_adaptor_signature = *own_buffer_adaptor_signature;
+        let is_offer = *is_offer;
+        let buffer_tx = buffer_tx.clone();
+        let signed_contract_id = *signed_contract_id;
+
+        crate::channel_updater::settle_channel_on_finalize(
+            &self.secp,
+            &mut signed_channel,
+            settle_finalize,
+        )?;
+
+        let tx_type = TxType::Revoked {
+            update_idx: signed_channel.update_idx + 1,
+            own_adaptor_signature: own_buffer_adaptor_signature,
+            is_offer,
+            revoked_tx_type: RevokedTxType::Buffer,
+        };
+
+        self.chain_monitor.lock().unwrap().add_tx(
+            buffer_tx.txid(),
+            ChannelInfo {
+                channel_id: signed_channel.channel_id,
+                tx_type,
+            },
+        );
+
+        self.export_punishment_package(&signed_channel, &buffer_tx, tx_type)
+            .await?;
+
+        let closed_contract = Contract::Closed(self.get_collaboratively_closed_contract(
+            &signed_contract_id,
+            own_payout,
+            true,
+        )?);
+        let events = [
+            Event::ChannelSettled {
+                channel_id: signed_channel.channel_id,
+                own_payout,
+            },
+            Event::from_closed_contract(&closed_contract).expect("a closed contract"),
+        ];
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), Some(closed_contract))?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+        self.push_events(events)?;
+
+        Ok(())
+    }
+
+    fn on_renew_offer(
+        &self,
+        renew_offer: &RenewOffer,
+        peer_id: &PublicKey,
+    ) -> Result<Option<Reject>, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &renew_offer.channel_id, Signed, Some(*peer_id))?;
+
+        // Received a renew offer when we already sent one, we reject it.
+        if let SignedChannelState::RenewOffered { is_offer, .. } = signed_channel.state {
+            if is_offer {
+                return Ok(Some(Reject {
+                    channel_id: renew_offer.channel_id,
+                }));
+            }
+        }
+
+        let offered_contract = crate::channel_updater::on_renew_offer(
+            &mut signed_channel,
+            renew_offer,
+            self.config.peer_timeout,
+            self.config.min_counter_offer_fee_share,
+            &self.time,
+        )?;
+
+        self.store.create_contract(&offered_contract)?;
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok(None)
+    }
+
+    async fn on_renew_accept(
+        &self,
+        renew_accept: &RenewAccept,
+        peer_id: &PublicKey,
+    ) -> Result<RenewConfirm, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &renew_accept.channel_id, Signed, Some(*peer_id))?;
+        let offered_contract_id = signed_channel.get_contract_id().ok_or_else(|| {
+            Error::InvalidState(
+                "Expected to be in a state with an associated contract id but was not.".to_string(),
+            )
+        })?;
+
+        let offered_contract =
+            get_contract_in_state!(self, &offered_contract_id, Offered, Some(*peer_id))?;
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let (signed_contract, msg) = crate::channel_updater::verify_renew_accept_and_confirm(
+            &self.secp,
+            renew_accept,
+            &mut signed_channel,
+            &offered_contract,
+            cet_nsequence,
+            self.config.peer_timeout,
+            &self.wallet,
+            &self.signer_provider,
+            &self.time,
+        )
+        .await?;
+
+        // Directly confirmed as we're in a channel the fund tx is already confirmed.
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Confirmed(signed_contract)),
+        )?;
+
+        Ok(msg)
+    }
+
+    async fn on_renew_confirm(
+        &self,
+        renew_confirm: &RenewConfirm,
+        peer_id: &PublicKey,
+    ) -> Result<RenewFinalize, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &renew_confirm.channel_id, Signed, Some(*peer_id))?;
+        let own_payout = get_signed_channel_state!(signed_channel, RenewAccepted, own_payout)?;
+        let contract_id = signed_channel.get_contract_id().ok_or_else(|| {
+            Error::InvalidState(
+                "Expected to be in a state with an associated contract id but was not.".to_string(),
+            )
+        })?;
+
+        let (tx_type, prev_tx, closed_contract) = match signed_channel
+            .roll_back_state
+            .as_ref()
+            .expect("to have a rollback state")
+        {
+            SignedChannelState::Established {
+                own_buffer_adaptor_signature,
+                buffer_transaction,
+                signed_contract_id,
+                ..
+            } => {
+                let closed_contract = Contract::Closed(self.get_collaboratively_closed_contract(
+                    signed_contract_id,
+                    *own_payout,
+                    true,
+                )?);
+                (
+                    TxType::Revoked {
+                        update_idx: signed_channel.update_idx,
+                        own_adaptor_signature: *own_buffer_adaptor_signature,
+                        is_offer: false,
+                        revoked_tx_type: RevokedTxType::Buffer,
+                    },
+                    buffer_transaction.clone(),
+                    Some(closed_contract),
+                )
+            }
+            SignedChannelState::Settled {
+                settle_tx,
+                own_settle_adaptor_signature,
+                ..
+            } => (
+                TxType::Revoked {
+                    update_idx: signed_channel.update_idx,
+                    own_adaptor_signature: *own_settle_adaptor_signature,
+                    is_offer: false,
+                    revoked_tx_type: RevokedTxType::Settle,
+                },
+                settle_tx.clone(),
+                None,
+            ),
+            s => {
+                return Err(Error::InvalidState(format!(
+                    "Expected rollback state Established or Revoked but found {s:?}"
+                )))
+            }
+        };
+        let accepted_contract =
+            get_contract_in_state!(self, &contract_id, Accepted, Some(*peer_id))?;
+
+        let (signed_contract, msg) = crate::channel_updater::verify_renew_confirm_and_finalize(
+            &self.secp,
+            &mut signed_channel,
+            &accepted_contract,
+            renew_confirm,
+            self.config.peer_timeout,
+            &self.time,
+            &self.wallet,
+            &self.signer_provider,
+            &self.chain_monitor,
+        )
+        .await?;
+
+        self.chain_monitor.lock().unwrap().add_tx(
+            prev_tx.txid(),
+            ChannelInfo {
+                channel_id: signed_channel.channel_id,
+                tx_type,
+            },
+        );
+
+        self.export_punishment_package(&signed_channel, &prev_tx, tx_type)
+            .await?;
+
+        // Directly confirmed as we're in a channel the fund tx is already confirmed.
+        self.store.upsert_channel(
+            Channel::Signed(signed_channel),
+            Some(Contract::Confirmed(signed_contract)),
+        )?;
+
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        if let Some(closed_contract) = closed_contract {
+            self.store.update_contract(&closed_contract)?;
+            self.push_events(Event::from_closed_contract(&closed_contract))?;
+        }
+
+        Ok(msg)
+    }
+
+    async fn on_renew_finalize(
+        &self,
+        renew_finalize: &RenewFinalize,
+        peer_id: &PublicKey,
+    ) -> Result<RenewRevoke, Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &renew_finalize.channel_id, Signed, Some(*peer_id))?;
+        let own_payout = get_signed_channel_state!(signed_channel, RenewConfirmed, own_payout)?;
+
+        let (tx_type, prev_tx, closed_contract) = match signed_channel
+            .roll_back_state
+            .as_ref()
+            .expect("to have a rollback state")
+        {
+            SignedChannelState::Established {
+                own_buffer_adaptor_signature,
+                buffer_transaction,
+                signed_contract_id,
+                ..
+            } => {
+                let closed_contract = self.get_collaboratively_closed_contract(
+                    signed_contract_id,
+                    *own_payout,
+                    true,
+                )?;
+                (
+                    TxType::Revoked {
+                        update_idx: signed_channel.update_idx,
+                        own_adaptor_signature: *own_buffer_adaptor_signature,
+                        is_offer: false,
+                        revoked_tx_type: RevokedTxType::Buffer,
+                    },
+                    buffer_transaction.clone(),
+                    Some(Contract::Closed(closed_contract)),
+                )
+            }
+            SignedChannelState::Settled {
+                settle_tx,
+                own_settle_adaptor_signature,
+                ..
+            } => (
+                TxType::Revoked {
+                    update_idx: signed_channel.update_idx,
+                    own_adaptor_signature: *own_settle_adaptor_signature,
+                    is_offer: false,
+                    revoked_tx_type: RevokedTxType::Settle,
+                },
+                settle_tx.clone(),
+                None,
+            ),
+            s => {
+                return Err(Error::InvalidState(format!(
+                    "Expected rollback state of Established or Settled but was {s:?}"
+                )))
+            }
+        };
+
+        let msg = crate::channel_updater::renew_channel_on_finalize(
+            &self.secp,
+            &mut signed_channel,
+            renew_finalize,
+            &self.signer_provider,
+        )?;
+
+        self.chain_monitor.lock().unwrap().add_tx(
+            prev_tx.txid(),
+            ChannelInfo {
+                channel_id: signed_channel.channel_id,
+                tx_type,
+            },
+        );
+
+        self.export_punishment_package(&signed_channel, &prev_tx, tx_type)
+            .await?;
+
+        let buffer_tx =
+            get_signed_channel_state!(signed_channel, Established, ref buffer_transaction)?;
+
+        self.chain_monitor.lock().unwrap().add_tx(
+            buffer_tx.txid(),
+            ChannelInfo {
+                channel_id: signed_channel.channel_id,
+                tx_type: TxType::BufferTx,
+            },
+        );
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        if let Some(closed_contract) = closed_contract {
+            self.store.update_contract(&closed_contract)?;
+            self.push_events(Event::from_closed_contract(&closed_contract))?;
+        }
+
+        Ok(msg)
+    }
+
+    fn on_renew_revoke(
+        &self,
+        renew_revoke: &RenewRevoke,
+        peer_id: &PublicKey,
+    ) -> Result<(), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &renew_revoke.channel_id, Signed, Some(*peer_id))?;
+
+        crate::channel_updater::renew_channel_on_revoke(
+            &self.secp,
+            &mut signed_channel,
+            renew_revoke,
+        )?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)
+    }
+
+    fn on_collaborative_close_offer(
+        &self,
+        close_offer: &CollaborativeCloseOffer,
+        peer_id: &PublicKey,
+    ) -> Result<(), Error> {
+        let mut signed_channel =
+            get_channel_in_state!(self, &close_offer.channel_id, Signed, Some(*peer_id))?;
+
+        crate::channel_updater::on_collaborative_close_offer(
+            &self.secp,
+            &mut signed_channel,
+            close_offer,
+            self.config.min_counter_offer_fee_share,
+            self.config.peer_timeout,
+            &self.time,
+        )?;
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        Ok(())
+    }
+
+    async fn on_channel_reestablish(
+        &self,
+        channel_reestablish: &ChannelReestablish,
+        peer_id: &PublicKey,
+    ) -> Result<Option<DlcMessage>, Error> {
+        let mut signed_channel = get_channel_in_state!(
+            self,
+            &channel_reestablish.channel_id,
+            Signed,
+            Some(*peer_id)
+        )?;
+
+        let contract = match signed_channel.state {
+            SignedChannelState::RenewOffered {
+                offered_contract_id: contract_id,
+                ..
+            }
+            | SignedChannelState::RenewAccepted { contract_id, .. }
+            | SignedChannelState::RenewConfirmed { contract_id, .. } => {
+                self.store.get_contract(&contract_id)?
+            }
+            _ => None,
+        };
+
+        let had_roll_back_state = signed_channel.roll_back_state.is_some();
+        let splice_inputs = get_splice_inputs(&signed_channel.state);
+
+        let cet_nsequence = signed_channel.cet_nsequence;
+        let msg = crate::channel_updater::on_channel_reestablish(
+            &self.secp,
+            &mut signed_channel,
+            channel_reestablish,
+            contract.as_ref(),
+            cet_nsequence,
+            self.config.peer_timeout,
+            &self.signer_provider,
+            &self.time,
+        )?;
+
+        // A pending renewal that was rolled back will never complete.
+        let rejected_contract = if had_roll_back_state && signed_channel.roll_back_state.is_none() {
+            match contract {
+                Some(Contract::Offered(o)) => Some(Contract::Rejected(o)),
+                Some(Contract::Accepted(a)) => Some(Contract::Rejected(a.offered_contract)),
+                Some(Contract::Signed(s)) => {
+                    Some(Contract::Rejected(s.accepted_contract.offered_contract))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        if !signed_channel
+            .state
+            .is_of_type(&SignedChannelStateType::SpliceOffered)
+        {
+            self.wallet.unreserve_utxos(&splice_inputs).await?;
+        }
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), rejected_contract)?;
+
+        Ok(msg)
+    }
+
+    async fn on_reject(&self, reject: &Reject, counter_party: &PublicKey) -> Result<(), Error> {
+        let channel = self.store.get_channel(&reject.channel_id)?;
+
+        if let Some(channel) = channel {
+            if channel.get_counter_party_id() != *counter_party {
+                return Err(Error::InvalidParameters(format!(
+                    "Peer {:02x?} is not involved with {} {:02x?}.",
+                    counter_party,
+                    stringify!(Channel),
+                    channel.get_id()
+                )));
+            }
+            match channel {
+                Channel::Offered(offered_channel) => {
+                    let offered_contract = get_contract_in_state!(
+                        self,
+                        &offered_channel.offered_contract_id,
+                        Offered,
+                        None as Option<PublicKey>
+                    )?;
+                    let utxos = offered_contract
+                        .funding_inputs
+                        .iter()
+                        .map(|funding_input| {
+                            let txid = Transaction::consensus_decode(
+                                &mut funding_input.prev_tx.as_slice(),
+                            )
+                            .expect("Transaction Decode Error")
+                            .txid();
+                            let vout = funding_input.prev_tx_vout;
+                            OutPoint { txid, vout }
+                        })
+                        .collect::<Vec<_>>();
+
+                    self.wallet.unreserve_utxos(&utxos).await?;
+
+                    // remove rejected channel, since nothing has been confirmed on chain yet.
+                    self.store.upsert_channel(
+                        Channel::Cancelled(offered_channel),
+                        Some(Contract::Rejected(offered_contract)),
+                    )?;
+                }
+                Channel::Signed(mut signed_channel) => {
+                    let contract = match signed_channel.state {
+                        SignedChannelState::RenewOffered {
+                            offered_contract_id,
+                            ..
+                        } => {
+                            let offered_contract = get_contract_in_state!(
+                                self,
+                                &offered_contract_id,
+                                Offered,
+                                None::<PublicKey>
+                            )?;
+                            Some(Contract::Rejected(offered_contract))
+                        }
+                        _ => None,
+                    };
+
+                    let splice_inputs = get_splice_inputs(&signed_channel.state);
+
+                    crate::channel_updater::on_reject(&mut signed_channel)?;
+
+                    self.wallet.unreserve_utxos(&splice_inputs).await?;
+
+                    self.store
+                        .upsert_channel(Channel::Signed(signed_channel), contract)?;
+                }
+                channel => {
+                    return Err(Error::InvalidState(format!(
+                        "Not in a state adequate to receive a reject message. {:?}",
+                        channel
+                    )))
+                }
+            }
+        } else {
+            warn!(
+                "Couldn't find rejected dlc channel with id: {}",
+                reject.channel_id.to_lower_hex_string()
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn channel_checks(&self) -> Result<(), Error> {
+        let established_closing_channels = self
+            .store
+            .get_signed_channels(Some(SignedChannelStateType::Closing))?;
+
+        for channel in established_closing_channels {
+            if let Err(e) = self.try_finalize_closing_established_channel(channel).await {
+                error!("Error trying to close established channel: {}", e);
+            }
+        }
+
+        if let Err(e) = self.check_for_timed_out_channels().await {
+            error!("Error checking timed out channels {}", e);
+        }
+        self.check_for_watched_tx().await
+    }
+
+    async fn check_for_timed_out_channels(&self) -> Result<(), Error> {
+        check_for_timed_out_channels!(self, RenewOffered);
+        check_for_timed_out_channels!(self, RenewAccepted);
+        check_for_timed_out_channels!(self, RenewConfirmed);
+        check_for_timed_out_channels!(self, SettledOffered);
+        check_for_timed_out_channels!(self, SettledAccepted);
+        check_for_timed_out_channels!(self, SettledConfirmed);
+        check_for_timed_out_channels!(self, SpliceOffered);
+        check_for_timed_out_channels!(self, SpliceAccepted);
+
+        Ok(())
+    }
+
+    pub(crate) async fn process_watched_txs(
+        &self,
+        watched_txs: Vec<(Transaction, ChannelInfo, u64)>,
+    ) -> Result<(), Error> {
+        for (tx, channel_info, height) in watched_txs {
+            let mut signed_channel = match get_channel_in_state!(
+                self,
+                &channel_info.channel_id,
+                Signed,
+                None as Option<PublicKey>
+            ) {
+                Ok(c) => c,
+                Err(e) => {
+                    error!(
+                        "Could not retrieve channel {:?}: {}",
+                        channel_info.channel_id, e
+                    );
+                    continue;
+                }
+            };
+
+            if let (TxType::BufferTx, SignedChannelState::Closing { .. }) =
+                (&channel_info.tx_type, &signed_channel.state)
+            {
+                continue;
+            }
+
+            self.snapshot_channel(&signed_channel, height)?;
+
+            let persist = match channel_info.tx_type {
+                TxType::BufferTx => {
+                    // TODO(tibo): should only considered closed after some confirmations.
+                    let contract_id = signed_channel
+                        .get_contract_id()
+                        .expect("to have a contract id");
+                    let mut state = SignedChannelState::Closing {
+                        buffer_transaction: tx.clone(),
+                        is_initiator: false,
+                        contract_id,
+                        keys_id: signed_channel.keys_id().ok_or_else(|| {
+                            Error::InvalidState("Expected to have keys_id.".to_string())
+                        })?,
+                    };
+                    std::mem::swap(&mut signed_channel.state, &mut state);
+
+                    signed_channel.roll_back_state = Some(state);
+
+                    self.store
+                        .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+                    true
+                }
+                TxType::Revoked {
+                    update_idx,
+                    own_adaptor_signature,
+                    is_offer,
+                    revoked_tx_type,
+                } => {
+                    let (
+                        own_revocation_params,
+                        counter_revocation_params,
+                        own_sk,
+                        counter_revocation_sk,
+                    ) = self.get_revoked_state_keys(&signed_channel, update_idx)?;
+
+                    let witness = if signed_channel.own_params.fund_pubkey
                         < signed_channel.counter_params.fund_pubkey
                     {
                         tx.input[0].witness.to_vec().remove(1)
@@ -2204,381 +4420,1183 @@ where
                         tx.input[0].witness.to_vec().remove(2)
                     };
 
-                    let sig_data = witness
-                        .iter()
-                        .take(witness.len() - 1)
-                        .cloned()
-                        .collect::<Vec<_>>();
-                    let own_sig = Signature::from_der(&sig_data)?;
+                    let sig_data = witness
+                        .iter()
+                        .take(witness.len() - 1)
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let own_sig = Signature::from_der(&sig_data)?;
+
+                    let counter_sk = own_adaptor_signature.recover(
+                        &self.secp,
+                        &own_sig,
+                        &counter_revocation_params.publish_pk.inner,
+                    )?;
+
+                    let (offer_params, accept_params) = if is_offer {
+                        (&own_revocation_params, &counter_revocation_params)
+                    } else {
+                        (&counter_revocation_params, &own_revocation_params)
+                    };
+
+                    let fee_rate_per_vb: u64 = (self.fee_estimator.get_est_sat_per_1000_weight(
+                        lightning::chain::chaininterface::ConfirmationTarget::OnChainSweep,
+                    ) / 250)
+                        .into();
+
+                    let signed_tx = match revoked_tx_type {
+                        RevokedTxType::Buffer => {
+                            dlc::channel::create_and_sign_punish_buffer_transaction(
+                                &self.secp,
+                                offer_params,
+                                accept_params,
+                                &own_sk,
+                                &counter_sk,
+                                &counter_revocation_sk,
+                                &tx,
+                                &self.wallet.get_new_address().await?,
+                                0,
+                                fee_rate_per_vb,
+                            )?
+                        }
+                        RevokedTxType::Settle => {
+                            dlc::channel::create_and_sign_punish_settle_transaction(
+                                &self.secp,
+                                offer_params,
+                                accept_params,
+                                &own_sk,
+                                &counter_sk,
+                                &counter_revocation_sk,
+                                &tx,
+                                &self.wallet.get_new_address().await?,
+                                signed_channel.cet_nsequence,
+                                0,
+                                fee_rate_per_vb,
+                                is_offer,
+                            )?
+                        }
+                    };
+
+                    self.blockchain.send_transaction(&signed_tx).await?;
+
+                    let closed_channel = Channel::ClosedPunished(ClosedPunishedChannel {
+                        counter_party: signed_channel.counter_party,
+                        temporary_channel_id: signed_channel.temporary_channel_id,
+                        channel_id: signed_channel.channel_id,
+                        punish_txid: signed_tx.txid(),
+                    });
+                    self.push_events(Some(Event::ChannelPunished {
+                        channel_id: signed_channel.channel_id,
+                        punish_txid: signed_tx.txid(),
+                    }))?;
+
+                    //TODO(tibo): should probably make sure the tx is confirmed somewhere before
+                    //stop watching the cheating tx.
+                    self.chain_monitor
+                        .lock()
+                        .unwrap()
+                        .cleanup_channel(signed_channel.channel_id);
+                    self.store.upsert_channel(closed_channel, None)?;
+                    true
+                }
+                TxType::CollaborativeClose => {
+                    if let Some(SignedChannelState::Established {
+                        signed_contract_id, ..
+                    }) = signed_channel.roll_back_state
+                    {
+                        let counter_payout = get_signed_channel_state!(
+                            signed_channel,
+                            CollaborativeCloseOffered,
+                            counter_payout
+                        )?;
+                        let closed_contract = self.get_collaboratively_closed_contract(
+                            &signed_contract_id,
+                            *counter_payout,
+                            false,
+                        )?;
+                        let closed_contract = Contract::Closed(closed_contract);
+                        self.store.update_contract(&closed_contract)?;
+                        self.push_events(Event::from_closed_contract(&closed_contract))?;
+                    }
+                    let closed_channel = Channel::CollaborativelyClosed(ClosedChannel {
+                        counter_party: signed_channel.counter_party,
+                        temporary_channel_id: signed_channel.temporary_channel_id,
+                        channel_id: signed_channel.channel_id,
+                    });
+                    self.chain_monitor
+                        .lock()
+                        .unwrap()
+                        .cleanup_channel(signed_channel.channel_id);
+                    self.store.upsert_channel(closed_channel, None)?;
+                    true
+                }
+                TxType::SettleTx => {
+                    let closed_channel = Channel::CounterClosed(ClosedChannel {
+                        counter_party: signed_channel.counter_party,
+                        temporary_channel_id: signed_channel.temporary_channel_id,
+                        channel_id: signed_channel.channel_id,
+                    });
+                    self.chain_monitor
+                        .lock()
+                        .unwrap()
+                        .cleanup_channel(signed_channel.channel_id);
+                    self.store.upsert_channel(closed_channel, None)?;
+                    true
+                }
+                TxType::SpliceTx => {
+                    match crate::channel_updater::on_splice_tx_confirmed(
+                        &mut signed_channel,
+                        &tx,
+                        &self.chain_monitor,
+                    ) {
+                        Ok(own_payout) => {
+                            let channel_id = signed_channel.channel_id;
+                            self.store
+                                .upsert_channel(Channel::Signed(signed_channel), None)?;
+                            self.push_events(Some(Event::ChannelSpliced {
+                                channel_id,
+                                own_payout,
+                            }))?;
+                            true
+                        }
+                        Err(e) => {
+                            error!("Could not process confirmed splice transaction: {}", e);
+                            false
+                        }
+                    }
+                }
+                TxType::Cet => {
+                    let contract_id = signed_channel.get_contract_id();
+                    let closed_channel = {
+                        match &signed_channel.state {
+                            SignedChannelState::Closing { is_initiator, .. } => {
+                                if *is_initiator {
+                                    Channel::Closed(ClosedChannel {
+                                        counter_party: signed_channel.counter_party,
+                                        temporary_channel_id: signed_channel.temporary_channel_id,
+                                        channel_id: signed_channel.channel_id,
+                                    })
+                                } else {
+                                    Channel::CounterClosed(ClosedChannel {
+                                        counter_party: signed_channel.counter_party,
+                                        temporary_channel_id: signed_channel.temporary_channel_id,
+                                        channel_id: signed_channel.channel_id,
+                                    })
+                                }
+                            }
+                            _ => {
+                                error!("Saw spending of buffer transaction without being in closing state");
+                                Channel::Closed(ClosedChannel {
+                                    counter_party: signed_channel.counter_party,
+                                    temporary_channel_id: signed_channel.temporary_channel_id,
+                                    channel_id: signed_channel.channel_id,
+                                })
+                            }
+                        }
+                    };
+
+                    self.chain_monitor
+                        .lock()
+                        .unwrap()
+                        .cleanup_channel(signed_channel.channel_id);
+
+                    let pre_closed_contract = contract_id
+                        .map(|contract_id| {
+                            self.store.get_contract(&contract_id).map(|contract| {
+                                contract.map(|contract| match contract {
+                                    Contract::Confirmed(signed_contract) => {
+                                        Some(Contract::PreClosed(PreClosedContract {
+                                            signed_contract,
+                                            attestations: None,
+                                            signed_cet: tx.clone(),
+                                        }))
+                                    }
+                                    _ => None,
+                                })
+                            })
+                        })
+                        .transpose()?
+                        .flatten()
+                        .flatten();
+
+                    self.store
+                        .upsert_channel(closed_channel, pre_closed_contract)?;
+
+                    true
+                }
+            };
+
+            if persist {
+                self.store
+                    .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the revocation parameters of the local and counter party for the
+    /// revoked state of the given channel with the given update index, together
+    /// with the own secret key and the revocation secret key of the counter
+    /// party for that state.
+    fn get_revoked_state_keys(
+        &self,
+        signed_channel: &SignedChannel,
+        update_idx: u64,
+    ) -> Result<(RevokeParams, RevokeParams, SecretKey, SecretKey), Error> {
+        let secret = signed_channel
+            .counter_party_commitment_secrets
+            .get_secret(update_idx)
+            .ok_or_else(|| {
+                Error::InvalidState(format!(
+                    "Could not retrieve the counter per update secret for update {update_idx}."
+                ))
+            })?;
+        let counter_per_update_secret = SecretKey::from_slice(&secret)
+            .expect("to be able to parse the counter per update secret.");
+
+        let per_update_seed_pk = signed_channel.own_per_update_seed;
+
+        let per_update_seed_sk = self
+            .signer_provider
+            .get_secret_key_for_pubkey(&per_update_seed_pk)?;
+
+        let per_update_secret = SecretKey::from_slice(&build_commitment_secret(
+            per_update_seed_sk.as_ref(),
+            update_idx,
+        ))
+        .expect("a valid secret key.");
+
+        let per_update_point = PublicKey::from_secret_key(&self.secp, &per_update_secret);
+
+        let own_revocation_params = signed_channel.own_points.get_revokable_params(
+            &self.secp,
+            &signed_channel.counter_points.revocation_basepoint,
+            &per_update_point,
+        );
+
+        let counter_per_update_point =
+            PublicKey::from_secret_key(&self.secp, &counter_per_update_secret);
+
+        let base_own_sk = self
+            .signer_provider
+            .get_secret_key_for_pubkey(&signed_channel.own_points.own_basepoint)?;
+
+        let own_sk = derive_private_key(&self.secp, &per_update_point, &base_own_sk);
+
+        let counter_revocation_params = signed_channel.counter_points.get_revokable_params(
+            &self.secp,
+            &signed_channel.own_points.revocation_basepoint,
+            &counter_per_update_point,
+        );
+
+        let own_revocation_base_secret = &self
+            .signer_provider
+            .get_secret_key_for_pubkey(&signed_channel.own_points.revocation_basepoint)?;
+
+        let counter_revocation_sk = derive_private_revocation_key(
+            &self.secp,
+            &counter_per_update_secret,
+            own_revocation_base_secret,
+        );
+
+        Ok((
+            own_revocation_params,
+            counter_revocation_params,
+            own_sk,
+            counter_revocation_sk,
+        ))
+    }
+
+    /// Returns a [`PunishmentPackage`] enabling a watchtower to punish the
+    /// publication of the given revoked transaction of the given channel.
+    async fn get_punishment_package(
+        &self,
+        signed_channel: &SignedChannel,
+        revoked_tx: &Transaction,
+        tx_type: TxType,
+    ) -> Result<PunishmentPackage, Error> {
+        let (update_idx, own_adaptor_signature, is_offer, revoked_tx_type) = match tx_type {
+            TxType::Revoked {
+                update_idx,
+                own_adaptor_signature,
+                is_offer,
+                revoked_tx_type,
+            } => (update_idx, own_adaptor_signature, is_offer, revoked_tx_type),
+            _ => {
+                return Err(Error::InvalidParameters(
+                    "Expected a revoked transaction.".to_string(),
+                ))
+            }
+        };
+
+        let (own_revocation_params, counter_revocation_params, own_sk, counter_revocation_sk) =
+            self.get_revoked_state_keys(signed_channel, update_idx)?;
+
+        let (offer_params, accept_params) = if is_offer {
+            (&own_revocation_params, &counter_revocation_params)
+        } else {
+            (&counter_revocation_params, &own_revocation_params)
+        };
+
+        let fee_rate_per_vb: u64 = (self.fee_estimator.get_est_sat_per_1000_weight(
+            lightning::chain::chaininterface::ConfirmationTarget::OnChainSweep,
+        ) / 250)
+            .into();
+        let dest_address = self.wallet.get_new_address().await?;
+        let csv_timelock = signed_channel.cet_nsequence;
+
+        let (punish_tx, own_signatures, counter_revoke_signatures) = match revoked_tx_type {
+            RevokedTxType::Buffer => {
+                let punish_tx = dlc::channel::create_punish_buffer_transaction(
+                    offer_params,
+                    accept_params,
+                    revoked_tx,
+                    &dest_address,
+                    0,
+                    fee_rate_per_vb,
+                )?;
+                let mut sigs = [&own_sk, &counter_revocation_sk]
+                    .iter()
+                    .map(|sk| {
+                        dlc::channel::sign_punish_buffer_transaction(
+                            &self.secp,
+                            offer_params,
+                            accept_params,
+                            &punish_tx,
+                            revoked_tx,
+                            sk,
+                        )
+                        .map(|sigs| sigs.into_iter().map(|x| x.sig).collect::<Vec<_>>())
+                    })
+                    .collect::<Result<Vec<_>, dlc::Error>>()?;
+                let counter_revoke_signatures = sigs.pop().expect("to have two signatures");
+                let own_signatures = sigs.pop().expect("to have two signatures");
+                (punish_tx, own_signatures, counter_revoke_signatures)
+            }
+            RevokedTxType::Settle => {
+                let punish_tx = dlc::channel::create_punish_settle_transaction(
+                    revoked_tx,
+                    &dest_address,
+                    0,
+                    fee_rate_per_vb,
+                    is_offer,
+                )?;
+                let mut sigs = [&own_sk, &counter_revocation_sk]
+                    .iter()
+                    .map(|sk| {
+                        dlc::channel::sign_punish_settle_transaction(
+                            &self.secp,
+                            offer_params,
+                            accept_params,
+                            &punish_tx,
+                            revoked_tx,
+                            sk,
+                            csv_timelock,
+                            is_offer,
+                        )
+                        .map(|x| vec![x.sig])
+                    })
+                    .collect::<Result<Vec<_>, dlc::Error>>()?;
+                let counter_revoke_signatures = sigs.pop().expect("to have two signatures");
+                let own_signatures = sigs.pop().expect("to have two signatures");
+                (punish_tx, own_signatures, counter_revoke_signatures)
+            }
+        };
+
+        Ok(PunishmentPackage {
+            channel_id: signed_channel.channel_id,
+            revoked_tx_type,
+            is_offer,
+            offer_revoke_keys: offer_params.into(),
+            accept_revoke_keys: accept_params.into(),
+            csv_timelock,
+            own_adaptor_signature,
+            punish_tx,
+            own_signatures,
+            counter_revoke_signatures,
+        })
+    }
+
+    /// If enabled in the configuration, pushes an
+    /// [`Event::PunishmentPackageExported`] for the given revoked transaction
+    /// of the given channel. A failure to build the package is only logged so
+    /// that it does not interrupt the update of the channel.
+    async fn export_punishment_package(
+        &self,
+        signed_channel: &SignedChannel,
+        revoked_tx: &Transaction,
+        tx_type: TxType,
+    ) -> Result<(), Error> {
+        if !self.config.export_punishment_packages {
+            return Ok(());
+        }
+
+        let package = match self
+            .get_punishment_package(signed_channel, revoked_tx, tx_type)
+            .await
+            .and_then(|x| x.encrypt())
+        {
+            Ok(package) => package,
+            Err(e) => {
+                error!(
+                    "Could not export punishment package for channel {}: {}",
+                    signed_channel.channel_id.to_lower_hex_string(),
+                    e
+                );
+                return Ok(());
+            }
+        };
+
+        self.push_events(Some(Event::PunishmentPackageExported {
+            channel_id: signed_channel.channel_id,
+            package,
+        }))
+    }
+
+    /// Keeps the current state of the given channel and of its confirmed
+    /// contract in the chain monitor, so that it can be restored if the block
+    /// at the given height gets disconnected.
+    fn snapshot_channel(&self, signed_channel: &SignedChannel, height: u64) -> Result<(), Error> {
+        let contract_id =
+            signed_channel
+                .get_contract_id()
+                .or_else(|| match &signed_channel.roll_back_state {
+                    Some(SignedChannelState::Established {
+                        signed_contract_id, ..
+                    }) => Some(*signed_contract_id),
+                    _ => None,
+                });
+        let signed_contract = match contract_id {
+            Some(contract_id) => match self.store.get_contract(&contract_id)? {
+                Some(Contract::Confirmed(c)) => Some(c),
+                _ => None,
+            },
+            None => None,
+        };
+        self.chain_monitor.lock().unwrap().add_channel_snapshot(
+            height,
+            signed_channel.clone(),
+            signed_contract,
+        );
+        Ok(())
+    }
+
+    async fn check_for_watched_tx(&self) -> Result<(), Error> {
+        let confirmed_txs = self.chain_monitor.lock().unwrap().confirmed_txs();
+
+        self.process_watched_txs(confirmed_txs).await?;
+
+        self.get_store()
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        Ok(())
+    }
+
+    async fn force_close_channel_internal(
+        &self,
+        mut channel: SignedChannel,
+        is_initiator: bool,
+    ) -> Result<(), Error> {
+        // Intermediate states are rolled back to the last established or
+        // settled state, which is the one that can be closed on chain.
+        while matches!(
+            channel.state,
+            SignedChannelState::SettledOffered { .. }
+                | SignedChannelState::SettledReceived { .. }
+                | SignedChannelState::SettledAccepted { .. }
+                | SignedChannelState::SettledConfirmed { .. }
+                | SignedChannelState::RenewOffered { .. }
+                | SignedChannelState::RenewAccepted { .. }
+                | SignedChannelState::RenewConfirmed { .. }
+                | SignedChannelState::CollaborativeCloseOffered { .. }
+                | SignedChannelState::SpliceOffered { .. }
+                | SignedChannelState::SpliceReceived { .. }
+                | SignedChannelState::SpliceAccepted { .. }
+                | SignedChannelState::SpliceConfirmed { .. }
+        ) {
+            self.wallet
+                .unreserve_utxos(&get_splice_inputs(&channel.state))
+                .await?;
+            channel.state = channel
+                .roll_back_state
+                .take()
+                .expect("to have a rollback state");
+        }
+
+        match &channel.state {
+            SignedChannelState::Established {
+                counter_buffer_adaptor_signature,
+                buffer_transaction,
+                ..
+            } => {
+                let counter_buffer_adaptor_signature = *counter_buffer_adaptor_signature;
+                let buffer_transaction = buffer_transaction.clone();
+                self.initiate_unilateral_close_established_channel(
+                    channel,
+                    is_initiator,
+                    counter_buffer_adaptor_signature,
+                    buffer_transaction,
+                )
+                .await
+            }
+            SignedChannelState::RenewFinalized {
+                buffer_transaction,
+                offer_buffer_adaptor_signature,
+                ..
+            } => {
+                let offer_buffer_adaptor_signature = *offer_buffer_adaptor_signature;
+                let buffer_transaction = buffer_transaction.clone();
+                self.initiate_unilateral_close_established_channel(
+                    channel,
+                    is_initiator,
+                    offer_buffer_adaptor_signature,
+                    buffer_transaction,
+                )
+                .await
+            }
+            SignedChannelState::Settled { .. } => {
+                self.close_settled_channel(channel, is_initiator).await
+            }
+            SignedChannelState::SettledOffered { .. }
+            | SignedChannelState::SettledReceived { .. }
+            | SignedChannelState::SettledAccepted { .. }
+            | SignedChannelState::SettledConfirmed { .. }
+            | SignedChannelState::RenewOffered { .. }
+            | SignedChannelState::RenewAccepted { .. }
+            | SignedChannelState::RenewConfirmed { .. }
+            | SignedChannelState::CollaborativeCloseOffered { .. }
+            | SignedChannelState::SpliceOffered { .. }
+            | SignedChannelState::SpliceReceived { .. }
+            | SignedChannelState::SpliceAccepted { .. }
+            | SignedChannelState::SpliceConfirmed { .. } => unreachable!(),
+            SignedChannelState::Closing { .. } => Err(Error::InvalidState(
+                "Channel is already closing.".to_string(),
+            )),
+        }
+    }
+
+    /// Initiate the unilateral closing of a channel that has been established.
+    async fn initiate_unilateral_close_established_channel(
+        &self,
+        mut signed_channel: SignedChannel,
+        is_initiator: bool,
+        buffer_adaptor_signature: EcdsaAdaptorSignature,
+        buffer_transaction: Transaction,
+    ) -> Result<(), Error> {
+        let keys_id = signed_channel.keys_id().ok_or_else(|| {
+            Error::InvalidState("Expected to be in a state with an associated keys id.".to_string())
+        })?;
+
+        crate::channel_updater::initiate_unilateral_close_established_channel(
+            &self.secp,
+            &mut signed_channel,
+            buffer_adaptor_signature,
+            keys_id,
+            buffer_transaction,
+            &self.signer_provider,
+            is_initiator,
+        )?;
+
+        let buffer_transaction =
+            get_signed_channel_state!(signed_channel, Closing, ref buffer_transaction)?;
+
+        self.blockchain.send_transaction(buffer_transaction).await?;
+
+        self.chain_monitor
+            .lock()
+            .unwrap()
+            .remove_tx(&buffer_transaction.txid());
+
+        self.store
+            .upsert_channel(Channel::Signed(signed_channel), None)?;
+
+        self.store
+            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+
+        Ok(())
+    }
+
+    /// Unilaterally close a channel that has been settled.
+    async fn close_settled_channel(
+        &self,
+        signed_channel: SignedChannel,
+        is_initiator: bool,
+    ) -> Result<(), Error> {
+        let (settle_tx, closed_channel) = crate::channel_updater::close_settled_channel(
+            &self.secp,
+            &signed_channel,
+            &self.signer_provider,
+            is_initiator,
+        )?;
+
+        if self
+            .blockchain
+            .get_transaction_confirmations(&settle_tx.txid())
+            .await
+            .unwrap_or(0)
+            == 0
+        {
+            self.blockchain.send_transaction(&settle_tx).await?;
+        }
+
+        self.chain_monitor
+            .lock()
+            .unwrap()
+            .cleanup_channel(signed_channel.channel_id);
+
+        self.store.upsert_channel(closed_channel, None)?;
+
+        Ok(())
+    }
+
+    fn get_collaboratively_closed_contract(
+        &self,
+        contract_id: &ContractId,
+        payout: u64,
+        is_own_payout: bool,
+    ) -> Result<ClosedContract, Error> {
+        let contract = get_contract_in_state!(self, contract_id, Confirmed, None::<PublicKey>)?;
+        let own_collateral = if contract.accepted_contract.offered_contract.is_offer_party {
+            contract
+                .accepted_contract
+                .offered_contract
+                .offer_params
+                .collateral
+        } else {
+            contract.accepted_contract.accept_params.collateral
+        };
+        let own_payout = if is_own_payout {
+            payout
+        } else {
+            contract.accepted_contract.offered_contract.total_collateral - payout
+        };
+        let pnl = own_payout as i64 - own_collateral as i64;
+        Ok(ClosedContract {
+            attestations: None,
+            signed_cet: None,
+            contract_id: *contract_id,
+            temporary_contract_id: contract.accepted_contract.offered_contract.id,
+            counter_party_id: contract.accepted_contract.offered_contract.counter_party,
+            pnl,
+        })
+    }
+}
+
+struct NoopWaker;
 
-                    let counter_sk = own_adaptor_signature.recover(
-                        &self.secp,
-                        &own_sig,
-                        &counter_revocation_params.publish_pk.inner,
-                    )?;
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
 
-                    let own_revocation_base_secret =
-                        &self.signer_provider.get_secret_key_for_pubkey(
-                            &signed_channel.own_points.revocation_basepoint,
-                        )?;
+/// Runs the given future of the wrapped [`AsyncManager`] of a [`Manager`] to
+/// completion. The futures of the wrapped manager are expected to complete on
+/// their first poll, the wallet, blockchain and oracle calls being made
+/// synchronously through [`SyncWalletAdapter`], [`SyncBlockchainAdapter`] and
+/// [`SyncOracleAdapter`]. An error is returned if the future is not ready when
+/// polled, in which case it is dropped.
+fn block_on<R, Fut: Future<Output = Result<R, Error>>>(future: Fut) -> Result<R, Error> {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+    match Box::pin(future).as_mut().poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => Err(Error::InvalidState(
+            "Expected future to complete without waiting.".to_string(),
+        )),
+    }
+}
 
-                    let counter_revocation_sk = derive_private_revocation_key(
-                        &self.secp,
-                        &counter_per_update_secret,
-                        own_revocation_base_secret,
-                    );
+/// Used to create and update DLCs using blocking wallet, blockchain and oracle
+/// providers. Wraps an [`AsyncManager`] whose futures never need to wait, as
+/// the provider calls are made synchronously.
+///
+/// The blocking methods return an [`Error::InvalidState`] if a future of the
+/// wrapped [`AsyncManager`] is not ready when first polled. This cannot happen
+/// with the providers it is built with, which are all blocking; use an
+/// [`AsyncManager`] directly with providers that need to wait on an
+/// asynchronous runtime.
+pub struct Manager<
+    W: Deref,
+    SP: Deref,
+    B: Deref,
+    S: Deref,
+    O: Deref,
+    T: Deref,
+    F: Deref,
+    X: ContractSigner,
+> where
+    W: Send + Sync,
+    W::Target: Wallet,
+    SP::Target: ContractSignerProvider<Signer = X>,
+    B: Send + Sync,
+    B::Target: Blockchain,
+    S::Target: Storage,
+    O: Send + Sync,
+    O::Target: Oracle,
+    T::Target: Time,
+    F::Target: FeeEstimator,
+{
+    inner: AsyncManager<
+        Box<SyncWalletAdapter<W>>,
+        SP,
+        Box<SyncBlockchainAdapter<B>>,
+        S,
+        Box<SyncOracleAdapter<O>>,
+        T,
+        F,
+        X,
+    >,
+}
 
-                    let (offer_params, accept_params) = if is_offer {
-                        (&own_revocation_params, &counter_revocation_params)
-                    } else {
-                        (&counter_revocation_params, &own_revocation_params)
-                    };
+impl<W: Deref, SP: Deref, B: Deref, S: Deref, O: Deref, T: Deref, F: Deref, X: ContractSigner>
+    Manager<W, Arc<CachedContractSignerProvider<SP, X>>, B, S, O, T, F, X>
+where
+    W: Send + Sync,
+    W::Target: Wallet,
+    SP::Target: ContractSignerProvider<Signer = X>,
+    B: Send + Sync,
+    B::Target: Blockchain,
+    S::Target: Storage,
+    O: Send + Sync,
+    O::Target: Oracle,
+    T::Target: Time,
+    F::Target: FeeEstimator,
+{
+    /// Create a new Manager struct.
+    pub fn new(
+        wallet: W,
+        signer_provider: SP,
+        blockchain: B,
+        store: S,
+        oracles: HashMap<XOnlyPublicKey, O>,
+        time: T,
+        fee_estimator: F,
+        config: ManagerConfig,
+    ) -> Result<Self, Error> {
+        let oracles = oracles
+            .into_iter()
+            .map(|(pubkey, oracle)| (pubkey, Box::new(SyncOracleAdapter(oracle))))
+            .collect();
+        let inner = block_on(AsyncManager::new(
+            Box::new(SyncWalletAdapter(wallet)),
+            signer_provider,
+            Box::new(SyncBlockchainAdapter(blockchain)),
+            store,
+            oracles,
+            time,
+            fee_estimator,
+            config,
+        ))?;
 
-                    let fee_rate_per_vb: u64 = (self.fee_estimator.get_est_sat_per_1000_weight(
-                        lightning::chain::chaininterface::ConfirmationTarget::OnChainSweep,
-                    ) / 250)
-                        .into();
+        Ok(Manager { inner })
+    }
 
-                    let signed_tx = match revoked_tx_type {
-                        RevokedTxType::Buffer => {
-                            dlc::channel::create_and_sign_punish_buffer_transaction(
-                                &self.secp,
-                                offer_params,
-                                accept_params,
-                                &own_sk,
-                                &counter_sk,
-                                &counter_revocation_sk,
-                                &tx,
-                                &self.wallet.get_new_address()?,
-                                0,
-                                fee_rate_per_vb,
-                            )?
-                        }
-                        RevokedTxType::Settle => {
-                            dlc::channel::create_and_sign_punish_settle_transaction(
-                                &self.secp,
-                                offer_params,
-                                accept_params,
-                                &own_sk,
-                                &counter_sk,
-                                &counter_revocation_sk,
-                                &tx,
-                                &self.wallet.get_new_address()?,
-                                CET_NSEQUENCE,
-                                0,
-                                fee_rate_per_vb,
-                                is_offer,
-                            )?
-                        }
-                    };
+    /// See [`AsyncManager::get_store`].
+    pub fn get_store(&self) -> &S {
+        self.inner.get_store()
+    }
 
-                    self.blockchain.send_transaction(&signed_tx)?;
+    /// See [`AsyncManager::get_config`].
+    pub fn get_config(&self) -> &ManagerConfig {
+        self.inner.get_config()
+    }
 
-                    let closed_channel = Channel::ClosedPunished(ClosedPunishedChannel {
-                        counter_party: signed_channel.counter_party,
-                        temporary_channel_id: signed_channel.temporary_channel_id,
-                        channel_id: signed_channel.channel_id,
-                        punish_txid: signed_tx.txid(),
-                    });
+    /// See [`AsyncManager::set_offer_policy`].
+    pub fn set_offer_policy(&mut self, offer_policy: Option<Box<dyn OfferPolicy>>) {
+        self.inner.set_offer_policy(offer_policy)
+    }
 
-                    //TODO(tibo): should probably make sure the tx is confirmed somewhere before
-                    //stop watching the cheating tx.
-                    self.chain_monitor
-                        .lock()
-                        .unwrap()
-                        .cleanup_channel(signed_channel.channel_id);
-                    self.store.upsert_channel(closed_channel, None)?;
-                    true
-                }
-                TxType::CollaborativeClose => {
-                    if let Some(SignedChannelState::Established {
-                        signed_contract_id, ..
-                    }) = signed_channel.roll_back_state
-                    {
-                        let counter_payout = get_signed_channel_state!(
-                            signed_channel,
-                            CollaborativeCloseOffered,
-                            counter_payout
-                        )?;
-                        let closed_contract = self.get_collaboratively_closed_contract(
-                            &signed_contract_id,
-                            *counter_payout,
-                            false,
-                        )?;
-                        self.store
-                            .update_contract(&Contract::Closed(closed_contract))?;
-                    }
-                    let closed_channel = Channel::CollaborativelyClosed(ClosedChannel {
-                        counter_party: signed_channel.counter_party,
-                        temporary_channel_id: signed_channel.temporary_channel_id,
-                        channel_id: signed_channel.channel_id,
-                    });
-                    self.chain_monitor
-                        .lock()
-                        .unwrap()
-                        .cleanup_channel(signed_channel.channel_id);
-                    self.store.upsert_channel(closed_channel, None)?;
-                    true
-                }
-                TxType::SettleTx => {
-                    let closed_channel = Channel::CounterClosed(ClosedChannel {
-                        counter_party: signed_channel.counter_party,
-                        temporary_channel_id: signed_channel.temporary_channel_id,
-                        channel_id: signed_channel.channel_id,
-                    });
-                    self.chain_monitor
-                        .lock()
-                        .unwrap()
-                        .cleanup_channel(signed_channel.channel_id);
-                    self.store.upsert_channel(closed_channel, None)?;
-                    true
-                }
-                TxType::Cet => {
-                    let contract_id = signed_channel.get_contract_id();
-                    let closed_channel = {
-                        match &signed_channel.state {
-                            SignedChannelState::Closing { is_initiator, .. } => {
-                                if *is_initiator {
-                                    Channel::Closed(ClosedChannel {
-                                        counter_party: signed_channel.counter_party,
-                                        temporary_channel_id: signed_channel.temporary_channel_id,
-                                        channel_id: signed_channel.channel_id,
-                                    })
-                                } else {
-                                    Channel::CounterClosed(ClosedChannel {
-                                        counter_party: signed_channel.counter_party,
-                                        temporary_channel_id: signed_channel.temporary_channel_id,
-                                        channel_id: signed_channel.channel_id,
-                                    })
-                                }
-                            }
-                            _ => {
-                                error!("Saw spending of buffer transaction without being in closing state");
-                                Channel::Closed(ClosedChannel {
-                                    counter_party: signed_channel.counter_party,
-                                    temporary_channel_id: signed_channel.temporary_channel_id,
-                                    channel_id: signed_channel.channel_id,
-                                })
-                            }
-                        }
-                    };
+    /// See [`AsyncManager::get_and_clear_pending_events`].
+    pub fn get_and_clear_pending_events(&self) -> Result<Vec<Event>, Error> {
+        self.inner.get_and_clear_pending_events()
+    }
+
+    /// Blocking version of [`AsyncManager::on_dlc_message`].
+    pub fn on_dlc_message(
+        &self,
+        msg: &DlcMessage,
+        counter_party: PublicKey,
+    ) -> Result<Option<DlcMessage>, Error> {
+        block_on(self.inner.on_dlc_message(msg, counter_party))
+    }
+
+    /// Blocking version of [`AsyncManager::send_offer`].
+    pub fn send_offer(
+        &self,
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+    ) -> Result<OfferDlc, Error> {
+        block_on(self.inner.send_offer(contract_input, counter_party))
+    }
+
+    /// Blocking version of [`AsyncManager::send_offer_with_announcements`].
+    pub fn send_offer_with_announcements(
+        &self,
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+        oracle_announcements: Vec<Vec<OracleAnnouncement>>,
+    ) -> Result<OfferDlc, Error> {
+        block_on(self.inner.send_offer_with_announcements(
+            contract_input,
+            counter_party,
+            oracle_announcements,
+        ))
+    }
+
+    /// Blocking version of [`AsyncManager::send_offer_with_fee_payer`].
+    pub fn send_offer_with_fee_payer(
+        &self,
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+        fee_payer: FeePayerInfo,
+    ) -> Result<OfferDlc, Error> {
+        block_on(
+            self.inner
+                .send_offer_with_fee_payer(contract_input, counter_party, fee_payer),
+        )
+    }
+
+    /// Blocking version of [`AsyncManager::accept_contract_offer`].
+    pub fn accept_contract_offer(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<(ContractId, PublicKey, AcceptDlc), Error> {
+        block_on(self.inner.accept_contract_offer(contract_id))
+    }
 
-                    self.chain_monitor
-                        .lock()
-                        .unwrap()
-                        .cleanup_channel(signed_channel.channel_id);
+    /// Blocking version of [`AsyncManager::cancel_offer`].
+    pub fn cancel_offer(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<(CancelOffer, PublicKey), Error> {
+        block_on(self.inner.cancel_offer(contract_id))
+    }
 
-                    let pre_closed_contract = contract_id
-                        .map(|contract_id| {
-                            self.store.get_contract(&contract_id).map(|contract| {
-                                contract.map(|contract| match contract {
-                                    Contract::Confirmed(signed_contract) => {
-                                        Some(Contract::PreClosed(PreClosedContract {
-                                            signed_contract,
-                                            attestations: None,
-                                            signed_cet: tx.clone(),
-                                        }))
-                                    }
-                                    _ => None,
-                                })
-                            })
-                        })
-                        .transpose()?
-                        .flatten()
-                        .flatten();
+    /// See [`AsyncManager::reject_contract_offer`].
+    pub fn reject_contract_offer(
+        &self,
+        contract_id: &ContractId,
+        error_code: RejectErrorCode,
+        reason: String,
+    ) -> Result<(RejectOffer, PublicKey), Error> {
+        self.inner
+            .reject_contract_offer(contract_id, error_code, reason)
+    }
 
-                    self.store
-                        .upsert_channel(closed_channel, pre_closed_contract)?;
+    /// Blocking version of [`AsyncManager::send_batch_offer`].
+    pub fn send_batch_offer(
+        &self,
+        contract_inputs: &[ContractInput],
+        counter_party: PublicKey,
+    ) -> Result<OfferBatchDlc, Error> {
+        block_on(self.inner.send_batch_offer(contract_inputs, counter_party))
+    }
 
-                    true
-                }
-            };
+    /// Blocking version of [`AsyncManager::accept_batch_offer`].
+    pub fn accept_batch_offer(
+        &self,
+        temporary_batch_id: &[u8; 32],
+    ) -> Result<(Vec<ContractId>, PublicKey, AcceptBatchDlc), Error> {
+        block_on(self.inner.accept_batch_offer(temporary_batch_id))
+    }
 
-            if persist {
-                self.store
-                    .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
-            }
-        }
-        Ok(())
+    /// Blocking version of [`AsyncManager::periodic_chain_monitor`].
+    pub fn periodic_chain_monitor(&self) -> Result<(), Error> {
+        block_on(self.inner.periodic_chain_monitor())
     }
 
-    fn check_for_watched_tx(&self) -> Result<(), Error> {
-        let confirmed_txs = self.chain_monitor.lock().unwrap().confirmed_txs();
+    /// Blocking version of [`AsyncManager::periodic_check`].
+    pub fn periodic_check(&self, check_channels: bool) -> Result<(), Error> {
+        block_on(self.inner.periodic_check(check_channels))
+    }
 
-        self.process_watched_txs(confirmed_txs)?;
+    /// See [`AsyncManager::send_fee_bump_offer`].
+    pub fn send_fee_bump_offer(
+        &self,
+        contract_id: &ContractId,
+        fee_rate_per_vb: u64,
+    ) -> Result<(FeeBumpOffer, PublicKey), Error> {
+        self.inner.send_fee_bump_offer(contract_id, fee_rate_per_vb)
+    }
 
-        self.get_store()
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+    /// See [`AsyncManager::accept_fee_bump_offer`].
+    pub fn accept_fee_bump_offer(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<(ContractId, PublicKey, FeeBumpAccept), Error> {
+        self.inner.accept_fee_bump_offer(contract_id)
+    }
 
-        Ok(())
+    /// See [`AsyncManager::offer_contract_close`].
+    pub fn offer_contract_close(
+        &self,
+        contract_id: &ContractId,
+        counter_payout: u64,
+    ) -> Result<(CloseOffer, PublicKey), Error> {
+        self.inner.offer_contract_close(contract_id, counter_payout)
     }
 
-    fn force_close_channel_internal(
+    /// Blocking version of [`AsyncManager::accept_contract_close`].
+    pub fn accept_contract_close(
         &self,
-        mut channel: SignedChannel,
-        is_initiator: bool,
-    ) -> Result<(), Error> {
-        match &channel.state {
-            SignedChannelState::Established {
-                counter_buffer_adaptor_signature,
-                buffer_transaction,
-                ..
-            } => {
-                let counter_buffer_adaptor_signature = *counter_buffer_adaptor_signature;
-                let buffer_transaction = buffer_transaction.clone();
-                self.initiate_unilateral_close_established_channel(
-                    channel,
-                    is_initiator,
-                    counter_buffer_adaptor_signature,
-                    buffer_transaction,
-                )
-            }
-            SignedChannelState::RenewFinalized {
-                buffer_transaction,
-                offer_buffer_adaptor_signature,
-                ..
-            } => {
-                let offer_buffer_adaptor_signature = *offer_buffer_adaptor_signature;
-                let buffer_transaction = buffer_transaction.clone();
-                self.initiate_unilateral_close_established_channel(
-                    channel,
-                    is_initiator,
-                    offer_buffer_adaptor_signature,
-                    buffer_transaction,
-                )
-            }
-            SignedChannelState::Settled { .. } => self.close_settled_channel(channel, is_initiator),
-            SignedChannelState::SettledOffered { .. }
-            | SignedChannelState::SettledReceived { .. }
-            | SignedChannelState::SettledAccepted { .. }
-            | SignedChannelState::SettledConfirmed { .. }
-            | SignedChannelState::RenewOffered { .. }
-            | SignedChannelState::RenewAccepted { .. }
-            | SignedChannelState::RenewConfirmed { .. }
-            | SignedChannelState::CollaborativeCloseOffered { .. } => {
-                channel.state = channel
-                    .roll_back_state
-                    .take()
-                    .expect("to have a rollback state");
-                self.force_close_channel_internal(channel, is_initiator)
-            }
-            SignedChannelState::Closing { .. } => Err(Error::InvalidState(
-                "Channel is already closing.".to_string(),
-            )),
-        }
+        contract_id: &ContractId,
+    ) -> Result<(CloseAccept, PublicKey), Error> {
+        block_on(self.inner.accept_contract_close(contract_id))
     }
 
-    /// Initiate the unilateral closing of a channel that has been established.
-    fn initiate_unilateral_close_established_channel(
+    /// Blocking version of [`AsyncManager::offer_contract_rollover`].
+    pub fn offer_contract_rollover(
         &self,
-        mut signed_channel: SignedChannel,
-        is_initiator: bool,
-        buffer_adaptor_signature: EcdsaAdaptorSignature,
-        buffer_transaction: Transaction,
-    ) -> Result<(), Error> {
-        let keys_id = signed_channel.keys_id().ok_or_else(|| {
-            Error::InvalidState("Expected to be in a state with an associated keys id.".to_string())
-        })?;
+        contract_id: &ContractId,
+        contract_input: &ContractInput,
+    ) -> Result<(RolloverOffer, PublicKey), Error> {
+        block_on(
+            self.inner
+                .offer_contract_rollover(contract_id, contract_input),
+        )
+    }
 
-        crate::channel_updater::initiate_unilateral_close_established_channel(
-            &self.secp,
-            &mut signed_channel,
-            buffer_adaptor_signature,
-            keys_id,
-            buffer_transaction,
-            &self.signer_provider,
-            is_initiator,
-        )?;
+    /// See [`AsyncManager::accept_contract_rollover`].
+    pub fn accept_contract_rollover(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<(ContractId, PublicKey, RolloverAccept), Error> {
+        self.inner.accept_contract_rollover(contract_id)
+    }
 
-        let buffer_transaction =
-            get_signed_channel_state!(signed_channel, Closing, ref buffer_transaction)?;
+    /// See [`AsyncManager::get_fee_payer_psbt`].
+    pub fn get_fee_payer_psbt(
+        &self,
+        contract_id: &ContractId,
+    ) -> Result<PartiallySignedTransaction, Error> {
+        self.inner.get_fee_payer_psbt(contract_id)
+    }
 
-        self.blockchain.send_transaction(buffer_transaction)?;
+    /// Blocking version of [`AsyncManager::sign_with_fee_payer_psbt`].
+    pub fn sign_with_fee_payer_psbt(
+        &self,
+        contract_id: &ContractId,
+        fee_payer_psbt: &PartiallySignedTransaction,
+    ) -> Result<(SignDlc, PublicKey), Error> {
+        block_on(
+            self.inner
+                .sign_with_fee_payer_psbt(contract_id, fee_payer_psbt),
+        )
+    }
 
-        self.chain_monitor
-            .lock()
-            .unwrap()
-            .remove_tx(&buffer_transaction.txid());
+    /// Blocking version of [`AsyncManager::close_confirmed_contract`].
+    pub fn close_confirmed_contract(
+        &self,
+        contract_id: &ContractId,
+        attestations: Vec<(usize, OracleAttestation)>,
+    ) -> Result<Contract, Error> {
+        block_on(
+            self.inner
+                .close_confirmed_contract(contract_id, attestations),
+        )
+    }
 
-        self.store
-            .upsert_channel(Channel::Signed(signed_channel), None)?;
+    /// See [`AsyncManager::on_counterparty_close`].
+    pub fn on_counterparty_close(
+        &self,
+        contract: &SignedContract,
+        closing_tx: Transaction,
+        confirmations: u32,
+    ) -> Result<Contract, Error> {
+        self.inner
+            .on_counterparty_close(contract, closing_tx, confirmations)
+    }
 
-        self.store
-            .persist_chain_monitor(&self.chain_monitor.lock().unwrap())?;
+    /// Blocking version of [`AsyncManager::offer_channel`].
+    pub fn offer_channel(
+        &self,
+        contract_input: &ContractInput,
+        counter_party: PublicKey,
+    ) -> Result<OfferChannel, Error> {
+        block_on(self.inner.offer_channel(contract_input, counter_party))
+    }
 
-        Ok(())
+    /// See [`AsyncManager::reject_channel`].
+    pub fn reject_channel(&self, channel_id: &ChannelId) -> Result<(Reject, PublicKey), Error> {
+        self.inner.reject_channel(channel_id)
     }
 
-    /// Unilaterally close a channel that has been settled.
-    fn close_settled_channel(
+    /// Blocking version of [`AsyncManager::accept_channel`].
+    pub fn accept_channel(
         &self,
-        signed_channel: SignedChannel,
-        is_initiator: bool,
-    ) -> Result<(), Error> {
-        let (settle_tx, closed_channel) = crate::channel_updater::close_settled_channel(
-            &self.secp,
-            &signed_channel,
-            &self.signer_provider,
-            is_initiator,
-        )?;
+        channel_id: &ChannelId,
+    ) -> Result<(AcceptChannel, ChannelId, ContractId, PublicKey), Error> {
+        block_on(self.inner.accept_channel(channel_id))
+    }
 
-        if self
-            .blockchain
-            .get_transaction_confirmations(&settle_tx.txid())
-            .unwrap_or(0)
-            == 0
-        {
-            self.blockchain.send_transaction(&settle_tx)?;
-        }
+    /// Blocking version of [`AsyncManager::force_close_channel`].
+    pub fn force_close_channel(&self, channel_id: &ChannelId) -> Result<(), Error> {
+        block_on(self.inner.force_close_channel(channel_id))
+    }
 
-        self.chain_monitor
-            .lock()
-            .unwrap()
-            .cleanup_channel(signed_channel.channel_id);
+    /// See [`AsyncManager::settle_offer`].
+    pub fn settle_offer(
+        &self,
+        channel_id: &ChannelId,
+        counter_payout: u64,
+    ) -> Result<(SettleOffer, PublicKey), Error> {
+        self.inner.settle_offer(channel_id, counter_payout)
+    }
 
-        self.store.upsert_channel(closed_channel, None)?;
+    /// See [`AsyncManager::accept_settle_offer`].
+    pub fn accept_settle_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(SettleAccept, PublicKey), Error> {
+        self.inner.accept_settle_offer(channel_id)
+    }
+
+    /// Blocking version of [`AsyncManager::renew_offer`].
+    pub fn renew_offer(
+        &self,
+        channel_id: &ChannelId,
+        counter_payout: u64,
+        contract_input: &ContractInput,
+    ) -> Result<(RenewOffer, PublicKey), Error> {
+        block_on(
+            self.inner
+                .renew_offer(channel_id, counter_payout, contract_input),
+        )
+    }
+
+    /// See [`AsyncManager::accept_renew_offer`].
+    pub fn accept_renew_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(RenewAccept, PublicKey), Error> {
+        self.inner.accept_renew_offer(channel_id)
+    }
+
+    /// See [`AsyncManager::reject_renew_offer`].
+    pub fn reject_renew_offer(&self, channel_id: &ChannelId) -> Result<(Reject, PublicKey), Error> {
+        self.inner.reject_renew_offer(channel_id)
+    }
+
+    /// See [`AsyncManager::reject_settle_offer`].
+    pub fn reject_settle_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(Reject, PublicKey), Error> {
+        self.inner.reject_settle_offer(channel_id)
+    }
+
+    /// Blocking version of [`AsyncManager::splice_channel`].
+    pub fn splice_channel(
+        &self,
+        channel_id: &ChannelId,
+        offer_balance: u64,
+        fee_rate_per_vb: u64,
+    ) -> Result<(SpliceOffer, PublicKey), Error> {
+        block_on(
+            self.inner
+                .splice_channel(channel_id, offer_balance, fee_rate_per_vb),
+        )
+    }
+
+    /// See [`AsyncManager::accept_splice_offer`].
+    pub fn accept_splice_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(SpliceAccept, PublicKey), Error> {
+        self.inner.accept_splice_offer(channel_id)
+    }
+
+    /// See [`AsyncManager::reject_splice_offer`].
+    pub fn reject_splice_offer(
+        &self,
+        channel_id: &ChannelId,
+    ) -> Result<(Reject, PublicKey), Error> {
+        self.inner.reject_splice_offer(channel_id)
+    }
+
+    /// See [`AsyncManager::get_channel_reestablish_messages`].
+    pub fn get_channel_reestablish_messages(
+        &self,
+        counter_party: &PublicKey,
+    ) -> Result<Vec<ChannelReestablish>, Error> {
+        self.inner.get_channel_reestablish_messages(counter_party)
+    }
+
+    /// See [`AsyncManager::offer_collaborative_close`].
+    pub fn offer_collaborative_close(
+        &self,
+        channel_id: &ChannelId,
+        counter_payout: u64,
+    ) -> Result<CollaborativeCloseOffer, Error> {
+        self.inner
+            .offer_collaborative_close(channel_id, counter_payout)
+    }
 
-        Ok(())
+    /// Blocking version of [`AsyncManager::accept_collaborative_close`].
+    pub fn accept_collaborative_close(&self, channel_id: &ChannelId) -> Result<(), Error> {
+        block_on(self.inner.accept_collaborative_close(channel_id))
     }
+}
 
-    fn get_collaboratively_closed_contract(
-        &self,
-        contract_id: &ContractId,
-        payout: u64,
-        is_own_payout: bool,
-    ) -> Result<ClosedContract, Error> {
-        let contract = get_contract_in_state!(self, contract_id, Confirmed, None::<PublicKey>)?;
-        let own_collateral = if contract.accepted_contract.offered_contract.is_offer_party {
-            contract
-                .accepted_contract
-                .offered_contract
-                .offer_params
-                .collateral
-        } else {
-            contract.accepted_contract.accept_params.collateral
-        };
-        let own_payout = if is_own_payout {
-            payout
-        } else {
-            contract.accepted_contract.offered_contract.total_collateral - payout
-        };
-        let pnl = own_payout as i64 - own_collateral as i64;
-        Ok(ClosedContract {
-            attestations: None,
-            signed_cet: None,
-            contract_id: *contract_id,
-            temporary_contract_id: contract.accepted_contract.offered_contract.id,
-            counter_party_id: contract.accepted_contract.offered_contract.counter_party,
-            pnl,
-        })
+/// Returns the outpoints of the wallet inputs reserved for a splice offered by
+/// the local party, if the channel is in
+/// [`SignedChannelState::SpliceOffered`] state.
+fn get_splice_inputs(state: &SignedChannelState) -> Vec<OutPoint> {
+    match state {
+        SignedChannelState::SpliceOffered { splice_tx, .. } => splice_tx
+            .input
+            .iter()
+            .skip(1)
+            .map(|input| input.previous_output)
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
+/// Returns the outpoints of the wallet inputs of the given CPFP transaction,
+/// that is all its inputs but the anchor output of the CET.
+fn get_cpfp_wallet_inputs(cpfp_tx: &Transaction) -> Vec<OutPoint> {
+    cpfp_tx
+        .input
+        .iter()
+        .skip(1)
+        .map(|input| input.previous_output)
+        .collect()
+}
+
+/// Returns the address of the fund output of the given DLC transactions, so
+/// that it can be watched by the wallet.
+fn get_fund_address(
+    dlc_transactions: &DlcTransactions,
+    network: Network,
+) -> Result<Address, Error> {
+    Address::from_script(&dlc_transactions.get_fund_output_script_pubkey(), network)
+        .map_err(|_| Error::InvalidState("Invalid fund output script pubkey.".to_string()))
+}
+
 #[cfg(test)]
 mod test {
-    use dlc_messages::Message;
+    use bitcoin::{Block, BlockHash, Network, Transaction, Txid};
+    use dlc::FundingOutputType;
+    use dlc_messages::batch::OfferBatchDlc;
+    use dlc_messages::cancel::CancelOffer;
+    use dlc_messages::channel::OfferChannel;
+    use dlc_messages::oracle_msgs::{OracleAnnouncement, OracleAttestation};
+    use dlc_messages::reject::{RejectErrorCode, RejectOffer};
+    use dlc_messages::{AcceptDlc, CetAdaptorSignatures, Message, OfferDlc};
     use mocks::{
-        dlc_manager::{manager::Manager, CachedContractSignerProvider, Oracle, SimpleSigner},
+        dlc_manager::{
+            contract::{
+                offered_contract::{OfferedContract, TaprootSecretNonces},
+                Contract,
+            },
+            error::Error,
+            events::Event,
+            legacy,
+            manager::{AsyncManager, Manager, ManagerConfig},
+            offer_policy::RulesOfferPolicy,
+            AsyncBlockchain, AsyncOracle, Blockchain, CachedContractSignerProvider, Oracle,
+            SimpleSigner, Storage, SyncWalletAdapter,
+        },
         memory_storage_provider::MemoryStorage,
         mock_blockchain::MockBlockchain,
         mock_oracle_provider::MockOracle,
@@ -2586,23 +5604,107 @@ mod test {
         mock_wallet::MockWallet,
     };
     use secp256k1_zkp::{PublicKey, XOnlyPublicKey};
-    use std::{collections::HashMap, rc::Rc, sync::Arc};
+    use std::{collections::HashMap, io::Cursor, rc::Rc, sync::Arc};
 
     type TestManager = Manager<
-        Rc<MockWallet>,
-        Arc<CachedContractSignerProvider<Rc<MockWallet>, SimpleSigner>>,
-        Rc<MockBlockchain>,
+        Arc<MockWallet>,
+        Arc<CachedContractSignerProvider<Arc<MockWallet>, SimpleSigner>>,
+        Arc<MockBlockchain>,
+        Rc<MemoryStorage>,
+        Arc<MockOracle>,
+        Rc<MockTime>,
+        Arc<MockBlockchain>,
+        SimpleSigner,
+    >;
+
+    type AsyncTestManager = AsyncManager<
+        Box<SyncWalletAdapter<Arc<MockWallet>>>,
+        Arc<CachedContractSignerProvider<Arc<MockWallet>, SimpleSigner>>,
+        Box<YieldingBlockchain>,
         Rc<MemoryStorage>,
-        Rc<MockOracle>,
+        Box<YieldingOracle>,
         Rc<MockTime>,
-        Rc<MockBlockchain>,
+        Arc<MockBlockchain>,
         SimpleSigner,
     >;
 
+    /// Blockchain yielding to the executor before each call, so that the
+    /// futures of the manager using it need to wait before completing.
+    struct YieldingBlockchain(Arc<MockBlockchain>);
+
+    #[async_trait::async_trait]
+    impl AsyncBlockchain for YieldingBlockchain {
+        async fn send_transaction(&self, transaction: &Transaction) -> Result<(), Error> {
+            tokio::task::yield_now().await;
+            self.0.send_transaction(transaction)
+        }
+
+        async fn get_network(&self) -> Result<Network, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_network()
+        }
+
+        async fn get_blockchain_height(&self) -> Result<u64, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_blockchain_height()
+        }
+
+        async fn get_block_at_height(&self, height: u64) -> Result<Block, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_block_at_height(height)
+        }
+
+        async fn get_block_hash_at_height(&self, height: u64) -> Result<BlockHash, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_block_hash_at_height(height)
+        }
+
+        async fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_transaction(tx_id)
+        }
+
+        async fn get_transaction_confirmations(&self, tx_id: &Txid) -> Result<u32, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_transaction_confirmations(tx_id)
+        }
+    }
+
+    /// Oracle yielding to the executor before returning announcements and
+    /// attestations.
+    struct YieldingOracle(MockOracle);
+
+    #[async_trait::async_trait]
+    impl AsyncOracle for YieldingOracle {
+        fn get_public_key(&self) -> XOnlyPublicKey {
+            self.0.get_public_key()
+        }
+
+        async fn get_announcement(&self, event_id: &str) -> Result<OracleAnnouncement, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_announcement(event_id)
+        }
+
+        async fn get_attestation(&self, event_id: &str) -> Result<OracleAttestation, Error> {
+            tokio::task::yield_now().await;
+            self.0.get_attestation(event_id)
+        }
+    }
+
     fn get_manager() -> TestManager {
-        let blockchain = Rc::new(MockBlockchain::new());
+        get_manager_with_config(ManagerConfig::default()).unwrap()
+    }
+
+    fn get_manager_with_config(config: ManagerConfig) -> Result<TestManager, Error> {
+        get_manager_with_blockchain(config, Arc::new(MockBlockchain::new()))
+    }
+
+    fn get_manager_with_blockchain(
+        config: ManagerConfig,
+        blockchain: Arc<MockBlockchain>,
+    ) -> Result<TestManager, Error> {
         let store = Rc::new(MemoryStorage::new());
-        let wallet = Rc::new(MockWallet::new(
+        let wallet = Arc::new(MockWallet::new(
             &blockchain,
             &(0..100).map(|x| x as u64 * 1000000).collect::<Vec<_>>(),
         ));
@@ -2610,7 +5712,7 @@ mod test {
         let oracle_list = (0..5).map(|_| MockOracle::new()).collect::<Vec<_>>();
         let oracles: HashMap<XOnlyPublicKey, _> = oracle_list
             .into_iter()
-            .map(|x| (x.get_public_key(), Rc::new(x)))
+            .map(|x| (x.get_public_key(), Arc::new(x)))
             .collect();
         let time = Rc::new(MockTime {});
 
@@ -2624,7 +5726,31 @@ mod test {
             oracles,
             time,
             blockchain,
+            config,
+        )
+    }
+
+    async fn get_async_manager(blockchain: Arc<MockBlockchain>) -> AsyncTestManager {
+        let wallet = Arc::new(MockWallet::new(
+            &blockchain,
+            &(0..100).map(|x| x as u64 * 1000000).collect::<Vec<_>>(),
+        ));
+        let oracle = MockOracle::new();
+        let mut oracles = HashMap::new();
+        oracles.insert(oracle.get_public_key(), Box::new(YieldingOracle(oracle)));
+        mocks::mock_time::set_time(0);
+
+        AsyncManager::new(
+            Box::new(SyncWalletAdapter(wallet.clone())),
+            wallet,
+            Box::new(YieldingBlockchain(blockchain.clone())),
+            Rc::new(MemoryStorage::new()),
+            oracles,
+            Rc::new(MockTime {}),
+            blockchain,
+            ManagerConfig::default(),
         )
+        .await
         .unwrap()
     }
 
@@ -2651,6 +5777,196 @@ mod test {
             .expect_err("To reject the second offer message");
     }
 
+    #[tokio::test]
+    async fn async_manager_accepts_offer() {
+        let offer_message = Message::Offer(
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap(),
+        );
+        let manager = get_async_manager(Arc::new(MockBlockchain::new())).await;
+
+        manager
+            .on_dlc_message(&offer_message, pubkey())
+            .await
+            .expect("To accept the offer message");
+        assert!(matches!(
+            manager.get_and_clear_pending_events().unwrap().as_slice(),
+            [Event::OfferReceived { .. }]
+        ));
+        manager
+            .on_dlc_message(&offer_message, pubkey())
+            .await
+            .expect_err("To reject the second offer message");
+    }
+
+    #[tokio::test]
+    async fn async_manager_reverts_reorged_funding_transaction() {
+        let blockchain = Arc::new(MockBlockchain::new_with_chain());
+        let manager = get_async_manager(blockchain.clone()).await;
+        let buf = include_bytes!("../../dlc-sled-storage-provider/test_files/Confirmed");
+        let signed_contract = legacy::read_signed_contract(&mut Cursor::new(&buf)).unwrap();
+        let contract_id = signed_contract.accepted_contract.get_contract_id();
+
+        blockchain.mine_block();
+        blockchain
+            .send_transaction(&signed_contract.accepted_contract.dlc_transactions.fund)
+            .unwrap();
+        blockchain.mine_block();
+        manager.periodic_chain_monitor().await.unwrap();
+        manager
+            .get_store()
+            .update_contract(&Contract::Confirmed(signed_contract))
+            .unwrap();
+
+        blockchain.disconnect_blocks(1);
+        manager.periodic_chain_monitor().await.unwrap();
+
+        assert!(matches!(
+            manager.get_store().get_contract(&contract_id).unwrap(),
+            Some(Contract::Signed(_))
+        ));
+
+        blockchain.disconnect_blocks(1);
+        for _ in 0..ManagerConfig::default().nb_confirmations {
+            blockchain.mine_block();
+        }
+        manager.periodic_chain_monitor().await.unwrap();
+        manager.periodic_check(false).await.unwrap();
+
+        assert!(matches!(
+            manager.get_store().get_contract(&contract_id).unwrap(),
+            Some(Contract::Confirmed(_))
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        let config = ManagerConfig {
+            refund_delay: ManagerConfig::default().max_refund_delay + 1,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            get_manager_with_config(config),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_fee_share_config() {
+        let config = ManagerConfig {
+            offer_fee_share: 101,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            get_manager_with_config(config),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn reject_offer_with_refund_delay_outside_policy() {
+        let offer_message = Message::Offer(
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap(),
+        );
+        let refund_delay = ManagerConfig::default().max_refund_delay + 1;
+        let config = ManagerConfig {
+            refund_delay,
+            min_refund_delay: refund_delay,
+            max_refund_delay: refund_delay * 2,
+            ..Default::default()
+        };
+
+        let manager = get_manager_with_config(config).unwrap();
+
+        manager
+            .on_dlc_message(&offer_message, pubkey())
+            .expect_err("To reject the offer message");
+    }
+
+    #[test]
+    fn reorged_funding_transaction_reverts_contract_to_signed() {
+        let blockchain = Arc::new(MockBlockchain::new_with_chain());
+        let manager =
+            get_manager_with_blockchain(ManagerConfig::default(), blockchain.clone()).unwrap();
+        let buf = include_bytes!("../../dlc-sled-storage-provider/test_files/Confirmed");
+        let signed_contract = legacy::read_signed_contract(&mut Cursor::new(&buf)).unwrap();
+        let contract_id = signed_contract.accepted_contract.get_contract_id();
+
+        blockchain.mine_block();
+        blockchain
+            .send_transaction(&signed_contract.accepted_contract.dlc_transactions.fund)
+            .unwrap();
+        blockchain.mine_block();
+        manager.periodic_chain_monitor().unwrap();
+        manager
+            .get_store()
+            .update_contract(&Contract::Confirmed(signed_contract))
+            .unwrap();
+
+        blockchain.disconnect_blocks(1);
+        manager.periodic_chain_monitor().unwrap();
+
+        assert!(matches!(
+            manager.get_store().get_contract(&contract_id).unwrap(),
+            Some(Contract::Signed(_))
+        ));
+
+        blockchain.disconnect_blocks(1);
+        for _ in 0..ManagerConfig::default().nb_confirmations {
+            blockchain.mine_block();
+        }
+        manager.periodic_chain_monitor().unwrap();
+        manager.periodic_check(false).unwrap();
+
+        assert!(matches!(
+            manager.get_store().get_contract(&contract_id).unwrap(),
+            Some(Contract::Confirmed(_))
+        ));
+    }
+
+    #[test]
+    fn reorged_cet_reverts_closed_contract_to_confirmed() {
+        let blockchain = Arc::new(MockBlockchain::new_with_chain());
+        let manager =
+            get_manager_with_blockchain(ManagerConfig::default(), blockchain.clone()).unwrap();
+        let nb_confirmations = ManagerConfig::default().nb_confirmations;
+        let buf = include_bytes!("../../dlc-sled-storage-provider/test_files/Confirmed");
+        let signed_contract = legacy::read_signed_contract(&mut Cursor::new(&buf)).unwrap();
+        let contract_id = signed_contract.accepted_contract.get_contract_id();
+        let cet = signed_contract.accepted_contract.dlc_transactions.cets[0].clone();
+
+        blockchain
+            .send_transaction(&signed_contract.accepted_contract.dlc_transactions.fund)
+            .unwrap();
+        blockchain.mine_block();
+        blockchain.send_transaction(&cet).unwrap();
+        for _ in 0..nb_confirmations {
+            blockchain.mine_block();
+        }
+        manager.periodic_chain_monitor().unwrap();
+        manager
+            .get_store()
+            .update_contract(&Contract::Confirmed(signed_contract.clone()))
+            .unwrap();
+        manager
+            .on_counterparty_close(&signed_contract, cet, nb_confirmations)
+            .unwrap();
+
+        assert!(matches!(
+            manager.get_store().get_contract(&contract_id).unwrap(),
+            Some(Contract::Closed(_))
+        ));
+
+        blockchain.disconnect_blocks(nb_confirmations as usize);
+        manager.periodic_chain_monitor().unwrap();
+
+        assert!(matches!(
+            manager.get_store().get_contract(&contract_id).unwrap(),
+            Some(Contract::Confirmed(_))
+        ));
+    }
+
     #[test]
     fn reject_channel_offer_with_existing_channel_id() {
         let offer_message = Message::OfferChannel(
@@ -2667,4 +5983,329 @@ mod test {
             .on_dlc_message(&offer_message, pubkey())
             .expect_err("To reject the second offer message");
     }
+
+    #[test]
+    fn channel_offer_with_anchor_outputs_flag_is_accepted() {
+        let mut offer: OfferChannel =
+            serde_json::from_str(include_str!("../test_inputs/offer_channel.json")).unwrap();
+        offer.contract_flags = dlc_messages::ANCHOR_OUTPUTS_CONTRACT_FLAG;
+        let temporary_contract_id = offer.temporary_contract_id;
+
+        let manager = get_manager();
+
+        manager
+            .on_dlc_message(&Message::OfferChannel(offer), pubkey())
+            .expect("To accept the offer message");
+
+        match manager
+            .get_store()
+            .get_contract(&temporary_contract_id)
+            .unwrap()
+        {
+            Some(Contract::Offered(c)) => assert!(c.anchor_outputs),
+            _ => panic!("Expected an offered contract"),
+        }
+    }
+
+    #[test]
+    fn offer_received_event_is_persisted_until_cleared() {
+        let offer_message = Message::Offer(
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap(),
+        );
+        let temporary_contract_id = match &offer_message {
+            Message::Offer(o) => o.temporary_contract_id,
+            _ => unreachable!(),
+        };
+
+        let manager = get_manager();
+
+        manager
+            .on_dlc_message(&offer_message, pubkey())
+            .expect("To accept the offer message");
+
+        let expected = vec![Event::OfferReceived {
+            temporary_contract_id,
+            counter_party: pubkey(),
+        }];
+        assert_eq!(expected, manager.get_store().get_events().unwrap());
+        assert_eq!(expected, manager.get_and_clear_pending_events().unwrap());
+        assert!(manager.get_and_clear_pending_events().unwrap().is_empty());
+        assert!(manager.get_store().get_events().unwrap().is_empty());
+    }
+
+    #[test]
+    fn expired_offer_cannot_be_accepted_and_gets_cancelled() {
+        let mut offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        offer.offer_expiry = Some(100);
+        let temporary_contract_id = offer.temporary_contract_id;
+
+        let manager = get_manager();
+
+        manager
+            .on_dlc_message(&Message::Offer(offer), pubkey())
+            .expect("To accept the offer message");
+        manager.get_and_clear_pending_events().unwrap();
+
+        mocks::mock_time::set_time(100);
+
+        manager
+            .accept_contract_offer(&temporary_contract_id)
+            .expect_err("To reject accepting an expired offer");
+
+        manager.periodic_check(false).unwrap();
+
+        assert!(matches!(
+            manager
+                .get_store()
+                .get_contract(&temporary_contract_id)
+                .unwrap(),
+            Some(Contract::Cancelled(_))
+        ));
+        assert_eq!(
+            vec![Event::OfferCancelled {
+                temporary_contract_id
+            }],
+            manager.get_and_clear_pending_events().unwrap()
+        );
+    }
+
+    #[test]
+    fn offer_cancelled_by_counter_party_cannot_be_accepted() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let temporary_contract_id = offer.temporary_contract_id;
+        let cancel_message = Message::CancelOffer(CancelOffer {
+            protocol_version: offer.protocol_version,
+            temporary_contract_id,
+        });
+
+        let manager = get_manager();
+
+        manager
+            .on_dlc_message(&Message::Offer(offer), pubkey())
+            .expect("To accept the offer message");
+
+        manager
+            .cancel_offer(&temporary_contract_id)
+            .expect_err("To only allow the offer party to cancel the offer");
+
+        manager
+            .on_dlc_message(&cancel_message, pubkey())
+            .expect("To accept the cancel message");
+
+        assert!(matches!(
+            manager
+                .get_store()
+                .get_contract(&temporary_contract_id)
+                .unwrap(),
+            Some(Contract::Cancelled(_))
+        ));
+        manager
+            .accept_contract_offer(&temporary_contract_id)
+            .expect_err("To reject accepting a cancelled offer");
+    }
+
+    #[test]
+    fn rejected_offer_cannot_be_accepted() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let temporary_contract_id = offer.temporary_contract_id;
+
+        let manager = get_manager();
+
+        manager
+            .on_dlc_message(&Message::Offer(offer), pubkey())
+            .expect("To accept the offer message");
+
+        let (reject_msg, counter_party) = manager
+            .reject_contract_offer(
+                &temporary_contract_id,
+                RejectErrorCode::InsufficientFunds,
+                "Not enough funds.".to_string(),
+            )
+            .expect("To be able to reject the offer");
+
+        assert_eq!(pubkey(), counter_party);
+        assert_eq!(temporary_contract_id, reject_msg.temporary_contract_id);
+        assert_eq!(RejectErrorCode::InsufficientFunds, reject_msg.error_code);
+        assert!(matches!(
+            manager
+                .get_store()
+                .get_contract(&temporary_contract_id)
+                .unwrap(),
+            Some(Contract::Rejected(_))
+        ));
+        manager
+            .accept_contract_offer(&temporary_contract_id)
+            .expect_err("To reject accepting a rejected offer");
+        manager
+            .on_dlc_message(&Message::RejectOffer(reject_msg), pubkey())
+            .expect_err("To only process rejects of offers sent by us");
+    }
+
+    #[test]
+    fn batch_offer_cannot_be_accepted_individually() {
+        let mut offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let temporary_contract_id = offer.temporary_contract_id;
+        let funding_inputs = std::mem::take(&mut offer.funding_inputs);
+        let batch_offer = OfferBatchDlc {
+            protocol_version: offer.protocol_version,
+            temporary_batch_id: [1u8; 32],
+            funding_inputs,
+            offer_dlcs: vec![offer],
+        };
+
+        let manager = get_manager();
+
+        manager
+            .on_dlc_message(&Message::OfferBatch(batch_offer), pubkey())
+            .expect("To accept the batch offer message");
+
+        assert!(matches!(
+            manager.accept_contract_offer(&temporary_contract_id),
+            Err(Error::InvalidState(_))
+        ));
+        assert!(matches!(
+            manager
+                .get_store()
+                .get_contract(&temporary_contract_id)
+                .unwrap(),
+            Some(Contract::Offered(_))
+        ));
+    }
+
+    #[test]
+    fn taproot_secret_nonces_are_deleted_before_being_used() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let temporary_contract_id = offer.temporary_contract_id;
+        let mut offered_contract =
+            OfferedContract::try_from_offer_dlc(&offer, pubkey(), [0u8; 32]).unwrap();
+        offered_contract.is_offer_party = true;
+        offered_contract.funding_output_type = FundingOutputType::Taproot;
+        offered_contract.taproot_secret_nonces = Some(TaprootSecretNonces {
+            cet_nonces: Vec::new(),
+            refund_nonce: [1u8; 64],
+        });
+        let accept = AcceptDlc {
+            protocol_version: offer.protocol_version,
+            temporary_contract_id,
+            accept_collateral: offer.get_total_collateral() - offer.offer_collateral,
+            funding_pubkey: pubkey(),
+            payout_spk: offer.payout_spk.clone(),
+            payout_serial_id: offer.payout_serial_id + 1,
+            funding_inputs: Vec::new(),
+            change_spk: offer.change_spk.clone(),
+            change_serial_id: offer.change_serial_id + 1,
+            cet_adaptor_signatures: CetAdaptorSignatures {
+                ecdsa_adaptor_signatures: Vec::new(),
+            },
+            refund_signature: None,
+            negotiation_fields: None,
+            taproot_signatures: None,
+        };
+
+        let manager = get_manager();
+        manager
+            .get_store()
+            .create_contract(&offered_contract)
+            .unwrap();
+
+        manager
+            .on_dlc_message(&Message::Accept(accept), pubkey())
+            .expect_err("To reject an accept message without taproot signatures");
+
+        match manager
+            .get_store()
+            .get_contract(&temporary_contract_id)
+            .unwrap()
+        {
+            Some(Contract::FailedAccept(c)) => {
+                assert!(c.offered_contract.taproot_secret_nonces.is_none())
+            }
+            _ => panic!("Expected a failed accept contract."),
+        }
+    }
+
+    #[test]
+    fn offer_breaking_offer_policy_gets_rejected() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let temporary_contract_id = offer.temporary_contract_id;
+
+        let mut manager = get_manager();
+        manager.set_offer_policy(Some(Box::new(RulesOfferPolicy {
+            oracle_public_keys: Some(Vec::new()),
+            ..Default::default()
+        })));
+
+        let res = manager
+            .on_dlc_message(&Message::Offer(offer), pubkey())
+            .expect("To accept the offer message");
+
+        assert!(matches!(
+            res,
+            Some(Message::RejectOffer(RejectOffer {
+                error_code: RejectErrorCode::UnsupportedOracle,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            manager
+                .get_store()
+                .get_contract(&temporary_contract_id)
+                .unwrap(),
+            Some(Contract::Rejected(_))
+        ));
+        assert!(matches!(
+            manager.get_and_clear_pending_events().unwrap().as_slice(),
+            [
+                Event::OfferReceived { .. },
+                Event::OfferRejected {
+                    temporary_contract_id: id,
+                    ..
+                }
+            ] if *id == temporary_contract_id
+        ));
+    }
+
+    #[test]
+    fn offer_held_by_offer_policy_stays_offered() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let temporary_contract_id = offer.temporary_contract_id;
+
+        let mut manager = get_manager();
+        manager.set_offer_policy(Some(Box::new(RulesOfferPolicy {
+            hold_above_collateral: Some(0),
+            ..Default::default()
+        })));
+
+        let res = manager
+            .on_dlc_message(&Message::Offer(offer), pubkey())
+            .expect("To accept the offer message");
+
+        assert!(res.is_none());
+        assert!(matches!(
+            manager
+                .get_store()
+                .get_contract(&temporary_contract_id)
+                .unwrap(),
+            Some(Contract::Offered(_))
+        ));
+    }
+
+    #[test]
+    fn block_on_returns_error_if_future_is_pending() {
+        assert_eq!(
+            1,
+            super::block_on(async { Ok::<_, crate::error::Error>(1) }).unwrap()
+        );
+        assert!(matches!(
+            super::block_on(std::future::pending::<Result<(), crate::error::Error>>()),
+            Err(crate::error::Error::InvalidState(_))
+        ));
+    }
 }
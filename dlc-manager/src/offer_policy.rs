@@ -0,0 +1,319 @@
+//! Policies used to automatically decide what to do with contract offers
+//! received from peers.
+
+use crate::contract::offered_contract::OfferedContract;
+use crate::contract::ContractDescriptor;
+use dlc_messages::reject::RejectErrorCode;
+use secp256k1_zkp::XOnlyPublicKey;
+
+/// The decision taken by an [`OfferPolicy`] about a received contract offer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OfferDecision {
+    /// The offer is accepted automatically.
+    Accept,
+    /// The offer is rejected with the given error code and reason, which are
+    /// sent to the offer party.
+    Reject(RejectErrorCode, String),
+    /// The offer is kept to be accepted or rejected manually.
+    Hold,
+}
+
+/// Trait to be implemented to automatically accept or reject contract offers
+/// received by the [`crate::manager::Manager`].
+pub trait OfferPolicy: Send + Sync {
+    /// Returns the decision to apply to the given received offer, `now` being
+    /// the current unix timestamp.
+    fn decide(&self, offered_contract: &OfferedContract, now: u64) -> OfferDecision;
+}
+
+/// The kinds of contract descriptors that can be allowed by a
+/// [`RulesOfferPolicy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContractDescriptorKind {
+    /// Contracts over an enumeration event.
+    Enum,
+    /// Contracts over a numerical event, with a payout curve.
+    Numerical,
+    /// Contracts over the joint outcome of several enumeration events.
+    MultiEvent,
+}
+
+impl From<&ContractDescriptor> for ContractDescriptorKind {
+    fn from(descriptor: &ContractDescriptor) -> Self {
+        match descriptor {
+            ContractDescriptor::Enum(_) => ContractDescriptorKind::Enum,
+            ContractDescriptor::Numerical(_) => ContractDescriptorKind::Numerical,
+            ContractDescriptor::MultiEvent(_) => ContractDescriptorKind::MultiEvent,
+        }
+    }
+}
+
+/// An [`OfferPolicy`] rejecting the offers breaking any of the configured
+/// rules, holding the ones requiring a collateral above the configured limit,
+/// and accepting the others. Rules that are not set are not checked.
+#[derive(Clone, Debug, Default)]
+pub struct RulesOfferPolicy {
+    /// The oracles that can be used by the contracts.
+    pub oracle_public_keys: Option<Vec<XOnlyPublicKey>>,
+    /// The prefixes of the ids of the events the contracts can be about.
+    pub event_id_prefixes: Option<Vec<String>>,
+    /// The kinds of contract descriptors that can be used by the contracts.
+    pub descriptor_kinds: Option<Vec<ContractDescriptorKind>>,
+    /// The minimum ratio of the local collateral to the one of the offer party.
+    pub min_collateral_ratio: Option<f64>,
+    /// The maximum ratio of the local collateral to the one of the offer party.
+    pub max_collateral_ratio: Option<f64>,
+    /// The maximum fee rate of the contract transactions in sats/vbyte.
+    pub max_fee_rate_per_vb: Option<u64>,
+    /// The minimum delay in seconds between now and the latest maturity of the
+    /// contract events.
+    pub min_maturity_delay: Option<u64>,
+    /// The maximum delay in seconds between now and the latest maturity of the
+    /// contract events.
+    pub max_maturity_delay: Option<u64>,
+    /// The local collateral above which offers are held to be reviewed
+    /// manually.
+    pub hold_above_collateral: Option<u64>,
+}
+
+impl RulesOfferPolicy {
+    fn check_rules(
+        &self,
+        offered_contract: &OfferedContract,
+        now: u64,
+    ) -> Result<(), (RejectErrorCode, String)> {
+        let announcements = offered_contract
+            .contract_info
+            .iter()
+            .flat_map(|x| x.oracle_announcements.iter());
+
+        if let Some(oracle_public_keys) = &self.oracle_public_keys {
+            if let Some(announcement) = announcements
+                .clone()
+                .find(|x| !oracle_public_keys.contains(&x.oracle_public_key))
+            {
+                return Err((
+                    RejectErrorCode::UnsupportedOracle,
+                    format!("Oracle {} is not allowed.", announcement.oracle_public_key),
+                ));
+            }
+        }
+
+        if let Some(event_id_prefixes) = &self.event_id_prefixes {
+            if let Some(announcement) = announcements.clone().find(|x| {
+                !event_id_prefixes
+                    .iter()
+                    .any(|prefix| x.oracle_event.event_id.starts_with(prefix))
+            }) {
+                return Err((
+                    RejectErrorCode::UnacceptableTerms,
+                    format!(
+                        "Event {} is not allowed.",
+                        announcement.oracle_event.event_id
+                    ),
+                ));
+            }
+        }
+
+        if let Some(descriptor_kinds) = &self.descriptor_kinds {
+            if let Some(contract_info) = offered_contract.contract_info.iter().find(|x| {
+                !descriptor_kinds.contains(&ContractDescriptorKind::from(&x.contract_descriptor))
+            }) {
+                return Err((
+                    RejectErrorCode::UnacceptableTerms,
+                    format!(
+                        "{:?} contract descriptors are not allowed.",
+                        ContractDescriptorKind::from(&contract_info.contract_descriptor)
+                    ),
+                ));
+            }
+        }
+
+        let offer_collateral = offered_contract.offer_params.collateral;
+        let own_collateral = offered_contract.total_collateral - offer_collateral;
+        let collateral_ratio = own_collateral as f64 / offer_collateral as f64;
+        if self
+            .min_collateral_ratio
+            .map_or(false, |min| collateral_ratio < min)
+            || self
+                .max_collateral_ratio
+                .map_or(false, |max| collateral_ratio > max)
+        {
+            return Err((
+                RejectErrorCode::UnacceptableTerms,
+                format!(
+                    "Collateral ratio {} is out of the allowed range.",
+                    collateral_ratio
+                ),
+            ));
+        }
+
+        if let Some(max_fee_rate_per_vb) = self.max_fee_rate_per_vb {
+            if offered_contract.fee_rate_per_vb > max_fee_rate_per_vb {
+                return Err((
+                    RejectErrorCode::UnacceptableTerms,
+                    format!(
+                        "Fee rate {} is higher than the allowed maximum.",
+                        offered_contract.fee_rate_per_vb
+                    ),
+                ));
+            }
+        }
+
+        let latest_maturity = announcements
+            .map(|x| x.oracle_event.event_maturity_epoch as u64)
+            .max()
+            .unwrap_or(0);
+        let maturity_delay = latest_maturity.saturating_sub(now);
+        if self
+            .min_maturity_delay
+            .map_or(false, |min| maturity_delay < min)
+            || self
+                .max_maturity_delay
+                .map_or(false, |max| maturity_delay > max)
+        {
+            return Err((
+                RejectErrorCode::UnacceptableTerms,
+                format!("Maturity {} is out of the allowed range.", latest_maturity),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl OfferPolicy for RulesOfferPolicy {
+    fn decide(&self, offered_contract: &OfferedContract, now: u64) -> OfferDecision {
+        if let Err((error_code, reason)) = self.check_rules(offered_contract, now) {
+            return OfferDecision::Reject(error_code, reason);
+        }
+
+        let own_collateral =
+            offered_contract.total_collateral - offered_contract.offer_params.collateral;
+        if self
+            .hold_above_collateral
+            .map_or(false, |max| own_collateral > max)
+        {
+            return OfferDecision::Hold;
+        }
+
+        OfferDecision::Accept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dlc_messages::OfferDlc;
+    use secp256k1_zkp::PublicKey;
+
+    // Offer with a numerical contract over the "Test" event maturing at
+    // 1623133104, with an offer collateral of 90000000 sats, an accept
+    // collateral of 11000000 sats and a fee rate of 2 sats/vbyte.
+    fn get_offered_contract() -> OfferedContract {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("../test_inputs/offer_contract.json")).unwrap();
+        let counter_party: PublicKey =
+            "0218845781f631c48f1c9709e23092067d06837f30aa0cd0544ac887fe91ddd166"
+                .parse()
+                .unwrap();
+        OfferedContract::try_from_offer_dlc(&offer, counter_party, [0u8; 32]).unwrap()
+    }
+
+    const NOW: u64 = 1623133104 - 3600;
+
+    fn assert_rejected(policy: RulesOfferPolicy) {
+        assert!(matches!(
+            policy.decide(&get_offered_contract(), NOW),
+            OfferDecision::Reject(..)
+        ));
+    }
+
+    #[test]
+    fn accepts_offer_when_no_rule_is_set() {
+        assert_eq!(
+            OfferDecision::Accept,
+            RulesOfferPolicy::default().decide(&get_offered_contract(), NOW)
+        );
+    }
+
+    #[test]
+    fn accepts_offer_satisfying_all_rules() {
+        let offered_contract = get_offered_contract();
+        let policy = RulesOfferPolicy {
+            oracle_public_keys: Some(vec![
+                offered_contract.contract_info[0].oracle_announcements[0].oracle_public_key,
+            ]),
+            event_id_prefixes: Some(vec!["Te".to_string()]),
+            descriptor_kinds: Some(vec![ContractDescriptorKind::Numerical]),
+            min_collateral_ratio: Some(0.1),
+            max_collateral_ratio: Some(0.2),
+            max_fee_rate_per_vb: Some(2),
+            min_maturity_delay: Some(3600),
+            max_maturity_delay: Some(7200),
+            hold_above_collateral: Some(11000000),
+        };
+
+        assert_eq!(OfferDecision::Accept, policy.decide(&offered_contract, NOW));
+    }
+
+    #[test]
+    fn rejects_offer_breaking_a_rule() {
+        assert_eq!(
+            OfferDecision::Reject(
+                RejectErrorCode::UnsupportedOracle,
+                format!(
+                    "Oracle {} is not allowed.",
+                    get_offered_contract().contract_info[0].oracle_announcements[0]
+                        .oracle_public_key
+                )
+            ),
+            RulesOfferPolicy {
+                oracle_public_keys: Some(Vec::new()),
+                ..Default::default()
+            }
+            .decide(&get_offered_contract(), NOW)
+        );
+        assert_rejected(RulesOfferPolicy {
+            event_id_prefixes: Some(vec!["btcusd".to_string()]),
+            ..Default::default()
+        });
+        assert_rejected(RulesOfferPolicy {
+            descriptor_kinds: Some(vec![ContractDescriptorKind::Enum]),
+            ..Default::default()
+        });
+        assert_rejected(RulesOfferPolicy {
+            min_collateral_ratio: Some(0.5),
+            ..Default::default()
+        });
+        assert_rejected(RulesOfferPolicy {
+            max_collateral_ratio: Some(0.1),
+            ..Default::default()
+        });
+        assert_rejected(RulesOfferPolicy {
+            max_fee_rate_per_vb: Some(1),
+            ..Default::default()
+        });
+        assert_rejected(RulesOfferPolicy {
+            min_maturity_delay: Some(3601),
+            ..Default::default()
+        });
+        assert_rejected(RulesOfferPolicy {
+            max_maturity_delay: Some(3599),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn holds_offer_above_collateral_limit() {
+        let policy = RulesOfferPolicy {
+            hold_above_collateral: Some(10000000),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            OfferDecision::Hold,
+            policy.decide(&get_offered_contract(), NOW)
+        );
+    }
+}
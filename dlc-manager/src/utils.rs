@@ -1,8 +1,9 @@
 //! #Utils
+use std::convert::TryFrom;
 use std::ops::Deref;
 
-use bitcoin::{consensus::Encodable, Txid};
-use dlc::{PartyParams, TxInputInfo};
+use bitcoin::{consensus::Encodable, ScriptBuf, Txid};
+use dlc::{DlcTransactionsOptions, PartyParams, TxInputInfo};
 use dlc_messages::{
     oracle_msgs::{OracleAnnouncement, OracleAttestation},
     FundingInput,
@@ -16,7 +17,7 @@ use crate::{
     channel::party_points::PartyBasePoints,
     contract::{contract_info::ContractInfo, AdaptorInfo},
     error::Error,
-    Blockchain, ContractSigner, ContractSignerProvider, Wallet,
+    AsyncBlockchain, AsyncWallet, ContractSigner, ContractSignerProvider,
 };
 
 macro_rules! get_object_in_state {
@@ -75,6 +76,34 @@ pub(crate) fn get_new_temporary_id() -> [u8; 32] {
     res
 }
 
+#[cfg(not(feature = "fuzztarget"))]
+pub(crate) fn get_new_session_rand() -> [u8; 32] {
+    thread_rng().gen::<[u8; 32]>()
+}
+
+#[cfg(feature = "fuzztarget")]
+pub(crate) fn get_new_session_rand() -> [u8; 32] {
+    use rand_chacha::rand_core::RngCore;
+    use rand_chacha::rand_core::SeedableRng;
+    let mut res = [0u8; 32];
+    rand_chacha::ChaCha8Rng::from_seed([0u8; 32]).fill_bytes(&mut res);
+    res
+}
+
+#[cfg(not(feature = "fuzztarget"))]
+pub(crate) fn get_new_nonce() -> [u8; 12] {
+    thread_rng().gen::<[u8; 12]>()
+}
+
+#[cfg(feature = "fuzztarget")]
+pub(crate) fn get_new_nonce() -> [u8; 12] {
+    use rand_chacha::rand_core::RngCore;
+    use rand_chacha::rand_core::SeedableRng;
+    let mut res = [0u8; 12];
+    rand_chacha::ChaCha8Rng::from_seed([0u8; 32]).fill_bytes(&mut res);
+    res
+}
+
 pub(crate) fn compute_id(
     fund_tx_id: Txid,
     fund_output_index: u16,
@@ -89,43 +118,95 @@ pub(crate) fn compute_id(
     res
 }
 
-pub(crate) fn get_party_params<W: Deref, B: Deref, X: ContractSigner, C: Signing>(
+pub(crate) async fn get_party_params<W: Deref, B: Deref, X: ContractSigner, C: Signing>(
     secp: &Secp256k1<C>,
     own_collateral: u64,
     fee_rate: u64,
+    options: &DlcTransactionsOptions,
     wallet: &W,
     signer: &X,
     blockchain: &B,
 ) -> Result<(PartyParams, Vec<FundingInput>), Error>
 where
-    W::Target: Wallet,
-    B::Target: Blockchain,
+    W::Target: AsyncWallet,
+    B::Target: AsyncBlockchain,
+{
+    let (mut party_params, funding_inputs) = get_batch_party_params(
+        secp,
+        &[(own_collateral, *options, signer)],
+        fee_rate,
+        wallet,
+        blockchain,
+    )
+    .await?;
+
+    Ok((party_params.remove(0), funding_inputs))
+}
+
+/// Returns the parameters of the party for each contract of a batch, given as
+/// tuples of own collateral, transaction options and signer. A single set of
+/// funding inputs is selected for the whole batch and attached to the first
+/// contract, the other ones having no inputs. All contracts share the same
+/// change output.
+pub(crate) async fn get_batch_party_params<W: Deref, B: Deref, X: ContractSigner, C: Signing>(
+    secp: &Secp256k1<C>,
+    contracts: &[(u64, DlcTransactionsOptions, &X)],
+    fee_rate: u64,
+    wallet: &W,
+    blockchain: &B,
+) -> Result<(Vec<PartyParams>, Vec<FundingInput>), Error>
+where
+    W::Target: AsyncWallet,
+    B::Target: AsyncBlockchain,
 {
-    let funding_pubkey = signer.get_public_key(secp)?;
+    if contracts.is_empty() {
+        return Err(Error::InvalidParameters(
+            "At least one contract is required.".to_string(),
+        ));
+    }
 
-    let payout_addr = wallet.get_new_address()?;
-    let payout_spk = payout_addr.script_pubkey();
-    let payout_serial_id = get_new_serial_id();
-    let change_addr = wallet.get_new_change_address()?;
+    let change_addr = wallet.get_new_change_address().await?;
     let change_spk = change_addr.script_pubkey();
     let change_serial_id = get_new_serial_id();
 
     // Add base cost of fund tx + CET / 2 and a CET output to the collateral.
-    let appr_required_amount =
-        own_collateral + get_half_common_fee(fee_rate)? + dlc::util::weight_to_fee(124, fee_rate)?;
-    let utxos = wallet.get_utxos_for_amount(appr_required_amount, fee_rate, true)?;
+    let mut appr_required_amount = get_half_common_fee(fee_rate)?;
+    for (i, (own_collateral, options, _)) in contracts.iter().enumerate() {
+        let mut appr_fees = dlc::util::weight_to_fee(124, fee_rate)?;
+        // Add the anchor output and its cost to the CET.
+        if options.anchor_outputs {
+            appr_fees += dlc::ANCHOR_OUTPUT_VALUE + dlc::util::weight_to_fee(124, fee_rate)?;
+        }
+        // Cover the fees of the counter party, which does not contribute funds.
+        if options.single_funded {
+            appr_fees = 2 * appr_fees + get_half_common_fee(fee_rate)?;
+        }
+        appr_required_amount += own_collateral + appr_fees;
+        // Add the cost of the additional fund outputs.
+        if i > 0 {
+            appr_required_amount += dlc::util::weight_to_fee(172, fee_rate)?;
+        }
+    }
+    let utxos = wallet
+        .get_utxos_for_amount(appr_required_amount, fee_rate, true)
+        .await?;
 
     let mut funding_inputs: Vec<FundingInput> = Vec::new();
     let mut funding_tx_info: Vec<TxInputInfo> = Vec::new();
     let mut total_input = 0;
     for utxo in utxos {
-        let prev_tx = blockchain.get_transaction(&utxo.outpoint.txid)?;
+        let prev_tx = blockchain.get_transaction(&utxo.outpoint.txid).await?;
         let mut writer = Vec::new();
         prev_tx.consensus_encode(&mut writer)?;
         let prev_tx_vout = utxo.outpoint.vout;
-        let sequence = 0xffffffff;
-        // TODO(tibo): this assumes P2WPKH with low R
-        let max_witness_len = 107;
+        // Signal replaceability so that the funding transaction can be fee bumped.
+        let sequence = 0xfffffffd;
+        let max_witness_len = u16::try_from(utxo.max_witness_len).map_err(|_| {
+            Error::InvalidParameters(format!(
+                "Witness length of utxo {} is too large",
+                utxo.outpoint
+            ))
+        })?;
         let funding_input = FundingInput {
             input_serial_id: get_new_serial_id(),
             prev_tx: writer,
@@ -139,20 +220,53 @@ where
         funding_inputs.push(funding_input);
     }
 
-    let party_params = PartyParams {
-        fund_pubkey: funding_pubkey,
-        change_script_pubkey: change_spk,
-        change_serial_id,
-        payout_script_pubkey: payout_spk,
-        payout_serial_id,
-        inputs: funding_tx_info,
-        collateral: own_collateral,
-        input_amount: total_input,
-    };
+    let mut party_params = Vec::with_capacity(contracts.len());
+    for (i, (own_collateral, _, signer)) in contracts.iter().enumerate() {
+        let payout_addr = wallet.get_new_address().await?;
+        let (inputs, input_amount) = if i == 0 {
+            (funding_tx_info.clone(), total_input)
+        } else {
+            (Vec::new(), 0)
+        };
+        party_params.push(PartyParams {
+            fund_pubkey: signer.get_public_key(secp)?,
+            change_script_pubkey: change_spk.clone(),
+            change_serial_id,
+            payout_script_pubkey: payout_addr.script_pubkey(),
+            payout_serial_id: get_new_serial_id(),
+            inputs,
+            collateral: *own_collateral,
+            input_amount,
+        });
+    }
 
     Ok((party_params, funding_inputs))
 }
 
+/// Returns the parameters of a party which does not contribute any funds to
+/// the contract.
+pub(crate) async fn get_unfunded_party_params<W: Deref, X: ContractSigner, C: Signing>(
+    secp: &Secp256k1<C>,
+    wallet: &W,
+    signer: &X,
+) -> Result<PartyParams, Error>
+where
+    W::Target: AsyncWallet,
+{
+    let payout_addr = wallet.get_new_address().await?;
+
+    Ok(PartyParams {
+        fund_pubkey: signer.get_public_key(secp)?,
+        change_script_pubkey: ScriptBuf::new(),
+        change_serial_id: get_new_serial_id(),
+        payout_script_pubkey: payout_addr.script_pubkey(),
+        payout_serial_id: get_new_serial_id(),
+        inputs: Vec::new(),
+        collateral: 0,
+        input_amount: 0,
+    })
+}
+
 pub(crate) fn get_party_base_points<C: Signing, SP: Deref>(
     secp: &Secp256k1<C>,
     signer_provider: &SP,
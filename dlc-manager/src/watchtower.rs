@@ -0,0 +1,485 @@
+//! Data structures enabling a watchtower to punish the publication of revoked
+//! transactions of DLC channels on behalf of the local party.
+
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::{Transaction, Txid};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use dlc::channel::RevokeParams;
+use dlc_messages::ser_impls::{read_ecdsa_adaptor_signature, write_ecdsa_adaptor_signature};
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+use secp256k1_zkp::ecdsa::Signature;
+use secp256k1_zkp::{EcdsaAdaptorSignature, PublicKey, Secp256k1, SecretKey, Signing};
+
+use crate::chain_monitor::RevokedTxType;
+use crate::error::Error;
+use crate::ChannelId;
+
+/// The public keys of a party used to restrict the outputs of a transaction of
+/// a DLC channel that can later on be revoked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokePublicKeys {
+    /// Key used to restrict the transaction output path.
+    pub own_pk: PublicKey,
+    /// Key for which an adaptor signature is revealed when the transaction is
+    /// published.
+    pub publish_pk: PublicKey,
+    /// Key used to revoke the transaction.
+    pub revoke_pk: PublicKey,
+}
+
+impl_dlc_writeable!(RevokePublicKeys, {
+    (own_pk, writeable),
+    (publish_pk, writeable),
+    (revoke_pk, writeable)
+});
+
+impl From<&RevokeParams> for RevokePublicKeys {
+    fn from(params: &RevokeParams) -> Self {
+        RevokePublicKeys {
+            own_pk: params.own_pk.inner,
+            publish_pk: params.publish_pk.inner,
+            revoke_pk: params.revoke_pk.inner,
+        }
+    }
+}
+
+impl From<&RevokePublicKeys> for RevokeParams {
+    fn from(keys: &RevokePublicKeys) -> Self {
+        RevokeParams {
+            own_pk: bitcoin::PublicKey::new(keys.own_pk),
+            publish_pk: bitcoin::PublicKey::new(keys.publish_pk),
+            revoke_pk: bitcoin::PublicKey::new(keys.revoke_pk),
+        }
+    }
+}
+
+/// Information required to punish the publication of a revoked transaction of
+/// a DLC channel. The punishment transaction is signed ahead of time by the
+/// local party, using its own key and the revocation key of the counter party.
+/// The missing signature is generated using the publish key of the counter
+/// party, which is recovered from the revoked transaction once published, so
+/// that the holder of the package never learns any secret of the local party.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PunishmentPackage {
+    /// The id of the channel that the revoked transaction belongs to.
+    pub channel_id: ChannelId,
+    /// The type of the revoked transaction.
+    pub revoked_tx_type: RevokedTxType,
+    /// Whether the local party was the offer party of the revoked state.
+    pub is_offer: bool,
+    /// The keys of the offer party for the revoked state.
+    pub offer_revoke_keys: RevokePublicKeys,
+    /// The keys of the accept party for the revoked state.
+    pub accept_revoke_keys: RevokePublicKeys,
+    /// The relative time lock of the outputs of the revoked transaction if it
+    /// is a settle transaction.
+    pub csv_timelock: u32,
+    /// The adaptor signature of the local party for the revoked transaction,
+    /// used to recover the publish secret key of the counter party.
+    pub own_adaptor_signature: EcdsaAdaptorSignature,
+    /// The unsigned punishment transaction.
+    pub punish_tx: Transaction,
+    /// The signatures of the local party for each input of the punishment
+    /// transaction.
+    pub own_signatures: Vec<Signature>,
+    /// The signatures using the revocation key of the counter party for each
+    /// input of the punishment transaction.
+    pub counter_revoke_signatures: Vec<Signature>,
+}
+
+impl_dlc_writeable!(PunishmentPackage, {
+    (channel_id, writeable),
+    (revoked_tx_type, writeable),
+    (is_offer, writeable),
+    (offer_revoke_keys, writeable),
+    (accept_revoke_keys, writeable),
+    (csv_timelock, writeable),
+    (own_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}),
+    (punish_tx, writeable),
+    (own_signatures, vec),
+    (counter_revoke_signatures, vec)
+});
+
+impl PunishmentPackage {
+    /// Returns the id of the revoked transaction.
+    pub fn revoked_txid(&self) -> Result<Txid, Error> {
+        self.punish_tx
+            .input
+            .first()
+            .map(|x| x.previous_output.txid)
+            .ok_or_else(|| Error::InvalidState("Punishment transaction without input.".to_string()))
+    }
+
+    /// Encrypts the package using the id of the revoked transaction and a
+    /// random nonce, so that it can only be decrypted once the revoked
+    /// transaction is published.
+    pub fn encrypt(&self) -> Result<EncryptedPunishmentPackage, Error> {
+        let revoked_txid = self.revoked_txid()?;
+        let nonce = crate::utils::get_new_nonce();
+        let data = get_cipher(&revoked_txid)
+            .encrypt(Nonce::from_slice(&nonce), &self.encode()[..])
+            .map_err(|_| {
+                Error::InvalidState("Could not encrypt punishment package.".to_string())
+            })?;
+
+        Ok(EncryptedPunishmentPackage {
+            hint: get_hint(&revoked_txid),
+            nonce,
+            data,
+        })
+    }
+
+    /// Returns the signed punishment transaction for the given published
+    /// revoked transaction.
+    pub fn get_punishment_transaction<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        revoked_tx: &Transaction,
+    ) -> Result<Transaction, Error> {
+        if revoked_tx.txid() != self.revoked_txid()? {
+            return Err(Error::InvalidParameters(
+                "Transaction does not match the revoked transaction of the package.".to_string(),
+            ));
+        }
+
+        let offer_params = RevokeParams::from(&self.offer_revoke_keys);
+        let accept_params = RevokeParams::from(&self.accept_revoke_keys);
+        let (own_params, counter_params) = if self.is_offer {
+            (&offer_params, &accept_params)
+        } else {
+            (&accept_params, &offer_params)
+        };
+
+        let counter_publish_sk = self.recover_counter_publish_sk(secp, revoked_tx)?;
+        let mut punish_tx = self.punish_tx.clone();
+
+        match self.revoked_tx_type {
+            RevokedTxType::Buffer => {
+                let counter_publish_sigs = dlc::channel::sign_punish_buffer_transaction(
+                    secp,
+                    &offer_params,
+                    &accept_params,
+                    &punish_tx,
+                    revoked_tx,
+                    &counter_publish_sk,
+                )?;
+                let sigs = [
+                    (
+                        own_params.own_pk,
+                        to_sighash_all_signatures(&self.own_signatures),
+                    ),
+                    (
+                        counter_params.revoke_pk,
+                        to_sighash_all_signatures(&self.counter_revoke_signatures),
+                    ),
+                    (counter_params.publish_pk, counter_publish_sigs),
+                ];
+                dlc::channel::finalize_punish_buffer_transaction(
+                    &offer_params,
+                    &accept_params,
+                    &mut punish_tx,
+                    &sigs,
+                )?;
+            }
+            RevokedTxType::Settle => {
+                let counter_publish_sig = dlc::channel::sign_punish_settle_transaction(
+                    secp,
+                    &offer_params,
+                    &accept_params,
+                    &punish_tx,
+                    revoked_tx,
+                    &counter_publish_sk,
+                    self.csv_timelock,
+                    self.is_offer,
+                )?;
+                let (own_sig, counter_revoke_sig) = match (
+                    self.own_signatures.first(),
+                    self.counter_revoke_signatures.first(),
+                ) {
+                    (Some(own_sig), Some(counter_revoke_sig)) => (own_sig, counter_revoke_sig),
+                    _ => {
+                        return Err(Error::InvalidState(
+                            "Missing punishment transaction signature.".to_string(),
+                        ))
+                    }
+                };
+                let sigs = [
+                    (
+                        own_params.own_pk,
+                        bitcoin::ecdsa::Signature::sighash_all(*own_sig),
+                    ),
+                    (
+                        counter_params.revoke_pk,
+                        bitcoin::ecdsa::Signature::sighash_all(*counter_revoke_sig),
+                    ),
+                    (counter_params.publish_pk, counter_publish_sig),
+                ];
+                dlc::channel::finalize_punish_settle_transaction(
+                    &offer_params,
+                    &accept_params,
+                    &mut punish_tx,
+                    &sigs,
+                    self.csv_timelock,
+                    self.is_offer,
+                )?;
+            }
+        }
+
+        Ok(punish_tx)
+    }
+
+    /// Recovers the publish secret key of the counter party from the signature
+    /// of the local party included in the published revoked transaction, which
+    /// the counter party decrypted using it.
+    fn recover_counter_publish_sk<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        revoked_tx: &Transaction,
+    ) -> Result<SecretKey, Error> {
+        let counter_publish_pk = if self.is_offer {
+            self.accept_revoke_keys.publish_pk
+        } else {
+            self.offer_revoke_keys.publish_pk
+        };
+
+        let witness = revoked_tx
+            .input
+            .first()
+            .map(|x| x.witness.to_vec())
+            .unwrap_or_default();
+
+        for element in witness.iter().filter(|x| x.len() > 1) {
+            // Witness signatures are followed by their sighash type.
+            let sig = match Signature::from_der(&element[..element.len() - 1]) {
+                Ok(sig) => sig,
+                Err(_) => continue,
+            };
+            if let Ok(sk) = self
+                .own_adaptor_signature
+                .recover(secp, &sig, &counter_publish_pk)
+            {
+                if PublicKey::from_secret_key(secp, &sk) == counter_publish_pk {
+                    return Ok(sk);
+                }
+            }
+        }
+
+        Err(Error::InvalidParameters(
+            "Could not recover the publish secret key of the counter party.".to_string(),
+        ))
+    }
+}
+
+/// A [`PunishmentPackage`] encrypted using the id of its revoked transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedPunishmentPackage {
+    /// The hash of the id of the revoked transaction, used to find the package
+    /// matching a published transaction without revealing the id.
+    pub hint: [u8; 32],
+    /// The nonce used to encrypt the package.
+    pub nonce: [u8; 12],
+    /// The encrypted package.
+    pub data: Vec<u8>,
+}
+
+impl_dlc_writeable!(EncryptedPunishmentPackage, {
+    (hint, writeable),
+    (nonce, writeable),
+    (data, vec)
+});
+
+impl EncryptedPunishmentPackage {
+    /// Returns the hint of the package that would match the transaction with
+    /// the given id.
+    pub fn get_hint(txid: &Txid) -> [u8; 32] {
+        get_hint(txid)
+    }
+
+    /// Decrypts the package using the id of the published revoked transaction.
+    pub fn decrypt(&self, revoked_txid: &Txid) -> Result<PunishmentPackage, Error> {
+        if self.hint != get_hint(revoked_txid) {
+            return Err(Error::InvalidParameters(
+                "Transaction id does not match the package hint.".to_string(),
+            ));
+        }
+
+        let plaintext = get_cipher(revoked_txid)
+            .decrypt(Nonce::from_slice(&self.nonce), &self.data[..])
+            .map_err(|_| {
+                Error::InvalidParameters("Could not decrypt punishment package.".to_string())
+            })?;
+
+        Readable::read(&mut lightning::io::Cursor::new(plaintext)).map_err(|_| {
+            Error::InvalidParameters("Could not decode punishment package.".to_string())
+        })
+    }
+}
+
+fn get_hint(txid: &Txid) -> [u8; 32] {
+    sha256::Hash::hash(txid.as_byte_array()).to_byte_array()
+}
+
+fn get_cipher(txid: &Txid) -> ChaCha20Poly1305 {
+    ChaCha20Poly1305::new(Key::from_slice(txid.as_byte_array()))
+}
+
+fn to_sighash_all_signatures(sigs: &[Signature]) -> Vec<bitcoin::ecdsa::Signature> {
+    sigs.iter()
+        .map(|x| bitcoin::ecdsa::Signature::sighash_all(*x))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{Address, Network, OutPoint, TxIn};
+    use secp256k1_zkp::rand::thread_rng;
+
+    struct RevokeSecretKeys {
+        own_sk: SecretKey,
+        publish_sk: SecretKey,
+        revoke_sk: SecretKey,
+    }
+
+    impl RevokeSecretKeys {
+        fn new() -> Self {
+            RevokeSecretKeys {
+                own_sk: SecretKey::new(&mut thread_rng()),
+                publish_sk: SecretKey::new(&mut thread_rng()),
+                revoke_sk: SecretKey::new(&mut thread_rng()),
+            }
+        }
+
+        fn public_keys<C: Signing>(&self, secp: &Secp256k1<C>) -> RevokePublicKeys {
+            RevokePublicKeys {
+                own_pk: PublicKey::from_secret_key(secp, &self.own_sk),
+                publish_pk: PublicKey::from_secret_key(secp, &self.publish_sk),
+                revoke_pk: PublicKey::from_secret_key(secp, &self.revoke_sk),
+            }
+        }
+    }
+
+    #[test]
+    fn punishment_package_test() {
+        let secp = Secp256k1::new();
+        let own_keys = RevokeSecretKeys::new();
+        let counter_keys = RevokeSecretKeys::new();
+        let offer_revoke_keys = own_keys.public_keys(&secp);
+        let accept_revoke_keys = counter_keys.public_keys(&secp);
+        let offer_params = RevokeParams::from(&offer_revoke_keys);
+        let accept_params = RevokeParams::from(&accept_revoke_keys);
+
+        let own_fund_sk = SecretKey::new(&mut thread_rng());
+        let counter_fund_sk = SecretKey::new(&mut thread_rng());
+        let own_fund_pk = PublicKey::from_secret_key(&secp, &own_fund_sk);
+        let counter_fund_pk = PublicKey::from_secret_key(&secp, &counter_fund_sk);
+        let funding_script = dlc::make_funding_redeemscript(&own_fund_pk, &counter_fund_pk);
+        let fund_output_value = 200020000;
+
+        let mut buffer_tx = dlc::channel::create_buffer_transaction(
+            &TxIn {
+                previous_output: OutPoint::default(),
+                ..Default::default()
+            },
+            &dlc::channel::buffer_descriptor(&offer_params, &accept_params),
+            200000000,
+            0,
+        );
+
+        let own_adaptor_signature = dlc::channel::get_tx_adaptor_signature(
+            &secp,
+            &buffer_tx,
+            fund_output_value,
+            &funding_script,
+            &own_fund_sk,
+            &accept_revoke_keys.publish_pk,
+        )
+        .unwrap();
+
+        let dest_address = Address::p2wpkh(
+            &bitcoin::PublicKey::new(PublicKey::from_secret_key(
+                &secp,
+                &SecretKey::new(&mut thread_rng()),
+            )),
+            Network::Regtest,
+        )
+        .unwrap();
+
+        let punish_tx = dlc::channel::create_punish_buffer_transaction(
+            &offer_params,
+            &accept_params,
+            &buffer_tx,
+            &dest_address,
+            0,
+            2,
+        )
+        .unwrap();
+
+        let sign = |sk: &SecretKey| {
+            dlc::channel::sign_punish_buffer_transaction(
+                &secp,
+                &offer_params,
+                &accept_params,
+                &punish_tx,
+                &buffer_tx,
+                sk,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|x| x.sig)
+            .collect::<Vec<_>>()
+        };
+
+        let package = PunishmentPackage {
+            channel_id: [1u8; 32],
+            revoked_tx_type: RevokedTxType::Buffer,
+            is_offer: true,
+            offer_revoke_keys,
+            accept_revoke_keys,
+            csv_timelock: 288,
+            own_adaptor_signature,
+            punish_tx: punish_tx.clone(),
+            own_signatures: sign(&own_keys.own_sk),
+            counter_revoke_signatures: sign(&counter_keys.revoke_sk),
+        };
+
+        let encrypted = package.encrypt().unwrap();
+        let encrypted_again = package.encrypt().unwrap();
+        assert_ne!(encrypted.nonce, encrypted_again.nonce);
+        assert_ne!(encrypted.data, encrypted_again.data);
+        let buffer_txid = buffer_tx.txid();
+        assert_eq!(
+            EncryptedPunishmentPackage::get_hint(&buffer_txid),
+            encrypted.hint
+        );
+        assert!(encrypted.decrypt(&punish_tx.txid()).is_err());
+        let decrypted = encrypted.decrypt(&buffer_txid).unwrap();
+        assert_eq!(package, decrypted);
+
+        // The counter party publishes the revoked buffer transaction,
+        // decrypting the adaptor signature of the local party.
+        let own_sig = own_adaptor_signature
+            .decrypt(&counter_keys.publish_sk)
+            .unwrap();
+        dlc::util::sign_multi_sig_input(
+            &secp,
+            &mut buffer_tx,
+            &own_sig,
+            &own_fund_pk,
+            &counter_fund_sk,
+            &funding_script,
+            fund_output_value,
+            0,
+        )
+        .unwrap();
+
+        let signed_punish_tx = decrypted
+            .get_punishment_transaction(&secp, &buffer_tx)
+            .expect("to be able to complete the punishment transaction");
+
+        assert_eq!(punish_tx.txid(), signed_punish_tx.txid());
+        assert!(signed_punish_tx.input.iter().all(|x| !x.witness.is_empty()));
+    }
+}
@@ -5,10 +5,11 @@ use bitcoin::Amount;
 use bitcoin_test_utils::rpc_helpers::init_clients;
 use bitcoincore_rpc::RpcApi;
 use dlc_manager::contract::contract_input::ContractInput;
-use dlc_manager::manager::Manager;
+use dlc_manager::manager::{Manager, ManagerConfig};
 use dlc_manager::{
-    channel::Channel, contract::Contract, Blockchain, CachedContractSignerProvider, Oracle,
-    SimpleSigner, Storage, Wallet,
+    channel::{signed_channel::SignedChannelState, Channel},
+    contract::Contract,
+    Blockchain, CachedContractSignerProvider, Oracle, SimpleSigner, Storage, Wallet,
 };
 use dlc_manager::{ChannelId, ContractId};
 use dlc_messages::Message;
@@ -113,6 +114,10 @@ enum TestPath {
     RenewRace,
     RenewEstablishedClose,
     CancelOffer,
+    SettleConfirmReestablish,
+    RenewAcceptReestablish,
+    RenewConfirmReestablish,
+    SettleSplice,
 }
 
 #[test]
@@ -283,6 +288,39 @@ fn channel_offer_reject_test() {
     channel_execution_test(get_enum_test_params(1, 1, None), TestPath::CancelOffer);
 }
 
+#[test]
+#[ignore]
+fn channel_settle_confirm_reestablish_test() {
+    channel_execution_test(
+        get_enum_test_params(1, 1, None),
+        TestPath::SettleConfirmReestablish,
+    );
+}
+
+#[test]
+#[ignore]
+fn channel_renew_accept_reestablish_test() {
+    channel_execution_test(
+        get_enum_test_params(1, 1, None),
+        TestPath::RenewAcceptReestablish,
+    );
+}
+
+#[test]
+#[ignore]
+fn channel_renew_confirm_reestablish_test() {
+    channel_execution_test(
+        get_enum_test_params(1, 1, None),
+        TestPath::RenewConfirmReestablish,
+    );
+}
+
+#[test]
+#[ignore]
+fn channel_settle_splice_test() {
+    channel_execution_test(get_enum_test_params(1, 1, None), TestPath::SettleSplice);
+}
+
 fn channel_execution_test(test_params: TestParams, path: TestPath) {
     env_logger::init();
     let (alice_send, bob_receive) = channel::<Option<Message>>();
@@ -385,6 +423,7 @@ fn channel_execution_test(test_params: TestParams, path: TestPath) {
             alice_oracles,
             Arc::clone(&mock_time),
             Arc::clone(&electrs),
+            ManagerConfig::default(),
         )
         .unwrap(),
     ));
@@ -401,6 +440,7 @@ fn channel_execution_test(test_params: TestParams, path: TestPath) {
             bob_oracles,
             Arc::clone(&mock_time),
             Arc::clone(&electrs),
+            ManagerConfig::default(),
         )
         .unwrap(),
     ));
@@ -417,7 +457,32 @@ fn channel_execution_test(test_params: TestParams, path: TestPath) {
     let bob_expect_error_loop = bob_expect_error.clone();
 
     let path_copy = path.clone();
+    // Messages dropped to simulate a disconnection are only dropped once, so
+    // that they can be sent again after the channel is reestablished.
+    let dropped = Arc::new(AtomicBool::new(false));
     let msg_filter = move |msg| {
+        let drop_once = |dropped: &AtomicBool| !dropped.swap(true, Ordering::Relaxed);
+        if let TestPath::SettleConfirmReestablish = path_copy {
+            if let Message::SettleFinalize(_) = msg {
+                if drop_once(&dropped) {
+                    return None;
+                }
+            }
+        }
+        if let TestPath::RenewAcceptReestablish = path_copy {
+            if let Message::RenewConfirm(_) = msg {
+                if drop_once(&dropped) {
+                    return None;
+                }
+            }
+        }
+        if let TestPath::RenewConfirmReestablish = path_copy {
+            if let Message::RenewFinalize(_) = msg {
+                if drop_once(&dropped) {
+                    return None;
+                }
+            }
+        }
         if let TestPath::SettleAcceptTimeout = path_copy {
             if let Message::SettleConfirm(_) = msg {
                 return None;
@@ -612,6 +677,17 @@ fn channel_execution_test(test_params: TestParams, path: TestPath) {
                         path,
                     );
                 }
+                TestPath::SettleConfirmReestablish => {
+                    settle_reestablish(
+                        first,
+                        first_send,
+                        first_receive,
+                        second,
+                        second_send,
+                        second_receive,
+                        channel_id,
+                    );
+                }
                 TestPath::SettleReject => {
                     settle_reject(
                         first,
@@ -705,6 +781,19 @@ fn channel_execution_test(test_params: TestParams, path: TestPath) {
                                 &generate_blocks,
                             );
                         }
+                        TestPath::RenewAcceptReestablish | TestPath::RenewConfirmReestablish => {
+                            renew_reestablish(
+                                first,
+                                first_send,
+                                first_receive,
+                                second,
+                                second_send,
+                                second_receive,
+                                channel_id,
+                                &test_params.contract_input,
+                                path,
+                            );
+                        }
                         TestPath::RenewReject => {
                             renew_reject(
                                 first,
@@ -764,6 +853,18 @@ fn channel_execution_test(test_params: TestParams, path: TestPath) {
                                 cheat_punish(first, second, channel_id, &generate_blocks, false);
                             }
                         }
+                        TestPath::SettleSplice => {
+                            splice_channel(
+                                first,
+                                first_send,
+                                first_receive,
+                                second,
+                                second_send,
+                                second_receive,
+                                channel_id,
+                                &generate_blocks,
+                            );
+                        }
                         TestPath::SettleRenewSettle => {
                             renew_channel(
                                 first.clone(),
@@ -934,6 +1035,89 @@ fn settle_channel(
     assert_channel_state!(second, channel_id, Signed, Settled);
 }
 
+fn get_settled_own_payout(dlc_party: &DlcParty, channel_id: &ChannelId) -> u64 {
+    let channel = dlc_party
+        .lock()
+        .unwrap()
+        .get_store()
+        .get_channel(channel_id)
+        .unwrap()
+        .unwrap();
+    if let Channel::Signed(s) = &channel {
+        if let SignedChannelState::Settled { own_payout, .. } = s.state {
+            return own_payout;
+        }
+    }
+
+    panic!("Invalid channel state {:?}.", channel);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn splice_channel<F: Fn(u64)>(
+    first: DlcParty,
+    first_send: &Sender<Option<Message>>,
+    first_receive: &Receiver<()>,
+    second: DlcParty,
+    second_send: &Sender<Option<Message>>,
+    second_receive: &Receiver<()>,
+    channel_id: ChannelId,
+    generate_blocks: &F,
+) {
+    let own_payout = get_settled_own_payout(&first, &channel_id);
+    let counter_payout = get_settled_own_payout(&second, &channel_id);
+
+    // Splice in some funds.
+    let (splice_offer, _) = first
+        .lock()
+        .unwrap()
+        .splice_channel(&channel_id, own_payout + 10_000_000, 2)
+        .expect("to be able to offer to splice the channel.");
+
+    first_send
+        .send(Some(Message::SpliceOffer(splice_offer)))
+        .unwrap();
+
+    second_receive.recv().expect("Error synchronizing");
+
+    assert_channel_state!(first, channel_id, Signed, SpliceOffered);
+
+    assert_channel_state!(second, channel_id, Signed, SpliceReceived);
+
+    let (splice_accept, _) = second
+        .lock()
+        .unwrap()
+        .accept_splice_offer(&channel_id)
+        .expect("to be able to accept a splice offer");
+
+    second_send
+        .send(Some(Message::SpliceAccept(splice_accept)))
+        .unwrap();
+
+    // Process Accept
+    first_receive.recv().expect("Error synchronizing");
+    // Process Confirm
+    second_receive.recv().expect("Error synchronizing");
+
+    assert_channel_state!(first, channel_id, Signed, SpliceConfirmed);
+
+    assert_channel_state!(second, channel_id, Signed, SpliceConfirmed);
+
+    generate_blocks(1);
+
+    periodic_check(first.clone());
+    periodic_check(second.clone());
+
+    assert_channel_state!(first, channel_id, Signed, Settled);
+
+    assert_channel_state!(second, channel_id, Signed, Settled);
+
+    assert_eq!(
+        own_payout + 10_000_000,
+        get_settled_own_payout(&first, &channel_id)
+    );
+    assert_eq!(counter_payout, get_settled_own_payout(&second, &channel_id));
+}
+
 fn settle_reject(
     first: DlcParty,
     first_send: &Sender<Option<Message>>,
@@ -1371,3 +1555,149 @@ fn settle_timeout(
         }
     }
 }
+
+fn send_channel_reestablish(party: &DlcParty, send: &Sender<Option<Message>>) {
+    let msgs = party
+        .lock()
+        .unwrap()
+        .get_channel_reestablish_messages(
+            &"0218845781f631c48f1c9709e23092067d06837f30aa0cd0544ac887fe91ddd166"
+                .parse()
+                .unwrap(),
+        )
+        .expect("to be able to get the channel reestablish messages.");
+    assert_eq!(1, msgs.len());
+
+    for msg in msgs {
+        send.send(Some(Message::ChannelReestablish(msg))).unwrap();
+    }
+}
+
+fn settle_reestablish(
+    first: DlcParty,
+    first_send: &Sender<Option<Message>>,
+    first_receive: &Receiver<()>,
+    second: DlcParty,
+    second_send: &Sender<Option<Message>>,
+    second_receive: &Receiver<()>,
+    channel_id: ChannelId,
+) {
+    let (settle_offer, _) = first
+        .lock()
+        .unwrap()
+        .settle_offer(&channel_id, test_utils::ACCEPT_COLLATERAL)
+        .expect("to be able to offer a settlement of the contract.");
+
+    first_send
+        .send(Some(Message::SettleOffer(settle_offer)))
+        .unwrap();
+
+    second_receive.recv().expect("Error synchronizing");
+
+    let (settle_accept, _) = second
+        .lock()
+        .unwrap()
+        .accept_settle_offer(&channel_id)
+        .expect("to be able to accept a settlement offer");
+
+    second_send
+        .send(Some(Message::SettleAccept(settle_accept)))
+        .unwrap();
+
+    // Process Accept
+    first_receive.recv().expect("Error synchronizing");
+    // Process Confirm, the finalize message gets lost
+    second_receive.recv().expect("Error synchronizing");
+
+    assert_channel_state!(first, channel_id, Signed, SettledConfirmed);
+    assert_channel_state!(second, channel_id, Signed, Settled);
+
+    send_channel_reestablish(&first, first_send);
+    // Process reestablish, resending finalize
+    second_receive.recv().expect("Error synchronizing");
+    // Process Finalize
+    first_receive.recv().expect("Error synchronizing");
+
+    send_channel_reestablish(&second, second_send);
+    first_receive.recv().expect("Error synchronizing");
+
+    assert_channel_state!(first, channel_id, Signed, Settled);
+    assert_channel_state!(second, channel_id, Signed, Settled);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn renew_reestablish(
+    first: DlcParty,
+    first_send: &Sender<Option<Message>>,
+    first_receive: &Receiver<()>,
+    second: DlcParty,
+    second_send: &Sender<Option<Message>>,
+    second_receive: &Receiver<()>,
+    channel_id: ChannelId,
+    contract_input: &ContractInput,
+    path: TestPath,
+) {
+    let (renew_offer, _) = first
+        .lock()
+        .unwrap()
+        .renew_offer(&channel_id, test_utils::ACCEPT_COLLATERAL, contract_input)
+        .expect("to be able to renew channel contract");
+
+    first_send
+        .send(Some(Message::RenewOffer(renew_offer)))
+        .unwrap();
+
+    second_receive.recv().expect("Error synchronizing");
+
+    let (renew_accept, _) = second
+        .lock()
+        .unwrap()
+        .accept_renew_offer(&channel_id)
+        .expect("to be able to accept a renewal offer");
+
+    second_send
+        .send(Some(Message::RenewAccept(renew_accept)))
+        .unwrap();
+
+    // Process Accept
+    first_receive.recv().expect("Error synchronizing");
+
+    if let TestPath::RenewAcceptReestablish = path {
+        // The confirm message got lost
+        assert_channel_state!(first, channel_id, Signed, RenewConfirmed);
+        assert_channel_state!(second, channel_id, Signed, RenewAccepted);
+
+        send_channel_reestablish(&first, first_send);
+        second_receive.recv().expect("Error synchronizing");
+
+        send_channel_reestablish(&second, second_send);
+        // Process reestablish, resending confirm
+        first_receive.recv().expect("Error synchronizing");
+        // Process Confirm
+        second_receive.recv().expect("Error synchronizing");
+        // Process Finalize
+        first_receive.recv().expect("Error synchronizing");
+        // Process Revoke
+        second_receive.recv().expect("Error synchronizing");
+    } else {
+        // Process Confirm, the finalize message gets lost
+        second_receive.recv().expect("Error synchronizing");
+
+        assert_channel_state!(first, channel_id, Signed, RenewConfirmed);
+        assert_channel_state!(second, channel_id, Signed, RenewFinalized);
+
+        send_channel_reestablish(&first, first_send);
+        // Process reestablish, resending finalize
+        second_receive.recv().expect("Error synchronizing");
+        // Process Finalize
+        first_receive.recv().expect("Error synchronizing");
+        // Process Revoke
+        second_receive.recv().expect("Error synchronizing");
+
+        send_channel_reestablish(&second, second_send);
+        first_receive.recv().expect("Error synchronizing");
+    }
+
+    assert_channel_state!(first, channel_id, Signed, Established);
+    assert_channel_state!(second, channel_id, Signed, Established);
+}
@@ -9,6 +9,7 @@ extern crate dlc_manager;
 mod test_utils;
 
 use bitcoin::Amount;
+use dlc::FundingOutputType;
 use dlc_manager::payout_curve::PayoutFunctionPiece;
 use electrs_blockchain_provider::ElectrsBlockchainProvider;
 use simple_wallet::SimpleWallet;
@@ -17,9 +18,10 @@ use test_utils::*;
 use bitcoin_test_utils::rpc_helpers::init_clients;
 use bitcoincore_rpc::RpcApi;
 use dlc_manager::contract::{numerical_descriptor::DifferenceParams, Contract};
-use dlc_manager::manager::Manager;
+use dlc_manager::manager::{Manager, ManagerConfig};
 use dlc_manager::{Blockchain, Oracle, Storage, Wallet};
 use dlc_messages::oracle_msgs::OracleAttestation;
+use dlc_messages::reject::RejectErrorCode;
 use dlc_messages::{AcceptDlc, OfferDlc, SignDlc};
 use dlc_messages::{CetAdaptorSignatures, Message};
 use lightning::ln::wire::Type;
@@ -89,6 +91,10 @@ fn create_test_vector() {
 
 macro_rules! periodic_check {
     ($d:expr, $id:expr, $p:ident) => {
+        $d.lock()
+            .unwrap()
+            .periodic_chain_monitor()
+            .expect("Chain monitor error");
         $d.lock()
             .unwrap()
             .periodic_check(true)
@@ -182,6 +188,7 @@ enum TestPath {
     BadAcceptRefundSignature,
     BadSignCetSignature,
     BadSignRefundSignature,
+    RejectOffer,
 }
 
 #[test]
@@ -280,6 +287,46 @@ fn enum_3_of_5_manual_test() {
     manager_execution_test(get_enum_test_params(5, 3, None), TestPath::Close, true);
 }
 
+#[test]
+#[ignore]
+fn enum_single_oracle_taproot_test() {
+    manager_execution_test(
+        with_taproot_funding(get_enum_test_params(1, 1, None)),
+        TestPath::Close,
+        false,
+    );
+}
+
+#[test]
+#[ignore]
+fn enum_3_of_5_taproot_test() {
+    manager_execution_test(
+        with_taproot_funding(get_enum_test_params(5, 3, None)),
+        TestPath::Close,
+        false,
+    );
+}
+
+#[test]
+#[ignore]
+fn enum_single_oracle_taproot_refund_test() {
+    manager_execution_test(
+        with_taproot_funding(get_enum_test_params(1, 1, Some(get_enum_oracles(1, 0)))),
+        TestPath::Refund,
+        false,
+    );
+}
+
+#[test]
+#[ignore]
+fn enum_and_numerical_3_of_5_taproot_test() {
+    manager_execution_test(
+        with_taproot_funding(get_enum_and_numerical_test_params(5, 3, false, None)),
+        TestPath::Close,
+        false,
+    );
+}
+
 #[test]
 #[ignore]
 fn enum_and_numerical_with_diff_3_of_5_test() {
@@ -380,6 +427,16 @@ fn enum_single_oracle_refund_manual_test() {
     );
 }
 
+#[test]
+#[ignore]
+fn enum_single_oracle_reject_offer_test() {
+    manager_execution_test(
+        get_enum_test_params(1, 1, Some(get_enum_oracles(1, 0))),
+        TestPath::RejectOffer,
+        false,
+    );
+}
+
 #[test]
 #[ignore]
 fn enum_single_oracle_bad_accept_cet_sig_test() {
@@ -530,6 +587,11 @@ fn alter_refund_sig(refund_signature: &Signature) -> Signature {
     Signature::from_compact(&copy).unwrap()
 }
 
+fn with_taproot_funding(mut test_params: TestParams) -> TestParams {
+    test_params.contract_input.funding_output_type = FundingOutputType::Taproot;
+    test_params
+}
+
 fn get_attestations(test_params: &TestParams) -> Vec<(usize, OracleAttestation)> {
     for contract_info in test_params.contract_input.contract_infos.iter() {
         let attestations: Vec<_> = contract_info
@@ -544,7 +606,7 @@ fn get_attestations(test_params: &TestParams) -> Vec<(usize, OracleAttestation)>
                     .find(|x| x.get_public_key() == *pk);
 
                 oracle
-                    .and_then(|o| o.get_attestation(&contract_info.oracles.event_id).ok())
+                    .and_then(|o| o.get_attestation(&contract_info.oracles.event_ids[0]).ok())
                     .map(|a| (i, a))
             })
             .collect();
@@ -658,6 +720,7 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
             alice_oracles,
             Arc::clone(&mock_time),
             Arc::clone(&electrs),
+            ManagerConfig::default(),
         )
         .unwrap(),
     ));
@@ -674,6 +737,7 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
             bob_oracles,
             Arc::clone(&mock_time),
             Arc::clone(&electrs),
+            ManagerConfig::default(),
         )
         .unwrap(),
     ));
@@ -697,7 +761,8 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
                     alter_adaptor_sig(&mut sign_dlc.cet_adaptor_signatures)
                 }
                 TestPath::BadSignRefundSignature => {
-                    sign_dlc.refund_signature = alter_refund_sig(&sign_dlc.refund_signature);
+                    sign_dlc.refund_signature =
+                        sign_dlc.refund_signature.as_ref().map(alter_refund_sig);
                 }
                 _ => {}
             }
@@ -753,6 +818,26 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
 
     assert_contract_state!(alice_manager_send, temporary_contract_id, Offered);
 
+    if let TestPath::RejectOffer = path {
+        let (reject_msg, _) = alice_manager_send
+            .lock()
+            .unwrap()
+            .reject_contract_offer(
+                &temporary_contract_id,
+                RejectErrorCode::UnacceptableTerms,
+                "Collateral is too high.".to_string(),
+            )
+            .expect("Error rejecting contract offer");
+        assert_contract_state!(alice_manager_send, temporary_contract_id, Rejected);
+        alice_send
+            .send(Some(Message::RejectOffer(reject_msg)))
+            .unwrap();
+
+        sync_receive.recv().expect("Error synchronizing");
+        assert_contract_state!(bob_manager_send, temporary_contract_id, Rejected);
+        return;
+    }
+
     let (contract_id, _, mut accept_msg) = alice_manager_send
         .lock()
         .unwrap()
@@ -770,7 +855,8 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
                     alter_adaptor_sig(&mut accept_msg.cet_adaptor_signatures)
                 }
                 TestPath::BadAcceptRefundSignature => {
-                    accept_msg.refund_signature = alter_refund_sig(&accept_msg.refund_signature);
+                    accept_msg.refund_signature =
+                        accept_msg.refund_signature.as_ref().map(alter_refund_sig);
                 }
                 _ => {}
             };
@@ -839,7 +925,7 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
                             .expect("Error closing contract");
 
                         if let Contract::PreClosed(contract) = contract {
-                            let mut s = second.lock().unwrap();
+                            let s = second.lock().unwrap();
                             let second_contract =
                                 s.get_store().get_contract(&contract_id).unwrap().unwrap();
                             if let Contract::Confirmed(signed) = second_contract {
@@ -885,18 +971,24 @@ fn manager_execution_test(test_params: TestParams, path: TestPath, manual_close:
 
                     generate_blocks(10);
 
-                    periodic_check!(first, contract_id, Refunded);
+                    periodic_check!(first, contract_id, PreRefunded);
 
                     // Randomly check with or without having the Refund mined.
                     if thread_rng().next_u32() % 2 == 0 {
                         generate_blocks(1);
                     }
 
+                    periodic_check!(second, contract_id, PreRefunded);
+
+                    generate_blocks(dlc_manager::manager::NB_CONFIRMATIONS as u64);
+
+                    periodic_check!(first, contract_id, Refunded);
                     periodic_check!(second, contract_id, Refunded);
                 }
                 _ => unreachable!(),
             }
         }
+        TestPath::RejectOffer => unreachable!(),
     }
 
     alice_send.send(None).unwrap();
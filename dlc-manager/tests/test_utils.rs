@@ -8,7 +8,7 @@ extern crate dlc_manager;
 
 use std::ops::Deref;
 
-use dlc::{EnumerationPayout, Payout};
+use dlc::{EnumerationPayout, FundingOutputType, Payout};
 use dlc_manager::payout_curve::{
     PayoutFunction, PayoutFunctionPiece, PayoutPoint, PolynomialPayoutCurvePiece, RoundingInterval,
     RoundingIntervals,
@@ -281,7 +281,7 @@ pub fn get_enum_test_params(
         contract_descriptor,
         oracles: OracleInput {
             public_keys: oracles.iter().map(|x| x.get_public_key()).collect(),
-            event_id: EVENT_ID.to_owned(),
+            event_ids: vec![EVENT_ID.to_owned()],
             threshold: threshold as u16,
         },
     };
@@ -290,6 +290,11 @@ pub fn get_enum_test_params(
         offer_collateral: OFFER_COLLATERAL,
         accept_collateral: ACCEPT_COLLATERAL,
         fee_rate: 2,
+        anchor_outputs: false,
+        single_funded: false,
+        funding_output_type: FundingOutputType::P2wsh,
+        refund_delay: None,
+        offer_expiry: None,
         contract_infos: vec![contract_info],
     };
 
@@ -478,7 +483,7 @@ pub fn get_numerical_test_params(
     let contract_info = ContractInputInfo {
         oracles: OracleInput {
             public_keys: oracles.iter().map(|x| x.get_public_key()).collect(),
-            event_id: EVENT_ID.to_owned(),
+            event_ids: vec![EVENT_ID.to_owned()],
             threshold: threshold as u16,
         },
         contract_descriptor,
@@ -488,6 +493,11 @@ pub fn get_numerical_test_params(
         offer_collateral: OFFER_COLLATERAL,
         accept_collateral: ACCEPT_COLLATERAL,
         fee_rate: 2,
+        anchor_outputs: false,
+        single_funded: false,
+        funding_output_type: FundingOutputType::P2wsh,
+        refund_delay: None,
+        offer_expiry: None,
         contract_infos: vec![contract_info],
     };
 
@@ -509,7 +519,7 @@ pub fn get_enum_and_numerical_test_params(
     let enum_contract_info = ContractInputInfo {
         oracles: OracleInput {
             public_keys: enum_oracles.iter().map(|x| x.get_public_key()).collect(),
-            event_id: EVENT_ID.to_owned(),
+            event_ids: vec![EVENT_ID.to_owned()],
             threshold: threshold as u16,
         },
         contract_descriptor: enum_contract_descriptor,
@@ -527,7 +537,7 @@ pub fn get_enum_and_numerical_test_params(
                 .iter()
                 .map(|x| x.get_public_key())
                 .collect(),
-            event_id: EVENT_ID.to_owned(),
+            event_ids: vec![EVENT_ID.to_owned()],
             threshold: threshold as u16,
         },
         contract_descriptor: numerical_contract_descriptor,
@@ -543,6 +553,11 @@ pub fn get_enum_and_numerical_test_params(
         offer_collateral: OFFER_COLLATERAL,
         accept_collateral: ACCEPT_COLLATERAL,
         fee_rate: 2,
+        anchor_outputs: false,
+        single_funded: false,
+        funding_output_type: FundingOutputType::P2wsh,
+        refund_delay: None,
+        offer_expiry: None,
         contract_infos,
     };
 
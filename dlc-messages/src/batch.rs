@@ -0,0 +1,143 @@
+//! Contains messages used for the establishment of a batch of DLCs funded by a
+//! single funding transaction.
+
+use bitcoin::Txid;
+use dlc::Error;
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+use secp256k1_zkp::{Secp256k1, Verification};
+
+use crate::{AcceptDlc, FundingInput, FundingSignatures, OfferDlc, SignDlc};
+
+/// Contains information about a party wishing to enter into a batch of DLCs
+/// with another party, all funded by a single funding transaction in which each
+/// contract has its own fund output.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct OfferBatchDlc {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// Temporary id of the batch.
+    pub temporary_batch_id: [u8; 32],
+    /// Inputs used by the offer party to fund all the contracts of the batch.
+    pub funding_inputs: Vec<FundingInput>,
+    /// The offer for each contract of the batch. They do not contain any
+    /// funding input and all use the same change script pubkey and serial id.
+    pub offer_dlcs: Vec<OfferDlc>,
+}
+
+impl OfferBatchDlc {
+    /// Returns whether the message satisfies validity requirements.
+    pub fn validate<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        min_timeout_interval: u32,
+        max_timeout_interval: u32,
+    ) -> Result<(), Error> {
+        let first = self.offer_dlcs.first().ok_or(Error::InvalidArgument)?;
+
+        for (i, offer) in self.offer_dlcs.iter().enumerate() {
+            offer.validate(secp, min_timeout_interval, max_timeout_interval)?;
+
+            let is_consistent = offer.funding_inputs.is_empty()
+                && offer.fee_payer.is_none()
+                && offer.change_spk == first.change_spk
+                && offer.change_serial_id == first.change_serial_id
+                && offer.fee_rate_per_vb == first.fee_rate_per_vb
+                && self.offer_dlcs[..i]
+                    .iter()
+                    .all(|x| x.temporary_contract_id != offer.temporary_contract_id);
+            if !is_consistent {
+                return Err(Error::InvalidArgument);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl_dlc_writeable!(OfferBatchDlc, {
+    (protocol_version, writeable),
+    (temporary_batch_id, writeable),
+    (funding_inputs, vec),
+    (offer_dlcs, vec)
+});
+
+/// Contains information about a party wishing to accept a batch of DLCs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AcceptBatchDlc {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// Temporary id of the batch.
+    pub temporary_batch_id: [u8; 32],
+    /// Inputs used by the accept party to fund all the contracts of the batch.
+    pub funding_inputs: Vec<FundingInput>,
+    /// The id of the funding transaction shared by the contracts of the batch.
+    pub fund_txid: Txid,
+    /// The accept message for each contract of the batch. They do not contain
+    /// any funding input and all use the same change script pubkey and serial id.
+    pub accept_dlcs: Vec<AcceptDlc>,
+    /// The index of the fund output of each contract in the funding
+    /// transaction, in the same order as `accept_dlcs`.
+    pub fund_output_indexes: Vec<u16>,
+}
+
+impl_dlc_writeable!(AcceptBatchDlc, {
+    (protocol_version, writeable),
+    (temporary_batch_id, writeable),
+    (funding_inputs, vec),
+    (fund_txid, writeable),
+    (accept_dlcs, vec),
+    (fund_output_indexes, vec)
+});
+
+/// Contains all the required signatures from the offer party for a batch of
+/// DLCs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SignBatchDlc {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    /// The id of the funding transaction shared by the contracts of the batch.
+    pub fund_txid: Txid,
+    /// The signatures of the offer party for their inputs in the funding
+    /// transaction.
+    pub funding_signatures: FundingSignatures,
+    /// The sign message for each contract of the batch. They do not contain
+    /// any funding signature.
+    pub sign_dlcs: Vec<SignDlc>,
+}
+
+impl_dlc_writeable!(SignBatchDlc, {
+    (protocol_version, writeable),
+    (fund_txid, writeable),
+    (funding_signatures, writeable),
+    (sign_dlcs, vec)
+});
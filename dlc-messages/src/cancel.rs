@@ -0,0 +1,32 @@
+//! Contains the message used by the offer party of a DLC to withdraw an offer
+//! that was not accepted yet.
+
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+/// Message used to inform the counter party that a contract offer was
+/// cancelled and cannot be accepted anymore.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CancelOffer {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The temporary id of the cancelled contract offer.
+    pub temporary_contract_id: [u8; 32],
+}
+
+impl_dlc_writeable!(CancelOffer, {
+    (protocol_version, writeable),
+    (temporary_contract_id, writeable)
+});
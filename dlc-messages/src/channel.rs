@@ -270,12 +270,16 @@ pub struct SettleOffer {
     /// The per update point to be used by the sending party to setup the next
     /// channel state.
     pub next_per_update_point: PublicKey,
+    /// The percentage (from 0 to 100) of the fee of the settle transaction paid
+    /// by the sending party.
+    pub offer_fee_share: u8,
 }
 
 impl_dlc_writeable!(SettleOffer, {
     (channel_id, writeable),
     (counter_payout, writeable),
-    (next_per_update_point, writeable)
+    (next_per_update_point, writeable),
+    (offer_fee_share, writeable)
 });
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -400,6 +404,9 @@ pub struct RenewOffer {
     pub refund_locktime: u32,
     /// The nSequence value to use for the CETs.
     pub cet_nsequence: u32,
+    /// The percentage (from 0 to 100) of the fee of the renewed buffer
+    /// transaction and CETs paid by the sending party.
+    pub offer_fee_share: u8,
 }
 
 impl_dlc_writeable!(RenewOffer, {
@@ -410,7 +417,8 @@ impl_dlc_writeable!(RenewOffer, {
     (contract_info, writeable),
     (cet_locktime, writeable),
     (refund_locktime, writeable),
-    (cet_nsequence, writeable)
+    (cet_nsequence, writeable),
+    (offer_fee_share, writeable)
 });
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -559,12 +567,16 @@ pub struct CollaborativeCloseOffer {
     pub counter_payout: u64,
     /// The signature of the sending party for the closing transaction.
     pub close_signature: Signature,
+    /// The percentage (from 0 to 100) of the fee of the closing transaction
+    /// paid by the sending party.
+    pub offer_fee_share: u8,
 }
 
 impl_dlc_writeable!(CollaborativeCloseOffer, {
     (channel_id, writeable),
     (counter_payout, writeable),
-    (close_signature, writeable)
+    (close_signature, writeable),
+    (offer_fee_share, writeable)
 });
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -588,3 +600,209 @@ pub struct Reject {
 }
 
 impl_dlc_writeable!(Reject, { (channel_id, writeable) });
+
+/// The step of the channel update protocol at which a party is, as reported in a
+/// [`ChannelReestablish`] message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum ChannelUpdateState {
+    /// A contract is established in the channel and no update is in progress.
+    Established,
+    /// The party sent a [`SettleOffer`].
+    SettledOffered,
+    /// The party received a [`SettleOffer`].
+    SettledReceived,
+    /// The party sent a [`SettleAccept`].
+    SettledAccepted,
+    /// The party sent a [`SettleConfirm`].
+    SettledConfirmed,
+    /// The channel is settled and no update is in progress.
+    Settled,
+    /// The party sent a [`RenewOffer`].
+    RenewOffered,
+    /// The party received a [`RenewOffer`].
+    RenewReceived,
+    /// The party sent a [`RenewAccept`].
+    RenewAccepted,
+    /// The party sent a [`RenewConfirm`].
+    RenewConfirmed,
+    /// The party sent a [`RenewFinalize`].
+    RenewFinalized,
+    /// The party sent a [`CollaborativeCloseOffer`].
+    CollaborativeCloseOffered,
+    /// The party received a [`CollaborativeCloseOffer`].
+    CollaborativeCloseReceived,
+    /// The party sent a [`SpliceOffer`].
+    SpliceOffered,
+    /// The party received a [`SpliceOffer`].
+    SpliceReceived,
+    /// The party sent a [`SpliceAccept`].
+    SpliceAccepted,
+    /// The party sent or received a [`SpliceConfirm`] and is waiting for the
+    /// splice transaction to confirm.
+    SpliceConfirmed,
+}
+
+impl_dlc_writeable_enum!(ChannelUpdateState,;;;
+    (0, Established),
+    (1, SettledOffered),
+    (2, SettledReceived),
+    (3, SettledAccepted),
+    (4, SettledConfirmed),
+    (5, Settled),
+    (6, RenewOffered),
+    (7, RenewReceived),
+    (8, RenewAccepted),
+    (9, RenewConfirmed),
+    (10, RenewFinalized),
+    (11, CollaborativeCloseOffered),
+    (12, CollaborativeCloseReceived),
+    (13, SpliceOffered),
+    (14, SpliceReceived),
+    (15, SpliceAccepted),
+    (16, SpliceConfirmed)
+);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Message sent to the counter party upon reconnection to resynchronize the
+/// state of a channel, so that updates interrupted by a disconnection can be
+/// completed or rolled back.
+pub struct ChannelReestablish {
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the channel referred to by the message.
+    pub channel_id: [u8; 32],
+    /// The current update index of the channel for the sending party.
+    pub update_idx: u64,
+    /// The step of the channel update protocol at which the sending party is.
+    pub update_state: ChannelUpdateState,
+}
+
+impl_dlc_writeable!(ChannelReestablish, {
+    (channel_id, writeable),
+    (update_idx, writeable),
+    (update_state, writeable)
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Message used to offer to splice funds in or out of a settled channel, by
+/// spending its fund output together with the given inputs in a transaction
+/// creating a new fund output.
+pub struct SpliceOffer {
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the channel referred to by the message.
+    pub channel_id: [u8; 32],
+    /// The balance of the sending party in the channel after the splice.
+    pub offer_balance: u64,
+    /// The inputs used by the sending party to add funds to the channel.
+    pub funding_inputs: Vec<FundingInput>,
+    /// The script pubkey receiving the change of the inputs or the funds
+    /// spliced out of the channel.
+    pub change_spk: ScriptBuf,
+    /// The fee rate used for the splice transaction, paid by the sending party.
+    pub fee_rate_per_vb: u64,
+    /// The percentage (from 0 to 100) of the fee of the new settle transaction
+    /// paid by the sending party.
+    pub offer_fee_share: u8,
+    /// The adaptor signature of the sending party for the settle transaction
+    /// spending the new fund output.
+    pub settle_adaptor_signature: EcdsaAdaptorSignature,
+}
+
+impl_dlc_writeable!(SpliceOffer, {
+    (channel_id, writeable),
+    (offer_balance, writeable),
+    (funding_inputs, vec),
+    (change_spk, writeable),
+    (fee_rate_per_vb, writeable),
+    (offer_fee_share, writeable),
+    (settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature})
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Message used to accept a [`SpliceOffer`].
+pub struct SpliceAccept {
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the channel referred to by the message.
+    pub channel_id: [u8; 32],
+    /// The adaptor signature of the sending party for the settle transaction
+    /// spending the new fund output.
+    pub settle_adaptor_signature: EcdsaAdaptorSignature,
+    /// The signature of the sending party for the fund output input of the
+    /// splice transaction.
+    pub splice_signature: Signature,
+}
+
+impl_dlc_writeable!(SpliceAccept, {
+    (channel_id, writeable),
+    (settle_adaptor_signature, {cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature}),
+    (splice_signature, writeable)
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Message used to provide the signatures of the offer party for the splice
+/// transaction, after which it can be broadcast.
+pub struct SpliceConfirm {
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the channel referred to by the message.
+    pub channel_id: [u8; 32],
+    /// The signature of the sending party for the fund output input of the
+    /// splice transaction.
+    pub splice_signature: Signature,
+    /// The signatures for the inputs added to the splice transaction by the
+    /// sending party.
+    pub funding_signatures: FundingSignatures,
+}
+
+impl_dlc_writeable!(SpliceConfirm, {
+    (channel_id, writeable),
+    (splice_signature, writeable),
+    (funding_signatures, writeable)
+});
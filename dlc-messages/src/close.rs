@@ -0,0 +1,70 @@
+//! Contains messages used to collaboratively close a DLC before its maturity,
+//! spending the funding output directly.
+
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+use secp256k1_zkp::ecdsa::Signature;
+
+/// Message used to offer to collaboratively close a DLC with the given payout
+/// for the receiving party.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CloseOffer {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the contract referred to by the message.
+    pub contract_id: [u8; 32],
+    /// The proposed payout for the receiving party to close the contract with.
+    pub counter_payout: u64,
+    /// The signature of the sending party for the closing transaction.
+    pub close_signature: Signature,
+}
+
+impl_dlc_writeable!(CloseOffer, {
+    (protocol_version, writeable),
+    (contract_id, writeable),
+    (counter_payout, writeable),
+    (close_signature, writeable)
+});
+
+/// Message used to accept an offer to collaboratively close a DLC. The sending
+/// party broadcasts the closing transaction, and includes its signature so that
+/// the receiving party can do so as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CloseAccept {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the contract referred to by the message.
+    pub contract_id: [u8; 32],
+    /// The signature of the sending party for the closing transaction.
+    pub close_signature: Signature,
+}
+
+impl_dlc_writeable!(CloseAccept, {
+    (protocol_version, writeable),
+    (contract_id, writeable),
+    (close_signature, writeable)
+});
@@ -1,5 +1,6 @@
 //! Structure containing information about contract details.
 
+use crate::ser_impls::{read_string, write_string};
 use lightning::ln::msgs::DecodeError;
 use lightning::util::ser::{Readable, Writeable, Writer};
 use oracle_msgs::OracleInfo;
@@ -21,6 +22,49 @@ pub struct ContractOutcome {
 
 impl_dlc_writeable!(ContractOutcome, {(outcome, string), (offer_payout, writeable)});
 
+#[derive(Clone, PartialEq, Debug, Eq)]
+#[cfg_attr(
+    any(test, feature = "use-serde"),
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "camelCase")
+)]
+/// Represents a joint outcome of the events of a multi event DLC contract and
+/// the associated offer party payout.
+pub struct MultiEventContractOutcome {
+    /// The outcome of each event, in the order in which the events appear in
+    /// the oracle announcements of the contract.
+    pub outcomes: Vec<EventOutcome>,
+    /// The payout of the offer party for the joint outcome.
+    pub offer_payout: u64,
+}
+
+impl_dlc_writeable!(MultiEventContractOutcome, {(outcomes, vec), (offer_payout, writeable)});
+
+#[derive(Clone, PartialEq, Debug, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(test, feature = "use-serde"),
+    derive(serde::Deserialize, serde::Serialize),
+    serde(untagged)
+)]
+/// The outcome of one of the events of a multi event DLC contract.
+pub enum EventOutcome {
+    /// An outcome of an enumerated outcome event.
+    Enum(String),
+    /// The outcomes of a numerical outcome event included in the range
+    /// `[start, end]`.
+    Range {
+        /// The first outcome of the range.
+        start: u64,
+        /// The last outcome of the range.
+        end: u64,
+    },
+}
+
+impl_dlc_writeable_enum!(EventOutcome,;
+    (1, Range, {(start, writeable), (end, writeable)});
+    (0, Enum, write_string, read_string);
+);
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(
     feature = "use-serde",
@@ -126,10 +170,12 @@ pub enum ContractDescriptor {
     EnumeratedContractDescriptor(EnumeratedContractDescriptor),
     /// Used for contract based on numerical outcomes.
     NumericOutcomeContractDescriptor(NumericOutcomeContractDescriptor),
+    /// Used for contract based on the joint outcome of several events.
+    MultiEventContractDescriptor(MultiEventContractDescriptor),
 }
 
 impl_dlc_writeable_enum!(
-    ContractDescriptor, (0, EnumeratedContractDescriptor), (1, NumericOutcomeContractDescriptor);;;
+    ContractDescriptor, (0, EnumeratedContractDescriptor), (1, NumericOutcomeContractDescriptor), (2, MultiEventContractDescriptor);;;
 );
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -147,6 +193,22 @@ pub struct EnumeratedContractDescriptor {
 
 impl_dlc_writeable!(EnumeratedContractDescriptor, { (payouts, vec) });
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Information about joint outcomes and payouts for a contract based on several
+/// enumerated or numerical outcome events. The oracle announcements of the
+/// contract are grouped by event id.
+pub struct MultiEventContractDescriptor {
+    /// The payouts for the different joint outcomes.
+    pub payouts: Vec<MultiEventContractOutcome>,
+}
+
+impl_dlc_writeable!(MultiEventContractDescriptor, { (payouts, vec) });
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(
     feature = "use-serde",
@@ -0,0 +1,84 @@
+//! Contains messages used to replace the funding transaction of a DLC with one
+//! paying a higher fee rate.
+
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+use secp256k1_zkp::ecdsa::Signature;
+
+use crate::CetAdaptorSignatures;
+
+/// Contains information about the offer party of a DLC wishing to replace its
+/// unconfirmed funding transaction with one paying a higher fee rate. The
+/// replacement is negotiated as a new contract re-using the funding inputs and
+/// parameters of the replaced one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct FeeBumpOffer {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the contract whose funding transaction is replaced.
+    pub contract_id: [u8; 32],
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// Temporary contract id to identify the replacement contract.
+    pub temporary_contract_id: [u8; 32],
+    /// The fee rate to use to compute the fees of the replacement transactions.
+    pub fee_rate_per_vb: u64,
+}
+
+impl_dlc_writeable!(FeeBumpOffer, {
+    (protocol_version, writeable),
+    (contract_id, writeable),
+    (temporary_contract_id, writeable),
+    (fee_rate_per_vb, writeable)
+});
+
+/// Contains the signatures of the accept party for the transactions of the
+/// replacement contract. The offer party replies with a [`crate::SignDlc`]
+/// message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct FeeBumpAccept {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The temporary contract id of the replacement contract.
+    pub temporary_contract_id: [u8; 32],
+    /// The set of adaptor signatures from the accept party.
+    pub cet_adaptor_signatures: CetAdaptorSignatures,
+    /// The refund signature of the accept party.
+    pub refund_signature: Signature,
+}
+
+impl_dlc_writeable!(FeeBumpAccept, {
+    (protocol_version, writeable),
+    (temporary_contract_id, writeable),
+    (cet_adaptor_signatures, writeable),
+    (refund_signature, writeable)
+});
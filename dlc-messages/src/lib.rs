@@ -24,10 +24,16 @@ extern crate serde;
 #[cfg(test)]
 extern crate serde_json;
 
+pub mod batch;
+pub mod cancel;
 pub mod channel;
+pub mod close;
 pub mod contract_msgs;
+pub mod fee_bump;
 pub mod message_handler;
 pub mod oracle_msgs;
+pub mod reject;
+pub mod rollover;
 pub mod segmentation;
 
 #[cfg(any(test, feature = "use-serde"))]
@@ -35,21 +41,37 @@ pub mod serde_utils;
 
 use std::fmt::Display;
 
-use crate::ser_impls::{read_ecdsa_adaptor_signature, write_ecdsa_adaptor_signature};
+use crate::ser_impls::{
+    read_ecdsa_adaptor_signature, read_partial_signature, read_refund_signature,
+    read_schnorr_adaptor_signature, read_schnorrsig, write_ecdsa_adaptor_signature,
+    write_partial_signature, write_refund_signature, write_schnorr_adaptor_signature,
+    write_schnorrsig,
+};
+use batch::{AcceptBatchDlc, OfferBatchDlc, SignBatchDlc};
 use bitcoin::ScriptBuf;
 use bitcoin::{consensus::Decodable, OutPoint, Transaction};
+use cancel::CancelOffer;
 use channel::{
-    AcceptChannel, CollaborativeCloseOffer, OfferChannel, Reject, RenewAccept, RenewConfirm,
-    RenewFinalize, RenewOffer, RenewRevoke, SettleAccept, SettleConfirm, SettleFinalize,
-    SettleOffer, SignChannel,
+    AcceptChannel, ChannelReestablish, CollaborativeCloseOffer, OfferChannel, Reject, RenewAccept,
+    RenewConfirm, RenewFinalize, RenewOffer, RenewRevoke, SettleAccept, SettleConfirm,
+    SettleFinalize, SettleOffer, SignChannel, SpliceAccept, SpliceConfirm, SpliceOffer,
 };
+use close::{CloseAccept, CloseOffer};
 use contract_msgs::ContractInfo;
+use dlc::schnorr_adaptor::SchnorrAdaptorSignature;
+use dlc::taproot::{PartialSignature, PublicNonce};
 use dlc::{Error, TxInputInfo};
+use fee_bump::{FeeBumpAccept, FeeBumpOffer};
 use lightning::ln::msgs::DecodeError;
 use lightning::ln::wire::Type;
 use lightning::util::ser::{Readable, Writeable, Writer};
+use reject::RejectOffer;
+use rollover::{RolloverAccept, RolloverConfirm, RolloverOffer};
 use secp256k1_zkp::Verification;
-use secp256k1_zkp::{ecdsa::Signature, EcdsaAdaptorSignature, PublicKey, Secp256k1};
+use secp256k1_zkp::{
+    ecdsa::Signature, schnorr::Signature as SchnorrSignature, EcdsaAdaptorSignature, PublicKey,
+    Secp256k1,
+};
 use segmentation::{SegmentChunk, SegmentStart};
 
 macro_rules! impl_type {
@@ -68,6 +90,18 @@ macro_rules! impl_type {
 impl_type!(OFFER_TYPE, OfferDlc, 42778);
 impl_type!(ACCEPT_TYPE, AcceptDlc, 42780);
 impl_type!(SIGN_TYPE, SignDlc, 42782);
+impl_type!(OFFER_BATCH_TYPE, OfferBatchDlc, 42784);
+impl_type!(ACCEPT_BATCH_TYPE, AcceptBatchDlc, 42786);
+impl_type!(SIGN_BATCH_TYPE, SignBatchDlc, 42788);
+impl_type!(FEE_BUMP_OFFER_TYPE, FeeBumpOffer, 42790);
+impl_type!(FEE_BUMP_ACCEPT_TYPE, FeeBumpAccept, 42792);
+impl_type!(CLOSE_OFFER_TYPE, CloseOffer, 42794);
+impl_type!(CLOSE_ACCEPT_TYPE, CloseAccept, 42796);
+impl_type!(ROLLOVER_OFFER_TYPE, RolloverOffer, 42798);
+impl_type!(ROLLOVER_ACCEPT_TYPE, RolloverAccept, 42800);
+impl_type!(ROLLOVER_CONFIRM_TYPE, RolloverConfirm, 42802);
+impl_type!(CANCEL_OFFER_TYPE, CancelOffer, 42804);
+impl_type!(REJECT_OFFER_TYPE, RejectOffer, 42806);
 impl_type!(OFFER_CHANNEL_TYPE, OfferChannel, 43000);
 impl_type!(ACCEPT_CHANNEL_TYPE, AcceptChannel, 43002);
 impl_type!(SIGN_CHANNEL_TYPE, SignChannel, 43004);
@@ -86,6 +120,10 @@ impl_type!(
     43022
 );
 impl_type!(REJECT, Reject, 43024);
+impl_type!(CHANNEL_REESTABLISH_TYPE, ChannelReestablish, 43028);
+impl_type!(SPLICE_CHANNEL_OFFER_TYPE, SpliceOffer, 43030);
+impl_type!(SPLICE_CHANNEL_ACCEPT_TYPE, SpliceAccept, 43032);
+impl_type!(SPLICE_CHANNEL_CONFIRM_TYPE, SpliceConfirm, 43034);
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -126,6 +164,29 @@ impl_dlc_writeable!(FundingInput, {
     (redeem_script, writeable)
 });
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains information about a third party paying the fees of the fund, CET
+/// and refund transactions of a contract.
+pub struct FeePayerInfo {
+    /// Inputs used by the fee payer to pay the fees.
+    pub funding_inputs: Vec<FundingInput>,
+    /// The SPK where the fee payer will receive their change.
+    pub change_spk: ScriptBuf,
+    /// Serial id to order funding transaction outputs.
+    pub change_serial_id: u64,
+}
+
+impl_dlc_writeable!(FeePayerInfo, {
+    (funding_inputs, vec),
+    (change_spk, writeable),
+    (change_serial_id, writeable)
+});
+
 impl From<&FundingInput> for TxInputInfo {
     fn from(funding_input: &FundingInput) -> TxInputInfo {
         TxInputInfo {
@@ -158,6 +219,30 @@ impl_dlc_writeable!(CetAdaptorSignature, {
      (signature, { cb_writeable, write_ecdsa_adaptor_signature, read_ecdsa_adaptor_signature })
 });
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains a Schnorr adaptor signature for a CET input spending a taproot
+/// fund output.
+pub struct SchnorrCetAdaptorSignature {
+    /// The signature.
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_schnorr_adaptor_signature",
+            deserialize_with = "crate::serde_utils::deserialize_schnorr_adaptor_signature"
+        )
+    )]
+    pub signature: SchnorrAdaptorSignature,
+}
+
+impl_dlc_writeable!(SchnorrCetAdaptorSignature, {
+     (signature, { cb_writeable, write_schnorr_adaptor_signature, read_schnorr_adaptor_signature })
+});
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "use-serde",
@@ -193,6 +278,129 @@ impl From<&CetAdaptorSignatures> for Vec<EcdsaAdaptorSignature> {
 
 impl_dlc_writeable!(CetAdaptorSignatures, { (ecdsa_adaptor_signatures, vec) });
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains the MuSig2 public nonce of a party for a signature spending a
+/// taproot fund output.
+pub struct FundingNonce {
+    /// The first nonce point.
+    pub r1: PublicKey,
+    /// The second nonce point.
+    pub r2: PublicKey,
+}
+
+impl_dlc_writeable!(FundingNonce, { (r1, writeable), (r2, writeable) });
+
+impl From<&PublicNonce> for FundingNonce {
+    fn from(nonce: &PublicNonce) -> Self {
+        FundingNonce {
+            r1: nonce.r1,
+            r2: nonce.r2,
+        }
+    }
+}
+
+impl From<&FundingNonce> for PublicNonce {
+    fn from(nonce: &FundingNonce) -> Self {
+        PublicNonce {
+            r1: nonce.r1,
+            r2: nonce.r2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains the MuSig2 public nonces of a party for the CETs and refund
+/// transaction of a contract funded with a taproot output.
+pub struct TaprootFundingNonces {
+    /// The nonces for the CET adaptor signatures, in the order of the adaptor
+    /// signatures.
+    pub cet_nonces: Vec<FundingNonce>,
+    /// The nonce for the refund transaction signature.
+    pub refund_nonce: FundingNonce,
+}
+
+impl_dlc_writeable!(TaprootFundingNonces, { (cet_nonces, vec), (refund_nonce, writeable) });
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains a MuSig2 partial signature for an input spending a taproot fund
+/// output.
+pub struct FundingPartialSignature {
+    /// The partial signature.
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_partial_signature",
+            deserialize_with = "crate::serde_utils::deserialize_partial_signature"
+        )
+    )]
+    pub signature: PartialSignature,
+}
+
+impl_dlc_writeable!(FundingPartialSignature, {
+     (signature, { cb_writeable, write_partial_signature, read_partial_signature })
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains the MuSig2 public nonces and partial signatures of the accept party
+/// for the CETs and refund transaction of a contract funded with a taproot
+/// output. The partial signatures for the CETs are partial adaptor signatures
+/// encrypted with the same adaptor points as ECDSA adaptor signatures would be.
+pub struct TaprootAcceptSignatures {
+    /// The public nonces of the accept party.
+    pub nonces: TaprootFundingNonces,
+    /// The partial adaptor signatures for the CETs.
+    pub cet_partial_signatures: Vec<FundingPartialSignature>,
+    /// The partial signature for the refund transaction.
+    pub refund_partial_signature: FundingPartialSignature,
+}
+
+impl_dlc_writeable!(TaprootAcceptSignatures, {
+    (nonces, writeable),
+    (cet_partial_signatures, vec),
+    (refund_partial_signature, writeable)
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+/// Contains the signatures of the offer party for the CETs and refund
+/// transaction of a contract funded with a taproot output, aggregating the
+/// MuSig2 partial signatures of both parties.
+pub struct TaprootSignSignatures {
+    /// The Schnorr adaptor signatures for the CETs.
+    pub cet_adaptor_signatures: Vec<SchnorrCetAdaptorSignature>,
+    /// The signature for the refund transaction.
+    pub refund_signature: SchnorrSignature,
+}
+
+impl_dlc_writeable!(TaprootSignSignatures, {
+    (cet_adaptor_signatures, vec),
+    (refund_signature, {cb_writeable, write_schnorrsig, read_schnorrsig})
+});
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "use-serde",
@@ -287,6 +495,19 @@ pub struct DisjointNegotiationFields {
 
 impl_dlc_writeable!(DisjointNegotiationFields, { (negotiation_fields, vec) });
 
+/// Contract flag indicating that the CETs and refund transaction of the
+/// contract should include an anchor output for each party.
+pub const ANCHOR_OUTPUTS_CONTRACT_FLAG: u8 = 0x01;
+
+/// Contract flag indicating that the accept party does not contribute any
+/// funds to the contract, the offer party paying all the fees.
+pub const SINGLE_FUNDED_CONTRACT_FLAG: u8 = 0x02;
+
+/// Contract flag indicating that the collateral of the contract is locked in a
+/// taproot output whose key is the MuSig2 aggregate of the fund public keys of
+/// both parties, instead of a 2-of-2 P2WSH multisig output.
+pub const TAPROOT_FUNDING_CONTRACT_FLAG: u8 = 0x04;
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(
     feature = "use-serde",
@@ -343,6 +564,17 @@ pub struct OfferDlc {
     pub cet_locktime: u32,
     /// The lock time for the refund transactions.
     pub refund_locktime: u32,
+    /// Information about a third party paying the fees of the contract
+    /// transactions, if any.
+    pub fee_payer: Option<FeePayerInfo>,
+    /// The unix timestamp after which the offer cannot be accepted anymore, if
+    /// any.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub offer_expiry: Option<u64>,
+    /// The MuSig2 public nonces of the offer party, set if and only if the
+    /// [`TAPROOT_FUNDING_CONTRACT_FLAG`] is set.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub taproot_funding_nonces: Option<TaprootFundingNonces>,
 }
 
 impl OfferDlc {
@@ -361,29 +593,54 @@ impl OfferDlc {
         min_timeout_interval: u32,
         max_timeout_interval: u32,
     ) -> Result<(), Error> {
-        match &self.contract_info {
-            ContractInfo::SingleContractInfo(s) => s.contract_info.oracle_info.validate(secp)?,
-            ContractInfo::DisjointContractInfo(d) => {
-                if d.contract_infos.len() < 2 {
-                    return Err(Error::InvalidArgument);
-                }
+        let taproot_funding = self.contract_flags & TAPROOT_FUNDING_CONTRACT_FLAG != 0;
+        if taproot_funding != self.taproot_funding_nonces.is_some() {
+            return Err(Error::InvalidArgument);
+        }
 
-                for c in &d.contract_infos {
-                    c.oracle_info.validate(secp)?;
-                }
+        validate_contract_info_and_locktimes(
+            secp,
+            &self.contract_info,
+            self.cet_locktime,
+            self.refund_locktime,
+            min_timeout_interval,
+            max_timeout_interval,
+        )
+    }
+}
+
+/// Checks the oracle information of the given contract info, and that the lock
+/// times of the CETs and refund transaction are consistent with its maturity.
+pub(crate) fn validate_contract_info_and_locktimes<C: Verification>(
+    secp: &Secp256k1<C>,
+    contract_info: &ContractInfo,
+    cet_locktime: u32,
+    refund_locktime: u32,
+    min_timeout_interval: u32,
+    max_timeout_interval: u32,
+) -> Result<(), Error> {
+    match contract_info {
+        ContractInfo::SingleContractInfo(s) => s.contract_info.oracle_info.validate(secp)?,
+        ContractInfo::DisjointContractInfo(d) => {
+            if d.contract_infos.len() < 2 {
+                return Err(Error::InvalidArgument);
             }
-        }
 
-        let closest_maturity_date = self.contract_info.get_closest_maturity_date();
-        let valid_dates = self.cet_locktime <= closest_maturity_date
-            && closest_maturity_date + min_timeout_interval <= self.refund_locktime
-            && self.refund_locktime <= closest_maturity_date + max_timeout_interval;
-        if !valid_dates {
-            return Err(Error::InvalidArgument);
+            for c in &d.contract_infos {
+                c.oracle_info.validate(secp)?;
+            }
         }
+    }
 
-        Ok(())
+    let closest_maturity_date = contract_info.get_closest_maturity_date();
+    let valid_dates = cet_locktime <= closest_maturity_date
+        && closest_maturity_date + min_timeout_interval <= refund_locktime
+        && refund_locktime <= closest_maturity_date + max_timeout_interval;
+    if !valid_dates {
+        return Err(Error::InvalidArgument);
     }
+
+    Ok(())
 }
 
 impl_dlc_writeable!(OfferDlc, {
@@ -402,7 +659,10 @@ impl_dlc_writeable!(OfferDlc, {
         (fund_output_serial_id, writeable),
         (fee_rate_per_vb, writeable),
         (cet_locktime, writeable),
-        (refund_locktime, writeable)
+        (refund_locktime, writeable),
+        (fee_payer, option),
+        (offer_expiry, option),
+        (taproot_funding_nonces, option)
 });
 
 /// Contains information about a party wishing to accept a DLC offer. The contained
@@ -441,12 +701,18 @@ pub struct AcceptDlc {
     pub change_spk: ScriptBuf,
     /// Serial id to order funding transaction outputs.
     pub change_serial_id: u64,
-    /// The set of adaptor signatures from the accept party.
+    /// The set of adaptor signatures from the accept party, empty for
+    /// contracts funded with a taproot output.
     pub cet_adaptor_signatures: CetAdaptorSignatures,
-    /// The refund signature of the accept party.
-    pub refund_signature: Signature,
+    /// The refund signature of the accept party, absent for contracts funded
+    /// with a taproot output.
+    pub refund_signature: Option<Signature>,
     /// The negotiation fields from the accept party.
     pub negotiation_fields: Option<NegotiationFields>,
+    /// The MuSig2 public nonces and partial signatures of the accept party for
+    /// contracts funded with a taproot output.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub taproot_signatures: Option<TaprootAcceptSignatures>,
 }
 
 impl_dlc_writeable!(AcceptDlc, {
@@ -460,8 +726,9 @@ impl_dlc_writeable!(AcceptDlc, {
     (change_spk, writeable),
     (change_serial_id, writeable),
     (cet_adaptor_signatures, writeable),
-    (refund_signature, writeable),
-    (negotiation_fields, option)
+    (refund_signature, {cb_writeable, write_refund_signature, read_refund_signature}),
+    (negotiation_fields, option),
+    (taproot_signatures, option)
 });
 
 /// Contains all the required signatures for the DLC transactions from the offering
@@ -484,20 +751,30 @@ pub struct SignDlc {
     )]
     /// The id of the contract referred to by this message.
     pub contract_id: [u8; 32],
-    /// The set of adaptor signatures from the offer party.
+    /// The set of adaptor signatures from the offer party, empty for contracts
+    /// funded with a taproot output.
     pub cet_adaptor_signatures: CetAdaptorSignatures,
-    /// The refund signature from the offer party.
-    pub refund_signature: Signature,
+    /// The refund signature from the offer party, absent for contracts funded
+    /// with a taproot output.
+    pub refund_signature: Option<Signature>,
     /// The set of funding signatures from the offer party.
     pub funding_signatures: FundingSignatures,
+    /// The set of funding signatures from the fee payer, if any.
+    pub fee_payer_funding_signatures: Option<FundingSignatures>,
+    /// The CET adaptor signatures and refund signature aggregating the partial
+    /// signatures of both parties for contracts funded with a taproot output.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub taproot_signatures: Option<TaprootSignSignatures>,
 }
 
 impl_dlc_writeable!(SignDlc, {
     (protocol_version, writeable),
     (contract_id, writeable),
     (cet_adaptor_signatures, writeable),
-    (refund_signature, writeable),
-    (funding_signatures, writeable)
+    (refund_signature, {cb_writeable, write_refund_signature, read_refund_signature}),
+    (funding_signatures, writeable),
+    (fee_payer_funding_signatures, option),
+    (taproot_signatures, option)
 });
 
 #[allow(missing_docs)]
@@ -506,6 +783,18 @@ pub enum Message {
     Offer(OfferDlc),
     Accept(AcceptDlc),
     Sign(SignDlc),
+    OfferBatch(OfferBatchDlc),
+    AcceptBatch(AcceptBatchDlc),
+    SignBatch(SignBatchDlc),
+    FeeBumpOffer(FeeBumpOffer),
+    FeeBumpAccept(FeeBumpAccept),
+    CloseOffer(CloseOffer),
+    CloseAccept(CloseAccept),
+    RolloverOffer(RolloverOffer),
+    RolloverAccept(RolloverAccept),
+    RolloverConfirm(RolloverConfirm),
+    CancelOffer(CancelOffer),
+    RejectOffer(RejectOffer),
     OfferChannel(OfferChannel),
     AcceptChannel(AcceptChannel),
     SignChannel(SignChannel),
@@ -520,6 +809,10 @@ pub enum Message {
     RenewRevoke(RenewRevoke),
     CollaborativeCloseOffer(CollaborativeCloseOffer),
     Reject(Reject),
+    ChannelReestablish(ChannelReestablish),
+    SpliceOffer(SpliceOffer),
+    SpliceAccept(SpliceAccept),
+    SpliceConfirm(SpliceConfirm),
 }
 
 macro_rules! impl_type_writeable_for_enum {
@@ -547,6 +840,18 @@ impl_type_writeable_for_enum!(Message,
     Offer,
     Accept,
     Sign,
+    OfferBatch,
+    AcceptBatch,
+    SignBatch,
+    FeeBumpOffer,
+    FeeBumpAccept,
+    CloseOffer,
+    CloseAccept,
+    RolloverOffer,
+    RolloverAccept,
+    RolloverConfirm,
+    CancelOffer,
+    RejectOffer,
     OfferChannel,
     AcceptChannel,
     SignChannel,
@@ -560,7 +865,11 @@ impl_type_writeable_for_enum!(Message,
     RenewFinalize,
     RenewRevoke,
     CollaborativeCloseOffer,
-    Reject
+    Reject,
+    ChannelReestablish,
+    SpliceOffer,
+    SpliceAccept,
+    SpliceConfirm
 });
 
 #[derive(Debug, Clone)]
@@ -626,6 +935,321 @@ mod tests {
         roundtrip_test!(SignDlc, input);
     }
 
+    #[test]
+    fn fee_payer_msgs_roundtrip() {
+        let mut offer: OfferDlc =
+            serde_json::from_str(include_str!("./test_inputs/offer_msg.json")).unwrap();
+        let mut sign: SignDlc =
+            serde_json::from_str(include_str!("./test_inputs/sign_msg.json")).unwrap();
+        offer.fee_payer = Some(FeePayerInfo {
+            funding_inputs: offer.funding_inputs.clone(),
+            change_spk: offer.change_spk.clone(),
+            change_serial_id: offer.change_serial_id + 1,
+        });
+        offer.offer_expiry = Some(1623133104);
+        sign.fee_payer_funding_signatures = Some(sign.funding_signatures.clone());
+
+        test_roundtrip(offer);
+        test_roundtrip(sign);
+    }
+
+    #[test]
+    fn batch_msgs_roundtrip() {
+        use bitcoin::hashes::Hash;
+
+        let mut offer: OfferDlc =
+            serde_json::from_str(include_str!("./test_inputs/offer_msg.json")).unwrap();
+        let funding_inputs = std::mem::take(&mut offer.funding_inputs);
+        let mut accept: AcceptDlc =
+            serde_json::from_str(include_str!("./test_inputs/accept_msg.json")).unwrap();
+        let accept_funding_inputs = std::mem::take(&mut accept.funding_inputs);
+        let mut sign: SignDlc =
+            serde_json::from_str(include_str!("./test_inputs/sign_msg.json")).unwrap();
+        let funding_signatures = std::mem::replace(
+            &mut sign.funding_signatures,
+            FundingSignatures {
+                funding_signatures: Vec::new(),
+            },
+        );
+        let fund_txid = bitcoin::Txid::from_slice(&[1u8; 32]).unwrap();
+
+        test_roundtrip(OfferBatchDlc {
+            protocol_version: offer.protocol_version,
+            temporary_batch_id: [2u8; 32],
+            funding_inputs,
+            offer_dlcs: vec![offer.clone(), offer],
+        });
+        test_roundtrip(AcceptBatchDlc {
+            protocol_version: accept.protocol_version,
+            temporary_batch_id: [2u8; 32],
+            funding_inputs: accept_funding_inputs,
+            fund_txid,
+            accept_dlcs: vec![accept.clone(), accept],
+            fund_output_indexes: vec![0, 2],
+        });
+        test_roundtrip(SignBatchDlc {
+            protocol_version: sign.protocol_version,
+            fund_txid,
+            funding_signatures,
+            sign_dlcs: vec![sign.clone(), sign],
+        });
+    }
+
+    #[test]
+    fn fee_bump_msgs_roundtrip() {
+        let accept: AcceptDlc =
+            serde_json::from_str(include_str!("./test_inputs/accept_msg.json")).unwrap();
+
+        test_roundtrip(FeeBumpOffer {
+            protocol_version: accept.protocol_version,
+            contract_id: [1u8; 32],
+            temporary_contract_id: [2u8; 32],
+            fee_rate_per_vb: 10,
+        });
+        test_roundtrip(FeeBumpAccept {
+            protocol_version: accept.protocol_version,
+            temporary_contract_id: [2u8; 32],
+            cet_adaptor_signatures: accept.cet_adaptor_signatures,
+            refund_signature: accept.refund_signature.unwrap(),
+        });
+    }
+
+    #[test]
+    fn close_msgs_roundtrip() {
+        let accept: AcceptDlc =
+            serde_json::from_str(include_str!("./test_inputs/accept_msg.json")).unwrap();
+
+        test_roundtrip(CloseOffer {
+            protocol_version: accept.protocol_version,
+            contract_id: [1u8; 32],
+            counter_payout: 100000,
+            close_signature: accept.refund_signature.unwrap(),
+        });
+        test_roundtrip(CloseAccept {
+            protocol_version: accept.protocol_version,
+            contract_id: [1u8; 32],
+            close_signature: accept.refund_signature.unwrap(),
+        });
+    }
+
+    #[test]
+    fn cancel_offer_msg_roundtrip() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("./test_inputs/offer_msg.json")).unwrap();
+
+        test_roundtrip(CancelOffer {
+            protocol_version: offer.protocol_version,
+            temporary_contract_id: offer.temporary_contract_id,
+        });
+    }
+
+    #[test]
+    fn reject_offer_msg_roundtrip() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("./test_inputs/offer_msg.json")).unwrap();
+
+        test_roundtrip(RejectOffer {
+            protocol_version: offer.protocol_version,
+            temporary_contract_id: offer.temporary_contract_id,
+            error_code: reject::RejectErrorCode::UnacceptableTerms,
+            reason: "Collateral is too high.".to_string(),
+        });
+    }
+
+    #[test]
+    fn channel_reestablish_msg_roundtrip() {
+        test_roundtrip(channel::ChannelReestablish {
+            channel_id: [1u8; 32],
+            update_idx: (1 << 48) - 2,
+            update_state: channel::ChannelUpdateState::RenewFinalized,
+        });
+    }
+
+    #[test]
+    fn splice_msgs_roundtrip() {
+        let accept: AcceptDlc =
+            serde_json::from_str(include_str!("./test_inputs/accept_msg.json")).unwrap();
+        let sign: SignDlc =
+            serde_json::from_str(include_str!("./test_inputs/sign_msg.json")).unwrap();
+        let settle_adaptor_signature =
+            accept.cet_adaptor_signatures.ecdsa_adaptor_signatures[0].signature;
+
+        test_roundtrip(SpliceOffer {
+            channel_id: [1u8; 32],
+            offer_balance: accept.accept_collateral,
+            funding_inputs: accept.funding_inputs.clone(),
+            change_spk: accept.change_spk.clone(),
+            fee_rate_per_vb: 2,
+            offer_fee_share: 50,
+            settle_adaptor_signature,
+        });
+        test_roundtrip(SpliceAccept {
+            channel_id: [1u8; 32],
+            settle_adaptor_signature,
+            splice_signature: accept.refund_signature.unwrap(),
+        });
+        test_roundtrip(SpliceConfirm {
+            channel_id: [1u8; 32],
+            splice_signature: sign.refund_signature.unwrap(),
+            funding_signatures: sign.funding_signatures,
+        });
+    }
+
+    #[test]
+    fn rollover_msgs_roundtrip() {
+        let offer: OfferDlc =
+            serde_json::from_str(include_str!("./test_inputs/offer_msg.json")).unwrap();
+        let accept: AcceptDlc =
+            serde_json::from_str(include_str!("./test_inputs/accept_msg.json")).unwrap();
+
+        test_roundtrip(RolloverOffer {
+            protocol_version: offer.protocol_version,
+            contract_id: [1u8; 32],
+            temporary_contract_id: [2u8; 32],
+            offer_collateral: offer.offer_collateral,
+            contract_info: offer.contract_info,
+            cet_locktime: offer.cet_locktime,
+            refund_locktime: offer.refund_locktime,
+        });
+        test_roundtrip(RolloverAccept {
+            protocol_version: accept.protocol_version,
+            temporary_contract_id: [2u8; 32],
+            cet_adaptor_signatures: accept.cet_adaptor_signatures.clone(),
+            refund_signature: accept.refund_signature.unwrap(),
+        });
+        test_roundtrip(RolloverConfirm {
+            protocol_version: accept.protocol_version,
+            contract_id: [3u8; 32],
+            cet_adaptor_signatures: accept.cet_adaptor_signatures,
+            refund_signature: accept.refund_signature.unwrap(),
+        });
+    }
+
+    #[test]
+    fn taproot_sign_signatures_roundtrip() {
+        let keypair = secp256k1_zkp::KeyPair::from_seckey_slice(SECP256K1, &[1u8; 32]).unwrap();
+        let adaptor_point = PublicKey::from_secret_key(
+            SECP256K1,
+            &secp256k1_zkp::SecretKey::from_slice(&[3u8; 32]).unwrap(),
+        );
+        let msg = secp256k1_zkp::Message::from_slice(&[2u8; 32]).unwrap();
+        let signature = SchnorrCetAdaptorSignature {
+            signature: SchnorrAdaptorSignature::encrypt_no_aux_rand(
+                SECP256K1,
+                &msg,
+                &keypair,
+                &adaptor_point,
+            )
+            .unwrap(),
+        };
+        let signatures = TaprootSignSignatures {
+            cet_adaptor_signatures: vec![signature.clone(), signature],
+            refund_signature: SECP256K1.sign_schnorr_no_aux_rand(&msg, &keypair),
+        };
+
+        test_roundtrip(signatures.clone());
+
+        let json = serde_json::to_string(&signatures).unwrap();
+        assert_eq!(
+            signatures,
+            serde_json::from_str::<TaprootSignSignatures>(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn empty_cet_adaptor_signatures_use_spec_encoding() {
+        let signatures = CetAdaptorSignatures {
+            ecdsa_adaptor_signatures: Vec::new(),
+        };
+        let mut buf = Vec::new();
+        signatures.write(&mut buf).unwrap();
+
+        assert_eq!(vec![0u8], buf);
+        test_roundtrip(signatures);
+    }
+
+    #[test]
+    fn taproot_funding_msgs_roundtrip() {
+        let mut offer: OfferDlc =
+            serde_json::from_str(include_str!("./test_inputs/offer_msg.json")).unwrap();
+        let mut accept: AcceptDlc =
+            serde_json::from_str(include_str!("./test_inputs/accept_msg.json")).unwrap();
+        let mut sign: SignDlc =
+            serde_json::from_str(include_str!("./test_inputs/sign_msg.json")).unwrap();
+        let nonce = FundingNonce {
+            r1: offer.funding_pubkey,
+            r2: accept.funding_pubkey,
+        };
+        let nonces = TaprootFundingNonces {
+            cet_nonces: vec![nonce.clone(), nonce.clone()],
+            refund_nonce: nonce,
+        };
+        let partial_signature = FundingPartialSignature {
+            signature: PartialSignature::from_slice(&[1u8; 32]).unwrap(),
+        };
+        offer.contract_flags = TAPROOT_FUNDING_CONTRACT_FLAG;
+        offer.taproot_funding_nonces = Some(nonces.clone());
+        accept.cet_adaptor_signatures = CetAdaptorSignatures {
+            ecdsa_adaptor_signatures: Vec::new(),
+        };
+        accept.refund_signature = None;
+        accept.taproot_signatures = Some(TaprootAcceptSignatures {
+            nonces,
+            cet_partial_signatures: vec![partial_signature.clone(), partial_signature.clone()],
+            refund_partial_signature: partial_signature,
+        });
+        sign.refund_signature = None;
+        sign.cet_adaptor_signatures = CetAdaptorSignatures {
+            ecdsa_adaptor_signatures: Vec::new(),
+        };
+        sign.taproot_signatures = Some(TaprootSignSignatures {
+            cet_adaptor_signatures: Vec::new(),
+            refund_signature: SchnorrSignature::from_slice(
+                &[
+                    offer.funding_pubkey.x_only_public_key().0.serialize(),
+                    [2u8; 32],
+                ]
+                .concat(),
+            )
+            .unwrap(),
+        });
+
+        let json = serde_json::to_string(&offer).unwrap();
+        assert_eq!(offer, serde_json::from_str::<OfferDlc>(&json).unwrap());
+        let json = serde_json::to_string(&accept).unwrap();
+        assert_eq!(accept, serde_json::from_str::<AcceptDlc>(&json).unwrap());
+        let json = serde_json::to_string(&sign).unwrap();
+        assert_eq!(sign, serde_json::from_str::<SignDlc>(&json).unwrap());
+
+        test_roundtrip(offer);
+        test_roundtrip(accept);
+        test_roundtrip(sign);
+    }
+
+    #[test]
+    fn taproot_funding_offer_without_nonces_fails_validation() {
+        let input = include_str!("./test_inputs/offer_msg.json");
+        let offer: OfferDlc = serde_json::from_str(input).unwrap();
+
+        let mut missing_nonces = offer.clone();
+        missing_nonces.contract_flags |= TAPROOT_FUNDING_CONTRACT_FLAG;
+
+        let mut missing_flag = offer.clone();
+        missing_flag.taproot_funding_nonces = Some(TaprootFundingNonces {
+            cet_nonces: Vec::new(),
+            refund_nonce: FundingNonce {
+                r1: offer.funding_pubkey,
+                r2: offer.funding_pubkey,
+            },
+        });
+
+        for invalid in &[missing_nonces, missing_flag] {
+            invalid
+                .validate(SECP256K1, 86400 * 7, 86400 * 14)
+                .expect_err("Should not pass validation of invalid offer message.");
+        }
+    }
+
     #[test]
     fn valid_offer_message_passes_validation() {
         let input = include_str!("./test_inputs/offer_msg.json");
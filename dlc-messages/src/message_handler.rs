@@ -105,6 +105,18 @@ pub fn read_dlc_message<R: ::lightning::io::Read>(
         (OFFER_TYPE, Offer),
         (ACCEPT_TYPE, Accept),
         (SIGN_TYPE, Sign),
+        (OFFER_BATCH_TYPE, OfferBatch),
+        (ACCEPT_BATCH_TYPE, AcceptBatch),
+        (SIGN_BATCH_TYPE, SignBatch),
+        (FEE_BUMP_OFFER_TYPE, FeeBumpOffer),
+        (FEE_BUMP_ACCEPT_TYPE, FeeBumpAccept),
+        (CLOSE_OFFER_TYPE, CloseOffer),
+        (CLOSE_ACCEPT_TYPE, CloseAccept),
+        (ROLLOVER_OFFER_TYPE, RolloverOffer),
+        (ROLLOVER_ACCEPT_TYPE, RolloverAccept),
+        (ROLLOVER_CONFIRM_TYPE, RolloverConfirm),
+        (CANCEL_OFFER_TYPE, CancelOffer),
+        (REJECT_OFFER_TYPE, RejectOffer),
         (OFFER_CHANNEL_TYPE, OfferChannel),
         (ACCEPT_CHANNEL_TYPE, AcceptChannel),
         (SIGN_CHANNEL_TYPE, SignChannel),
@@ -117,7 +129,11 @@ pub fn read_dlc_message<R: ::lightning::io::Read>(
         (RENEW_CHANNEL_CONFIRM_TYPE, RenewConfirm),
         (RENEW_CHANNEL_FINALIZE_TYPE, RenewFinalize),
         (COLLABORATIVE_CLOSE_OFFER_TYPE, CollaborativeCloseOffer),
-        (REJECT, Reject)
+        (REJECT, Reject),
+        (CHANNEL_REESTABLISH_TYPE, ChannelReestablish),
+        (SPLICE_CHANNEL_OFFER_TYPE, SpliceOffer),
+        (SPLICE_CHANNEL_ACCEPT_TYPE, SpliceAccept),
+        (SPLICE_CHANNEL_CONFIRM_TYPE, SpliceConfirm)
     )
 }
 
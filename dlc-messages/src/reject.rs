@@ -0,0 +1,69 @@
+//! Contains the message used by the receiving party of a DLC offer to reject
+//! it, together with the reason for the rejection.
+
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+
+/// Code indicating why a contract offer was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum RejectErrorCode {
+    /// The offer was rejected for a reason not covered by the other codes.
+    Other,
+    /// The offer is invalid or could not be processed.
+    InvalidOffer,
+    /// One of the oracles used by the contract is not supported.
+    UnsupportedOracle,
+    /// The terms of the contract (collateral, payouts, fee rate, maturity...)
+    /// are not acceptable.
+    UnacceptableTerms,
+    /// The receiving party does not have enough funds to accept the offer.
+    InsufficientFunds,
+    /// The offer expired before it could be accepted.
+    Expired,
+}
+
+impl_dlc_writeable_enum!(RejectErrorCode,;;;
+    (0, Other),
+    (1, InvalidOffer),
+    (2, UnsupportedOracle),
+    (3, UnacceptableTerms),
+    (4, InsufficientFunds),
+    (5, Expired)
+);
+
+/// Message used to inform the offer party that a contract offer was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct RejectOffer {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The temporary id of the rejected contract offer.
+    pub temporary_contract_id: [u8; 32],
+    /// The code indicating why the offer was rejected.
+    pub error_code: RejectErrorCode,
+    /// A human readable description of the reason for the rejection.
+    pub reason: String,
+}
+
+impl_dlc_writeable!(RejectOffer, {
+    (protocol_version, writeable),
+    (temporary_contract_id, writeable),
+    (error_code, writeable),
+    (reason, string)
+});
@@ -0,0 +1,146 @@
+//! Contains messages used to roll a DLC over into a new contract spending the
+//! same funding output, without closing it on-chain.
+
+use dlc::Error;
+use lightning::ln::msgs::DecodeError;
+use lightning::util::ser::{Readable, Writeable, Writer};
+use secp256k1_zkp::{ecdsa::Signature, Secp256k1, Verification};
+
+use crate::contract_msgs::ContractInfo;
+use crate::CetAdaptorSignatures;
+
+/// Contains information about a party wishing to replace the CETs and refund
+/// transaction of an existing DLC with the ones of a new contract. The sending
+/// party becomes the offer party of the new contract.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct RolloverOffer {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the contract being rolled over.
+    pub contract_id: [u8; 32],
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// Temporary contract id to identify the new contract.
+    pub temporary_contract_id: [u8; 32],
+    /// The collateral of the sending party in the new contract.
+    pub offer_collateral: u64,
+    /// Information about the new contract.
+    pub contract_info: ContractInfo,
+    /// Lock time for the CETs of the new contract.
+    pub cet_locktime: u32,
+    /// Lock time for the refund transaction of the new contract.
+    pub refund_locktime: u32,
+}
+
+impl RolloverOffer {
+    /// Returns whether the message satisfies validity requirements.
+    pub fn validate<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        min_timeout_interval: u32,
+        max_timeout_interval: u32,
+    ) -> Result<(), Error> {
+        crate::validate_contract_info_and_locktimes(
+            secp,
+            &self.contract_info,
+            self.cet_locktime,
+            self.refund_locktime,
+            min_timeout_interval,
+            max_timeout_interval,
+        )
+    }
+}
+
+impl_dlc_writeable!(RolloverOffer, {
+    (protocol_version, writeable),
+    (contract_id, writeable),
+    (temporary_contract_id, writeable),
+    (offer_collateral, writeable),
+    (contract_info, writeable),
+    (cet_locktime, writeable),
+    (refund_locktime, writeable)
+});
+
+/// Contains the signatures of the accept party for the CETs and refund
+/// transaction of the new contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct RolloverAccept {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The temporary contract id of the new contract.
+    pub temporary_contract_id: [u8; 32],
+    /// The set of adaptor signatures from the accept party.
+    pub cet_adaptor_signatures: CetAdaptorSignatures,
+    /// The refund signature of the accept party.
+    pub refund_signature: Signature,
+}
+
+impl_dlc_writeable!(RolloverAccept, {
+    (protocol_version, writeable),
+    (temporary_contract_id, writeable),
+    (cet_adaptor_signatures, writeable),
+    (refund_signature, writeable)
+});
+
+/// Contains the signatures of the offer party for the CETs and refund
+/// transaction of the new contract. Upon receiving it, the accept party
+/// considers the rolled over contract replaced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct RolloverConfirm {
+    /// The version of the protocol used by the peer.
+    pub protocol_version: u32,
+    #[cfg_attr(
+        feature = "use-serde",
+        serde(
+            serialize_with = "crate::serde_utils::serialize_hex",
+            deserialize_with = "crate::serde_utils::deserialize_hex_array"
+        )
+    )]
+    /// The id of the new contract.
+    pub contract_id: [u8; 32],
+    /// The set of adaptor signatures from the offer party.
+    pub cet_adaptor_signatures: CetAdaptorSignatures,
+    /// The refund signature of the offer party.
+    pub refund_signature: Signature,
+}
+
+impl_dlc_writeable!(RolloverConfirm, {
+    (protocol_version, writeable),
+    (contract_id, writeable),
+    (cet_adaptor_signatures, writeable),
+    (refund_signature, writeable)
+});
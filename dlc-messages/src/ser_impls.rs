@@ -2,6 +2,8 @@
 
 use bitcoin::network::constants::Network;
 use bitcoin::Address;
+use dlc::schnorr_adaptor::{SchnorrAdaptorSignature, SCHNORR_ADAPTOR_SIGNATURE_SIZE};
+use dlc::taproot::PartialSignature;
 use dlc::{EnumerationPayout, PartyParams, Payout, TxInputInfo};
 use lightning::io::Read;
 use lightning::ln::msgs::DecodeError;
@@ -11,7 +13,7 @@ use secp256k1_zkp::{ffi::ECDSA_ADAPTOR_SIGNATURE_LENGTH, EcdsaAdaptorSignature};
 use std::collections::HashMap;
 use std::hash::Hash;
 
-const MAX_VEC_SIZE: u64 = 1000000;
+pub(crate) const MAX_VEC_SIZE: u64 = 1000000;
 
 /// Taken from rust-lightning: <https://github.com/rust-bitcoin/rust-lightning/blob/v0.0.101/lightning/src/util/ser.rs#L295>
 ///
@@ -211,6 +213,34 @@ pub fn read_schnorrsig<R: ::lightning::io::Read>(
     }
 }
 
+/// Writes an optional refund signature to the given writer. As the refund
+/// signature field of the DLC specification has a fixed size, an absent
+/// signature is written as 64 zero bytes, which is not a valid signature.
+pub fn write_refund_signature<W: lightning::util::ser::Writer>(
+    signature: &Option<secp256k1_zkp::ecdsa::Signature>,
+    writer: &mut W,
+) -> Result<(), ::lightning::io::Error> {
+    match signature {
+        Some(signature) => signature.write(writer),
+        None => [0u8; secp256k1_zkp::constants::COMPACT_SIGNATURE_SIZE].write(writer),
+    }
+}
+
+/// Reads an optional refund signature written with [`write_refund_signature`]
+/// from the given reader.
+pub fn read_refund_signature<R: ::lightning::io::Read>(
+    reader: &mut R,
+) -> Result<Option<secp256k1_zkp::ecdsa::Signature>, lightning::ln::msgs::DecodeError> {
+    let buf: [u8; secp256k1_zkp::constants::COMPACT_SIGNATURE_SIZE] = Readable::read(reader)?;
+    if buf.iter().all(|x| *x == 0) {
+        return Ok(None);
+    }
+    match secp256k1_zkp::ecdsa::Signature::from_compact(&buf) {
+        Ok(sig) => Ok(Some(sig)),
+        Err(_) => Err(lightning::ln::msgs::DecodeError::InvalidValue),
+    }
+}
+
 /// Writes a set of [`secp256k1_zkp::schnorrsig::Signature`] to the given writer.
 pub fn write_schnorr_signatures<W: lightning::util::ser::Writer>(
     signatures: &[secp256k1_zkp::schnorr::Signature],
@@ -539,6 +569,45 @@ pub fn read_ecdsa_adaptor_signatures<R: ::lightning::io::Read>(
     read_vec_cb(reader, &read_ecdsa_adaptor_signature)
 }
 
+/// Writes a [`dlc::schnorr_adaptor::SchnorrAdaptorSignature`] to the given writer.
+pub fn write_schnorr_adaptor_signature<W: Writer>(
+    sig: &SchnorrAdaptorSignature,
+    writer: &mut W,
+) -> Result<(), ::lightning::io::Error> {
+    for x in sig.serialize().iter() {
+        x.write(writer)?;
+    }
+    Ok(())
+}
+
+/// Reads a [`dlc::schnorr_adaptor::SchnorrAdaptorSignature`] from the given reader.
+pub fn read_schnorr_adaptor_signature<R: ::lightning::io::Read>(
+    reader: &mut R,
+) -> Result<SchnorrAdaptorSignature, DecodeError> {
+    let mut buf: Vec<u8> = Vec::with_capacity(SCHNORR_ADAPTOR_SIGNATURE_SIZE);
+
+    for _ in 0..SCHNORR_ADAPTOR_SIGNATURE_SIZE {
+        buf.push(Readable::read(reader)?);
+    }
+    SchnorrAdaptorSignature::from_slice(&buf).map_err(|_| DecodeError::InvalidValue)
+}
+
+/// Writes a [`dlc::taproot::PartialSignature`] to the given writer.
+pub fn write_partial_signature<W: Writer>(
+    sig: &PartialSignature,
+    writer: &mut W,
+) -> Result<(), ::lightning::io::Error> {
+    sig.serialize().write(writer)
+}
+
+/// Reads a [`dlc::taproot::PartialSignature`] from the given reader.
+pub fn read_partial_signature<R: ::lightning::io::Read>(
+    reader: &mut R,
+) -> Result<PartialSignature, DecodeError> {
+    let buf: [u8; 32] = Readable::read(reader)?;
+    PartialSignature::from_slice(&buf).map_err(|_| DecodeError::InvalidValue)
+}
+
 /// Writes an `i32` value to the given writer.
 pub fn write_i32<W: Writer>(i: &i32, writer: &mut W) -> Result<(), ::lightning::io::Error> {
     i.to_be_bytes().write(writer)
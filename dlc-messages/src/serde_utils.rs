@@ -49,6 +49,51 @@ where
     }
 }
 
+/// Serialize a Schnorr adaptor signature as an hexadecimal value.
+pub fn serialize_schnorr_adaptor_signature<S>(
+    sig: &dlc::schnorr_adaptor::SchnorrAdaptorSignature,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serialize_hex(&sig.serialize(), s)
+}
+
+/// Deserialize a Schnorr adaptor signature represented as an hexadecimal value.
+pub fn deserialize_schnorr_adaptor_signature<'de, D>(
+    deserializer: D,
+) -> Result<dlc::schnorr_adaptor::SchnorrAdaptorSignature, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let bytes = deserialize_hex_string(deserializer)?;
+    dlc::schnorr_adaptor::SchnorrAdaptorSignature::from_slice(&bytes)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Serialize a MuSig2 partial signature as an hexadecimal value.
+pub fn serialize_partial_signature<S>(
+    sig: &dlc::taproot::PartialSignature,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serialize_hex(&sig.serialize(), s)
+}
+
+/// Deserialize a MuSig2 partial signature represented as an hexadecimal value.
+pub fn deserialize_partial_signature<'de, D>(
+    deserializer: D,
+) -> Result<dlc::taproot::PartialSignature, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let bytes = deserialize_hex_array(deserializer)?;
+    dlc::taproot::PartialSignature::from_slice(&bytes).map_err(serde::de::Error::custom)
+}
+
 fn from_hex(hex: &str, target: &mut [u8]) -> Result<usize, String> {
     if hex.len() % 2 == 1 || hex.len() > target.len() * 2 {
         return Err("Invalid hex length".to_string());
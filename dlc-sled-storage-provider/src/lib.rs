@@ -28,11 +28,13 @@ use dlc_manager::contract::offered_contract::OfferedContract;
 use dlc_manager::contract::ser::Serializable;
 use dlc_manager::contract::signed_contract::SignedContract;
 use dlc_manager::contract::{
-    ClosedContract, Contract, FailedAcceptContract, FailedSignContract, PreClosedContract,
+    ClosedContract, CollaborativelyClosedContract, Contract, FailedAcceptContract,
+    FailedSignContract, PreClosedContract,
 };
+use dlc_manager::events::Event;
 #[cfg(feature = "wallet")]
 use dlc_manager::Utxo;
-use dlc_manager::{error::Error, ContractId, Storage};
+use dlc_manager::{error::Error, legacy, ContractId, Storage};
 #[cfg(feature = "wallet")]
 use lightning::util::ser::{Readable, Writeable};
 #[cfg(feature = "wallet")]
@@ -48,12 +50,18 @@ const CONTRACT_TREE: u8 = 1;
 const CHANNEL_TREE: u8 = 2;
 const CHAIN_MONITOR_TREE: u8 = 3;
 const CHAIN_MONITOR_KEY: u8 = 4;
-#[cfg(feature = "wallet")]
 const UTXO_TREE: u8 = 6;
 #[cfg(feature = "wallet")]
 const KEY_PAIR_TREE: u8 = 7;
 #[cfg(feature = "wallet")]
 const ADDRESS_TREE: u8 = 8;
+const EVENT_TREE: u8 = 9;
+const STORAGE_VERSION_KEY: u8 = 10;
+
+/// The version of the format in which the data is stored. Version 0 is the
+/// format used before the version was persisted, which is read using the
+/// functions of [`dlc_manager::legacy`].
+const STORAGE_VERSION: u8 = 1;
 
 /// Implementation of Storage interface using the sled DB backend.
 pub struct SledStorageProvider {
@@ -112,7 +120,11 @@ convertible_enum!(
         FailedAccept,
         FailedSign,
         Refunded,
-        Rejected,;
+        Rejected,
+        CollaborativelyClosed,
+        RolledOver,
+        PreRefunded,
+        Cancelled,;
     },
     Contract
 );
@@ -148,6 +160,10 @@ convertible_enum!(
         RenewOffered,
         RenewConfirmed,
         RenewFinalized,
+        SpliceOffered,
+        SpliceReceived,
+        SpliceAccepted,
+        SpliceConfirmed,
     },
     SignedChannelStateType
 );
@@ -160,11 +176,136 @@ where
 }
 
 impl SledStorageProvider {
-    /// Creates a new instance of a SledStorageProvider.
+    /// Creates a new instance of a SledStorageProvider, migrating the data
+    /// stored at the given path to the current storage format if required.
     pub fn new(path: &str) -> Result<Self, sled::Error> {
-        Ok(SledStorageProvider {
+        let storage = SledStorageProvider {
             db: sled::open(path)?,
-        })
+        };
+        storage
+            .migrate()
+            .map_err(|e| sled::Error::Unsupported(e.to_string()))?;
+        Ok(storage)
+    }
+
+    fn migrate(&self) -> Result<(), Error> {
+        let version = match self
+            .db
+            .get([STORAGE_VERSION_KEY])
+            .map_err(to_storage_error)?
+        {
+            Some(version) => *version
+                .first()
+                .ok_or_else(|| Error::StorageError("Invalid storage version".to_string()))?,
+            None if self.is_empty()? => STORAGE_VERSION,
+            None => 0,
+        };
+
+        match version {
+            0 => self.migrate_from_v0(),
+            STORAGE_VERSION => {
+                self.db
+                    .insert([STORAGE_VERSION_KEY], vec![STORAGE_VERSION])
+                    .map_err(to_storage_error)?;
+                Ok(())
+            }
+            _ => Err(Error::StorageError(format!(
+                "Unsupported storage version {}",
+                version
+            ))),
+        }
+    }
+
+    fn is_empty(&self) -> Result<bool, Error> {
+        for name in self.db.tree_names() {
+            if !self
+                .db
+                .open_tree(name)
+                .map_err(to_storage_error)?
+                .is_empty()
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Rewrites the data stored in the format of version 0 in the current
+    /// format. All the data is rewritten in a single transaction together with
+    /// the storage version, so that an interrupted migration is started over.
+    fn migrate_from_v0(&self) -> Result<(), Error> {
+        let contract_tree = self.contract_tree()?;
+        let channel_tree = self.channel_tree()?;
+        let chain_monitor_tree = self.open_tree(&[CHAIN_MONITOR_TREE])?;
+        let utxo_tree = self.open_tree(&[UTXO_TREE])?;
+        let default_tree: &Tree = &self.db;
+
+        let contracts = contract_tree
+            .iter()
+            .map(|res| {
+                let (key, value) = res.map_err(to_storage_error)?;
+                let contract = deserialize_contract_v0(&value)?;
+                Ok((key, serialize_contract(&contract)?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let channels = channel_tree
+            .iter()
+            .map(|res| {
+                let (key, value) = res.map_err(to_storage_error)?;
+                let channel = deserialize_channel_v0(&value)?;
+                Ok((key, serialize_channel(&channel)?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let chain_monitor = match chain_monitor_tree
+            .get([CHAIN_MONITOR_KEY])
+            .map_err(to_storage_error)?
+        {
+            Some(value) => Some(
+                legacy::read_chain_monitor(&mut Cursor::new(&value))
+                    .map_err(to_storage_error)?
+                    .serialize()?,
+            ),
+            None => None,
+        };
+
+        let utxos = utxo_tree
+            .iter()
+            .map(|res| {
+                let (key, value) = res.map_err(to_storage_error)?;
+                let utxo = legacy::read_utxo(&mut Cursor::new(&value)).map_err(to_storage_error)?;
+                Ok((key, utxo.serialize()?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        (
+            &contract_tree,
+            &channel_tree,
+            &chain_monitor_tree,
+            &utxo_tree,
+            default_tree,
+        )
+            .transaction::<_, ()>(
+                |(contract_db, channel_db, chain_monitor_db, utxo_db, default_db)| -> ConflictableTransactionResult<(), UnabortableTransactionError> {
+                    for (key, value) in &contracts {
+                        contract_db.insert(key.clone(), value.clone())?;
+                    }
+                    for (key, value) in &channels {
+                        channel_db.insert(key.clone(), value.clone())?;
+                    }
+                    if let Some(chain_monitor) = &chain_monitor {
+                        chain_monitor_db.insert(&[CHAIN_MONITOR_KEY], chain_monitor.clone())?;
+                    }
+                    for (key, value) in &utxos {
+                        utxo_db.insert(key.clone(), value.clone())?;
+                    }
+                    default_db.insert(&[STORAGE_VERSION_KEY], vec![STORAGE_VERSION])?;
+                    Ok(())
+                },
+            )
+            .map_err(to_storage_error)?;
+        Ok(())
     }
 
     fn get_data_with_prefix<T: Serializable>(
@@ -205,6 +346,10 @@ impl SledStorageProvider {
     fn channel_tree(&self) -> Result<Tree, Error> {
         self.open_tree(&[CHANNEL_TREE])
     }
+
+    fn event_tree(&self) -> Result<Tree, Error> {
+        self.open_tree(&[EVENT_TREE])
+    }
 }
 
 #[cfg(feature = "wallet")]
@@ -307,6 +452,44 @@ impl Storage for SledStorageProvider {
         )
     }
 
+    fn get_prerefunded_contracts(&self) -> Result<Vec<SignedContract>, Error> {
+        self.get_data_with_prefix(
+            &self.contract_tree()?,
+            &[ContractPrefix::PreRefunded.into()],
+            None,
+        )
+    }
+
+    fn get_rolled_over_contracts(&self) -> Result<Vec<SignedContract>, Error> {
+        self.get_data_with_prefix(
+            &self.contract_tree()?,
+            &[ContractPrefix::RolledOver.into()],
+            None,
+        )
+    }
+
+    fn roll_over_contract(
+        &self,
+        rolled_over_contract: &SignedContract,
+        contract: &Contract,
+    ) -> Result<(), Error> {
+        let rolled_over_contract = Contract::RolledOver(rolled_over_contract.clone());
+        let serialized_rolled_over = serialize_contract(&rolled_over_contract)?;
+        let serialized = serialize_contract(contract)?;
+        self.contract_tree()?
+            .transaction::<_, _, UnabortableTransactionError>(|db| {
+                db.insert(
+                    &rolled_over_contract.get_id(),
+                    serialized_rolled_over.clone(),
+                )?;
+                db.remove(&contract.get_temporary_id())?;
+                db.insert(&contract.get_id(), serialized.clone())?;
+                Ok(())
+            })
+            .map_err(to_storage_error)?;
+        Ok(())
+    }
+
     fn upsert_channel(&self, channel: Channel, contract: Option<Contract>) -> Result<(), Error> {
         let serialized = serialize_channel(&channel)?;
         let serialized_contract = match contract.as_ref() {
@@ -408,6 +591,30 @@ impl Storage for SledStorageProvider {
         };
         Ok(deserialized)
     }
+
+    fn persist_events(&self, events: &[Event]) -> Result<(), Error> {
+        let tree = self.event_tree()?;
+        let mut batch = sled::Batch::default();
+        for key in tree.iter().keys() {
+            batch.remove(key.map_err(to_storage_error)?);
+        }
+        for (i, event) in events.iter().enumerate() {
+            batch.insert(&(i as u64).to_be_bytes(), event.serialize()?);
+        }
+        tree.apply_batch(batch)
+            .map_err(|e| Error::StorageError(format!("Error writing events: {}", e)))
+    }
+
+    fn get_events(&self) -> Result<Vec<Event>, Error> {
+        self.event_tree()?
+            .iter()
+            .values()
+            .map(|x| {
+                let value = x.map_err(to_storage_error)?;
+                Event::deserialize(&mut Cursor::new(&value)).map_err(to_storage_error)
+            })
+            .collect()
+    }
 }
 
 #[cfg(feature = "wallet")]
@@ -548,13 +755,18 @@ fn insert_contract(
 
 fn serialize_contract(contract: &Contract) -> Result<Vec<u8>, ::std::io::Error> {
     let serialized = match contract {
-        Contract::Offered(o) | Contract::Rejected(o) => o.serialize(),
+        Contract::Offered(o) | Contract::Rejected(o) | Contract::Cancelled(o) => o.serialize(),
         Contract::Accepted(o) => o.serialize(),
-        Contract::Signed(o) | Contract::Confirmed(o) | Contract::Refunded(o) => o.serialize(),
+        Contract::Signed(o)
+        | Contract::Confirmed(o)
+        | Contract::PreRefunded(o)
+        | Contract::Refunded(o)
+        | Contract::RolledOver(o) => o.serialize(),
         Contract::FailedAccept(c) => c.serialize(),
         Contract::FailedSign(c) => c.serialize(),
         Contract::PreClosed(c) => c.serialize(),
         Contract::Closed(c) => c.serialize(),
+        Contract::CollaborativelyClosed(c) => c.serialize(),
     };
     let mut serialized = serialized?;
     let mut res = Vec::with_capacity(serialized.len() + 1);
@@ -599,10 +811,91 @@ fn deserialize_contract(buff: &sled::IVec) -> Result<Contract, Error> {
         ContractPrefix::Rejected => {
             Contract::Rejected(OfferedContract::deserialize(&mut cursor).map_err(to_storage_error)?)
         }
+        ContractPrefix::CollaborativelyClosed => Contract::CollaborativelyClosed(
+            CollaborativelyClosedContract::deserialize(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::RolledOver => Contract::RolledOver(
+            SignedContract::deserialize(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::PreRefunded => Contract::PreRefunded(
+            SignedContract::deserialize(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::Cancelled => Contract::Cancelled(
+            OfferedContract::deserialize(&mut cursor).map_err(to_storage_error)?,
+        ),
     };
     Ok(contract)
 }
 
+fn deserialize_contract_v0(buff: &sled::IVec) -> Result<Contract, Error> {
+    let mut cursor = ::std::io::Cursor::new(buff);
+    let mut prefix = [0u8; 1];
+    cursor.read_exact(&mut prefix)?;
+    let contract_prefix: ContractPrefix = prefix[0].try_into()?;
+    let contract = match contract_prefix {
+        ContractPrefix::Offered => {
+            Contract::Offered(legacy::read_offered_contract(&mut cursor).map_err(to_storage_error)?)
+        }
+        ContractPrefix::Accepted => Contract::Accepted(
+            legacy::read_accepted_contract(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::Signed => {
+            Contract::Signed(legacy::read_signed_contract(&mut cursor).map_err(to_storage_error)?)
+        }
+        ContractPrefix::Confirmed => Contract::Confirmed(
+            legacy::read_signed_contract(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::PreClosed => Contract::PreClosed(
+            legacy::read_pre_closed_contract(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::FailedAccept => Contract::FailedAccept(
+            legacy::read_failed_accept_contract(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::FailedSign => Contract::FailedSign(
+            legacy::read_failed_sign_contract(&mut cursor).map_err(to_storage_error)?,
+        ),
+        ContractPrefix::Refunded => {
+            Contract::Refunded(legacy::read_signed_contract(&mut cursor).map_err(to_storage_error)?)
+        }
+        ContractPrefix::Rejected => Contract::Rejected(
+            legacy::read_offered_contract(&mut cursor).map_err(to_storage_error)?,
+        ),
+        // The format of closed contracts did not change.
+        ContractPrefix::Closed => deserialize_contract(buff)?,
+        ContractPrefix::CollaborativelyClosed
+        | ContractPrefix::RolledOver
+        | ContractPrefix::PreRefunded
+        | ContractPrefix::Cancelled => {
+            return Err(Error::StorageError(format!(
+                "Unexpected contract prefix {:?} in storage version 0",
+                contract_prefix
+            )))
+        }
+    };
+    Ok(contract)
+}
+
+fn deserialize_channel_v0(buff: &sled::IVec) -> Result<Channel, Error> {
+    let mut cursor = ::std::io::Cursor::new(buff);
+    let mut prefix = [0u8; 1];
+    cursor.read_exact(&mut prefix)?;
+    let channel_prefix: ChannelPrefix = prefix[0].try_into()?;
+    match channel_prefix {
+        ChannelPrefix::Accepted => Ok(Channel::Accepted(
+            legacy::read_accepted_channel(&mut cursor).map_err(to_storage_error)?,
+        )),
+        ChannelPrefix::Signed => {
+            // Skip the channel state prefix.
+            cursor.set_position(cursor.position() + 1);
+            Ok(Channel::Signed(
+                legacy::read_signed_channel(&mut cursor).map_err(to_storage_error)?,
+            ))
+        }
+        // The format of the other channels did not change.
+        _ => deserialize_channel(buff),
+    }
+}
+
 fn serialize_channel(channel: &Channel) -> Result<Vec<u8>, ::std::io::Error> {
     let serialized = match channel {
         Channel::Offered(o) => o.serialize(),
@@ -713,11 +1006,21 @@ mod tests {
         T::deserialize(&mut cursor).unwrap()
     }
 
+    /// Deserializes an object stored in the format of version 0, used by the
+    /// test files of the structures whose format changed since.
+    fn deserialize_legacy<T, F, E>(serialized: &[u8], read: F) -> T
+    where
+        F: Fn(&mut Cursor<&[u8]>) -> Result<T, E>,
+        E: std::fmt::Debug,
+    {
+        read(&mut Cursor::new(serialized)).unwrap()
+    }
+
     sled_test!(
         create_contract_can_be_retrieved,
         |storage: SledStorageProvider| {
             let serialized = include_bytes!("../test_files/Offered");
-            let contract = deserialize_object(serialized);
+            let contract = deserialize_legacy(serialized, legacy::read_offered_contract);
 
             storage
                 .create_contract(&contract)
@@ -728,7 +1031,10 @@ mod tests {
                 .expect("Error retrieving contract.");
 
             if let Some(Contract::Offered(retrieved_offer)) = retrieved {
-                assert_eq!(serialized[..], retrieved_offer.serialize().unwrap()[..]);
+                assert_eq!(
+                    contract.serialize().unwrap(),
+                    retrieved_offer.serialize().unwrap()
+                );
             } else {
                 unreachable!();
             }
@@ -739,9 +1045,9 @@ mod tests {
         update_contract_is_updated,
         |storage: SledStorageProvider| {
             let serialized = include_bytes!("../test_files/Offered");
-            let offered_contract = deserialize_object(serialized);
+            let offered_contract = deserialize_legacy(serialized, legacy::read_offered_contract);
             let serialized = include_bytes!("../test_files/Accepted");
-            let accepted_contract = deserialize_object(serialized);
+            let accepted_contract = deserialize_legacy(serialized, legacy::read_accepted_contract);
             let accepted_contract = Contract::Accepted(accepted_contract);
 
             storage
@@ -766,7 +1072,7 @@ mod tests {
         delete_contract_is_deleted,
         |storage: SledStorageProvider| {
             let serialized = include_bytes!("../test_files/Offered");
-            let contract = deserialize_object(serialized);
+            let contract = deserialize_legacy(serialized, legacy::read_offered_contract);
             storage
                 .create_contract(&contract)
                 .expect("Error creating contract");
@@ -784,35 +1090,42 @@ mod tests {
 
     fn insert_offered_signed_and_confirmed(storage: &mut SledStorageProvider) {
         let serialized = include_bytes!("../test_files/Offered");
-        let offered_contract = deserialize_object(serialized);
+        let offered_contract = deserialize_legacy(serialized, legacy::read_offered_contract);
         storage
             .create_contract(&offered_contract)
             .expect("Error creating contract");
 
         let serialized = include_bytes!("../test_files/Signed");
-        let signed_contract = Contract::Signed(deserialize_object(serialized));
+        let signed_contract =
+            Contract::Signed(deserialize_legacy(serialized, legacy::read_signed_contract));
         storage
             .update_contract(&signed_contract)
             .expect("Error creating contract");
         let serialized = include_bytes!("../test_files/Signed1");
-        let signed_contract = Contract::Signed(deserialize_object(serialized));
+        let signed_contract =
+            Contract::Signed(deserialize_legacy(serialized, legacy::read_signed_contract));
         storage
             .update_contract(&signed_contract)
             .expect("Error creating contract");
 
         let serialized = include_bytes!("../test_files/Confirmed");
-        let confirmed_contract = Contract::Confirmed(deserialize_object(serialized));
+        let confirmed_contract =
+            Contract::Confirmed(deserialize_legacy(serialized, legacy::read_signed_contract));
         storage
             .update_contract(&confirmed_contract)
             .expect("Error creating contract");
         let serialized = include_bytes!("../test_files/Confirmed1");
-        let confirmed_contract = Contract::Confirmed(deserialize_object(serialized));
+        let confirmed_contract =
+            Contract::Confirmed(deserialize_legacy(serialized, legacy::read_signed_contract));
         storage
             .update_contract(&confirmed_contract)
             .expect("Error creating contract");
 
         let serialized = include_bytes!("../test_files/PreClosed");
-        let preclosed_contract = Contract::PreClosed(deserialize_object(serialized));
+        let preclosed_contract = Contract::PreClosed(deserialize_legacy(
+            serialized,
+            legacy::read_pre_closed_contract,
+        ));
         storage
             .update_contract(&preclosed_contract)
             .expect("Error creating contract");
@@ -820,7 +1133,7 @@ mod tests {
 
     fn insert_offered_and_signed_channels(storage: &mut SledStorageProvider) {
         let serialized = include_bytes!("../test_files/Offered");
-        let offered_contract = deserialize_object(serialized);
+        let offered_contract = deserialize_legacy(serialized, legacy::read_offered_contract);
         let serialized = include_bytes!("../test_files/OfferedChannel");
         let offered_channel = deserialize_object(serialized);
         storage
@@ -831,18 +1144,80 @@ mod tests {
             .expect("Error creating contract");
 
         let serialized = include_bytes!("../test_files/SignedChannelEstablished");
-        let signed_channel = Channel::Signed(deserialize_object(serialized));
+        let signed_channel =
+            Channel::Signed(deserialize_legacy(serialized, legacy::read_signed_channel));
         storage
             .upsert_channel(signed_channel, None)
             .expect("Error creating contract");
 
         let serialized = include_bytes!("../test_files/SignedChannelSettled");
-        let signed_channel = Channel::Signed(deserialize_object(serialized));
+        let signed_channel =
+            Channel::Signed(deserialize_legacy(serialized, legacy::read_signed_channel));
         storage
             .upsert_channel(signed_channel, None)
             .expect("Error creating contract");
     }
 
+    sled_test!(
+        roll_over_contract_keeps_rolled_over_contract,
+        |mut storage: SledStorageProvider| {
+            insert_offered_signed_and_confirmed(&mut storage);
+
+            let serialized = include_bytes!("../test_files/Confirmed");
+            let rolled_over_contract: SignedContract =
+                deserialize_legacy(serialized, legacy::read_signed_contract);
+            let serialized = include_bytes!("../test_files/Confirmed1");
+            let contract =
+                Contract::Confirmed(deserialize_legacy(serialized, legacy::read_signed_contract));
+
+            storage
+                .roll_over_contract(&rolled_over_contract, &contract)
+                .expect("Error rolling over contract");
+
+            let rolled_over_contracts = storage
+                .get_rolled_over_contracts()
+                .expect("Error retrieving rolled over contracts");
+            assert_eq!(1, rolled_over_contracts.len());
+            assert_eq!(
+                rolled_over_contract.accepted_contract.get_contract_id(),
+                rolled_over_contracts[0].accepted_contract.get_contract_id()
+            );
+
+            let confirmed_contracts = storage
+                .get_confirmed_contracts()
+                .expect("Error retrieving confirmed contracts");
+            assert_eq!(1, confirmed_contracts.len());
+        }
+    );
+
+    sled_test!(
+        get_prerefunded_contracts_only_prerefunded,
+        |mut storage: SledStorageProvider| {
+            insert_offered_signed_and_confirmed(&mut storage);
+
+            let serialized = include_bytes!("../test_files/Confirmed");
+            let prerefunded_contract =
+                Contract::PreRefunded(deserialize_legacy(serialized, legacy::read_signed_contract));
+            storage
+                .update_contract(&prerefunded_contract)
+                .expect("Error updating contract");
+
+            let prerefunded_contracts = storage
+                .get_prerefunded_contracts()
+                .expect("Error retrieving pre-refunded contracts");
+            assert_eq!(1, prerefunded_contracts.len());
+            assert_eq!(
+                prerefunded_contract.get_id(),
+                prerefunded_contracts[0].accepted_contract.get_contract_id()
+            );
+
+            let confirmed_contracts = storage
+                .get_confirmed_contracts()
+                .expect("Error retrieving confirmed contracts");
+            assert_eq!(1, confirmed_contracts.len());
+        }
+    );
+
     sled_test!(
         get_signed_contracts_only_signed,
         |mut storage: SledStorageProvider| {
@@ -947,7 +1322,7 @@ mod tests {
             insert_offered_and_signed_channels(&mut storage);
 
             let serialized = include_bytes!("../test_files/AcceptedChannel");
-            let accepted_channel: AcceptedChannel = deserialize_object(serialized);
+            let accepted_channel = deserialize_legacy(serialized, legacy::read_accepted_channel);
             let channel_id = accepted_channel.channel_id;
             storage
                 .upsert_channel(Channel::Accepted(accepted_channel), None)
@@ -966,7 +1341,7 @@ mod tests {
             insert_offered_and_signed_channels(&mut storage);
 
             let serialized = include_bytes!("../test_files/AcceptedChannel");
-            let accepted_channel: AcceptedChannel = deserialize_object(serialized);
+            let accepted_channel = deserialize_legacy(serialized, legacy::read_accepted_channel);
             let channel_id = accepted_channel.channel_id;
             storage
                 .upsert_channel(Channel::Accepted(accepted_channel), None)
@@ -1004,4 +1379,178 @@ mod tests {
             assert_eq!(chain_monitor, retrieved);
         }
     );
+
+    sled_test!(persist_events_test, |storage: SledStorageProvider| {
+        let events = (0..12u8)
+            .map(|i| Event::ContractConfirmed {
+                contract_id: [i; 32],
+            })
+            .collect::<Vec<_>>();
+
+        storage
+            .persist_events(&events)
+            .expect("to be able to persist events.");
+        assert_eq!(
+            events,
+            storage
+                .get_events()
+                .expect("to be able to retrieve events.")
+        );
+
+        storage
+            .persist_events(&events[..3])
+            .expect("to be able to persist events.");
+        assert_eq!(
+            events[..3].to_vec(),
+            storage
+                .get_events()
+                .expect("to be able to retrieve events.")
+        );
+
+        storage
+            .persist_events(&[])
+            .expect("to be able to persist events.");
+        assert!(storage
+            .get_events()
+            .expect("to be able to retrieve events.")
+            .is_empty());
+    });
+
+    fn insert_v0_records(path: &str) {
+        let db = sled::open(path).expect("Error opening sled DB");
+
+        let contract_tree = db.open_tree([CONTRACT_TREE]).unwrap();
+        let contracts: Vec<(ContractPrefix, &[u8])> = vec![
+            (
+                ContractPrefix::Offered,
+                include_bytes!("../test_files/Offered"),
+            ),
+            (
+                ContractPrefix::Accepted,
+                include_bytes!("../test_files/Accepted"),
+            ),
+            (
+                ContractPrefix::Signed,
+                include_bytes!("../test_files/Signed"),
+            ),
+            (
+                ContractPrefix::Confirmed,
+                include_bytes!("../test_files/Confirmed1"),
+            ),
+            (
+                ContractPrefix::PreClosed,
+                include_bytes!("../test_files/PreClosed"),
+            ),
+        ];
+        for (i, (prefix, serialized)) in contracts.into_iter().enumerate() {
+            let mut value: Vec<u8> = vec![prefix.into()];
+            value.extend_from_slice(serialized);
+            contract_tree.insert([i as u8; 32], value).unwrap();
+        }
+
+        let channel_tree = db.open_tree([CHANNEL_TREE]).unwrap();
+        let channels: Vec<(SignedChannelPrefix, &[u8])> = vec![
+            (
+                SignedChannelPrefix::Established,
+                include_bytes!("../test_files/SignedChannelEstablished"),
+            ),
+            (
+                SignedChannelPrefix::Settled,
+                include_bytes!("../test_files/SignedChannelSettled"),
+            ),
+        ];
+        for (i, (prefix, serialized)) in channels.into_iter().enumerate() {
+            let mut value: Vec<u8> = vec![ChannelPrefix::Signed.into(), prefix.into()];
+            value.extend_from_slice(serialized);
+            channel_tree.insert([i as u8; 32], value).unwrap();
+        }
+        let mut value: Vec<u8> = vec![ChannelPrefix::Accepted.into()];
+        value.extend_from_slice(include_bytes!("../test_files/AcceptedChannel"));
+        channel_tree.insert([2u8; 32], value).unwrap();
+
+        // A chain monitor without any watched transaction at height 123.
+        let mut chain_monitor = vec![0u8; 16];
+        chain_monitor.extend_from_slice(&123u64.to_be_bytes());
+        db.open_tree([CHAIN_MONITOR_TREE])
+            .unwrap()
+            .insert([CHAIN_MONITOR_KEY], chain_monitor)
+            .unwrap();
+
+        db.flush().unwrap();
+    }
+
+    fn assert_v0_records_readable(storage: &SledStorageProvider) {
+        assert_eq!(5, storage.get_contracts().unwrap().len());
+        assert_eq!(1, storage.get_contract_offers().unwrap().len());
+        assert_eq!(1, storage.get_signed_contracts().unwrap().len());
+        assert_eq!(1, storage.get_confirmed_contracts().unwrap().len());
+        assert_eq!(1, storage.get_preclosed_contracts().unwrap().len());
+        assert_eq!(2, storage.get_signed_channels(None).unwrap().len());
+        assert!(matches!(
+            storage.get_channel(&[2u8; 32]).unwrap(),
+            Some(Channel::Accepted(_))
+        ));
+        assert_eq!(
+            ChainMonitor::new(123),
+            storage
+                .get_chain_monitor()
+                .unwrap()
+                .expect("to have a persisted chain monitor.")
+        );
+    }
+
+    #[test]
+    fn v0_storage_is_migrated() {
+        let path = "test_files/sleddb/v0_storage_is_migrated";
+        insert_v0_records(path);
+
+        {
+            let storage = SledStorageProvider::new(path).expect("Error migrating sled DB");
+            assert_v0_records_readable(&storage);
+        }
+
+        {
+            let storage = SledStorageProvider::new(path).expect("Error opening sled DB");
+            assert_v0_records_readable(&storage);
+        }
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn current_storage_is_not_migrated() {
+        let path = "test_files/sleddb/current_storage_is_not_migrated";
+
+        {
+            let storage = SledStorageProvider::new(path).expect("Error opening sled DB");
+            let serialized = include_bytes!("../test_files/Offered");
+            let contract = deserialize_legacy(serialized, legacy::read_offered_contract);
+            storage
+                .create_contract(&contract)
+                .expect("Error creating contract");
+        }
+
+        {
+            let storage = SledStorageProvider::new(path).expect("Error opening sled DB");
+            assert_eq!(1, storage.get_contract_offers().unwrap().len());
+        }
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn newer_storage_version_is_rejected() {
+        let path = "test_files/sleddb/newer_storage_version_is_rejected";
+
+        {
+            let db = sled::open(path).expect("Error opening sled DB");
+            db.insert([STORAGE_VERSION_KEY], vec![STORAGE_VERSION + 1])
+                .unwrap();
+            db.flush().unwrap();
+        }
+
+        assert!(SledStorageProvider::new(path).is_err());
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
 }
@@ -192,6 +192,18 @@ pub trait DlcTrie<'a, TrieIterator: Iterator<Item = TrieIterInfo>> {
         )
     }
 
+    /// Generate the trie and return the CET index and adaptor point of each
+    /// adaptor signature required for it, ordered by adaptor signature index.
+    fn generate_adaptor_points(
+        &'a mut self,
+        outcomes: &[RangePayout],
+        precomputed_points: &[Vec<Vec<PublicKey>>],
+        adaptor_index_start: usize,
+    ) -> Result<Vec<(usize, PublicKey)>, Error> {
+        let trie_info = self.generate(adaptor_index_start, outcomes)?;
+        adaptor_points_helper(precomputed_points, trie_info.into_iter())
+    }
+
     /// Produce the set of adaptor signatures for the trie.
     fn sign(
         &'a self,
@@ -223,6 +235,24 @@ pub struct TrieIterInfo {
     value: RangeInfo,
 }
 
+fn adaptor_points_helper<T: Iterator<Item = TrieIterInfo>>(
+    precomputed_points: &[Vec<Vec<PublicKey>>],
+    trie_info: T,
+) -> Result<Vec<(usize, PublicKey)>, Error> {
+    let mut unsorted = trie_info
+        .map(|x| {
+            let adaptor_point = utils::get_adaptor_point_for_indexed_paths(
+                &x.indexes,
+                &x.paths,
+                precomputed_points,
+            )?;
+            Ok((x.value.adaptor_index, (x.value.cet_index, adaptor_point)))
+        })
+        .collect::<Result<Vec<(usize, (usize, PublicKey))>, Error>>()?;
+    unsorted.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(unsorted.into_iter().map(|(_, y)| y).collect())
+}
+
 #[cfg(not(feature = "parallel"))]
 fn sign_helper<T: Iterator<Item = TrieIterInfo>>(
     secp: &Secp256k1<All>,
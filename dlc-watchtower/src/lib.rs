@@ -0,0 +1,606 @@
+//! # dlc-watchtower
+//! A watchtower monitoring the blockchain on behalf of parties of DLC channels,
+//! and broadcasting a punishment transaction when a revoked transaction of one
+//! of their channels gets published. The watchtower only holds the encrypted
+//! punishment packages exported by the [`dlc_manager::manager::Manager`], which
+//! it can only decrypt once the matching revoked transaction is published.
+
+#![crate_name = "dlc_watchtower"]
+// Coding conventions
+#![forbid(unsafe_code)]
+#![deny(non_upper_case_globals)]
+#![deny(non_camel_case_types)]
+#![deny(non_snake_case)]
+#![deny(unused_mut)]
+#![deny(dead_code)]
+#![deny(unused_imports)]
+#![deny(missing_docs)]
+
+extern crate bitcoin;
+extern crate dlc_manager;
+extern crate log;
+extern crate secp256k1_zkp;
+
+pub mod memory_storage;
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use bitcoin::{BlockHash, Transaction};
+use dlc_manager::chain_monitor::REORG_SAFETY_DEPTH;
+use dlc_manager::error::Error;
+use dlc_manager::watchtower::EncryptedPunishmentPackage;
+use dlc_manager::Blockchain;
+use log::{error, info, warn};
+use secp256k1_zkp::{All, Secp256k1};
+
+/// The blocks processed by a [`Watchtower`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainState {
+    /// The height of the last processed block.
+    pub last_height: u64,
+    /// The hashes of the latest processed blocks, the last one being the hash
+    /// of the block at `last_height`, used to detect chain reorganizations.
+    pub block_hashes: Vec<BlockHash>,
+}
+
+impl ChainState {
+    /// Creates the state of a watchtower that will process the blocks above
+    /// `last_height`.
+    pub fn new(last_height: u64) -> Self {
+        ChainState {
+            last_height,
+            block_hashes: Vec::new(),
+        }
+    }
+
+    fn get_block_hash(&self, height: u64) -> Option<BlockHash> {
+        if height > self.last_height {
+            return None;
+        }
+        let depth = (self.last_height - height) as usize;
+        if depth >= self.block_hashes.len() {
+            return None;
+        }
+        Some(self.block_hashes[self.block_hashes.len() - 1 - depth])
+    }
+
+    fn lowest_tracked_height(&self) -> u64 {
+        self.last_height + 1 - self.block_hashes.len() as u64
+    }
+}
+
+/// A punishment transaction broadcast by a [`Watchtower`] after the revoked
+/// transaction of one of its packages was included in the chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Punishment {
+    /// The hint of the package used to create the punishment transaction.
+    pub hint: [u8; 32],
+    /// The signed punishment transaction.
+    pub punish_tx: Transaction,
+    /// The height of the block including the revoked transaction.
+    pub height: u64,
+}
+
+/// Storage of the state of a [`Watchtower`], so that it can be restored after
+/// a restart.
+pub trait Storage {
+    /// Creates or updates the record of the given package.
+    fn upsert_package(&self, package: &EncryptedPunishmentPackage) -> Result<(), Error>;
+    /// Returns the package with the given hint if found.
+    fn get_package(&self, hint: &[u8; 32]) -> Result<Option<EncryptedPunishmentPackage>, Error>;
+    /// Returns all the stored packages.
+    fn get_packages(&self) -> Result<Vec<EncryptedPunishmentPackage>, Error>;
+    /// Deletes the package with the given hint.
+    fn delete_package(&self, hint: &[u8; 32]) -> Result<(), Error>;
+    /// Creates or updates the record of the given punishment.
+    fn upsert_punishment(&self, punishment: &Punishment) -> Result<(), Error>;
+    /// Returns all the stored punishments.
+    fn get_punishments(&self) -> Result<Vec<Punishment>, Error>;
+    /// Deletes the punishment of the package with the given hint.
+    fn delete_punishment(&self, hint: &[u8; 32]) -> Result<(), Error>;
+    /// Persists the given chain state.
+    fn persist_chain_state(&self, chain_state: &ChainState) -> Result<(), Error>;
+    /// Returns the persisted chain state if any.
+    fn get_chain_state(&self) -> Result<Option<ChainState>, Error>;
+}
+
+/// Watches the blockchain for the revoked transactions of the punishment
+/// packages it holds. Packages are kept until their punishment transaction is
+/// buried under [`REORG_SAFETY_DEPTH`] blocks, so that a revoked transaction
+/// reorganized out of the chain and mined again is punished again.
+pub struct Watchtower<B: Deref, S: Deref>
+where
+    B::Target: Blockchain,
+    S::Target: Storage,
+{
+    blockchain: B,
+    store: S,
+    chain_state: Mutex<ChainState>,
+    secp: Secp256k1<All>,
+}
+
+impl<B: Deref, S: Deref> Watchtower<B, S>
+where
+    B::Target: Blockchain,
+    S::Target: Storage,
+{
+    /// Creates a watchtower restoring its state from the given store, or, if
+    /// none was persisted, that will process the blocks above `init_height`.
+    pub fn new(blockchain: B, store: S, init_height: u64) -> Result<Self, Error> {
+        let chain_state = match store.get_chain_state()? {
+            Some(chain_state) => chain_state,
+            None => {
+                let chain_state = ChainState::new(init_height);
+                store.persist_chain_state(&chain_state)?;
+                chain_state
+            }
+        };
+
+        Ok(Watchtower {
+            blockchain,
+            store,
+            chain_state: Mutex::new(chain_state),
+            secp: Secp256k1::new(),
+        })
+    }
+
+    /// Adds the given package to the set of packages held by the watchtower.
+    pub fn add_package(&self, package: EncryptedPunishmentPackage) -> Result<(), Error> {
+        self.store.upsert_package(&package)
+    }
+
+    /// Returns the packages held by the watchtower.
+    pub fn get_packages(&self) -> Result<Vec<EncryptedPunishmentPackage>, Error> {
+        self.store.get_packages()
+    }
+
+    /// Returns the punishments whose transaction is not yet buried under
+    /// [`REORG_SAFETY_DEPTH`] blocks.
+    pub fn get_punishments(&self) -> Result<Vec<Punishment>, Error> {
+        self.store.get_punishments()
+    }
+
+    /// Returns the height of the last processed block.
+    pub fn get_last_height(&self) -> u64 {
+        self.chain_state.lock().unwrap().last_height
+    }
+
+    /// Processes the blocks mined since the last call, broadcasting a
+    /// punishment transaction for each revoked transaction found. Returns the
+    /// broadcast punishment transactions. If broadcasting fails, the block is
+    /// processed again on the next call. Processed blocks that were
+    /// disconnected from the chain are reverted, and the punishment
+    /// transactions broadcast by previous calls that are not confirmed are
+    /// broadcast again.
+    pub fn process_new_blocks(&self) -> Result<Vec<Transaction>, Error> {
+        let chain_height = self.blockchain.get_blockchain_height()?;
+        let mut chain_state = self.chain_state.lock().unwrap();
+
+        if let Some(fork_height) = self.find_fork_height(&chain_state, chain_height)? {
+            self.on_reorg(&mut chain_state, fork_height)?;
+        }
+
+        self.check_punishments()?;
+
+        let mut punish_txs = Vec::new();
+
+        while chain_state.last_height < chain_height {
+            let height = chain_state.last_height + 1;
+            let block = self.blockchain.get_block_at_height(height)?;
+            for tx in &block.txdata {
+                if let Some(punish_tx) = self.process_transaction(tx, height)? {
+                    punish_txs.push(punish_tx);
+                }
+            }
+            chain_state.last_height = height;
+            chain_state.block_hashes.push(block.block_hash());
+            if chain_state.block_hashes.len() > REORG_SAFETY_DEPTH {
+                chain_state.block_hashes.remove(0);
+            }
+            self.store.persist_chain_state(&chain_state)?;
+        }
+
+        Ok(punish_txs)
+    }
+
+    /// Returns the height of the last processed block that is still part of
+    /// the chain if some processed blocks were disconnected, `None` otherwise.
+    fn find_fork_height(
+        &self,
+        chain_state: &ChainState,
+        chain_height: u64,
+    ) -> Result<Option<u64>, Error> {
+        let last_height = chain_state.last_height;
+        let lowest_tracked_height = chain_state.lowest_tracked_height();
+
+        let mut height = std::cmp::min(last_height, chain_height);
+        while height >= lowest_tracked_height && height > 0 {
+            let tracked_hash = match chain_state.get_block_hash(height) {
+                Some(h) => h,
+                None => break,
+            };
+            if tracked_hash == self.blockchain.get_block_hash_at_height(height)? {
+                return Ok(if height < last_height {
+                    Some(height)
+                } else {
+                    None
+                });
+            }
+            height -= 1;
+        }
+
+        if height < last_height && lowest_tracked_height <= last_height {
+            if chain_state.block_hashes.len() >= REORG_SAFETY_DEPTH {
+                return Err(Error::InvalidState(format!(
+                    "Chain reorganization deeper than {} blocks cannot be handled.",
+                    REORG_SAFETY_DEPTH
+                )));
+            }
+            return Ok(Some(height));
+        }
+
+        Ok(None)
+    }
+
+    /// Reverts the processed blocks above the given height which were
+    /// disconnected from the chain. The packages of the punishments whose
+    /// revoked transaction was disconnected are kept, so that the revoked
+    /// transaction is punished again if it gets mined again.
+    fn on_reorg(&self, chain_state: &mut ChainState, fork_height: u64) -> Result<(), Error> {
+        warn!(
+            "Chain reorganization detected, blocks above height {} were disconnected.",
+            fork_height
+        );
+
+        let nb_disconnected = (chain_state.last_height - fork_height) as usize;
+        let nb_kept = chain_state
+            .block_hashes
+            .len()
+            .saturating_sub(nb_disconnected);
+        chain_state.block_hashes.truncate(nb_kept);
+        chain_state.last_height = fork_height;
+
+        for punishment in self.store.get_punishments()? {
+            if punishment.height > fork_height {
+                info!(
+                    "Revoked transaction punished by {} was disconnected from the chain",
+                    punishment.punish_tx.txid()
+                );
+                self.store.delete_punishment(&punishment.hint)?;
+            }
+        }
+
+        self.store.persist_chain_state(chain_state)
+    }
+
+    fn process_transaction(
+        &self,
+        tx: &Transaction,
+        height: u64,
+    ) -> Result<Option<Transaction>, Error> {
+        let txid = tx.txid();
+        let hint = EncryptedPunishmentPackage::get_hint(&txid);
+        let package = match self.store.get_package(&hint)? {
+            Some(package) => package,
+            None => return Ok(None),
+        };
+
+        let punish_tx = match package
+            .decrypt(&txid)
+            .and_then(|x| x.get_punishment_transaction(&self.secp, tx))
+        {
+            Ok(punish_tx) => punish_tx,
+            Err(e) => {
+                error!("Could not create punishment transaction for transaction {txid}: {e}");
+                return Ok(None);
+            }
+        };
+
+        self.blockchain.send_transaction(&punish_tx)?;
+        info!(
+            "Broadcast punishment transaction {} for revoked transaction {txid}",
+            punish_tx.txid()
+        );
+        self.store.upsert_punishment(&Punishment {
+            hint,
+            punish_tx: punish_tx.clone(),
+            height,
+        })?;
+
+        Ok(Some(punish_tx))
+    }
+
+    /// Forgets the punishments whose transaction is buried under
+    /// [`REORG_SAFETY_DEPTH`] blocks together with their package, and
+    /// broadcasts again the punishment transactions that are not confirmed.
+    fn check_punishments(&self) -> Result<(), Error> {
+        for punishment in self.store.get_punishments()? {
+            let punish_txid = punishment.punish_tx.txid();
+            let confirmations = self
+                .blockchain
+                .get_transaction_confirmations(&punish_txid)?;
+            if confirmations as usize >= REORG_SAFETY_DEPTH {
+                self.store.delete_package(&punishment.hint)?;
+                self.store.delete_punishment(&punishment.hint)?;
+            } else if confirmations == 0 {
+                if let Err(e) = self.blockchain.send_transaction(&punishment.punish_tx) {
+                    error!("Could not broadcast punishment transaction {punish_txid}: {e}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{Address, Network, OutPoint, TxIn};
+    use dlc::channel::RevokeParams;
+    use dlc_manager::chain_monitor::RevokedTxType;
+    use dlc_manager::watchtower::{PunishmentPackage, RevokePublicKeys};
+    use memory_storage::MemoryStorage;
+    use mocks::mock_blockchain::MockBlockchain;
+    use secp256k1_zkp::{rand::thread_rng, PublicKey, SecretKey};
+
+    fn get_revoke_keys(secp: &Secp256k1<All>) -> (RevokePublicKeys, SecretKey) {
+        let own_sk = SecretKey::new(&mut thread_rng());
+        let keys = RevokePublicKeys {
+            own_pk: PublicKey::from_secret_key(secp, &own_sk),
+            publish_pk: PublicKey::from_secret_key(secp, &SecretKey::new(&mut thread_rng())),
+            revoke_pk: PublicKey::from_secret_key(secp, &SecretKey::new(&mut thread_rng())),
+        };
+        (keys, own_sk)
+    }
+
+    /// Returns a punishment package for a revoked buffer transaction, together
+    /// with the buffer transaction signed by the counter party.
+    fn get_package_and_revoked_tx() -> (PunishmentPackage, Transaction) {
+        let secp = Secp256k1::new();
+        let (offer_revoke_keys, own_sk) = get_revoke_keys(&secp);
+        let counter_revoke_sk = SecretKey::new(&mut thread_rng());
+        let counter_publish_sk = SecretKey::new(&mut thread_rng());
+        let accept_revoke_keys = RevokePublicKeys {
+            own_pk: PublicKey::from_secret_key(&secp, &SecretKey::new(&mut thread_rng())),
+            publish_pk: PublicKey::from_secret_key(&secp, &counter_publish_sk),
+            revoke_pk: PublicKey::from_secret_key(&secp, &counter_revoke_sk),
+        };
+        let offer_params = RevokeParams::from(&offer_revoke_keys);
+        let accept_params = RevokeParams::from(&accept_revoke_keys);
+
+        let own_fund_sk = SecretKey::new(&mut thread_rng());
+        let counter_fund_sk = SecretKey::new(&mut thread_rng());
+        let own_fund_pk = PublicKey::from_secret_key(&secp, &own_fund_sk);
+        let funding_script = dlc::make_funding_redeemscript(
+            &own_fund_pk,
+            &PublicKey::from_secret_key(&secp, &counter_fund_sk),
+        );
+        let fund_output_value = 200020000;
+
+        let mut buffer_tx = dlc::channel::create_buffer_transaction(
+            &TxIn {
+                previous_output: OutPoint::default(),
+                ..Default::default()
+            },
+            &dlc::channel::buffer_descriptor(&offer_params, &accept_params),
+            200000000,
+            0,
+        );
+
+        let own_adaptor_signature = dlc::channel::get_tx_adaptor_signature(
+            &secp,
+            &buffer_tx,
+            fund_output_value,
+            &funding_script,
+            &own_fund_sk,
+            &accept_revoke_keys.publish_pk,
+        )
+        .unwrap();
+
+        let dest_address = Address::p2wpkh(
+            &bitcoin::PublicKey::new(PublicKey::from_secret_key(
+                &secp,
+                &SecretKey::new(&mut thread_rng()),
+            )),
+            Network::Regtest,
+        )
+        .unwrap();
+
+        let punish_tx = dlc::channel::create_punish_buffer_transaction(
+            &offer_params,
+            &accept_params,
+            &buffer_tx,
+            &dest_address,
+            0,
+            2,
+        )
+        .unwrap();
+
+        let sign = |sk: &SecretKey| {
+            dlc::channel::sign_punish_buffer_transaction(
+                &secp,
+                &offer_params,
+                &accept_params,
+                &punish_tx,
+                &buffer_tx,
+                sk,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|x| x.sig)
+            .collect::<Vec<_>>()
+        };
+
+        let package = PunishmentPackage {
+            channel_id: [1u8; 32],
+            revoked_tx_type: RevokedTxType::Buffer,
+            is_offer: true,
+            offer_revoke_keys,
+            accept_revoke_keys,
+            csv_timelock: 288,
+            own_adaptor_signature,
+            punish_tx,
+            own_signatures: sign(&own_sk),
+            counter_revoke_signatures: sign(&counter_revoke_sk),
+        };
+
+        // The counter party signs the revoked buffer transaction, decrypting
+        // the adaptor signature of the local party.
+        let own_sig = own_adaptor_signature.decrypt(&counter_publish_sk).unwrap();
+        dlc::util::sign_multi_sig_input(
+            &secp,
+            &mut buffer_tx,
+            &own_sig,
+            &own_fund_pk,
+            &counter_fund_sk,
+            &funding_script,
+            fund_output_value,
+            0,
+        )
+        .unwrap();
+
+        (package, buffer_tx)
+    }
+
+    #[test]
+    fn watchtower_punishes_revoked_buffer_transaction_test() {
+        let (package, buffer_tx) = get_package_and_revoked_tx();
+        let punish_tx = package.punish_tx.clone();
+
+        let blockchain = MockBlockchain::new_with_chain();
+        let store = MemoryStorage::new();
+        let watchtower = Watchtower::new(
+            &blockchain,
+            &store,
+            blockchain.get_blockchain_height().unwrap(),
+        )
+        .unwrap();
+        watchtower.add_package(package.encrypt().unwrap()).unwrap();
+
+        blockchain.mine_block();
+        assert!(watchtower.process_new_blocks().unwrap().is_empty());
+        assert_eq!(1, watchtower.get_packages().unwrap().len());
+
+        // The counter party publishes the revoked buffer transaction.
+        blockchain.send_transaction(&buffer_tx).unwrap();
+        blockchain.mine_block();
+
+        let punish_txs = watchtower.process_new_blocks().unwrap();
+
+        assert_eq!(1, punish_txs.len());
+        assert_eq!(punish_tx.txid(), punish_txs[0].txid());
+        let punishments = watchtower.get_punishments().unwrap();
+        assert_eq!(1, punishments.len());
+        assert_eq!(
+            blockchain.get_blockchain_height().unwrap(),
+            punishments[0].height
+        );
+        assert_eq!(
+            blockchain.get_blockchain_height().unwrap(),
+            watchtower.get_last_height()
+        );
+
+        blockchain.mine_block();
+        assert_eq!(
+            1,
+            blockchain
+                .get_transaction_confirmations(&punish_tx.txid())
+                .unwrap()
+        );
+
+        for _ in 1..REORG_SAFETY_DEPTH {
+            blockchain.mine_block();
+        }
+        assert!(watchtower.process_new_blocks().unwrap().is_empty());
+        assert!(watchtower.get_packages().unwrap().is_empty());
+        assert!(watchtower.get_punishments().unwrap().is_empty());
+    }
+
+    #[test]
+    fn watchtower_punishes_revoked_transaction_mined_again_after_reorg_test() {
+        let (package, buffer_tx) = get_package_and_revoked_tx();
+        let punish_tx = package.punish_tx.clone();
+
+        let blockchain = MockBlockchain::new_with_chain();
+        let store = MemoryStorage::new();
+        let watchtower = Watchtower::new(
+            &blockchain,
+            &store,
+            blockchain.get_blockchain_height().unwrap(),
+        )
+        .unwrap();
+        watchtower.add_package(package.encrypt().unwrap()).unwrap();
+
+        blockchain.mine_block();
+        blockchain.send_transaction(&buffer_tx).unwrap();
+        blockchain.mine_block();
+        assert_eq!(1, watchtower.process_new_blocks().unwrap().len());
+        let fork_height = blockchain.get_blockchain_height().unwrap() - 1;
+
+        // The block including the revoked transaction is disconnected.
+        blockchain.disconnect_blocks(1);
+        assert!(watchtower.process_new_blocks().unwrap().is_empty());
+        assert!(watchtower.get_punishments().unwrap().is_empty());
+        assert_eq!(1, watchtower.get_packages().unwrap().len());
+        assert_eq!(fork_height, watchtower.get_last_height());
+
+        // The revoked transaction gets mined again.
+        blockchain.mine_block();
+        let punish_txs = watchtower.process_new_blocks().unwrap();
+        assert_eq!(1, punish_txs.len());
+        assert_eq!(punish_tx.txid(), punish_txs[0].txid());
+        assert_eq!(
+            blockchain.get_blockchain_height().unwrap(),
+            watchtower.get_punishments().unwrap()[0].height
+        );
+    }
+
+    #[test]
+    fn watchtower_state_is_restored_from_storage_test() {
+        let (package, buffer_tx) = get_package_and_revoked_tx();
+        let encrypted = package.encrypt().unwrap();
+
+        let blockchain = MockBlockchain::new_with_chain();
+        let store = MemoryStorage::new();
+        let init_height = blockchain.get_blockchain_height().unwrap();
+        {
+            let watchtower = Watchtower::new(&blockchain, &store, init_height).unwrap();
+            watchtower.add_package(encrypted.clone()).unwrap();
+            blockchain.mine_block();
+            watchtower.process_new_blocks().unwrap();
+        }
+
+        let watchtower = Watchtower::new(&blockchain, &store, 0).unwrap();
+        assert_eq!(init_height + 1, watchtower.get_last_height());
+        assert_eq!(vec![encrypted], watchtower.get_packages().unwrap());
+
+        blockchain.send_transaction(&buffer_tx).unwrap();
+        blockchain.mine_block();
+        assert_eq!(1, watchtower.process_new_blocks().unwrap().len());
+    }
+
+    #[test]
+    fn watchtower_rejects_reorg_deeper_than_safety_depth_test() {
+        let blockchain = MockBlockchain::new_with_chain();
+        let store = MemoryStorage::new();
+        let watchtower = Watchtower::new(
+            &blockchain,
+            &store,
+            blockchain.get_blockchain_height().unwrap(),
+        )
+        .unwrap();
+
+        for _ in 0..REORG_SAFETY_DEPTH {
+            blockchain.mine_block();
+        }
+        watchtower.process_new_blocks().unwrap();
+
+        blockchain.disconnect_blocks(REORG_SAFETY_DEPTH);
+        for _ in 0..REORG_SAFETY_DEPTH {
+            blockchain.mine_block();
+        }
+        assert!(watchtower.process_new_blocks().is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! In-memory implementation of the watchtower [`Storage`] trait, whose state is
+//! lost when the watchtower is dropped.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use dlc_manager::error::Error;
+use dlc_manager::watchtower::EncryptedPunishmentPackage;
+
+use crate::{ChainState, Punishment, Storage};
+
+/// Keeps the state of a watchtower in memory.
+pub struct MemoryStorage {
+    packages: RwLock<HashMap<[u8; 32], EncryptedPunishmentPackage>>,
+    punishments: RwLock<HashMap<[u8; 32], Punishment>>,
+    chain_state: RwLock<Option<ChainState>>,
+}
+
+impl MemoryStorage {
+    /// Creates an empty storage.
+    pub fn new() -> Self {
+        MemoryStorage {
+            packages: RwLock::new(HashMap::new()),
+            punishments: RwLock::new(HashMap::new()),
+            chain_state: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for MemoryStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn upsert_package(&self, package: &EncryptedPunishmentPackage) -> Result<(), Error> {
+        self.packages
+            .write()
+            .expect("Could not get write lock")
+            .insert(package.hint, package.clone());
+        Ok(())
+    }
+
+    fn get_package(&self, hint: &[u8; 32]) -> Result<Option<EncryptedPunishmentPackage>, Error> {
+        Ok(self
+            .packages
+            .read()
+            .expect("Could not get read lock")
+            .get(hint)
+            .cloned())
+    }
+
+    fn get_packages(&self) -> Result<Vec<EncryptedPunishmentPackage>, Error> {
+        Ok(self
+            .packages
+            .read()
+            .expect("Could not get read lock")
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    fn delete_package(&self, hint: &[u8; 32]) -> Result<(), Error> {
+        self.packages
+            .write()
+            .expect("Could not get write lock")
+            .remove(hint);
+        Ok(())
+    }
+
+    fn upsert_punishment(&self, punishment: &Punishment) -> Result<(), Error> {
+        self.punishments
+            .write()
+            .expect("Could not get write lock")
+            .insert(punishment.hint, punishment.clone());
+        Ok(())
+    }
+
+    fn get_punishments(&self) -> Result<Vec<Punishment>, Error> {
+        Ok(self
+            .punishments
+            .read()
+            .expect("Could not get read lock")
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    fn delete_punishment(&self, hint: &[u8; 32]) -> Result<(), Error> {
+        self.punishments
+            .write()
+            .expect("Could not get write lock")
+            .remove(hint);
+        Ok(())
+    }
+
+    fn persist_chain_state(&self, chain_state: &ChainState) -> Result<(), Error> {
+        *self.chain_state.write().expect("Could not get write lock") = Some(chain_state.clone());
+        Ok(())
+    }
+
+    fn get_chain_state(&self) -> Result<Option<ChainState>, Error> {
+        Ok(self
+            .chain_state
+            .read()
+            .expect("Could not get read lock")
+            .clone())
+    }
+}
@@ -171,7 +171,11 @@ pub fn verify_tx_adaptor_signature<C: Verification>(
     Ok(())
 }
 
-/// Returns a settle transaction.
+/// Returns a settle transaction paying `offer_payout` and `accept_payout` to
+/// the offer and accept party respectively, with the fee reserve of the channel
+/// split between them as described in [`get_fee_split_payouts`]. If
+/// `anchor_script_pubkeys` is provided, an anchor output paying to the given
+/// script pubkey is added for each of the offer and accept party respectively.
 pub fn create_settle_transaction(
     fund_tx_in: &TxIn,
     offer_revoke_params: &RevokeParams,
@@ -182,6 +186,8 @@ pub fn create_settle_transaction(
     lock_time: u32,
     fund_output_value: u64,
     fee_rate_per_vb: u64,
+    offer_fee_share: u8,
+    anchor_script_pubkeys: Option<(&Script, &Script)>,
 ) -> Result<Transaction, Error> {
     let offer_descriptor = settle_descriptor(
         offer_revoke_params,
@@ -194,44 +200,170 @@ pub fn create_settle_transaction(
         csv_timelock,
     );
 
-    let mut output = crate::util::discard_dust(
+    let fee = get_settle_tx_fee(fee_rate_per_vb, anchor_script_pubkeys)?;
+    let (offer_value, accept_value) = get_fee_split_payouts(
+        fund_output_value,
+        offer_payout,
+        accept_payout,
+        fee,
+        offer_fee_share,
+    )?;
+
+    let output = crate::util::discard_dust(
         vec![
             TxOut {
-                value: offer_payout,
+                value: offer_value,
                 script_pubkey: offer_descriptor.script_pubkey(),
             },
             TxOut {
-                value: accept_payout,
+                value: accept_value,
                 script_pubkey: accept_descriptor.script_pubkey(),
             },
         ],
         crate::DUST_LIMIT,
     );
 
-    // We compute the fee that is left as the unilateral closing in settle state
-    //  is cheaper than in established state.
-    let remaining_fee = (fund_output_value
-        - offer_payout
-        - accept_payout
-        - crate::util::weight_to_fee(
-            SETTLE_INPUT_WEIGHT + output.len() * SETTLE_OUTPUT_WEIGHT,
-            fee_rate_per_vb,
-        )?)
-        / (output.len() as u64);
-
-    for o in &mut output {
-        o.value += remaining_fee;
-    }
-
-    Ok(Transaction {
+    let mut settle_tx = Transaction {
         version: super::TX_VERSION,
         lock_time: LockTime::from_consensus(lock_time),
         input: vec![fund_tx_in.clone()],
         output,
-    })
+    };
+
+    if let Some((offer_anchor_script_pubkey, accept_anchor_script_pubkey)) = anchor_script_pubkeys {
+        super::add_anchor_outputs(
+            &mut settle_tx,
+            offer_anchor_script_pubkey,
+            accept_anchor_script_pubkey,
+        );
+    }
+
+    Ok(settle_tx)
+}
+
+/// Returns the fee of a settle transaction at the given fee rate, including the
+/// value of the anchor outputs paying to `anchor_script_pubkeys` if provided.
+pub fn get_settle_tx_fee(
+    fee_rate_per_vb: u64,
+    anchor_script_pubkeys: Option<(&Script, &Script)>,
+) -> Result<u64, Error> {
+    let (anchors_weight, anchors_value) =
+        get_anchor_outputs_weight_and_value(anchor_script_pubkeys)?;
+
+    let fee = crate::util::weight_to_fee(
+        SETTLE_INPUT_WEIGHT + 2 * SETTLE_OUTPUT_WEIGHT + anchors_weight,
+        fee_rate_per_vb,
+    )?;
+
+    fee.checked_add(anchors_value).ok_or(Error::InvalidArgument)
+}
+
+/// Returns the fee of a collaborative close transaction paying to the payout
+/// script pubkeys of the given parties at the given fee rate.
+pub fn get_collaborative_close_tx_fee(
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_rate_per_vb: u64,
+) -> Result<u64, Error> {
+    let outputs_weight =
+        (offer_params.payout_script_pubkey.len() + accept_params.payout_script_pubkey.len()) * 4;
+    crate::util::weight_to_fee(super::CET_BASE_WEIGHT + outputs_weight, fee_rate_per_vb)
+}
+
+/// Returns the values of the offer and accept outputs of a transaction spending
+/// the fund output of a channel, given the balance of each party and the `fee`
+/// of the transaction. The fee reserve of the channel, that is the part of the
+/// fund output value not included in the balances, pays for the fee,
+/// `offer_fee_share` being the percentage of it paid by the offer party, and
+/// what remains of it is returned to the parties in equal parts. The values
+/// always add up to the fund output value minus the fee.
+pub fn get_fee_split_payouts(
+    fund_output_value: u64,
+    offer_balance: u64,
+    accept_balance: u64,
+    fee: u64,
+    offer_fee_share: u8,
+) -> Result<(u64, u64), Error> {
+    if offer_fee_share > 100 {
+        return Err(Error::InvalidArgument);
+    }
+
+    let reserve = offer_balance
+        .checked_add(accept_balance)
+        .and_then(|total| fund_output_value.checked_sub(total))
+        .ok_or(Error::InvalidArgument)?;
+    let remaining = fund_output_value
+        .checked_sub(fee)
+        .ok_or(Error::InvalidArgument)?;
+    if reserve < fee {
+        return Err(Error::InvalidArgument);
+    }
+
+    let offer_fee = fee * offer_fee_share as u64 / 100;
+    let offer_value = (offer_balance + reserve / 2)
+        .checked_sub(offer_fee)
+        .ok_or(Error::InvalidArgument)?;
+    let accept_value = remaining
+        .checked_sub(offer_value)
+        .ok_or(Error::InvalidArgument)?;
+
+    Ok((offer_value, accept_value))
+}
+
+/// Returns the total weight and value of the anchor outputs paying to the given
+/// offer and accept script pubkeys, or zeros if none are provided.
+fn get_anchor_outputs_weight_and_value(
+    anchor_script_pubkeys: Option<(&Script, &Script)>,
+) -> Result<(usize, u64), Error> {
+    match anchor_script_pubkeys {
+        Some((offer_script_pubkey, accept_script_pubkey)) => Ok((
+            super::get_anchor_output_weight(offer_script_pubkey)?
+                + super::get_anchor_output_weight(accept_script_pubkey)?,
+            2 * super::ANCHOR_OUTPUT_VALUE,
+        )),
+        None => Ok((0, 0)),
+    }
+}
+
+/// Returns the anchor script pubkeys of the given parties, that is their payout
+/// script pubkeys, if `anchor_outputs` is set.
+fn get_anchor_script_pubkeys<'a>(
+    offer_params: &'a PartyParams,
+    accept_params: &'a PartyParams,
+    anchor_outputs: bool,
+) -> Option<(&'a Script, &'a Script)> {
+    if anchor_outputs {
+        Some((
+            &offer_params.payout_script_pubkey,
+            &accept_params.payout_script_pubkey,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns the fee that needs to be provisioned in the fund transaction of a
+/// channel in addition to the fee of the fund transaction itself.
+fn get_channel_extra_fee(
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_rate_per_vb: u64,
+    anchor_outputs: bool,
+) -> Result<u64, Error> {
+    let (anchors_weight, anchors_value) = get_anchor_outputs_weight_and_value(
+        get_anchor_script_pubkeys(offer_params, accept_params, anchor_outputs),
+    )?;
+    super::util::weight_to_fee(
+        BUFFER_TX_WEIGHT + CET_EXTRA_WEIGHT + anchors_weight,
+        fee_rate_per_vb,
+    )?
+    .checked_add(anchors_value)
+    .ok_or(Error::InvalidArgument)
 }
 
-/// Returns the transactions necessary to establish a DLC channel.
+/// Returns the transactions necessary to establish a DLC channel. If
+/// `anchor_outputs` is set, anchor outputs are added to the buffer
+/// transaction, the CETs and the refund transaction.
 pub fn create_channel_transactions(
     offer_params: &PartyParams,
     accept_params: &PartyParams,
@@ -244,9 +376,10 @@ pub fn create_channel_transactions(
     cet_lock_time: u32,
     fund_output_serial_id: u64,
     cet_nsequence: Sequence,
+    anchor_outputs: bool,
 ) -> Result<DlcChannelTransactions, Error> {
     let extra_fee =
-        super::util::weight_to_fee(BUFFER_TX_WEIGHT + CET_EXTRA_WEIGHT, fee_rate_per_vb)?;
+        get_channel_extra_fee(offer_params, accept_params, fee_rate_per_vb, anchor_outputs)?;
     let (fund, funding_script_pubkey) = super::create_fund_transaction_with_fees(
         offer_params,
         accept_params,
@@ -254,9 +387,10 @@ pub fn create_channel_transactions(
         fund_lock_time,
         fund_output_serial_id,
         extra_fee,
+        anchor_outputs,
     )?;
 
-    create_renewal_channel_transactions(
+    create_channel_transactions_from_fund(
         offer_params,
         accept_params,
         offer_revoke_params,
@@ -268,11 +402,15 @@ pub fn create_channel_transactions(
         fee_rate_per_vb,
         cet_lock_time,
         cet_nsequence,
+        anchor_outputs,
     )
 }
 
 /// Returns the transactions necessary to renew the contract of a DLC
-/// channel.
+/// channel. The fee of the buffer transaction and of the CETs is paid from the
+/// fee reserve of the channel, the payouts of the CETs and of the refund
+/// transaction being split as described in [`get_fee_split_payouts`] with
+/// `offer_fee_share` being the percentage of the fee paid by the offer party.
 pub fn create_renewal_channel_transactions(
     offer_params: &PartyParams,
     accept_params: &PartyParams,
@@ -285,9 +423,99 @@ pub fn create_renewal_channel_transactions(
     fee_rate_per_vb: u64,
     cet_lock_time: u32,
     cet_nsequence: Sequence,
+    anchor_outputs: bool,
+    offer_fee_share: u8,
+) -> Result<DlcChannelTransactions, Error> {
+    let (_, fund_output) =
+        super::util::get_output_for_script_pubkey(fund_tx, &funding_script_pubkey.to_v0_p2wsh())
+            .ok_or(Error::InvalidArgument)?;
+    let fee = get_renewal_tx_fee(offer_params, accept_params, fee_rate_per_vb, anchor_outputs)?;
+    let split = |offer_balance, accept_balance| {
+        get_fee_split_payouts(
+            fund_output.value,
+            offer_balance,
+            accept_balance,
+            fee,
+            offer_fee_share,
+        )
+    };
+    let payouts = payouts
+        .iter()
+        .map(|payout| {
+            let (offer, accept) = split(payout.offer, payout.accept)?;
+            Ok(Payout { offer, accept })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let (offer_collateral, accept_collateral) =
+        split(offer_params.collateral, accept_params.collateral)?;
+    let offer_params = PartyParams {
+        collateral: offer_collateral,
+        ..offer_params.clone()
+    };
+    let accept_params = PartyParams {
+        collateral: accept_collateral,
+        ..accept_params.clone()
+    };
+
+    create_channel_transactions_from_fund(
+        &offer_params,
+        &accept_params,
+        offer_revoke_params,
+        accept_revoke_params,
+        fund_tx,
+        funding_script_pubkey,
+        &payouts,
+        refund_lock_time,
+        fee_rate_per_vb,
+        cet_lock_time,
+        cet_nsequence,
+        anchor_outputs,
+    )
+}
+
+/// Returns the fee paid by the buffer transaction and a CET of a renewed
+/// channel contract at the given fee rate, including the value of the anchor
+/// outputs if `anchor_outputs` is set.
+pub fn get_renewal_tx_fee(
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_rate_per_vb: u64,
+    anchor_outputs: bool,
+) -> Result<u64, Error> {
+    let (anchors_weight, anchors_value) = get_anchor_outputs_weight_and_value(
+        get_anchor_script_pubkeys(offer_params, accept_params, anchor_outputs),
+    )?;
+    let outputs_weight =
+        (offer_params.payout_script_pubkey.len() + accept_params.payout_script_pubkey.len()) * 4;
+    let cet_fee = crate::util::weight_to_fee(
+        super::CET_BASE_WEIGHT + outputs_weight + anchors_weight,
+        fee_rate_per_vb,
+    )?;
+
+    get_channel_extra_fee(offer_params, accept_params, fee_rate_per_vb, anchor_outputs)?
+        .checked_add(cet_fee)
+        .and_then(|fee| fee.checked_add(anchors_value))
+        .ok_or(Error::InvalidArgument)
+}
+
+/// Returns the buffer transaction, CETs and refund transaction of a channel
+/// spending the fund output of the given fund transaction.
+fn create_channel_transactions_from_fund(
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    offer_revoke_params: &RevokeParams,
+    accept_revoke_params: &RevokeParams,
+    fund_tx: &Transaction,
+    funding_script_pubkey: &Script,
+    payouts: &[Payout],
+    refund_lock_time: u32,
+    fee_rate_per_vb: u64,
+    cet_lock_time: u32,
+    cet_nsequence: Sequence,
+    anchor_outputs: bool,
 ) -> Result<DlcChannelTransactions, Error> {
     let extra_fee =
-        super::util::weight_to_fee(BUFFER_TX_WEIGHT + CET_EXTRA_WEIGHT, fee_rate_per_vb)?;
+        get_channel_extra_fee(offer_params, accept_params, fee_rate_per_vb, anchor_outputs)?;
 
     let (fund_vout, fund_output) =
         super::util::get_output_for_script_pubkey(fund_tx, &funding_script_pubkey.to_v0_p2wsh())
@@ -307,13 +535,21 @@ pub fn create_renewal_channel_transactions(
 
     let buffer_descriptor = buffer_descriptor(offer_revoke_params, accept_revoke_params);
 
-    let buffer_transaction = create_buffer_transaction(
+    let mut buffer_transaction = create_buffer_transaction(
         &tx_in,
         &buffer_descriptor,
         fund_output.value - extra_fee,
         cet_lock_time,
     );
 
+    if anchor_outputs {
+        super::add_anchor_outputs(
+            &mut buffer_transaction,
+            &offer_params.payout_script_pubkey,
+            &accept_params.payout_script_pubkey,
+        );
+    }
+
     let outpoint = OutPoint {
         txid: buffer_transaction.txid(),
         vout: 0,
@@ -327,6 +563,7 @@ pub fn create_renewal_channel_transactions(
         refund_lock_time,
         cet_lock_time,
         Some(cet_nsequence),
+        anchor_outputs,
     )?;
 
     Ok(DlcChannelTransactions {
@@ -408,7 +645,53 @@ pub fn create_and_sign_punish_buffer_transaction<C: Signing>(
     lock_time: u32,
     fee_rate_per_vb: u64,
 ) -> Result<Transaction, Error> {
-    let descriptor = buffer_descriptor(offer_params, accept_params);
+    let mut tx = create_punish_buffer_transaction(
+        offer_params,
+        accept_params,
+        prev_tx,
+        dest_address,
+        lock_time,
+        fee_rate_per_vb,
+    )?;
+
+    let sigs = [own_sk, counter_publish_sk, counter_revoke_sk]
+        .iter()
+        .map(|sk| {
+            Ok((
+                get_compressed_public_key(secp, sk),
+                sign_punish_buffer_transaction(
+                    secp,
+                    offer_params,
+                    accept_params,
+                    &tx,
+                    prev_tx,
+                    sk,
+                )?,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    finalize_punish_buffer_transaction(offer_params, accept_params, &mut tx, &sigs)?;
+
+    Ok(tx)
+}
+
+/// Returns an unsigned transaction spending the output of the given revoked
+/// buffer transaction to `dest_address`.
+pub fn create_punish_buffer_transaction(
+    offer_params: &RevokeParams,
+    accept_params: &RevokeParams,
+    prev_tx: &Transaction,
+    dest_address: &Address,
+    lock_time: u32,
+    fee_rate_per_vb: u64,
+) -> Result<Transaction, Error> {
+    let buffer_script_pubkey = buffer_descriptor(offer_params, accept_params).script_pubkey();
+    let prev_output = prev_tx
+        .output
+        .first()
+        .filter(|x| x.script_pubkey == buffer_script_pubkey)
+        .ok_or(Error::InvalidArgument)?;
 
     let tx_in = TxIn {
         previous_output: OutPoint {
@@ -426,9 +709,12 @@ pub fn create_and_sign_punish_buffer_transaction<C: Signing>(
     let tx_fee =
         crate::util::weight_to_fee(PUNISH_BUFFER_INPUT_WEIGHT + output_weight, fee_rate_per_vb)?;
 
-    let output_value = prev_tx.output[0].value - tx_fee;
+    let output_value = prev_output
+        .value
+        .checked_sub(tx_fee)
+        .ok_or(Error::InvalidArgument)?;
 
-    let mut tx = Transaction {
+    Ok(Transaction {
         version: super::TX_VERSION,
         lock_time: LockTime::from_consensus(lock_time),
         input: vec![tx_in],
@@ -436,41 +722,71 @@ pub fn create_and_sign_punish_buffer_transaction<C: Signing>(
             value: output_value,
             script_pubkey: dest_address.script_pubkey(),
         }],
-    };
+    })
+}
 
-    let mut sigs = HashMap::new();
+/// Returns a signature for each input of the given transaction punishing the
+/// publication of the revoked buffer transaction `prev_tx`, generated using
+/// `sk`.
+pub fn sign_punish_buffer_transaction<C: Signing>(
+    secp: &Secp256k1<C>,
+    offer_params: &RevokeParams,
+    accept_params: &RevokeParams,
+    punish_tx: &Transaction,
+    prev_tx: &Transaction,
+    sk: &SecretKey,
+) -> Result<Vec<Signature>, Error> {
+    let script_code = buffer_descriptor(offer_params, accept_params).script_code()?;
+
+    punish_tx
+        .input
+        .iter()
+        .enumerate()
+        .map(|(input_index, input)| {
+            let prev_output = prev_tx
+                .output
+                .get(input.previous_output.vout as usize)
+                .ok_or(Error::InvalidArgument)?;
+            Ok(Signature::sighash_all(
+                super::util::get_raw_sig_for_tx_input(
+                    secp,
+                    punish_tx,
+                    input_index,
+                    &script_code,
+                    prev_output.value,
+                    sk,
+                )?,
+            ))
+        })
+        .collect()
+}
 
-    for sk in &[&own_sk, &counter_publish_sk, &counter_revoke_sk] {
-        let pk = PublicKey {
-            inner: SecpPublicKey::from_secret_key(secp, sk),
-            compressed: true,
-        };
+/// Satisfies the inputs of the given transaction punishing the publication of a
+/// revoked buffer transaction, using the given signatures of each public key
+/// for each input.
+pub fn finalize_punish_buffer_transaction(
+    offer_params: &RevokeParams,
+    accept_params: &RevokeParams,
+    punish_tx: &mut Transaction,
+    signatures: &[(PublicKey, Vec<Signature>)],
+) -> Result<(), Error> {
+    let descriptor = buffer_descriptor(offer_params, accept_params);
 
-        let pkh = pk.pubkey_hash().to_raw_hash();
-        sigs.insert(
-            pkh,
-            (
-                pk,
-                bitcoin::ecdsa::Signature {
-                    sig: super::util::get_raw_sig_for_tx_input(
-                        secp,
-                        &tx,
-                        0,
-                        &descriptor.script_code()?,
-                        prev_tx.output[0].value,
-                        sk,
-                    )?,
-                    hash_ty: EcdsaSighashType::All,
-                },
-            ),
-        );
-    }
+    for (input_index, input) in punish_tx.input.iter_mut().enumerate() {
+        let sigs = signatures
+            .iter()
+            .map(|(pk, sigs)| {
+                let sig = sigs.get(input_index).ok_or(Error::InvalidArgument)?;
+                Ok((pk.pubkey_hash().to_raw_hash(), (*pk, *sig)))
+            })
+            .collect::<Result<HashMap<_, _>, Error>>()?;
 
-    descriptor
-        .satisfy(&mut tx.input[0], sigs)
-        .map_err(|_| Error::InvalidArgument)?;
+        descriptor
+            .satisfy(input, sigs)
+            .map_err(|_| Error::InvalidArgument)?;
+    }
 
-    Ok(tx)
+    Ok(())
 }
 
 /// Create and sign a punishment transaction for a revoked settle transaction.
@@ -488,14 +804,54 @@ pub fn create_and_sign_punish_settle_transaction<C: Signing>(
     fee_rate_per_vb: u64,
     is_offer: bool,
 ) -> Result<Transaction, Error> {
-    let (own_params, counter_params) = if is_offer {
-        (offer_params, accept_params)
-    } else {
-        (accept_params, offer_params)
-    };
+    let mut tx = create_punish_settle_transaction(
+        prev_tx,
+        dest_address,
+        lock_time,
+        fee_rate_per_vb,
+        is_offer,
+    )?;
+
+    let sigs = [own_sk, counter_publish_sk, counter_revoke_sk]
+        .iter()
+        .map(|sk| {
+            Ok((
+                get_compressed_public_key(secp, sk),
+                sign_punish_settle_transaction(
+                    secp,
+                    offer_params,
+                    accept_params,
+                    &tx,
+                    prev_tx,
+                    sk,
+                    csv_timelock,
+                    is_offer,
+                )?,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    finalize_punish_settle_transaction(
+        offer_params,
+        accept_params,
+        &mut tx,
+        &sigs,
+        csv_timelock,
+        is_offer,
+    )?;
 
-    let descriptor = settle_descriptor(counter_params, &own_params.own_pk, csv_timelock);
+    Ok(tx)
+}
 
+/// Returns an unsigned transaction spending the output of the counter party in
+/// the given revoked settle transaction to `dest_address`.
+pub fn create_punish_settle_transaction(
+    prev_tx: &Transaction,
+    dest_address: &Address,
+    lock_time: u32,
+    fee_rate_per_vb: u64,
+    is_offer: bool,
+) -> Result<Transaction, Error> {
     let vout = u32::from(is_offer);
 
     let tx_in = TxIn {
@@ -508,7 +864,11 @@ pub fn create_and_sign_punish_settle_transaction<C: Signing>(
         witness: Witness::default(),
     };
 
-    let input_value = prev_tx.output[vout as usize].value;
+    let input_value = prev_tx
+        .output
+        .get(vout as usize)
+        .ok_or(Error::InvalidArgument)?
+        .value;
 
     let dest_script_pk_len = dest_address.script_pubkey().len();
     let var_int_prefix_len = crate::util::compute_var_int_prefix_size(dest_script_pk_len);
@@ -516,52 +876,109 @@ pub fn create_and_sign_punish_settle_transaction<C: Signing>(
     let tx_fee =
         crate::util::weight_to_fee(PUNISH_SETTLE_INPUT_WEIGHT + output_weight, fee_rate_per_vb)?;
 
-    let mut tx = Transaction {
+    Ok(Transaction {
         version: super::TX_VERSION,
         lock_time: LockTime::from_consensus(lock_time),
         input: vec![tx_in],
         output: vec![TxOut {
-            value: input_value - tx_fee,
+            value: input_value
+                .checked_sub(tx_fee)
+                .ok_or(Error::InvalidArgument)?,
             script_pubkey: dest_address.script_pubkey(),
         }],
-    };
+    })
+}
 
-    let mut sigs = HashMap::new();
+/// Returns a signature for the given transaction punishing the publication of
+/// the revoked settle transaction `prev_tx`, generated using `sk`.
+pub fn sign_punish_settle_transaction<C: Signing>(
+    secp: &Secp256k1<C>,
+    offer_params: &RevokeParams,
+    accept_params: &RevokeParams,
+    punish_tx: &Transaction,
+    prev_tx: &Transaction,
+    sk: &SecretKey,
+    csv_timelock: u32,
+    is_offer: bool,
+) -> Result<Signature, Error> {
+    let descriptor =
+        get_punish_settle_descriptor(offer_params, accept_params, csv_timelock, is_offer);
+    let input_value = prev_tx
+        .output
+        .get(u32::from(is_offer) as usize)
+        .ok_or(Error::InvalidArgument)?
+        .value;
+
+    Ok(Signature::sighash_all(
+        super::util::get_raw_sig_for_tx_input(
+            secp,
+            punish_tx,
+            0,
+            &descriptor.script_code()?,
+            input_value,
+            sk,
+        )?,
+    ))
+}
 
-    for sk in &[&own_sk, &counter_publish_sk, &counter_revoke_sk] {
-        let pk = PublicKey {
-            inner: SecpPublicKey::from_secret_key(secp, sk),
-            compressed: true,
-        };
-        sigs.insert(
-            pk,
-            Signature::sighash_all(super::util::get_raw_sig_for_tx_input(
-                secp,
-                &tx,
-                0,
-                &descriptor.script_code()?,
-                input_value,
-                sk,
-            )?),
-        );
-    }
+/// Satisfies the input of the given transaction punishing the publication of a
+/// revoked settle transaction, using the given signatures.
+pub fn finalize_punish_settle_transaction(
+    offer_params: &RevokeParams,
+    accept_params: &RevokeParams,
+    punish_tx: &mut Transaction,
+    signatures: &[(PublicKey, Signature)],
+    csv_timelock: u32,
+    is_offer: bool,
+) -> Result<(), Error> {
+    let descriptor =
+        get_punish_settle_descriptor(offer_params, accept_params, csv_timelock, is_offer);
+    let sigs = signatures.iter().cloned().collect::<HashMap<_, _>>();
+    let input = punish_tx.input.get_mut(0).ok_or(Error::InvalidArgument)?;
 
     descriptor
-        .satisfy(&mut tx.input[0], sigs)
+        .satisfy(input, sigs)
         .map_err(|_| Error::InvalidArgument)?;
 
-    Ok(tx)
+    Ok(())
+}
+
+fn get_punish_settle_descriptor(
+    offer_params: &RevokeParams,
+    accept_params: &RevokeParams,
+    csv_timelock: u32,
+    is_offer: bool,
+) -> Descriptor<PublicKey> {
+    let (own_params, counter_params) = if is_offer {
+        (offer_params, accept_params)
+    } else {
+        (accept_params, offer_params)
+    };
+
+    settle_descriptor(counter_params, &own_params.own_pk, csv_timelock)
+}
+
+fn get_compressed_public_key<C: Signing>(secp: &Secp256k1<C>, sk: &SecretKey) -> PublicKey {
+    PublicKey {
+        inner: SecpPublicKey::from_secret_key(secp, sk),
+        compressed: true,
+    }
 }
 
-/// Create a transaction for collaboratively closing a channel.
+/// Create a transaction for collaboratively closing a channel, paying
+/// `offer_payout` and `accept_payout` to the offer and accept party
+/// respectively, with the fee reserve of the channel split between them as
+/// described in [`get_fee_split_payouts`].
 pub fn create_collaborative_close_transaction(
     offer_params: &PartyParams,
     offer_payout: u64,
     accept_params: &PartyParams,
     accept_payout: u64,
     fund_outpoint: OutPoint,
-    _fund_output_amount: u64,
-) -> Transaction {
+    fund_output_amount: u64,
+    fee_rate_per_vb: u64,
+    offer_fee_share: u8,
+) -> Result<Transaction, Error> {
     let input = TxIn {
         previous_output: fund_outpoint,
         witness: Witness::default(),
@@ -569,14 +986,22 @@ pub fn create_collaborative_close_transaction(
         sequence: crate::util::DISABLE_LOCKTIME,
     };
 
-    //TODO(tibo): add fee re-payment
+    let fee = get_collaborative_close_tx_fee(offer_params, accept_params, fee_rate_per_vb)?;
+    let (offer_value, accept_value) = get_fee_split_payouts(
+        fund_output_amount,
+        offer_payout,
+        accept_payout,
+        fee,
+        offer_fee_share,
+    )?;
+
     let offer_output = TxOut {
-        value: offer_payout,
+        value: offer_value,
         script_pubkey: offer_params.payout_script_pubkey.clone(),
     };
 
     let accept_output = TxOut {
-        value: accept_payout,
+        value: accept_value,
         script_pubkey: accept_params.payout_script_pubkey.clone(),
     };
 
@@ -588,12 +1013,88 @@ pub fn create_collaborative_close_transaction(
 
     output = crate::util::discard_dust(output, crate::DUST_LIMIT);
 
-    Transaction {
+    Ok(Transaction {
         version: crate::TX_VERSION,
         lock_time: LockTime::ZERO,
         input: vec![input],
         output,
+    })
+}
+
+/// Returns the fee paid by the splicing party for spending the fund output of a
+/// channel and creating the new one in a splice transaction, not including the
+/// cost of its own inputs and change output.
+pub fn get_splice_tx_base_fee(fee_rate_per_vb: u64) -> Result<u64, Error> {
+    crate::util::weight_to_fee(
+        SETTLE_INPUT_WEIGHT + super::FUND_OUTPUT_WEIGHT,
+        fee_rate_per_vb,
+    )
+}
+
+/// Returns a splice transaction spending the fund output of a channel at
+/// `fund_outpoint` together with the given `inputs` of the splicing party, whose
+/// values add up to `input_amount`. The transaction creates a new fund output of
+/// `new_fund_output_value`, locked by the same `funding_script_pubkey` as the
+/// previous one, and a change output paying what remains to the splicing party
+/// minus the fee of the transaction. The new fund output is always the first
+/// output of the transaction, and the change output is discarded if below the
+/// dust limit.
+pub fn create_splice_transaction(
+    fund_outpoint: OutPoint,
+    fund_output_value: u64,
+    funding_script_pubkey: &Script,
+    new_fund_output_value: u64,
+    inputs: &[crate::TxInputInfo],
+    input_amount: u64,
+    change_script_pubkey: &Script,
+    fee_rate_per_vb: u64,
+) -> Result<Transaction, Error> {
+    let weight = super::get_inputs_weight(inputs)?
+        .checked_add(SETTLE_INPUT_WEIGHT + super::FUND_OUTPUT_WEIGHT)
+        .and_then(|w| w.checked_add(change_script_pubkey.len() * 4 + 36))
+        .ok_or(Error::InvalidArgument)?;
+    let fee = crate::util::weight_to_fee(weight, fee_rate_per_vb)?;
+
+    let change_value = fund_output_value
+        .checked_add(input_amount)
+        .and_then(|total| total.checked_sub(new_fund_output_value))
+        .and_then(|remaining| remaining.checked_sub(fee))
+        .ok_or(Error::InvalidArgument)?;
+
+    let mut input = vec![TxIn {
+        previous_output: fund_outpoint,
+        witness: Witness::default(),
+        script_sig: ScriptBuf::default(),
+        sequence: crate::util::ENABLE_RBF,
+    }];
+
+    let mut ordered_inputs = inputs.iter().collect::<Vec<_>>();
+    ordered_inputs.sort_by_key(|x| x.serial_id);
+    input.extend(ordered_inputs.into_iter().map(|x| TxIn {
+        previous_output: x.outpoint,
+        witness: Witness::default(),
+        script_sig: crate::util::redeem_script_to_script_sig(&x.redeem_script),
+        sequence: crate::util::ENABLE_RBF,
+    }));
+
+    let mut output = vec![TxOut {
+        value: new_fund_output_value,
+        script_pubkey: funding_script_pubkey.to_v0_p2wsh(),
+    }];
+
+    if change_value >= crate::DUST_LIMIT {
+        output.push(TxOut {
+            value: change_value,
+            script_pubkey: change_script_pubkey.to_owned(),
+        });
     }
+
+    Ok(Transaction {
+        version: crate::TX_VERSION,
+        lock_time: LockTime::ZERO,
+        input,
+        output,
+    })
 }
 
 /// Returns a descriptor for a buffer transaction.
@@ -827,6 +1328,8 @@ mod tests {
             0,
             200020000,
             FEE_RATE_PER_VB,
+            50,
+            None,
         )
         .unwrap();
 
@@ -973,4 +1476,226 @@ mod tests {
         )
         .expect("the signature to be valid");
     }
+
+    #[test]
+    fn fee_split_payouts_add_up_test() {
+        let fund_output_value = 200020001;
+        let fee = 1001;
+
+        for offer_fee_share in [0, 33, 50, 100] {
+            let (offer_value, accept_value) =
+                get_fee_split_payouts(fund_output_value, 150000000, 50000000, fee, offer_fee_share)
+                    .unwrap();
+            let offer_fee = fee * offer_fee_share as u64 / 100;
+
+            assert_eq!(fund_output_value - fee, offer_value + accept_value);
+            assert_eq!(150000000 + 10000 - offer_fee, offer_value);
+        }
+    }
+
+    #[test]
+    fn fee_split_payouts_invalid_test() {
+        // Fee share above 100%.
+        assert!(get_fee_split_payouts(200020000, 100000000, 100000000, 1000, 101).is_err());
+        // Balances above the fund output value.
+        assert!(get_fee_split_payouts(200020000, 200000000, 100000, 1000, 50).is_err());
+        // Fee above the fee reserve.
+        assert!(get_fee_split_payouts(200020000, 100000000, 100000000, 30000, 50).is_err());
+        // Fee above the share of the reserve and balance of the offer party.
+        assert!(get_fee_split_payouts(200020000, 0, 200000000, 15000, 100).is_err());
+    }
+
+    #[test]
+    fn settle_transaction_outputs_add_up_test() {
+        let offer_params = RevokePrivateParams::new(Network::Regtest).public_params(SECP256K1);
+        let accept_params = RevokePrivateParams::new(Network::Regtest).public_params(SECP256K1);
+        let fund_output_value = 200020000;
+
+        let settle_tx = create_settle_transaction(
+            &TxIn::default(),
+            &offer_params,
+            &accept_params,
+            120000000,
+            80000000,
+            100,
+            0,
+            fund_output_value,
+            FEE_RATE_PER_VB,
+            100,
+            None,
+        )
+        .unwrap();
+
+        let fee = get_settle_tx_fee(FEE_RATE_PER_VB, None).unwrap();
+        assert_eq!(
+            fund_output_value - fee,
+            settle_tx.output.iter().map(|x| x.value).sum::<u64>()
+        );
+        assert_eq!(120000000 + 10000 - fee, settle_tx.output[0].value);
+    }
+
+    #[test]
+    fn renewal_transactions_outputs_add_up_test() {
+        let party_params = |collateral| PartyParams {
+            fund_pubkey: SecpPublicKey::from_secret_key(
+                SECP256K1,
+                &SecretKey::new(&mut thread_rng()),
+            ),
+            change_script_pubkey: Address::from_str("bcrt1qlgmznucxpdkp5k3ktsct7eh6qrc4tju7ktjukn")
+                .unwrap()
+                .assume_checked()
+                .script_pubkey(),
+            change_serial_id: 0,
+            payout_script_pubkey: Address::from_str("bcrt1qlgmznucxpdkp5k3ktsct7eh6qrc4tju7ktjukn")
+                .unwrap()
+                .assume_checked()
+                .script_pubkey(),
+            payout_serial_id: 1,
+            inputs: vec![crate::TxInputInfo {
+                outpoint: OutPoint::default(),
+                max_witness_len: crate::P2WPKH_WITNESS_SIZE,
+                redeem_script: ScriptBuf::new(),
+                serial_id: 0,
+            }],
+            input_amount: collateral + 100000,
+            collateral,
+        };
+        let offer_params = party_params(100000000);
+        let accept_params = party_params(100000000);
+        let offer_revoke_params =
+            RevokePrivateParams::new(Network::Regtest).public_params(SECP256K1);
+        let accept_revoke_params =
+            RevokePrivateParams::new(Network::Regtest).public_params(SECP256K1);
+        let payouts = vec![
+            Payout {
+                offer: 200000000,
+                accept: 0,
+            },
+            Payout {
+                offer: 0,
+                accept: 200000000,
+            },
+        ];
+
+        let channel_txs = create_channel_transactions(
+            &offer_params,
+            &accept_params,
+            &offer_revoke_params,
+            &accept_revoke_params,
+            &payouts,
+            100,
+            FEE_RATE_PER_VB,
+            0,
+            100,
+            0,
+            Sequence(100),
+            false,
+        )
+        .unwrap();
+        let fund_tx = &channel_txs.dlc_transactions.fund;
+        let funding_script_pubkey = &channel_txs.dlc_transactions.funding_script_pubkey;
+        let fund_output_value = channel_txs.dlc_transactions.get_fund_output().value;
+
+        let renewal_txs = create_renewal_channel_transactions(
+            &offer_params,
+            &accept_params,
+            &offer_revoke_params,
+            &accept_revoke_params,
+            fund_tx,
+            funding_script_pubkey,
+            &payouts,
+            100,
+            FEE_RATE_PER_VB,
+            100,
+            Sequence(100),
+            false,
+            50,
+        )
+        .unwrap();
+
+        let fee =
+            get_renewal_tx_fee(&offer_params, &accept_params, FEE_RATE_PER_VB, false).unwrap();
+        for tx in renewal_txs
+            .dlc_transactions
+            .cets
+            .iter()
+            .chain(std::iter::once(&renewal_txs.dlc_transactions.refund))
+        {
+            assert_eq!(
+                fund_output_value - fee,
+                tx.output.iter().map(|x| x.value).sum::<u64>()
+            );
+        }
+        assert!(renewal_txs.buffer_transaction.output[0].value > fund_output_value - fee);
+    }
+
+    #[test]
+    fn splice_transaction_test() {
+        let funding_script_pubkey = crate::make_funding_redeemscript(
+            &SecpPublicKey::from_secret_key(SECP256K1, &SecretKey::new(&mut thread_rng())),
+            &SecpPublicKey::from_secret_key(SECP256K1, &SecretKey::new(&mut thread_rng())),
+        );
+        let change_script_pubkey =
+            Address::from_str("bcrt1qlgmznucxpdkp5k3ktsct7eh6qrc4tju7ktjukn")
+                .unwrap()
+                .assume_checked()
+                .script_pubkey();
+        let inputs = vec![crate::TxInputInfo {
+            outpoint: OutPoint::default(),
+            max_witness_len: crate::P2WPKH_WITNESS_SIZE,
+            redeem_script: ScriptBuf::new(),
+            serial_id: 0,
+        }];
+        let fund_output_value = 200020000;
+
+        let splice_in_tx = create_splice_transaction(
+            OutPoint::default(),
+            fund_output_value,
+            &funding_script_pubkey,
+            fund_output_value + 50000000,
+            &inputs,
+            60000000,
+            &change_script_pubkey,
+            FEE_RATE_PER_VB,
+        )
+        .unwrap();
+
+        assert_eq!(2, splice_in_tx.input.len());
+        assert_eq!(
+            funding_script_pubkey.to_v0_p2wsh(),
+            splice_in_tx.output[0].script_pubkey
+        );
+        assert_eq!(fund_output_value + 50000000, splice_in_tx.output[0].value);
+        let splice_in_fee =
+            fund_output_value + 60000000 - splice_in_tx.output.iter().map(|x| x.value).sum::<u64>();
+        assert!(splice_in_fee > 0 && splice_in_fee < 10000000);
+
+        let splice_out_tx = create_splice_transaction(
+            OutPoint::default(),
+            fund_output_value,
+            &funding_script_pubkey,
+            fund_output_value - 50000000,
+            &[],
+            0,
+            &change_script_pubkey,
+            FEE_RATE_PER_VB,
+        )
+        .unwrap();
+
+        assert_eq!(1, splice_out_tx.input.len());
+        assert_eq!(2, splice_out_tx.output.len());
+        assert!(splice_out_tx.output[1].value < 50000000);
+
+        create_splice_transaction(
+            OutPoint::default(),
+            fund_output_value,
+            &funding_script_pubkey,
+            fund_output_value + 50000000,
+            &inputs,
+            40000000,
+            &change_script_pubkey,
+            FEE_RATE_PER_VB,
+        )
+        .expect_err("the inputs to be insufficient");
+    }
 }
@@ -40,17 +40,19 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 pub mod channel;
+pub mod schnorr_adaptor;
 pub mod secp_utils;
+pub mod taproot;
 pub mod util;
 
 /// Minimum value that can be included in a transaction output. Under this value,
 /// outputs are discarded
 /// See: https://github.com/discreetlogcontracts/dlcspecs/blob/master/Transactions.md#change-outputs
-const DUST_LIMIT: u64 = 1000;
+pub const DUST_LIMIT: u64 = 1000;
 
 /// The transaction version
 /// See: https://github.com/discreetlogcontracts/dlcspecs/blob/master/Transactions.md#funding-transaction
-const TX_VERSION: i32 = 2;
+pub const TX_VERSION: i32 = 2;
 
 /// The base weight of a fund transaction
 /// See: https://github.com/discreetlogcontracts/dlcspecs/blob/master/Transactions.md#fees
@@ -60,6 +62,11 @@ const FUND_TX_BASE_WEIGHT: usize = 214;
 /// See: https://github.com/discreetlogcontracts/dlcspecs/blob/master/Transactions.md#fees
 const CET_BASE_WEIGHT: usize = 500;
 
+/// The weight of a CET spending a taproot fund output excluding payout outputs.
+/// Computed from [`CET_BASE_WEIGHT`] by replacing the 2-of-2 multisig witness
+/// (220) with a key path spend witness (66).
+const TAPROOT_CET_BASE_WEIGHT: usize = 346;
+
 /// The base weight of a transaction input computed as: (outpoint(36) + sequence(4) + scriptPubKeySize(1)) * 4
 /// See: <https://github.com/discreetlogcontracts/dlcspecs/blob/master/Transactions.md#fees>
 const TX_INPUT_BASE_WEIGHT: usize = 164;
@@ -68,6 +75,21 @@ const TX_INPUT_BASE_WEIGHT: usize = 164;
 /// See: <https://github.com/discreetlogcontracts/dlcspecs/blob/master/Transactions.md#fees>
 pub const P2WPKH_WITNESS_SIZE: usize = 107;
 
+/// The witness size of a P2TR key path spend using the default sighash type,
+/// computed as: witness_items_count(1) + signature_length(1) + signature(64)
+pub const P2TR_KEY_PATH_WITNESS_SIZE: usize = 66;
+
+/// The value of an anchor output, same as the one used for anchor outputs of
+/// lightning commitment transactions.
+pub const ANCHOR_OUTPUT_VALUE: u64 = 330;
+
+/// The weight of a fund output computed as: (nValue(8) + scriptPubkeyLen(1) + scriptPubkey(34)) * 4
+const FUND_OUTPUT_WEIGHT: usize = 172;
+
+/// The weight of the fixed fields of a segwit transaction computed as:
+/// (version(4) + input_count(1) + output_count(1) + lock_time(4)) * 4 + segwit_marker_and_flag(2)
+const SEGWIT_TX_BASE_WEIGHT: usize = 42;
+
 macro_rules! checked_add {
     ($a: expr, $b: expr) => {
         $a.checked_add($b).ok_or(Error::InvalidArgument)
@@ -113,6 +135,72 @@ pub struct EnumerationPayout {
     pub payout: Payout,
 }
 
+/// The type of output used to lock the collateral of both parties in the fund
+/// transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "use-serde", derive(Serialize, Deserialize))]
+pub enum FundingOutputType {
+    /// A P2WSH output with a 2-of-2 multisig script.
+    P2wsh,
+    /// A P2TR output whose internal key is the MuSig2 aggregate of the fund
+    /// public keys, spent through the key path.
+    Taproot,
+}
+
+impl Default for FundingOutputType {
+    fn default() -> Self {
+        FundingOutputType::P2wsh
+    }
+}
+
+impl FundingOutputType {
+    fn cet_base_weight(&self) -> usize {
+        match self {
+            FundingOutputType::P2wsh => CET_BASE_WEIGHT,
+            FundingOutputType::Taproot => TAPROOT_CET_BASE_WEIGHT,
+        }
+    }
+}
+
+/// Options for the construction of the transactions of a DLC.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct DlcTransactionsOptions {
+    /// The type of output used to lock the collateral in the fund transaction.
+    pub funding_output_type: FundingOutputType,
+    /// Whether to add to the CETs and refund transaction an anchor output for
+    /// each party, paying to its payout script pubkey, enabling them to bump
+    /// the fee of these transactions using CPFP.
+    pub anchor_outputs: bool,
+    /// Whether the accept party contributes no inputs, collateral or change
+    /// output to the fund transaction, in which case the offer party pays the
+    /// fees of both parties.
+    #[cfg_attr(feature = "use-serde", serde(default))]
+    pub single_funded: bool,
+}
+
+impl DlcTransactionsOptions {
+    fn anchor_outputs_weight(&self, anchor_script_pubkey: &Script) -> Result<usize, Error> {
+        if self.anchor_outputs {
+            get_anchor_output_weight(anchor_script_pubkey)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn anchor_outputs_value(&self) -> u64 {
+        if self.anchor_outputs {
+            ANCHOR_OUTPUT_VALUE
+        } else {
+            0
+        }
+    }
+}
+
 /// Contains the necessary transactions for establishing a DLC
 #[derive(Clone)]
 pub struct DlcTransactions {
@@ -125,23 +213,40 @@ pub struct DlcTransactions {
     /// case of an oracle misbehavior
     pub refund: Transaction,
 
-    /// The script pubkey of the fund output in the fund transaction
+    /// The script of the fund output in the fund transaction. For P2WSH
+    /// funding this is the 2-of-2 multisig witness script, for taproot funding
+    /// the P2TR script pubkey of the fund output.
     pub funding_script_pubkey: ScriptBuf,
 }
 
 impl DlcTransactions {
+    /// Get the type of the fund output in the fund transaction
+    pub fn get_funding_output_type(&self) -> FundingOutputType {
+        if self.funding_script_pubkey.is_v1_p2tr() {
+            FundingOutputType::Taproot
+        } else {
+            FundingOutputType::P2wsh
+        }
+    }
+
+    /// Get the script pubkey of the fund output in the fund transaction
+    pub fn get_fund_output_script_pubkey(&self) -> ScriptBuf {
+        match self.get_funding_output_type() {
+            FundingOutputType::P2wsh => self.funding_script_pubkey.to_v0_p2wsh(),
+            FundingOutputType::Taproot => self.funding_script_pubkey.clone(),
+        }
+    }
+
     /// Get the fund output in the fund transaction
     pub fn get_fund_output(&self) -> &TxOut {
-        let v0_witness_fund_script = self.funding_script_pubkey.to_v0_p2wsh();
-        util::get_output_for_script_pubkey(&self.fund, &v0_witness_fund_script)
+        util::get_output_for_script_pubkey(&self.fund, &self.get_fund_output_script_pubkey())
             .unwrap()
             .1
     }
 
     /// Get the fund output in the fund transaction
     pub fn get_fund_output_index(&self) -> usize {
-        let v0_witness_fund_script = self.funding_script_pubkey.to_v0_p2wsh();
-        util::get_output_for_script_pubkey(&self.fund, &v0_witness_fund_script)
+        util::get_output_for_script_pubkey(&self.fund, &self.get_fund_output_script_pubkey())
             .unwrap()
             .0
     }
@@ -282,50 +387,36 @@ impl PartyParams {
         fee_rate_per_vb: u64,
         extra_fee: u64,
     ) -> Result<(TxOut, u64, u64), Error> {
-        let mut inputs_weight: usize = 0;
-
-        for w in &self.inputs {
-            let script_weight = util::redeem_script_to_script_sig(&w.redeem_script)
-                .len()
-                .checked_mul(4)
-                .ok_or(Error::InvalidArgument)?;
-            inputs_weight = checked_add!(
-                inputs_weight,
-                TX_INPUT_BASE_WEIGHT,
-                script_weight,
-                w.max_witness_len
-            )?;
-        }
-
-        // Value size + script length var_int + ouput script pubkey size
-        let change_size = self.change_script_pubkey.len();
-        // Change size is scaled by 4 from vBytes to weight units
-        let change_weight = change_size.checked_mul(4).ok_or(Error::InvalidArgument)?;
+        self.get_change_output_and_fees_with_options(
+            fee_rate_per_vb,
+            extra_fee,
+            &DlcTransactionsOptions::default(),
+        )
+    }
 
+    /// Same as [`PartyParams::get_change_output_and_fees`] but taking into
+    /// account the size of the witness required to spend the given type of fund
+    /// output when computing the CET or refund transaction fee. If anchor
+    /// outputs are enabled, the returned CET or refund fee also covers the
+    /// value of the party's anchor output.
+    pub(crate) fn get_change_output_and_fees_with_options(
+        &self,
+        fee_rate_per_vb: u64,
+        extra_fee: u64,
+        options: &DlcTransactionsOptions,
+    ) -> Result<(TxOut, u64, u64), Error> {
         // Base weight (nLocktime, nVersion, ...) is distributed among parties
         // independently of inputs contributed
         let this_party_fund_base_weight = FUND_TX_BASE_WEIGHT / 2;
 
         let total_fund_weight = checked_add!(
             this_party_fund_base_weight,
-            inputs_weight,
-            change_weight,
-            36
+            self.get_inputs_weight()?,
+            self.get_change_output_weight()?
         )?;
         let fund_fee = util::weight_to_fee(total_fund_weight, fee_rate_per_vb)?;
 
-        // Base weight (nLocktime, nVersion, funding input ...) is distributed
-        // among parties independently of output types
-        let this_party_cet_base_weight = CET_BASE_WEIGHT / 2;
-
-        // size of the payout script pubkey scaled by 4 from vBytes to weight units
-        let output_spk_weight = self
-            .payout_script_pubkey
-            .len()
-            .checked_mul(4)
-            .ok_or(Error::InvalidArgument)?;
-        let total_cet_weight = checked_add!(this_party_cet_base_weight, output_spk_weight)?;
-        let cet_or_refund_fee = util::weight_to_fee(total_cet_weight, fee_rate_per_vb)?;
+        let cet_or_refund_fee = self.get_cet_or_refund_fee(fee_rate_per_vb, options)?;
         let required_input_funds =
             checked_add!(self.collateral, fund_fee, cet_or_refund_fee, extra_fee)?;
         if self.input_amount < required_input_funds {
@@ -340,6 +431,66 @@ impl PartyParams {
         Ok((change_output, fund_fee, cet_or_refund_fee))
     }
 
+    /// Returns the fees that a party contributing no inputs nor change output
+    /// to the fund transaction is required to pay for the fund transaction and
+    /// the cet or refund transaction.
+    fn get_unfunded_fees(
+        &self,
+        fee_rate_per_vb: u64,
+        options: &DlcTransactionsOptions,
+    ) -> Result<(u64, u64), Error> {
+        let fund_fee = util::weight_to_fee(FUND_TX_BASE_WEIGHT / 2, fee_rate_per_vb)?;
+        let cet_or_refund_fee = self.get_cet_or_refund_fee(fee_rate_per_vb, options)?;
+        Ok((fund_fee, cet_or_refund_fee))
+    }
+
+    fn get_inputs_weight(&self) -> Result<usize, Error> {
+        get_inputs_weight(&self.inputs)
+    }
+
+    fn get_change_output_weight(&self) -> Result<usize, Error> {
+        get_change_output_weight(&self.change_script_pubkey)
+    }
+
+    /// Returns the change output of a party whose fees are paid by a third
+    /// party, which only needs to cover its collateral.
+    fn get_change_output_without_fees(&self) -> Result<TxOut, Error> {
+        if self.input_amount < self.collateral {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(TxOut {
+            value: self.input_amount - self.collateral,
+            script_pubkey: self.change_script_pubkey.clone(),
+        })
+    }
+
+    fn get_cet_or_refund_fee(
+        &self,
+        fee_rate_per_vb: u64,
+        options: &DlcTransactionsOptions,
+    ) -> Result<u64, Error> {
+        // Base weight (nLocktime, nVersion, funding input ...) is distributed
+        // among parties independently of output types
+        let this_party_cet_base_weight = options.funding_output_type.cet_base_weight() / 2;
+
+        // size of the payout script pubkey scaled by 4 from vBytes to weight units
+        let output_spk_weight = self
+            .payout_script_pubkey
+            .len()
+            .checked_mul(4)
+            .ok_or(Error::InvalidArgument)?;
+        let total_cet_weight = checked_add!(
+            this_party_cet_base_weight,
+            output_spk_weight,
+            options.anchor_outputs_weight(&self.payout_script_pubkey)?
+        )?;
+        checked_add!(
+            util::weight_to_fee(total_cet_weight, fee_rate_per_vb)?,
+            options.anchor_outputs_value()
+        )
+    }
+
     fn get_unsigned_tx_inputs_and_serial_ids(&self, sequence: Sequence) -> (Vec<TxIn>, Vec<u64>) {
         let mut tx_ins = Vec::with_capacity(self.inputs.len());
         let mut serial_ids = Vec::with_capacity(self.inputs.len());
@@ -359,6 +510,47 @@ impl PartyParams {
     }
 }
 
+/// Contains the parameters of a third party paying the fees of the fund, CET
+/// and refund transactions of a contract, by contributing inputs and a change
+/// output to the fund transaction.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "use-serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct FeePayerParams {
+    /// The inputs used to pay the fees.
+    pub inputs: Vec<TxInputInfo>,
+    /// The sum of the inputs values.
+    pub input_amount: u64,
+    /// An address to receive change
+    pub change_script_pubkey: ScriptBuf,
+    /// Id used to order fund outputs
+    pub change_serial_id: u64,
+}
+
+fn get_inputs_weight(inputs: &[TxInputInfo]) -> Result<usize, Error> {
+    let mut inputs_weight: usize = 0;
+
+    for w in inputs {
+        inputs_weight = checked_add!(
+            inputs_weight,
+            util::get_input_weight(&w.redeem_script, w.max_witness_len)?
+        )?;
+    }
+
+    Ok(inputs_weight)
+}
+
+fn get_change_output_weight(change_script_pubkey: &Script) -> Result<usize, Error> {
+    // Value size + script length var_int + ouput script pubkey size
+    let change_size = change_script_pubkey.len();
+    // Change size is scaled by 4 from vBytes to weight units
+    let change_weight = change_size.checked_mul(4).ok_or(Error::InvalidArgument)?;
+    checked_add!(change_weight, 36)
+}
+
 /// Create the transactions for a DLC contract based on the provided parameters
 pub fn create_dlc_transactions(
     offer_params: &PartyParams,
@@ -377,6 +569,7 @@ pub fn create_dlc_transactions(
         fund_lock_time,
         fund_output_serial_id,
         0,
+        false,
     )?;
     let fund_outpoint = OutPoint {
         txid: fund_tx.txid(),
@@ -392,6 +585,164 @@ pub fn create_dlc_transactions(
         refund_lock_time,
         cet_lock_time,
         None,
+        false,
+    )?;
+
+    Ok(DlcTransactions {
+        fund: fund_tx,
+        cets,
+        refund: refund_tx,
+        funding_script_pubkey,
+    })
+}
+
+/// Create the transactions for a DLC contract based on the provided parameters,
+/// locking the collateral in a fund output of the given type.
+pub fn create_dlc_transactions_with_funding_output_type<C: Verification>(
+    secp: &Secp256k1<C>,
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    payouts: &[Payout],
+    refund_lock_time: u32,
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+    cet_lock_time: u32,
+    fund_output_serial_id: u64,
+    funding_output_type: FundingOutputType,
+) -> Result<DlcTransactions, Error> {
+    create_dlc_transactions_with_options(
+        secp,
+        offer_params,
+        accept_params,
+        payouts,
+        refund_lock_time,
+        fee_rate_per_vb,
+        fund_lock_time,
+        cet_lock_time,
+        fund_output_serial_id,
+        &DlcTransactionsOptions {
+            funding_output_type,
+            ..Default::default()
+        },
+    )
+}
+
+/// Create the transactions for a DLC contract based on the provided parameters
+/// and options.
+pub fn create_dlc_transactions_with_options<C: Verification>(
+    secp: &Secp256k1<C>,
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    payouts: &[Payout],
+    refund_lock_time: u32,
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+    cet_lock_time: u32,
+    fund_output_serial_id: u64,
+    options: &DlcTransactionsOptions,
+) -> Result<DlcTransactions, Error> {
+    create_dlc_transactions_internal(
+        secp,
+        offer_params,
+        accept_params,
+        None,
+        payouts,
+        refund_lock_time,
+        fee_rate_per_vb,
+        fund_lock_time,
+        cet_lock_time,
+        fund_output_serial_id,
+        options,
+    )
+}
+
+/// Same as [`create_dlc_transactions_with_options`] but with a third party
+/// paying all the fees of the fund, CET and refund transactions. The offer and
+/// accept parties then only contribute their collateral.
+pub fn create_dlc_transactions_with_fee_payer<C: Verification>(
+    secp: &Secp256k1<C>,
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_payer_params: &FeePayerParams,
+    payouts: &[Payout],
+    refund_lock_time: u32,
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+    cet_lock_time: u32,
+    fund_output_serial_id: u64,
+    options: &DlcTransactionsOptions,
+) -> Result<DlcTransactions, Error> {
+    create_dlc_transactions_internal(
+        secp,
+        offer_params,
+        accept_params,
+        Some(fee_payer_params),
+        payouts,
+        refund_lock_time,
+        fee_rate_per_vb,
+        fund_lock_time,
+        cet_lock_time,
+        fund_output_serial_id,
+        options,
+    )
+}
+
+fn create_dlc_transactions_internal<C: Verification>(
+    secp: &Secp256k1<C>,
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_payer_params: Option<&FeePayerParams>,
+    payouts: &[Payout],
+    refund_lock_time: u32,
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+    cet_lock_time: u32,
+    fund_output_serial_id: u64,
+    options: &DlcTransactionsOptions,
+) -> Result<DlcTransactions, Error> {
+    let (funding_script_pubkey, fund_output_script_pubkey) = get_funding_script_pubkeys(
+        secp,
+        offer_params,
+        accept_params,
+        options.funding_output_type,
+    )?;
+    let fund_tx = match fee_payer_params {
+        Some(fee_payer_params) => create_fund_transaction_with_fee_payer(
+            offer_params,
+            accept_params,
+            fee_payer_params,
+            fee_rate_per_vb,
+            fund_lock_time,
+            fund_output_serial_id,
+            &fund_output_script_pubkey,
+            options,
+        )?,
+        None => create_fund_transaction_for_output(
+            offer_params,
+            accept_params,
+            fee_rate_per_vb,
+            fund_lock_time,
+            fund_output_serial_id,
+            0,
+            &fund_output_script_pubkey,
+            options,
+        )?,
+    };
+    let fund_outpoint = OutPoint {
+        txid: fund_tx.txid(),
+        vout: util::get_output_for_script_pubkey(&fund_tx, &fund_output_script_pubkey)
+            .expect("to find the funding script pubkey")
+            .0 as u32,
+    };
+    let (cets, refund_tx) = create_cets_and_refund_tx(
+        offer_params,
+        accept_params,
+        fund_outpoint,
+        payouts,
+        refund_lock_time,
+        cet_lock_time,
+        None,
+        options.anchor_outputs,
     )?;
 
     Ok(DlcTransactions {
@@ -402,6 +753,233 @@ pub fn create_dlc_transactions(
     })
 }
 
+/// Returns the funding script pubkey and the script pubkey of the fund output
+/// for the given parties and type of fund output.
+fn get_funding_script_pubkeys<C: Verification>(
+    secp: &Secp256k1<C>,
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    funding_output_type: FundingOutputType,
+) -> Result<(ScriptBuf, ScriptBuf), Error> {
+    match funding_output_type {
+        FundingOutputType::P2wsh => {
+            let funding_script_pubkey =
+                make_funding_redeemscript(&offer_params.fund_pubkey, &accept_params.fund_pubkey);
+            let fund_output_script_pubkey = funding_script_pubkey.to_v0_p2wsh();
+            Ok((funding_script_pubkey, fund_output_script_pubkey))
+        }
+        FundingOutputType::Taproot => {
+            let funding_script_pubkey = taproot::make_taproot_funding_script_pubkey(
+                secp,
+                &offer_params.fund_pubkey,
+                &accept_params.fund_pubkey,
+            )?;
+            Ok((funding_script_pubkey.clone(), funding_script_pubkey))
+        }
+    }
+}
+
+/// Contains the parameters of a contract funded by a batch fund transaction.
+#[derive(Clone, Debug)]
+pub struct BatchContractParams {
+    /// The parameters of the offer party for the contract. The inputs of the
+    /// offer party are the union of the inputs of all the contracts of the
+    /// batch, which must all use the same change script pubkey and serial id.
+    pub offer_params: PartyParams,
+    /// The parameters of the accept party for the contract, following the same
+    /// rules as for the offer party.
+    pub accept_params: PartyParams,
+    /// The payouts of the contract.
+    pub payouts: Vec<Payout>,
+    /// The lock time of the refund transaction of the contract.
+    pub refund_lock_time: u32,
+    /// The lock time of the CETs of the contract.
+    pub cet_lock_time: u32,
+    /// Id used to order the fund output of the contract in the fund transaction.
+    pub fund_output_serial_id: u64,
+    /// The options to use to create the transactions of the contract.
+    pub options: DlcTransactionsOptions,
+}
+
+/// Create the transactions for a batch of DLC contracts sharing a single fund
+/// transaction, in which each contract has its own fund output. The returned
+/// transactions are in the same order as the given contracts, and all have the
+/// same fund transaction. Single funded contracts are not supported in batches.
+pub fn create_batch_dlc_transactions<C: Verification>(
+    secp: &Secp256k1<C>,
+    contracts: &[BatchContractParams],
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+) -> Result<Vec<DlcTransactions>, Error> {
+    let mut funding_script_pubkeys = Vec::with_capacity(contracts.len());
+    for c in contracts {
+        if c.options.single_funded {
+            return Err(Error::InvalidArgument);
+        }
+        let scripts = get_funding_script_pubkeys(
+            secp,
+            &c.offer_params,
+            &c.accept_params,
+            c.options.funding_output_type,
+        )?;
+        // The fund output of each contract is identified by its script pubkey.
+        if funding_script_pubkeys
+            .iter()
+            .any(|(_, x): &(ScriptBuf, ScriptBuf)| x == &scripts.1)
+        {
+            return Err(Error::InvalidArgument);
+        }
+        funding_script_pubkeys.push(scripts);
+    }
+
+    let (offer_change_output, offer_cet_fees) = get_batch_change_output_and_fees(
+        contracts.iter().map(|c| (&c.offer_params, &c.options)),
+        fee_rate_per_vb,
+    )?;
+    let (accept_change_output, accept_cet_fees) = get_batch_change_output_and_fees(
+        contracts.iter().map(|c| (&c.accept_params, &c.options)),
+        fee_rate_per_vb,
+    )?;
+
+    let mut fund_tx_outs = Vec::with_capacity(contracts.len());
+    for (i, c) in contracts.iter().enumerate() {
+        fund_tx_outs.push(TxOut {
+            value: checked_add!(
+                c.offer_params.collateral,
+                c.accept_params.collateral,
+                offer_cet_fees[i],
+                accept_cet_fees[i]
+            )?,
+            script_pubkey: funding_script_pubkeys[i].1.clone(),
+        });
+    }
+    let fund_output_serial_ids = contracts
+        .iter()
+        .map(|c| c.fund_output_serial_id)
+        .collect::<Vec<_>>();
+
+    let fund_sequence = util::ENABLE_RBF;
+    let mut offer_tx_ins = Vec::new();
+    let mut offer_inputs_serial_ids = Vec::new();
+    let mut accept_tx_ins = Vec::new();
+    let mut accept_inputs_serial_ids = Vec::new();
+    for c in contracts {
+        let (tx_ins, serial_ids) = c
+            .offer_params
+            .get_unsigned_tx_inputs_and_serial_ids(fund_sequence);
+        offer_tx_ins.extend(tx_ins);
+        offer_inputs_serial_ids.extend(serial_ids);
+        let (tx_ins, serial_ids) = c
+            .accept_params
+            .get_unsigned_tx_inputs_and_serial_ids(fund_sequence);
+        accept_tx_ins.extend(tx_ins);
+        accept_inputs_serial_ids.extend(serial_ids);
+    }
+
+    let fund_tx = create_funding_transaction_with_outputs(
+        fund_tx_outs,
+        &fund_output_serial_ids,
+        &offer_tx_ins,
+        &offer_inputs_serial_ids,
+        &accept_tx_ins,
+        &accept_inputs_serial_ids,
+        offer_change_output,
+        contracts[0].offer_params.change_serial_id,
+        accept_change_output,
+        contracts[0].accept_params.change_serial_id,
+        fund_lock_time,
+    );
+
+    contracts
+        .iter()
+        .zip(funding_script_pubkeys)
+        .map(|(c, (funding_script_pubkey, fund_output_script_pubkey))| {
+            let fund_outpoint = OutPoint {
+                txid: fund_tx.txid(),
+                vout: util::get_output_for_script_pubkey(&fund_tx, &fund_output_script_pubkey)
+                    .ok_or(Error::InvalidArgument)?
+                    .0 as u32,
+            };
+            let (cets, refund) = create_cets_and_refund_tx(
+                &c.offer_params,
+                &c.accept_params,
+                fund_outpoint,
+                &c.payouts,
+                c.refund_lock_time,
+                c.cet_lock_time,
+                None,
+                c.options.anchor_outputs,
+            )?;
+            Ok(DlcTransactions {
+                fund: fund_tx.clone(),
+                cets,
+                refund,
+                funding_script_pubkey,
+            })
+        })
+        .collect()
+}
+
+/// Returns the change output of a party funding a batch of contracts, which
+/// accounts for the fund transaction fee, as well as the fee that the party pays
+/// for the CET or refund transaction of each contract.
+fn get_batch_change_output_and_fees<'a, I>(
+    contracts_params: I,
+    fee_rate_per_vb: u64,
+) -> Result<(TxOut, Vec<u64>), Error>
+where
+    I: Iterator<Item = (&'a PartyParams, &'a DlcTransactionsOptions)>,
+{
+    let mut first_params: Option<&PartyParams> = None;
+    let mut inputs_weight: usize = 0;
+    let mut input_amount: u64 = 0;
+    let mut required_input_funds: u64 = 0;
+    let mut cet_or_refund_fees = Vec::new();
+
+    for (params, options) in contracts_params {
+        let first = first_params.get_or_insert(params);
+        if first.change_script_pubkey != params.change_script_pubkey
+            || first.change_serial_id != params.change_serial_id
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        inputs_weight = checked_add!(inputs_weight, params.get_inputs_weight()?)?;
+        input_amount = checked_add!(input_amount, params.input_amount)?;
+        let cet_or_refund_fee = params.get_cet_or_refund_fee(fee_rate_per_vb, options)?;
+        required_input_funds =
+            checked_add!(required_input_funds, params.collateral, cet_or_refund_fee)?;
+        cet_or_refund_fees.push(cet_or_refund_fee);
+    }
+
+    let first_params = first_params.ok_or(Error::InvalidArgument)?;
+
+    // The weight of the fund outputs beyond the one included in the base weight
+    // is distributed among parties.
+    let extra_fund_outputs_weight = (FUND_OUTPUT_WEIGHT / 2)
+        .checked_mul(cet_or_refund_fees.len() - 1)
+        .ok_or(Error::InvalidArgument)?;
+    let total_fund_weight = checked_add!(
+        FUND_TX_BASE_WEIGHT / 2,
+        extra_fund_outputs_weight,
+        inputs_weight,
+        first_params.get_change_output_weight()?
+    )?;
+    let fund_fee = util::weight_to_fee(total_fund_weight, fee_rate_per_vb)?;
+    required_input_funds = checked_add!(required_input_funds, fund_fee)?;
+
+    if input_amount < required_input_funds {
+        return Err(Error::InvalidArgument);
+    }
+
+    let change_output = TxOut {
+        value: input_amount - required_input_funds,
+        script_pubkey: first_params.change_script_pubkey.clone(),
+    };
+
+    Ok((change_output, cet_or_refund_fees))
+}
+
 pub(crate) fn create_fund_transaction_with_fees(
     offer_params: &PartyParams,
     accept_params: &PartyParams,
@@ -409,13 +987,61 @@ pub(crate) fn create_fund_transaction_with_fees(
     fund_lock_time: u32,
     fund_output_serial_id: u64,
     extra_fee: u64,
+    anchor_outputs: bool,
 ) -> Result<(Transaction, ScriptBuf), Error> {
+    let funding_script_pubkey =
+        make_funding_redeemscript(&offer_params.fund_pubkey, &accept_params.fund_pubkey);
+
+    let fund_tx = create_fund_transaction_for_output(
+        offer_params,
+        accept_params,
+        fee_rate_per_vb,
+        fund_lock_time,
+        fund_output_serial_id,
+        extra_fee,
+        &funding_script_pubkey.to_v0_p2wsh(),
+        &DlcTransactionsOptions {
+            funding_output_type: FundingOutputType::P2wsh,
+            anchor_outputs,
+            ..Default::default()
+        },
+    )?;
+
+    Ok((fund_tx, funding_script_pubkey))
+}
+
+fn create_fund_transaction_for_output(
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+    fund_output_serial_id: u64,
+    extra_fee: u64,
+    fund_output_script_pubkey: &Script,
+    options: &DlcTransactionsOptions,
+) -> Result<Transaction, Error> {
     let total_collateral = checked_add!(offer_params.collateral, accept_params.collateral)?;
 
-    let (offer_change_output, offer_fund_fee, offer_cet_fee) =
-        offer_params.get_change_output_and_fees(fee_rate_per_vb, extra_fee)?;
-    let (accept_change_output, accept_fund_fee, accept_cet_fee) =
-        accept_params.get_change_output_and_fees(fee_rate_per_vb, extra_fee)?;
+    let (accept_change_output, accept_fund_fee, accept_cet_fee, offer_extra_fee) =
+        if options.single_funded {
+            if !accept_params.inputs.is_empty() || accept_params.collateral != 0 {
+                return Err(Error::InvalidArgument);
+            }
+            let (fund_fee, cet_fee) = accept_params.get_unfunded_fees(fee_rate_per_vb, options)?;
+            let change_output = TxOut {
+                value: 0,
+                script_pubkey: accept_params.change_script_pubkey.clone(),
+            };
+            // The offer party pays the fees of the accept party on top of its own.
+            let offer_extra_fee = checked_add!(extra_fee, extra_fee, fund_fee, cet_fee)?;
+            (change_output, fund_fee, cet_fee, offer_extra_fee)
+        } else {
+            let (change_output, fund_fee, cet_fee) = accept_params
+                .get_change_output_and_fees_with_options(fee_rate_per_vb, extra_fee, options)?;
+            (change_output, fund_fee, cet_fee, extra_fee)
+        };
+    let (offer_change_output, offer_fund_fee, offer_cet_fee) = offer_params
+        .get_change_output_and_fees_with_options(fee_rate_per_vb, offer_extra_fee, options)?;
 
     let fund_output_value = checked_add!(offer_params.input_amount, accept_params.input_amount)?
         - offer_change_output.value
@@ -439,18 +1065,20 @@ pub(crate) fn create_fund_transaction_with_fees(
             + extra_fee
     );
 
-    let fund_sequence = util::get_sequence(fund_lock_time);
+    let fund_sequence = util::ENABLE_RBF;
     let (offer_tx_ins, offer_inputs_serial_ids) =
         offer_params.get_unsigned_tx_inputs_and_serial_ids(fund_sequence);
     let (accept_tx_ins, accept_inputs_serial_ids) =
         accept_params.get_unsigned_tx_inputs_and_serial_ids(fund_sequence);
 
-    let funding_script_pubkey =
-        make_funding_redeemscript(&offer_params.fund_pubkey, &accept_params.fund_pubkey);
+    let fund_tx_out = TxOut {
+        value: fund_output_value,
+        script_pubkey: fund_output_script_pubkey.to_owned(),
+    };
 
-    let fund_tx = create_funding_transaction(
-        &funding_script_pubkey,
-        fund_output_value,
+    let fund_tx = create_funding_transaction_with_outputs(
+        vec![fund_tx_out],
+        &[fund_output_serial_id],
         &offer_tx_ins,
         &offer_inputs_serial_ids,
         &accept_tx_ins,
@@ -459,11 +1087,143 @@ pub(crate) fn create_fund_transaction_with_fees(
         offer_params.change_serial_id,
         accept_change_output,
         accept_params.change_serial_id,
-        fund_output_serial_id,
         fund_lock_time,
     );
 
-    Ok((fund_tx, funding_script_pubkey))
+    Ok(fund_tx)
+}
+
+fn create_fund_transaction_with_fee_payer(
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    fee_payer_params: &FeePayerParams,
+    fee_rate_per_vb: u64,
+    fund_lock_time: u32,
+    fund_output_serial_id: u64,
+    fund_output_script_pubkey: &Script,
+    options: &DlcTransactionsOptions,
+) -> Result<Transaction, Error> {
+    if options.single_funded && (!accept_params.inputs.is_empty() || accept_params.collateral != 0)
+    {
+        return Err(Error::InvalidArgument);
+    }
+
+    let offer_change_output = offer_params.get_change_output_without_fees()?;
+    let accept_change_output = accept_params.get_change_output_without_fees()?;
+    let offer_cet_fee = offer_params.get_cet_or_refund_fee(fee_rate_per_vb, options)?;
+    let accept_cet_fee = accept_params.get_cet_or_refund_fee(fee_rate_per_vb, options)?;
+
+    // The fee payer pays for the whole fund transaction.
+    let offer_weight = checked_add!(
+        offer_params.get_inputs_weight()?,
+        offer_params.get_change_output_weight()?
+    )?;
+    let accept_weight = checked_add!(
+        accept_params.get_inputs_weight()?,
+        accept_params.get_change_output_weight()?
+    )?;
+    let fee_payer_weight = checked_add!(
+        get_inputs_weight(&fee_payer_params.inputs)?,
+        get_change_output_weight(&fee_payer_params.change_script_pubkey)?
+    )?;
+    let total_fund_weight = checked_add!(
+        FUND_TX_BASE_WEIGHT,
+        offer_weight,
+        accept_weight,
+        fee_payer_weight
+    )?;
+    let fund_fee = util::weight_to_fee(total_fund_weight, fee_rate_per_vb)?;
+    let required_fee_payer_funds = checked_add!(fund_fee, offer_cet_fee, accept_cet_fee)?;
+    if fee_payer_params.input_amount < required_fee_payer_funds {
+        return Err(Error::InvalidArgument);
+    }
+
+    let fee_payer_change_output = TxOut {
+        value: fee_payer_params.input_amount - required_fee_payer_funds,
+        script_pubkey: fee_payer_params.change_script_pubkey.clone(),
+    };
+    let fund_tx_out = TxOut {
+        value: checked_add!(
+            offer_params.collateral,
+            accept_params.collateral,
+            offer_cet_fee,
+            accept_cet_fee
+        )?,
+        script_pubkey: fund_output_script_pubkey.to_owned(),
+    };
+
+    let fund_sequence = util::ENABLE_RBF;
+    let mut inputs = Vec::new();
+    let mut input_serial_ids = Vec::new();
+    for params in [offer_params, accept_params] {
+        let (tx_ins, serial_ids) = params.get_unsigned_tx_inputs_and_serial_ids(fund_sequence);
+        inputs.extend(tx_ins);
+        input_serial_ids.extend(serial_ids);
+    }
+    for input in &fee_payer_params.inputs {
+        inputs.push(TxIn {
+            previous_output: input.outpoint,
+            script_sig: util::redeem_script_to_script_sig(&input.redeem_script),
+            sequence: fund_sequence,
+            witness: Witness::new(),
+        });
+        input_serial_ids.push(input.serial_id);
+    }
+
+    Ok(create_funding_transaction_ordered(
+        vec![
+            fund_tx_out,
+            offer_change_output,
+            accept_change_output,
+            fee_payer_change_output,
+        ],
+        &[
+            fund_output_serial_id,
+            offer_params.change_serial_id,
+            accept_params.change_serial_id,
+            fee_payer_params.change_serial_id,
+        ],
+        inputs,
+        &input_serial_ids,
+        fund_lock_time,
+    ))
+}
+
+/// Create the CETs and refund transaction of a new contract spending the fund
+/// output of the given existing DLC transactions, re-using their funding
+/// transaction. The total collateral of the new contract cannot exceed the
+/// value of the fund output.
+pub fn create_dlc_transactions_for_fund_output(
+    dlc_transactions: &DlcTransactions,
+    offer_params: &PartyParams,
+    accept_params: &PartyParams,
+    payouts: &[Payout],
+    refund_lock_time: u32,
+    cet_lock_time: u32,
+    anchor_outputs: bool,
+) -> Result<DlcTransactions, Error> {
+    let total_collateral = checked_add!(offer_params.collateral, accept_params.collateral)?;
+    if total_collateral > dlc_transactions.get_fund_output().value {
+        return Err(Error::InvalidArgument);
+    }
+
+    let (cets, refund) = create_cets_and_refund_tx(
+        offer_params,
+        accept_params,
+        dlc_transactions.get_fund_outpoint(),
+        payouts,
+        refund_lock_time,
+        cet_lock_time,
+        None,
+        anchor_outputs,
+    )?;
+
+    Ok(DlcTransactions {
+        fund: dlc_transactions.fund.clone(),
+        cets,
+        refund,
+        funding_script_pubkey: dlc_transactions.funding_script_pubkey.clone(),
+    })
 }
 
 pub(crate) fn create_cets_and_refund_tx(
@@ -474,6 +1234,7 @@ pub(crate) fn create_cets_and_refund_tx(
     refund_lock_time: u32,
     cet_lock_time: u32,
     cet_nsequence: Option<Sequence>,
+    anchor_outputs: bool,
 ) -> Result<(Vec<Transaction>, Transaction), Error> {
     let total_collateral = checked_add!(offer_params.collateral, accept_params.collateral)?;
 
@@ -497,7 +1258,7 @@ pub(crate) fn create_cets_and_refund_tx(
         sequence: cet_nsequence.unwrap_or_else(|| util::get_sequence(cet_lock_time)),
     };
 
-    let cets = create_cets(
+    let mut cets = create_cets(
         &cet_input,
         &offer_params.payout_script_pubkey,
         offer_params.payout_serial_id,
@@ -524,13 +1285,23 @@ pub(crate) fn create_cets_and_refund_tx(
         sequence: util::ENABLE_LOCKTIME,
     };
 
-    let refund_tx = create_refund_transaction(
+    let mut refund_tx = create_refund_transaction(
         offer_refund_output,
         accept_refund_ouput,
         refund_input,
         refund_lock_time,
     );
 
+    if anchor_outputs {
+        for cet in cets.iter_mut().chain(std::iter::once(&mut refund_tx)) {
+            add_anchor_outputs(
+                cet,
+                &offer_params.payout_script_pubkey,
+                &accept_params.payout_script_pubkey,
+            );
+        }
+    }
+
     Ok((cets, refund_tx))
 }
 
@@ -614,25 +1385,65 @@ pub fn create_funding_transaction(
         script_pubkey: funding_script_pubkey.to_v0_p2wsh(),
     };
 
-    let output: Vec<TxOut> = {
-        let serial_ids = vec![
-            fund_output_serial_id,
-            offer_change_serial_id,
-            accept_change_serial_id,
-        ];
-        util::discard_dust(
-            util::order_by_serial_ids(
-                vec![fund_tx_out, offer_change_output, accept_change_output],
-                &serial_ids,
-            ),
-            DUST_LIMIT,
-        )
-    };
+    create_funding_transaction_with_outputs(
+        vec![fund_tx_out],
+        &[fund_output_serial_id],
+        offer_inputs,
+        offer_inputs_serial_ids,
+        accept_inputs,
+        accept_inputs_serial_ids,
+        offer_change_output,
+        offer_change_serial_id,
+        accept_change_output,
+        accept_change_serial_id,
+        lock_time,
+    )
+}
+
+fn create_funding_transaction_with_outputs(
+    fund_tx_outs: Vec<TxOut>,
+    fund_output_serial_ids: &[u64],
+    offer_inputs: &[TxIn],
+    offer_inputs_serial_ids: &[u64],
+    accept_inputs: &[TxIn],
+    accept_inputs_serial_ids: &[u64],
+    offer_change_output: TxOut,
+    offer_change_serial_id: u64,
+    accept_change_output: TxOut,
+    accept_change_serial_id: u64,
+    lock_time: u32,
+) -> Transaction {
+    let mut outputs = fund_tx_outs;
+    outputs.push(offer_change_output);
+    outputs.push(accept_change_output);
+
+    create_funding_transaction_ordered(
+        outputs,
+        &[
+            fund_output_serial_ids,
+            &[offer_change_serial_id, accept_change_serial_id],
+        ]
+        .concat(),
+        [offer_inputs, accept_inputs].concat(),
+        &[offer_inputs_serial_ids, accept_inputs_serial_ids].concat(),
+        lock_time,
+    )
+}
 
-    let input = util::order_by_serial_ids(
-        [offer_inputs, accept_inputs].concat(),
-        &[offer_inputs_serial_ids, accept_inputs_serial_ids].concat(),
+/// Creates a funding transaction with the given inputs and outputs, ordered
+/// by their serial ids. Outputs below the dust limit are discarded.
+fn create_funding_transaction_ordered(
+    outputs: Vec<TxOut>,
+    output_serial_ids: &[u64],
+    inputs: Vec<TxIn>,
+    input_serial_ids: &[u64],
+    lock_time: u32,
+) -> Transaction {
+    let output = util::discard_dust(
+        util::order_by_serial_ids(outputs, output_serial_ids),
+        DUST_LIMIT,
     );
+    let input = util::order_by_serial_ids(inputs, input_serial_ids);
 
     Transaction {
         version: TX_VERSION,
@@ -658,6 +1469,58 @@ pub fn create_refund_transaction(
     }
 }
 
+/// Returns the weight of an anchor output paying to the given script pubkey,
+/// computed as: (nValue(8) + scriptPubkeyLen(1) + scriptPubkey) * 4
+pub fn get_anchor_output_weight(script_pubkey: &Script) -> Result<usize, Error> {
+    checked_add!(script_pubkey.len(), 8 + 1)?
+        .checked_mul(4)
+        .ok_or(Error::InvalidArgument)
+}
+
+/// Returns the fee that a transaction spending an anchor output of the given
+/// parent transaction needs to pay for the package to reach the given fee rate.
+/// The child transaction is assumed to have inputs (including the anchor one)
+/// of total weight `inputs_weight` (see [`util::get_input_weight`]), and a
+/// single output with the given script pubkey. The fee paid is never lower than
+/// the one required for the child transaction alone to reach the given fee
+/// rate.
+pub fn get_cpfp_fee(
+    parent: &Transaction,
+    parent_fee: u64,
+    inputs_weight: usize,
+    output_script_pubkey: &Script,
+    fee_rate_per_vb: u64,
+) -> Result<u64, Error> {
+    let output_weight = (8 + 1 + output_script_pubkey.len()) * 4;
+    let child_weight = checked_add!(SEGWIT_TX_BASE_WEIGHT, inputs_weight, output_weight)?;
+    let package_weight = checked_add!(parent.weight().to_wu() as usize, child_weight)?;
+    let package_fee = util::weight_to_fee(package_weight, fee_rate_per_vb)?;
+    let child_fee = util::weight_to_fee(child_weight, fee_rate_per_vb)?;
+
+    Ok(std::cmp::max(
+        package_fee.saturating_sub(parent_fee),
+        child_fee,
+    ))
+}
+
+/// Append to the given transaction an anchor output for the offer party
+/// followed by one for the accept party, each paying to the given script pubkey
+/// of the party (usually its payout one). The value of the anchor outputs is
+/// expected to have been accounted for when computing the value of the
+/// transaction input.
+pub fn add_anchor_outputs(
+    tx: &mut Transaction,
+    offer_anchor_script_pubkey: &Script,
+    accept_anchor_script_pubkey: &Script,
+) {
+    for script_pubkey in [offer_anchor_script_pubkey, accept_anchor_script_pubkey].iter() {
+        tx.output.push(TxOut {
+            value: ANCHOR_OUTPUT_VALUE,
+            script_pubkey: (*script_pubkey).to_owned(),
+        });
+    }
+}
+
 /// Create the multisig redeem script for the funding output
 pub fn make_funding_redeemscript(a: &PublicKey, b: &PublicKey) -> ScriptBuf {
     let (first, second) = if a <= b { (a, b) } else { (b, a) };
@@ -807,7 +1670,9 @@ pub fn create_cet_adaptor_sigs_from_oracle_info(
         .collect()
 }
 
-fn signatures_to_secret(signatures: &[Vec<SchnorrSignature>]) -> Result<SecretKey, Error> {
+pub(crate) fn signatures_to_secret(
+    signatures: &[Vec<SchnorrSignature>],
+) -> Result<SecretKey, Error> {
     let s_values = signatures
         .iter()
         .flatten()
@@ -1300,6 +2165,537 @@ mod tests {
             .all(|x| x.lock_time.to_consensus_u32() == 10));
     }
 
+    #[test]
+    fn create_taproot_dlc_transactions_lower_cet_fees() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_party_params, _) = get_party_params(1000000000, 100000000, None);
+        let (accept_party_params, _) = get_party_params(1000000000, 100000000, None);
+
+        // Act
+        let create = |funding_output_type| {
+            create_dlc_transactions_with_funding_output_type(
+                &secp,
+                &offer_party_params,
+                &accept_party_params,
+                &payouts(),
+                100,
+                4,
+                10,
+                10,
+                0,
+                funding_output_type,
+            )
+            .unwrap()
+        };
+        let p2wsh_txs = create(FundingOutputType::P2wsh);
+        let taproot_txs = create(FundingOutputType::Taproot);
+
+        // Assert
+        assert_eq!(
+            FundingOutputType::P2wsh,
+            p2wsh_txs.get_funding_output_type()
+        );
+        assert_eq!(
+            FundingOutputType::Taproot,
+            taproot_txs.get_funding_output_type()
+        );
+        assert!(taproot_txs.get_fund_output().script_pubkey.is_v1_p2tr());
+        assert!(taproot_txs.get_fund_output().value < p2wsh_txs.get_fund_output().value);
+        assert_eq!(
+            taproot_txs.get_fund_outpoint(),
+            taproot_txs.refund.input[0].previous_output
+        );
+    }
+
+    #[test]
+    fn create_dlc_transactions_with_anchor_outputs() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_party_params, _) = get_party_params(1000000000, 100000000, None);
+        let (accept_party_params, _) = get_party_params(1000000000, 100000000, None);
+
+        // Act
+        let create = |anchor_outputs| {
+            create_dlc_transactions_with_options(
+                &secp,
+                &offer_party_params,
+                &accept_party_params,
+                &payouts(),
+                100,
+                4,
+                10,
+                10,
+                0,
+                &DlcTransactionsOptions {
+                    anchor_outputs,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        let txs = create(false);
+        let anchor_txs = create(true);
+
+        // Assert
+        let offer_anchor_spk = &offer_party_params.payout_script_pubkey;
+        let accept_anchor_spk = &accept_party_params.payout_script_pubkey;
+        assert!(anchor_txs.get_fund_output().value > txs.get_fund_output().value);
+        for (tx, anchor_tx) in txs.cets.iter().chain(std::iter::once(&txs.refund)).zip(
+            anchor_txs
+                .cets
+                .iter()
+                .chain(std::iter::once(&anchor_txs.refund)),
+        ) {
+            let nb_outputs = tx.output.len();
+            assert_eq!(nb_outputs + 2, anchor_tx.output.len());
+            assert_eq!(
+                tx.output.iter().map(|x| x.value).collect::<Vec<_>>(),
+                anchor_tx.output[..nb_outputs]
+                    .iter()
+                    .map(|x| x.value)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                *offer_anchor_spk,
+                anchor_tx.output[nb_outputs].script_pubkey
+            );
+            assert_eq!(
+                *accept_anchor_spk,
+                anchor_tx.output[nb_outputs + 1].script_pubkey
+            );
+            assert!(anchor_tx.output[nb_outputs..]
+                .iter()
+                .all(|x| x.value == ANCHOR_OUTPUT_VALUE));
+        }
+    }
+
+    #[test]
+    fn create_single_funded_dlc_transactions() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_party_params, _) = get_party_params(1000000000, 200000000, None);
+        let (accept_party_params, _) = get_party_params(0, 0, Some(2));
+        let accept_party_params = PartyParams {
+            inputs: Vec::new(),
+            ..accept_party_params
+        };
+        let options = DlcTransactionsOptions {
+            single_funded: true,
+            ..Default::default()
+        };
+
+        // Act
+        let dlc_txs = create_dlc_transactions_with_options(
+            &secp,
+            &offer_party_params,
+            &accept_party_params,
+            &payouts(),
+            100,
+            4,
+            10,
+            10,
+            0,
+            &options,
+        )
+        .unwrap();
+
+        // Assert
+        let (accept_fund_fee, accept_cet_fee) =
+            accept_party_params.get_unfunded_fees(4, &options).unwrap();
+        let (offer_change, offer_fund_fee, offer_cet_fee) = offer_party_params
+            .get_change_output_and_fees_with_options(4, accept_fund_fee + accept_cet_fee, &options)
+            .unwrap();
+        let fund_output = dlc_txs.get_fund_output();
+        assert_eq!(1, dlc_txs.fund.input.len());
+        assert_eq!(2, dlc_txs.fund.output.len());
+        assert_eq!(
+            200000000 + offer_cet_fee + accept_cet_fee,
+            fund_output.value
+        );
+        assert_eq!(
+            1000000000 - offer_change.value - fund_output.value,
+            offer_fund_fee + accept_fund_fee
+        );
+        assert!(dlc_txs
+            .fund
+            .output
+            .iter()
+            .all(|x| x.script_pubkey != accept_party_params.change_script_pubkey));
+    }
+
+    #[test]
+    fn create_single_funded_dlc_transactions_accept_inputs_error() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_party_params, _) = get_party_params(1000000000, 200000000, None);
+        let (accept_party_params, _) = get_party_params(100000000, 0, Some(2));
+
+        // Act
+        let res = create_dlc_transactions_with_options(
+            &secp,
+            &offer_party_params,
+            &accept_party_params,
+            &payouts(),
+            100,
+            4,
+            10,
+            10,
+            0,
+            &DlcTransactionsOptions {
+                single_funded: true,
+                ..Default::default()
+            },
+        );
+
+        // Assert
+        assert!(matches!(res, Err(Error::InvalidArgument)));
+    }
+
+    fn get_fee_payer_params(input_amount: u64) -> FeePayerParams {
+        let secp = Secp256k1::new();
+        let mut rng = secp256k1_zkp::rand::thread_rng();
+        FeePayerParams {
+            inputs: vec![TxInputInfo {
+                max_witness_len: 108,
+                redeem_script: ScriptBuf::new(),
+                outpoint: OutPoint {
+                    txid: Txid::from_str(
+                        "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456",
+                    )
+                    .unwrap(),
+                    vout: 3,
+                },
+                serial_id: 3,
+            }],
+            input_amount,
+            change_script_pubkey: get_p2wpkh_script_pubkey(&secp, &mut rng),
+            change_serial_id: 3,
+        }
+    }
+
+    #[test]
+    fn create_dlc_transactions_with_fee_payer_test() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_party_params, _) = get_party_params(1000000000, 100000000, None);
+        let (accept_party_params, _) = get_party_params(1000000000, 100000000, Some(2));
+        let fee_payer_params = get_fee_payer_params(100000);
+
+        // Act
+        let dlc_txs = create_dlc_transactions_with_fee_payer(
+            &secp,
+            &offer_party_params,
+            &accept_party_params,
+            &fee_payer_params,
+            &payouts(),
+            100,
+            4,
+            10,
+            10,
+            0,
+            &DlcTransactionsOptions::default(),
+        )
+        .unwrap();
+
+        // Assert
+        let fund_output = dlc_txs.get_fund_output();
+        assert_eq!(3, dlc_txs.fund.input.len());
+        assert_eq!(4, dlc_txs.fund.output.len());
+        let change_value = |script_pubkey: &Script| {
+            dlc_txs
+                .fund
+                .output
+                .iter()
+                .find(|x| x.script_pubkey.as_script() == script_pubkey)
+                .unwrap()
+                .value
+        };
+        assert_eq!(
+            900000000,
+            change_value(&offer_party_params.change_script_pubkey)
+        );
+        assert_eq!(
+            900000000,
+            change_value(&accept_party_params.change_script_pubkey)
+        );
+        let fee_payer_change = change_value(&fee_payer_params.change_script_pubkey);
+        let cet_fees = offer_party_params
+            .get_cet_or_refund_fee(4, &DlcTransactionsOptions::default())
+            .unwrap()
+            * 2;
+        let fund_weight = FUND_TX_BASE_WEIGHT
+            + get_inputs_weight(&offer_party_params.inputs).unwrap() * 3
+            + get_change_output_weight(&offer_party_params.change_script_pubkey).unwrap() * 3;
+        let fund_fee = util::weight_to_fee(fund_weight, 4).unwrap();
+        assert_eq!(200000000 + cet_fees, fund_output.value);
+        assert_eq!(100000 - fund_fee - cet_fees, fee_payer_change);
+        assert_eq!(dlc_txs.cets[0].output[0].value, 200000000);
+        assert_eq!(dlc_txs.refund.output[0].value, 100000000);
+        assert_eq!(dlc_txs.refund.output[1].value, 100000000);
+    }
+
+    #[test]
+    fn create_dlc_transactions_with_fee_payer_insufficient_funds_error() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_party_params, _) = get_party_params(1000000000, 100000000, None);
+        let (accept_party_params, _) = get_party_params(1000000000, 100000000, Some(2));
+        let fee_payer_params = get_fee_payer_params(100);
+
+        // Act
+        let res = create_dlc_transactions_with_fee_payer(
+            &secp,
+            &offer_party_params,
+            &accept_party_params,
+            &fee_payer_params,
+            &payouts(),
+            100,
+            4,
+            10,
+            10,
+            0,
+            &DlcTransactionsOptions::default(),
+        );
+
+        // Assert
+        assert!(matches!(res, Err(Error::InvalidArgument)));
+    }
+
+    #[test]
+    fn get_max_witness_len_test() {
+        let secp = Secp256k1::new();
+        let mut rng = secp256k1_zkp::rand::thread_rng();
+        let p2wpkh = get_p2wpkh_script_pubkey(&secp, &mut rng);
+        let p2sh_p2wpkh = ScriptBuf::new_p2sh(&p2wpkh.script_hash());
+        let (xonly, _) = KeyPair::new(&secp, &mut rng).x_only_public_key();
+        let p2tr = ScriptBuf::new_v1_p2tr(&secp, xonly, None);
+        let multisig = make_funding_redeemscript(
+            &PublicKey::from_secret_key(&secp, &SecretKey::new(&mut rng)),
+            &PublicKey::from_secret_key(&secp, &SecretKey::new(&mut rng)),
+        );
+        let p2wsh = multisig.to_v0_p2wsh();
+        let empty = ScriptBuf::new();
+
+        assert_eq!(
+            Some(P2WPKH_WITNESS_SIZE),
+            util::get_max_witness_len(&p2wpkh, &empty, None)
+        );
+        assert_eq!(
+            Some(P2WPKH_WITNESS_SIZE),
+            util::get_max_witness_len(&p2sh_p2wpkh, &p2wpkh, None)
+        );
+        assert_eq!(
+            Some(P2TR_KEY_PATH_WITNESS_SIZE),
+            util::get_max_witness_len(&p2tr, &empty, None)
+        );
+        assert_eq!(
+            Some(218),
+            util::get_max_witness_len(&p2wsh, &empty, Some(&multisig))
+        );
+        assert_eq!(None, util::get_max_witness_len(&p2wsh, &empty, None));
+        assert_eq!(
+            None,
+            util::get_max_witness_len(&p2wsh, &empty, Some(&p2wpkh))
+        );
+    }
+
+    #[test]
+    fn create_batch_dlc_transactions_shares_fund_transaction() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_params, _) = get_party_params(1000000000, 100000000, None);
+        let (accept_params, _) = get_party_params(1000000000, 100000000, Some(2));
+        let other_contract_params = |params: &PartyParams| {
+            let (other_params, _) = get_party_params(0, 100000000, None);
+            PartyParams {
+                change_script_pubkey: params.change_script_pubkey.clone(),
+                change_serial_id: params.change_serial_id,
+                inputs: Vec::new(),
+                ..other_params
+            }
+        };
+        let contracts = (0..2)
+            .map(|i| BatchContractParams {
+                offer_params: if i == 0 {
+                    offer_params.clone()
+                } else {
+                    other_contract_params(&offer_params)
+                },
+                accept_params: if i == 0 {
+                    accept_params.clone()
+                } else {
+                    other_contract_params(&accept_params)
+                },
+                payouts: payouts(),
+                refund_lock_time: 100,
+                cet_lock_time: 10,
+                fund_output_serial_id: i + 10,
+                options: DlcTransactionsOptions::default(),
+            })
+            .collect::<Vec<_>>();
+
+        // Act
+        let batch_txs = create_batch_dlc_transactions(&secp, &contracts, 4, 10).unwrap();
+
+        // Assert
+        assert_eq!(2, batch_txs.len());
+        assert_eq!(4, batch_txs[0].fund.output.len());
+        assert_eq!(2, batch_txs[0].fund.input.len());
+        assert_eq!(batch_txs[0].fund, batch_txs[1].fund);
+        assert_ne!(
+            batch_txs[0].get_fund_output_index(),
+            batch_txs[1].get_fund_output_index()
+        );
+        for txs in &batch_txs {
+            assert_eq!(txs.get_fund_outpoint(), txs.refund.input[0].previous_output);
+            assert!(txs
+                .cets
+                .iter()
+                .all(|x| x.input[0].previous_output == txs.get_fund_outpoint()));
+        }
+    }
+
+    #[test]
+    fn create_batch_dlc_transactions_different_change_error() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let (offer_params, _) = get_party_params(1000000000, 100000000, None);
+        let (accept_params, _) = get_party_params(1000000000, 100000000, Some(2));
+        let (other_offer_params, _) = get_party_params(1000000000, 100000000, Some(3));
+        let contract = |offer_params: &PartyParams| BatchContractParams {
+            offer_params: offer_params.clone(),
+            accept_params: accept_params.clone(),
+            payouts: payouts(),
+            refund_lock_time: 100,
+            cet_lock_time: 10,
+            fund_output_serial_id: 0,
+            options: DlcTransactionsOptions::default(),
+        };
+
+        // Act
+        let res = create_batch_dlc_transactions(
+            &secp,
+            &[contract(&offer_params), contract(&other_offer_params)],
+            4,
+            10,
+        );
+
+        // Assert
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn taproot_refund_transaction_signature_is_valid() {
+        // Arrange
+        let secp = Secp256k1::new();
+        let mut rng = secp256k1_zkp::rand::thread_rng();
+        let (offer_party_params, offer_fund_sk) = get_party_params(1000000000, 100000000, None);
+        let (accept_party_params, accept_fund_sk) = get_party_params(1000000000, 100000000, None);
+        let dlc_txs = create_dlc_transactions_with_funding_output_type(
+            &secp,
+            &offer_party_params,
+            &accept_party_params,
+            &payouts(),
+            100,
+            4,
+            10,
+            10,
+            0,
+            FundingOutputType::Taproot,
+        )
+        .unwrap();
+        let fund_output = dlc_txs.get_fund_output().clone();
+        let key_agg_context = taproot::get_funding_key_agg_context(
+            &secp,
+            &offer_party_params.fund_pubkey,
+            &accept_party_params.fund_pubkey,
+        )
+        .unwrap();
+        let (offer_sec_nonce, offer_pub_nonce) = taproot::generate_nonce(
+            &secp,
+            rng.gen(),
+            &offer_fund_sk,
+            Some(&key_agg_context),
+            None,
+        )
+        .unwrap();
+        let (accept_sec_nonce, accept_pub_nonce) = taproot::generate_nonce(
+            &secp,
+            rng.gen(),
+            &accept_fund_sk,
+            Some(&key_agg_context),
+            None,
+        )
+        .unwrap();
+        let agg_nonce = taproot::aggregate_nonces(&[offer_pub_nonce, accept_pub_nonce]).unwrap();
+        let mut refund = dlc_txs.refund.clone();
+
+        // Act
+        let offer_partial_sig = taproot::create_funding_input_partial_sig(
+            &secp,
+            &refund,
+            &fund_output,
+            offer_sec_nonce,
+            &offer_fund_sk,
+            &key_agg_context,
+            &agg_nonce,
+        )
+        .unwrap();
+        let accept_partial_sig = taproot::create_funding_input_partial_sig(
+            &secp,
+            &refund,
+            &fund_output,
+            accept_sec_nonce,
+            &accept_fund_sk,
+            &key_agg_context,
+            &agg_nonce,
+        )
+        .unwrap();
+
+        // Assert
+        taproot::verify_funding_input_partial_sig(
+            &secp,
+            &offer_partial_sig,
+            &refund,
+            &fund_output,
+            &offer_pub_nonce,
+            &offer_party_params.fund_pubkey,
+            &key_agg_context,
+            &agg_nonce,
+        )
+        .expect("offer partial signature to be valid");
+        taproot::verify_funding_input_partial_sig(
+            &secp,
+            &accept_partial_sig,
+            &refund,
+            &fund_output,
+            &accept_pub_nonce,
+            &accept_party_params.fund_pubkey,
+            &key_agg_context,
+            &agg_nonce,
+        )
+        .expect("accept partial signature to be valid");
+        taproot::finalize_funding_input(
+            &secp,
+            &mut refund,
+            &fund_output,
+            &key_agg_context,
+            &agg_nonce,
+            &[offer_partial_sig, accept_partial_sig],
+        )
+        .expect("to be able to aggregate the partial signatures");
+        let sig = SchnorrSignature::from_slice(&refund.input[0].witness.to_vec()[0]).unwrap();
+        taproot::verify_funding_input_sig(
+            &secp,
+            &sig,
+            &dlc_txs.refund,
+            &fund_output,
+            &key_agg_context,
+        )
+        .expect("refund signature to be valid");
+    }
+
     #[test]
     fn create_cet_adaptor_sig_is_valid() {
         // Arrange
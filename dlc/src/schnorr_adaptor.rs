@@ -0,0 +1,490 @@
+//! Schnorr adaptor signatures compatible with [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki)
+//! verification, used as an alternative to ECDSA adaptor signatures for CETs
+//! spending a taproot fund output.
+//!
+//! An adaptor signature `(R', s')` for a message `m`, public key `P` and
+//! adaptor point `T` satisfies `s'G = (-1)^p R' + eP` where `R = R' + T`, `p`
+//! is set if `R` has an odd Y coordinate and `e = H(x(R) || x(P) || m)` is the
+//! BIP340 challenge. Given the discrete logarithm `t` of `T`, it can be
+//! decrypted into the BIP340 signature `(x(R), s' + (-1)^p t)`, and given that
+//! signature `t` can in turn be recovered.
+
+use bitcoin::{Transaction, TxOut, Witness};
+use secp256k1_zkp::{
+    schnorr::Signature as SchnorrSignature, KeyPair, Message, Parity, PublicKey, Scalar, Secp256k1,
+    SecretKey, Signing, UpstreamError, Verification, XOnlyPublicKey,
+};
+
+use crate::taproot::{hash_to_scalar, tagged_hash, CHALLENGE_TAG};
+use crate::{get_adaptor_point_from_oracle_info, signatures_to_secret, util, Error, OracleInfo};
+
+const NONCE_AUX_TAG: &str = "DLC/schnorr_adaptor/aux";
+const NONCE_TAG: &str = "DLC/schnorr_adaptor/nonce";
+
+/// The size in bytes of a serialized [`SchnorrAdaptorSignature`].
+pub const SCHNORR_ADAPTOR_SIGNATURE_SIZE: usize = 65;
+
+/// A Schnorr adaptor signature, made of the public nonce `R'` (before
+/// addition of the adaptor point) and the adapted scalar `s'`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchnorrAdaptorSignature {
+    nonce: PublicKey,
+    s: SecretKey,
+}
+
+impl SchnorrAdaptorSignature {
+    pub(crate) fn from_parts(nonce: PublicKey, s: SecretKey) -> Self {
+        SchnorrAdaptorSignature { nonce, s }
+    }
+
+    /// Creates an adaptor signature for `msg` using the given key pair,
+    /// encrypted with `adaptor_point`, drawing auxiliary randomness from the
+    /// thread local random number generator.
+    #[cfg(feature = "std")]
+    pub fn encrypt<C: Signing>(
+        secp: &Secp256k1<C>,
+        msg: &Message,
+        keypair: &KeyPair,
+        adaptor_point: &PublicKey,
+    ) -> Result<Self, Error> {
+        use secp256k1_zkp::rand::{thread_rng, Rng};
+        Self::encrypt_with_aux_rand(secp, msg, keypair, adaptor_point, &thread_rng().gen())
+    }
+
+    /// Creates an adaptor signature for `msg` using the given key pair,
+    /// encrypted with `adaptor_point`, without auxiliary randomness.
+    pub fn encrypt_no_aux_rand<C: Signing>(
+        secp: &Secp256k1<C>,
+        msg: &Message,
+        keypair: &KeyPair,
+        adaptor_point: &PublicKey,
+    ) -> Result<Self, Error> {
+        Self::encrypt_with_aux_rand(secp, msg, keypair, adaptor_point, &[0u8; 32])
+    }
+
+    /// Creates an adaptor signature for `msg` using the given key pair,
+    /// encrypted with `adaptor_point`, mixing `aux_rand` into the nonce
+    /// derivation.
+    pub fn encrypt_with_aux_rand<C: Signing>(
+        secp: &Secp256k1<C>,
+        msg: &Message,
+        keypair: &KeyPair,
+        adaptor_point: &PublicKey,
+        aux_rand: &[u8; 32],
+    ) -> Result<Self, Error> {
+        let (pubkey, parity) = keypair.x_only_public_key();
+        let sk = SecretKey::from_keypair(keypair);
+        let d = if parity == Parity::Odd {
+            sk.negate()
+        } else {
+            sk
+        };
+
+        let mut t = tagged_hash(NONCE_AUX_TAG, &[&aux_rand[..]]);
+        for (t_byte, d_byte) in t.iter_mut().zip(d.secret_bytes().iter()) {
+            *t_byte ^= d_byte;
+        }
+        let k = SecretKey::from_slice(&tagged_hash(
+            NONCE_TAG,
+            &[
+                &t[..],
+                &adaptor_point.serialize()[..],
+                &pubkey.serialize()[..],
+                &msg.as_ref()[..],
+            ],
+        ))?;
+
+        let nonce = PublicKey::from_secret_key(secp, &k);
+        let (r_x, r_parity) = nonce.combine(adaptor_point)?.x_only_public_key();
+        let k = if r_parity == Parity::Odd {
+            k.negate()
+        } else {
+            k
+        };
+        let e = challenge(&r_x, &pubkey, msg)?;
+        let s = k.add_tweak(&Scalar::from(d.mul_tweak(&e)?))?;
+
+        Ok(SchnorrAdaptorSignature { nonce, s })
+    }
+
+    /// Verifies that the adaptor signature is valid for `msg` and `pubkey`,
+    /// and that it decrypts into a valid signature with the discrete logarithm
+    /// of `adaptor_point`.
+    pub fn verify<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        msg: &Message,
+        pubkey: &XOnlyPublicKey,
+        adaptor_point: &PublicKey,
+    ) -> Result<(), Error> {
+        let (r_x, r_parity) = self.nonce.combine(adaptor_point)?.x_only_public_key();
+        let e = challenge(&r_x, pubkey, msg)?;
+        let nonce = if r_parity == Parity::Odd {
+            self.nonce.negate(secp)
+        } else {
+            self.nonce
+        };
+        let expected = PublicKey::from_x_only_public_key(*pubkey, Parity::Even)
+            .mul_tweak(secp, &e)?
+            .combine(&nonce)?;
+
+        if expected != self.s_point(secp) {
+            return Err(UpstreamError::IncorrectSignature.into());
+        }
+
+        Ok(())
+    }
+
+    /// Decrypts the adaptor signature into a BIP340 signature using the
+    /// discrete logarithm of the adaptor point.
+    pub fn decrypt<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        adaptor_secret: &SecretKey,
+    ) -> Result<SchnorrSignature, Error> {
+        let adaptor_point = PublicKey::from_secret_key(secp, adaptor_secret);
+        let (r_x, r_parity) = self.nonce.combine(&adaptor_point)?.x_only_public_key();
+        let t = if r_parity == Parity::Odd {
+            adaptor_secret.negate()
+        } else {
+            *adaptor_secret
+        };
+        let s = self.s.add_tweak(&Scalar::from(t))?;
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&r_x.serialize());
+        sig[32..].copy_from_slice(&s.secret_bytes());
+        Ok(SchnorrSignature::from_slice(&sig)?)
+    }
+
+    /// Recovers the discrete logarithm of `adaptor_point` from a signature
+    /// obtained by decrypting this adaptor signature.
+    pub fn recover<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        signature: &SchnorrSignature,
+        adaptor_point: &PublicKey,
+    ) -> Result<SecretKey, Error> {
+        let (r_x, r_parity) = self.nonce.combine(adaptor_point)?.x_only_public_key();
+        let sig_bytes = signature.as_ref();
+        if sig_bytes[..32] != r_x.serialize()[..] {
+            return Err(Error::InvalidArgument);
+        }
+
+        let s = SecretKey::from_slice(&sig_bytes[32..])?;
+        let t = s.add_tweak(&Scalar::from(self.s.negate()))?;
+        let t = if r_parity == Parity::Odd {
+            t.negate()
+        } else {
+            t
+        };
+
+        if PublicKey::from_secret_key(secp, &t) != *adaptor_point {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(t)
+    }
+
+    /// Serializes the adaptor signature as the compressed nonce point followed
+    /// by the adapted scalar.
+    pub fn serialize(&self) -> [u8; SCHNORR_ADAPTOR_SIGNATURE_SIZE] {
+        let mut res = [0u8; SCHNORR_ADAPTOR_SIGNATURE_SIZE];
+        res[..33].copy_from_slice(&self.nonce.serialize());
+        res[33..].copy_from_slice(&self.s.secret_bytes());
+        res
+    }
+
+    /// Parses an adaptor signature serialized using [`Self::serialize`].
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        if data.len() != SCHNORR_ADAPTOR_SIGNATURE_SIZE {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(SchnorrAdaptorSignature {
+            nonce: PublicKey::from_slice(&data[..33])?,
+            s: SecretKey::from_slice(&data[33..])?,
+        })
+    }
+
+    fn s_point<C: Verification>(&self, secp: &Secp256k1<C>) -> PublicKey {
+        // Multiplying the generator is not available for verification only
+        // contexts, so the generator is tweaked as a regular point instead.
+        let mut g = [0x02u8; 33];
+        g[1..].copy_from_slice(&secp256k1_zkp::constants::GENERATOR_X);
+        PublicKey::from_slice(&g)
+            .expect("generator to be a valid point")
+            .mul_tweak(secp, &Scalar::from(self.s))
+            .expect("non zero scalar to produce a valid point")
+    }
+}
+
+fn challenge(
+    r_x: &XOnlyPublicKey,
+    pubkey: &XOnlyPublicKey,
+    msg: &Message,
+) -> Result<Scalar, Error> {
+    hash_to_scalar(tagged_hash(
+        CHALLENGE_TAG,
+        &[
+            &r_x.serialize()[..],
+            &pubkey.serialize()[..],
+            &msg.as_ref()[..],
+        ],
+    ))
+}
+
+/// Verify that a given Schnorr adaptor signature for a given cet spending a
+/// taproot fund output is valid with respect to an adaptor point.
+pub fn verify_cet_adaptor_sig_from_point<C: Verification>(
+    secp: &Secp256k1<C>,
+    adaptor_sig: &SchnorrAdaptorSignature,
+    cet: &Transaction,
+    adaptor_point: &PublicKey,
+    output_key: &XOnlyPublicKey,
+    fund_output: &TxOut,
+) -> Result<(), Error> {
+    let sig_hash = util::get_taproot_sig_hash_msg(cet, 0, &[fund_output.clone()])?;
+    adaptor_sig.verify(secp, &sig_hash, output_key, adaptor_point)
+}
+
+/// Verify that a given Schnorr adaptor signature for a given cet spending a
+/// taproot fund output is valid with respect to an oracle public key, nonce
+/// and a given message.
+pub fn verify_cet_adaptor_sig_from_oracle_info<C: Verification>(
+    secp: &Secp256k1<C>,
+    adaptor_sig: &SchnorrAdaptorSignature,
+    cet: &Transaction,
+    oracle_infos: &[OracleInfo],
+    output_key: &XOnlyPublicKey,
+    fund_output: &TxOut,
+    msgs: &[Vec<Message>],
+) -> Result<(), Error> {
+    let adaptor_point = get_adaptor_point_from_oracle_info(secp, oracle_infos, msgs)?;
+    verify_cet_adaptor_sig_from_point(
+        secp,
+        adaptor_sig,
+        cet,
+        &adaptor_point,
+        output_key,
+        fund_output,
+    )
+}
+
+/// Sign a cet spending a taproot fund output by decrypting the given Schnorr
+/// adaptor signature using the oracle signatures, and place the resulting key
+/// path signature on the witness stack.
+pub fn sign_cet<C: Signing + Verification>(
+    secp: &Secp256k1<C>,
+    cet: &mut Transaction,
+    adaptor_sig: &SchnorrAdaptorSignature,
+    oracle_signatures: &[Vec<SchnorrSignature>],
+    output_key: &XOnlyPublicKey,
+    fund_output: &TxOut,
+) -> Result<(), Error> {
+    let adaptor_secret = signatures_to_secret(oracle_signatures)?;
+    let sig = adaptor_sig.decrypt(secp, &adaptor_secret)?;
+
+    let sig_hash = util::get_taproot_sig_hash_msg(cet, 0, &[fund_output.clone()])?;
+    secp.verify_schnorr(&sig, &sig_hash, output_key)?;
+
+    cet.input[0].witness = Witness::from_slice(&[sig.as_ref().to_vec()]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::hashes::hex::FromHex;
+    use secp256k1_zkp::rand::{thread_rng, Rng};
+    use std::str::FromStr;
+
+    const REGRESSION_VECTOR_MSG: &str =
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89";
+
+    // Regression data generated with this implementation, as there is no
+    // reference implementation of this adaptor signature scheme to take
+    // vectors from. They detect changes in the nonce derivation or encoding,
+    // while the validity of the signatures is checked against BIP340 in the
+    // test using them.
+    //
+    // Each vector is (secret key, adaptor secret, aux_rand, x-only public key,
+    // adaptor point, adaptor signature, decrypted signature). They cover keys
+    // and combined nonces `R' + T` with both even and odd Y coordinates.
+    const REGRESSION_VECTORS: [[&str; 7]; 6] = [
+        [
+            "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+            "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "0325D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+            "024A69111C75501992DFD76722BEE3AFEC83059C1AADC4D5E40CCF11CD5573F1083956DA5C03141839D493E37DEF68CE410B63425315F2178562D4F4B5AA632570",
+            "BD323C13F47CF6F2BD8C61F796542EFEE179F676D0E9B5061A006167DDFAA5E4449A05827AA78BBB83843F2E19CFBB111DDA72B5E531B9DA0119EA447CA33C80",
+        ],
+        [
+            "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+            "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "0325D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+            "0304EA637807BA03095BECC317824AD9AC8C9C59DC33579E7A598E8602AE1B6F5C5D815C3ADD13E967C05C4B56AA040ADDF489B10E568F99F0010012ED83EF6201",
+            "3A2076F5E6F56DF38EC3C62B3CA9916FC21447D12E03B95753BAAABAF6D196EE68C4876154A75CE96F4CA706D46AF7AE0700E17125CF3C449F45087C562F7911",
+        ],
+        [
+            "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03C84B912867DE813B58DFD75D26E83BDD07A9A41E1F4FCE881E0DAE391C2B9986BE71786404FB8AB25ED0BBDC90A1D3C6301064F7685B9BDAA00D7B973DC4361D",
+            "86316CA8B91388985302A6C3C5CFF3820CF4BF7D6F4EEFCA5920AE012691CDB2BE71786404FB8AB25ED0BBDC90A1D3C6301064F7685B9BDAA00D7B973DC4361A",
+        ],
+        [
+            "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02B77B37F53C7ABFB0469539F3E5A933594BDEE8E7F2F2C0E0E9462C48EA9276DBE7E0149702A542AD057FC156F547424FEABC29D97FB055C91AF1FE1AAF4D9F04",
+            "9339CAAD25E87C03EB02C788D5EFF102A2C726A89EF9A64AE4D2467B95D0C72FE7E0149702A542AD057FC156F547424FEABC29D97FB055C91AF1FE1AAF4D9F07",
+        ],
+        [
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C7",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03FAC2114C2FBB091527EB7C64ECB11F8021CB45E8E7809D3C0938E4B8C0E5F84B",
+            "02A8E5AAB7E0C562CFDE0D2F41C2E1602BE2A9906AE56765BE07A54C19F8F9E703FF9DCDBAAD4F543B81E8862A814B547253C69D28D036184DA47260AC0199FE1E",
+            "776216121A660C7C38B6B3ADD43EBFEE81F6A5D26488E75A5A4052FE949047EAC8ADA85CCEB8167046AEE8B602277144C21A0E4AAB554485E6ABC0C56C78A2A4",
+        ],
+        [
+            "0000000000000000000000000000000000000000000000000000000000000006",
+            "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A1460297556",
+            "0325D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+            "02DF3869BEBBE8D9702A03533173E45D7837CE0EAA5148A83E01640B2E0F5EA8483EE4531CC1BCBE692F151A4467AE5AFA9F2D1DD4A63077EC3F018D6E7CF9878D",
+            "6E3FAF7EFCE3548A423181A7DF0A776E16F7CC626B8A8BF43FCBAE7FF259DB1733A127F64A294AE78024BE943D476E2A8CB5ED71D6F0D597A0BC97DFAAB9707D",
+        ],
+    ];
+
+    fn setup() -> (Secp256k1<secp256k1_zkp::All>, KeyPair, SecretKey, Message) {
+        let secp = Secp256k1::new();
+        let mut rng = thread_rng();
+        let keypair = KeyPair::new(&secp, &mut rng);
+        let adaptor_secret = SecretKey::new(&mut rng);
+        let msg = Message::from_slice(&rng.gen::<[u8; 32]>()).unwrap();
+        (secp, keypair, adaptor_secret, msg)
+    }
+
+    #[test]
+    fn adaptor_signature_decrypts_into_valid_signature() {
+        for _ in 0..20 {
+            let (secp, keypair, adaptor_secret, msg) = setup();
+            let adaptor_point = PublicKey::from_secret_key(&secp, &adaptor_secret);
+            let pubkey = keypair.x_only_public_key().0;
+
+            let adaptor_sig =
+                SchnorrAdaptorSignature::encrypt(&secp, &msg, &keypair, &adaptor_point).unwrap();
+            adaptor_sig
+                .verify(&secp, &msg, &pubkey, &adaptor_point)
+                .expect("adaptor signature to be valid");
+
+            let sig = adaptor_sig.decrypt(&secp, &adaptor_secret).unwrap();
+            secp.verify_schnorr(&sig, &msg, &pubkey)
+                .expect("decrypted signature to be valid");
+
+            let recovered = adaptor_sig.recover(&secp, &sig, &adaptor_point).unwrap();
+            assert_eq!(adaptor_secret, recovered);
+        }
+    }
+
+    #[test]
+    fn adaptor_signature_with_wrong_adaptor_point_is_invalid() {
+        let (secp, keypair, adaptor_secret, msg) = setup();
+        let adaptor_point = PublicKey::from_secret_key(&secp, &adaptor_secret);
+        let other_point = PublicKey::from_secret_key(&secp, &SecretKey::new(&mut thread_rng()));
+
+        let adaptor_sig =
+            SchnorrAdaptorSignature::encrypt(&secp, &msg, &keypair, &adaptor_point).unwrap();
+
+        assert!(adaptor_sig
+            .verify(&secp, &msg, &keypair.x_only_public_key().0, &other_point)
+            .is_err());
+    }
+
+    #[test]
+    fn adaptor_signature_serialization_round_trips() {
+        let (secp, keypair, adaptor_secret, msg) = setup();
+        let adaptor_point = PublicKey::from_secret_key(&secp, &adaptor_secret);
+
+        let adaptor_sig =
+            SchnorrAdaptorSignature::encrypt(&secp, &msg, &keypair, &adaptor_point).unwrap();
+
+        assert_eq!(
+            adaptor_sig,
+            SchnorrAdaptorSignature::from_slice(&adaptor_sig.serialize()).unwrap()
+        );
+    }
+
+    #[test]
+    fn adaptor_signature_regression_vectors() {
+        let secp = Secp256k1::new();
+        let msg =
+            Message::from_slice(&Vec::<u8>::from_hex(REGRESSION_VECTOR_MSG).unwrap()).unwrap();
+
+        for &[sk, adaptor_secret, aux_rand, pubkey, adaptor_point, adaptor_sig, sig] in
+            REGRESSION_VECTORS.iter()
+        {
+            let keypair = KeyPair::from_seckey_str(&secp, sk).unwrap();
+            let adaptor_secret = SecretKey::from_str(adaptor_secret).unwrap();
+            let mut aux = [0u8; 32];
+            aux.copy_from_slice(&Vec::<u8>::from_hex(aux_rand).unwrap());
+            let pubkey = XOnlyPublicKey::from_str(pubkey).unwrap();
+            let adaptor_point = PublicKey::from_str(adaptor_point).unwrap();
+            let expected_adaptor_sig = Vec::<u8>::from_hex(adaptor_sig).unwrap();
+            let expected_sig = SchnorrSignature::from_str(sig).unwrap();
+
+            assert_eq!(pubkey, keypair.x_only_public_key().0);
+            assert_eq!(
+                adaptor_point,
+                PublicKey::from_secret_key(&secp, &adaptor_secret)
+            );
+
+            let computed = SchnorrAdaptorSignature::encrypt_with_aux_rand(
+                &secp,
+                &msg,
+                &keypair,
+                &adaptor_point,
+                &aux,
+            )
+            .unwrap();
+            assert_eq!(&computed.serialize()[..], &expected_adaptor_sig[..]);
+
+            let adaptor_sig = SchnorrAdaptorSignature::from_slice(&expected_adaptor_sig).unwrap();
+            adaptor_sig
+                .verify(&secp, &msg, &pubkey, &adaptor_point)
+                .expect("adaptor signature to be valid");
+
+            let decrypted = adaptor_sig.decrypt(&secp, &adaptor_secret).unwrap();
+            assert_eq!(expected_sig, decrypted);
+            secp.verify_schnorr(&decrypted, &msg, &pubkey)
+                .expect("decrypted signature to be valid");
+
+            assert_eq!(
+                adaptor_secret,
+                adaptor_sig
+                    .recover(&secp, &decrypted, &adaptor_point)
+                    .unwrap()
+            );
+
+            let mut tampered = expected_adaptor_sig.clone();
+            tampered[SCHNORR_ADAPTOR_SIGNATURE_SIZE - 1] ^= 1;
+            assert!(SchnorrAdaptorSignature::from_slice(&tampered)
+                .unwrap()
+                .verify(&secp, &msg, &pubkey, &adaptor_point)
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,1359 @@
+//! Functions to lock the collateral of a contract in a taproot (P2TR) output
+//! whose internal key is the MuSig2 ([BIP327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki))
+//! aggregate of the fund public keys of both parties, and to spend it through
+//! the key path.
+//!
+//! Signing happens in two rounds. Each party first generates a nonce pair using
+//! [`generate_nonce`] and shares the public part with its counter party. Once
+//! the public nonces are aggregated using [`aggregate_nonces`], each party
+//! produces a partial signature, and the partial signatures are combined into a
+//! single BIP340 signature valid for the fund output key.
+
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::taproot::TapTweakHash;
+use bitcoin::{ScriptBuf, Transaction, TxOut, Witness};
+use secp256k1_zkp::{
+    schnorr::Signature as SchnorrSignature, All, Message, Parity, PublicKey, Scalar, Secp256k1,
+    SecretKey, UpstreamError, Verification, XOnlyPublicKey,
+};
+
+use crate::schnorr_adaptor::SchnorrAdaptorSignature;
+use crate::{util, Error};
+
+const KEYAGG_LIST_TAG: &str = "KeyAgg list";
+const KEYAGG_COEFFICIENT_TAG: &str = "KeyAgg coefficient";
+const NONCE_AUX_TAG: &str = "MuSig/aux";
+const NONCE_TAG: &str = "MuSig/nonce";
+const NONCE_COEFFICIENT_TAG: &str = "MuSig/noncecoef";
+pub(crate) const CHALLENGE_TAG: &str = "BIP0340/challenge";
+
+/// Context resulting from the aggregation of a set of public keys, keeping track
+/// of the tweaks applied to the aggregate key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyAggContext {
+    pubkeys: Vec<PublicKey>,
+    keys_hash: [u8; 32],
+    second_key: Option<PublicKey>,
+    internal_key: XOnlyPublicKey,
+    agg_pk: PublicKey,
+    is_negated: bool,
+    tweak_acc: Option<SecretKey>,
+}
+
+impl KeyAggContext {
+    /// Aggregates the given public keys. The keys are sorted prior to aggregation
+    /// so that the result does not depend on the order in which they are given.
+    pub fn new<C: Verification>(
+        secp: &Secp256k1<C>,
+        pubkeys: &[PublicKey],
+    ) -> Result<KeyAggContext, Error> {
+        let mut pubkeys = pubkeys.to_vec();
+        pubkeys.sort_by_key(|pk| pk.serialize());
+        if pubkeys.windows(2).any(|w| w[0] == w[1]) {
+            return Err(Error::InvalidArgument);
+        }
+
+        Self::key_agg(secp, pubkeys)
+    }
+
+    /// Aggregates the given public keys in the given order, following the
+    /// `KeyAgg` algorithm of BIP327.
+    fn key_agg<C: Verification>(
+        secp: &Secp256k1<C>,
+        pubkeys: Vec<PublicKey>,
+    ) -> Result<KeyAggContext, Error> {
+        let first_key = *pubkeys.first().ok_or(Error::InvalidArgument)?;
+        let serialized = pubkeys
+            .iter()
+            .flat_map(|pk| pk.serialize())
+            .collect::<Vec<_>>();
+        let keys_hash = tagged_hash(KEYAGG_LIST_TAG, &[&serialized[..]]);
+        let second_key = pubkeys.iter().find(|pk| **pk != first_key).copied();
+
+        let points = pubkeys
+            .iter()
+            .map(|pk| {
+                let coefficient = key_agg_coefficient(&keys_hash, &second_key, pk)?;
+                Ok(pk.mul_tweak(secp, &coefficient)?)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let agg_pk = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>())?;
+
+        Ok(KeyAggContext {
+            pubkeys,
+            keys_hash,
+            second_key,
+            internal_key: agg_pk.x_only_public_key().0,
+            agg_pk,
+            is_negated: false,
+            tweak_acc: None,
+        })
+    }
+
+    /// Applies the taproot tweak for an output without script tree
+    /// ([BIP86](https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki))
+    /// to the aggregate key.
+    pub fn apply_taproot_tweak<C: Verification>(
+        &mut self,
+        secp: &Secp256k1<C>,
+    ) -> Result<(), Error> {
+        let tweak = TapTweakHash::from_key_and_tweak(self.output_key(), None).to_scalar();
+        self.apply_tweak(secp, &tweak, true)
+    }
+
+    /// Returns the aggregate key prior to any tweak being applied.
+    pub fn internal_key(&self) -> XOnlyPublicKey {
+        self.internal_key
+    }
+
+    /// Returns the x-only aggregate key including all the tweaks applied to it,
+    /// which is the key for which aggregated signatures are valid.
+    pub fn output_key(&self) -> XOnlyPublicKey {
+        self.agg_pk.x_only_public_key().0
+    }
+
+    /// Returns the (sorted) public keys that were aggregated.
+    pub fn pubkeys(&self) -> &[PublicKey] {
+        &self.pubkeys
+    }
+
+    /// Adds `tweak` times the generator to the aggregate key, negating the key
+    /// first if `is_x_only` is set and its y coordinate is odd, as done by the
+    /// `ApplyTweak` algorithm of BIP327.
+    fn apply_tweak<C: Verification>(
+        &mut self,
+        secp: &Secp256k1<C>,
+        tweak: &Scalar,
+        is_x_only: bool,
+    ) -> Result<(), Error> {
+        let is_odd = is_x_only && self.agg_pk.x_only_public_key().1 == Parity::Odd;
+        let base = if is_odd {
+            self.agg_pk.negate(secp)
+        } else {
+            self.agg_pk
+        };
+        self.agg_pk = base.add_exp_tweak(secp, tweak)?;
+        self.is_negated ^= is_odd;
+        self.tweak_acc = Some(match self.tweak_acc {
+            Some(acc) => {
+                let acc = if is_odd { acc.negate() } else { acc };
+                acc.add_tweak(tweak)?
+            }
+            None => SecretKey::from_slice(&tweak.to_be_bytes())?,
+        });
+        Ok(())
+    }
+
+    fn key_agg_coefficient(&self, pubkey: &PublicKey) -> Result<Scalar, Error> {
+        if !self.pubkeys.contains(pubkey) {
+            return Err(Error::InvalidArgument);
+        }
+
+        key_agg_coefficient(&self.keys_hash, &self.second_key, pubkey)
+    }
+}
+
+fn key_agg_coefficient(
+    keys_hash: &[u8; 32],
+    second_key: &Option<PublicKey>,
+    pubkey: &PublicKey,
+) -> Result<Scalar, Error> {
+    if Some(*pubkey) == *second_key {
+        return Ok(Scalar::ONE);
+    }
+
+    hash_to_scalar(tagged_hash(
+        KEYAGG_COEFFICIENT_TAG,
+        &[&keys_hash[..], &pubkey.serialize()[..]],
+    ))
+}
+
+/// The secret part of a nonce pair used by a signer for a single signing
+/// session. A secret nonce must never be used to produce more than one partial
+/// signature, which is why it is consumed when signing and cannot be cloned.
+pub struct SecretNonce {
+    k1: SecretKey,
+    k2: SecretKey,
+}
+
+impl SecretNonce {
+    /// Serializes the secret nonce as the concatenation of its two 32 bytes big
+    /// endian scalars, for it to be persisted until it is used. The serialized
+    /// value must be deleted before the nonce is used to produce a partial
+    /// signature, to ensure that it cannot be used again.
+    pub fn serialize(&self) -> [u8; 64] {
+        let mut res = [0u8; 64];
+        res[..32].copy_from_slice(&self.k1.secret_bytes());
+        res[32..].copy_from_slice(&self.k2.secret_bytes());
+        res
+    }
+
+    /// Parses a secret nonce serialized with [`SecretNonce::serialize`].
+    pub fn from_slice(data: &[u8]) -> Result<SecretNonce, Error> {
+        if data.len() != 64 {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(SecretNonce {
+            k1: SecretKey::from_slice(&data[..32])?,
+            k2: SecretKey::from_slice(&data[32..])?,
+        })
+    }
+}
+
+/// The public part of a nonce pair, to be shared with the other signers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicNonce {
+    /// The first nonce point.
+    pub r1: PublicKey,
+    /// The second nonce point.
+    pub r2: PublicKey,
+}
+
+/// The aggregate of the public nonces of all signers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregateNonce {
+    /// The sum of the first nonce points.
+    pub r1: PublicKey,
+    /// The sum of the second nonce points.
+    pub r2: PublicKey,
+}
+
+/// A partial signature produced by a single signer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialSignature(SecretKey);
+
+impl PartialSignature {
+    /// Serializes the partial signature as a 32 bytes big endian scalar.
+    pub fn serialize(&self) -> [u8; 32] {
+        self.0.secret_bytes()
+    }
+
+    /// Parses a partial signature from a 32 bytes big endian scalar.
+    pub fn from_slice(data: &[u8]) -> Result<PartialSignature, Error> {
+        Ok(PartialSignature(SecretKey::from_slice(data)?))
+    }
+}
+
+struct SessionValues {
+    b: Scalar,
+    r_base: PublicKey,
+    r: PublicKey,
+    e: Scalar,
+    r_is_odd: bool,
+    q_is_odd: bool,
+}
+
+/// Generates a nonce pair for the signer owning `sk`. `session_rand` must be
+/// 32 bytes of fresh randomness that is never reused across signing sessions.
+/// The key aggregation context can be omitted if the nonce is generated before
+/// the public keys of all signers are known. The message to be signed can
+/// optionally be provided as additional protection against randomness
+/// failures.
+pub fn generate_nonce(
+    secp: &Secp256k1<All>,
+    session_rand: [u8; 32],
+    sk: &SecretKey,
+    key_agg_context: Option<&KeyAggContext>,
+    msg: Option<&Message>,
+) -> Result<(SecretNonce, PublicNonce), Error> {
+    nonce_gen(
+        secp,
+        &session_rand,
+        Some(sk),
+        &PublicKey::from_secret_key(secp, sk),
+        key_agg_context.map(|x| x.output_key()).as_ref(),
+        msg.map(|m| m.as_ref() as &[u8]),
+        None,
+    )
+}
+
+/// Generates a nonce pair following the `NonceGen` algorithm of BIP327.
+fn nonce_gen(
+    secp: &Secp256k1<All>,
+    session_rand: &[u8; 32],
+    sk: Option<&SecretKey>,
+    pubkey: &PublicKey,
+    agg_pk: Option<&XOnlyPublicKey>,
+    msg: Option<&[u8]>,
+    extra_in: Option<&[u8]>,
+) -> Result<(SecretNonce, PublicNonce), Error> {
+    let mut rand = *session_rand;
+    if let Some(sk) = sk {
+        let aux = tagged_hash(NONCE_AUX_TAG, &[&session_rand[..]]);
+        for ((r, a), s) in rand
+            .iter_mut()
+            .zip(aux.iter())
+            .zip(sk.secret_bytes().iter())
+        {
+            *r = a ^ s;
+        }
+    }
+
+    let pubkey = pubkey.serialize();
+    let agg_pk = agg_pk.map(|x| x.serialize().to_vec()).unwrap_or_default();
+    let msg_prefixed = match msg {
+        Some(m) => [&[1u8][..], &(m.len() as u64).to_be_bytes()[..], m].concat(),
+        None => vec![0u8],
+    };
+    let extra_in = extra_in.unwrap_or_default();
+
+    let nonce_key = |i: u8| -> Result<SecretKey, Error> {
+        let hash = tagged_hash(
+            NONCE_TAG,
+            &[
+                &rand[..],
+                &[pubkey.len() as u8][..],
+                &pubkey[..],
+                &[agg_pk.len() as u8][..],
+                &agg_pk[..],
+                &msg_prefixed[..],
+                &(extra_in.len() as u32).to_be_bytes()[..],
+                extra_in,
+                &[i][..],
+            ],
+        );
+        Ok(SecretKey::from_slice(&hash)?)
+    };
+
+    let k1 = nonce_key(0)?;
+    let k2 = nonce_key(1)?;
+    let pub_nonce = PublicNonce {
+        r1: PublicKey::from_secret_key(secp, &k1),
+        r2: PublicKey::from_secret_key(secp, &k2),
+    };
+
+    Ok((SecretNonce { k1, k2 }, pub_nonce))
+}
+
+/// Aggregates the public nonces of all signers.
+pub fn aggregate_nonces(pub_nonces: &[PublicNonce]) -> Result<AggregateNonce, Error> {
+    if pub_nonces.is_empty() {
+        return Err(Error::InvalidArgument);
+    }
+
+    Ok(AggregateNonce {
+        r1: PublicKey::combine_keys(&pub_nonces.iter().map(|n| &n.r1).collect::<Vec<_>>())?,
+        r2: PublicKey::combine_keys(&pub_nonces.iter().map(|n| &n.r2).collect::<Vec<_>>())?,
+    })
+}
+
+fn get_session_values(
+    secp: &Secp256k1<All>,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: Option<&PublicKey>,
+    msg: &Message,
+) -> Result<SessionValues, Error> {
+    let output_key = key_agg_context.output_key().serialize();
+    let b = hash_to_scalar(tagged_hash(
+        NONCE_COEFFICIENT_TAG,
+        &[
+            &agg_nonce.r1.serialize()[..],
+            &agg_nonce.r2.serialize()[..],
+            &output_key[..],
+            &msg.as_ref()[..],
+        ],
+    ))?;
+    let r_base = agg_nonce.r2.mul_tweak(secp, &b)?.combine(&agg_nonce.r1)?;
+    let r = match adaptor_point {
+        Some(adaptor_point) => r_base.combine(adaptor_point)?,
+        None => r_base,
+    };
+    let (r_x, r_parity) = r.x_only_public_key();
+    let e = hash_to_scalar(tagged_hash(
+        CHALLENGE_TAG,
+        &[&r_x.serialize()[..], &output_key[..], &msg.as_ref()[..]],
+    ))?;
+
+    Ok(SessionValues {
+        b,
+        r_base,
+        r,
+        e,
+        r_is_odd: r_parity == Parity::Odd,
+        q_is_odd: key_agg_context.agg_pk.x_only_public_key().1 == Parity::Odd,
+    })
+}
+
+/// Produces a partial signature over `msg` using the given secret key and
+/// secret nonce.
+pub fn partial_sign(
+    secp: &Secp256k1<All>,
+    sec_nonce: SecretNonce,
+    sk: &SecretKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    msg: &Message,
+) -> Result<PartialSignature, Error> {
+    partial_sign_internal(secp, sec_nonce, sk, key_agg_context, agg_nonce, None, msg)
+}
+
+/// Produces a partial signature over `msg` that can only be combined into a
+/// valid signature with the knowledge of the discrete logarithm of
+/// `adaptor_point`.
+pub fn partial_adaptor_sign(
+    secp: &Secp256k1<All>,
+    sec_nonce: SecretNonce,
+    sk: &SecretKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: &PublicKey,
+    msg: &Message,
+) -> Result<PartialSignature, Error> {
+    partial_sign_internal(
+        secp,
+        sec_nonce,
+        sk,
+        key_agg_context,
+        agg_nonce,
+        Some(adaptor_point),
+        msg,
+    )
+}
+
+fn partial_sign_internal(
+    secp: &Secp256k1<All>,
+    sec_nonce: SecretNonce,
+    sk: &SecretKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: Option<&PublicKey>,
+    msg: &Message,
+) -> Result<PartialSignature, Error> {
+    let session = get_session_values(secp, key_agg_context, agg_nonce, adaptor_point, msg)?;
+    let pubkey = PublicKey::from_secret_key(secp, sk);
+    let a = key_agg_context.key_agg_coefficient(&pubkey)?;
+
+    let (k1, k2) = if session.r_is_odd {
+        (sec_nonce.k1.negate(), sec_nonce.k2.negate())
+    } else {
+        (sec_nonce.k1, sec_nonce.k2)
+    };
+    let d = if session.q_is_odd ^ key_agg_context.is_negated {
+        sk.negate()
+    } else {
+        *sk
+    };
+
+    let b_k2 = k2.mul_tweak(&session.b)?;
+    let e_a_d = d.mul_tweak(&a)?.mul_tweak(&session.e)?;
+    let s = k1
+        .add_tweak(&Scalar::from(b_k2))?
+        .add_tweak(&Scalar::from(e_a_d))?;
+
+    Ok(PartialSignature(s))
+}
+
+/// Verifies a partial signature produced by the owner of `pubkey` using the
+/// given public nonce.
+pub fn verify_partial_signature(
+    secp: &Secp256k1<All>,
+    partial_sig: &PartialSignature,
+    pub_nonce: &PublicNonce,
+    pubkey: &PublicKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    msg: &Message,
+) -> Result<(), Error> {
+    verify_partial_signature_internal(
+        secp,
+        partial_sig,
+        pub_nonce,
+        pubkey,
+        key_agg_context,
+        agg_nonce,
+        None,
+        msg,
+    )
+}
+
+/// Verifies a partial adaptor signature produced by the owner of `pubkey` using
+/// the given public nonce and adaptor point.
+pub fn verify_partial_adaptor_signature(
+    secp: &Secp256k1<All>,
+    partial_sig: &PartialSignature,
+    pub_nonce: &PublicNonce,
+    pubkey: &PublicKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: &PublicKey,
+    msg: &Message,
+) -> Result<(), Error> {
+    verify_partial_signature_internal(
+        secp,
+        partial_sig,
+        pub_nonce,
+        pubkey,
+        key_agg_context,
+        agg_nonce,
+        Some(adaptor_point),
+        msg,
+    )
+}
+
+fn verify_partial_signature_internal(
+    secp: &Secp256k1<All>,
+    partial_sig: &PartialSignature,
+    pub_nonce: &PublicNonce,
+    pubkey: &PublicKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: Option<&PublicKey>,
+    msg: &Message,
+) -> Result<(), Error> {
+    let session = get_session_values(secp, key_agg_context, agg_nonce, adaptor_point, msg)?;
+    let a = key_agg_context.key_agg_coefficient(pubkey)?;
+
+    let r_e = pub_nonce
+        .r2
+        .mul_tweak(secp, &session.b)?
+        .combine(&pub_nonce.r1)?;
+    let r_e = if session.r_is_odd {
+        r_e.negate(secp)
+    } else {
+        r_e
+    };
+    let pubkey = if session.q_is_odd ^ key_agg_context.is_negated {
+        pubkey.negate(secp)
+    } else {
+        *pubkey
+    };
+
+    let expected = pubkey
+        .mul_tweak(secp, &a)?
+        .mul_tweak(secp, &session.e)?
+        .combine(&r_e)?;
+
+    if PublicKey::from_secret_key(secp, &partial_sig.0) != expected {
+        return Err(UpstreamError::IncorrectSignature.into());
+    }
+
+    Ok(())
+}
+
+/// Combines the partial signatures of all signers into a BIP340 signature valid
+/// for the output key of `key_agg_context`.
+pub fn aggregate_partial_signatures(
+    secp: &Secp256k1<All>,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    msg: &Message,
+    partial_sigs: &[PartialSignature],
+) -> Result<SchnorrSignature, Error> {
+    let session = get_session_values(secp, key_agg_context, agg_nonce, None, msg)?;
+    let s = sum_partial_signatures(key_agg_context, &session, partial_sigs)?;
+
+    let mut sig = [0u8; 64];
+    sig[..32].copy_from_slice(&session.r.x_only_public_key().0.serialize());
+    sig[32..].copy_from_slice(&s.secret_bytes());
+    let sig = SchnorrSignature::from_slice(&sig)?;
+    secp.verify_schnorr(&sig, msg, &key_agg_context.output_key())?;
+
+    Ok(sig)
+}
+
+/// Combines partial adaptor signatures of all signers into a Schnorr adaptor
+/// signature valid for the output key of `key_agg_context`, which can be
+/// decrypted into a BIP340 signature using the discrete logarithm of
+/// `adaptor_point`.
+pub fn aggregate_partial_adaptor_signatures(
+    secp: &Secp256k1<All>,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: &PublicKey,
+    msg: &Message,
+    partial_sigs: &[PartialSignature],
+) -> Result<SchnorrAdaptorSignature, Error> {
+    let session = get_session_values(secp, key_agg_context, agg_nonce, Some(adaptor_point), msg)?;
+    let s = sum_partial_signatures(key_agg_context, &session, partial_sigs)?;
+    let adaptor_sig = SchnorrAdaptorSignature::from_parts(session.r_base, s);
+    adaptor_sig.verify(secp, msg, &key_agg_context.output_key(), adaptor_point)?;
+
+    Ok(adaptor_sig)
+}
+
+fn sum_partial_signatures(
+    key_agg_context: &KeyAggContext,
+    session: &SessionValues,
+    partial_sigs: &[PartialSignature],
+) -> Result<SecretKey, Error> {
+    let (first, others) = partial_sigs.split_first().ok_or(Error::InvalidArgument)?;
+
+    let mut s = first.0;
+    for partial_sig in others {
+        s = s.add_tweak(&Scalar::from(partial_sig.0))?;
+    }
+
+    if let Some(tweak_acc) = key_agg_context.tweak_acc {
+        let tweak_acc = if session.q_is_odd {
+            tweak_acc.negate()
+        } else {
+            tweak_acc
+        };
+        s = s.add_tweak(&Scalar::from(tweak_acc.mul_tweak(&session.e)?))?;
+    }
+
+    Ok(s)
+}
+
+/// Returns the key aggregation context for the fund output of a contract
+/// between the owners of the given fund public keys, including the taproot
+/// tweak.
+pub fn get_funding_key_agg_context<C: Verification>(
+    secp: &Secp256k1<C>,
+    a: &PublicKey,
+    b: &PublicKey,
+) -> Result<KeyAggContext, Error> {
+    let mut context = KeyAggContext::new(secp, &[*a, *b])?;
+    context.apply_taproot_tweak(secp)?;
+    Ok(context)
+}
+
+/// Create the P2TR script pubkey for the funding output, committing to the
+/// MuSig2 aggregate of both fund public keys without any script path.
+pub fn make_taproot_funding_script_pubkey<C: Verification>(
+    secp: &Secp256k1<C>,
+    a: &PublicKey,
+    b: &PublicKey,
+) -> Result<ScriptBuf, Error> {
+    let context = KeyAggContext::new(secp, &[*a, *b])?;
+    Ok(ScriptBuf::new_v1_p2tr(secp, context.internal_key(), None))
+}
+
+/// Create a partial signature for the input of the given transaction (CET or
+/// refund) spending a taproot fund output.
+pub fn create_funding_input_partial_sig(
+    secp: &Secp256k1<All>,
+    tx: &Transaction,
+    fund_output: &TxOut,
+    sec_nonce: SecretNonce,
+    funding_sk: &SecretKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+) -> Result<PartialSignature, Error> {
+    let msg = util::get_taproot_sig_hash_msg(tx, 0, &[fund_output.clone()])?;
+    partial_sign(
+        secp,
+        sec_nonce,
+        funding_sk,
+        key_agg_context,
+        agg_nonce,
+        &msg,
+    )
+}
+
+/// Verify a partial signature for the input of the given transaction (CET or
+/// refund) spending a taproot fund output.
+pub fn verify_funding_input_partial_sig(
+    secp: &Secp256k1<All>,
+    partial_sig: &PartialSignature,
+    tx: &Transaction,
+    fund_output: &TxOut,
+    pub_nonce: &PublicNonce,
+    pubkey: &PublicKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+) -> Result<(), Error> {
+    let msg = util::get_taproot_sig_hash_msg(tx, 0, &[fund_output.clone()])?;
+    verify_partial_signature(
+        secp,
+        partial_sig,
+        pub_nonce,
+        pubkey,
+        key_agg_context,
+        agg_nonce,
+        &msg,
+    )
+}
+
+/// Create a partial adaptor signature for the input of a CET spending a
+/// taproot fund output, encrypted with the given adaptor point.
+pub fn create_cet_partial_adaptor_sig(
+    secp: &Secp256k1<All>,
+    cet: &Transaction,
+    fund_output: &TxOut,
+    sec_nonce: SecretNonce,
+    funding_sk: &SecretKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: &PublicKey,
+) -> Result<PartialSignature, Error> {
+    let msg = util::get_taproot_sig_hash_msg(cet, 0, &[fund_output.clone()])?;
+    partial_adaptor_sign(
+        secp,
+        sec_nonce,
+        funding_sk,
+        key_agg_context,
+        agg_nonce,
+        adaptor_point,
+        &msg,
+    )
+}
+
+/// Verify a partial adaptor signature for the input of a CET spending a
+/// taproot fund output.
+pub fn verify_cet_partial_adaptor_sig(
+    secp: &Secp256k1<All>,
+    partial_sig: &PartialSignature,
+    cet: &Transaction,
+    fund_output: &TxOut,
+    pub_nonce: &PublicNonce,
+    pubkey: &PublicKey,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: &PublicKey,
+) -> Result<(), Error> {
+    let msg = util::get_taproot_sig_hash_msg(cet, 0, &[fund_output.clone()])?;
+    verify_partial_adaptor_signature(
+        secp,
+        partial_sig,
+        pub_nonce,
+        pubkey,
+        key_agg_context,
+        agg_nonce,
+        adaptor_point,
+        &msg,
+    )
+}
+
+/// Aggregate the partial adaptor signatures of both parties for the input of
+/// a CET spending a taproot fund output into a Schnorr adaptor signature that
+/// can be decrypted using the oracle attestation.
+pub fn aggregate_cet_adaptor_sig(
+    secp: &Secp256k1<All>,
+    cet: &Transaction,
+    fund_output: &TxOut,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    adaptor_point: &PublicKey,
+    partial_sigs: &[PartialSignature],
+) -> Result<SchnorrAdaptorSignature, Error> {
+    let msg = util::get_taproot_sig_hash_msg(cet, 0, &[fund_output.clone()])?;
+    aggregate_partial_adaptor_signatures(
+        secp,
+        key_agg_context,
+        agg_nonce,
+        adaptor_point,
+        &msg,
+        partial_sigs,
+    )
+}
+
+/// Aggregate the partial signatures of both parties for the input of the given
+/// transaction (CET or refund) spending a taproot fund output.
+pub fn aggregate_funding_input_sig(
+    secp: &Secp256k1<All>,
+    tx: &Transaction,
+    fund_output: &TxOut,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    partial_sigs: &[PartialSignature],
+) -> Result<SchnorrSignature, Error> {
+    let msg = util::get_taproot_sig_hash_msg(tx, 0, &[fund_output.clone()])?;
+    aggregate_partial_signatures(secp, key_agg_context, agg_nonce, &msg, partial_sigs)
+}
+
+/// Aggregate the partial signatures of both parties for the input of the given
+/// transaction spending a taproot fund output and place the resulting
+/// signature on the witness stack.
+pub fn finalize_funding_input(
+    secp: &Secp256k1<All>,
+    tx: &mut Transaction,
+    fund_output: &TxOut,
+    key_agg_context: &KeyAggContext,
+    agg_nonce: &AggregateNonce,
+    partial_sigs: &[PartialSignature],
+) -> Result<(), Error> {
+    let sig = aggregate_funding_input_sig(
+        secp,
+        tx,
+        fund_output,
+        key_agg_context,
+        agg_nonce,
+        partial_sigs,
+    )?;
+    tx.input[0].witness = Witness::from_slice(&[sig.as_ref().to_vec()]);
+    Ok(())
+}
+
+/// Verify the given key path signature for the input of the given transaction
+/// spending a taproot fund output and place it on the witness stack.
+pub fn sign_funding_input<C: Verification>(
+    secp: &Secp256k1<C>,
+    tx: &mut Transaction,
+    signature: &SchnorrSignature,
+    fund_output: &TxOut,
+    key_agg_context: &KeyAggContext,
+) -> Result<(), Error> {
+    verify_funding_input_sig(secp, signature, tx, fund_output, key_agg_context)?;
+    tx.input[0].witness = Witness::from_slice(&[signature.as_ref().to_vec()]);
+    Ok(())
+}
+
+/// Verify the key path signature for the input of the given transaction
+/// spending a taproot fund output.
+pub fn verify_funding_input_sig<C: Verification>(
+    secp: &Secp256k1<C>,
+    signature: &SchnorrSignature,
+    tx: &Transaction,
+    fund_output: &TxOut,
+    key_agg_context: &KeyAggContext,
+) -> Result<(), Error> {
+    let msg = util::get_taproot_sig_hash_msg(tx, 0, &[fund_output.clone()])?;
+    secp.verify_schnorr(signature, &msg, &key_agg_context.output_key())?;
+    Ok(())
+}
+
+pub(crate) fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = sha256::Hash::hash(tag.as_bytes());
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag_hash.as_ref());
+    engine.input(tag_hash.as_ref());
+    for d in data {
+        engine.input(d);
+    }
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+pub(crate) fn hash_to_scalar(hash: [u8; 32]) -> Result<Scalar, Error> {
+    // The probability for a hash output to be greater than the curve order is
+    // negligible, so we simply error in that case.
+    Scalar::from_be_bytes(hash).map_err(|_| Error::InvalidArgument)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::hashes::hex::FromHex;
+    use secp256k1_zkp::rand::{thread_rng, Rng};
+    use std::str::FromStr;
+
+    fn get_keys(secp: &Secp256k1<All>) -> (SecretKey, PublicKey, SecretKey, PublicKey) {
+        let mut rng = thread_rng();
+        let sk1 = SecretKey::new(&mut rng);
+        let sk2 = SecretKey::new(&mut rng);
+        (
+            sk1,
+            PublicKey::from_secret_key(secp, &sk1),
+            sk2,
+            PublicKey::from_secret_key(secp, &sk2),
+        )
+    }
+
+    fn sign_message(
+        secp: &Secp256k1<All>,
+        sks: &[SecretKey],
+        context: &KeyAggContext,
+        msg: &Message,
+    ) -> Result<SchnorrSignature, Error> {
+        let mut rng = thread_rng();
+        let (sec_nonces, pub_nonces): (Vec<_>, Vec<_>) = sks
+            .iter()
+            .map(|sk| generate_nonce(secp, rng.gen(), sk, Some(context), Some(msg)).unwrap())
+            .unzip();
+        let agg_nonce = aggregate_nonces(&pub_nonces)?;
+        let partial_sigs = sec_nonces
+            .into_iter()
+            .zip(sks.iter())
+            .map(|(sec_nonce, sk)| partial_sign(secp, sec_nonce, sk, context, &agg_nonce, msg))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for ((partial_sig, pub_nonce), sk) in partial_sigs.iter().zip(pub_nonces.iter()).zip(sks) {
+            verify_partial_signature(
+                secp,
+                partial_sig,
+                pub_nonce,
+                &PublicKey::from_secret_key(secp, sk),
+                context,
+                &agg_nonce,
+                msg,
+            )?;
+        }
+
+        aggregate_partial_signatures(secp, context, &agg_nonce, msg, &partial_sigs)
+    }
+
+    #[test]
+    fn aggregated_adaptor_signature_decrypts_into_valid_signature() {
+        let secp = Secp256k1::new();
+        let mut rng = thread_rng();
+        let (sk1, pk1, sk2, pk2) = get_keys(&secp);
+        let context = get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap();
+        let msg = Message::from_slice(&rng.gen::<[u8; 32]>()).unwrap();
+        let adaptor_secret = SecretKey::new(&mut rng);
+        let adaptor_point = PublicKey::from_secret_key(&secp, &adaptor_secret);
+
+        let (sec_nonce1, pub_nonce1) =
+            generate_nonce(&secp, rng.gen(), &sk1, Some(&context), Some(&msg)).unwrap();
+        let (sec_nonce2, pub_nonce2) =
+            generate_nonce(&secp, rng.gen(), &sk2, Some(&context), Some(&msg)).unwrap();
+        let agg_nonce = aggregate_nonces(&[pub_nonce1, pub_nonce2]).unwrap();
+
+        let partial_sig1 = partial_adaptor_sign(
+            &secp,
+            sec_nonce1,
+            &sk1,
+            &context,
+            &agg_nonce,
+            &adaptor_point,
+            &msg,
+        )
+        .unwrap();
+        let partial_sig2 = partial_adaptor_sign(
+            &secp,
+            sec_nonce2,
+            &sk2,
+            &context,
+            &agg_nonce,
+            &adaptor_point,
+            &msg,
+        )
+        .unwrap();
+        verify_partial_adaptor_signature(
+            &secp,
+            &partial_sig1,
+            &pub_nonce1,
+            &pk1,
+            &context,
+            &agg_nonce,
+            &adaptor_point,
+            &msg,
+        )
+        .expect("partial adaptor signature to be valid");
+
+        let adaptor_sig = aggregate_partial_adaptor_signatures(
+            &secp,
+            &context,
+            &agg_nonce,
+            &adaptor_point,
+            &msg,
+            &[partial_sig1, partial_sig2],
+        )
+        .unwrap();
+        let sig = adaptor_sig.decrypt(&secp, &adaptor_secret).unwrap();
+        secp.verify_schnorr(&sig, &msg, &context.output_key())
+            .expect("decrypted signature to be valid");
+        assert_eq!(
+            adaptor_secret,
+            adaptor_sig.recover(&secp, &sig, &adaptor_point).unwrap()
+        );
+    }
+
+    #[test]
+    fn key_aggregation_does_not_depend_on_order() {
+        let secp = Secp256k1::new();
+        let (_, pk1, _, pk2) = get_keys(&secp);
+
+        let context1 = get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap();
+        let context2 = get_funding_key_agg_context(&secp, &pk2, &pk1).unwrap();
+
+        assert_eq!(context1.output_key(), context2.output_key());
+        assert_eq!(
+            make_taproot_funding_script_pubkey(&secp, &pk1, &pk2).unwrap(),
+            make_taproot_funding_script_pubkey(&secp, &pk2, &pk1).unwrap()
+        );
+    }
+
+    #[test]
+    fn funding_script_pubkey_commits_to_output_key() {
+        let secp = Secp256k1::new();
+        let (_, pk1, _, pk2) = get_keys(&secp);
+
+        let context = get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap();
+        let script_pubkey = make_taproot_funding_script_pubkey(&secp, &pk1, &pk2).unwrap();
+
+        assert!(script_pubkey.is_v1_p2tr());
+        assert_eq!(
+            &script_pubkey.as_bytes()[2..],
+            &context.output_key().serialize()
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected() {
+        let secp = Secp256k1::new();
+        let (_, pk1, _, _) = get_keys(&secp);
+
+        KeyAggContext::new(&secp, &[pk1, pk1]).expect_err("should not aggregate duplicate keys");
+    }
+
+    #[test]
+    fn aggregated_signature_is_valid() {
+        let secp = Secp256k1::new();
+        let (sk1, pk1, sk2, pk2) = get_keys(&secp);
+        let msg = Message::from_slice(&[3u8; 32]).unwrap();
+
+        for context in [
+            KeyAggContext::new(&secp, &[pk1, pk2]).unwrap(),
+            get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap(),
+        ] {
+            let sig = sign_message(&secp, &[sk1, sk2], &context, &msg)
+                .expect("to be able to create a valid signature");
+            secp.verify_schnorr(&sig, &msg, &context.output_key())
+                .expect("signature to be valid for the aggregate key");
+        }
+    }
+
+    #[test]
+    fn deserialized_secret_nonce_produces_same_partial_signature() {
+        let secp = Secp256k1::new();
+        let (sk1, pk1, _, pk2) = get_keys(&secp);
+        let msg = Message::from_slice(&[3u8; 32]).unwrap();
+        let context = get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap();
+        let session_rand = thread_rng().gen();
+
+        let (sec_nonce, pub_nonce) =
+            generate_nonce(&secp, session_rand, &sk1, Some(&context), None).unwrap();
+        let agg_nonce = aggregate_nonces(&[pub_nonce, pub_nonce]).unwrap();
+        let deserialized = SecretNonce::from_slice(&sec_nonce.serialize()).unwrap();
+        let partial_sig = partial_sign(&secp, sec_nonce, &sk1, &context, &agg_nonce, &msg)
+            .expect("to be able to sign");
+        let deserialized_partial_sig =
+            partial_sign(&secp, deserialized, &sk1, &context, &agg_nonce, &msg)
+                .expect("to be able to sign");
+
+        assert_eq!(partial_sig, deserialized_partial_sig);
+        assert!(SecretNonce::from_slice(&[1u8; 32]).is_err());
+    }
+
+    #[test]
+    fn partial_signature_from_wrong_signer_is_invalid() {
+        let secp = Secp256k1::new();
+        let (sk1, pk1, sk2, pk2) = get_keys(&secp);
+        let msg = Message::from_slice(&[3u8; 32]).unwrap();
+        let context = get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap();
+        let mut rng = thread_rng();
+
+        let (sec_nonce1, pub_nonce1) =
+            generate_nonce(&secp, rng.gen(), &sk1, Some(&context), None).unwrap();
+        let (_, pub_nonce2) = generate_nonce(&secp, rng.gen(), &sk2, Some(&context), None).unwrap();
+        let agg_nonce = aggregate_nonces(&[pub_nonce1, pub_nonce2]).unwrap();
+        let partial_sig = partial_sign(&secp, sec_nonce1, &sk1, &context, &agg_nonce, &msg)
+            .expect("to be able to sign");
+
+        verify_partial_signature(
+            &secp,
+            &partial_sig,
+            &pub_nonce1,
+            &pk2,
+            &context,
+            &agg_nonce,
+            &msg,
+        )
+        .expect_err("partial signature should not be valid for another key");
+    }
+
+    #[test]
+    fn partial_sign_with_foreign_key_fails() {
+        let secp = Secp256k1::new();
+        let (sk1, pk1, _, pk2) = get_keys(&secp);
+        let (other_sk, _, _, _) = get_keys(&secp);
+        let msg = Message::from_slice(&[3u8; 32]).unwrap();
+        let context = get_funding_key_agg_context(&secp, &pk1, &pk2).unwrap();
+        let mut rng = thread_rng();
+
+        let (sec_nonce, pub_nonce) =
+            generate_nonce(&secp, rng.gen(), &other_sk, Some(&context), None).unwrap();
+        let (_, pub_nonce1) = generate_nonce(&secp, rng.gen(), &sk1, Some(&context), None).unwrap();
+        let agg_nonce = aggregate_nonces(&[pub_nonce, pub_nonce1]).unwrap();
+
+        partial_sign(&secp, sec_nonce, &other_sk, &context, &agg_nonce, &msg)
+            .expect_err("should not sign with a key that is not part of the aggregate");
+    }
+
+    fn pubkey(s: &str) -> PublicKey {
+        PublicKey::from_str(s).unwrap()
+    }
+
+    fn pub_nonce(s: &str) -> PublicNonce {
+        PublicNonce {
+            r1: pubkey(&s[..66]),
+            r2: pubkey(&s[66..]),
+        }
+    }
+
+    fn sec_nonce(s: &str) -> SecretNonce {
+        SecretNonce {
+            k1: SecretKey::from_str(&s[..64]).unwrap(),
+            k2: SecretKey::from_str(&s[64..128]).unwrap(),
+        }
+    }
+
+    fn key_agg(secp: &Secp256k1<All>, pubkeys: &[PublicKey], indices: &[usize]) -> KeyAggContext {
+        KeyAggContext::key_agg(secp, indices.iter().map(|i| pubkeys[*i]).collect()).unwrap()
+    }
+
+    // Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/key_agg_vectors.json
+    #[test]
+    fn bip327_key_agg_vectors() {
+        let secp = Secp256k1::new();
+        let pubkeys = [
+            pubkey("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            pubkey("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+            pubkey("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"),
+        ];
+        let cases: [(&[usize], &str); 4] = [
+            (
+                &[0, 1, 2],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                &[2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                &[0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                &[0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ];
+
+        for (indices, expected) in cases {
+            assert_eq!(
+                XOnlyPublicKey::from_str(expected).unwrap(),
+                key_agg(&secp, &pubkeys, indices).output_key()
+            );
+        }
+    }
+
+    // Inputs of the test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/nonce_gen_vectors.json
+    #[test]
+    fn bip327_nonce_gen_vectors() {
+        let secp = Secp256k1::new();
+        let sk = SecretKey::from_slice(&[2u8; 32]).unwrap();
+        let pk = pubkey("024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766");
+        let agg_pk = XOnlyPublicKey::from_slice(&[7u8; 32]).unwrap();
+        let extra_in = [8u8; 32];
+        let cases: [(_, _, _, _, Option<&[u8]>, Option<&[u8]>, &str, &str); 4] = [
+            (
+                [0u8; 32],
+                Some(&sk),
+                pk,
+                Some(&agg_pk),
+                Some(&[1u8; 32]),
+                Some(&extra_in),
+                "227243DCB40EF2A13A981DB188FA433717B506BDFA14B1AE47D5DC027C9C3B9EF2370B2AD206E724243215137C86365699361126991E6FEC816845F837BDDAC3",
+                "020A25526B002885996358B3EE5092F2F2F197393E59C06CDFC7A92A91931E20C3024C9FECC6795D5D761F96968D871A1F3BAC605F6ECC4E52E1EBF49E1FF9208AD0",
+            ),
+            (
+                [0u8; 32],
+                Some(&sk),
+                pk,
+                Some(&agg_pk),
+                Some(&[]),
+                Some(&extra_in),
+                "CD0F47FE471D6788FF3243F47345EA0A179AEF69476BE8348322EF39C2723318870C2065AFB52DEDF02BF4FDBF6D2F442E608692F50C2374C08FFFE57042A61C",
+                "0283D01F92F2B6A8540867AD8C7E725E420BBE27D8A949B67F1602219A3218EDE3034EDB05E0FCC6A1AF733DA418D47F863C874ED150B0F92821BF38B9C1835958E5",
+            ),
+            (
+                [0u8; 32],
+                Some(&sk),
+                pk,
+                Some(&agg_pk),
+                Some(&[0x26u8; 38]),
+                Some(&extra_in),
+                "011F8BC60EF061DEEF4D72A0A87200D9994B3F0CD9867910085C38D5366E3E6B9FF03BC0124E56B24069E91EC3F162378983F194E8BD0ED89BE3059649EAE262",
+                "036C9E0851CCC4C93589C870EF67ECAD52CF883FBAFAA27C1D980199B33407D7D3023AFDDECC096613B4A8B3288FC7A2918F5014674E9F8A80A24572D68CA5506AA8",
+            ),
+            (
+                [0x80u8; 32],
+                None,
+                pubkey("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+                None,
+                None,
+                None,
+                "0766ED0C1E2707C77F8BECF6EC629DE96E770F48E21F850B380142F4FB844714432094B3F44816D7445F531557DB243644ABE5CCD808EBD3A1C3601B71D14C9E",
+                "02E4D888B0CFE0451081915853373C64B55C0682EF15EFF9078EB24F5D5CA6F39D03695DCD3C3743AC159B99EC53FF558E2922D5A1DAC58756BF7C2227BD7E869BB8",
+            ),
+        ];
+
+        for (rand, sk, pk, agg_pk, msg, extra_in, expected_sec_nonce, expected_pub_nonce) in cases {
+            let (sec_nonce, pub_nonce) =
+                nonce_gen(&secp, &rand, sk, &pk, agg_pk, msg, extra_in).unwrap();
+            assert_eq!(
+                Vec::<u8>::from_hex(expected_sec_nonce).unwrap(),
+                [sec_nonce.k1.secret_bytes(), sec_nonce.k2.secret_bytes()].concat()
+            );
+            assert_eq!(self::pub_nonce(expected_pub_nonce), pub_nonce);
+        }
+    }
+
+    const SIGN_SK: &str = "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671";
+    const SIGN_SEC_NONCE: &str = "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7";
+    const SIGN_PUB_NONCES: [&str; 3] = [
+        "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+    ];
+    const SIGN_AGG_NONCE: &str = "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9";
+    const SIGN_MSG: &str = "F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF";
+
+    // Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/sign_verify_vectors.json
+    #[test]
+    fn bip327_sign_verify_vectors() {
+        let secp = Secp256k1::new();
+        let sk = SecretKey::from_str(SIGN_SK).unwrap();
+        let pubkeys = [
+            pubkey("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+            pubkey("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            pubkey("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661"),
+        ];
+        let pub_nonces = SIGN_PUB_NONCES.map(pub_nonce);
+        let msg = Message::from_slice(&Vec::<u8>::from_hex(SIGN_MSG).unwrap()).unwrap();
+        assert_eq!(pubkeys[0], PublicKey::from_secret_key(&secp, &sk));
+
+        let cases: [(&[usize], &str); 3] = [
+            (
+                &[0, 1, 2],
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ),
+            (
+                &[1, 0, 2],
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ),
+            (
+                &[1, 2, 0],
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ),
+        ];
+
+        for (indices, expected) in cases {
+            let context = key_agg(&secp, &pubkeys, indices);
+            let agg_nonce =
+                aggregate_nonces(&indices.iter().map(|i| pub_nonces[*i]).collect::<Vec<_>>())
+                    .unwrap();
+            let expected_agg_nonce = pub_nonce(SIGN_AGG_NONCE);
+            assert_eq!(expected_agg_nonce.r1, agg_nonce.r1);
+            assert_eq!(expected_agg_nonce.r2, agg_nonce.r2);
+
+            let partial_sig = partial_sign(
+                &secp,
+                sec_nonce(SIGN_SEC_NONCE),
+                &sk,
+                &context,
+                &agg_nonce,
+                &msg,
+            )
+            .unwrap();
+            assert_eq!(
+                Vec::<u8>::from_hex(expected).unwrap(),
+                partial_sig.serialize()
+            );
+            verify_partial_signature(
+                &secp,
+                &partial_sig,
+                &pub_nonces[0],
+                &pubkeys[0],
+                &context,
+                &agg_nonce,
+                &msg,
+            )
+            .unwrap();
+        }
+
+        // The negation of the first valid signature.
+        let context = key_agg(&secp, &pubkeys, &[0, 1, 2]);
+        let agg_nonce = aggregate_nonces(&pub_nonces).unwrap();
+        let wrong_sig = PartialSignature::from_slice(
+            &Vec::<u8>::from_hex(
+                "FED54434AD4CFE953FC527DC6A5E5BE8F6234907B7C187559557CE87A0541C46",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        verify_partial_signature(
+            &secp,
+            &wrong_sig,
+            &pub_nonces[0],
+            &pubkeys[0],
+            &context,
+            &agg_nonce,
+            &msg,
+        )
+        .expect_err("negated signature should not be valid");
+    }
+
+    // Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/tweak_vectors.json
+    #[test]
+    fn bip327_tweak_vectors() {
+        let secp = Secp256k1::new();
+        let sk = SecretKey::from_str(SIGN_SK).unwrap();
+        let pubkeys = [
+            pubkey("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+            pubkey("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            pubkey("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+        ];
+        let pub_nonces = SIGN_PUB_NONCES.map(pub_nonce);
+        let tweaks = [
+            "E8F791FF9225A2AF0102AFFF4A9A723D9612A682A25EBE79802B263CDFCD83BB",
+            "AE2EA797CC0FE72AC5B97B97F3C6957D7E4199A167A58EB08BCAFFDA70AC0455",
+            "F52ECBC565B3D8BEA2DFD5B75A4F457E54369809322E4120831626F290FA87E0",
+            "1969AD73CC177FA0B4FCED6DF1F7BF9907E665FDE9BA196A74FED0A3CF5AEF9D",
+        ]
+        .map(|x| {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&Vec::<u8>::from_hex(x).unwrap());
+            Scalar::from_be_bytes(bytes).unwrap()
+        });
+        let msg = Message::from_slice(&Vec::<u8>::from_hex(SIGN_MSG).unwrap()).unwrap();
+        let agg_nonce = aggregate_nonces(&[pub_nonces[1], pub_nonces[2], pub_nonces[0]]).unwrap();
+
+        let cases: [(&[bool], &str); 5] = [
+            (
+                &[true],
+                "E28A5C66E61E178C2BA19DB77B6CF9F7E2F0F56C17918CD13135E60CC848FE91",
+            ),
+            (
+                &[false],
+                "38B0767798252F21BF5702C48028B095428320F73A4B14DB1E25DE58543D2D2D",
+            ),
+            (
+                &[false, true],
+                "408A0A21C4A0F5DACAF9646AD6EB6FECD7F7A11F03ED1F48DFFF2185BC2C2408",
+            ),
+            (
+                &[false, false, true, true],
+                "45ABD206E61E3DF2EC9E264A6FEC8292141A633C28586388235541F9ADE75435",
+            ),
+            (
+                &[true, false, true, false],
+                "B255FDCAC27B40C7CE7848E2D3B7BF5EA0ED756DA81565AC804CCCA3E1D5D239",
+            ),
+        ];
+
+        for (is_x_only, expected) in cases {
+            let mut context = key_agg(&secp, &pubkeys, &[1, 2, 0]);
+            for (tweak, is_x_only) in tweaks.iter().zip(is_x_only) {
+                context.apply_tweak(&secp, tweak, *is_x_only).unwrap();
+            }
+
+            let partial_sig = partial_sign(
+                &secp,
+                sec_nonce(SIGN_SEC_NONCE),
+                &sk,
+                &context,
+                &agg_nonce,
+                &msg,
+            )
+            .unwrap();
+            assert_eq!(
+                Vec::<u8>::from_hex(expected).unwrap(),
+                partial_sig.serialize()
+            );
+            verify_partial_signature(
+                &secp,
+                &partial_sig,
+                &pub_nonces[0],
+                &pubkeys[0],
+                &context,
+                &agg_nonce,
+                &msg,
+            )
+            .unwrap();
+        }
+    }
+}
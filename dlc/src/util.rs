@@ -2,11 +2,11 @@
 
 use bitcoin::address::{WitnessProgram, WitnessVersion};
 use bitcoin::script::PushBytesBuf;
-use bitcoin::sighash::SighashCache;
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
 use bitcoin::{
     address::Payload, hash_types::PubkeyHash, sighash::EcdsaSighashType, Script, Transaction, TxOut,
 };
-use bitcoin::{ScriptBuf, Sequence, Witness};
+use bitcoin::{opcodes, ScriptBuf, Sequence, Witness};
 use secp256k1_zkp::{ecdsa::Signature, Message, PublicKey, Secp256k1, SecretKey, Signing};
 
 use crate::Error;
@@ -17,6 +17,10 @@ pub(crate) const DISABLE_LOCKTIME: Sequence = Sequence(0xffffffff);
 // Setting the nSequence for every input of a transaction to this value disables
 // RBF but enables nLockTime usage.
 pub(crate) const ENABLE_LOCKTIME: Sequence = Sequence(0xfffffffe);
+// Setting the nSequence for every input of a transaction to this value signals
+// that the transaction can be replaced by one paying a higher fee (BIP 125), and
+// enables nLockTime usage.
+pub(crate) const ENABLE_RBF: Sequence = Sequence(0xfffffffd);
 
 /// Get a BIP143 (https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki)
 /// signature hash with sighash all flag for a segwit transaction input as
@@ -36,6 +40,22 @@ pub(crate) fn get_sig_hash_msg(
     Ok(Message::from_slice(sig_hash.as_ref()).unwrap())
 }
 
+/// Get a BIP341 (https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki)
+/// signature hash with default sighash flag for a taproot key path spend of a
+/// transaction input as a Message instance
+pub(crate) fn get_taproot_sig_hash_msg(
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &[TxOut],
+) -> Result<Message, Error> {
+    let sig_hash = SighashCache::new(tx).taproot_key_spend_signature_hash(
+        input_index,
+        &Prevouts::All(prevouts),
+        TapSighashType::Default,
+    )?;
+    Ok(Message::from_slice(sig_hash.as_ref()).unwrap())
+}
+
 /// Convert a raw signature to DER encoded and append the sighash type, to use
 /// a signature in a signature script
 pub(crate) fn finalize_sig(sig: &Signature, sig_hash_type: EcdsaSighashType) -> Vec<u8> {
@@ -200,7 +220,7 @@ pub fn sign_multi_sig_input<C: Signing>(
 }
 
 /// Transforms a redeem script for a p2sh-p2w* output to a script signature.
-pub(crate) fn redeem_script_to_script_sig(redeem: &Script) -> ScriptBuf {
+pub fn redeem_script_to_script_sig(redeem: &Script) -> ScriptBuf {
     match redeem.len() {
         0 => ScriptBuf::new(),
         _ => {
@@ -211,6 +231,78 @@ pub(crate) fn redeem_script_to_script_sig(redeem: &Script) -> ScriptBuf {
     }
 }
 
+/// Returns the weight of an input spending an output with the given redeem
+/// script, which is empty for non P2SH wrapped outputs, and whose witness has
+/// at most `max_witness_len` bytes.
+pub fn get_input_weight(redeem_script: &Script, max_witness_len: usize) -> Result<usize, Error> {
+    let script_weight = redeem_script_to_script_sig(redeem_script)
+        .len()
+        .checked_mul(4)
+        .ok_or(Error::InvalidArgument)?;
+    crate::TX_INPUT_BASE_WEIGHT
+        .checked_add(script_weight)
+        .and_then(|weight| weight.checked_add(max_witness_len))
+        .ok_or(Error::InvalidArgument)
+}
+
+/// Returns the maximum size of the witness required to spend an output with
+/// the given script pubkey. The redeem script must be provided for P2SH wrapped
+/// segwit outputs, and the witness script for P2WSH outputs, for which only
+/// multisig scripts are supported. Returns `None` if the size cannot be
+/// determined.
+pub fn get_max_witness_len(
+    script_pubkey: &Script,
+    redeem_script: &Script,
+    witness_script: Option<&Script>,
+) -> Option<usize> {
+    let script_pubkey = if script_pubkey.is_p2sh() {
+        redeem_script
+    } else {
+        script_pubkey
+    };
+
+    if script_pubkey.is_v0_p2wpkh() {
+        Some(crate::P2WPKH_WITNESS_SIZE)
+    } else if script_pubkey.is_v1_p2tr() {
+        Some(crate::P2TR_KEY_PATH_WITNESS_SIZE)
+    } else if script_pubkey.is_v0_p2wsh() {
+        witness_script.and_then(get_multisig_witness_len)
+    } else {
+        None
+    }
+}
+
+/// Returns the maximum size of the witness required to spend a P2WSH output
+/// locked by the given multisig witness script, or `None` if the script is not
+/// a multisig script.
+fn get_multisig_witness_len(witness_script: &Script) -> Option<usize> {
+    let bytes = witness_script.as_bytes();
+    let len = bytes.len();
+    if len < 3 || bytes[len - 1] != opcodes::all::OP_CHECKMULTISIG.to_u8() {
+        return None;
+    }
+
+    let get_pushnum = |b: u8| {
+        let first = opcodes::all::OP_PUSHNUM_1.to_u8();
+        let last = opcodes::all::OP_PUSHNUM_16.to_u8();
+        if (first..=last).contains(&b) {
+            Some((b - first + 1) as usize)
+        } else {
+            None
+        }
+    };
+    let nb_sigs = get_pushnum(bytes[0])?;
+    let nb_keys = get_pushnum(bytes[len - 2])?;
+    if nb_sigs > nb_keys {
+        return None;
+    }
+
+    let script_len_size = if len < 0xfd { 1 } else { 3 };
+    // witness_items_count(1) + empty_element(1) + nb_sigs * (signature_length(1) + signature(71))
+    // + script_length + script
+    Some(2 + nb_sigs * 72 + script_len_size + len)
+}
+
 /// Sorts the given inputs in following the order of the ids.
 pub(crate) fn order_by_serial_ids<T>(inputs: Vec<T>, ids: &[u64]) -> Vec<T> {
     debug_assert!(inputs.len() == ids.len());
@@ -9,7 +9,7 @@ use bitcoin::{
     block::Header, Block, BlockHash, Network, OutPoint, ScriptBuf, Transaction, TxOut, Txid,
 };
 use bitcoin_test_utils::tx_to_string;
-use dlc_manager::{error::Error, Blockchain, Utxo};
+use dlc_manager::{error::Error, AsyncBlockchain, Blockchain, Utxo};
 use lightning::chain::chaininterface::{BroadcasterInterface, ConfirmationTarget, FeeEstimator};
 use lightning_block_sync::{BlockData, BlockHeaderData, BlockSource, BlockSourceError};
 use reqwest::blocking::Response;
@@ -105,6 +105,45 @@ impl ElectrsBlockchainProvider {
     pub fn get_outspends(&self, txid: &Txid) -> Result<Vec<OutSpendResp>, Error> {
         self.get_from_json(&format!("tx/{txid}/outspends"))
     }
+
+    async fn get_text_async(&self, sub_url: &str) -> Result<String, Error> {
+        self.get_async(sub_url)
+            .await
+            .map_err(to_io_error)?
+            .text()
+            .await
+            .map_err(to_io_error)
+    }
+
+    async fn get_u64_async(&self, sub_url: &str) -> Result<u64, Error> {
+        self.get_text_async(sub_url)
+            .await?
+            .parse()
+            .map_err(|e: std::num::ParseIntError| Error::BlockchainError(e.to_string()))
+    }
+
+    async fn get_bytes_async(&self, sub_url: &str) -> Result<Vec<u8>, Error> {
+        let bytes = self
+            .get_async(sub_url)
+            .await
+            .map_err(to_io_error)?
+            .bytes()
+            .await
+            .map_err(|e| Error::BlockchainError(e.to_string()))?;
+        Ok(bytes.into_iter().collect::<Vec<_>>())
+    }
+
+    async fn get_from_json_async<T>(&self, sub_url: &str) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.get_async(sub_url)
+            .await
+            .map_err(to_io_error)?
+            .json::<T>()
+            .await
+            .map_err(|e| Error::BlockchainError(e.to_string()))
+    }
 }
 
 impl Blockchain for ElectrsBlockchainProvider {
@@ -146,6 +185,14 @@ impl Blockchain for ElectrsBlockchainProvider {
             .map_err(|e| Error::BlockchainError(e.to_string()))
     }
 
+    fn get_block_hash_at_height(
+        &self,
+        height: u64,
+    ) -> Result<BlockHash, dlc_manager::error::Error> {
+        let hash_at_height = self.get_text(&format!("block-height/{height}"))?;
+        BlockHash::from_str(&hash_at_height).map_err(|e| Error::BlockchainError(e.to_string()))
+    }
+
     fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, dlc_manager::error::Error> {
         let raw_tx = self.get_bytes(&format!("tx/{tx_id}/raw"))?;
         Transaction::consensus_decode(&mut std::io::Cursor::new(&*raw_tx))
@@ -158,7 +205,73 @@ impl Blockchain for ElectrsBlockchainProvider {
     ) -> Result<u32, dlc_manager::error::Error> {
         let tx_status = self.get_from_json::<TxStatus>(&format!("tx/{tx_id}/status"))?;
         if tx_status.confirmed {
-            let block_chain_height = self.get_blockchain_height()?;
+            let block_chain_height = Blockchain::get_blockchain_height(self)?;
+            if let Some(block_height) = tx_status.block_height {
+                return Ok((block_chain_height - block_height + 1) as u32);
+            }
+        }
+
+        Ok(0)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncBlockchain for ElectrsBlockchainProvider {
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<(), Error> {
+        let res = self
+            .async_client
+            .post(format!("{}tx", self.host))
+            .body(tx_to_string(transaction))
+            .send()
+            .await
+            .map_err(to_io_error)?;
+        if let Err(error) = res.error_for_status_ref() {
+            let body = res.text().await.unwrap_or_default();
+            return Err(Error::InvalidParameters(format!(
+                "Server returned error: {error} {body}"
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get_network(&self) -> Result<Network, Error> {
+        Ok(self.network)
+    }
+
+    async fn get_blockchain_height(&self) -> Result<u64, Error> {
+        self.get_u64_async("blocks/tip/height").await
+    }
+
+    async fn get_block_at_height(&self, height: u64) -> Result<Block, Error> {
+        let hash_at_height = self
+            .get_text_async(&format!("block-height/{height}"))
+            .await?;
+        let raw_block = self
+            .get_bytes_async(&format!("block/{hash_at_height}/raw"))
+            .await?;
+        Block::consensus_decode(&mut std::io::Cursor::new(&*raw_block))
+            .map_err(|e| Error::BlockchainError(e.to_string()))
+    }
+
+    async fn get_block_hash_at_height(&self, height: u64) -> Result<BlockHash, Error> {
+        let hash_at_height = self
+            .get_text_async(&format!("block-height/{height}"))
+            .await?;
+        BlockHash::from_str(&hash_at_height).map_err(|e| Error::BlockchainError(e.to_string()))
+    }
+
+    async fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, Error> {
+        let raw_tx = self.get_bytes_async(&format!("tx/{tx_id}/raw")).await?;
+        Transaction::consensus_decode(&mut std::io::Cursor::new(&*raw_tx))
+            .map_err(|e| Error::BlockchainError(e.to_string()))
+    }
+
+    async fn get_transaction_confirmations(&self, tx_id: &Txid) -> Result<u32, Error> {
+        let tx_status = self
+            .get_from_json_async::<TxStatus>(&format!("tx/{tx_id}/status"))
+            .await?;
+        if tx_status.confirmed {
+            let block_chain_height = self.get_u64_async("blocks/tip/height").await?;
             if let Some(block_height) = tx_status.block_height {
                 return Ok((block_chain_height - block_height + 1) as u32);
             }
@@ -171,6 +284,11 @@ impl Blockchain for ElectrsBlockchainProvider {
 impl simple_wallet::WalletBlockchainProvider for ElectrsBlockchainProvider {
     fn get_utxos_for_address(&self, address: &bitcoin::Address) -> Result<Vec<Utxo>, Error> {
         let utxos: Vec<UtxoResp> = self.get_from_json(&format!("address/{address}/utxo"))?;
+        let script_pubkey = address.script_pubkey();
+        let max_witness_len =
+            dlc::util::get_max_witness_len(&script_pubkey, &ScriptBuf::new(), None).ok_or_else(
+                || Error::BlockchainError(format!("Unsupported address type for {address}")),
+            )?;
 
         utxos
             .into_iter()
@@ -187,10 +305,11 @@ impl simple_wallet::WalletBlockchainProvider for ElectrsBlockchainProvider {
                         vout: x.vout,
                     },
                     redeem_script: ScriptBuf::default(),
+                    max_witness_len,
                     reserved: false,
                     tx_out: TxOut {
                         value: x.value,
-                        script_pubkey: address.script_pubkey(),
+                        script_pubkey: script_pubkey.clone(),
                     },
                 })
             })
@@ -379,6 +498,13 @@ struct SpentResp {
 
 type FeeEstimates = std::collections::HashMap<u16, f32>;
 
+fn to_io_error(e: reqwest::Error) -> Error {
+    Error::IOError(lightning::io::Error::new(
+        lightning::io::ErrorKind::Other,
+        e,
+    ))
+}
+
 fn store_estimate_for_target(
     fees: &Arc<HashMap<Target, AtomicU32>>,
     fee_estimates: &FeeEstimates,
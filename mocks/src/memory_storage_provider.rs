@@ -8,6 +8,7 @@ use dlc_manager::channel::{
 use dlc_manager::contract::{
     offered_contract::OfferedContract, signed_contract::SignedContract, Contract, PreClosedContract,
 };
+use dlc_manager::events::Event;
 use dlc_manager::Storage;
 use dlc_manager::{error::Error as DaemonError, ChannelId, ContractId, Utxo};
 use secp256k1_zkp::SecretKey;
@@ -23,6 +24,7 @@ pub struct MemoryStorage {
     addresses: RwLock<HashMap<Address, SecretKey>>,
     utxos: RwLock<HashMap<OutPoint, Utxo>>,
     key_pairs: RwLock<HashMap<Vec<u8>, SecretKey>>,
+    events: RwLock<Vec<Event>>,
 }
 
 impl MemoryStorage {
@@ -35,6 +37,7 @@ impl MemoryStorage {
             addresses: RwLock::new(HashMap::new()),
             utxos: RwLock::new(HashMap::new()),
             key_pairs: RwLock::new(HashMap::new()),
+            events: RwLock::new(Vec::new()),
         }
     }
 
@@ -176,6 +179,50 @@ impl Storage for MemoryStorage {
         }
         Ok(res)
     }
+
+    fn get_prerefunded_contracts(&self) -> Result<Vec<SignedContract>, DaemonError> {
+        let map = self.contracts.read().expect("Could not get read lock");
+
+        let mut res: Vec<SignedContract> = Vec::new();
+
+        for (_, val) in map.iter() {
+            if let Contract::PreRefunded(c) = val {
+                res.push(c.clone());
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn get_rolled_over_contracts(&self) -> Result<Vec<SignedContract>, DaemonError> {
+        let map = self.contracts.read().expect("Could not get read lock");
+
+        let mut res: Vec<SignedContract> = Vec::new();
+
+        for (_, val) in map.iter() {
+            if let Contract::RolledOver(c) = val {
+                res.push(c.clone());
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn roll_over_contract(
+        &self,
+        rolled_over_contract: &SignedContract,
+        contract: &Contract,
+    ) -> Result<(), DaemonError> {
+        let mut map = self.contracts.write().expect("Could not get write lock");
+        map.insert(
+            rolled_over_contract.accepted_contract.get_contract_id(),
+            Contract::RolledOver(rolled_over_contract.clone()),
+        );
+        map.remove(&contract.get_temporary_id());
+        map.insert(contract.get_id(), contract.clone());
+        Ok(())
+    }
+
     fn upsert_channel(
         &self,
         channel: Channel,
@@ -254,6 +301,15 @@ impl Storage for MemoryStorage {
     fn get_chain_monitor(&self) -> Result<Option<ChainMonitor>, DaemonError> {
         Ok(None)
     }
+
+    fn persist_events(&self, events: &[Event]) -> Result<(), DaemonError> {
+        *self.events.write().expect("Could not get write lock") = events.to_vec();
+        Ok(())
+    }
+
+    fn get_events(&self) -> Result<Vec<Event>, DaemonError> {
+        Ok(self.events.read().expect("Could not get read lock").clone())
+    }
 }
 
 impl WalletStorage for MemoryStorage {
@@ -1,20 +1,91 @@
 use std::sync::Mutex;
 
-use bitcoin::{Block, Transaction, Txid};
+use bitcoin::block::{Header, Version};
+use bitcoin::hash_types::TxMerkleNode;
+use bitcoin::hashes::Hash;
+use bitcoin::{Block, BlockHash, CompactTarget, Transaction, Txid};
 use dlc_manager::{error::Error, Blockchain, Utxo};
 use lightning::chain::chaininterface::FeeEstimator;
 use simple_wallet::WalletBlockchainProvider;
 
+const INITIAL_HEIGHT: u64 = 10;
+
 pub struct MockBlockchain {
     transactions: Mutex<Vec<Transaction>>,
+    /// Blocks mined on top of the initial height when the blockchain was
+    /// created using [`MockBlockchain::new_with_chain`].
+    blocks: Mutex<Option<Vec<Block>>>,
+    nb_mined_blocks: Mutex<u32>,
 }
 
 impl MockBlockchain {
     pub fn new() -> Self {
         Self {
             transactions: Mutex::new(Vec::new()),
+            blocks: Mutex::new(None),
+            nb_mined_blocks: Mutex::new(0),
+        }
+    }
+
+    /// Creates a blockchain keeping track of mined blocks, which can be
+    /// disconnected to simulate chain reorganizations. Transactions are only
+    /// considered confirmed once included in a block.
+    pub fn new_with_chain() -> Self {
+        Self {
+            transactions: Mutex::new(Vec::new()),
+            blocks: Mutex::new(Some(Vec::new())),
+            nb_mined_blocks: Mutex::new(0),
         }
     }
+
+    /// Mines a block including all the sent transactions that are not yet
+    /// part of the chain, and returns its hash.
+    pub fn mine_block(&self) -> BlockHash {
+        let mut blocks = self.blocks.lock().unwrap();
+        let blocks = blocks.as_mut().expect("to have a chain");
+        let txdata = self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|tx| {
+                !blocks
+                    .iter()
+                    .any(|b| b.txdata.iter().any(|x| x.txid() == tx.txid()))
+            })
+            .cloned()
+            .collect();
+        let mut nb_mined_blocks = self.nb_mined_blocks.lock().unwrap();
+        *nb_mined_blocks += 1;
+        let block = Block {
+            header: Header {
+                version: Version::ONE,
+                prev_blockhash: blocks
+                    .last()
+                    .map(|b| b.block_hash())
+                    .unwrap_or_else(BlockHash::all_zeros),
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: 0,
+                bits: CompactTarget::from_consensus(0x207fffff),
+                // Makes blocks mined at the same height on different forks differ.
+                nonce: *nb_mined_blocks,
+            },
+            txdata,
+        };
+        let hash = block.block_hash();
+        blocks.push(block);
+        hash
+    }
+
+    /// Disconnects the given number of blocks from the tip of the chain. The
+    /// transactions they included are not confirmed anymore but can be mined
+    /// again.
+    pub fn disconnect_blocks(&self, nb_blocks: usize) {
+        let mut blocks = self.blocks.lock().unwrap();
+        let blocks = blocks.as_mut().expect("to have a chain");
+        let new_len = blocks.len() - nb_blocks;
+        blocks.truncate(new_len);
+    }
 }
 
 impl Default for MockBlockchain {
@@ -32,10 +103,24 @@ impl Blockchain for MockBlockchain {
         Ok(bitcoin::Network::Regtest)
     }
     fn get_blockchain_height(&self) -> Result<u64, Error> {
-        Ok(10)
+        Ok(INITIAL_HEIGHT
+            + self
+                .blocks
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|x| x.len() as u64)
+                .unwrap_or(0))
     }
-    fn get_block_at_height(&self, _height: u64) -> Result<Block, Error> {
-        unimplemented!();
+    fn get_block_at_height(&self, height: u64) -> Result<Block, Error> {
+        let blocks = self.blocks.lock().unwrap();
+        let blocks = blocks.as_ref().expect("to have a chain");
+        if height <= INITIAL_HEIGHT || height > INITIAL_HEIGHT + blocks.len() as u64 {
+            return Err(Error::BlockchainError(format!(
+                "No block at height {height}"
+            )));
+        }
+        Ok(blocks[(height - INITIAL_HEIGHT - 1) as usize].clone())
     }
     fn get_transaction(&self, tx_id: &Txid) -> Result<Transaction, Error> {
         Ok(self
@@ -47,8 +132,15 @@ impl Blockchain for MockBlockchain {
             .unwrap()
             .clone())
     }
-    fn get_transaction_confirmations(&self, _tx_id: &Txid) -> Result<u32, Error> {
-        Ok(6)
+    fn get_transaction_confirmations(&self, tx_id: &Txid) -> Result<u32, Error> {
+        match self.blocks.lock().unwrap().as_ref() {
+            Some(blocks) => Ok(blocks
+                .iter()
+                .position(|b| b.txdata.iter().any(|x| &x.txid() == tx_id))
+                .map(|i| (blocks.len() - i) as u32)
+                .unwrap_or(0)),
+            None => Ok(6),
+        }
     }
 }
 
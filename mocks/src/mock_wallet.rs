@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use bitcoin::psbt::PartiallySignedTransaction;
 use bitcoin::{absolute::LockTime, Address, OutPoint, ScriptBuf, Transaction, TxOut};
 use dlc_manager::{error::Error, Blockchain, ContractSignerProvider, SimpleSigner, Utxo, Wallet};
@@ -12,7 +10,7 @@ pub struct MockWallet {
 }
 
 impl MockWallet {
-    pub fn new(blockchain: &Rc<MockBlockchain>, utxo_values: &[u64]) -> Self {
+    pub fn new(blockchain: &MockBlockchain, utxo_values: &[u64]) -> Self {
         let mut utxos = Vec::with_capacity(utxo_values.len());
 
         for utxo_value in utxo_values {
@@ -35,6 +33,7 @@ impl MockWallet {
                 },
                 address: get_address(),
                 redeem_script: ScriptBuf::default(),
+                max_witness_len: dlc::P2WPKH_WITNESS_SIZE,
                 reserved: false,
             };
 
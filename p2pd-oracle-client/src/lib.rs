@@ -11,6 +11,7 @@
 #![deny(unused_imports)]
 #![deny(missing_docs)]
 
+extern crate async_trait;
 extern crate chrono;
 extern crate dlc_manager;
 extern crate dlc_messages;
@@ -81,6 +82,20 @@ where
         .map_err(|e| dlc_manager::error::Error::OracleError(e.to_string()))
 }
 
+async fn get_async<T>(path: &str) -> Result<T, DlcManagerError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    reqwest::get(path)
+        .await
+        .map_err(|x| {
+            dlc_manager::error::Error::IOError(std::io::Error::new(std::io::ErrorKind::Other, x))
+        })?
+        .json::<T>()
+        .await
+        .map_err(|e| dlc_manager::error::Error::OracleError(e.to_string()))
+}
+
 fn pubkey_path(host: &str) -> String {
     format!("{}{}", host, "oracle/publickey")
 }
@@ -174,6 +189,38 @@ impl Oracle for P2PDOracleClient {
     }
 }
 
+#[async_trait::async_trait]
+impl dlc_manager::AsyncOracle for P2PDOracleClient {
+    fn get_public_key(&self) -> XOnlyPublicKey {
+        self.public_key
+    }
+
+    async fn get_announcement(
+        &self,
+        event_id: &str,
+    ) -> Result<OracleAnnouncement, DlcManagerError> {
+        let (asset_id, date_time) = parse_event_id(event_id)?;
+        let path = announcement_path(&self.host, &asset_id, &date_time);
+        get_async(&path).await
+    }
+
+    async fn get_attestation(&self, event_id: &str) -> Result<OracleAttestation, DlcManagerError> {
+        let (asset_id, date_time) = parse_event_id(event_id)?;
+        let path = attestation_path(&self.host, &asset_id, &date_time);
+        let AttestationResponse {
+            event_id: _,
+            signatures,
+            values,
+        } = get_async::<AttestationResponse>(&path).await?;
+
+        Ok(OracleAttestation {
+            oracle_public_key: self.public_key,
+            signatures,
+            outcomes: values,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate mockito;
@@ -9,6 +9,7 @@ use dlc_manager::channel::signed_channel::SignedChannelStateType;
 use dlc_manager::contract::contract_input::ContractInput;
 use dlc_manager::contract::Contract;
 use dlc_manager::Storage;
+use dlc_messages::reject::RejectErrorCode;
 use dlc_messages::Message as DlcMessage;
 use hex_utils::{hex_str, to_slice};
 use serde::Deserialize;
@@ -126,6 +127,16 @@ pub(crate) async fn poll_for_user_input(
                         .is_ok()
                     {
                         println!("SUCCESS: connected to peer {}", pubkey);
+                        let msgs = dlc_manager
+                            .lock()
+                            .unwrap()
+                            .get_channel_reestablish_messages(&pubkey)
+                            .expect("Error getting channel reestablish messages.");
+                        for msg in msgs {
+                            dlc_message_handler
+                                .send_message(pubkey, DlcMessage::ChannelReestablish(msg));
+                        }
+                        peer_manager.process_events();
                     }
                 }
                 "listpeers" => list_peers(peer_manager.clone()),
@@ -219,6 +230,29 @@ pub(crate) async fn poll_for_user_input(
                     dlc_message_handler.send_message(node_id, DlcMessage::Accept(msg));
                     peer_manager.process_events();
                 }
+                r @ "rejectoffer" => {
+                    let contract_id = read_id_or_continue!(words, r, "contract id");
+                    let reason = words.collect::<Vec<_>>().join(" ");
+
+                    let (msg, node_id) = dlc_manager
+                        .lock()
+                        .unwrap()
+                        .reject_contract_offer(&contract_id, RejectErrorCode::Other, reason)
+                        .expect("Error rejecting contract offer.");
+                    dlc_message_handler.send_message(node_id, DlcMessage::RejectOffer(msg));
+                    peer_manager.process_events();
+                }
+                c @ "canceloffer" => {
+                    let contract_id = read_id_or_continue!(words, c, "contract id");
+
+                    let (msg, node_id) = dlc_manager
+                        .lock()
+                        .unwrap()
+                        .cancel_offer(&contract_id)
+                        .expect("Error cancelling contract offer.");
+                    dlc_message_handler.send_message(node_id, DlcMessage::CancelOffer(msg));
+                    peer_manager.process_events();
+                }
                 "listcontracts" => {
                     let manager_clone = dlc_manager.clone();
                     // Because the oracle client is currently blocking we need to use `spawn_blocking` here.
@@ -262,6 +296,9 @@ pub(crate) async fn poll_for_user_input(
                                     }
                                     println!("PnL: {} sats", closed.pnl)
                                 }
+                                Contract::PreRefunded(_) => {
+                                    println!("Pre-refunded contract: {}", id);
+                                }
                                 Contract::Refunded(_) => {
                                     println!("Refunded contract: {}", id);
                                 }
@@ -269,7 +306,15 @@ pub(crate) async fn poll_for_user_input(
                                     println!("Failed contract: {}", id);
                                 }
                                 Contract::Rejected(_) => println!("Rejected contract: {}", id),
+                                Contract::Cancelled(_) => println!("Cancelled contract: {}", id),
+                                Contract::RolledOver(_) => {
+                                    println!("Rolled over contract: {}", id)
+                                }
                                 Contract::PreClosed(_) => println!("Pre-closed contract: {}", id),
+                                Contract::CollaborativelyClosed(closed) => {
+                                    println!("Collaboratively closed contract: {}", id);
+                                    println!("PnL: {} sats", closed.pnl)
+                                }
                             }
                         }
                     })
@@ -368,6 +413,72 @@ pub(crate) async fn poll_for_user_input(
                         );
                     }
                 }
+                s @ "splicechannel" => {
+                    let channel_id = read_id_or_continue!(words, s, "channel id");
+                    let (offer_balance, fee_rate): (u64, u64) = match (
+                        words.next().map(|w| w.parse().ok()),
+                        words.next().map(|w| w.parse().ok()),
+                    ) {
+                        (Some(Some(b)), Some(Some(f))) => (b, f),
+                        _ => {
+                            println!("Missing or invalid balance or fee rate parameter");
+                            continue;
+                        }
+                    };
+                    let manager_clone = dlc_manager.clone();
+                    let (msg, node_id) = tokio::task::spawn_blocking(move || {
+                        manager_clone
+                            .lock()
+                            .unwrap()
+                            .splice_channel(&channel_id, offer_balance, fee_rate)
+                            .expect("Error getting splice offer message.")
+                    })
+                    .await
+                    .unwrap();
+                    dlc_message_handler.send_message(node_id, DlcMessage::SpliceOffer(msg));
+                    peer_manager.process_events();
+                }
+                l @ "acceptsplicechanneloffer" => {
+                    let channel_id = read_id_or_continue!(words, l, "channel id");
+                    let (msg, node_id) = dlc_manager
+                        .lock()
+                        .unwrap()
+                        .accept_splice_offer(&channel_id)
+                        .expect("Error accepting splice channel offer.");
+                    dlc_message_handler.send_message(node_id, DlcMessage::SpliceAccept(msg));
+                    peer_manager.process_events();
+                }
+                l @ "rejectsplicechanneloffer" => {
+                    let channel_id = read_id_or_continue!(words, l, "channel id");
+                    let (msg, node_id) = dlc_manager
+                        .lock()
+                        .unwrap()
+                        .reject_splice_offer(&channel_id)
+                        .expect("Error rejecting splice channel offer.");
+                    dlc_message_handler.send_message(node_id, DlcMessage::Reject(msg));
+                    peer_manager.process_events();
+                }
+                "listsplicechanneloffers" => {
+                    let locked_manager = dlc_manager.lock().unwrap();
+                    for channel in locked_manager
+                        .get_store()
+                        .get_signed_channels(Some(SignedChannelStateType::SpliceReceived))
+                        .unwrap()
+                        .iter()
+                    {
+                        let channel_id = hex_str(&channel.channel_id);
+                        let counter_payout = match channel.state {
+                            SignedChannelState::SpliceReceived { counter_payout, .. } => {
+                                counter_payout
+                            }
+                            _ => continue,
+                        };
+                        println!(
+                            "Splice offer channel {:?} from {} with counter balance: {}",
+                            channel_id, channel.counter_party, counter_payout
+                        );
+                    }
+                }
                 o @ "offerchannelrenew" => {
                     let channel_id = read_id_or_continue!(words, o, "channel id");
                     let (counter_payout, contract_path) =
@@ -490,6 +601,8 @@ fn help() {
     println!("offercontract <pubkey@host:port> <path_to_contract_input_json>");
     println!("listoffers");
     println!("acceptoffer <contract_id>");
+    println!("rejectoffer <contract_id> [reason]");
+    println!("canceloffer <contract_id>");
     println!("listcontracts");
     println!("offerchannel <pubkey@host:port> <path_to_contract_input_json>");
     println!("listchanneloffers");
@@ -498,6 +611,10 @@ fn help() {
     println!("listsettlechanneloffers");
     println!("acceptsettlechanneloffer <channel_id>");
     println!("rejectsettlechanneloffer <channel_id>");
+    println!("splicechannel <channel_id> <own_balance> <fee_rate_per_vb>");
+    println!("listsplicechanneloffers");
+    println!("acceptsplicechanneloffer <channel_id>");
+    println!("rejectsplicechanneloffer <channel_id>");
     println!("offerrenewchannel <channel_id> <path_to_contract_input_json>");
     println!("listrenewchanneloffers");
     println!("acceptrenewchannel <channel_id>");
@@ -93,6 +93,7 @@ async fn main() {
             oracles,
             Arc::new(dlc_manager::SystemTimeProvider {}),
             bitcoind_provider.clone(),
+            dlc_manager::manager::ManagerConfig::default(),
         )
         .expect("Could not create manager."),
     ));
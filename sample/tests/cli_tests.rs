@@ -15,7 +15,7 @@ fn sample_cli_test() {
         .unwrap()
         + Duration::new(300, 0))
     .as_secs();
-    contract.contract_infos[0].oracles.event_id = format!("btcusd{}", unix_time);
+    contract.contract_infos[0].oracles.event_ids = vec![format!("btcusd{}", unix_time)];
 
     let alice_config_str = include_str!("../examples/configurations/alice.yml");
     let bob_config_str = include_str!("../examples/configurations/bob.yml");
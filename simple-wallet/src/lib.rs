@@ -7,7 +7,7 @@ use bdk::{
 };
 use bitcoin::{
     hashes::Hash, Address, Network, OutPoint, Script, Sequence, Transaction, TxIn, TxOut, Txid,
-    Witness,
+    VarInt, Witness,
 };
 use bitcoin::{psbt::PartiallySignedTransaction, ScriptBuf};
 use dlc_manager::{
@@ -249,14 +249,20 @@ where
         let utxos = org_utxos
             .iter()
             .filter(|x| !x.reserved)
-            .map(|x| WeightedUtxo {
-                utxo: BdkUtxo::Local(LocalUtxo {
-                    outpoint: x.outpoint,
-                    txout: x.tx_out.clone(),
-                    keychain: KeychainKind::External,
-                    is_spent: false,
-                }),
-                satisfaction_weight: 107,
+            .map(|x| {
+                let script_sig_len = dlc::util::redeem_script_to_script_sig(&x.redeem_script).len();
+                WeightedUtxo {
+                    utxo: BdkUtxo::Local(LocalUtxo {
+                        outpoint: x.outpoint,
+                        txout: x.tx_out.clone(),
+                        keychain: KeychainKind::External,
+                        is_spent: false,
+                    }),
+                    // The base weight of an input used by BDK does not include the
+                    // length prefix of its script sig.
+                    satisfaction_weight: x.max_witness_len
+                        + (VarInt(script_sig_len as u64).len() + script_sig_len) * 4,
+                }
             })
             .collect::<Vec<_>>();
         let coin_selection = BranchAndBoundCoinSelection::default();